@@ -4,32 +4,448 @@ use serde::de::DeserializeOwned;
 use serde_json::json;
 use tokio::sync::mpsc;
 
+use crate::api::retry::{RetryNotice, send_with_retry};
+use crate::api::settings::{ApiSettings, ChatContext, Provider};
+use crate::api::tools::{MAX_TOOL_ROUNDS, ToolCall, tool_definitions};
 use crate::api::types::{
-    ApiError, RECHUNK_STEP_TOOL, RechunkResponse, TokenUsage, WalkthroughStepResponse,
-    chat_system_prompt, rechunk_system_prompt, walkthrough_system_prompt,
+    ApiError, FOLLOW_UPS_SYSTEM_PROMPT, FOLLOW_UPS_TOOL, FollowUpResponse, HistorySummaryResponse,
+    PARTITION_DIFF_TOOL, PARTITION_SYSTEM_PROMPT, PartitionResponse, RECHUNK_STEP_TOOL,
+    RechunkResponse, SUMMARIZE_HISTORY_SYSTEM_PROMPT, SUMMARIZE_HISTORY_TOOL, TokenUsage,
+    WalkthroughStepResponse, chat_system_prompt, rechunk_system_prompt, walkthrough_system_prompt,
 };
-use crate::model::{Message, MessageRole, ReviewMode, Walkthrough};
+use std::collections::HashSet;
+
+use crate::diff::read_worktree_file;
+use crate::model::{Message, MessageRole, ReviewMode, Step, Walkthrough};
 
 pub enum ClientStreamEvent {
     StepComplete(WalkthroughStepResponse),
+    /// Output tokens received so far
+    Progress(u32),
+}
+
+/// Rough characters-per-token ratio, used to report streaming progress
+/// before the API's own output count arrives at the end of the message.
+pub(super) const CHARS_PER_TOKEN: usize = 4;
+
+/// Full files larger than this are left out of the `full_file` chat context.
+const FULL_FILE_MAX_BYTES: usize = 100_000;
+
+/// Rough token count of `text`, for showing context sizes.
+pub fn estimate_tokens(text: &str) -> usize {
+    text.len() / CHARS_PER_TOKEN
+}
+
+fn format_step(heading: &str, step: &Step) -> String {
+    format!(
+        "## {}: {}\n\n{}\n\n### Code Changes\n\n{}",
+        heading,
+        step.title,
+        step.summary,
+        format_hunks(step)
+    )
+}
+
+fn format_hunks(step: &Step) -> String {
+    step.hunks
+        .iter()
+        .map(|h| format!("#### {}\n```\n{}\n```", h.file_path, h.content))
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+/// Lines `ranges` (1-based, inclusive) of `file`, each widened by `context`
+/// lines and merged where they overlap, numbered and separated by `...`.
+fn surrounding_excerpt(file: &str, ranges: &[(usize, usize)], context: usize) -> String {
+    let lines: Vec<&str> = file.lines().collect();
+    let mut widened: Vec<(usize, usize)> = ranges
+        .iter()
+        .map(|&(first, last)| {
+            (first.saturating_sub(context).max(1), (last + context).min(lines.len()))
+        })
+        .filter(|(first, last)| first <= last)
+        .collect();
+    widened.sort();
+
+    let mut merged: Vec<(usize, usize)> = Vec::new();
+    for (first, last) in widened {
+        match merged.last_mut() {
+            Some((_, end)) if first <= *end + 1 => *end = (*end).max(last),
+            _ => merged.push((first, last)),
+        }
+    }
+
+    merged
+        .iter()
+        .map(|&(first, last)| {
+            (first..=last)
+                .map(|n| format!("{:>5} | {}", n, lines[n - 1]))
+                .collect::<Vec<_>>()
+                .join("\n")
+        })
+        .collect::<Vec<_>>()
+        .join("\n...\n")
+}
+
+/// Builds the context sent ahead of a conversation about step `step_index`.
+/// `level` sets how much of the surrounding change is included;
+/// `context_lines` is how far around each hunk the `Surrounding` level reads.
+pub fn chat_context(
+    walkthrough: &Walkthrough,
+    step_index: usize,
+    level: ChatContext,
+    context_lines: usize,
+) -> Option<String> {
+    let step = walkthrough.get_step(step_index)?;
+    let mut sections = Vec::new();
+
+    if level != ChatContext::Step {
+        let overview: String = walkthrough
+            .steps
+            .iter()
+            .enumerate()
+            .map(|(i, s)| {
+                let marker = if i == step_index { " ← current" } else { "" };
+                format!("{}. {}{}", i + 1, s.title, marker)
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        sections.push(format!("## Walkthrough Overview\n{}", overview));
+    }
+
+    if level == ChatContext::Neighbors
+        && let Some(previous) = step_index
+            .checked_sub(1)
+            .and_then(|i| walkthrough.get_step(i))
+    {
+        sections.push(format_step("Previous Step", previous));
+    }
+    sections.push(format_step("Current Step", step));
+    if level == ChatContext::Neighbors
+        && let Some(next) = walkthrough.get_step(step_index + 1)
+    {
+        sections.push(format_step("Next Step", next));
+    }
+
+    if level == ChatContext::Surrounding {
+        // New-file line range of each hunk, grouped by file in step order
+        let mut files: Vec<(&str, Vec<(usize, usize)>)> = Vec::new();
+        for hunk in &step.hunks {
+            let numbers: Vec<usize> = hunk.line_numbers().iter().filter_map(|(_, n)| *n).collect();
+            let first = numbers.first().copied().unwrap_or(hunk.start_line);
+            let last = numbers.last().copied().unwrap_or(first);
+            match files.iter_mut().find(|(path, _)| *path == hunk.file_path) {
+                Some((_, ranges)) => ranges.push((first, last)),
+                None => files.push((&hunk.file_path, vec![(first, last)])),
+            }
+        }
+        for (path, ranges) in files {
+            if let Some(content) = read_worktree_file(path) {
+                sections.push(format!(
+                    "## Surrounding Code: {}\n```\n{}\n```",
+                    path,
+                    surrounding_excerpt(&content, &ranges, context_lines)
+                ));
+            }
+        }
+    }
+
+    if level == ChatContext::FullFile {
+        let mut seen = HashSet::new();
+        for hunk in &step.hunks {
+            if !seen.insert(hunk.file_path.as_str()) {
+                continue;
+            }
+            match read_worktree_file(&hunk.file_path) {
+                Some(content) if content.len() <= FULL_FILE_MAX_BYTES => sections.push(format!(
+                    "## Full File: {}\n```\n{}\n```",
+                    hunk.file_path, content
+                )),
+                _ => {}
+            }
+        }
+    }
+
+    Some(sections.join("\n\n"))
+}
+
+/// Estimated tokens of hunks sent with a question about the whole change.
+/// Steps whose hunks don't fit are sent with their summary only.
+const WHOLE_CHANGE_HUNK_TOKEN_BUDGET: usize = 40_000;
+
+/// Context for a question about the whole change rather than one step: every
+/// step's title and summary, then, with `include_hunks`, the hunks of as many
+/// steps as fit the budget.
+pub fn whole_change_context(walkthrough: &Walkthrough, include_hunks: bool) -> String {
+    let overview = walkthrough
+        .steps
+        .iter()
+        .enumerate()
+        .map(|(i, s)| format!("{}. {}\n{}", i + 1, s.title, s.summary))
+        .collect::<Vec<_>>()
+        .join("\n\n");
+    let mut sections = vec![
+        "The question is about the change as a whole, not any one step.".to_string(),
+        format!("## Walkthrough Overview\n{}", overview),
+    ];
+
+    if include_hunks {
+        let mut budget = WHOLE_CHANGE_HUNK_TOKEN_BUDGET * CHARS_PER_TOKEN;
+        let mut left_out = Vec::new();
+        for (i, step) in walkthrough.steps.iter().enumerate() {
+            if step.hunks.is_empty() {
+                continue;
+            }
+            let section = format!("## Step {} Code Changes\n\n{}", i + 1, format_hunks(step));
+            if section.len() > budget {
+                left_out.push((i + 1).to_string());
+                continue;
+            }
+            budget -= section.len();
+            sections.push(section);
+        }
+        if !left_out.is_empty() {
+            sections.push(format!(
+                "(The code changes of these steps were left out to fit the context window: {}.)",
+                left_out.join(", ")
+            ));
+        }
+    }
+
+    sections.join("\n\n")
+}
+
+/// Estimated tokens of chat history sent with each follow-up question. Older
+/// turns beyond this are dropped so long conversations keep fitting in the
+/// model's context window alongside the step's diff.
+const CHAT_HISTORY_TOKEN_BUDGET: usize = 60_000;
+
+/// The messages of a step's conversation that fit the history budget, and
+/// how many older ones were left out. The step summary (the opening assistant
+/// message) and the latest message are always kept, and the kept history
+/// never starts with an assistant reply to a dropped question.
+pub fn fit_chat_history(messages: &[Message]) -> (Vec<&Message>, usize) {
+    let (summary, rest) = match messages.split_first() {
+        Some((first, rest)) if first.role == MessageRole::Assistant => (Some(first), rest),
+        _ => (None, messages),
+    };
+    let mut budget = (CHAT_HISTORY_TOKEN_BUDGET * CHARS_PER_TOKEN)
+        .saturating_sub(summary.map_or(0, |m| m.content.len()));
+
+    let mut start = rest.len();
+    while start > 0 {
+        let len = rest[start - 1].content.len();
+        if len > budget && start < rest.len() {
+            break;
+        }
+        budget = budget.saturating_sub(len);
+        start -= 1;
+    }
+    while start < rest.len().saturating_sub(1) && rest[start].role == MessageRole::Assistant {
+        start += 1;
+    }
+
+    let kept = summary.into_iter().chain(&rest[start..]).collect();
+    (kept, start)
 }
 
-const API_URL: &str = "https://api.anthropic.com/v1/messages";
-const MODEL: &str = "claude-sonnet-4-20250514";
+/// Follow-up questions shown after each answer, at most.
+pub const MAX_FOLLOW_UPS: usize = 3;
+
+/// The prompt asking for follow-up questions: the step's chat context and
+/// the conversation that fits the history budget, as a transcript.
+pub fn follow_up_prompt(context: &str, messages: &[Message]) -> String {
+    let (kept, _) = fit_chat_history(messages);
+    format!(
+        "{}\n\n## Conversation\n\n{}\n\nSuggest up to {} follow-up questions.",
+        context,
+        transcript(kept),
+        MAX_FOLLOW_UPS
+    )
+}
+
+/// Messages as a Markdown transcript naming who said each.
+fn transcript<'a>(messages: impl IntoIterator<Item = &'a Message>) -> String {
+    let turns: Vec<String> = messages
+        .into_iter()
+        .map(|m| {
+            let speaker = match m.role {
+                MessageRole::User => "Reviewer",
+                MessageRole::Assistant => "Assistant",
+            };
+            format!("**{}:** {}", speaker, m.content)
+        })
+        .collect();
+    turns.join("\n\n")
+}
+
+/// The prompt asking for a summary of the messages `fit_chat_history` drops
+/// from a conversation, and how many they are. `previous` is an earlier
+/// summary and how many messages it covers: when it covers exactly the
+/// dropped ones no new summary is needed, and when it covers fewer only the
+/// rest are sent along with it.
+pub fn history_summary_prompt(
+    messages: &[Message],
+    previous: Option<(usize, &str)>,
+) -> Option<(usize, String)> {
+    let (_, omitted) = fit_chat_history(messages);
+    let previous = previous.filter(|(covered, _)| *covered <= omitted);
+    if omitted == 0 || previous.is_some_and(|(covered, _)| covered == omitted) {
+        return None;
+    }
+    let rest = match messages.first() {
+        Some(first) if first.role == MessageRole::Assistant => &messages[1..],
+        _ => messages,
+    };
+    let covered = previous.map_or(0, |(covered, _)| covered);
+    let mut prompt = String::new();
+    if let Some((_, summary)) = previous {
+        prompt.push_str(&format!("## Summary of the conversation so far\n\n{}\n\n", summary));
+    }
+    prompt.push_str(&format!(
+        "## Conversation\n\n{}\n\nSummarize {}.",
+        transcript(&rest[covered..omitted]),
+        if previous.is_some() {
+            "the summary and these messages together"
+        } else {
+            "these messages"
+        }
+    ));
+    Some((omitted, prompt))
+}
+
+/// A chat context followed by the summary of the conversation's dropped
+/// messages.
+pub fn context_with_summary(context: &str, summary: &str) -> String {
+    format!(
+        "{}\n\n## Earlier in this conversation\n\n\
+         The first messages of this conversation are no longer included. \
+         This is a summary of them:\n\n{}",
+        context, summary
+    )
+}
+
+/// The provider and API key, and the model and request parameters to call
+/// the API with.
+#[derive(Debug, Clone, Default)]
+pub struct ClientConfig {
+    pub provider: Provider,
+    pub api_key: String,
+    pub settings: ApiSettings,
+    /// Where to announce waits before retrying a rate-limited request
+    pub retry_notices: Option<mpsc::UnboundedSender<RetryNotice>>,
+}
+
+impl ClientConfig {
+    /// The base URL from settings, the provider's environment variable, or
+    /// the provider's public API, without a trailing slash.
+    pub fn base_url(&self) -> String {
+        let url = self
+            .settings
+            .base_url
+            .clone()
+            .or_else(|| std::env::var(self.provider.base_url_env_var()?).ok())
+            .filter(|url| !url.is_empty())
+            .unwrap_or_else(|| self.provider.default_base_url().to_string());
+        url.trim_end_matches('/').to_string()
+    }
+
+    /// The model from settings, or the provider's default.
+    pub fn model(&self) -> &str {
+        self.settings
+            .model
+            .as_deref()
+            .unwrap_or(self.provider.default_model())
+    }
+
+    /// An HTTP client honoring the configured request timeout.
+    pub(super) fn http_client(&self) -> reqwest::Client {
+        let mut builder = reqwest::Client::builder();
+        if let Some(secs) = self.settings.timeout_secs {
+            builder = builder.timeout(std::time::Duration::from_secs(secs));
+        }
+        builder.build().unwrap_or_default()
+    }
+}
+
+/// The opening user message and conversation history for a chat request,
+/// in the role/content shape both supported APIs accept.
+pub(super) fn chat_messages(context: &str, messages: &[Message]) -> Vec<serde_json::Value> {
+    let (history, omitted) = fit_chat_history(messages);
+    let mut full_context = format!("Here is the code change I'm reviewing:\n\n{}", context);
+    if omitted > 0 {
+        full_context.push_str(&format!(
+            "\n\n(Note: {} earlier messages of this conversation were omitted to stay \
+             within the context window.)",
+            omitted
+        ));
+    }
+
+    std::iter::once(json!({
+        "role": "user",
+        "content": full_context
+    }))
+    .chain(history.into_iter().map(|m| {
+        json!({
+            "role": match m.role {
+                MessageRole::User => "user",
+                MessageRole::Assistant => "assistant",
+            },
+            "content": m.content.clone()
+        })
+    }))
+    .collect()
+}
 
 pub struct ClaudeClient {
     api_key: String,
+    url: String,
+    model: String,
+    settings: ApiSettings,
     client: reqwest::Client,
+    retry_notices: Option<mpsc::UnboundedSender<RetryNotice>>,
 }
 
 impl ClaudeClient {
-    pub fn new(api_key: String) -> Self {
+    pub fn new(config: ClientConfig) -> Self {
         Self {
-            api_key,
-            client: reqwest::Client::new(),
+            client: config.http_client(),
+            url: format!("{}/messages", config.base_url()),
+            model: config.model().to_string(),
+            api_key: config.api_key,
+            settings: config.settings,
+            retry_notices: config.retry_notices,
+        }
+    }
+
+    pub fn model(&self) -> &str {
+        &self.model
+    }
+
+    /// Sets the configured temperature on a request body, if any.
+    fn apply_temperature(&self, request_body: &mut serde_json::Value) {
+        if let Some(temperature) = self.settings.temperature {
+            request_body["temperature"] = json!(temperature);
         }
     }
 
+    /// Posts a Messages API request, retrying while rate limited or overloaded.
+    async fn send(&self, request_body: &serde_json::Value) -> Result<reqwest::Response, ApiError> {
+        send_with_retry(
+            || {
+                self.client
+                    .post(&self.url)
+                    .header("x-api-key", &self.api_key)
+                    .header("anthropic-version", "2023-06-01")
+                    .header("content-type", "application/json")
+                    .json(request_body)
+            },
+            self.settings.max_attempts,
+            self.retry_notices.as_ref(),
+        )
+        .await
+    }
+
     async fn tool_use_request<T: DeserializeOwned>(
         &self,
         tool_schema: &str,
@@ -40,9 +456,9 @@ impl ClaudeClient {
         let tool: serde_json::Value = serde_json::from_str(tool_schema)
             .map_err(|e| ApiError::Parse(format!("invalid tool schema: {}", e)))?;
 
-        let request_body = json!({
-            "model": MODEL,
-            "max_tokens": 4096,
+        let mut request_body = json!({
+            "model": self.model(),
+            "max_tokens": self.settings.generation_max_tokens,
             "system": system_prompt,
             "tools": [tool],
             "tool_choice": {"type": "tool", "name": tool_name},
@@ -53,25 +469,9 @@ impl ClaudeClient {
                 }
             ]
         });
+        self.apply_temperature(&mut request_body);
 
-        let response = self
-            .client
-            .post(API_URL)
-            .header("x-api-key", &self.api_key)
-            .header("anthropic-version", "2023-06-01")
-            .header("content-type", "application/json")
-            .json(&request_body)
-            .send()
-            .await?;
-
-        let status = response.status();
-        if !status.is_success() {
-            let body = response.text().await.unwrap_or_default();
-            return Err(ApiError::ApiResponse {
-                status: status.as_u16(),
-                message: body,
-            });
-        }
+        let response = self.send(&request_body).await?;
 
         let api_response: ApiResponse = response
             .json()
@@ -111,6 +511,47 @@ impl ClaudeClient {
         .await
     }
 
+    /// Ask the model how to split a large diff into separately reviewed parts.
+    pub async fn suggest_parts(
+        &self,
+        prompt: &str,
+    ) -> Result<(PartitionResponse, TokenUsage), ApiError> {
+        self.tool_use_request(
+            PARTITION_DIFF_TOOL,
+            "partition_diff",
+            PARTITION_SYSTEM_PROMPT,
+            prompt,
+        )
+        .await
+    }
+
+    pub async fn suggest_follow_ups(
+        &self,
+        prompt: &str,
+    ) -> Result<(FollowUpResponse, TokenUsage), ApiError> {
+        self.tool_use_request(
+            FOLLOW_UPS_TOOL,
+            "suggest_follow_ups",
+            FOLLOW_UPS_SYSTEM_PROMPT,
+            prompt,
+        )
+        .await
+    }
+
+    /// Ask for a summary of the messages dropped from a long conversation.
+    pub async fn summarize_history(
+        &self,
+        prompt: &str,
+    ) -> Result<(HistorySummaryResponse, TokenUsage), ApiError> {
+        self.tool_use_request(
+            SUMMARIZE_HISTORY_TOOL,
+            "summarize_history",
+            SUMMARIZE_HISTORY_SYSTEM_PROMPT,
+            prompt,
+        )
+        .await
+    }
+
     /// Stream the walkthrough generation, sending complete steps as they're detected.
     /// Uses text mode with assistant prefill for true token-by-token streaming
     /// (tool_use streaming batches the entire response before streaming tokens).
@@ -121,11 +562,15 @@ impl ClaudeClient {
         event_tx: mpsc::Sender<ClientStreamEvent>,
     ) -> Result<TokenUsage, ApiError> {
         let prefill = r#"{"steps": ["#;
-
-        let request_body = json!({
-            "model": MODEL,
-            "max_tokens": 4096,
-            "system": walkthrough_system_prompt(mode),
+        let system = self
+            .settings
+            .prompts
+            .walkthrough_prompt(walkthrough_system_prompt(mode))?;
+
+        let mut request_body = json!({
+            "model": self.model(),
+            "max_tokens": self.settings.generation_max_tokens,
+            "system": system,
             "stream": true,
             "messages": [
                 {
@@ -138,25 +583,9 @@ impl ClaudeClient {
                 }
             ]
         });
+        self.apply_temperature(&mut request_body);
 
-        let response = self
-            .client
-            .post(API_URL)
-            .header("x-api-key", &self.api_key)
-            .header("anthropic-version", "2023-06-01")
-            .header("content-type", "application/json")
-            .json(&request_body)
-            .send()
-            .await?;
-
-        let status = response.status();
-        if !status.is_success() {
-            let body = response.text().await.unwrap_or_default();
-            return Err(ApiError::ApiResponse {
-                status: status.as_u16(),
-                message: body,
-            });
-        }
+        let response = self.send(&request_body).await?;
 
         let mut stream = response.bytes_stream();
         let mut sse_buffer = String::new();
@@ -198,6 +627,9 @@ impl ClaudeClient {
 
                 if let Some(text) = parse_sse_text_delta(&event_data) {
                     json_buffer.push_str(&text);
+                    let estimated = ((json_buffer.len() - prefill.len()) / CHARS_PER_TOKEN) as u32;
+                    let tokens = usage.output_tokens.max(estimated);
+                    let _ = event_tx.send(ClientStreamEvent::Progress(tokens)).await;
                     let steps = step_extractor.feed(&json_buffer);
                     if let Some(ref mut f) = debug_log {
                         use std::io::Write;
@@ -243,119 +675,195 @@ impl ClaudeClient {
     }
 
     /// Chat about a specific step in the walkthrough with streaming.
+    /// `context` is the step context built by `chat_context`.
     /// Sends text chunks through the provided sender as they arrive.
     /// Returns Ok(()) on success, or an error.
     pub async fn chat_streaming(
         &self,
-        walkthrough: &Walkthrough,
-        step_index: usize,
+        context: &str,
         messages: &[Message],
         mode: ReviewMode,
         chunk_tx: mpsc::Sender<String>,
     ) -> Result<TokenUsage, ApiError> {
-        let step = walkthrough
-            .get_step(step_index)
-            .ok_or_else(|| ApiError::Parse("invalid step index".to_string()))?;
+        let system = self.settings.prompts.chat_prompt(chat_system_prompt(mode))?;
+        let mut api_messages = chat_messages(context, messages);
+        let mut usage = TokenUsage::default();
+        let mut streamed_text = false;
+
+        for round in 0..=MAX_TOOL_ROUNDS {
+            let mut request_body = json!({
+                "model": self.model(),
+                "max_tokens": self.settings.chat_max_tokens,
+                "system": system,
+                "stream": true,
+                "messages": api_messages
+            });
+            self.apply_temperature(&mut request_body);
+            if self.settings.chat_tools {
+                request_body["tools"] = tool_definitions();
+                if round == MAX_TOOL_ROUNDS {
+                    // Out of rounds: answer with what has been read so far
+                    request_body["tool_choice"] = json!({"type": "none"});
+                }
+            }
 
-        // Build walkthrough overview
-        let overview: String = walkthrough
-            .steps
-            .iter()
-            .enumerate()
-            .map(|(i, s)| {
-                let marker = if i == step_index { " ← current" } else { "" };
-                format!("{}. {}{}", i + 1, s.title, marker)
-            })
-            .collect::<Vec<_>>()
-            .join("\n");
+            let response = self.send(&request_body).await?;
 
-        // Build context about the current step
-        let step_context = format!(
-            "## Walkthrough Overview\n{}\n\n## Current Step: {}\n\n{}\n\n## Code Changes\n\n{}",
-            overview,
-            step.title,
-            step.summary,
-            step.hunks
-                .iter()
-                .map(|h| format!("### {}\n```\n{}\n```", h.file_path, h.content))
-                .collect::<Vec<_>>()
-                .join("\n\n")
-        );
+            // Process SSE stream
+            let mut stream = response.bytes_stream();
+            let mut buffer = String::new();
+            let mut turn = TurnCollector::default();
 
-        let full_context = format!("Here is the code change I'm reviewing:\n\n{}", step_context);
-
-        // Convert messages to API format
-        let api_messages: Vec<serde_json::Value> = std::iter::once(json!({
-            "role": "user",
-            "content": full_context
-        }))
-        .chain(messages.iter().map(|m| {
-            json!({
-                "role": match m.role {
-                    MessageRole::User => "user",
-                    MessageRole::Assistant => "assistant",
-                },
-                "content": m.content.clone()
-            })
-        }))
-        .collect();
+            while let Some(chunk_result) = stream.next().await {
+                let chunk = chunk_result?;
+                buffer.push_str(&String::from_utf8_lossy(&chunk));
 
-        let request_body = json!({
-            "model": MODEL,
-            "max_tokens": 1024,
-            "system": chat_system_prompt(mode),
-            "stream": true,
-            "messages": api_messages
-        });
+                // Process complete SSE events in the buffer
+                while let Some(event_end) = buffer.find("\n\n") {
+                    let event_data = buffer[..event_end].to_string();
+                    buffer = buffer[event_end + 2..].to_string();
 
-        let response = self
-            .client
-            .post(API_URL)
-            .header("x-api-key", &self.api_key)
-            .header("anthropic-version", "2023-06-01")
-            .header("content-type", "application/json")
-            .json(&request_body)
-            .send()
-            .await?;
-
-        let status = response.status();
-        if !status.is_success() {
-            let body = response.text().await.unwrap_or_default();
-            return Err(ApiError::ApiResponse {
-                status: status.as_u16(),
-                message: body,
-            });
+                    if let Some((input_delta, output_delta)) = parse_sse_usage(&event_data) {
+                        usage.input_tokens += input_delta;
+                        usage.output_tokens += output_delta;
+                    }
+
+                    if let Some(text) = parse_sse_text_delta(&event_data) {
+                        streamed_text = true;
+                        let _ = chunk_tx.send(text).await;
+                    }
+
+                    turn.feed(&event_data);
+                }
+            }
+
+            let calls = turn.tool_calls();
+            if turn.stop_reason.as_deref() != Some("tool_use") || calls.is_empty() {
+                break;
+            }
+
+            // Run the requested tools and send their results back
+            let mut results = Vec::new();
+            for call in &calls {
+                let separator = if streamed_text { "\n\n" } else { "" };
+                let _ = chunk_tx.send(format!("{separator}`→ {}`\n\n", call.describe())).await;
+                streamed_text = true;
+                let (output, is_error) = call.run().await;
+                results.push(json!({
+                    "type": "tool_result",
+                    "tool_use_id": call.id,
+                    "content": output,
+                    "is_error": is_error,
+                }));
+            }
+            api_messages.push(json!({"role": "assistant", "content": turn.content()}));
+            api_messages.push(json!({"role": "user", "content": results}));
         }
 
-        // Process SSE stream
-        let mut stream = response.bytes_stream();
-        let mut buffer = String::new();
-        let mut usage = TokenUsage::default();
+        Ok(usage)
+    }
+}
 
-        while let Some(chunk_result) = stream.next().await {
-            let chunk = chunk_result?;
-            buffer.push_str(&String::from_utf8_lossy(&chunk));
+/// Content blocks of one streamed assistant turn, kept so a tool-use turn can
+/// be replayed to the API alongside the tool results.
+#[derive(Debug, Default)]
+struct TurnCollector {
+    blocks: Vec<TurnBlock>,
+    stop_reason: Option<String>,
+}
 
-            // Process complete SSE events in the buffer
-            while let Some(event_end) = buffer.find("\n\n") {
-                let event_data = buffer[..event_end].to_string();
-                buffer = buffer[event_end + 2..].to_string();
+#[derive(Debug)]
+enum TurnBlock {
+    Text(String),
+    ToolUse {
+        id: String,
+        name: String,
+        json: String,
+    },
+}
 
-                if let Some((input_delta, output_delta)) = parse_sse_usage(&event_data) {
-                    usage.input_tokens += input_delta;
-                    usage.output_tokens += output_delta;
+impl TurnCollector {
+    fn feed(&mut self, event: &str) {
+        if let Some(text) = parse_sse_text_delta(event) {
+            if let Some(TurnBlock::Text(t)) = self.blocks.last_mut() {
+                t.push_str(&text);
+            }
+            return;
+        }
+        if let Some(partial) = parse_sse_input_json_delta(event) {
+            if let Some(TurnBlock::ToolUse { json, .. }) = self.blocks.last_mut() {
+                json.push_str(&partial);
+            }
+            return;
+        }
+        for line in event.lines() {
+            let Some(data) = line.strip_prefix("data: ") else {
+                continue;
+            };
+            let Ok(json) = serde_json::from_str::<serde_json::Value>(data) else {
+                continue;
+            };
+            match json.get("type").and_then(|t| t.as_str()) {
+                Some("content_block_start") => {
+                    let block = &json["content_block"];
+                    let str_field = |key: &str| block[key].as_str().unwrap_or("").to_string();
+                    match block["type"].as_str() {
+                        Some("text") => self.blocks.push(TurnBlock::Text(str_field("text"))),
+                        Some("tool_use") => self.blocks.push(TurnBlock::ToolUse {
+                            id: str_field("id"),
+                            name: str_field("name"),
+                            json: String::new(),
+                        }),
+                        _ => {}
+                    }
                 }
-
-                if let Some(text) = parse_sse_text_delta(&event_data) {
-                    let _ = chunk_tx.send(text).await;
+                Some("message_delta") => {
+                    if let Some(reason) = json["delta"]["stop_reason"].as_str() {
+                        self.stop_reason = Some(reason.to_string());
+                    }
                 }
+                _ => {}
             }
         }
+    }
 
-        Ok(usage)
+    fn tool_calls(&self) -> Vec<ToolCall> {
+        self.blocks
+            .iter()
+            .filter_map(|block| match block {
+                TurnBlock::ToolUse { id, name, json } => Some(ToolCall {
+                    id: id.clone(),
+                    name: name.clone(),
+                    input: tool_input(json),
+                }),
+                TurnBlock::Text(_) => None,
+            })
+            .collect()
+    }
+
+    /// The turn's content blocks in the Messages API format.
+    fn content(&self) -> serde_json::Value {
+        self.blocks
+            .iter()
+            .filter_map(|block| match block {
+                TurnBlock::Text(text) if text.is_empty() => None,
+                TurnBlock::Text(text) => Some(json!({"type": "text", "text": text})),
+                TurnBlock::ToolUse { id, name, json } => Some(json!({
+                    "type": "tool_use",
+                    "id": id,
+                    "name": name,
+                    "input": tool_input(json),
+                })),
+            })
+            .collect()
     }
 }
 
+/// Parses streamed tool input; a tool without arguments streams nothing.
+fn tool_input(json: &str) -> serde_json::Value {
+    serde_json::from_str(json).unwrap_or_else(|_| json!({}))
+}
+
 /// Parse SSE event data to extract token usage deltas.
 /// Returns `(input_delta, output_delta)`.
 /// Handles both `message_start` (input + output) and `message_delta` (output only).
@@ -396,7 +904,6 @@ fn parse_sse_text_delta(event: &str) -> Option<String> {
     None
 }
 
-#[allow(dead_code)]
 /// Parse SSE event data to extract input_json_delta content (for streaming tool_use)
 fn parse_sse_input_json_delta(event: &str) -> Option<String> {
     for line in event.lines() {
@@ -528,7 +1035,7 @@ fn extract_json_string_value(buffer: &str, from: usize) -> Option<(String, usize
 
 /// Detects complete step JSON objects within the streaming `steps` array.
 /// Tracks brace depth to find complete `{...}` objects, respecting string boundaries.
-struct StepExtractor {
+pub(super) struct StepExtractor {
     scan_pos:    usize,
     state:       StepExtractorState,
     brace_depth: i32,
@@ -545,7 +1052,7 @@ enum StepExtractorState {
 }
 
 impl StepExtractor {
-    fn new() -> Self {
+    pub(super) fn new() -> Self {
         Self {
             scan_pos:    0,
             state:       StepExtractorState::Initial,
@@ -565,7 +1072,7 @@ impl StepExtractor {
         }
     }
 
-    fn feed(&mut self, buffer: &str) -> Vec<WalkthroughStepResponse> {
+    pub(super) fn feed(&mut self, buffer: &str) -> Vec<WalkthroughStepResponse> {
         let mut results = Vec::new();
         let bytes = buffer.as_bytes();
 
@@ -709,6 +1216,153 @@ struct ContentBlock {
 mod tests {
     use super::*;
 
+    #[test]
+    fn chat_context_levels() {
+        let step = |title: &str| Step {
+            title: title.to_string(),
+            hunks: vec![],
+            ..Step::test("", "")
+        };
+        let walkthrough = Walkthrough {
+            steps: vec![step("Add model"), step("Validate"), step("Use it")],
+            messages: vec![],
+        };
+
+        let current = chat_context(&walkthrough, 1, ChatContext::Step, 0).unwrap();
+        assert!(!current.contains("Walkthrough Overview"));
+        assert!(current.contains("## Current Step: Validate"));
+
+        let overview = chat_context(&walkthrough, 1, ChatContext::Overview, 0).unwrap();
+        assert!(overview.contains("2. Validate ← current"));
+        assert!(!overview.contains("Previous Step"));
+
+        let neighbors = chat_context(&walkthrough, 1, ChatContext::Neighbors, 0).unwrap();
+        assert!(neighbors.contains("## Previous Step: Add model"));
+        assert!(neighbors.contains("## Next Step: Use it"));
+
+        assert!(chat_context(&walkthrough, 99, ChatContext::Step, 0).is_none());
+    }
+
+    #[test]
+    fn whole_change_context_fits_hunks_to_the_budget() {
+        let step = |title: &str, content: String| Step {
+            title: title.to_string(),
+            summary: format!("{} summary", title),
+            hunks: vec![crate::model::Hunk::test(
+                &format!("src/{}.rs", title),
+                1,
+                1,
+                &content,
+            )],
+            ..Step::test("", "")
+        };
+        let huge = "+x\n".repeat(WHOLE_CHANGE_HUNK_TOKEN_BUDGET * CHARS_PER_TOKEN / 3);
+        let walkthrough = Walkthrough {
+            steps: vec![
+                step("model", "+struct User;".to_string()),
+                step("vendored", huge),
+                step("api", "+fn get_user() {}".to_string()),
+            ],
+            messages: vec![],
+        };
+
+        let context = whole_change_context(&walkthrough, true);
+        assert!(context.contains("1. model\nmodel summary"));
+        assert!(context.contains("2. vendored\nvendored summary"));
+        assert!(context.contains("## Step 3 Code Changes\n\n#### src/api.rs"));
+        assert!(!context.contains("## Step 2 Code Changes"));
+        assert!(context.contains("to fit the context window: 2."));
+
+        let summaries = whole_change_context(&walkthrough, false);
+        assert!(summaries.contains("3. api\napi summary"));
+        assert!(!summaries.contains("Code Changes"));
+    }
+
+    #[test]
+    fn surrounding_excerpt_merges_nearby_ranges() {
+        let file: String = (1..=20).map(|n| format!("line {}\n", n)).collect();
+        let excerpt = surrounding_excerpt(&file, &[(5, 5), (8, 9), (18, 18)], 1);
+        let numbers: Vec<&str> = excerpt
+            .lines()
+            .map(|line| line.split('|').next().unwrap().trim())
+            .collect();
+        assert_eq!(
+            numbers,
+            ["4", "5", "6", "7", "8", "9", "10", "...", "17", "18", "19"]
+        );
+        assert!(excerpt.starts_with("    4 | line 4"));
+        // Context stops at the ends of the file
+        assert_eq!(surrounding_excerpt(&file, &[(1, 1)], 3).lines().count(), 4);
+    }
+
+    #[test]
+    fn chat_history_drops_oldest_turns_over_budget() {
+        let long = "x".repeat(CHAT_HISTORY_TOKEN_BUDGET * CHARS_PER_TOKEN / 2);
+        let messages = vec![
+            Message::assistant("Step summary"),
+            Message::user(long.clone()),
+            Message::assistant(long.clone()),
+            Message::user("short question"),
+            Message::assistant(long),
+            Message::user("latest"),
+        ];
+
+        let (kept, omitted) = fit_chat_history(&messages);
+        assert_eq!(omitted, 2);
+        let contents: Vec<&str> = kept.iter().map(|m| m.content.as_str()).collect();
+        assert_eq!(contents[0], "Step summary");
+        assert_eq!(contents[1], "short question");
+        assert_eq!(kept.len(), 4);
+
+        let (kept, omitted) = fit_chat_history(&messages[..1]);
+        assert_eq!((kept.len(), omitted), (1, 0));
+    }
+
+    #[test]
+    fn history_summary_covers_only_new_dropped_messages() {
+        let long = "x".repeat(CHAT_HISTORY_TOKEN_BUDGET * CHARS_PER_TOKEN / 2);
+        let messages = vec![
+            Message::assistant("Step summary"),
+            Message::user("first question"),
+            Message::assistant(long.clone()),
+            Message::user("second question"),
+            Message::assistant(long.clone()),
+            Message::user("latest"),
+        ];
+
+        let (covered, prompt) = history_summary_prompt(&messages, None).unwrap();
+        assert_eq!(covered, 2);
+        assert!(prompt.contains("**Reviewer:** first question"));
+        assert!(!prompt.contains("Step summary"));
+        assert!(!prompt.contains("second question"));
+
+        assert!(history_summary_prompt(&messages, Some((2, "Asked why"))).is_none());
+        assert!(history_summary_prompt(&messages[..2], None).is_none());
+
+        let (covered, prompt) = history_summary_prompt(&messages, Some((1, "Asked why"))).unwrap();
+        assert_eq!(covered, 2);
+        assert!(prompt.starts_with("## Summary of the conversation so far\n\nAsked why"));
+        assert!(!prompt.contains("first question"));
+
+        // A summary of more than is dropped belongs to another conversation
+        let (_, prompt) = history_summary_prompt(&messages, Some((3, "Stale"))).unwrap();
+        assert!(!prompt.contains("Stale"));
+    }
+
+    #[test]
+    fn follow_up_prompt_includes_the_conversation() {
+        let messages = vec![
+            Message::assistant("Step summary"),
+            Message::user("Why a mutex?"),
+            Message::assistant("The cache is shared across tasks."),
+        ];
+        let prompt = follow_up_prompt("## Step context", &messages);
+        assert!(prompt.starts_with("## Step context"));
+        assert!(prompt.contains("**Reviewer:** Why a mutex?"));
+        assert!(prompt.contains("**Assistant:** The cache is shared across tasks."));
+        assert!(prompt.ends_with("Suggest up to 3 follow-up questions."));
+    }
+
     #[test]
     fn title_extractor_finds_titles() {
         let mut extractor = TitleExtractor::new();
@@ -771,6 +1425,36 @@ mod tests {
         assert!(parse_sse_input_json_delta(event).is_none());
     }
 
+    #[test]
+    fn turn_collector_gathers_text_and_tool_calls() {
+        let events = [
+            r#"data: {"type":"content_block_start","index":0,"content_block":{"type":"text","text":""}}"#,
+            r#"data: {"type":"content_block_delta","index":0,"delta":{"type":"text_delta","text":"Let me look."}}"#,
+            r#"data: {"type":"content_block_start","index":1,"content_block":{"type":"tool_use","id":"toolu_1","name":"read_file","input":{}}}"#,
+            r#"data: {"type":"content_block_delta","index":1,"delta":{"type":"input_json_delta","partial_json":"{\"path\": \"src/"}}"#,
+            r#"data: {"type":"content_block_delta","index":1,"delta":{"type":"input_json_delta","partial_json":"app.rs\"}"}}"#,
+            r#"data: {"type":"message_delta","delta":{"stop_reason":"tool_use"},"usage":{"output_tokens":30}}"#,
+        ];
+        let mut turn = TurnCollector::default();
+        for event in events {
+            turn.feed(event);
+        }
+
+        assert_eq!(turn.stop_reason.as_deref(), Some("tool_use"));
+        let calls = turn.tool_calls();
+        assert_eq!(calls.len(), 1);
+        assert_eq!(calls[0].id, "toolu_1");
+        assert_eq!(calls[0].input, json!({"path": "src/app.rs"}));
+        assert_eq!(
+            turn.content(),
+            json!([
+                {"type": "text", "text": "Let me look."},
+                {"type": "tool_use", "id": "toolu_1", "name": "read_file",
+                 "input": {"path": "src/app.rs"}},
+            ])
+        );
+    }
+
     #[test]
     fn step_extractor_finds_complete_steps() {
         let mut extractor = StepExtractor::new();
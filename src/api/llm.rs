@@ -0,0 +1,192 @@
+//! The operations docent needs from a model provider, so callers don't care
+//! which API is behind them.
+
+use futures::future::BoxFuture;
+use tokio::sync::mpsc;
+
+use crate::api::openai::OpenAiClient;
+use crate::api::settings::Provider;
+use crate::api::types::{
+    ApiError, FollowUpResponse, HistorySummaryResponse, PartitionResponse, RechunkResponse,
+    TokenUsage,
+};
+use crate::api::{ClaudeClient, ClientConfig, ClientStreamEvent};
+use crate::model::{Message, ReviewMode};
+
+pub trait LlmClient: Send + Sync {
+    /// The model requests are sent to.
+    fn model(&self) -> &str;
+
+    /// Streams walkthrough generation, sending complete steps as they're detected.
+    fn generate_walkthrough_streaming<'a>(
+        &'a self,
+        diff_prompt: &'a str,
+        mode: ReviewMode,
+        event_tx: mpsc::Sender<ClientStreamEvent>,
+    ) -> BoxFuture<'a, Result<TokenUsage, ApiError>>;
+
+    /// Streams a chat reply about a step, sending text chunks as they arrive.
+    fn chat_streaming<'a>(
+        &'a self,
+        context: &'a str,
+        messages: &'a [Message],
+        mode: ReviewMode,
+        chunk_tx: mpsc::Sender<String>,
+    ) -> BoxFuture<'a, Result<TokenUsage, ApiError>>;
+
+    /// Asks for a step to be split into smaller sub-steps.
+    fn rechunk_step<'a>(
+        &'a self,
+        prompt: &'a str,
+        mode: ReviewMode,
+    ) -> BoxFuture<'a, Result<(RechunkResponse, TokenUsage), ApiError>>;
+
+    /// Asks how to split a large diff into separately reviewed parts.
+    fn suggest_parts<'a>(
+        &'a self,
+        prompt: &'a str,
+    ) -> BoxFuture<'a, Result<(PartitionResponse, TokenUsage), ApiError>>;
+
+    /// Asks for questions the reviewer could ask next about a step.
+    fn suggest_follow_ups<'a>(
+        &'a self,
+        prompt: &'a str,
+    ) -> BoxFuture<'a, Result<(FollowUpResponse, TokenUsage), ApiError>>;
+
+    /// Asks for a summary of the messages dropped from a long conversation.
+    fn summarize_history<'a>(
+        &'a self,
+        prompt: &'a str,
+    ) -> BoxFuture<'a, Result<(HistorySummaryResponse, TokenUsage), ApiError>>;
+}
+
+/// A client for the configured provider.
+pub fn connect(config: ClientConfig) -> Box<dyn LlmClient> {
+    match config.provider {
+        Provider::Anthropic => Box::new(ClaudeClient::new(config)),
+        // Ollama serves the Chat Completions API under /v1
+        Provider::OpenAi | Provider::Ollama => Box::new(OpenAiClient::new(config)),
+    }
+}
+
+impl LlmClient for ClaudeClient {
+    fn model(&self) -> &str {
+        ClaudeClient::model(self)
+    }
+
+    fn generate_walkthrough_streaming<'a>(
+        &'a self,
+        diff_prompt: &'a str,
+        mode: ReviewMode,
+        event_tx: mpsc::Sender<ClientStreamEvent>,
+    ) -> BoxFuture<'a, Result<TokenUsage, ApiError>> {
+        Box::pin(ClaudeClient::generate_walkthrough_streaming(
+            self,
+            diff_prompt,
+            mode,
+            event_tx,
+        ))
+    }
+
+    fn chat_streaming<'a>(
+        &'a self,
+        context: &'a str,
+        messages: &'a [Message],
+        mode: ReviewMode,
+        chunk_tx: mpsc::Sender<String>,
+    ) -> BoxFuture<'a, Result<TokenUsage, ApiError>> {
+        Box::pin(ClaudeClient::chat_streaming(
+            self, context, messages, mode, chunk_tx,
+        ))
+    }
+
+    fn rechunk_step<'a>(
+        &'a self,
+        prompt: &'a str,
+        mode: ReviewMode,
+    ) -> BoxFuture<'a, Result<(RechunkResponse, TokenUsage), ApiError>> {
+        Box::pin(ClaudeClient::rechunk_step(self, prompt, mode))
+    }
+
+    fn suggest_parts<'a>(
+        &'a self,
+        prompt: &'a str,
+    ) -> BoxFuture<'a, Result<(PartitionResponse, TokenUsage), ApiError>> {
+        Box::pin(ClaudeClient::suggest_parts(self, prompt))
+    }
+
+    fn suggest_follow_ups<'a>(
+        &'a self,
+        prompt: &'a str,
+    ) -> BoxFuture<'a, Result<(FollowUpResponse, TokenUsage), ApiError>> {
+        Box::pin(ClaudeClient::suggest_follow_ups(self, prompt))
+    }
+
+    fn summarize_history<'a>(
+        &'a self,
+        prompt: &'a str,
+    ) -> BoxFuture<'a, Result<(HistorySummaryResponse, TokenUsage), ApiError>> {
+        Box::pin(ClaudeClient::summarize_history(self, prompt))
+    }
+}
+
+impl LlmClient for OpenAiClient {
+    fn model(&self) -> &str {
+        OpenAiClient::model(self)
+    }
+
+    fn generate_walkthrough_streaming<'a>(
+        &'a self,
+        diff_prompt: &'a str,
+        mode: ReviewMode,
+        event_tx: mpsc::Sender<ClientStreamEvent>,
+    ) -> BoxFuture<'a, Result<TokenUsage, ApiError>> {
+        Box::pin(OpenAiClient::generate_walkthrough_streaming(
+            self,
+            diff_prompt,
+            mode,
+            event_tx,
+        ))
+    }
+
+    fn chat_streaming<'a>(
+        &'a self,
+        context: &'a str,
+        messages: &'a [Message],
+        mode: ReviewMode,
+        chunk_tx: mpsc::Sender<String>,
+    ) -> BoxFuture<'a, Result<TokenUsage, ApiError>> {
+        Box::pin(OpenAiClient::chat_streaming(
+            self, context, messages, mode, chunk_tx,
+        ))
+    }
+
+    fn rechunk_step<'a>(
+        &'a self,
+        prompt: &'a str,
+        mode: ReviewMode,
+    ) -> BoxFuture<'a, Result<(RechunkResponse, TokenUsage), ApiError>> {
+        Box::pin(OpenAiClient::rechunk_step(self, prompt, mode))
+    }
+
+    fn suggest_parts<'a>(
+        &'a self,
+        prompt: &'a str,
+    ) -> BoxFuture<'a, Result<(PartitionResponse, TokenUsage), ApiError>> {
+        Box::pin(OpenAiClient::suggest_parts(self, prompt))
+    }
+
+    fn suggest_follow_ups<'a>(
+        &'a self,
+        prompt: &'a str,
+    ) -> BoxFuture<'a, Result<(FollowUpResponse, TokenUsage), ApiError>> {
+        Box::pin(OpenAiClient::suggest_follow_ups(self, prompt))
+    }
+
+    fn summarize_history<'a>(
+        &'a self,
+        prompt: &'a str,
+    ) -> BoxFuture<'a, Result<(HistorySummaryResponse, TokenUsage), ApiError>> {
+        Box::pin(OpenAiClient::summarize_history(self, prompt))
+    }
+}
@@ -1,5 +1,19 @@
 mod client;
+mod llm;
+mod openai;
+mod prompts;
+mod retry;
+mod settings;
+mod tools;
 mod types;
 
-pub use client::{ClaudeClient, ClientStreamEvent};
+pub use client::{
+    ClaudeClient, ClientConfig, ClientStreamEvent, MAX_FOLLOW_UPS, chat_context,
+    context_with_summary, estimate_tokens, fit_chat_history, follow_up_prompt,
+    history_summary_prompt, whole_change_context,
+};
+pub use llm::{LlmClient, connect};
+pub use prompts::{Persona, PromptOverride, PromptSettings, PromptText};
+pub use retry::RetryNotice;
+pub use settings::{ApiSettings, ChatContext, Pricing, Provider};
 pub use types::*;
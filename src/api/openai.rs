@@ -0,0 +1,352 @@
+//! Client for OpenAI's Chat Completions API, which OpenAI, Azure OpenAI,
+//! vLLM, Ollama, and most LLM gateways implement.
+
+use futures::StreamExt;
+use serde::de::DeserializeOwned;
+use serde_json::{Value, json};
+use tokio::sync::mpsc;
+
+use crate::api::client::{CHARS_PER_TOKEN, StepExtractor, chat_messages};
+use crate::api::retry::{RetryNotice, send_with_retry};
+use crate::api::settings::ApiSettings;
+use crate::api::types::{
+    ApiError, FOLLOW_UPS_SYSTEM_PROMPT, FOLLOW_UPS_TOOL, FollowUpResponse, HistorySummaryResponse,
+    PARTITION_DIFF_TOOL, PARTITION_SYSTEM_PROMPT, PartitionResponse, RECHUNK_STEP_TOOL,
+    RechunkResponse, SUMMARIZE_HISTORY_SYSTEM_PROMPT, SUMMARIZE_HISTORY_TOOL, TokenUsage,
+    chat_system_prompt, rechunk_system_prompt, walkthrough_system_prompt,
+};
+use crate::api::{ClientConfig, ClientStreamEvent};
+use crate::model::{Message, ReviewMode};
+
+pub struct OpenAiClient {
+    api_key: String,
+    url: String,
+    model: String,
+    settings: ApiSettings,
+    client: reqwest::Client,
+    retry_notices: Option<mpsc::UnboundedSender<RetryNotice>>,
+}
+
+impl OpenAiClient {
+    pub fn new(config: ClientConfig) -> Self {
+        Self {
+            client: config.http_client(),
+            url: format!("{}/chat/completions", config.base_url()),
+            model: config.model().to_string(),
+            api_key: config.api_key,
+            settings: config.settings,
+            retry_notices: config.retry_notices,
+        }
+    }
+
+    pub fn model(&self) -> &str {
+        &self.model
+    }
+
+    fn request_body(&self, max_tokens: u32, messages: Vec<Value>) -> Value {
+        let mut body = json!({
+            "model": self.model(),
+            "max_tokens": max_tokens,
+            "messages": messages,
+        });
+        if let Some(temperature) = self.settings.temperature {
+            body["temperature"] = json!(temperature);
+        }
+        body
+    }
+
+    /// Posts a request, retrying while rate limited.
+    async fn send(&self, body: &Value) -> Result<reqwest::Response, ApiError> {
+        send_with_retry(
+            || {
+                let request = self
+                    .client
+                    .post(&self.url)
+                    .header("content-type", "application/json");
+                let request = if self.api_key.is_empty() {
+                    request
+                } else {
+                    request.bearer_auth(&self.api_key)
+                };
+                request.json(body)
+            },
+            self.settings.max_attempts,
+            self.retry_notices.as_ref(),
+        )
+        .await
+    }
+
+    /// Sends a request with streaming on.
+    async fn send_streaming(&self, mut body: Value) -> Result<reqwest::Response, ApiError> {
+        body["stream"] = json!(true);
+        body["stream_options"] = json!({"include_usage": true});
+        self.send(&body).await
+    }
+
+    /// Forces a call to the function described by an Anthropic-style tool
+    /// schema and parses its arguments.
+    async fn function_call_request<T: DeserializeOwned>(
+        &self,
+        tool_schema: &str,
+        system_prompt: &str,
+        user_prompt: &str,
+    ) -> Result<(T, TokenUsage), ApiError> {
+        let (name, tool) = function_tool(tool_schema)?;
+        let mut body = self.request_body(
+            self.settings.generation_max_tokens,
+            vec![
+                json!({"role": "system", "content": system_prompt}),
+                json!({"role": "user", "content": user_prompt}),
+            ],
+        );
+        body["tools"] = json!([tool]);
+        body["tool_choice"] = json!({"type": "function", "function": {"name": name}});
+
+        let response: Value = self
+            .send(&body)
+            .await?
+            .json()
+            .await
+            .map_err(|e| ApiError::Parse(format!("failed to parse response: {}", e)))?;
+
+        let usage = response.get("usage").map(parse_usage).unwrap_or_default();
+        let arguments = response["choices"][0]["message"]["tool_calls"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .find(|call| call["function"]["name"] == name.as_str())
+            .and_then(|call| call["function"]["arguments"].as_str())
+            .ok_or_else(|| ApiError::Parse("no tool call found in response".to_string()))?;
+        let parsed = serde_json::from_str(arguments)
+            .map_err(|e| ApiError::Parse(format!("failed to parse tool input: {}", e)))?;
+        Ok((parsed, usage))
+    }
+
+    pub async fn rechunk_step(
+        &self,
+        prompt: &str,
+        mode: ReviewMode,
+    ) -> Result<(RechunkResponse, TokenUsage), ApiError> {
+        self.function_call_request(RECHUNK_STEP_TOOL, rechunk_system_prompt(mode), prompt)
+            .await
+    }
+
+    pub async fn suggest_parts(
+        &self,
+        prompt: &str,
+    ) -> Result<(PartitionResponse, TokenUsage), ApiError> {
+        self.function_call_request(PARTITION_DIFF_TOOL, PARTITION_SYSTEM_PROMPT, prompt)
+            .await
+    }
+
+    pub async fn suggest_follow_ups(
+        &self,
+        prompt: &str,
+    ) -> Result<(FollowUpResponse, TokenUsage), ApiError> {
+        self.function_call_request(FOLLOW_UPS_TOOL, FOLLOW_UPS_SYSTEM_PROMPT, prompt)
+            .await
+    }
+
+    pub async fn summarize_history(
+        &self,
+        prompt: &str,
+    ) -> Result<(HistorySummaryResponse, TokenUsage), ApiError> {
+        self.function_call_request(
+            SUMMARIZE_HISTORY_TOOL,
+            SUMMARIZE_HISTORY_SYSTEM_PROMPT,
+            prompt,
+        )
+        .await
+    }
+
+    /// Streams the walkthrough as a JSON object, sending complete steps as
+    /// they're detected.
+    pub async fn generate_walkthrough_streaming(
+        &self,
+        diff_prompt: &str,
+        mode: ReviewMode,
+        event_tx: mpsc::Sender<ClientStreamEvent>,
+    ) -> Result<TokenUsage, ApiError> {
+        let system = self
+            .settings
+            .prompts
+            .walkthrough_prompt(walkthrough_system_prompt(mode))?;
+        let mut body = self.request_body(
+            self.settings.generation_max_tokens,
+            vec![
+                json!({"role": "system", "content": system}),
+                json!({"role": "user", "content": diff_prompt}),
+            ],
+        );
+        body["response_format"] = json!({"type": "json_object"});
+
+        let mut stream = self.send_streaming(body).await?.bytes_stream();
+        let mut buffer = String::new();
+        let mut json_buffer = String::new();
+        let mut step_extractor = StepExtractor::new();
+        let mut usage = TokenUsage::default();
+
+        while let Some(chunk_result) = stream.next().await {
+            buffer.push_str(&String::from_utf8_lossy(&chunk_result?));
+            for event in take_events(&mut buffer) {
+                let (text, event_usage) = parse_sse_chunk(&event);
+                usage = event_usage.unwrap_or(usage);
+                let Some(text) = text else { continue };
+                json_buffer.push_str(&text);
+                let tokens = (json_buffer.len() / CHARS_PER_TOKEN) as u32;
+                let _ = event_tx.send(ClientStreamEvent::Progress(tokens)).await;
+                for step in step_extractor.feed(&json_buffer) {
+                    let _ = event_tx.send(ClientStreamEvent::StepComplete(step)).await;
+                }
+            }
+        }
+
+        if usage.output_tokens == 0 {
+            usage.output_tokens = (json_buffer.len() / CHARS_PER_TOKEN) as u32;
+        }
+        let _ = event_tx
+            .send(ClientStreamEvent::Progress(usage.output_tokens))
+            .await;
+        Ok(usage)
+    }
+
+    /// Chat about a step with streaming, sending text chunks as they arrive.
+    pub async fn chat_streaming(
+        &self,
+        context: &str,
+        messages: &[Message],
+        mode: ReviewMode,
+        chunk_tx: mpsc::Sender<String>,
+    ) -> Result<TokenUsage, ApiError> {
+        let system = self
+            .settings
+            .prompts
+            .chat_prompt(chat_system_prompt(mode))?;
+        let api_messages = std::iter::once(json!({
+            "role": "system",
+            "content": system
+        }))
+        .chain(chat_messages(context, messages))
+        .collect();
+        let body = self.request_body(self.settings.chat_max_tokens, api_messages);
+
+        let mut stream = self.send_streaming(body).await?.bytes_stream();
+        let mut buffer = String::new();
+        let mut usage = TokenUsage::default();
+
+        while let Some(chunk_result) = stream.next().await {
+            buffer.push_str(&String::from_utf8_lossy(&chunk_result?));
+            for event in take_events(&mut buffer) {
+                let (text, event_usage) = parse_sse_chunk(&event);
+                usage = event_usage.unwrap_or(usage);
+                if let Some(text) = text {
+                    let _ = chunk_tx.send(text).await;
+                }
+            }
+        }
+
+        Ok(usage)
+    }
+}
+
+/// Removes the complete server-sent events from the front of `buffer`.
+fn take_events(buffer: &mut String) -> Vec<String> {
+    if buffer.contains('\r') {
+        *buffer = buffer.replace("\r\n", "\n");
+    }
+    let mut events = Vec::new();
+    while let Some(event_end) = buffer.find("\n\n") {
+        events.push(buffer[..event_end].to_string());
+        buffer.drain(..event_end + 2);
+    }
+    events
+}
+
+/// Converts an Anthropic tool schema (`name`, `description`,
+/// `input_schema`) into an OpenAI function tool, returning its name too.
+fn function_tool(tool_schema: &str) -> Result<(String, Value), ApiError> {
+    let tool: Value = serde_json::from_str(tool_schema)
+        .map_err(|e| ApiError::Parse(format!("invalid tool schema: {}", e)))?;
+    let name = tool["name"]
+        .as_str()
+        .ok_or_else(|| ApiError::Parse("tool schema has no name".to_string()))?
+        .to_string();
+    let function = json!({
+        "type": "function",
+        "function": {
+            "name": name,
+            "description": tool["description"],
+            "parameters": tool["input_schema"],
+        }
+    });
+    Ok((name, function))
+}
+
+/// Text delta and usage from one server-sent event of a streamed completion.
+fn parse_sse_chunk(event: &str) -> (Option<String>, Option<TokenUsage>) {
+    let mut text = None;
+    let mut usage = None;
+    for line in event.lines() {
+        let Some(data) = line.strip_prefix("data:").map(str::trim) else {
+            continue;
+        };
+        let Ok(json) = serde_json::from_str::<Value>(data) else {
+            continue;
+        };
+        if let Some(content) = json["choices"][0]["delta"]["content"].as_str()
+            && !content.is_empty()
+        {
+            text.get_or_insert_with(String::new).push_str(content);
+        }
+        if let Some(u) = json.get("usage").filter(|u| !u.is_null()) {
+            usage = Some(parse_usage(u));
+        }
+    }
+    (text, usage)
+}
+
+fn parse_usage(usage: &Value) -> TokenUsage {
+    TokenUsage {
+        input_tokens: usage["prompt_tokens"].as_u64().unwrap_or(0) as u32,
+        output_tokens: usage["completion_tokens"].as_u64().unwrap_or(0) as u32,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_sse_chunk() {
+        let (text, usage) = parse_sse_chunk(
+            r#"data: {"choices":[{"index":0,"delta":{"content":"{\"steps\": ["}}]}"#,
+        );
+        assert_eq!(text.as_deref(), Some("{\"steps\": ["));
+        assert!(usage.is_none());
+
+        let (text, usage) = parse_sse_chunk(
+            "data: {\"choices\":[],\"usage\":{\"prompt_tokens\":120,\"completion_tokens\":30}}",
+        );
+        assert!(text.is_none());
+        assert_eq!(usage.unwrap().output_tokens, 30);
+
+        let (text, usage) = parse_sse_chunk("data: [DONE]");
+        assert!(text.is_none() && usage.is_none());
+    }
+
+    #[test]
+    fn test_take_events_keeps_partial_event() {
+        let mut buffer = "data: {}\r\n\r\ndata: [DONE]\n\ndata: {\"choi".to_string();
+        assert_eq!(take_events(&mut buffer), ["data: {}", "data: [DONE]"]);
+        assert_eq!(buffer, "data: {\"choi");
+    }
+
+    #[test]
+    fn test_function_tool_from_anthropic_schema() {
+        let (name, tool) = function_tool(RECHUNK_STEP_TOOL).unwrap();
+        assert_eq!(name, "rechunk_step");
+        assert_eq!(tool["type"], "function");
+        assert_eq!(tool["function"]["parameters"]["type"], "object");
+        assert!(tool["function"]["parameters"]["properties"]["steps"].is_object());
+    }
+}
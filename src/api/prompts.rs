@@ -0,0 +1,383 @@
+//! Changes to the built-in system prompts, for house review conventions.
+//!
+//! These are `api.prompts` in ~/.docent/settings.json, or `prompts` in a
+//! project's .docent.json. Each prompt can be replaced, added to, or both,
+//! with text given inline or read from a file. The text can use `{{name}}`
+//! variables: `repo` and `pr_title` are filled in by docent, and more can be
+//! set under `variables`.
+//!
+//! A review persona adds emphasis to both prompts, such as security issues.
+//! The presets are in `types`; `personas` adds more.
+
+use std::collections::BTreeMap;
+use std::path::{Component, Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::api::types::{ApiError, PERSONA_PRESETS};
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PromptSettings {
+    /// The prompt walkthroughs are generated with
+    #[serde(default, skip_serializing_if = "PromptOverride::is_empty")]
+    pub walkthrough: PromptOverride,
+    /// The prompt chat questions are answered with
+    #[serde(default, skip_serializing_if = "PromptOverride::is_empty")]
+    pub chat: PromptOverride,
+    /// Values for `{{name}}` in the prompts
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub variables: BTreeMap<String, String>,
+    /// The review persona; "general" when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub persona: Option<String>,
+    /// Personas beyond the presets, or in place of one of the same name
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub personas: BTreeMap<String, Persona>,
+}
+
+/// Text replacing a built-in prompt, and text added after it.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PromptOverride {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub replace: Option<PromptText>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub append: Option<PromptText>,
+}
+
+/// Text a review persona adds to each prompt.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Persona {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub walkthrough: Option<PromptText>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub chat: Option<PromptText>,
+}
+
+/// Prompt text given inline, or as `{"file": "path"}` to read it from a file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum PromptText {
+    Inline(String),
+    File { file: PathBuf },
+}
+
+impl PromptSettings {
+    pub fn is_empty(&self) -> bool {
+        self.walkthrough.is_empty()
+            && self.chat.is_empty()
+            && self.variables.is_empty()
+            && self.persona.is_none()
+            && self.personas.is_empty()
+    }
+
+    /// The walkthrough prompt, `base` unless overridden.
+    pub fn walkthrough_prompt(&self, base: &str) -> Result<String, ApiError> {
+        let persona = self.persona()?;
+        self.apply(&self.walkthrough, base, persona.walkthrough.as_ref())
+    }
+
+    /// The chat prompt, `base` unless overridden.
+    pub fn chat_prompt(&self, base: &str) -> Result<String, ApiError> {
+        let persona = self.persona()?;
+        self.apply(&self.chat, base, persona.chat.as_ref())
+    }
+
+    pub fn persona_name(&self) -> &str {
+        self.persona.as_deref().unwrap_or("general")
+    }
+
+    /// Every persona that can be chosen: the presets, then the ones from
+    /// settings.
+    pub fn persona_names(&self) -> Vec<String> {
+        let presets = PERSONA_PRESETS.iter().map(|preset| preset.name.to_string());
+        let added = self
+            .personas
+            .keys()
+            .filter(|name| !PERSONA_PRESETS.iter().any(|preset| preset.name == *name))
+            .cloned();
+        presets.chain(added).collect()
+    }
+
+    /// The chosen persona's additions.
+    fn persona(&self) -> Result<Persona, ApiError> {
+        let name = self.persona_name();
+        if let Some(persona) = self.personas.get(name) {
+            return Ok(persona.clone());
+        }
+        let preset = PERSONA_PRESETS
+            .iter()
+            .find(|preset| preset.name == name)
+            .ok_or_else(|| ApiError::UnknownPersona(name.to_string()))?;
+        let text = |text: &str| (!text.is_empty()).then(|| PromptText::Inline(text.to_string()));
+        Ok(Persona {
+            walkthrough: text(preset.walkthrough),
+            chat: text(preset.chat),
+        })
+    }
+
+    /// Sets a variable the settings leave unset.
+    pub fn set_default(&mut self, name: &str, value: &str) {
+        self.variables
+            .entry(name.to_string())
+            .or_insert_with(|| value.to_string());
+    }
+
+    /// These settings with `other`'s overrides in place of the ones it
+    /// changes, and its variables added.
+    pub fn overridden_by(&self, other: &PromptSettings) -> PromptSettings {
+        let pick = |ours: &PromptOverride, theirs: &PromptOverride| {
+            if theirs.is_empty() { ours } else { theirs }.clone()
+        };
+        let mut variables = self.variables.clone();
+        variables.extend(other.variables.clone());
+        let mut personas = self.personas.clone();
+        personas.extend(other.personas.clone());
+        PromptSettings {
+            walkthrough: pick(&self.walkthrough, &other.walkthrough),
+            chat: pick(&self.chat, &other.chat),
+            variables,
+            persona: other.persona.clone().or_else(|| self.persona.clone()),
+            personas,
+        }
+    }
+
+    /// Resolves file paths against `root`, for the settings of a project's
+    /// .docent.json. Its files must be inside the repository: a path that is
+    /// absolute, starts with `~`, or leads out of `root` is refused, so the
+    /// repository under review can't have other files sent as a prompt.
+    pub fn within_project(mut self, root: &Path) -> Result<Self, ApiError> {
+        let root = root.canonicalize().unwrap_or_else(|_| root.to_path_buf());
+        let overrides = [&mut self.walkthrough, &mut self.chat]
+            .into_iter()
+            .flat_map(|over| [&mut over.replace, &mut over.append]);
+        let personas = self
+            .personas
+            .values_mut()
+            .flat_map(|persona| [&mut persona.walkthrough, &mut persona.chat]);
+        for text in overrides.chain(personas).flatten() {
+            if let PromptText::File { file } = text {
+                *file = project_file(&root, file)?;
+            }
+        }
+        Ok(self)
+    }
+
+    /// The prompt: `base` or its replacement, then the persona's and the
+    /// settings' additions.
+    fn apply(
+        &self,
+        over: &PromptOverride,
+        base: &str,
+        persona: Option<&PromptText>,
+    ) -> Result<String, ApiError> {
+        if over.is_empty() && persona.is_none() {
+            return Ok(base.to_string());
+        }
+        let mut prompt = match &over.replace {
+            Some(text) => text.read()?,
+            None => base.to_string(),
+        };
+        for text in persona.into_iter().chain(&over.append) {
+            prompt.push_str("\n\n");
+            prompt.push_str(&text.read()?);
+        }
+        Ok(self.fill(&prompt))
+    }
+
+    /// `text` with each `{{name}}` replaced by the variable's value. Names
+    /// without a value are left as they are.
+    fn fill(&self, text: &str) -> String {
+        let mut filled = String::with_capacity(text.len());
+        let mut rest = text;
+        while let Some(start) = rest.find("{{") {
+            let Some(len) = rest[start..].find("}}") else {
+                break;
+            };
+            let name = rest[start + 2..start + len].trim();
+            filled.push_str(&rest[..start]);
+            match self.variables.get(name) {
+                Some(value) => filled.push_str(value),
+                None => filled.push_str(&rest[start..start + len + 2]),
+            }
+            rest = &rest[start + len + 2..];
+        }
+        filled.push_str(rest);
+        filled
+    }
+}
+
+/// `file` under the canonical `root`, if it stays there. A file that doesn't
+/// exist is left for `PromptText::read` to report.
+fn project_file(root: &Path, file: &Path) -> Result<PathBuf, ApiError> {
+    let outside = || ApiError::PromptFile(format!("{}: outside the repository", file.display()));
+    if !file.is_relative() || file.starts_with("~") {
+        return Err(outside());
+    }
+    let joined = root.join(file);
+    match joined.canonicalize() {
+        Ok(resolved) if resolved.starts_with(root) => Ok(resolved),
+        Ok(_) => Err(outside()),
+        Err(_) if file.components().any(|c| c == Component::ParentDir) => Err(outside()),
+        Err(_) => Ok(joined),
+    }
+}
+
+impl PromptOverride {
+    pub fn is_empty(&self) -> bool {
+        self.replace.is_none() && self.append.is_none()
+    }
+}
+
+impl PromptText {
+    fn read(&self) -> Result<String, ApiError> {
+        match self {
+            Self::Inline(text) => Ok(text.clone()),
+            Self::File { file } => {
+                let path = match file.strip_prefix("~") {
+                    Ok(rest) => dirs::home_dir().map_or_else(|| file.clone(), |h| h.join(rest)),
+                    Err(_) => file.clone(),
+                };
+                std::fs::read_to_string(&path)
+                    .map(|text| text.trim_end().to_string())
+                    .map_err(|e| ApiError::PromptFile(format!("{}: {}", path.display(), e)))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settings(json: &str) -> PromptSettings {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn test_replace_append_and_fill_variables() {
+        let mut prompts = settings(
+            r#"{
+                "walkthrough": {"append": "Follow the {{team}} conventions for {{repo}}."},
+                "chat": {"replace": "Answer tersely.", "append": "PR: {{pr_title}} {{unknown}}"},
+                "variables": {"team": "payments"}
+            }"#,
+        );
+        prompts.set_default("repo", "acme/api");
+        prompts.set_default("team", "ignored");
+
+        assert_eq!(
+            prompts.walkthrough_prompt("Built in.").unwrap(),
+            "Built in.\n\nFollow the payments conventions for acme/api."
+        );
+        assert_eq!(
+            prompts.chat_prompt("Built in.").unwrap(),
+            "Answer tersely.\n\nPR: {{pr_title}} {{unknown}}"
+        );
+        assert_eq!(
+            PromptSettings::default().chat_prompt("Built in.").unwrap(),
+            "Built in."
+        );
+    }
+
+    #[test]
+    fn test_prompt_files_and_project_overrides() {
+        let dir = std::env::temp_dir().join(format!("docent-prompts-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("review.md"), "House rules.\n").unwrap();
+
+        let user = settings(r#"{"chat": {"append": "Mine."}, "variables": {"a": "1", "b": "2"}}"#);
+        let project = settings(
+            r#"{"walkthrough": {"replace": {"file": "review.md"}}, "variables": {"b": "3"}}"#,
+        )
+        .within_project(&dir)
+        .unwrap();
+        let merged = user.overridden_by(&project);
+        assert_eq!(
+            merged.walkthrough_prompt("Built in.").unwrap(),
+            "House rules."
+        );
+        assert_eq!(
+            merged.chat_prompt("Built in.").unwrap(),
+            "Built in.\n\nMine."
+        );
+        assert_eq!(merged.variables["a"], "1");
+        assert_eq!(merged.variables["b"], "3");
+
+        assert_eq!(merged.persona_name(), "general");
+
+        let missing = settings(r#"{"chat": {"append": {"file": "/nonexistent/docent.md"}}}"#);
+        assert!(matches!(
+            missing.chat_prompt(""),
+            Err(ApiError::PromptFile(_))
+        ));
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_project_prompt_files_stay_in_the_repository() {
+        let dir = std::env::temp_dir().join(format!("docent-project-{}", std::process::id()));
+        let repo = dir.join("repo");
+        std::fs::create_dir_all(&repo).unwrap();
+        std::fs::write(dir.join("secret"), "key").unwrap();
+
+        for file in [
+            "/etc/passwd",
+            "~/.ssh/id_ed25519",
+            "../secret",
+            "docs/../../secret",
+        ] {
+            let chat = format!(r#"{{"chat": {{"append": {{"file": "{file}"}}}}}}"#);
+            let persona =
+                format!(r#"{{"personas": {{"x": {{"walkthrough": {{"file": "{file}"}}}}}}}}"#);
+            for json in [chat, persona] {
+                let project = settings(&json).within_project(&repo);
+                assert!(matches!(project, Err(ApiError::PromptFile(_))), "{json}");
+            }
+        }
+        #[cfg(unix)]
+        {
+            std::os::unix::fs::symlink(dir.join("secret"), repo.join("link.md")).unwrap();
+            let project = settings(r#"{"chat": {"append": {"file": "link.md"}}}"#);
+            assert!(project.within_project(&repo).is_err());
+        }
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_personas() {
+        let mut prompts = settings(
+            r#"{
+                "chat": {"append": "Be brief."},
+                "personas": {"a11y": {"walkthrough": "Check {{what}}."}},
+                "variables": {"what": "contrast"}
+            }"#,
+        );
+        assert_eq!(
+            prompts.persona_names(),
+            ["general", "security", "perf", "api", "a11y"]
+        );
+
+        prompts.persona = Some("security".to_string());
+        let walkthrough = prompts.walkthrough_prompt("Built in.").unwrap();
+        assert!(walkthrough.starts_with("Built in.\n\nReview this change as a security reviewer."));
+        let chat = prompts.chat_prompt("Built in.").unwrap();
+        assert!(chat.starts_with("Built in.\n\nAnswer as a security reviewer"));
+        assert!(chat.ends_with("\n\nBe brief."));
+
+        prompts.persona = Some("a11y".to_string());
+        assert_eq!(
+            prompts.walkthrough_prompt("Built in.").unwrap(),
+            "Built in.\n\nCheck contrast."
+        );
+        assert_eq!(
+            prompts.chat_prompt("Built in.").unwrap(),
+            "Built in.\n\nBe brief."
+        );
+
+        prompts.persona = Some("nobody".to_string());
+        assert!(matches!(
+            prompts.chat_prompt(""),
+            Err(ApiError::UnknownPersona(_))
+        ));
+    }
+}
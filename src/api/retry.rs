@@ -0,0 +1,135 @@
+//! Retrying requests the API turns away because of rate limits or overload.
+
+use std::time::Duration;
+
+use tokio::sync::mpsc;
+
+use crate::api::types::ApiError;
+
+/// Wait before the first retry when the API doesn't say how long to wait.
+const BASE_DELAY: Duration = Duration::from_secs(2);
+
+/// Longest wait between attempts, whatever `retry-after` asks for.
+const MAX_DELAY: Duration = Duration::from_secs(60);
+
+/// A request was rate limited or the API was overloaded, and will be sent
+/// again after `delay`.
+#[derive(Debug, Clone)]
+pub struct RetryNotice {
+    pub status: u16,
+    pub delay: Duration,
+    /// The attempt about to be made, counting from 1
+    pub attempt: u32,
+    pub max_attempts: u32,
+}
+
+impl RetryNotice {
+    pub fn reason(&self) -> &'static str {
+        if self.status == 429 {
+            "Rate limited"
+        } else {
+            "API overloaded"
+        }
+    }
+
+    pub fn message(&self) -> String {
+        format!(
+            "{}, retrying in {}s (attempt {}/{})…",
+            self.reason(),
+            self.delay.as_secs(),
+            self.attempt,
+            self.max_attempts
+        )
+    }
+}
+
+/// Whether a response status means the same request may succeed later:
+/// 429 (rate limited) or 529 (overloaded).
+fn is_retryable(status: u16) -> bool {
+    matches!(status, 429 | 529)
+}
+
+/// Wait before retry number `retry` (from 1): the `retry-after` header's
+/// seconds when given, otherwise exponential backoff.
+fn retry_delay(retry_after: Option<&str>, retry: u32) -> Duration {
+    let delay = retry_after
+        .and_then(|value| value.trim().parse::<u64>().ok())
+        .map(Duration::from_secs)
+        .unwrap_or_else(|| BASE_DELAY.saturating_mul(2u32.saturating_pow(retry - 1)));
+    delay.min(MAX_DELAY)
+}
+
+/// Sends the request built by `request`, trying again with backoff while the
+/// API answers 429 or 529, up to `max_attempts` in all. Other failures are
+/// returned right away. Each wait is announced on `notices`.
+pub(super) async fn send_with_retry(
+    request: impl Fn() -> reqwest::RequestBuilder,
+    max_attempts: u32,
+    notices: Option<&mpsc::UnboundedSender<RetryNotice>>,
+) -> Result<reqwest::Response, ApiError> {
+    let mut attempt = 1;
+    loop {
+        let response = request().send().await?;
+        let status = response.status();
+        if status.is_success() {
+            return Ok(response);
+        }
+
+        if attempt < max_attempts && is_retryable(status.as_u16()) {
+            let retry_after = response
+                .headers()
+                .get("retry-after")
+                .and_then(|value| value.to_str().ok());
+            let delay = retry_delay(retry_after, attempt);
+            attempt += 1;
+            if let Some(notices) = notices {
+                let _ = notices.send(RetryNotice {
+                    status: status.as_u16(),
+                    delay,
+                    attempt,
+                    max_attempts,
+                });
+            }
+            tokio::time::sleep(delay).await;
+            continue;
+        }
+
+        let body = response.text().await.unwrap_or_default();
+        return Err(ApiError::ApiResponse {
+            status: status.as_u16(),
+            message: body,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backoff_doubles_up_to_the_cap() {
+        assert_eq!(retry_delay(None, 1), Duration::from_secs(2));
+        assert_eq!(retry_delay(None, 2), Duration::from_secs(4));
+        assert_eq!(retry_delay(None, 3), Duration::from_secs(8));
+        assert_eq!(retry_delay(None, 10), MAX_DELAY);
+    }
+
+    #[test]
+    fn retry_after_header_wins() {
+        assert_eq!(retry_delay(Some("7"), 3), Duration::from_secs(7));
+        assert_eq!(retry_delay(Some("600"), 1), MAX_DELAY);
+        // HTTP dates aren't parsed; fall back to backoff
+        assert_eq!(
+            retry_delay(Some("Wed, 21 Oct 2015 07:28:00 GMT"), 1),
+            Duration::from_secs(2)
+        );
+    }
+
+    #[test]
+    fn only_rate_limits_and_overload_retry() {
+        assert!(is_retryable(429));
+        assert!(is_retryable(529));
+        assert!(!is_retryable(500));
+        assert!(!is_retryable(401));
+    }
+}
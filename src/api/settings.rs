@@ -0,0 +1,192 @@
+//! Which provider and model to call, and how.
+//!
+//! These are the `provider` and `api` sections of ~/.docent/settings.json,
+//! and `chat.context` for how much of a walkthrough goes with a question.
+//! Embedders build them directly for a `ClientConfig`.
+
+use serde::{Deserialize, Serialize};
+
+use crate::api::prompts::PromptSettings;
+
+/// The model provider used for walkthroughs and chat.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum Provider {
+    #[default]
+    Anthropic,
+    /// Any endpoint implementing OpenAI's Chat Completions API
+    #[serde(rename = "openai")]
+    #[value(name = "openai")]
+    OpenAi,
+    /// A local Ollama server
+    Ollama,
+}
+
+impl Provider {
+    pub const ALL: [Provider; 3] = [Provider::Anthropic, Provider::OpenAi, Provider::Ollama];
+
+    pub fn display_name(self) -> &'static str {
+        match self {
+            Provider::Anthropic => "Anthropic (Claude)",
+            Provider::OpenAi => "OpenAI-compatible",
+            Provider::Ollama => "Ollama (local)",
+        }
+    }
+
+    /// Environment variable checked for this provider's API key.
+    pub fn env_var(self) -> &'static str {
+        match self {
+            Provider::Anthropic => "ANTHROPIC_API_KEY",
+            Provider::OpenAi => "OPENAI_API_KEY",
+            Provider::Ollama => "OLLAMA_API_KEY",
+        }
+    }
+
+    /// Whether requests fail without an API key. A local Ollama server takes
+    /// none, though a key is still sent if one is configured (e.g. for a proxy).
+    pub fn requires_api_key(self) -> bool {
+        !matches!(self, Provider::Ollama)
+    }
+
+    /// Environment variable checked for the API base URL when settings don't set one.
+    pub fn base_url_env_var(self) -> Option<&'static str> {
+        match self {
+            Provider::Anthropic => Some("ANTHROPIC_BASE_URL"),
+            Provider::OpenAi => Some("OPENAI_BASE_URL"),
+            Provider::Ollama => None,
+        }
+    }
+
+    pub fn default_base_url(self) -> &'static str {
+        match self {
+            Provider::Anthropic => "https://api.anthropic.com/v1",
+            Provider::OpenAi => "https://api.openai.com/v1",
+            Provider::Ollama => "http://localhost:11434/v1",
+        }
+    }
+
+    /// Model used when `api.model` isn't set.
+    pub fn default_model(self) -> &'static str {
+        match self {
+            Provider::Anthropic => "claude-sonnet-4-20250514",
+            Provider::OpenAi => "gpt-4o",
+            Provider::Ollama => "qwen2.5-coder",
+        }
+    }
+
+    pub fn key_placeholder(self) -> &'static str {
+        match self {
+            Provider::Anthropic => "sk-ant-...",
+            Provider::OpenAi => "sk-...",
+            Provider::Ollama => "(optional)",
+        }
+    }
+}
+
+/// How much surrounding context is sent with chat questions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ChatContext {
+    /// The current step's summary and hunks only
+    Step,
+    /// The current step plus the list of all step titles
+    #[default]
+    Overview,
+    /// The overview plus the previous and next steps' hunks
+    Neighbors,
+    /// The overview plus the lines around each of the current step's hunks
+    Surrounding,
+    /// The overview plus the full files the current step touches
+    FullFile,
+}
+
+impl ChatContext {
+    /// The next level, wrapping around, for switching between questions.
+    pub fn next(self) -> Self {
+        match self {
+            Self::Step => Self::Overview,
+            Self::Overview => Self::Neighbors,
+            Self::Neighbors => Self::Surrounding,
+            Self::Surrounding => Self::FullFile,
+            Self::FullFile => Self::Step,
+        }
+    }
+}
+
+/// Model and request parameters for API calls.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApiSettings {
+    /// Model ID, e.g. "claude-3-5-haiku-latest"; the provider's default when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    /// API base URL, e.g. "http://localhost:8000/v1" for a vLLM server
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base_url: Option<String>,
+    /// Output limit for walkthrough generation, splitting steps, and partitioning
+    #[serde(default = "default_generation_max_tokens")]
+    pub generation_max_tokens: u32,
+    /// Output limit for each chat response
+    #[serde(default = "default_chat_max_tokens")]
+    pub chat_max_tokens: u32,
+    /// Sampling temperature; the API's default when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<f32>,
+    /// Per-request timeout in seconds, including streaming; none when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_secs: Option<u64>,
+    /// Tries per request, counting the first, while the API answers 429 (rate
+    /// limited) or 529 (overloaded); 1 turns retrying off
+    #[serde(default = "default_max_attempts")]
+    pub max_attempts: u32,
+    /// Let the model read files and search the repository while answering
+    /// chat questions (Anthropic only)
+    #[serde(default = "default_chat_tools")]
+    pub chat_tools: bool,
+    /// Prices for the usage cost estimate, for models docent doesn't know
+    /// prices for or negotiated rates
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pricing: Option<Pricing>,
+    /// Changes to the walkthrough and chat system prompts
+    #[serde(default, skip_serializing_if = "PromptSettings::is_empty")]
+    pub prompts: PromptSettings,
+}
+
+/// USD per million tokens.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct Pricing {
+    pub input: f64,
+    pub output: f64,
+}
+
+impl Default for ApiSettings {
+    fn default() -> Self {
+        Self {
+            model: None,
+            base_url: None,
+            generation_max_tokens: default_generation_max_tokens(),
+            chat_max_tokens: default_chat_max_tokens(),
+            temperature: None,
+            timeout_secs: None,
+            max_attempts: default_max_attempts(),
+            chat_tools: true,
+            pricing: None,
+            prompts: PromptSettings::default(),
+        }
+    }
+}
+
+fn default_generation_max_tokens() -> u32 {
+    4096
+}
+
+fn default_chat_max_tokens() -> u32 {
+    1024
+}
+
+fn default_max_attempts() -> u32 {
+    4
+}
+
+fn default_chat_tools() -> bool {
+    true
+}
@@ -0,0 +1,303 @@
+//! Repository tools the model can call while answering chat questions.
+//!
+//! Tools run locally against the working tree. Paths are confined to the
+//! repository's tracked files, and output is capped so a single call can't
+//! flood the context.
+
+use std::path::{Component, Path, PathBuf};
+
+use serde_json::{Value, json};
+use tokio::process::Command;
+
+/// Tool-use rounds per chat answer before the model must answer with what it has.
+pub const MAX_TOOL_ROUNDS: usize = 6;
+
+/// Lines returned by one `read_file` call.
+const MAX_READ_LINES: usize = 400;
+
+/// Matching lines returned by one `grep` call.
+const MAX_GREP_LINES: usize = 100;
+
+/// Tool definitions in the Messages API format.
+pub fn tool_definitions() -> Value {
+    json!([
+        {
+            "name": "read_file",
+            "description": "Read a file from the repository's working tree. Returns numbered \
+                lines. Use start_line and end_line to read part of a long file.",
+            "input_schema": {
+                "type": "object",
+                "properties": {
+                    "path": {
+                        "type": "string",
+                        "description": "Path relative to the repository root"
+                    },
+                    "start_line": {
+                        "type": "integer",
+                        "description": "First line to read, counting from 1"
+                    },
+                    "end_line": {
+                        "type": "integer",
+                        "description": "Last line to read, inclusive"
+                    }
+                },
+                "required": ["path"]
+            }
+        },
+        {
+            "name": "grep",
+            "description": "Search tracked files in the repository for a regular expression. \
+                Returns matching lines as path:line:text.",
+            "input_schema": {
+                "type": "object",
+                "properties": {
+                    "pattern": {
+                        "type": "string",
+                        "description": "Extended regular expression to search for"
+                    },
+                    "path": {
+                        "type": "string",
+                        "description": "Directory or file to limit the search to"
+                    }
+                },
+                "required": ["pattern"]
+            }
+        }
+    ])
+}
+
+/// A tool call requested by the model.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ToolCall {
+    pub id: String,
+    pub name: String,
+    pub input: Value,
+}
+
+impl ToolCall {
+    /// Short description of the call, shown in the chat while it runs.
+    pub fn describe(&self) -> String {
+        let arg = |key: &str| self.input.get(key).and_then(Value::as_str).unwrap_or("");
+        match self.name.as_str() {
+            "read_file" => {
+                let start = self.input.get("start_line").and_then(Value::as_u64);
+                let end = self.input.get("end_line").and_then(Value::as_u64);
+                match (start, end) {
+                    (Some(s), Some(e)) => format!("read {}:{}-{}", arg("path"), s, e),
+                    (Some(s), None) => format!("read {}:{}-", arg("path"), s),
+                    _ => format!("read {}", arg("path")),
+                }
+            }
+            "grep" if !arg("path").is_empty() => {
+                format!("grep {} in {}", arg("pattern"), arg("path"))
+            }
+            "grep" => format!("grep {}", arg("pattern")),
+            other => other.to_string(),
+        }
+    }
+
+    /// Runs the call against the working tree. Errors are returned as text
+    /// for the model to read, with `true` marking them as errors.
+    pub async fn run(&self) -> (String, bool) {
+        let result = match self.name.as_str() {
+            "read_file" => read_file(&self.input).await,
+            "grep" => grep(&self.input).await,
+            other => Err(format!("Unknown tool: {other}")),
+        };
+        match result {
+            Ok(output) => (output, false),
+            Err(e) => (e, true),
+        }
+    }
+}
+
+/// Checks that `path` stays inside the repository: relative, and without
+/// `..` components.
+fn check_repo_path(path: &str) -> Result<&Path, String> {
+    let p = Path::new(path);
+    if path.is_empty() {
+        return Err("Path is empty".to_string());
+    }
+    for component in p.components() {
+        match component {
+            Component::Normal(_) | Component::CurDir => {}
+            _ => return Err(format!("Path must be relative to the repository: {path}")),
+        }
+    }
+    Ok(p)
+}
+
+/// The repository's top-level directory, canonicalized.
+async fn repo_root() -> Result<PathBuf, String> {
+    let output = Command::new("git")
+        .args(["rev-parse", "--show-toplevel"])
+        .output()
+        .await
+        .map_err(|e| format!("Failed to run git: {e}"))?;
+    if !output.status.success() {
+        return Err("Not in a git repository".to_string());
+    }
+    let root = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    std::fs::canonicalize(&root).map_err(|e| format!("Can't resolve {root}: {e}"))
+}
+
+/// Resolves `path` to a file git tracks under `root`, following symlinks
+/// only as far as they stay inside the repository. Untracked and ignored
+/// files, such as `.env` or `.git/config`, are refused.
+async fn tracked_file(root: &Path, path: &str) -> Result<PathBuf, String> {
+    let joined = root.join(check_repo_path(path)?);
+    let resolved = std::fs::canonicalize(&joined).map_err(|e| format!("Can't read {path}: {e}"))?;
+    let relative = resolved
+        .strip_prefix(root)
+        .map_err(|_| format!("Path leaves the repository: {path}"))?;
+    let tracked = Command::new("git")
+        .args(["ls-files", "--error-unmatch", "--"])
+        .arg(relative)
+        .current_dir(root)
+        .output()
+        .await
+        .map_err(|e| format!("Failed to run git ls-files: {e}"))?;
+    if !tracked.status.success() {
+        return Err(format!("Not a tracked file: {path}"));
+    }
+    Ok(resolved)
+}
+
+async fn read_file(input: &Value) -> Result<String, String> {
+    let path = input
+        .get("path")
+        .and_then(Value::as_str)
+        .ok_or("Missing path")?;
+    let root = repo_root().await?;
+    let file = tracked_file(&root, path).await?;
+    let content = std::fs::read_to_string(&file).map_err(|e| format!("Can't read {path}: {e}"))?;
+    let start = input.get("start_line").and_then(Value::as_u64).unwrap_or(1) as usize;
+    let end = input
+        .get("end_line")
+        .and_then(Value::as_u64)
+        .map(|e| e as usize);
+    Ok(numbered_lines(&content, start, end))
+}
+
+/// Numbers lines `start..=end` of `content` (1-based), stopping after
+/// `MAX_READ_LINES` with a note on how to read further.
+fn numbered_lines(content: &str, start: usize, end: Option<usize>) -> String {
+    let start = start.max(1);
+    let total = content.lines().count();
+    if start > total {
+        return format!("(file has {total} lines)");
+    }
+    let end = end.unwrap_or(total).min(total);
+    if end < start {
+        return format!("(empty range {start}-{end})");
+    }
+    let last = end.min(start + MAX_READ_LINES - 1);
+    let mut out: String = content
+        .lines()
+        .enumerate()
+        .skip(start - 1)
+        .take(last + 1 - start)
+        .map(|(i, line)| format!("{:>5}  {}\n", i + 1, line))
+        .collect();
+    if last < end {
+        out.push_str(&format!(
+            "(truncated at line {last} of {total}; use start_line to read more)\n"
+        ));
+    }
+    out
+}
+
+async fn grep(input: &Value) -> Result<String, String> {
+    let pattern = input
+        .get("pattern")
+        .and_then(Value::as_str)
+        .filter(|p| !p.is_empty())
+        .ok_or("Missing pattern")?;
+    let mut cmd = Command::new("git");
+    cmd.args(["grep", "-n", "-I", "-E", "-e", pattern]);
+    cmd.current_dir(repo_root().await?);
+    if let Some(path) = input.get("path").and_then(Value::as_str) {
+        cmd.arg("--").arg(check_repo_path(path)?);
+    }
+    let output = cmd
+        .output()
+        .await
+        .map_err(|e| format!("Failed to run git grep: {e}"))?;
+    // git grep exits 1 when nothing matches
+    match output.status.code() {
+        Some(0) => Ok(limit_matches(&String::from_utf8_lossy(&output.stdout))),
+        Some(1) => Ok("No matches".to_string()),
+        _ => Err(String::from_utf8_lossy(&output.stderr).trim().to_string()),
+    }
+}
+
+fn limit_matches(output: &str) -> String {
+    let total = output.lines().count();
+    let mut out: String = output
+        .lines()
+        .take(MAX_GREP_LINES)
+        .map(|line| format!("{line}\n"))
+        .collect();
+    if total > MAX_GREP_LINES {
+        out.push_str(&format!(
+            "({} more matches; narrow the pattern or path)\n",
+            total - MAX_GREP_LINES
+        ));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn repo_paths_must_stay_inside_the_repository() {
+        assert!(check_repo_path("src/main.rs").is_ok());
+        assert!(check_repo_path("./README.md").is_ok());
+        assert!(check_repo_path("").is_err());
+        assert!(check_repo_path("/etc/passwd").is_err());
+        assert!(check_repo_path("../secrets").is_err());
+        assert!(check_repo_path("src/../../x").is_err());
+    }
+
+    #[tokio::test]
+    async fn only_tracked_files_can_be_read() {
+        let root = repo_root().await.unwrap();
+        let file = tracked_file(&root, "Cargo.toml").await.unwrap();
+        assert_eq!(file, root.join("Cargo.toml"));
+        assert!(tracked_file(&root, ".git/config").await.is_err());
+        assert!(tracked_file(&root, "no/such/file.rs").await.is_err());
+    }
+
+    #[test]
+    fn numbered_lines_reads_a_range_and_truncates() {
+        let content = "a\nb\nc\nd\n";
+        assert_eq!(numbered_lines(content, 2, Some(3)), "    2  b\n    3  c\n");
+        assert_eq!(numbered_lines(content, 9, None), "(file has 4 lines)");
+        assert_eq!(numbered_lines(content, 3, Some(1)), "(empty range 3-1)");
+
+        let long: String = (1..=MAX_READ_LINES + 10)
+            .map(|i| format!("{i}\n"))
+            .collect();
+        let out = numbered_lines(&long, 1, None);
+        assert_eq!(out.lines().count(), MAX_READ_LINES + 1);
+        assert!(out.ends_with("use start_line to read more)\n"));
+    }
+
+    #[test]
+    fn describe_summarizes_the_call() {
+        let call = ToolCall {
+            id: "t1".to_string(),
+            name: "read_file".to_string(),
+            input: json!({"path": "src/app.rs", "start_line": 10, "end_line": 40}),
+        };
+        assert_eq!(call.describe(), "read src/app.rs:10-40");
+        let call = ToolCall {
+            id: "t2".to_string(),
+            name: "grep".to_string(),
+            input: json!({"pattern": "fn main"}),
+        };
+        assert_eq!(call.describe(), "grep fn main");
+    }
+}
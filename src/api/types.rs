@@ -10,12 +10,35 @@ pub struct TokenUsage {
 }
 
 impl TokenUsage {
-    pub fn cost_usd(&self) -> f64 {
-        // claude-sonnet-4-20250514: $3.00/1M input, $15.00/1M output
-        (self.input_tokens as f64 * 3.0 + self.output_tokens as f64 * 15.0) / 1_000_000.0
+    /// Cost in USD at `(input, output)` prices per million tokens.
+    pub fn cost_usd(&self, pricing: (f64, f64)) -> f64 {
+        (self.input_tokens as f64 * pricing.0 + self.output_tokens as f64 * pricing.1)
+            / 1_000_000.0
     }
 }
 
+/// Published `(input, output)` prices in USD per million tokens, matched on
+/// the model ID. More specific names come first.
+const MODEL_PRICING: &[(&str, (f64, f64))] = &[
+    ("opus-4-5", (5.0, 25.0)),
+    ("opus", (15.0, 75.0)),
+    ("sonnet", (3.0, 15.0)),
+    ("haiku-4", (1.0, 5.0)),
+    ("3-5-haiku", (0.8, 4.0)),
+    ("haiku", (0.25, 1.25)),
+    ("gpt-4o-mini", (0.15, 0.6)),
+    ("gpt-4o", (2.5, 10.0)),
+];
+
+/// Prices for `model`, when it is one with published prices.
+pub fn model_pricing(model: &str) -> Option<(f64, f64)> {
+    let model = model.to_ascii_lowercase();
+    MODEL_PRICING
+        .iter()
+        .find(|(name, _)| model.contains(name))
+        .map(|(_, pricing)| *pricing)
+}
+
 impl Add for TokenUsage {
     type Output = Self;
 
@@ -44,6 +67,12 @@ pub enum ApiError {
 
     #[error("failed to parse API response: {0}")]
     Parse(String),
+
+    #[error("failed to read prompt file {0}")]
+    PromptFile(String),
+
+    #[error("unknown review persona \"{0}\"")]
+    UnknownPersona(String),
 }
 
 #[allow(dead_code)]
@@ -178,6 +207,37 @@ pub fn chat_system_prompt(mode: ReviewMode) -> &'static str {
     }
 }
 
+/// A built-in review persona: emphasis added to the walkthrough and chat
+/// prompts. `general` adds nothing.
+pub struct PersonaPreset {
+    pub name: &'static str,
+    pub walkthrough: &'static str,
+    pub chat: &'static str,
+}
+
+pub const PERSONA_PRESETS: &[PersonaPreset] = &[
+    PersonaPreset {
+        name: "general",
+        walkthrough: "",
+        chat: "",
+    },
+    PersonaPreset {
+        name: "security",
+        walkthrough: r#"Review this change as a security reviewer. Look hardest for injection (SQL, shell, path traversal, templates), missing or bypassable authentication and authorization checks, secrets in code or logs, unsafe deserialization, and user input that is trusted without validation. Mark a step "critical" when it introduces or may introduce such an issue, and say in its summary how it could be exploited."#,
+        chat: "Answer as a security reviewer: consider which inputs an attacker controls, how the code could be abused, and whether authorization is checked where it needs to be.",
+    },
+    PersonaPreset {
+        name: "perf",
+        walkthrough: r#"Review this change for performance. Look hardest for work added to hot paths, accidentally quadratic loops, N+1 queries, unbounded allocations or caches, blocking calls in async code, lock contention, and lost batching or streaming. Mark a step "critical" when it is likely to regress latency, throughput, or memory at realistic scale, and say in its summary what load would expose it."#,
+        chat: "Answer with performance in mind: consider complexity, allocations, I/O and blocking, and how the code behaves under realistic load.",
+    },
+    PersonaPreset {
+        name: "api",
+        walkthrough: r#"Review this change for compatibility. Look hardest for changes to public APIs, wire formats, serialized data, database schemas, configuration, command-line flags, and default behavior that existing callers or stored data rely on. Mark a step "critical" when it breaks callers without a migration path or version bump, and say in its summary who would break."#,
+        chat: "Answer with compatibility in mind: consider who calls or stores what changed, and whether existing clients, data, and configuration keep working.",
+    },
+];
+
 /// A step as returned by Claude's tool call
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WalkthroughStepResponse {
@@ -299,3 +359,122 @@ pub struct RechunkStepResponse {
 pub struct RechunkResponse {
     pub steps: Vec<RechunkStepResponse>,
 }
+
+pub const PARTITION_DIFF_TOOL: &str = r#"{
+  "name": "partition_diff",
+  "description": "Split the files of a large diff into parts that can each be reviewed on their own",
+  "input_schema": {
+    "type": "object",
+    "properties": {
+      "parts": {
+        "type": "array",
+        "description": "Ordered list of review parts, foundational changes first",
+        "items": {
+          "type": "object",
+          "properties": {
+            "title": {
+              "type": "string",
+              "description": "Short title describing what this part of the change does"
+            },
+            "files": {
+              "type": "array",
+              "description": "Paths of the files in this part, exactly as listed",
+              "items": { "type": "string" }
+            }
+          },
+          "required": ["title", "files"]
+        }
+      }
+    },
+    "required": ["parts"]
+  }
+}"#;
+
+pub const PARTITION_SYSTEM_PROMPT: &str = r#"You are an expert code reviewer planning the review of a change that is too large to review in one sitting.
+
+You are given the list of files in the diff with the number of changed lines in each. Split the files into parts that can each be reviewed on their own.
+
+Guidelines:
+- Group files that belong to the same feature or concern, even across directories
+- Keep each part under the requested size where possible
+- Every file must appear in exactly one part
+- Order parts so that ones other parts build on come first
+- Give each part a short, descriptive title
+
+Call the partition_diff tool with your plan."#;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PartitionPart {
+    pub title: String,
+    pub files: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PartitionResponse {
+    pub parts: Vec<PartitionPart>,
+}
+
+pub const FOLLOW_UPS_TOOL: &str = r#"{
+  "name": "suggest_follow_ups",
+  "description": "Suggest questions the reviewer could ask next about the step",
+  "input_schema": {
+    "type": "object",
+    "properties": {
+      "questions": {
+        "type": "array",
+        "description": "Two or three short follow-up questions, most useful first",
+        "items": { "type": "string" }
+      }
+    },
+    "required": ["questions"]
+  }
+}"#;
+
+pub const FOLLOW_UPS_SYSTEM_PROMPT: &str = r#"You are helping a code reviewer who is chatting about one step of a review walkthrough.
+
+You are given the step and the conversation so far. Suggest the questions the reviewer would most likely want to ask next.
+
+Guidelines:
+- Suggest two or three questions
+- Keep each question to one short sentence, written as the reviewer would ask it
+- Build on the latest answer rather than repeating what it already covered
+- Prefer questions about correctness, edge cases, and design over trivia
+
+Call the suggest_follow_ups tool with your questions."#;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FollowUpResponse {
+    pub questions: Vec<String>,
+}
+
+pub const SUMMARIZE_HISTORY_TOOL: &str = r#"{
+  "name": "summarize_history",
+  "description": "Summarize the earlier part of a review conversation",
+  "input_schema": {
+    "type": "object",
+    "properties": {
+      "summary": {
+        "type": "string",
+        "description": "A few short paragraphs or bullets covering what was asked and concluded"
+      }
+    },
+    "required": ["summary"]
+  }
+}"#;
+
+pub const SUMMARIZE_HISTORY_SYSTEM_PROMPT: &str = r#"You are helping a code reviewer whose conversation about a code change has grown too long to send in full.
+
+You are given the earliest messages of the conversation, and possibly a summary of the messages before them. Write a summary that will be sent in their place.
+
+Guidelines:
+- Keep the questions the reviewer asked and what the answers concluded
+- Keep names of files, functions, and types, and any concerns still open
+- Leave out pleasantries and explanations that are no longer needed
+- Stay well under 400 words
+
+Call the summarize_history tool with your summary."#;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistorySummaryResponse {
+    pub summary: String,
+}
@@ -1,12 +1,34 @@
-use crate::editor::Editor;
-use crate::layout::{Layout, Pane};
-use crate::model::ReviewMode;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::time::Instant;
+
+use tui_textarea::CursorMove;
+
+use crate::api::RetryNotice;
+use crate::clipboard;
+use crate::colors::Theme;
+use crate::editor::{Editor, VimInputMode};
+use crate::export::ReviewTotals;
+use crate::file_selection::FileSelection;
+use crate::file_tree::FileTreeEntry;
+use crate::i18n::Strings;
+use crate::keymap::{Action, Keymap};
+use crate::layout::{FocusDirection, Layout, Pane};
+use crate::model::{CommitInfo, Flag, MessageRole, Note, ReviewMode, Severity};
 #[cfg(debug_assertions)]
 use crate::model::Walkthrough;
+use crate::onboarding::{KeyStorage, Onboarding};
+use crate::parts::{self, SplitChoice, SplitOffer};
+use crate::feedback::{FeedbackDialog, FeedbackEntry};
+use crate::quick_actions::{QuickAction, QuickActionMenu};
+use crate::recovery::RecoveryOffer;
 use crate::scroll::{ChatScroll, DiffScroll};
-use crate::search::SearchState;
-use crate::session::Session;
-use crate::settings::Settings;
+use crate::search::{GlobalSearch, SearchState, StepFinder};
+use crate::session::{PrReviewBlocked, Session, SessionState};
+use crate::settings::{ApiKeySource, Settings};
+use crate::toast::{ToastKind, Toasts};
+use crate::ui::diff_viewer::DiffLineCache;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum SetupFocus {
@@ -15,40 +37,221 @@ pub enum SetupFocus {
     ApiKey,
 }
 
+/// The note editor for a step: its existing notes and a new one being written.
+pub struct NoteEditor<'a> {
+    pub step: usize,
+    pub editor: Editor<'a>,
+}
+
+/// The flag dialog for a step, or for the hunk selected in it: the severity
+/// picked and the concern being written.
+pub struct FlagEditor<'a> {
+    pub step: usize,
+    /// File and first line of the flagged hunk
+    pub location: Option<(String, usize)>,
+    pub severity: Severity,
+    pub editor: Editor<'a>,
+}
+
+/// Every hunk of one file across the walkthrough, in file order, with the
+/// hunk picked to jump to.
+pub struct FileView {
+    pub path: String,
+    /// (step, hunk) indexes
+    pub hunks: Vec<(usize, usize)>,
+    pub selected: usize,
+}
+
+/// Lines of a step's diff selected in visual mode: the line selection
+/// started on and the line it has been extended to.
+pub struct DiffSelection {
+    pub step_id: String,
+    pub anchor: usize,
+    pub cursor: usize,
+}
+
+impl DiffSelection {
+    /// First and last selected display lines.
+    pub fn range(&self) -> (usize, usize) {
+        (self.anchor.min(self.cursor), self.anchor.max(self.cursor))
+    }
+}
+
+/// The diff's line cursor: the display line it is on, in the step it was
+/// last moved in.
+pub struct DiffCursor {
+    pub step_id: String,
+    pub line: usize,
+}
+
+/// Hunks of the current step picked to move into a new step.
+pub struct HunkSplit {
+    pub cursor: usize,
+    pub chosen: Vec<bool>,
+}
+
+/// Destination step being picked for a hunk of the current step.
+pub struct HunkMove {
+    pub hunk: usize,
+    pub cursor: usize,
+}
+
+/// A newer version of the diff under `--watch`, offered for regenerating.
+pub struct DiffUpdate {
+    pub diff_text: String,
+    pub commits: Vec<CommitInfo>,
+}
+
 pub struct App<'a> {
     pub session: Session,
     pub diff_scroll: DiffScroll,
     pub chat_scroll: ChatScroll,
+    /// Diff and chat offsets of the steps visited, by step id
+    step_scroll: HashMap<String, (usize, usize)>,
+    /// Unsent chat input of the steps left mid-question, by step id
+    step_drafts: HashMap<String, String>,
     pub layout: Layout,
     pub editor: Editor<'a>,
     pub should_quit: bool,
     pub quit_pending: bool,
     pub search: SearchState,
+    /// Walkthrough-wide search dialog
+    pub global_search: Option<GlobalSearch>,
+    /// Jump-to-step dialog
+    pub step_finder: Option<StepFinder>,
+    /// Index of the selected file among the file tree's files
+    pub file_tree_cursor: usize,
     pub show_help: bool,
+    /// Token usage and cost popup
+    pub show_usage: bool,
+    /// When the app opened, for the time spent on the review
+    pub started: Instant,
     pub setup_focus: SetupFocus,
+    /// Wrapped-row offsets of each section in the linear stream, recorded at render time
+    pub linear_sections: RefCell<Vec<usize>>,
+    /// Styled lines of the current step's diff, reused between frames
+    pub diff_lines: RefCell<DiffLineCache>,
+    pub theme: Theme,
+    /// Static indicators instead of spinners; the main loop only redraws on events
+    pub reduced_motion: bool,
+    /// Whether the terminal should capture the mouse; the main loop applies changes
+    pub mouse_capture: bool,
+    pub toasts: Toasts,
+    /// First-run onboarding, shown in place of the setup screen while active
+    pub onboarding: Option<Onboarding>,
+    /// Completed onboarding choices, for the main loop to persist to settings
+    pub onboarding_finished: Option<Onboarding>,
+    /// Pre-generation file checklist, shown after setup is confirmed
+    pub file_selection: Option<FileSelection>,
+    /// Offer to split an oversized diff into parts, shown before generation
+    pub split_offer: Option<SplitOffer>,
+    /// Changed lines above which a split is offered
+    pub max_part_lines: usize,
+    /// Cursor in the parts picker while it is open
+    pub parts_picker: Option<usize>,
+    /// One-shot AI actions for the hunk under the cursor
+    pub quick_actions: Option<QuickActionMenu>,
+    /// Rating and comment being entered for the current step
+    pub feedback: Option<FeedbackDialog>,
+    /// Note being written for a step
+    pub note_editor: Option<NoteEditor<'a>>,
+    /// Concern being flagged on a step or hunk
+    pub flag_editor: Option<FlagEditor<'a>>,
+    /// Hunks of the selected hunk's file across every step
+    pub file_view: Option<FileView>,
+    /// Instructions being written for a regenerated walkthrough
+    pub regenerate_prompt: Option<Editor<'a>>,
+    /// Instructions for rewriting the current step's summary
+    pub summary_prompt: Option<Editor<'a>>,
+    /// Hunk selected in the diff pane, with the id of the step it belongs to
+    pub hunk_cursor: Option<(String, usize)>,
+    /// Diff lines selected to quote into chat
+    pub diff_selection: Option<DiffSelection>,
+    /// Highlighted diff line moved by j/k, while turned on
+    pub diff_cursor: Option<DiffCursor>,
+    /// Hunks being picked to split off the current step
+    pub hunk_split: Option<HunkSplit>,
+    /// Step being picked to move the selected hunk to
+    pub hunk_move: Option<HunkMove>,
+    /// Review left behind by a session that crashed, offered at launch
+    pub recovery_offer: Option<RecoveryOffer>,
+    /// Snapshot file of a review restored or declined, deleted once this
+    /// session's own autosave lands
+    pub superseded_recovery: Option<PathBuf>,
+    /// The watched diff changed since the walkthrough was generated
+    pub diff_update: Option<DiffUpdate>,
+    pub keymap: Keymap,
+    pub strings: &'static Strings,
 }
 
 impl<'a> App<'a> {
     #[cfg(debug_assertions)]
     pub fn new(walkthrough: Walkthrough, settings: &Settings, mode: ReviewMode) -> Self {
+        let mut session = Session::new(walkthrough, mode);
+        session.chat_context = settings.chat.context;
+        session.chat_context_lines = settings.chat.context_lines;
+        session.chat_follow_ups = settings.chat.follow_ups;
+        session.whole_change_hunks = settings.chat.whole_change_hunks;
+        session.summarize_chat_history = settings.chat.summarize_history;
+        session.provider = settings.provider;
+        session.api_settings = settings.api.clone();
         Self {
-            session: Session::new(walkthrough, mode),
+            session,
             diff_scroll: DiffScroll::new(),
             chat_scroll: ChatScroll::new(),
-            layout: Layout::default(),
+            step_scroll: HashMap::new(),
+            step_drafts: HashMap::new(),
+            layout: Layout {
+                linear: settings.accessibility.linear_mode,
+                ..Layout::default()
+            },
             editor: Editor::new(settings.vim_enabled()),
             should_quit: false,
             quit_pending: false,
             search: SearchState::new(),
+            global_search: None,
+            step_finder: None,
+            file_tree_cursor: 0,
             show_help: false,
+            show_usage: false,
+            started: Instant::now(),
             setup_focus: SetupFocus::Review,
+            linear_sections: RefCell::new(vec![]),
+            diff_lines: RefCell::default(),
+            theme: Theme::from_settings(&settings.theme, &settings.diff_colors),
+            reduced_motion: settings.accessibility.reduced_motion,
+            mouse_capture: !settings.terminal.disable_mouse_capture,
+            toasts: Toasts::new(),
+            onboarding: None,
+            onboarding_finished: None,
+            file_selection: None,
+            split_offer: None,
+            max_part_lines: settings.parts.max_changed_lines,
+            parts_picker: None,
+            quick_actions: None,
+            feedback: None,
+            note_editor: None,
+            flag_editor: None,
+            file_view: None,
+            regenerate_prompt: None,
+            summary_prompt: None,
+            hunk_cursor: None,
+            diff_selection: None,
+            diff_cursor: None,
+            hunk_split: None,
+            hunk_move: None,
+            recovery_offer: None,
+            superseded_recovery: None,
+            diff_update: None,
+            keymap: Keymap::from_settings(&settings.keys),
+            strings: settings.language().strings(),
         }
     }
 
     pub fn setup(settings: &Settings, mode: ReviewMode) -> Self {
         let (api_key, source) = settings.resolve_api_key();
         let api_key_input = api_key.unwrap_or_default();
-        let focus = if source == crate::settings::ApiKeySource::Missing {
+        let focus = if source == ApiKeySource::Missing {
             SetupFocus::ApiKey
         } else {
             match mode {
@@ -56,47 +259,179 @@ impl<'a> App<'a> {
                 ReviewMode::Walkthrough => SetupFocus::Walkthrough,
             }
         };
+        let mut session = Session::setup(api_key_input, source, mode);
+        session.chat_context = settings.chat.context;
+        session.chat_context_lines = settings.chat.context_lines;
+        session.chat_follow_ups = settings.chat.follow_ups;
+        session.whole_change_hunks = settings.chat.whole_change_hunks;
+        session.summarize_chat_history = settings.chat.summarize_history;
+        session.provider = settings.provider;
+        session.api_settings = settings.api.clone();
         Self {
-            session: Session::setup(api_key_input, source, mode),
+            session,
             diff_scroll: DiffScroll::new(),
             chat_scroll: ChatScroll::new(),
-            layout: Layout::default(),
+            step_scroll: HashMap::new(),
+            step_drafts: HashMap::new(),
+            layout: Layout {
+                linear: settings.accessibility.linear_mode,
+                ..Layout::default()
+            },
             editor: Editor::new(settings.vim_enabled()),
             should_quit: false,
             quit_pending: false,
             search: SearchState::new(),
+            global_search: None,
+            step_finder: None,
+            file_tree_cursor: 0,
             show_help: false,
+            show_usage: false,
+            started: Instant::now(),
             setup_focus: focus,
+            linear_sections: RefCell::new(vec![]),
+            diff_lines: RefCell::default(),
+            theme: Theme::from_settings(&settings.theme, &settings.diff_colors),
+            reduced_motion: settings.accessibility.reduced_motion,
+            mouse_capture: !settings.terminal.disable_mouse_capture,
+            toasts: Toasts::new(),
+            onboarding: None,
+            onboarding_finished: None,
+            file_selection: None,
+            split_offer: None,
+            max_part_lines: settings.parts.max_changed_lines,
+            parts_picker: None,
+            quick_actions: None,
+            feedback: None,
+            note_editor: None,
+            flag_editor: None,
+            file_view: None,
+            regenerate_prompt: None,
+            summary_prompt: None,
+            hunk_cursor: None,
+            diff_selection: None,
+            diff_cursor: None,
+            hunk_split: None,
+            hunk_move: None,
+            recovery_offer: None,
+            superseded_recovery: None,
+            diff_update: None,
+            keymap: Keymap::from_settings(&settings.keys),
+            strings: settings.language().strings(),
         }
     }
 
-    // --- Delegated navigation (with scroll reset) ---
+    // --- Delegated navigation (with per-step scroll) ---
 
     pub fn next_step(&mut self) {
+        self.leave_step();
         if self.session.next_step() {
-            self.diff_scroll.reset();
-            self.chat_scroll.reset();
+            self.enter_step();
         }
+        self.announce_step();
     }
 
     pub fn prev_step(&mut self) {
+        self.leave_step();
         if self.session.prev_step() {
-            self.diff_scroll.reset();
-            self.chat_scroll.reset();
+            self.enter_step();
         }
+        self.announce_step();
     }
 
     pub fn go_to_step(&mut self, index: usize) {
+        self.leave_step();
         if self.session.go_to_step(index) {
-            self.diff_scroll.reset();
-            self.chat_scroll.reset();
+            self.enter_step();
+            self.announce_step();
         }
     }
 
     pub fn complete_step_and_advance(&mut self) {
+        self.leave_step();
         if self.session.complete_step_and_advance() {
-            self.diff_scroll.reset();
-            self.chat_scroll.reset();
+            self.enter_step();
+        }
+        self.announce_step();
+    }
+
+    /// Remembers where the current step is scrolled to, and the question
+    /// being typed on it, before leaving it.
+    fn leave_step(&mut self) {
+        let Some(id) = self.session.current_step_data().map(|step| step.id.clone()) else {
+            return;
+        };
+        let offsets = (self.diff_scroll.get(), self.chat_scroll.offset());
+        self.step_scroll.insert(id.clone(), offsets);
+        let draft = self.editor.take_draft();
+        if draft.is_empty() {
+            self.step_drafts.remove(&id);
+        } else {
+            self.step_drafts.insert(id, draft);
+        }
+    }
+
+    /// Scrolls back to where the current step was left, or to the top of a
+    /// step not visited yet, and brings back its unsent question.
+    fn enter_step(&mut self) {
+        let Some(id) = self.session.current_step_data().map(|step| step.id.clone()) else {
+            return;
+        };
+        let (diff, chat) = self.step_scroll.get(&id).copied().unwrap_or_default();
+        self.diff_scroll.set(diff);
+        self.chat_scroll.restore(chat);
+        let draft = self.step_drafts.remove(&id).unwrap_or_default();
+        self.editor.set_draft(&draft);
+    }
+
+    pub fn toggle_step_reviewed(&mut self) {
+        self.session.toggle_step_reviewed();
+        if self.layout.linear {
+            let s = self.strings;
+            let state = if self.session.is_step_reviewed(self.session.current_step) {
+                s.toast_marked_reviewed
+            } else {
+                s.toast_marked_unreviewed
+            };
+            self.announce(format!("{} {} {}", s.toast_step, self.session.current_step + 1, state));
+        }
+    }
+
+    /// Sets a verbose status announcement in linear mode, where the status
+    /// line is the main channel for telling screen-reader users what changed.
+    pub fn announce(&mut self, message: String) {
+        if self.layout.linear {
+            self.session.status_message = Some(message);
+        }
+    }
+
+    fn announce_step(&mut self) {
+        if self.session.is_walkthrough_complete() {
+            self.announce(self.strings.toast_walkthrough_complete.to_string());
+        } else if let Some(step) = self.session.current_step_data() {
+            let s = self.strings;
+            let message = format!(
+                "{} {} {} {}: {}",
+                s.toast_step,
+                self.session.current_step + 1,
+                s.toast_of,
+                self.session.walkthrough.step_count(),
+                step.title
+            );
+            self.announce(message);
+        }
+    }
+
+    /// Jumps the linear stream to the next (or previous) section boundary.
+    pub fn jump_linear_section(&mut self, forward: bool) {
+        let sections = self.linear_sections.borrow();
+        let current = self.diff_scroll.get();
+        let target = if forward {
+            sections.iter().copied().find(|&s| s > current)
+        } else {
+            sections.iter().copied().rev().find(|&s| s < current)
+        };
+        if let Some(target) = target {
+            self.diff_scroll.set(target);
         }
     }
 
@@ -130,6 +465,342 @@ impl<'a> App<'a> {
         }
     }
 
+    // --- Hunk cursor ---
+
+    /// Index of the selected hunk in the current step; the first hunk until
+    /// one is picked on this step.
+    pub fn current_hunk(&self) -> usize {
+        let Some(step) = self.session.current_step_data() else {
+            return 0;
+        };
+        match &self.hunk_cursor {
+            Some((id, hunk)) if *id == step.id => (*hunk).min(step.hunks.len().saturating_sub(1)),
+            _ => 0,
+        }
+    }
+
+    /// Moves the hunk cursor and scrolls the hunk's file header to the top.
+    pub fn move_hunk_cursor(&mut self, forward: bool) {
+        let current = self.current_hunk();
+        let Some(step) = self.session.current_step_data() else {
+            return;
+        };
+        let target = if forward {
+            (current + 1).min(step.hunks.len().saturating_sub(1))
+        } else {
+            current.saturating_sub(1)
+        };
+        self.select_hunk(target);
+    }
+
+    /// Moves the hunk cursor to the first hunk of the next or previous file.
+    pub fn move_file_cursor(&mut self, forward: bool) {
+        let current = self.current_hunk();
+        let Some(target) = self
+            .session
+            .current_step_data()
+            .and_then(|step| step.file_start_hunk(current, forward))
+        else {
+            return;
+        };
+        self.select_hunk(target);
+    }
+
+    fn select_hunk(&mut self, index: usize) {
+        let Some(step) = self.session.current_step_data() else {
+            return;
+        };
+        if let Some(line) = step.hunk_header_line(index) {
+            self.diff_scroll.set(line);
+        }
+        self.hunk_cursor = Some((step.id.clone(), index));
+    }
+
+    /// Toggles the selected hunk, moving on to the next one once it's reviewed.
+    pub fn toggle_hunk_reviewed(&mut self) {
+        let Some(step) = self.session.current_step_data() else {
+            return;
+        };
+        if step.hunks.is_empty() {
+            return;
+        }
+        let hunk = self.current_hunk();
+        self.session.toggle_hunk_reviewed(hunk);
+        let reviewed = self
+            .session
+            .current_step_data()
+            .is_some_and(|step| step.hunks[hunk].reviewed);
+        if reviewed {
+            self.move_hunk_cursor(true);
+        }
+    }
+
+    // --- Folding ---
+
+    /// Folds the selected hunk down to its @@ header, or unfolds it.
+    pub fn toggle_fold(&mut self) {
+        let hunk = self.current_hunk();
+        let Some(target) = self.session.current_step_data().and_then(|s| s.hunks.get(hunk)) else {
+            return;
+        };
+        let folded = !target.folded;
+        self.fold_hunks(&[hunk], folded);
+    }
+
+    /// Folds every hunk of the selected hunk's file in this step, or unfolds
+    /// them when the selected hunk is folded.
+    pub fn toggle_file_fold(&mut self) {
+        let hunk = self.current_hunk();
+        let Some(step) = self.session.current_step_data() else {
+            return;
+        };
+        let Some(target) = step.hunks.get(hunk) else {
+            return;
+        };
+        let folded = !target.folded;
+        let hunks: Vec<usize> = (0..step.hunks.len())
+            .filter(|&i| step.hunks[i].file_path == target.file_path)
+            .collect();
+        self.fold_hunks(&hunks, folded);
+    }
+
+    /// Folds or unfolds every hunk of the current step.
+    pub fn set_all_folded(&mut self, folded: bool) {
+        let count = self.session.current_step_data().map_or(0, |s| s.hunks.len());
+        self.fold_hunks(&(0..count).collect::<Vec<_>>(), folded);
+    }
+
+    /// Display lines shift when hunks fold, so the selection is dropped and
+    /// the selected hunk is scrolled back into view.
+    fn fold_hunks(&mut self, hunks: &[usize], folded: bool) {
+        let selected = self.current_hunk();
+        self.session.fold_hunks(hunks, folded);
+        self.diff_selection = None;
+        self.select_hunk(selected);
+    }
+
+    // --- Visual selection ---
+
+    /// The selection on the current step, if any.
+    pub fn current_selection(&self) -> Option<(usize, usize)> {
+        let step = self.session.current_step_data()?;
+        self.diff_selection
+            .as_ref()
+            .filter(|selection| selection.step_id == step.id)
+            .map(DiffSelection::range)
+    }
+
+    /// Starts selecting diff lines at the line cursor or the top of the diff
+    /// pane, or stops.
+    pub fn toggle_selection(&mut self) {
+        if self.current_selection().is_some() {
+            self.diff_selection = None;
+            return;
+        }
+        let Some(step) = self.session.current_step_data() else {
+            return;
+        };
+        let last = step.diff_line_count().saturating_sub(1);
+        let line = self.diff_anchor_line().min(last);
+        self.diff_selection = Some(DiffSelection {
+            step_id: step.id.clone(),
+            anchor: line,
+            cursor: line,
+        });
+        self.set_active_pane(Pane::Diff);
+    }
+
+    /// Moves the selection's end by `delta` lines, scrolling to keep it in view.
+    pub fn extend_selection(&mut self, delta: isize, viewport_height: usize) {
+        let Some(last) = self
+            .session
+            .current_step_data()
+            .map(|step| step.diff_line_count().saturating_sub(1))
+        else {
+            return;
+        };
+        let Some(selection) = self.diff_selection.as_mut() else {
+            return;
+        };
+        selection.cursor = selection.cursor.saturating_add_signed(delta).min(last);
+        let cursor = selection.cursor;
+        self.scroll_line_into_view(cursor, viewport_height);
+    }
+
+    fn scroll_line_into_view(&mut self, line: usize, viewport_height: usize) {
+        let scroll = self.diff_scroll.get();
+        if line < scroll {
+            self.diff_scroll.set(line);
+        } else if line >= scroll + viewport_height {
+            self.diff_scroll.set(line + 1 - viewport_height);
+        }
+    }
+
+    // --- Line cursor ---
+
+    /// The line cursor's display line on the current step, while it's on.
+    /// On a step it hasn't moved in yet, it sits at the top of the diff pane.
+    pub fn cursor_line(&self) -> Option<usize> {
+        let cursor = self.diff_cursor.as_ref()?;
+        let step = self.session.current_step_data()?;
+        let line = if cursor.step_id == step.id {
+            cursor.line
+        } else {
+            self.diff_scroll.get()
+        };
+        Some(line.min(step.diff_line_count().saturating_sub(1)))
+    }
+
+    /// Turns the line cursor on at the top of the diff pane, or off.
+    pub fn toggle_line_cursor(&mut self) {
+        if self.diff_cursor.take().is_some() {
+            return;
+        }
+        let Some(step) = self.session.current_step_data() else {
+            return;
+        };
+        self.diff_cursor = Some(DiffCursor {
+            step_id: step.id.clone(),
+            line: self.diff_scroll.get().min(step.diff_line_count().saturating_sub(1)),
+        });
+        self.set_active_pane(Pane::Diff);
+    }
+
+    /// Moves the line cursor by `delta` lines, scrolling to keep it in view.
+    pub fn move_line_cursor(&mut self, delta: isize, viewport_height: usize) {
+        let Some(line) = self.cursor_line() else {
+            return;
+        };
+        let Some(step) = self.session.current_step_data() else {
+            return;
+        };
+        let line = line
+            .saturating_add_signed(delta)
+            .min(step.diff_line_count().saturating_sub(1));
+        self.diff_cursor = Some(DiffCursor {
+            step_id: step.id.clone(),
+            line,
+        });
+        self.scroll_line_into_view(line, viewport_height);
+    }
+
+    /// The display line actions on "this line" apply to: the line cursor's
+    /// while it's on, otherwise the top of the diff pane.
+    pub fn diff_anchor_line(&self) -> usize {
+        self.cursor_line().unwrap_or_else(|| self.diff_scroll.get())
+    }
+
+    /// Copies the selected diff lines, the line under the line cursor, or
+    /// else the selected hunk, to the clipboard.
+    pub fn yank(&mut self) {
+        let Some(step) = self.session.current_step_data() else {
+            return;
+        };
+        let text = match self.current_selection().or(self.cursor_line().map(|l| (l, l))) {
+            Some((first, last)) => {
+                let lines = step.display_lines();
+                let last = last.min(lines.len().saturating_sub(1));
+                lines[first.min(last)..=last].join("\n")
+            }
+            None => match step.hunks.get(self.current_hunk()) {
+                Some(hunk) => hunk.content.clone(),
+                None => return,
+            },
+        };
+        self.diff_selection = None;
+        self.copy_to_clipboard(&text);
+    }
+
+    /// Progress, time, and tokens of the review so far.
+    pub fn review_totals(&self) -> ReviewTotals {
+        let (reviewed, steps) = self.session.overall_progress();
+        ReviewTotals {
+            reviewed,
+            steps,
+            elapsed: self.started.elapsed(),
+            usage: self.session.token_usage,
+            cost_usd: self.session.cost_usd(),
+        }
+    }
+
+    /// Copies a plain-text summary of the review and its flags to the
+    /// clipboard.
+    pub fn copy_review_summary(&mut self) {
+        let summary = crate::export::render_summary(
+            &self.session.walkthrough,
+            self.session.review_mode,
+            self.review_totals(),
+        );
+        self.copy_to_clipboard(&summary);
+    }
+
+    /// Leaves the completion screen for the last step, to look back over
+    /// the walkthrough.
+    pub fn leave_completion(&mut self) {
+        self.session.walkthrough_complete = false;
+    }
+
+    /// Copies the latest answer of the conversation shown to the clipboard.
+    pub fn yank_last_answer(&mut self) {
+        let Some(text) = self
+            .session
+            .chat_messages()
+            .iter()
+            .rev()
+            .find(|m| m.role == MessageRole::Assistant)
+            .map(|m| m.content.clone())
+        else {
+            self.notify(self.strings.toast_no_answer);
+            return;
+        };
+        self.copy_to_clipboard(&text);
+    }
+
+    /// Copies the chat input's visual selection to the editor's register
+    /// and the clipboard.
+    pub fn yank_chat_selection(&mut self) {
+        let text = self.editor.yank_selection();
+        self.copy_to_clipboard(&text);
+    }
+
+    fn copy_to_clipboard(&mut self, text: &str) {
+        match clipboard::copy(text) {
+            Ok(()) => {
+                let lines = text.lines().count();
+                let s = self.strings;
+                let noun = if lines == 1 { s.toast_line } else { s.toast_lines };
+                self.notify(format!("{} {} {}", s.toast_copied, lines, noun));
+            }
+            Err(e) => self.notify_error(e),
+        }
+    }
+
+    /// Puts the selected lines, with their file and line numbers, into the
+    /// chat input and the context of the next question, and focuses chat.
+    pub fn quote_selection(&mut self) {
+        let Some((first, last)) = self.current_selection() else {
+            return;
+        };
+        self.diff_selection = None;
+        let Some(quote) = self
+            .session
+            .current_step_data()
+            .and_then(|step| step.quote_display_lines(first, last))
+        else {
+            self.notify(self.strings.toast_no_selection);
+            return;
+        };
+        if !self.editor.is_empty() {
+            self.editor.textarea.move_cursor(CursorMove::Bottom);
+            self.editor.textarea.move_cursor(CursorMove::End);
+            self.editor.textarea.insert_str("\n\n");
+        }
+        self.editor.textarea.insert_str(format!("{}\n\n", quote));
+        self.session.chat_selection = Some((self.session.current_step, quote));
+        self.set_active_pane(Pane::Chat);
+        self.editor.vim_mode = VimInputMode::Insert;
+    }
+
     pub fn set_active_pane(&mut self, pane: Pane) {
         if self.layout.active_pane == Pane::Chat && pane != Pane::Chat {
             self.editor.reset_mode();
@@ -137,6 +808,102 @@ impl<'a> App<'a> {
         self.layout.active_pane = pane;
     }
 
+    pub fn focus_neighbor(&mut self, direction: FocusDirection) {
+        if let Some(pane) = self.layout.active_pane.neighbor(direction)
+            && (pane != Pane::Files || self.layout.file_tree)
+        {
+            self.set_active_pane(pane);
+        }
+    }
+
+    // --- File view ---
+
+    /// Opens the file view for the selected hunk's file, on that hunk.
+    pub fn open_file_view(&mut self) {
+        let current = self.current_hunk();
+        let Some(step) = self.session.current_step_data() else {
+            return;
+        };
+        let Some(hunk) = step.hunks.get(current) else {
+            return;
+        };
+        let path = hunk.file_path.clone();
+        let hunks = self.session.walkthrough.file_hunks(&path);
+        let here = (self.session.current_step, current);
+        let selected = hunks.iter().position(|&h| h == here).unwrap_or(0);
+        self.file_view = Some(FileView {
+            path,
+            hunks,
+            selected,
+        });
+    }
+
+    /// Goes to the step of the hunk picked in the file view, with the hunk
+    /// selected.
+    pub fn jump_to_file_view_hunk(&mut self) {
+        let Some(view) = self.file_view.take() else {
+            return;
+        };
+        let Some(&(step, hunk)) = view.hunks.get(view.selected) else {
+            return;
+        };
+        self.go_to_step(step);
+        self.set_active_pane(Pane::Diff);
+        self.select_hunk(hunk);
+    }
+
+    // --- File tree ---
+
+    /// Shows the file tree pane and focuses it, or hides it.
+    pub fn toggle_file_tree(&mut self) {
+        self.layout.file_tree = !self.layout.file_tree;
+        if self.layout.file_tree {
+            self.set_active_pane(Pane::Files);
+        } else if self.layout.active_pane == Pane::Files {
+            self.set_active_pane(Pane::Diff);
+        }
+        if self.layout.zoomed == Some(Pane::Files) {
+            self.layout.zoomed = None;
+        }
+    }
+
+    /// The files of the walkthrough as a tree.
+    pub fn file_tree(&self) -> Vec<FileTreeEntry> {
+        crate::file_tree::build(&self.session.walkthrough.steps)
+    }
+
+    /// Moves the file tree cursor by `delta` files, clamped to the list.
+    pub fn move_file_tree_cursor(&mut self, delta: isize) {
+        let files = self.file_tree().iter().filter(|e| e.path.is_some()).count();
+        let last = files.saturating_sub(1) as isize;
+        self.file_tree_cursor =
+            (self.file_tree_cursor as isize).saturating_add(delta).clamp(0, last) as usize;
+    }
+
+    /// Goes to the first step touching the file under the cursor, with its
+    /// first hunk in that file selected.
+    pub fn jump_to_tree_file(&mut self) {
+        let Some(entry) = self
+            .file_tree()
+            .into_iter()
+            .filter(|e| e.path.is_some())
+            .nth(self.file_tree_cursor)
+        else {
+            return;
+        };
+        let (Some(path), Some(&step)) = (entry.path, entry.steps.first()) else {
+            return;
+        };
+        self.go_to_step(step);
+        let hunk = self
+            .session
+            .current_step_data()
+            .and_then(|step| step.hunks.iter().position(|h| h.file_path == path));
+        if let Some(hunk) = hunk {
+            self.select_hunk(hunk);
+        }
+    }
+
     /// Sends the current editor content as a chat message.
     pub fn send_message(&mut self) {
         let content: String = self.editor.textarea.lines().join("\n");
@@ -150,9 +917,107 @@ impl<'a> App<'a> {
         self.editor.textarea.select_all();
         self.editor.textarea.delete_char();
 
+        self.editor.remember(content.clone());
         self.session.send_message(content);
     }
 
+    /// Asks the current step's last question again, dropping the answer or
+    /// error it got.
+    pub fn retry_last_question(&mut self) {
+        if self.session.retry_last_question() {
+            self.exit_chat_scrollback();
+        } else {
+            self.notify(self.strings.toast_no_retry);
+        }
+    }
+
+    /// Moves the current step's last question back into the input for
+    /// editing, dropping the answer or error it got.
+    pub fn edit_last_question(&mut self) {
+        if !self.editor.is_empty() {
+            self.notify(self.strings.toast_clear_input);
+            return;
+        }
+        let Some(question) = self.session.take_last_question() else {
+            self.notify(self.strings.toast_no_edit);
+            return;
+        };
+        self.editor.textarea.insert_str(question);
+        self.editor.vim_mode = VimInputMode::Insert;
+        self.exit_chat_scrollback();
+    }
+
+    /// Switches to the next chat context level for the questions that follow.
+    pub fn cycle_chat_context(&mut self) {
+        self.session.chat_context = self.session.chat_context.next();
+        let label = self.strings.chat_context_label(self.session.chat_context);
+        self.notify(format!("{}: {}", self.strings.toast_chat_context, label));
+    }
+
+    /// Switches to the next review persona. Chat answers use it from the next
+    /// question; the walkthrough changes when it's regenerated.
+    pub fn cycle_persona(&mut self) {
+        let prompts = &mut self.session.api_settings.prompts;
+        let names = prompts.persona_names();
+        let next = names
+            .iter()
+            .position(|name| name == prompts.persona_name())
+            .map_or(0, |i| (i + 1) % names.len());
+        prompts.persona = Some(names[next].clone());
+        let s = self.strings;
+        let key = self.keymap.label(&[Action::Regenerate]);
+        let regenerate = format!("{} {}", key, s.toast_persona_regenerate);
+        self.notify(format!("{}: {} ({})", s.toast_persona, names[next], regenerate));
+    }
+
+    /// Switches questions between the current step and the whole change.
+    pub fn toggle_whole_change_chat(&mut self) {
+        self.session.toggle_whole_change_chat();
+        self.chat_scroll.jump_to_bottom();
+        if self.session.whole_change_chat {
+            self.notify(self.strings.toast_chat_whole_change);
+        } else {
+            self.notify(self.strings.toast_chat_step);
+        }
+    }
+
+    /// Stops the chat reply being streamed. An unanswered question goes back
+    /// into the input so it can be edited and sent again.
+    pub fn cancel_chat(&mut self) -> bool {
+        if self.session.chat_pending.is_none() {
+            return false;
+        }
+        if let Some(question) = self.session.cancel_chat()
+            && self.editor.is_empty()
+        {
+            self.editor.textarea.insert_str(question);
+        }
+        self.notify(self.strings.toast_chat_cancelled);
+        true
+    }
+
+    /// Cancels the chat reply or, failing that, the walkthrough generation in
+    /// flight. Returns false when neither was running.
+    pub fn cancel_request(&mut self) -> bool {
+        if self.cancel_chat() {
+            return true;
+        }
+        if self.session.cancel_generation() {
+            let count = self.session.walkthrough.step_count();
+            if count > 0 {
+                let s = self.strings;
+                self.notify(format!(
+                    "{}; {} {}",
+                    s.toast_generation_cancelled, count, s.toast_steps_kept
+                ));
+            } else {
+                self.notify(self.strings.toast_generation_cancelled);
+            }
+            return true;
+        }
+        false
+    }
+
     pub fn scroll_chat_up(&mut self, amount: usize) {
         self.chat_scroll.scroll_up(amount);
     }
@@ -165,6 +1030,693 @@ impl<'a> App<'a> {
         self.chat_scroll.jump_to_bottom();
     }
 
+    /// Shows that an API request is waiting to be retried: in the generation
+    /// progress while a walkthrough is being generated, otherwise as a toast.
+    pub fn set_loading_status(&mut self, notice: RetryNotice) {
+        let s = self.strings;
+        let reason = s.retry_reason(&notice);
+        let message = format!(
+            "{}, {} {}s ({} {}/{})…",
+            reason,
+            s.toast_retrying_in,
+            notice.delay.as_secs(),
+            s.toast_attempt,
+            notice.attempt,
+            notice.max_attempts
+        );
+        if !self.session.receive_retry_notice(notice) {
+            self.notify(message);
+        }
+    }
+
+    /// Shows a transient notification for a background event.
+    pub fn notify(&mut self, message: impl Into<String>) {
+        self.toasts.push(message, ToastKind::Info);
+    }
+
+    pub fn notify_error(&mut self, message: impl Into<String>) {
+        self.toasts.push(message, ToastKind::Error);
+    }
+
+    /// Opens the quick actions menu for the hunk at the top of the diff pane.
+    pub fn open_quick_actions(&mut self) {
+        if self.session.chat_pending.is_some() {
+            self.notify(self.strings.toast_wait_answer);
+            return;
+        }
+        let hunk = self
+            .session
+            .current_step_data()
+            .and_then(|step| step.locate_display_line(self.diff_anchor_line()))
+            .map(|(hunk, _)| hunk);
+        if let Some(hunk) = hunk {
+            self.quick_actions = Some(QuickActionMenu::new(hunk));
+        }
+    }
+
+    /// Sends the action's question about the menu's hunk to the step's chat.
+    pub fn run_quick_action(&mut self, action: QuickAction) {
+        let Some(menu) = self.quick_actions.take() else {
+            return;
+        };
+        let Some(hunk) = self
+            .session
+            .current_step_data()
+            .and_then(|step| step.hunks.get(menu.hunk))
+        else {
+            return;
+        };
+        let prompt = action.prompt(hunk, menu.hunk);
+        self.session.send_message(prompt);
+        self.chat_scroll.jump_to_bottom();
+    }
+
+    /// Replaces whatever was loaded with the crashed session's review.
+    pub fn restore_session(&mut self) {
+        let Some(offer) = self.recovery_offer.take() else {
+            return;
+        };
+        self.onboarding = None;
+        self.file_selection = None;
+        self.split_offer = None;
+        self.session.restore(offer.snapshot);
+        self.superseded_recovery = Some(offer.path);
+        self.diff_scroll.reset();
+        self.chat_scroll.reset();
+        self.notify(self.strings.toast_session_restored);
+    }
+
+    pub fn discard_recovery(&mut self) {
+        if let Some(offer) = self.recovery_offer.take() {
+            self.superseded_recovery = Some(offer.path);
+        }
+    }
+
+    pub fn open_feedback(&mut self) {
+        if self.session.current_step_data().is_some() {
+            self.feedback = Some(FeedbackDialog::new(self.session.current_step));
+        }
+    }
+
+    /// Appends the dialog's rating of its step to the local feedback log.
+    pub fn submit_feedback(&mut self) {
+        let Some(dialog) = self.feedback.take() else {
+            return;
+        };
+        let Some(step) = self.session.walkthrough.steps.get(dialog.step) else {
+            return;
+        };
+        let entry = FeedbackEntry::new(&self.session, step, dialog.rating, &dialog.comment);
+        match crate::feedback::record(&entry) {
+            Ok(()) => self.notify(self.strings.toast_feedback_saved),
+            Err(e) => self.notify_error(format!("{}: {}", self.strings.toast_feedback_failed, e)),
+        }
+    }
+
+    pub fn open_note_editor(&mut self) {
+        if self.session.current_step_data().is_some() {
+            self.note_editor = Some(NoteEditor {
+                step: self.session.current_step,
+                editor: Editor::new(false),
+            });
+        }
+    }
+
+    /// Adds the editor's text as a note on its step and closes the editor.
+    pub fn save_note(&mut self) {
+        let Some(note_editor) = self.note_editor.take() else {
+            return;
+        };
+        let text = note_editor.editor.textarea.lines().join("\n");
+        let text = text.trim();
+        if text.is_empty() {
+            return;
+        }
+        if let Some(step) = self.session.walkthrough.steps.get_mut(note_editor.step) {
+            step.notes.push(Note {
+                text: text.to_string(),
+            });
+            self.notify(self.strings.toast_note_added);
+        }
+    }
+
+    /// Removes the most recent note on the editor's step.
+    pub fn delete_last_note(&mut self) {
+        let Some(note_editor) = &self.note_editor else {
+            return;
+        };
+        if let Some(step) = self.session.walkthrough.steps.get_mut(note_editor.step) {
+            step.notes.pop();
+        }
+    }
+
+    /// Opens the flag dialog for the selected hunk, once one has been picked
+    /// on this step, or else for the whole step.
+    pub fn open_flag_editor(&mut self) {
+        let Some(step) = self.session.current_step_data() else {
+            return;
+        };
+        let location = match &self.hunk_cursor {
+            Some((id, _)) if *id == step.id => step
+                .hunks
+                .get(self.current_hunk())
+                .map(|hunk| (hunk.file_path.clone(), hunk.start_line)),
+            _ => None,
+        };
+        self.flag_editor = Some(FlagEditor {
+            step: self.session.current_step,
+            location,
+            severity: Severity::Question,
+            editor: Editor::new(false),
+        });
+    }
+
+    /// Adds the dialog's flag to its step and closes the dialog.
+    pub fn save_flag(&mut self) {
+        let Some(flag_editor) = self.flag_editor.take() else {
+            return;
+        };
+        let text = flag_editor.editor.textarea.lines().join("\n");
+        if let Some(step) = self.session.walkthrough.steps.get_mut(flag_editor.step) {
+            step.flags.push(Flag {
+                severity: flag_editor.severity,
+                text: text.trim().to_string(),
+                location: flag_editor.location,
+            });
+            let severity = self.strings.severity_label(flag_editor.severity);
+            self.notify(format!("{} {}", self.strings.toast_flagged, severity));
+        }
+    }
+
+    /// Removes the most recent flag on the dialog's step.
+    pub fn delete_last_flag(&mut self) {
+        let Some(flag_editor) = &self.flag_editor else {
+            return;
+        };
+        if let Some(step) = self.session.walkthrough.steps.get_mut(flag_editor.step) {
+            step.flags.pop();
+        }
+    }
+
+    /// Opens the prompt for regenerating the walkthrough, filled with the
+    /// instructions of the last regeneration.
+    pub fn open_regenerate_prompt(&mut self) {
+        if self.session.generation_in_progress {
+            self.notify(self.strings.toast_wait_generation);
+            return;
+        }
+        let mut editor = Editor::new(false);
+        if let Some(instructions) = &self.session.generation_instructions {
+            editor.textarea.insert_str(instructions);
+        }
+        self.regenerate_prompt = Some(editor);
+    }
+
+    /// Generates the walkthrough again with the prompt's instructions.
+    pub fn regenerate(&mut self) {
+        let Some(editor) = self.regenerate_prompt.take() else {
+            return;
+        };
+        let instructions = editor.textarea.lines().join("\n").trim().to_string();
+        if self.session.request_regeneration(Some(instructions)) {
+            self.diff_scroll.reset();
+            self.chat_scroll.reset();
+            self.notify(self.strings.toast_regenerating);
+        }
+    }
+
+    /// Opens the prompt for rewriting the current step's summary.
+    pub fn open_summary_prompt(&mut self) {
+        if self.session.current_step_data().is_none() {
+            return;
+        }
+        if self.session.steps_busy() {
+            self.notify(self.strings.toast_wait_request);
+            return;
+        }
+        self.summary_prompt = Some(Editor::new(false));
+    }
+
+    /// Asks for a new summary of the current step with the prompt's
+    /// instructions.
+    pub fn regenerate_summary(&mut self) {
+        let Some(editor) = self.summary_prompt.take() else {
+            return;
+        };
+        let instructions = editor.textarea.lines().join("\n").trim().to_string();
+        if self.session.request_summary(&instructions) {
+            self.notify(self.strings.toast_rewriting_summary);
+        }
+    }
+
+    /// Posts step notes as a review on the pull request the diff came from.
+    pub fn post_pr_review(&mut self) {
+        let s = self.strings;
+        match self.session.request_pr_review() {
+            Ok(()) => self.session.status_message = Some(s.toast_posting_review.to_string()),
+            Err(PrReviewBlocked::NoPullRequest) => self.notify_error(s.toast_no_pull_request),
+            Err(PrReviewBlocked::NoNotes) => {
+                let key = self.keymap.label(&[Action::Notes]);
+                self.notify_error(format!("{} {}", s.toast_no_notes, key));
+            }
+        }
+    }
+
+    /// Shows how posting the pull request review went.
+    pub fn receive_pr_review_posted(&mut self, result: Result<usize, String>) {
+        let s = self.strings;
+        self.session.status_message = None;
+        match result {
+            Ok(count) => {
+                let target = self.session.forge.as_ref().and_then(|f| f.pull_request());
+                self.notify(format!(
+                    "{}: {} {} ({})",
+                    s.toast_review_posted,
+                    count,
+                    s.toast_comments,
+                    target.unwrap_or_default()
+                ));
+            }
+            Err(e) => self.notify_error(format!("{}: {}", s.toast_post_failed, e)),
+        }
+    }
+
+    /// Records the completed review as a git note on HEAD.
+    pub fn record_review(&mut self) {
+        if self.session.request_review_record() {
+            self.session.status_message = Some(self.strings.toast_recording_review.to_string());
+        }
+    }
+
+    /// Shows how recording the review went.
+    pub fn receive_review_recorded(&mut self, result: Result<String, String>) {
+        let s = self.strings;
+        self.session.status_message = None;
+        match result {
+            Ok(report) => self.notify(format!(
+                "{} (refs/notes/{}), {} {}",
+                s.toast_review_recorded,
+                crate::review_record::NOTES_REF,
+                s.toast_report,
+                report
+            )),
+            Err(e) => self.notify_error(format!("{}: {}", s.toast_record_failed, e)),
+        }
+    }
+
+    fn notify_exported(&mut self, path: &std::path::Path) {
+        self.notify(format!("{} {}", self.strings.toast_export_written, path.display()));
+    }
+
+    /// Writes the walkthrough as a standalone HTML bundle in the working directory.
+    pub fn export_html(&mut self) {
+        if self.session.walkthrough.steps.is_empty() {
+            return;
+        }
+        let path = std::path::Path::new(crate::export::HTML_EXPORT_FILE);
+        match crate::export::write_html(&self.session, path) {
+            Ok(path) => self.notify_exported(&path),
+            Err(e) => self.notify_error(format!("{}: {}", self.strings.toast_export_failed, e)),
+        }
+    }
+
+    /// Writes the walkthrough as a Markdown report in the working directory.
+    pub fn export_markdown(&mut self) {
+        if self.session.walkthrough.steps.is_empty() {
+            return;
+        }
+        let path = std::path::Path::new(crate::export::MARKDOWN_EXPORT_FILE);
+        match crate::export::write_markdown(&self.session, path) {
+            Ok(path) => self.notify_exported(&path),
+            Err(e) => self.notify_error(format!("{}: {}", self.strings.toast_export_failed, e)),
+        }
+    }
+
+    /// Writes the walkthrough and its review state as JSON in the working
+    /// directory, to reopen later with `--from-walkthrough`.
+    pub fn export_json(&mut self) {
+        if self.session.walkthrough.steps.is_empty() {
+            return;
+        }
+        let path = std::path::Path::new(crate::export::JSON_EXPORT_FILE);
+        match crate::export::write_json(&self.session, path) {
+            Ok(path) => self.notify_exported(&path),
+            Err(e) => self.notify_error(format!("{}: {}", self.strings.toast_export_failed, e)),
+        }
+    }
+
+    /// Opens the forge page this session came from: the file and line at the
+    /// line cursor (or the top of the diff) when the diff pane is focused,
+    /// otherwise the PR itself.
+    pub fn open_in_browser(&mut self) {
+        let Some(forge) = &self.session.forge else {
+            self.session.status_message = Some(self.strings.toast_no_forge.to_string());
+            return;
+        };
+
+        let url = match self.layout.active_pane {
+            Pane::Diff => self
+                .session
+                .current_step_data()
+                .and_then(|step| {
+                    let (hunk_index, content_index) =
+                        step.locate_display_line(self.diff_anchor_line())?;
+                    let hunk = &step.hunks[hunk_index];
+                    let line = content_index.map(|i| hunk.new_line_at(i));
+                    Some(forge.file_url(&hunk.file_path, line))
+                })
+                .unwrap_or_else(|| forge.page_url()),
+            _ => forge.page_url(),
+        };
+
+        self.session.status_message = Some(match crate::github::open_in_browser(&url) {
+            Ok(()) => format!("{} {}", self.strings.toast_opened, url),
+            Err(e) => e,
+        });
+    }
+
+    /// Confirms the setup screen. A diff touching several files goes to the
+    /// file checklist first; otherwise generation starts right away.
+    pub fn confirm_setup(&mut self) {
+        if !self.session.has_api_key() {
+            return;
+        }
+        let selection = self
+            .session
+            .diff_input
+            .as_deref()
+            .and_then(|diff| FileSelection::from_diff(diff, &self.session.diff_filter));
+        match selection {
+            Some(selection) => self.file_selection = Some(selection),
+            None => self.begin_generation(),
+        }
+    }
+
+    /// Generates from the checked files, replacing the CLI filter.
+    pub fn confirm_file_selection(&mut self) {
+        let Some(selection) = self.file_selection.as_mut() else {
+            return;
+        };
+        if selection.selected_count() == 0 {
+            selection.error = Some(self.strings.toast_select_file.to_string());
+            return;
+        }
+        self.session.diff_filter = selection.to_filter();
+        self.file_selection = None;
+        self.begin_generation();
+    }
+
+    /// Starts generation, first offering a split when the diff is over the
+    /// part size limit.
+    fn begin_generation(&mut self) {
+        let offer = self.session.diff_input.as_deref().and_then(|diff| {
+            SplitOffer::for_diff(diff, &self.session.diff_filter, self.max_part_lines)
+        });
+        match offer {
+            Some(offer) => self.split_offer = Some(offer),
+            None => self.session.confirm_setup(),
+        }
+    }
+
+    /// Acts on the highlighted choice in the split offer.
+    pub fn confirm_split(&mut self) {
+        let Some(offer) = self.split_offer.as_mut() else {
+            return;
+        };
+        if offer.pending {
+            return;
+        }
+        match offer.choice() {
+            SplitChoice::Whole => {
+                self.split_offer = None;
+                self.session.confirm_setup();
+            }
+            SplitChoice::ByDirectory => {
+                let parts = parts::split_by_directory(&offer.files, offer.max_lines);
+                self.split_offer = None;
+                self.session.start_parts(parts);
+            }
+            SplitChoice::ByModel => {
+                offer.pending = true;
+                self.session.partition_request = Some((offer.files.clone(), offer.max_lines));
+            }
+        }
+    }
+
+    /// Starts the split review from the model's partitioning, falling back to
+    /// splitting by directory if the request failed.
+    pub fn receive_partition(&mut self, result: Result<crate::api::PartitionResponse, String>) {
+        let Some(offer) = self.split_offer.take() else {
+            return;
+        };
+        let parts = match result {
+            Ok(response) => parts::parts_from_suggestion(response, &offer.files),
+            Err(e) => {
+                self.notify_error(format!("{}: {}", self.strings.toast_parts_fallback, e));
+                parts::split_by_directory(&offer.files, offer.max_lines)
+            }
+        };
+        self.session.start_parts(parts);
+    }
+
+    pub fn open_parts_picker(&mut self) {
+        if !self.session.parts.is_empty() {
+            self.parts_picker = Some(self.session.current_part);
+        }
+    }
+
+    /// Switches to the part highlighted in the picker.
+    pub fn switch_part(&mut self, index: usize) {
+        if !self.session.switch_part(index) {
+            self.notify(self.strings.toast_parts_busy);
+            return;
+        }
+        self.parts_picker = None;
+        self.diff_scroll.reset();
+        self.chat_scroll.reset();
+        self.search.clear();
+        let part = &self.session.parts[index];
+        let s = self.strings;
+        self.session.status_message = Some(format!(
+            "{} {} {} {}: {}",
+            s.toast_part,
+            index + 1,
+            s.toast_of,
+            self.session.parts.len(),
+            part.title
+        ));
+    }
+
+    /// Applies the onboarding choices to this session and hands them to the
+    /// main loop to save, then continues to the regular setup screen.
+    pub fn finish_onboarding(&mut self) {
+        let Some(onboarding) = self.onboarding.take() else {
+            return;
+        };
+        self.session.provider = onboarding.provider;
+        self.session.api_key_input = onboarding.api_key.trim().to_string();
+        self.session.api_key_source = match onboarding.key_storage {
+            _ if self.session.api_key_input.is_empty() => ApiKeySource::NotRequired,
+            KeyStorage::SettingsFile => ApiKeySource::Settings,
+            KeyStorage::Session => ApiKeySource::Session,
+        };
+        self.editor = Editor::new(onboarding.vim_mode.resolve());
+        self.setup_focus = match self.session.review_mode {
+            ReviewMode::Review => SetupFocus::Review,
+            ReviewMode::Walkthrough => SetupFocus::Walkthrough,
+        };
+        self.onboarding_finished = Some(onboarding);
+    }
+
+    /// Toggles mouse capture so the terminal's native selection and copy work.
+    pub fn toggle_mouse_capture(&mut self) {
+        self.mouse_capture = !self.mouse_capture;
+        let s = self.strings;
+        self.session.status_message = Some(if self.mouse_capture {
+            s.toast_mouse_on.to_string()
+        } else {
+            let key = self.keymap.label(&[Action::MouseCapture]);
+            format!("{} {} {}", s.toast_mouse_off, key, s.toast_mouse_restore)
+        });
+    }
+
+    /// Re-sorts the steps between the model's narrative order and file order.
+    pub fn toggle_step_order(&mut self) {
+        if !self.session.toggle_step_order() {
+            self.notify(self.strings.toast_sort_busy);
+            return;
+        }
+        let order = self.strings.step_order_label(self.session.step_order);
+        self.session.status_message = Some(format!("{} {}", self.strings.toast_steps_in, order));
+    }
+
+    /// Moves the current step (and its sub-steps) down or up the step list.
+    pub fn move_step(&mut self, down: bool) {
+        if self.session.steps_busy() {
+            self.notify(self.strings.toast_rearrange_busy);
+        } else if self.session.move_step(down) {
+            self.after_step_edit();
+        }
+    }
+
+    /// Merges the current step into the one before it.
+    pub fn merge_step(&mut self) {
+        if self.session.steps_busy() {
+            self.notify(self.strings.toast_rearrange_busy);
+        } else if self.session.merge_step_into_previous() {
+            self.after_step_edit();
+            self.notify(self.strings.toast_merged);
+        } else if self.session.current_step == 0 {
+            self.notify(self.strings.toast_merge_first);
+        } else {
+            self.notify(self.strings.toast_merge_sub_steps);
+        }
+    }
+
+    /// Opens the picker for hunks to move out of the current step.
+    pub fn open_hunk_split(&mut self) {
+        if self.session.steps_busy() {
+            self.notify(self.strings.toast_rearrange_busy);
+            return;
+        }
+        let hunks = self.session.current_step_data().map_or(0, |step| step.hunks.len());
+        if hunks < 2 {
+            self.notify(self.strings.toast_split_one_hunk);
+            return;
+        }
+        self.hunk_split = Some(HunkSplit {
+            cursor: self.current_hunk().min(hunks - 1),
+            chosen: vec![false; hunks],
+        });
+    }
+
+    /// Moves the picked hunks into a new step after the current one.
+    pub fn confirm_hunk_split(&mut self) {
+        let Some(split) = self.hunk_split.take() else {
+            return;
+        };
+        let hunks: Vec<usize> = (0..split.chosen.len()).filter(|&i| split.chosen[i]).collect();
+        if self.session.split_off_hunks(&hunks) {
+            self.after_step_edit();
+            let id = self.session.current_step_data().map(|step| step.id.clone());
+            let id = id.unwrap_or_default();
+            self.notify(format!("{} {}", self.strings.toast_split_moved, id));
+        } else {
+            self.notify(self.strings.toast_split_pick);
+            self.hunk_split = Some(split);
+        }
+    }
+
+    /// Opens the step picker for moving the selected hunk out of the current
+    /// step.
+    pub fn open_hunk_move(&mut self) {
+        if self.session.steps_busy() {
+            self.notify(self.strings.toast_rearrange_busy);
+            return;
+        }
+        if self.session.current_step_data().is_none_or(|step| step.hunks.len() < 2) {
+            self.notify(self.strings.toast_move_only_hunk);
+            return;
+        }
+        let current = self.session.current_step;
+        let last = self.session.walkthrough.step_count().saturating_sub(1);
+        self.hunk_move = Some(HunkMove {
+            hunk: self.current_hunk(),
+            cursor: if current < last { current + 1 } else { current.saturating_sub(1) },
+        });
+    }
+
+    /// Moves the hunk to the step picked in the hunk move dialog.
+    pub fn confirm_hunk_move(&mut self) {
+        let Some(pick) = self.hunk_move.take() else {
+            return;
+        };
+        if pick.cursor == self.session.current_step {
+            self.hunk_move = Some(pick);
+            return;
+        }
+        if self.session.move_hunk(pick.hunk, pick.cursor) {
+            self.diff_selection = None;
+            let id = &self.session.walkthrough.steps[pick.cursor].id;
+            self.notify(format!("{} {id}", self.strings.toast_hunk_moved));
+        }
+    }
+
+    /// Drops state tied to the old terminal size: drags in progress and where
+    /// the scrollbars were. Scroll offsets are clamped on the next render.
+    pub fn handle_resize(&mut self) {
+        self.layout.stop_drag();
+        self.diff_scroll.set_track(None);
+        self.chat_scroll.set_track(None);
+    }
+
+    /// Records a newer version of the watched diff, to offer regenerating
+    /// once no generation is running. Changing back to the reviewed diff
+    /// withdraws the offer.
+    pub fn receive_diff_update(&mut self, diff_text: String, commits: Vec<CommitInfo>) {
+        self.diff_update = (self.session.diff_input.as_ref() != Some(&diff_text))
+            .then_some(DiffUpdate { diff_text, commits });
+    }
+
+    /// The diff update to offer now, if any: not over setup or while a
+    /// walkthrough is being generated.
+    pub fn offered_diff_update(&self) -> Option<&DiffUpdate> {
+        self.diff_update.as_ref().filter(|_| {
+            matches!(self.session.state, SessionState::Ready)
+                && !self.session.generation_in_progress
+        })
+    }
+
+    /// Regenerates the walkthrough for the updated diff. Unchanged hunks
+    /// keep their reviewed marks.
+    pub fn accept_diff_update(&mut self) {
+        let Some(update) = self.diff_update.take() else {
+            return;
+        };
+        if self.session.regenerate_with_diff(update.diff_text, update.commits) {
+            self.diff_scroll.reset();
+            self.chat_scroll.reset();
+            self.notify(self.strings.toast_regenerating_diff);
+        }
+    }
+
+    /// Step ids change when steps are rearranged, so per-step view state is
+    /// dropped.
+    fn after_step_edit(&mut self) {
+        self.hunk_cursor = None;
+        self.diff_selection = None;
+        self.diff_scroll.reset();
+        self.chat_scroll.reset();
+        self.announce_step();
+    }
+
+    /// Cycles the step list between all steps, hiding minor ones, and critical
+    /// ones only.
+    pub fn cycle_priority_filter(&mut self) {
+        if self.session.cycle_priority_filter() {
+            self.diff_scroll.reset();
+            self.chat_scroll.reset();
+            self.announce_step();
+        }
+        let filter = self.strings.priority_filter_label(self.session.priority_filter);
+        self.session.status_message = Some(format!("{} {}", self.strings.toast_showing, filter));
+    }
+
+    /// Jumps to the next step related to the current one, wrapping around, so
+    /// repeated presses walk through every related step.
+    pub fn jump_to_related(&mut self) {
+        let current = self.session.current_step;
+        let related = self.session.walkthrough.related_steps(current);
+        let target = related
+            .iter()
+            .find(|r| r.index > current)
+            .or_else(|| related.first());
+        match target {
+            Some(related) => self.go_to_step(related.index),
+            None => self.notify(self.strings.toast_no_related),
+        }
+    }
+
     pub fn toggle_help(&mut self) {
         self.show_help = !self.show_help;
     }
@@ -204,6 +1756,72 @@ impl<'a> App<'a> {
         self.scroll_to_current_match();
     }
 
+    /// Opens the walkthrough-wide search, starting from `input`.
+    pub fn open_global_search(&mut self, input: String) {
+        self.global_search = Some(GlobalSearch::new(input, &self.session.walkthrough.steps));
+    }
+
+    pub fn update_global_search(&mut self) {
+        if let Some(search) = self.global_search.as_mut() {
+            search.update(&self.session.walkthrough.steps);
+        }
+    }
+
+    /// Goes back to searching the current step with the dialog's query.
+    pub fn search_current_step(&mut self) {
+        let Some(global) = self.global_search.take() else {
+            return;
+        };
+        self.search.start();
+        self.search.input = global.input;
+        self.execute_search_incremental();
+    }
+
+    /// Goes to the step of the highlighted result and searches it for the
+    /// same query, with the result as the current match.
+    pub fn jump_to_global_match(&mut self) {
+        let Some(global) = self.global_search.take() else {
+            return;
+        };
+        let Some(result) = global.selected_match() else {
+            return;
+        };
+        self.go_to_step(result.step);
+        self.set_active_pane(Pane::Diff);
+        self.search.input = global.input.clone();
+        self.execute_search();
+        if let Some(index) = self.search.matches.iter().position(|m| m.line == result.line) {
+            self.search.current = index;
+            self.scroll_to_current_match();
+        }
+    }
+
+    pub fn open_step_finder(&mut self) {
+        self.step_finder = Some(StepFinder::new(&self.session.walkthrough.steps));
+    }
+
+    pub fn update_step_finder(&mut self) {
+        if let Some(finder) = self.step_finder.as_mut() {
+            finder.update(&self.session.walkthrough.steps);
+        }
+    }
+
+    /// Goes to the highlighted step, selecting the hunk whose file path
+    /// matched.
+    pub fn jump_to_found_step(&mut self) {
+        let Some(finder) = self.step_finder.take() else {
+            return;
+        };
+        let Some(found) = finder.selected_match() else {
+            return;
+        };
+        self.go_to_step(found.step);
+        if let Some(hunk) = found.hunk {
+            self.set_active_pane(Pane::Diff);
+            self.select_hunk(hunk);
+        }
+    }
+
     fn scroll_to_current_match(&mut self) {
         if let Some(m) = self.search.current_match() {
             let target = m.line.saturating_sub(3);
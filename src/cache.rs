@@ -0,0 +1,164 @@
+//! Generated walkthroughs kept for reruns on the same diff.
+//!
+//! Each walkthrough is saved to ~/.docent/cache/<key>.json, where the key
+//! hashes the model and the full generation prompt: the filtered diff,
+//! commits, and prompt options. Running docent again on the same diff then
+//! loads the steps instead of calling the API. Entries older than
+//! `cache.ttl_days` are ignored and removed, and only the newest
+//! `cache.max_entries` are kept.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::model::Step;
+
+/// The `cache` section of settings.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CacheSettings {
+    #[serde(default = "default_cache_enabled")]
+    pub enabled: bool,
+    /// Days a cached walkthrough is used for
+    #[serde(default = "default_cache_ttl_days")]
+    pub ttl_days: u32,
+    /// Cached walkthroughs kept; the oldest are removed first
+    #[serde(default = "default_cache_max_entries")]
+    pub max_entries: usize,
+}
+
+impl Default for CacheSettings {
+    fn default() -> Self {
+        Self {
+            enabled: default_cache_enabled(),
+            ttl_days: default_cache_ttl_days(),
+            max_entries: default_cache_max_entries(),
+        }
+    }
+}
+
+fn default_cache_enabled() -> bool {
+    true
+}
+
+fn default_cache_ttl_days() -> u32 {
+    7
+}
+
+fn default_cache_max_entries() -> usize {
+    50
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CacheEntry {
+    /// Seconds since the Unix epoch
+    saved_at: u64,
+    model: String,
+    steps: Vec<Step>,
+}
+
+/// FNV-1a of `parts`, separated by zero bytes, so the key stays the same
+/// across builds.
+pub fn hash_key(parts: &[&str]) -> String {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for (i, part) in parts.iter().enumerate() {
+        let separator = (i + 1 < parts.len()).then_some(0);
+        for byte in part.bytes().chain(separator) {
+            hash ^= u64::from(byte);
+            hash = hash.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+    format!("{hash:016x}")
+}
+
+/// The model and steps cached under `key`, unless caching is off or the
+/// entry has expired.
+pub fn load(key: &str, settings: &CacheSettings) -> Option<(String, Vec<Step>)> {
+    if !settings.enabled {
+        return None;
+    }
+    let path = cache_dir()?.join(format!("{key}.json"));
+    let entry: CacheEntry = serde_json::from_str(&fs::read_to_string(path).ok()?).ok()?;
+    (!expired(entry.saved_at, settings.ttl_days) && !entry.steps.is_empty())
+        .then_some((entry.model, entry.steps))
+}
+
+/// Caches `steps` under `key` and prunes old entries.
+pub fn save(
+    key: &str,
+    model: &str,
+    steps: &[Step],
+    settings: &CacheSettings,
+) -> Result<(), String> {
+    if !settings.enabled || steps.is_empty() {
+        return Ok(());
+    }
+    let dir = cache_dir().ok_or("Could not determine home directory")?;
+    fs::create_dir_all(&dir).map_err(|e| format!("Failed to create cache dir: {e}"))?;
+    let entry = CacheEntry {
+        saved_at: now(),
+        model: model.to_string(),
+        steps: steps.to_vec(),
+    };
+    let json =
+        serde_json::to_string(&entry).map_err(|e| format!("Failed to serialize cache: {e}"))?;
+    fs::write(dir.join(format!("{key}.json")), json)
+        .map_err(|e| format!("Failed to write cache: {e}"))?;
+    prune(&dir, settings);
+    Ok(())
+}
+
+/// Removes expired entries, then the oldest past `max_entries`.
+fn prune(dir: &Path, settings: &CacheSettings) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    let mut saved: Vec<(u64, PathBuf)> = entries
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter_map(|path| {
+            let json = fs::read_to_string(&path).ok()?;
+            let entry: CacheEntry = serde_json::from_str(&json).ok()?;
+            Some((entry.saved_at, path))
+        })
+        .collect();
+    saved.sort_by_key(|(saved_at, _)| std::cmp::Reverse(*saved_at));
+    for (i, (saved_at, path)) in saved.into_iter().enumerate() {
+        if i >= settings.max_entries || expired(saved_at, settings.ttl_days) {
+            let _ = fs::remove_file(path);
+        }
+    }
+}
+
+fn expired(saved_at: u64, ttl_days: u32) -> bool {
+    now().saturating_sub(saved_at) > u64::from(ttl_days) * 86_400
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
+
+fn cache_dir() -> Option<PathBuf> {
+    dirs::home_dir().map(|h| h.join(".docent").join("cache"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hash_key_separates_parts() {
+        assert_eq!(hash_key(&["ab", "c"]), hash_key(&["ab", "c"]));
+        assert_ne!(hash_key(&["ab", "c"]), hash_key(&["a", "bc"]));
+        assert_eq!(hash_key(&["x"]).len(), 16);
+    }
+
+    #[test]
+    fn expired_after_ttl_days() {
+        assert!(!expired(now(), 1));
+        assert!(expired(now() - 2 * 86_400, 1));
+        assert!(expired(now() - 1, 0));
+    }
+}
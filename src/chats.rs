@@ -0,0 +1,214 @@
+//! Chat conversations kept between runs.
+//!
+//! On exit, each step's questions and answers are saved to
+//! ~/.docent/chats/<key>.json, where the key identifies the repository and
+//! diff under review. When the same diff is reviewed again, steps covering
+//! the same changes get their conversation back, and later questions are
+//! asked with it as history. Files older than `chat.history_days` are
+//! removed.
+
+use std::fs;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::model::{Message, MessageRole, Step, Walkthrough};
+
+/// One step's conversation, minus the generated summary.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SavedChat {
+    /// `(file_path, content)` of each hunk in the step, sorted
+    pub hunks: Vec<(String, String)>,
+    pub messages: Vec<Message>,
+}
+
+/// The conversations of one review.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SavedChats {
+    /// Seconds since the Unix epoch
+    pub saved_at: u64,
+    pub chats: Vec<SavedChat>,
+    /// The conversation about the whole change
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub whole_change: Vec<Message>,
+}
+
+impl SavedChats {
+    /// Conversations of the steps that have any questions.
+    pub fn capture(walkthrough: &Walkthrough) -> Self {
+        let chats = walkthrough
+            .steps
+            .iter()
+            .filter_map(|step| {
+                let messages: Vec<Message> = step
+                    .messages
+                    .iter()
+                    .skip(usize::from(starts_with_summary(step)))
+                    .cloned()
+                    .collect();
+                (!messages.is_empty()).then(|| SavedChat {
+                    hunks: hunk_key(step),
+                    messages,
+                })
+            })
+            .collect();
+        Self {
+            saved_at: now(),
+            chats,
+            whole_change: walkthrough.messages.clone(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.chats.is_empty() && self.whole_change.is_empty()
+    }
+
+    /// Removes and returns the conversation saved for a step covering the
+    /// same changes as `step`.
+    pub fn take_for(&mut self, step: &Step) -> Option<Vec<Message>> {
+        let key = hunk_key(step);
+        let index = self.chats.iter().position(|chat| chat.hunks == key)?;
+        Some(self.chats.remove(index).messages)
+    }
+}
+
+/// The generated summary is the step's first message, from the assistant.
+fn starts_with_summary(step: &Step) -> bool {
+    step.messages
+        .first()
+        .is_some_and(|m| m.role == MessageRole::Assistant)
+}
+
+fn hunk_key(step: &Step) -> Vec<(String, String)> {
+    let mut hunks: Vec<(String, String)> = step
+        .hunks
+        .iter()
+        .map(|h| (h.file_path.clone(), h.content.clone()))
+        .collect();
+    hunks.sort();
+    hunks
+}
+
+/// Identifies a review by its working directory and diff text.
+pub fn review_key(cwd: &str, diff: &str) -> String {
+    crate::cache::hash_key(&[cwd, diff])
+}
+
+/// The conversations saved for `key`, unless older than `history_days`.
+pub fn load(key: &str, history_days: u32) -> Option<SavedChats> {
+    if history_days == 0 {
+        return None;
+    }
+    let path = chats_dir()?.join(format!("{key}.json"));
+    let saved: SavedChats = serde_json::from_str(&fs::read_to_string(path).ok()?).ok()?;
+    (!expired(saved.saved_at, history_days)).then_some(saved)
+}
+
+/// Saves the walkthrough's conversations under `key`, along with `unclaimed`
+/// ones loaded earlier for steps that no longer exist, and removes files past
+/// `history_days`.
+pub fn save(
+    key: &str,
+    walkthrough: &Walkthrough,
+    unclaimed: Option<&SavedChats>,
+    history_days: u32,
+) -> Result<(), String> {
+    if history_days == 0 {
+        return Ok(());
+    }
+    let dir = chats_dir().ok_or("Could not determine home directory")?;
+    prune(&dir, history_days);
+    let path = dir.join(format!("{key}.json"));
+    let mut saved = SavedChats::capture(walkthrough);
+    if let Some(unclaimed) = unclaimed {
+        // A step's current conversation supersedes what was saved for it
+        let keys: Vec<Vec<(String, String)>> = walkthrough.steps.iter().map(hunk_key).collect();
+        saved.chats.extend(
+            unclaimed
+                .chats
+                .iter()
+                .filter(|chat| !keys.contains(&chat.hunks))
+                .cloned(),
+        );
+        if saved.whole_change.is_empty() {
+            saved.whole_change = unclaimed.whole_change.clone();
+        }
+    }
+    if saved.is_empty() {
+        let _ = fs::remove_file(path);
+        return Ok(());
+    }
+    fs::create_dir_all(&dir).map_err(|e| format!("Failed to create chats dir: {e}"))?;
+    let json =
+        serde_json::to_string(&saved).map_err(|e| format!("Failed to serialize chats: {e}"))?;
+    fs::write(path, json).map_err(|e| format!("Failed to write chats: {e}"))
+}
+
+fn prune(dir: &PathBuf, history_days: u32) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for path in entries.filter_map(|entry| entry.ok().map(|e| e.path())) {
+        let saved_at = fs::read_to_string(&path)
+            .ok()
+            .and_then(|json| serde_json::from_str::<SavedChats>(&json).ok())
+            .map(|saved| saved.saved_at);
+        if saved_at.is_some_and(|at| expired(at, history_days)) {
+            let _ = fs::remove_file(path);
+        }
+    }
+}
+
+fn expired(saved_at: u64, history_days: u32) -> bool {
+    now().saturating_sub(saved_at) > u64::from(history_days) * 86_400
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
+
+fn chats_dir() -> Option<PathBuf> {
+    dirs::home_dir().map(|h| h.join(".docent").join("chats"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::mock_walkthrough;
+
+    #[test]
+    fn saved_chats_skip_summaries_and_match_steps_by_hunks() {
+        let mut walkthrough = mock_walkthrough();
+        walkthrough.steps[1].messages.push(Message::user("Why?"));
+        walkthrough.steps[1]
+            .messages
+            .push(Message::assistant("Because."));
+
+        walkthrough
+            .messages
+            .push(Message::user("Any migration risk?"));
+
+        let mut saved = SavedChats::capture(&walkthrough);
+        assert_eq!(saved.chats.len(), 1);
+        assert_eq!(saved.whole_change.len(), 1);
+
+        let mut regenerated = walkthrough.steps[1].clone();
+        regenerated.hunks.reverse();
+        let messages = saved.take_for(&regenerated).unwrap();
+        assert_eq!(messages.len(), 2);
+        assert_eq!(messages[0].content, "Why?");
+        assert!(saved.take_for(&regenerated).is_none());
+    }
+
+    #[test]
+    fn review_key_depends_on_directory_and_diff() {
+        let key = review_key("/repo", "diff");
+        assert_eq!(key, review_key("/repo", "diff"));
+        assert_ne!(key, review_key("/other", "diff"));
+        assert_ne!(key, review_key("/repo", "diff2"));
+        assert_eq!(key.len(), 16);
+    }
+}
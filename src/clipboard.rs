@@ -0,0 +1,71 @@
+//! Copying text to the system clipboard.
+//!
+//! Text goes to the terminal as an OSC 52 escape sequence, which most modern
+//! terminals turn into a clipboard write, including over SSH. Locally, the
+//! platform's clipboard command is also run, for terminals that ignore
+//! OSC 52.
+
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+
+/// Clipboard commands tried in order on a local session; the first one that
+/// runs wins.
+const COMMANDS: &[(&str, &[&str])] = &[
+    ("pbcopy", &[]),
+    ("wl-copy", &[]),
+    ("xclip", &["-selection", "clipboard"]),
+    ("xsel", &["--clipboard", "--input"]),
+    ("clip.exe", &[]),
+];
+
+/// Copies `text`, reporting an error only when no method could be tried.
+pub fn copy(text: &str) -> Result<(), String> {
+    let osc = write_osc52(text);
+    let local = std::env::var_os("SSH_CONNECTION").is_none() && copy_with_command(text);
+    if osc.is_ok() || local { Ok(()) } else { osc }
+}
+
+/// The OSC 52 sequence that sets the clipboard to `text`.
+fn osc52(text: &str) -> String {
+    format!("\x1b]52;c;{}\x07", STANDARD.encode(text))
+}
+
+fn write_osc52(text: &str) -> Result<(), String> {
+    let mut stdout = std::io::stdout();
+    stdout
+        .write_all(osc52(text).as_bytes())
+        .and_then(|()| stdout.flush())
+        .map_err(|e| format!("Failed to write to the terminal: {e}"))
+}
+
+fn copy_with_command(text: &str) -> bool {
+    COMMANDS.iter().any(|(program, args)| {
+        let Ok(mut child) = Command::new(program)
+            .args(*args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+        else {
+            return false;
+        };
+        let written = child
+            .stdin
+            .take()
+            .is_some_and(|mut stdin| stdin.write_all(text.as_bytes()).is_ok());
+        child.wait().is_ok_and(|status| status.success()) && written
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn osc52_encodes_text_as_base64() {
+        assert_eq!(osc52("hi\n"), "\x1b]52;c;aGkK\x07");
+    }
+}
@@ -1,34 +1,315 @@
+use std::str::FromStr;
+
 use ratatui::style::Color;
 
-// Borders
-pub const BORDER_INACTIVE: Color = Color::DarkGray;
-pub const BORDER_ACTIVE: Color = Color::Green;
-
-// Diff viewer
-pub const DIFF_ADDED: Color = Color::Green;
-pub const DIFF_REMOVED: Color = Color::Red;
-pub const DIFF_HUNK_HEADER: Color = Color::Cyan;
-pub const DIFF_FILE_HEADER: Color = Color::Magenta;
-
-// Chat
-pub const CHAT_ASSISTANT_BULLET: Color = Color::Rgb(199, 199, 199);
-pub const CHAT_ASSISTANT_TEXT: Color = Color::Rgb(199, 199, 199);
-pub const CHAT_ASSISTANT_BOLD: Color = Color::White;
-pub const CHAT_ASSISTANT_CODE: Color = Color::Rgb(147, 154, 207);
-pub const CHAT_USER_TEXT: Color = Color::White;
-pub const CHAT_USER_BG: Color = Color::Rgb(60, 60, 60);
-
-// Input
-pub const INPUT_PLACEHOLDER: Color = Color::DarkGray;
-pub const INPUT_CURSOR_FG: Color = Color::Black;
-pub const INPUT_CURSOR_BG: Color = Color::White;
-
-// Minimap
-pub const STEP_CURRENT: Color = Color::White;
-pub const STEP_COMPLETED: Color = Color::Green;
-pub const STEP_PENDING: Color = Color::DarkGray;
-
-// Search
-pub const SEARCH_MATCH: Color = Color::Rgb(100, 100, 50);
-pub const SEARCH_MATCH_CURRENT: Color = Color::Yellow;
-pub const SEARCH_MATCH_TEXT: Color = Color::Black;
+use crate::settings::{DiffColorSettings, DiffPalette, ThemeName, ThemeSettings};
+
+// Okabe-Ito blue/orange, distinguishable with the common forms of color blindness
+pub const DIFF_ADDED_COLORBLIND: Color = Color::Rgb(0, 114, 178);
+pub const DIFF_REMOVED_COLORBLIND: Color = Color::Rgb(230, 159, 0);
+
+/// Colors for every part of the UI, picked by role.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Theme {
+    // General
+    /// Dialog borders, headings, and the selection cursor
+    pub accent: Color,
+    /// Key names in help text
+    pub key: Color,
+    /// Secondary text and hints
+    pub dim: Color,
+    /// Emphasized text
+    pub text: Color,
+    pub success: Color,
+    pub error: Color,
+
+    // Borders
+    pub border_active: Color,
+    pub border_inactive: Color,
+
+    // Diff viewer
+    pub diff: DiffColors,
+    /// Background of the changed words within a removed or added line
+    pub diff_removed_emphasis: Color,
+    pub diff_added_emphasis: Color,
+    pub diff_hunk_header: Color,
+    pub diff_file_header: Color,
+    /// Background of the line under the diff's line cursor
+    pub diff_cursor_line: Color,
+
+    // Chat
+    pub chat_assistant_bullet: Color,
+    pub chat_assistant_text: Color,
+    pub chat_assistant_bold: Color,
+    pub chat_assistant_code: Color,
+    pub chat_user_text: Color,
+    pub chat_user_bg: Color,
+    pub chat_note: Color,
+
+    // Input
+    pub input_placeholder: Color,
+    pub input_cursor_fg: Color,
+    pub input_cursor_bg: Color,
+
+    // Minimap
+    pub step_current: Color,
+    pub step_completed: Color,
+    pub step_pending: Color,
+    pub priority_critical: Color,
+    pub priority_normal: Color,
+    pub priority_minor: Color,
+
+    // Search
+    pub search_match: Color,
+    pub search_match_current: Color,
+    pub search_match_text: Color,
+}
+
+impl Theme {
+    /// For dark terminal backgrounds.
+    pub const DARK: Self = Self {
+        accent: Color::Cyan,
+        key: Color::Yellow,
+        dim: Color::DarkGray,
+        text: Color::White,
+        success: Color::Green,
+        error: Color::Red,
+        border_active: Color::Green,
+        border_inactive: Color::DarkGray,
+        diff: DiffColors {
+            added: Color::Green,
+            removed: Color::Red,
+        },
+        diff_removed_emphasis: Color::Rgb(95, 0, 0),
+        diff_added_emphasis: Color::Rgb(0, 75, 0),
+        diff_hunk_header: Color::Cyan,
+        diff_file_header: Color::Magenta,
+        diff_cursor_line: Color::Rgb(48, 48, 48),
+        chat_assistant_bullet: Color::Rgb(199, 199, 199),
+        chat_assistant_text: Color::Rgb(199, 199, 199),
+        chat_assistant_bold: Color::White,
+        chat_assistant_code: Color::Rgb(147, 154, 207),
+        chat_user_text: Color::White,
+        chat_user_bg: Color::Rgb(60, 60, 60),
+        chat_note: Color::Yellow,
+        input_placeholder: Color::DarkGray,
+        input_cursor_fg: Color::Black,
+        input_cursor_bg: Color::White,
+        step_current: Color::White,
+        step_completed: Color::Green,
+        step_pending: Color::DarkGray,
+        priority_critical: Color::Red,
+        priority_normal: Color::Yellow,
+        priority_minor: Color::Blue,
+        search_match: Color::Rgb(100, 100, 50),
+        search_match_current: Color::Yellow,
+        search_match_text: Color::Black,
+    };
+
+    /// For light terminal backgrounds, where white and yellow text disappear.
+    pub const LIGHT: Self = Self {
+        accent: Color::Rgb(0, 95, 135),
+        key: Color::Rgb(175, 95, 0),
+        dim: Color::Rgb(118, 118, 118),
+        text: Color::Black,
+        success: Color::Rgb(0, 128, 0),
+        error: Color::Rgb(175, 0, 0),
+        border_active: Color::Rgb(0, 128, 0),
+        border_inactive: Color::Rgb(160, 160, 160),
+        diff: DiffColors {
+            added: Color::Rgb(0, 128, 0),
+            removed: Color::Rgb(175, 0, 0),
+        },
+        diff_removed_emphasis: Color::Rgb(255, 190, 190),
+        diff_added_emphasis: Color::Rgb(180, 235, 180),
+        diff_hunk_header: Color::Rgb(0, 95, 135),
+        diff_file_header: Color::Rgb(135, 0, 135),
+        diff_cursor_line: Color::Rgb(235, 235, 235),
+        chat_assistant_bullet: Color::Rgb(88, 88, 88),
+        chat_assistant_text: Color::Rgb(48, 48, 48),
+        chat_assistant_bold: Color::Black,
+        chat_assistant_code: Color::Rgb(68, 68, 160),
+        chat_user_text: Color::Black,
+        chat_user_bg: Color::Rgb(228, 228, 228),
+        chat_note: Color::Rgb(175, 95, 0),
+        input_placeholder: Color::Rgb(150, 150, 150),
+        input_cursor_fg: Color::White,
+        input_cursor_bg: Color::Black,
+        step_current: Color::Black,
+        step_completed: Color::Rgb(0, 128, 0),
+        step_pending: Color::Rgb(150, 150, 150),
+        priority_critical: Color::Rgb(175, 0, 0),
+        priority_normal: Color::Rgb(175, 95, 0),
+        priority_minor: Color::Rgb(0, 95, 175),
+        search_match: Color::Rgb(255, 255, 175),
+        search_match_current: Color::Rgb(255, 200, 0),
+        search_match_text: Color::Black,
+    };
+
+    /// Starts from the named theme, applies the diff palette and overrides, then
+    /// per-role overrides. Unknown roles and colors that don't parse are ignored.
+    pub fn from_settings(theme: &ThemeSettings, diff: &DiffColorSettings) -> Self {
+        let mut resolved = match theme.name {
+            ThemeName::Dark => Self::DARK,
+            ThemeName::Light => Self::LIGHT,
+            ThemeName::Auto if light_background() => Self::LIGHT,
+            ThemeName::Auto => Self::DARK,
+        };
+        resolved.diff = DiffColors::from_settings(diff, resolved.diff);
+        for (role, value) in &theme.colors {
+            if let Some(slot) = resolved.role_mut(role)
+                && let Ok(color) = Color::from_str(value)
+            {
+                *slot = color;
+            }
+        }
+        resolved
+    }
+
+    fn role_mut(&mut self, role: &str) -> Option<&mut Color> {
+        Some(match role {
+            "accent" => &mut self.accent,
+            "key" => &mut self.key,
+            "dim" => &mut self.dim,
+            "text" => &mut self.text,
+            "success" => &mut self.success,
+            "error" => &mut self.error,
+            "border_active" => &mut self.border_active,
+            "border_inactive" => &mut self.border_inactive,
+            "diff_added" => &mut self.diff.added,
+            "diff_removed" => &mut self.diff.removed,
+            "diff_removed_emphasis" => &mut self.diff_removed_emphasis,
+            "diff_added_emphasis" => &mut self.diff_added_emphasis,
+            "diff_hunk_header" => &mut self.diff_hunk_header,
+            "diff_file_header" => &mut self.diff_file_header,
+            "diff_cursor_line" => &mut self.diff_cursor_line,
+            "chat_assistant_bullet" => &mut self.chat_assistant_bullet,
+            "chat_assistant_text" => &mut self.chat_assistant_text,
+            "chat_assistant_bold" => &mut self.chat_assistant_bold,
+            "chat_assistant_code" => &mut self.chat_assistant_code,
+            "chat_user_text" => &mut self.chat_user_text,
+            "chat_user_bg" => &mut self.chat_user_bg,
+            "chat_note" => &mut self.chat_note,
+            "input_placeholder" => &mut self.input_placeholder,
+            "input_cursor_fg" => &mut self.input_cursor_fg,
+            "input_cursor_bg" => &mut self.input_cursor_bg,
+            "step_current" => &mut self.step_current,
+            "step_completed" => &mut self.step_completed,
+            "step_pending" => &mut self.step_pending,
+            "priority_critical" => &mut self.priority_critical,
+            "priority_normal" => &mut self.priority_normal,
+            "priority_minor" => &mut self.priority_minor,
+            "search_match" => &mut self.search_match,
+            "search_match_current" => &mut self.search_match_current,
+            "search_match_text" => &mut self.search_match_text,
+            _ => return None,
+        })
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::DARK
+    }
+}
+
+/// Whether the terminal advertises a light background. Many terminals set
+/// `COLORFGBG` to "fg;bg" with ANSI indices, where 7 and 15 are white.
+fn light_background() -> bool {
+    std::env::var("COLORFGBG").is_ok_and(|value| is_light_colorfgbg(&value))
+}
+
+fn is_light_colorfgbg(value: &str) -> bool {
+    matches!(value.rsplit(';').next(), Some("7" | "15"))
+}
+
+/// Resolved colors for added and removed diff lines.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DiffColors {
+    pub added: Color,
+    pub removed: Color,
+}
+
+impl Default for DiffColors {
+    fn default() -> Self {
+        Theme::DARK.diff
+    }
+}
+
+impl DiffColors {
+    /// Starts from the chosen palette and applies any per-color overrides.
+    /// The default palette is the theme's `base`. Overrides that don't parse
+    /// as a color are ignored.
+    pub fn from_settings(settings: &DiffColorSettings, base: Self) -> Self {
+        let palette = match settings.palette {
+            DiffPalette::Default => base,
+            DiffPalette::Colorblind => Self {
+                added: DIFF_ADDED_COLORBLIND,
+                removed: DIFF_REMOVED_COLORBLIND,
+            },
+        };
+        let parse = |value: &Option<String>| value.as_deref().and_then(|v| Color::from_str(v).ok());
+        Self {
+            added: parse(&settings.added).unwrap_or(palette.added),
+            removed: parse(&settings.removed).unwrap_or(palette.removed),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_colorblind_palette() {
+        let settings = DiffColorSettings {
+            palette: DiffPalette::Colorblind,
+            ..Default::default()
+        };
+        let colors = DiffColors::from_settings(&settings, DiffColors::default());
+        assert_eq!(colors.added, DIFF_ADDED_COLORBLIND);
+        assert_eq!(colors.removed, DIFF_REMOVED_COLORBLIND);
+    }
+
+    #[test]
+    fn test_overrides_apply_independently() {
+        let settings = DiffColorSettings {
+            palette: DiffPalette::Colorblind,
+            added: Some("#00ff00".to_string()),
+            removed: Some("not a color".to_string()),
+        };
+        let colors = DiffColors::from_settings(&settings, DiffColors::default());
+        assert_eq!(colors.added, Color::Rgb(0, 255, 0));
+        assert_eq!(colors.removed, DIFF_REMOVED_COLORBLIND);
+    }
+
+    #[test]
+    fn test_theme_overrides() {
+        let theme = ThemeSettings {
+            name: ThemeName::Light,
+            colors: [
+                ("accent", "blue"),
+                ("diff_removed", "#ff0000"),
+                ("no_such_role", "red"),
+                ("key", "not a color"),
+            ]
+            .into_iter()
+            .map(|(role, color)| (role.to_string(), color.to_string()))
+            .collect(),
+        };
+        let resolved = Theme::from_settings(&theme, &DiffColorSettings::default());
+        assert_eq!(resolved.accent, Color::Blue);
+        assert_eq!(resolved.key, Theme::LIGHT.key);
+        // The default diff palette follows the theme
+        assert_eq!(resolved.diff.added, Theme::LIGHT.diff.added);
+        assert_eq!(resolved.diff.removed, Color::Rgb(255, 0, 0));
+    }
+
+    #[test]
+    fn test_light_colorfgbg() {
+        assert!(is_light_colorfgbg("0;15"));
+        assert!(is_light_colorfgbg("0;default;7"));
+        assert!(!is_light_colorfgbg("15;0"));
+        assert!(!is_light_colorfgbg(""));
+    }
+}
@@ -0,0 +1,67 @@
+//! Platform differences in how the TUI gets keyboard input.
+//!
+//! When a diff is piped in (`git diff | docent`), stdin is the pipe, so key
+//! events have to come from the controlling terminal instead. On Unix that's
+//! `/dev/tty` (crossterm's `use-dev-tty` feature); on Windows it's the console
+//! input buffer `CONIN$`, which crossterm opens directly, so it works under
+//! both the legacy console host and ConPTY terminals.
+
+use std::fs::OpenOptions;
+use std::io;
+
+use crossterm::event::{Event, KeyEventKind};
+
+#[cfg(unix)]
+const CONSOLE_INPUT: &str = "/dev/tty";
+#[cfg(windows)]
+const CONSOLE_INPUT: &str = "CONIN$";
+
+/// Checks that keyboard input is available after stdin has been consumed by
+/// a piped diff, so we fail with a clear message instead of a blank screen.
+pub fn ensure_console_input() -> io::Result<()> {
+    OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open(CONSOLE_INPUT)
+        .map(|_| ())
+        .map_err(|e| {
+            io::Error::new(
+                e.kind(),
+                format!(
+                    "stdin is piped but no terminal is available for keyboard input ({CONSOLE_INPUT}: {e}).\n\
+                     Pass the diff as a file instead: docent changes.patch"
+                ),
+            )
+        })
+}
+
+/// Whether a terminal event should reach the input handler.
+///
+/// Windows reports key releases as separate events (Unix terminals never do),
+/// which would otherwise make every keypress act twice.
+pub fn is_actionable(event: &Event) -> bool {
+    !matches!(event, Event::Key(key) if key.kind == KeyEventKind::Release)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::{KeyCode, KeyEvent, KeyEventState, KeyModifiers};
+
+    fn key(kind: KeyEventKind) -> Event {
+        Event::Key(KeyEvent {
+            code: KeyCode::Char('j'),
+            modifiers: KeyModifiers::NONE,
+            kind,
+            state: KeyEventState::NONE,
+        })
+    }
+
+    #[test]
+    fn test_key_releases_are_ignored() {
+        assert!(is_actionable(&key(KeyEventKind::Press)));
+        assert!(is_actionable(&key(KeyEventKind::Repeat)));
+        assert!(!is_actionable(&key(KeyEventKind::Release)));
+        assert!(is_actionable(&Event::Resize(80, 24)));
+    }
+}
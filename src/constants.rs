@@ -4,6 +4,17 @@ use std::time::Duration;
 pub const EVENT_POLL_INTERVAL: Duration = Duration::from_millis(50);
 pub const EVENT_RECV_TIMEOUT: Duration = Duration::from_millis(100);
 
+// How often `--watch` reads the diff again
+pub const DIFF_WATCH_INTERVAL: Duration = Duration::from_secs(2);
+
+// Toast notifications
+pub const TOAST_DURATION: Duration = Duration::from_secs(4);
+pub const TOAST_MAX_VISIBLE: usize = 3;
+
+// Smallest terminal the UI is drawn in; below it a notice asks for more room
+pub const MIN_TERMINAL_WIDTH: u16 = 60;
+pub const MIN_TERMINAL_HEIGHT: u16 = 16;
+
 // Viewport calculations
 pub const VIEWPORT_HEIGHT_OFFSET: u16 = 5;
 pub const HELP_BAR_HEIGHT: u16 = 1;
@@ -20,6 +31,7 @@ pub const LEFT_PANE_MIN_PERCENT: u16 = 20;
 pub const LEFT_PANE_MAX_PERCENT: u16 = 80;
 pub const MINIMAP_MIN_PERCENT: u16 = 15;
 pub const MINIMAP_MAX_PERCENT: u16 = 85;
+pub const FILE_TREE_PERCENT: u16 = 25;
 
 // Dialog sizing (percentages)
 pub const LOADING_DIALOG_WIDTH: u16 = 60;
@@ -30,3 +42,35 @@ pub const SETUP_DIALOG_WIDTH: u16 = 55;
 pub const SETUP_DIALOG_HEIGHT: u16 = 50;
 pub const HELP_DIALOG_WIDTH: u16 = 60;
 pub const HELP_DIALOG_HEIGHT: u16 = 70;
+pub const USAGE_DIALOG_WIDTH: u16 = 56;
+pub const USAGE_DIALOG_HEIGHT: u16 = 12;
+pub const FILE_SELECT_DIALOG_WIDTH: u16 = 70;
+pub const FILE_SELECT_DIALOG_HEIGHT: u16 = 70;
+pub const SPLIT_DIALOG_WIDTH: u16 = 60;
+pub const SPLIT_DIALOG_HEIGHT: u16 = 40;
+pub const PARTS_DIALOG_WIDTH: u16 = 60;
+pub const PARTS_DIALOG_HEIGHT: u16 = 60;
+pub const QUICK_ACTIONS_DIALOG_WIDTH: u16 = 50;
+pub const QUICK_ACTIONS_DIALOG_HEIGHT: u16 = 35;
+pub const HUNK_SPLIT_DIALOG_WIDTH: u16 = 60;
+pub const HUNK_SPLIT_DIALOG_HEIGHT: u16 = 50;
+pub const HUNK_MOVE_DIALOG_WIDTH: u16 = 60;
+pub const HUNK_MOVE_DIALOG_HEIGHT: u16 = 60;
+pub const FEEDBACK_DIALOG_WIDTH: u16 = 50;
+pub const FEEDBACK_DIALOG_HEIGHT: u16 = 30;
+pub const NOTE_DIALOG_WIDTH: u16 = 60;
+pub const NOTE_DIALOG_HEIGHT: u16 = 50;
+pub const FLAG_DIALOG_WIDTH: u16 = 60;
+pub const FLAG_DIALOG_HEIGHT: u16 = 50;
+pub const REGENERATE_DIALOG_WIDTH: u16 = 60;
+pub const REGENERATE_DIALOG_HEIGHT: u16 = 35;
+pub const GLOBAL_SEARCH_DIALOG_WIDTH: u16 = 80;
+pub const GLOBAL_SEARCH_DIALOG_HEIGHT: u16 = 70;
+pub const STEP_FINDER_DIALOG_WIDTH: u16 = 70;
+pub const STEP_FINDER_DIALOG_HEIGHT: u16 = 60;
+pub const FILE_VIEW_DIALOG_WIDTH: u16 = 90;
+pub const FILE_VIEW_DIALOG_HEIGHT: u16 = 85;
+pub const RECOVERY_DIALOG_WIDTH: u16 = 60;
+pub const RECOVERY_DIALOG_HEIGHT: u16 = 35;
+pub const DIFF_UPDATE_DIALOG_WIDTH: u16 = 60;
+pub const DIFF_UPDATE_DIALOG_HEIGHT: u16 = 30;
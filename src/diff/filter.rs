@@ -1,6 +1,10 @@
+use std::collections::HashSet;
+
 use globset::{Glob, GlobSet, GlobSetBuilder};
 use thiserror::Error;
 
+use super::IgnoreFile;
+
 #[derive(Debug, Error)]
 pub enum FilterError {
     #[error("invalid glob pattern '{0}': {1}")]
@@ -14,10 +18,17 @@ pub enum FilterError {
 /// Patterns use glob syntax (e.g., "*.clj", "src/**/*.rs", "test_*.py").
 /// - If include patterns are specified, a path must match at least one to pass
 /// - If exclude patterns are specified, a path must not match any to pass
+/// - Paths excluded by name (e.g. unchecked in file selection) never pass
+/// - Paths on the denylist (generated and vendored files) don't pass unless an
+///   include pattern names them explicitly
+/// - Paths the project's `.docentignore` ignores never pass
 #[derive(Debug, Clone, Default)]
 pub struct FileFilter {
     include: Option<GlobSet>,
     exclude: Option<GlobSet>,
+    excluded_files: HashSet<String>,
+    denylist: Option<GlobSet>,
+    ignore_file: Option<IgnoreFile>,
 }
 
 impl FileFilter {
@@ -27,12 +38,60 @@ impl FileFilter {
     pub fn new(include: &[String], exclude: &[String]) -> Result<Self, FilterError> {
         let include = Self::build_glob_set(include)?;
         let exclude = Self::build_glob_set(exclude)?;
-        Ok(Self { include, exclude })
+        Ok(Self {
+            include,
+            exclude,
+            excluded_files: HashSet::new(),
+            denylist: None,
+            ignore_file: None,
+        })
+    }
+
+    /// Adds the default denylist of generated and vendored file patterns.
+    pub fn with_denylist(mut self, patterns: &[String]) -> Result<Self, FilterError> {
+        self.denylist = Self::build_glob_set(patterns)?;
+        Ok(self)
+    }
+
+    /// Adds the patterns of a `.docentignore` file.
+    pub fn with_ignore_file(mut self, ignore_file: IgnoreFile) -> Self {
+        self.ignore_file = Some(ignore_file);
+        self
+    }
+
+    /// This filter without its denylist, for including skipped files anyway.
+    pub fn without_denylist(&self) -> Self {
+        Self {
+            denylist: None,
+            ..self.clone()
+        }
+    }
+
+    /// Whether the path is skipped only because it is on the denylist.
+    pub fn is_denied(&self, path: &str) -> bool {
+        let explicitly_included = self.include.as_ref().is_some_and(|set| set.is_match(path));
+        !explicitly_included && self.denylist.as_ref().is_some_and(|set| set.is_match(path))
+    }
+
+    /// Create a filter that passes everything except the given exact paths.
+    pub fn excluding_files(paths: impl IntoIterator<Item = String>) -> Self {
+        Self::default().and_excluding_files(paths)
+    }
+
+    /// This filter, additionally excluding the given exact paths.
+    pub fn and_excluding_files(mut self, paths: impl IntoIterator<Item = String>) -> Self {
+        self.excluded_files.extend(paths);
+        self
     }
 
-    /// Returns true if no patterns are specified.
+    /// Returns true if no patterns, excluded paths, denylist, or ignore file
+    /// are specified.
     pub fn is_empty(&self) -> bool {
-        self.include.is_none() && self.exclude.is_none()
+        self.include.is_none()
+            && self.exclude.is_none()
+            && self.excluded_files.is_empty()
+            && self.denylist.is_none()
+            && self.ignore_file.is_none()
     }
 
     /// Check if a path passes the filter.
@@ -40,6 +99,16 @@ impl FileFilter {
     /// - If include patterns exist, path must match at least one
     /// - If exclude patterns exist, path must not match any
     pub fn matches(&self, path: &str) -> bool {
+        if self.excluded_files.contains(path)
+            || self.is_denied(path)
+            || self
+                .ignore_file
+                .as_ref()
+                .is_some_and(|f| f.is_ignored(path))
+        {
+            return false;
+        }
+
         // If include patterns specified, path must match at least one
         if let Some(ref set) = self.include
             && !set.is_match(path)
@@ -112,6 +181,44 @@ mod tests {
         assert!(!filter.matches("main.rs"));
     }
 
+    #[test]
+    fn test_excluding_files() {
+        let filter = FileFilter::excluding_files(["Cargo.lock".to_string()]);
+        assert!(!filter.is_empty());
+        assert!(!filter.matches("Cargo.lock"));
+        assert!(filter.matches("sub/Cargo.lock"));
+        assert!(filter.matches("src/main.rs"));
+    }
+
+    #[test]
+    fn test_denylist() {
+        let denylist = ["**/Cargo.lock".to_string(), "**/vendor/**".to_string()];
+        let filter = FileFilter::default().with_denylist(&denylist).unwrap();
+        assert!(!filter.matches("Cargo.lock"));
+        assert!(!filter.matches("crates/foo/Cargo.lock"));
+        assert!(!filter.matches("vendor/lib/a.go"));
+        assert!(filter.matches("src/main.rs"));
+        assert!(filter.without_denylist().matches("Cargo.lock"));
+
+        // An explicit include pattern overrides the denylist
+        let filter = FileFilter::new(&["*.lock".to_string()], &[])
+            .unwrap()
+            .with_denylist(&denylist)
+            .unwrap();
+        assert!(filter.matches("Cargo.lock"));
+    }
+
+    #[test]
+    fn test_ignore_file() {
+        let ignore = IgnoreFile::parse("*.snap\n").unwrap();
+        let filter = FileFilter::new(&["tests/**".to_string()], &[])
+            .unwrap()
+            .with_ignore_file(ignore);
+        assert!(!filter.is_empty());
+        assert!(filter.matches("tests/parse.rs"));
+        assert!(!filter.matches("tests/snapshots/parse.snap"));
+    }
+
     #[test]
     fn test_invalid_pattern_error() {
         let result = FileFilter::new(&["[invalid".to_string()], &[]);
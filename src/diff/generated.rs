@@ -0,0 +1,99 @@
+//! Lockfiles and generated code.
+//!
+//! Their hunks rarely need a model to explain them, so the generator keeps
+//! them out of the prompt and gathers them in one minor step at the end of
+//! the walkthrough. A file counts as generated when its path matches a
+//! common lockfile or build output name, or when a hunk carries one of the
+//! markers code generators write into their output.
+
+use std::sync::LazyLock;
+
+use globset::{Glob, GlobSet, GlobSetBuilder};
+
+use super::ParsedHunk;
+
+/// Paths of lockfiles and generated output.
+const GENERATED_PATTERNS: &[&str] = &[
+    "**/Cargo.lock",
+    "**/package-lock.json",
+    "**/yarn.lock",
+    "**/pnpm-lock.yaml",
+    "**/Gemfile.lock",
+    "**/poetry.lock",
+    "**/composer.lock",
+    "**/go.sum",
+    "**/*.pb.go",
+    "**/*_pb2.py",
+    "**/*.min.js",
+    "**/*.min.css",
+    "dist/**",
+];
+
+/// Markers generators write at the top of their output.
+const GENERATED_MARKERS: &[&str] = &["@generated", "DO NOT EDIT"];
+
+/// Lines from the top of a file searched for a marker.
+const MARKER_LINES: usize = 5;
+
+static GENERATED_SET: LazyLock<GlobSet> = LazyLock::new(|| {
+    let mut builder = GlobSetBuilder::new();
+    for pattern in GENERATED_PATTERNS {
+        builder.add(Glob::new(pattern).expect("generated file patterns are valid"));
+    }
+    builder.build().expect("generated file patterns are valid")
+});
+
+/// Whether `path` names a lockfile or common generated output.
+pub fn is_generated_path(path: &str) -> bool {
+    GENERATED_SET.is_match(path)
+}
+
+/// Whether the hunk is in a lockfile or generated file. Markers are only
+/// found in hunks that start at the top of the file.
+pub fn is_generated(hunk: &ParsedHunk) -> bool {
+    is_generated_path(&hunk.file_path)
+        || (hunk.start_line <= 1
+            && hunk
+                .content
+                .lines()
+                .skip(1)
+                .take(MARKER_LINES)
+                .any(|line| GENERATED_MARKERS.iter().any(|marker| line.contains(marker))))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hunk(file_path: &str, content: &str) -> ParsedHunk {
+        ParsedHunk {
+            index: 1,
+            file_path: file_path.to_string(),
+            start_line: 1,
+            end_line: 1,
+            content: content.to_string(),
+            symbol: None,
+        }
+    }
+
+    #[test]
+    fn lockfiles_and_generated_output_are_detected() {
+        assert!(is_generated_path("Cargo.lock"));
+        assert!(is_generated_path("web/package-lock.json"));
+        assert!(is_generated_path("api/v1/service.pb.go"));
+        assert!(is_generated_path("dist/app.js"));
+        assert!(!is_generated_path("src/dist/mod.rs"));
+        assert!(!is_generated_path("src/main.rs"));
+    }
+
+    #[test]
+    fn generator_markers_are_detected() {
+        let marked = "@@ -0,0 +1,2 @@\n+// Code generated by stringer. DO NOT EDIT.\n+package x\n";
+        assert!(is_generated(&hunk("kind_string.go", marked)));
+        let plain = "@@ -1 +1 @@\n-let a = 1;\n+let a = 2;\n";
+        assert!(!is_generated(&hunk("src/lib.rs", plain)));
+        let mut deep = hunk("src/lib.rs", marked);
+        deep.start_line = 40;
+        assert!(!is_generated(&deep));
+    }
+}
@@ -0,0 +1,168 @@
+//! Syntax highlighting for hunks, with the highlight queries tree-sitter
+//! grammars ship.
+//!
+//! A hunk is only a fragment of its file, so each side of it is parsed on
+//! its own: the old side (context and removed lines) and the new side
+//! (context and added lines). Parsing is forgiving enough that keywords,
+//! strings, and comments are still found, and every line takes the
+//! highlights of the side it belongs to.
+
+use std::ops::Range;
+use std::sync::OnceLock;
+
+use tree_sitter::{Parser, Query, QueryCursor, StreamingIterator};
+
+use super::symbols::Lang;
+
+/// Kinds of highlight reported, matched against the first part of a query's
+/// capture names (`function.method` is a `function`). Other captures, like
+/// variables and punctuation, are left plain.
+const KINDS: &[&str] = &[
+    "attribute",
+    "comment",
+    "constant",
+    "constructor",
+    "function",
+    "keyword",
+    "number",
+    "property",
+    "string",
+    "type",
+];
+
+/// A highlighted byte range within a line, and its kind from `KINDS`.
+pub type Highlight = (Range<usize>, &'static str);
+
+/// The highlights on each line of a hunk's content, indexed like
+/// `content.lines()`, with ranges counting the `+`/`-` marker. `None` when
+/// the file's language has no grammar.
+pub fn highlight_hunk(file_path: &str, content: &str) -> Option<Vec<Vec<Highlight>>> {
+    let lang = Lang::from_path(file_path)?;
+    let query = query(lang)?;
+    let mut parser = Parser::new();
+    parser.set_language(&lang.language()).ok()?;
+
+    let lines: Vec<&str> = content.lines().collect();
+    let mut highlights = vec![Vec::new(); lines.len()];
+    for side in [b'-', b'+'] {
+        // The side's lines without their markers, joined as one source
+        let rows: Vec<usize> = (0..lines.len())
+            .filter(|&i| match lines[i].as_bytes().first() {
+                None | Some(b' ') => true,
+                Some(&marker) => marker == side,
+            })
+            .collect();
+        let mut source = String::new();
+        let mut starts = Vec::with_capacity(rows.len());
+        for &i in &rows {
+            starts.push(source.len());
+            source.push_str(lines[i].get(1..).unwrap_or(""));
+            source.push('\n');
+        }
+        let Some(tree) = parser.parse(&source, None) else {
+            continue;
+        };
+
+        // The first capture of each byte decides its kind
+        let mut kinds: Vec<Option<&'static str>> = vec![None; source.len()];
+        let mut cursor = QueryCursor::new();
+        let mut captures = cursor.captures(query, tree.root_node(), source.as_bytes());
+        while let Some((found, index)) = captures.next() {
+            let capture = found.captures[*index];
+            let name = query.capture_names()[capture.index as usize];
+            let Some(kind) = KINDS.iter().find(|k| name.split('.').next() == Some(**k)) else {
+                continue;
+            };
+            for byte in &mut kinds[capture.node.byte_range()] {
+                byte.get_or_insert(kind);
+            }
+        }
+
+        for (&i, &start) in rows.iter().zip(&starts) {
+            // Context lines are on both sides; the new side's highlights win
+            highlights[i].clear();
+            let end = start + lines[i].len().saturating_sub(1);
+            let mut byte = start;
+            while byte < end {
+                let kind = kinds[byte];
+                let run_end = (byte..end).find(|&b| kinds[b] != kind).unwrap_or(end);
+                if let Some(kind) = kind {
+                    highlights[i].push((byte - start + 1..run_end - start + 1, kind));
+                }
+                byte = run_end;
+            }
+        }
+    }
+    Some(highlights)
+}
+
+/// The compiled highlight query for `lang`, or `None` if it doesn't compile
+/// against the grammar.
+fn query(lang: Lang) -> Option<&'static Query> {
+    static QUERIES: [OnceLock<Option<Query>>; 6] = [const { OnceLock::new() }; 6];
+    let (slot, source) = match lang {
+        Lang::Rust => (0, tree_sitter_rust::HIGHLIGHTS_QUERY.to_string()),
+        Lang::Python => (1, tree_sitter_python::HIGHLIGHTS_QUERY.to_string()),
+        Lang::JavaScript => (2, tree_sitter_javascript::HIGHLIGHT_QUERY.to_string()),
+        // TypeScript's query only adds to JavaScript's, and comes first so
+        // its patterns take precedence
+        Lang::TypeScript => (3, typescript_query()),
+        Lang::Tsx => (4, typescript_query()),
+        Lang::Go => (5, tree_sitter_go::HIGHLIGHTS_QUERY.to_string()),
+    };
+    QUERIES[slot]
+        .get_or_init(|| Query::new(&lang.language(), &source).ok())
+        .as_ref()
+}
+
+fn typescript_query() -> String {
+    format!(
+        "{}\n{}",
+        tree_sitter_typescript::HIGHLIGHTS_QUERY,
+        tree_sitter_javascript::HIGHLIGHT_QUERY
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kinds_on<'a>(line: &'a str, highlights: &[Highlight]) -> Vec<(&'a str, &'static str)> {
+        highlights
+            .iter()
+            .map(|(range, kind)| (&line[range.clone()], *kind))
+            .collect()
+    }
+
+    #[test]
+    fn test_every_grammar_query_compiles() {
+        for path in ["a.rs", "a.py", "a.js", "a.ts", "a.tsx", "a.go"] {
+            let lang = Lang::from_path(path).unwrap();
+            assert!(query(lang).is_some(), "{path}");
+        }
+    }
+
+    #[test]
+    fn test_highlights_each_side_of_a_hunk() {
+        let content =
+            "@@ -1,3 +1,3 @@\n fn main() {\n-    let x = \"old\";\n+    let x = 42; // new\n }";
+        let lines: Vec<&str> = content.lines().collect();
+        let highlights = highlight_hunk("src/main.rs", content).unwrap();
+        assert_eq!(highlights.len(), lines.len());
+        assert!(highlights[0].is_empty());
+
+        let context = kinds_on(lines[1], &highlights[1]);
+        assert!(context.contains(&("fn", "keyword")));
+        assert!(context.contains(&("main", "function")));
+        let removed = kinds_on(lines[2], &highlights[2]);
+        assert!(removed.contains(&("let", "keyword")));
+        assert!(removed.contains(&("\"old\"", "string")));
+        let added = kinds_on(lines[3], &highlights[3]);
+        assert!(added.contains(&("// new", "comment")));
+    }
+
+    #[test]
+    fn test_unknown_languages_are_not_highlighted() {
+        assert!(highlight_hunk("notes.txt", "@@ -1 +1 @@\n-a\n+b").is_none());
+    }
+}
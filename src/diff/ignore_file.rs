@@ -0,0 +1,131 @@
+//! `.docentignore`: files a project never wants in a walkthrough.
+//!
+//! The file sits at the repository root and uses gitignore syntax. Blank
+//! lines and `#` comments are skipped, a pattern without a slash matches at
+//! any depth, a leading slash anchors it to the root, a trailing slash
+//! matches directories only, and `!` re-includes what an earlier pattern
+//! ignored. Ignoring a directory ignores everything under it.
+
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use globset::{GlobBuilder, GlobSet, GlobSetBuilder};
+
+use super::FilterError;
+
+/// Name of the ignore file at the repository root.
+pub const IGNORE_FILE: &str = ".docentignore";
+
+/// Compiled patterns of a `.docentignore` file.
+#[derive(Debug, Clone)]
+pub struct IgnoreFile {
+    set: GlobSet,
+    /// Whether each glob in `set`, in file order, re-includes
+    negated: Vec<bool>,
+}
+
+impl IgnoreFile {
+    /// Reads `.docentignore` from `root`, if there is one.
+    pub fn load(root: &Path) -> Result<Option<Self>, FilterError> {
+        match fs::read_to_string(root.join(IGNORE_FILE)) {
+            Ok(contents) => Self::parse(&contents).map(Some),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(FilterError::InvalidPattern(
+                IGNORE_FILE.to_string(),
+                e.to_string(),
+            )),
+        }
+    }
+
+    /// Compiles the patterns of an ignore file's contents.
+    pub fn parse(contents: &str) -> Result<Self, FilterError> {
+        let mut builder = GlobSetBuilder::new();
+        let mut negated = Vec::new();
+        for line in contents.lines() {
+            let line = line.trim_end();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (negate, pattern) = match line.strip_prefix('!') {
+                Some(rest) => (true, rest),
+                None => (false, line.strip_prefix('\\').unwrap_or(line)),
+            };
+            for glob in globs_for(pattern) {
+                let glob = GlobBuilder::new(&glob)
+                    .literal_separator(true)
+                    .build()
+                    .map_err(|e| FilterError::InvalidPattern(line.to_string(), e.to_string()))?;
+                builder.add(glob);
+                negated.push(negate);
+            }
+        }
+        let set = builder
+            .build()
+            .map_err(|e| FilterError::InvalidPattern(IGNORE_FILE.to_string(), e.to_string()))?;
+        Ok(Self { set, negated })
+    }
+
+    /// Whether the last pattern matching `path` ignores it.
+    pub fn is_ignored(&self, path: &str) -> bool {
+        self.set
+            .matches(path)
+            .into_iter()
+            .max()
+            .is_some_and(|i| !self.negated[i])
+    }
+}
+
+/// Globs matching what a gitignore `pattern` covers: the path itself (unless
+/// the pattern names a directory) and everything under it.
+fn globs_for(pattern: &str) -> Vec<String> {
+    let (pattern, dir_only) = match pattern.strip_suffix('/') {
+        Some(dir) => (dir, true),
+        None => (pattern, false),
+    };
+    let base = match pattern.strip_prefix('/') {
+        Some(anchored) => anchored.to_string(),
+        None if pattern.contains('/') => pattern.to_string(),
+        None => format!("**/{pattern}"),
+    };
+    let under = format!("{base}/**");
+    if dir_only {
+        vec![under]
+    } else {
+        vec![base, under]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn patterns_follow_gitignore_rules() {
+        let ignore = IgnoreFile::parse(
+            "# generated\n\
+             *.lock\n\
+             /build\n\
+             snapshots/\n\
+             docs/*.md\n\
+             !docs/README.md\n",
+        )
+        .unwrap();
+        assert!(ignore.is_ignored("Cargo.lock"));
+        assert!(ignore.is_ignored("crates/app/Cargo.lock"));
+        assert!(ignore.is_ignored("build/out.js"));
+        assert!(!ignore.is_ignored("src/build/mod.rs"));
+        assert!(ignore.is_ignored("tests/snapshots/a.snap"));
+        assert!(!ignore.is_ignored("snapshots"));
+        assert!(ignore.is_ignored("docs/guide.md"));
+        assert!(!ignore.is_ignored("docs/api/guide.md"));
+        assert!(!ignore.is_ignored("docs/README.md"));
+        assert!(!ignore.is_ignored("src/main.rs"));
+    }
+
+    #[test]
+    fn invalid_pattern_is_reported() {
+        let result = IgnoreFile::parse("src/[oops\n");
+        assert!(matches!(result, Err(FilterError::InvalidPattern(line, _)) if line == "src/[oops"));
+    }
+}
@@ -0,0 +1,378 @@
+//! Interdiffs: what changed between two revisions of the same change.
+//!
+//! Both diffs are read as edits to the same base. Overlapping hunks are
+//! replayed onto the base lines they cover, and the two resulting versions
+//! of that region are diffed against each other. Hunks only one revision
+//! has become plain additions (new since the first revision) or reversed
+//! hunks (dropped since the first revision).
+
+use std::collections::BTreeMap;
+
+use super::parse_hunk_header;
+use super::parser::{DiffParseError, ParsedDiff};
+
+/// Context lines kept around changes at the edges of each interdiff hunk.
+const CONTEXT_LINES: usize = 3;
+
+/// Line-diff table size above which a region is shown as fully replaced.
+const MAX_DIFF_CELLS: usize = 4_000_000;
+
+/// One hunk, as edits to base lines `base_start..base_end` (1-based).
+#[derive(Debug, Clone)]
+struct Edit {
+    base_start: usize,
+    base_end: usize,
+    /// Base lines in the range, each with whether this revision removes it
+    base: Vec<(String, bool)>,
+    /// Lines inserted before each base line, plus one slot for the end
+    inserts: Vec<Vec<String>>,
+}
+
+impl Edit {
+    fn parse(content: &str) -> Option<Self> {
+        let mut lines = content.lines();
+        let (old_start, _) = parse_hunk_header(lines.next()?)?;
+        let mut base = Vec::new();
+        let mut inserts: Vec<Vec<String>> = vec![vec![]];
+        for line in lines {
+            let (marker, text) = line.split_at(line.len().min(1));
+            match marker {
+                "+" => inserts.last_mut()?.push(text.to_string()),
+                "-" => {
+                    base.push((text.to_string(), true));
+                    inserts.push(vec![]);
+                }
+                "\\" => {}
+                _ => {
+                    base.push((text.to_string(), false));
+                    inserts.push(vec![]);
+                }
+            }
+        }
+        // An empty old side ("-5,0") inserts after line 5
+        let base_start = if base.is_empty() {
+            old_start + 1
+        } else {
+            old_start.max(1)
+        };
+        Some(Self {
+            base_start,
+            base_end: base_start + base.len(),
+            base,
+            inserts,
+        })
+    }
+
+    /// Lines this hunk adds minus lines it removes.
+    fn delta(&self) -> isize {
+        let added: usize = self.inserts.iter().map(Vec::len).sum();
+        let removed = self.base.iter().filter(|(_, removed)| *removed).count();
+        added as isize - removed as isize
+    }
+
+    fn touches(&self, start: usize, end: usize) -> bool {
+        self.base_start <= end && start <= self.base_end
+    }
+}
+
+/// The edits each revision makes to one file.
+#[derive(Debug, Default)]
+struct FileEdits {
+    old: Vec<Edit>,
+    new: Vec<Edit>,
+}
+
+/// Computes the changes between two diffs of the same change, as a unified
+/// diff. Returns an empty string when the revisions make identical changes.
+pub fn interdiff(old_diff: &str, new_diff: &str) -> Result<String, DiffParseError> {
+    let old = ParsedDiff::parse(old_diff)?;
+    let new = ParsedDiff::parse(new_diff)?;
+
+    // Files in the order the new revision lists them, then dropped files
+    let mut order: Vec<String> = Vec::new();
+    let mut files: BTreeMap<String, FileEdits> = BTreeMap::new();
+    for (hunks, is_new) in [(&new.hunks, true), (&old.hunks, false)] {
+        for hunk in hunks {
+            let Some(edit) = Edit::parse(&hunk.content) else {
+                continue;
+            };
+            if !files.contains_key(&hunk.file_path) {
+                order.push(hunk.file_path.clone());
+            }
+            let file = files.entry(hunk.file_path.clone()).or_default();
+            if is_new {
+                file.new.push(edit);
+            } else {
+                file.old.push(edit);
+            }
+        }
+    }
+
+    let mut out = String::new();
+    for path in order {
+        let file = &files[&path];
+        let hunks = file_interdiff(file);
+        if hunks.is_empty() {
+            continue;
+        }
+        out.push_str(&format!(
+            "diff --git a/{path} b/{path}\n--- a/{path}\n+++ b/{path}\n"
+        ));
+        for hunk in hunks {
+            out.push_str(&hunk);
+        }
+    }
+    Ok(out)
+}
+
+fn file_interdiff(file: &FileEdits) -> Vec<String> {
+    let mut hunks = Vec::new();
+    let mut old_used = vec![false; file.old.len()];
+    let mut new_used = vec![false; file.new.len()];
+
+    for seed in 0..file.new.len() + file.old.len() {
+        let seeded = if seed < file.new.len() {
+            &mut new_used[seed]
+        } else {
+            &mut old_used[seed - file.new.len()]
+        };
+        if *seeded {
+            continue;
+        }
+        *seeded = true;
+        let seed_edit = if seed < file.new.len() {
+            &file.new[seed]
+        } else {
+            &file.old[seed - file.new.len()]
+        };
+
+        // Grow the region until no hunk from either revision touches it
+        let (mut start, mut end) = (seed_edit.base_start, seed_edit.base_end);
+        loop {
+            let mut grew = false;
+            for (edits, used) in [(&file.old, &mut old_used), (&file.new, &mut new_used)] {
+                for (edit, used) in edits.iter().zip(used.iter_mut()) {
+                    if !*used && edit.touches(start, end) {
+                        *used = true;
+                        start = start.min(edit.base_start);
+                        end = end.max(edit.base_end);
+                        grew = true;
+                    }
+                }
+            }
+            if !grew {
+                break;
+            }
+        }
+
+        let old_edits: Vec<&Edit> = file.old.iter().filter(|e| e.touches(start, end)).collect();
+        let new_edits: Vec<&Edit> = file.new.iter().filter(|e| e.touches(start, end)).collect();
+        let base = base_lines(&old_edits, &new_edits, start, end);
+        let old_image = apply(&old_edits, &base, start);
+        let new_image = apply(&new_edits, &base, start);
+        if old_image == new_image {
+            continue;
+        }
+
+        let old_line = offset(&file.old, start);
+        let new_line = offset(&file.new, start);
+        hunks.push((
+            start,
+            render_hunk(&old_image, &new_image, old_line, new_line),
+        ));
+    }
+
+    hunks.sort_by_key(|(start, _)| *start);
+    hunks.into_iter().map(|(_, hunk)| hunk).collect()
+}
+
+/// Base text for lines `start..end`, taken from whichever hunk covers each.
+fn base_lines(old: &[&Edit], new: &[&Edit], start: usize, end: usize) -> Vec<String> {
+    (start..end)
+        .map(|line| {
+            old.iter()
+                .chain(new)
+                .find(|e| (e.base_start..e.base_end).contains(&line))
+                .map(|e| e.base[line - e.base_start].0.clone())
+                .unwrap_or_default()
+        })
+        .collect()
+}
+
+/// The region `start..start + base.len()` as one revision leaves it.
+fn apply(edits: &[&Edit], base: &[String], start: usize) -> Vec<String> {
+    let mut image = Vec::new();
+    for (i, text) in base.iter().enumerate() {
+        let line = start + i;
+        let mut removed = false;
+        for edit in edits {
+            if (edit.base_start..=edit.base_end).contains(&line) {
+                image.extend(edit.inserts[line - edit.base_start].iter().cloned());
+            }
+            if (edit.base_start..edit.base_end).contains(&line) {
+                removed |= edit.base[line - edit.base_start].1;
+            }
+        }
+        if !removed {
+            image.push(text.clone());
+        }
+    }
+    let end = start + base.len();
+    for edit in edits.iter().filter(|e| e.base_end == end) {
+        image.extend(edit.inserts[end - edit.base_start].iter().cloned());
+    }
+    image
+}
+
+/// Where base line `line` ends up in a revision's file.
+fn offset(edits: &[Edit], line: usize) -> usize {
+    let delta: isize = edits
+        .iter()
+        .filter(|e| e.base_end <= line && e.base_start < line)
+        .map(Edit::delta)
+        .sum();
+    (line as isize + delta).max(1) as usize
+}
+
+/// A hunk turning `old` into `new`, trimmed to `CONTEXT_LINES` of
+/// unchanged lines at each end.
+fn render_hunk(old: &[String], new: &[String], old_line: usize, new_line: usize) -> String {
+    let ops = line_diff(old, new);
+    let first_change = ops.iter().position(|(op, _)| *op != ' ').unwrap_or(0);
+    let last_change = ops.iter().rposition(|(op, _)| *op != ' ').unwrap_or(0);
+    let from = first_change.saturating_sub(CONTEXT_LINES);
+    let to = (last_change + CONTEXT_LINES + 1).min(ops.len());
+
+    let skipped_old = ops[..from].iter().filter(|(op, _)| *op != '+').count();
+    let skipped_new = ops[..from].iter().filter(|(op, _)| *op != '-').count();
+    let kept = &ops[from..to];
+    let old_count = kept.iter().filter(|(op, _)| *op != '+').count();
+    let new_count = kept.iter().filter(|(op, _)| *op != '-').count();
+
+    let mut hunk = format!(
+        "@@ -{},{} +{},{} @@\n",
+        old_line + skipped_old,
+        old_count,
+        new_line + skipped_new,
+        new_count
+    );
+    for (op, text) in kept {
+        hunk.push(*op);
+        hunk.push_str(text);
+        hunk.push('\n');
+    }
+    hunk
+}
+
+/// Longest-common-subsequence line diff, as (' ' | '-' | '+', line) pairs.
+fn line_diff<'a>(old: &'a [String], new: &'a [String]) -> Vec<(char, &'a str)> {
+    let (n, m) = (old.len(), new.len());
+    if n * m > MAX_DIFF_CELLS {
+        return old
+            .iter()
+            .map(|l| ('-', l.as_str()))
+            .chain(new.iter().map(|l| ('+', l.as_str())))
+            .collect();
+    }
+
+    // lengths[i][j]: LCS length of old[i..] and new[j..]
+    let mut lengths = vec![vec![0u32; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lengths[i][j] = if old[i] == new[j] {
+                lengths[i + 1][j + 1] + 1
+            } else {
+                lengths[i + 1][j].max(lengths[i][j + 1])
+            };
+        }
+    }
+
+    let mut ops = Vec::with_capacity(n + m);
+    let (mut i, mut j) = (0, 0);
+    while i < n && j < m {
+        if old[i] == new[j] {
+            ops.push((' ', old[i].as_str()));
+            i += 1;
+            j += 1;
+        } else if lengths[i + 1][j] >= lengths[i][j + 1] {
+            ops.push(('-', old[i].as_str()));
+            i += 1;
+        } else {
+            ops.push(('+', new[j].as_str()));
+            j += 1;
+        }
+    }
+    ops.extend(old[i..].iter().map(|l| ('-', l.as_str())));
+    ops.extend(new[j..].iter().map(|l| ('+', l.as_str())));
+    ops
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const REVISION_1: &str = "\
+diff --git a/src/lib.rs b/src/lib.rs
+--- a/src/lib.rs
++++ b/src/lib.rs
+@@ -1,3 +1,3 @@
+ fn a() {
+-    old();
++    first_try();
+ }
+@@ -20,2 +20,3 @@
+ fn z() {
++    unchanged_since_rev1();
+ }
+";
+
+    const REVISION_2: &str = "\
+diff --git a/src/lib.rs b/src/lib.rs
+--- a/src/lib.rs
++++ b/src/lib.rs
+@@ -1,3 +1,4 @@
+ fn a() {
+-    old();
++    second_try();
++    with_logging();
+ }
+@@ -20,2 +21,3 @@
+ fn z() {
++    unchanged_since_rev1();
+ }
+@@ -40,2 +41,3 @@
+ fn new_in_rev2() {
++    added();
+ }
+";
+
+    #[test]
+    fn test_interdiff_shows_only_changes_between_revisions() {
+        let diff = interdiff(REVISION_1, REVISION_2).unwrap();
+        let parsed = ParsedDiff::parse(&diff).unwrap();
+        assert_eq!(parsed.hunks.len(), 2);
+
+        let reworked = &parsed.hunks[0].content;
+        assert!(reworked.contains("-    first_try();"));
+        assert!(reworked.contains("+    second_try();"));
+        assert!(reworked.contains("+    with_logging();"));
+        assert!(!reworked.contains("old();"));
+
+        let added = &parsed.hunks[1].content;
+        assert!(added.contains("+    added();"));
+        assert!(!diff.contains("unchanged_since_rev1"));
+    }
+
+    #[test]
+    fn test_dropped_hunk_is_reversed() {
+        let diff = interdiff(REVISION_2, REVISION_1).unwrap();
+        let parsed = ParsedDiff::parse(&diff).unwrap();
+        let dropped = &parsed.hunks.last().unwrap().content;
+        assert!(dropped.contains("-    added();"));
+    }
+
+    #[test]
+    fn test_identical_revisions_have_empty_interdiff() {
+        assert_eq!(interdiff(REVISION_1, REVISION_1).unwrap(), "");
+    }
+}
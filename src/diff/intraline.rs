@@ -0,0 +1,182 @@
+//! Word-level differences between changed lines.
+//!
+//! Within a hunk, each run of removed lines followed by a run of added lines
+//! is paired off in order, and each pair is compared token by token. The
+//! tokens that differ can then be highlighted on top of the line colors, so
+//! a renamed variable in a long line stands out.
+
+use std::ops::Range;
+
+/// Tokens per line above which a pair isn't compared.
+const MAX_TOKENS: usize = 400;
+
+/// Pairs sharing less of their text than this are different lines rather
+/// than edits of one line, and get no highlighting.
+const MIN_SHARED_PERCENT: usize = 40;
+
+/// Byte ranges within one line.
+type Ranges = Vec<Range<usize>>;
+
+/// Byte ranges of the changed tokens on each line of a hunk's content,
+/// indexed like `content.lines()`. Context lines and unpaired changes have
+/// none.
+pub fn intraline_changes(content: &str) -> Vec<Ranges> {
+    let lines: Vec<&str> = content.lines().collect();
+    let mut changes = vec![Vec::new(); lines.len()];
+    let mut i = 0;
+    while i < lines.len() {
+        let removed_start = i;
+        while i < lines.len() && lines[i].starts_with('-') {
+            i += 1;
+        }
+        let added_start = i;
+        while i < lines.len() && lines[i].starts_with('+') {
+            i += 1;
+        }
+        for (old, new) in (removed_start..added_start).zip(added_start..i) {
+            // Skip the +/- marker, then shift the ranges back past it
+            if let Some((old_ranges, new_ranges)) = diff_pair(&lines[old][1..], &lines[new][1..]) {
+                changes[old] = old_ranges
+                    .into_iter()
+                    .map(|r| r.start + 1..r.end + 1)
+                    .collect();
+                changes[new] = new_ranges
+                    .into_iter()
+                    .map(|r| r.start + 1..r.end + 1)
+                    .collect();
+            }
+        }
+        if i == removed_start {
+            i += 1;
+        }
+    }
+    changes
+}
+
+/// Changed byte ranges of `old` and `new`, or `None` when the lines are too
+/// long or too different to be worth highlighting.
+fn diff_pair(old: &str, new: &str) -> Option<(Ranges, Ranges)> {
+    let old_tokens = tokenize(old);
+    let new_tokens = tokenize(new);
+    if old_tokens.len() > MAX_TOKENS || new_tokens.len() > MAX_TOKENS {
+        return None;
+    }
+    let old_words: Vec<&str> = old_tokens.iter().map(|r| &old[r.clone()]).collect();
+    let new_words: Vec<&str> = new_tokens.iter().map(|r| &new[r.clone()]).collect();
+
+    // lcs[i][j]: longest common subsequence of old_words[i..] and new_words[j..]
+    let (n, m) = (old_words.len(), new_words.len());
+    let mut lcs = vec![vec![0u32; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lcs[i][j] = if old_words[i] == new_words[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut old_changed = vec![true; n];
+    let mut new_changed = vec![true; m];
+    let mut shared = 0;
+    let (mut i, mut j) = (0, 0);
+    while i < n && j < m {
+        if old_words[i] == new_words[j] {
+            old_changed[i] = false;
+            new_changed[j] = false;
+            shared += old_words[i].len();
+            i += 1;
+            j += 1;
+        } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+
+    let longest = old.len().max(new.len());
+    if shared == longest || shared * 100 < longest * MIN_SHARED_PERCENT {
+        return None;
+    }
+    Some((
+        merge_changed(&old_tokens, &old_changed),
+        merge_changed(&new_tokens, &new_changed),
+    ))
+}
+
+/// Joins adjacent changed tokens into ranges.
+fn merge_changed(tokens: &[Range<usize>], changed: &[bool]) -> Ranges {
+    let mut ranges = Ranges::new();
+    for (range, _) in tokens.iter().zip(changed).filter(|(_, changed)| **changed) {
+        match ranges.last_mut() {
+            Some(last) if last.end == range.start => last.end = range.end,
+            _ => ranges.push(range.clone()),
+        }
+    }
+    ranges
+}
+
+/// Splits a line into runs of word characters, runs of whitespace, and
+/// single punctuation characters.
+fn tokenize(text: &str) -> Vec<Range<usize>> {
+    #[derive(PartialEq)]
+    enum Class {
+        Word,
+        Space,
+        Punct,
+    }
+    let class = |c: char| {
+        if c.is_alphanumeric() || c == '_' {
+            Class::Word
+        } else if c.is_whitespace() {
+            Class::Space
+        } else {
+            Class::Punct
+        }
+    };
+
+    let mut tokens = Vec::new();
+    let mut chars = text.char_indices().peekable();
+    while let Some((start, c)) = chars.next() {
+        let kind = class(c);
+        let mut end = start + c.len_utf8();
+        if kind != Class::Punct {
+            while let Some(&(i, next)) = chars.peek() {
+                if class(next) != kind {
+                    break;
+                }
+                end = i + next.len_utf8();
+                chars.next();
+            }
+        }
+        tokens.push(start..end);
+    }
+    tokens
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn paired_lines_highlight_only_the_changed_words() {
+        let content =
+            "@@ -1,3 +1,3 @@\n let a = 1;\n-let total = count + 1;\n+let total = amount + 1;\n";
+        let changes = intraline_changes(content);
+        assert_eq!(changes.len(), 4);
+        assert!(changes[0].is_empty() && changes[1].is_empty());
+        let removed = "-let total = count + 1;";
+        let added = "+let total = amount + 1;";
+        assert_eq!(changes[2].len(), 1);
+        assert_eq!(&removed[changes[2][0].clone()], "count");
+        assert_eq!(&added[changes[3][0].clone()], "amount");
+    }
+
+    #[test]
+    fn unrelated_and_unpaired_lines_are_not_highlighted() {
+        let content = "-fn parse(input: &str) {}\n+struct Config;\n+// new\n";
+        let changes = intraline_changes(content);
+        assert!(changes.iter().all(Vec::is_empty));
+    }
+}
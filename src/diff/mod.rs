@@ -1,7 +1,18 @@
 mod filter;
+mod generated;
+mod highlight;
+mod ignore_file;
+mod interdiff;
+mod intraline;
 mod parser;
+mod symbols;
 
 pub use filter::FileFilter;
+pub use highlight::{Highlight, highlight_hunk};
+pub use ignore_file::{IGNORE_FILE, IgnoreFile};
+pub use interdiff::interdiff;
+pub use intraline::intraline_changes;
 #[allow(unused_imports)]
 pub use filter::FilterError;
-pub use parser::{DiffParseError, ParsedDiff};
+pub use parser::{DiffParseError, ParsedDiff, ParsedHunk, parse_hunk_header};
+pub use symbols::read_worktree_file;
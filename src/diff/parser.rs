@@ -13,6 +13,13 @@ pub enum DiffParseError {
     Filter(#[from] FilterError),
 }
 
+/// File headers in `jj diff`'s default (non-unified) output.
+const JJ_NATIVE_HEADERS: [&str; 3] = [
+    "Modified regular file",
+    "Added regular file",
+    "Removed regular file",
+];
+
 /// A single hunk from a unified diff, with an assigned index for Claude reference.
 #[derive(Debug, Clone)]
 pub struct ParsedHunk {
@@ -26,6 +33,8 @@ pub struct ParsedHunk {
     pub end_line: usize,
     /// Raw hunk content including the @@ header
     pub content: String,
+    /// Enclosing function or type, once annotated (see `annotate_symbols`)
+    pub symbol: Option<String>,
 }
 
 /// A parsed unified diff containing indexed hunks.
@@ -36,6 +45,11 @@ pub struct ParsedDiff {
 
 impl ParsedDiff {
     /// Parse a unified diff string into indexed hunks.
+    ///
+    /// Accepts `git diff` output as well as plain unified patches without
+    /// `diff --git` headers: `diff -u` (including zero-context `-U0` hunks),
+    /// `hg diff`, `svn diff`, and bare `--- / +++ / @@` patches. `jj diff`
+    /// needs `--git`.
     pub fn parse(diff_text: &str) -> Result<Self, DiffParseError> {
         let diff_text = diff_text.trim();
         if diff_text.is_empty() {
@@ -51,10 +65,7 @@ impl ParsedDiff {
         let mut index = 1usize;
 
         for patched_file in patch_set {
-            let file_path = patched_file
-                .target_file
-                .trim_start_matches("b/")
-                .to_string();
+            let file_path = patch_file_path(&patched_file.source_file, &patched_file.target_file);
 
             for hunk in patched_file {
                 let start_line = hunk.target_start;
@@ -71,12 +82,22 @@ impl ParsedDiff {
                     start_line,
                     end_line,
                     content: hunk.to_string(),
+                    symbol: None,
                 });
                 index += 1;
             }
         }
 
         if hunks.is_empty() {
+            // `jj diff` defaults to a color-words format with no hunks to parse
+            if diff_text
+                .lines()
+                .any(|line| JJ_NATIVE_HEADERS.iter().any(|h| line.starts_with(h)))
+            {
+                return Err(DiffParseError::ParseError(
+                    "this looks like jj's native diff format; use `jj diff --git`".to_string(),
+                ));
+            }
             return Err(DiffParseError::ParseError("no hunks found".to_string()));
         }
 
@@ -88,20 +109,48 @@ impl ParsedDiff {
         self.hunks.iter().find(|h| h.index == index)
     }
 
+    /// Labels each hunk with its enclosing function or type. `read_source`
+    /// returns the new version of a file, if available.
+    pub fn annotate_symbols(&mut self, read_source: impl Fn(&str) -> Option<String>) {
+        super::symbols::annotate(&mut self.hunks, read_source);
+    }
+
     /// Format hunks for inclusion in a Claude prompt.
     pub fn format_for_prompt(&self) -> String {
         self.hunks
             .iter()
             .map(|h| {
+                let symbol = h
+                    .symbol
+                    .as_ref()
+                    .map(|s| format!(", in {}", s))
+                    .unwrap_or_default();
                 format!(
-                    "=== Hunk {} ({}, lines {}-{}) ===\n{}",
-                    h.index, h.file_path, h.start_line, h.end_line, h.content
+                    "=== Hunk {} ({}, lines {}-{}{}) ===\n{}",
+                    h.index, h.file_path, h.start_line, h.end_line, symbol, h.content
                 )
             })
             .collect::<Vec<_>>()
             .join("\n\n")
     }
 
+    /// Removes and returns the hunks of lockfiles and generated files (see
+    /// `generated`), re-indexing the rest. A diff made up only of such hunks
+    /// is left whole.
+    pub fn take_generated(&mut self) -> Vec<ParsedHunk> {
+        if self.hunks.iter().all(super::generated::is_generated) {
+            return Vec::new();
+        }
+        let (generated, rest) = std::mem::take(&mut self.hunks)
+            .into_iter()
+            .partition(super::generated::is_generated);
+        self.hunks = rest;
+        for (i, hunk) in self.hunks.iter_mut().enumerate() {
+            hunk.index = i + 1;
+        }
+        generated
+    }
+
     /// Filter hunks using a compiled FileFilter.
     ///
     /// Hunks whose file paths don't pass the filter are removed.
@@ -111,6 +160,19 @@ impl ParsedDiff {
             return Ok(());
         }
 
+        // A diff made up only of denylisted files is still worth reviewing
+        let relaxed;
+        let any_allowed = self
+            .hunks
+            .iter()
+            .any(|hunk| filter.matches(&hunk.file_path));
+        let filter = if any_allowed {
+            filter
+        } else {
+            relaxed = filter.without_denylist();
+            &relaxed
+        };
+
         self.hunks.retain(|hunk| filter.matches(&hunk.file_path));
 
         // Re-index hunks to maintain 1-based sequential indices
@@ -126,6 +188,64 @@ impl ParsedDiff {
     }
 }
 
+/// The path a patched file is reviewed under. Git's `a/` and `b/` prefixes,
+/// or snapshot directories as in `diff -ru old/ new/`, are removed; deleted
+/// files fall back to the source path. Any other differing top-level
+/// directory is a real move, so the full target path is kept.
+fn patch_file_path(source: &str, target: &str) -> String {
+    const DEV_NULL: &str = "/dev/null";
+    let source = source.split('\t').next().unwrap_or(source).trim();
+    let target = target.split('\t').next().unwrap_or(target).trim();
+
+    if target == DEV_NULL {
+        return source.strip_prefix("a/").unwrap_or(source).to_string();
+    }
+    if source == DEV_NULL || source.starts_with("a/") {
+        return target.strip_prefix("b/").unwrap_or(target).to_string();
+    }
+    match (source.split_once('/'), target.split_once('/')) {
+        (Some((source_root, source_rest)), Some((target_root, target_rest)))
+            if snapshot_roots(source_root, target_root) && source_rest == target_rest =>
+        {
+            target_rest.to_string()
+        }
+        _ => target.to_string(),
+    }
+}
+
+/// Whether two top-level directories are the before and after snapshots of a
+/// tree: `a`/`b`, `old`/`new`, or `pkg.orig`/`pkg`.
+fn snapshot_roots(source: &str, target: &str) -> bool {
+    matches!((source, target), ("a", "b") | ("old", "new"))
+        || source.strip_suffix(".orig") == Some(target)
+}
+
+/// Parse a @@ header to extract old and new starting line numbers.
+pub fn parse_hunk_header(header: &str) -> Option<(usize, usize)> {
+    // Format: @@ -A,B +C,D @@ optional text
+    let header = header.strip_prefix("@@ ")?;
+    let parts: Vec<&str> = header.splitn(3, ' ').collect();
+    if parts.len() < 2 {
+        return None;
+    }
+
+    let old_start = parts[0]
+        .strip_prefix('-')?
+        .split(',')
+        .next()?
+        .parse::<usize>()
+        .ok()?;
+
+    let new_start = parts[1]
+        .strip_prefix('+')?
+        .split(',')
+        .next()?
+        .parse::<usize>()
+        .ok()?;
+
+    Some((old_start, new_start))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -173,6 +293,17 @@ diff --git a/src/b.rs b/src/b.rs
         assert_eq!(parsed.hunks[1].file_path, "src/b.rs");
     }
 
+    #[test]
+    fn test_parse_hunk_header() {
+        assert_eq!(parse_hunk_header("@@ -0,0 +1,25 @@"), Some((0, 1)));
+        assert_eq!(parse_hunk_header("@@ -15,6 +15,21 @@"), Some((15, 15)));
+        assert_eq!(
+            parse_hunk_header("@@ -10,3 +10,18 @@ fn something"),
+            Some((10, 10))
+        );
+        assert_eq!(parse_hunk_header("not a header"), None);
+    }
+
     #[test]
     fn test_empty_diff_error() {
         let result = ParsedDiff::parse("");
@@ -273,4 +404,143 @@ diff --git a/test/test.rs b/test/test.rs
             Err(DiffParseError::Filter(FilterError::NoMatches))
         ));
     }
+
+    #[test]
+    fn test_denylist_only_diff_is_kept() {
+        let diff = r#"diff --git a/Cargo.lock b/Cargo.lock
+--- a/Cargo.lock
++++ b/Cargo.lock
+@@ -1,2 +1,2 @@
+-version = "1.0.0"
++version = "1.0.1"
+"#;
+        let mut parsed = ParsedDiff::parse(diff).unwrap();
+
+        let filter = FileFilter::default()
+            .with_denylist(&["**/Cargo.lock".to_string()])
+            .unwrap();
+        parsed.apply_filter(&filter).unwrap();
+        assert_eq!(parsed.hunks.len(), 1);
+    }
+
+    #[test]
+    fn test_take_generated() {
+        let diff = r#"diff --git a/Cargo.lock b/Cargo.lock
+--- a/Cargo.lock
++++ b/Cargo.lock
+@@ -1,2 +1,2 @@
+-version = "1.0.0"
++version = "1.0.1"
+diff --git a/src/main.rs b/src/main.rs
+--- a/src/main.rs
++++ b/src/main.rs
+@@ -1,2 +1,2 @@
+-fn main() {}
++fn main() { run(); }
+"#;
+        let mut parsed = ParsedDiff::parse(diff).unwrap();
+        let generated = parsed.take_generated();
+        assert_eq!(generated.len(), 1);
+        assert_eq!(generated[0].file_path, "Cargo.lock");
+        assert_eq!(parsed.hunks.len(), 1);
+        assert_eq!(parsed.hunks[0].index, 1);
+
+        // Nothing is taken when every hunk is generated
+        assert!(parsed.take_generated().is_empty());
+        let mut lock_only =
+            ParsedDiff::parse(diff.split("diff --git a/src").next().unwrap()).unwrap();
+        assert!(lock_only.take_generated().is_empty());
+        assert_eq!(lock_only.hunks.len(), 1);
+    }
+
+    #[test]
+    fn test_parse_plain_zero_context_patch() {
+        let diff = "--- a.txt\t2024-01-01 10:00:00.000000000 +0000
++++ a.txt\t2024-01-02 10:00:00.000000000 +0000
+@@ -3 +3 @@
+-old
++new
+@@ -10,0 +11,2 @@
++x
++y
+";
+        let parsed = ParsedDiff::parse(diff).unwrap();
+        assert_eq!(parsed.hunks.len(), 2);
+        assert_eq!(parsed.hunks[0].file_path, "a.txt");
+        assert_eq!(
+            (parsed.hunks[0].start_line, parsed.hunks[0].end_line),
+            (3, 3)
+        );
+        assert_eq!(
+            (parsed.hunks[1].start_line, parsed.hunks[1].end_line),
+            (11, 12)
+        );
+    }
+
+    #[test]
+    fn test_parse_svn_diff() {
+        let diff = "Index: src/foo.c
+===================================================================
+--- src/foo.c\t(revision 12)
++++ src/foo.c\t(working copy)
+@@ -1,2 +1,2 @@
+-a
++b
+ c
+";
+        let parsed = ParsedDiff::parse(diff).unwrap();
+        assert_eq!(parsed.hunks.len(), 1);
+        assert_eq!(parsed.hunks[0].file_path, "src/foo.c");
+    }
+
+    #[test]
+    fn test_patch_file_path() {
+        assert_eq!(
+            patch_file_path("a/src/lib.rs", "b/src/lib.rs"),
+            "src/lib.rs"
+        );
+        assert_eq!(patch_file_path("/dev/null", "b/new.txt"), "new.txt");
+        assert_eq!(patch_file_path("a/gone.txt", "/dev/null"), "gone.txt");
+        assert_eq!(patch_file_path("old/src/x.c", "new/src/x.c"), "src/x.c");
+        assert_eq!(patch_file_path("src/x.c", "src/x.c"), "src/x.c");
+        assert_eq!(patch_file_path("x.c.orig", "x.c"), "x.c");
+        assert_eq!(
+            patch_file_path("pkg.orig/src/x.c", "pkg/src/x.c"),
+            "src/x.c"
+        );
+    }
+
+    #[test]
+    fn test_patch_file_path_keeps_cross_directory_renames() {
+        assert_eq!(patch_file_path("src/a.c", "lib/a.c"), "lib/a.c");
+        assert_eq!(patch_file_path("old/a.c", "lib/a.c"), "lib/a.c");
+        assert_eq!(patch_file_path("a/src/a.c", "b/lib/a.c"), "lib/a.c");
+    }
+
+    #[test]
+    fn test_parse_hg_diff() {
+        let diff = "diff -r 1c2d3e4f5a6b -r 6b5a4f3e2d1c src/lib.rs
+--- a/src/lib.rs\tThu Jan 01 00:00:00 1970 +0000
++++ b/src/lib.rs\tThu Jan 01 00:00:00 1970 +0000
+@@ -1,2 +1,2 @@
+-a
++b
+ c
+diff -r 1c2d3e4f5a6b -r 6b5a4f3e2d1c new.txt
+--- /dev/null\tThu Jan 01 00:00:00 1970 +0000
++++ b/new.txt\tThu Jan 01 00:00:00 1970 +0000
+@@ -0,0 +1,1 @@
++hi
+";
+        let parsed = ParsedDiff::parse(diff).unwrap();
+        let paths: Vec<_> = parsed.hunks.iter().map(|h| h.file_path.as_str()).collect();
+        assert_eq!(paths, ["src/lib.rs", "new.txt"]);
+    }
+
+    #[test]
+    fn test_jj_native_format_error() {
+        let diff = "Modified regular file src/lib.rs:\n   1    1: fn main() {}\n";
+        let err = ParsedDiff::parse(diff).unwrap_err();
+        assert!(err.to_string().contains("jj diff --git"));
+    }
 }
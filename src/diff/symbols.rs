@@ -0,0 +1,296 @@
+//! Enclosing symbols for hunks, found with tree-sitter.
+//!
+//! Each hunk is labelled with the function, method, or type its changes sit
+//! in (`impl Session > fn switch_part`). The new version of the file is read
+//! from the working tree and used only when it matches the hunk; otherwise
+//! the label falls back to the section heading git puts after the `@@`.
+
+use tree_sitter::{Language, Node, Parser, Point};
+
+use super::parser::ParsedHunk;
+
+/// Separator between nested symbols in a label.
+const NESTING_SEPARATOR: &str = " > ";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum Lang {
+    Rust,
+    Python,
+    JavaScript,
+    TypeScript,
+    Tsx,
+    Go,
+}
+
+impl Lang {
+    pub(super) fn from_path(path: &str) -> Option<Self> {
+        let extension = path.rsplit_once('.')?.1;
+        Some(match extension {
+            "rs" => Self::Rust,
+            "py" => Self::Python,
+            "js" | "jsx" | "mjs" | "cjs" => Self::JavaScript,
+            "ts" | "mts" | "cts" => Self::TypeScript,
+            "tsx" => Self::Tsx,
+            "go" => Self::Go,
+            _ => return None,
+        })
+    }
+
+    pub(super) fn language(self) -> Language {
+        match self {
+            Self::Rust => tree_sitter_rust::LANGUAGE.into(),
+            Self::Python => tree_sitter_python::LANGUAGE.into(),
+            Self::JavaScript => tree_sitter_javascript::LANGUAGE.into(),
+            Self::TypeScript => tree_sitter_typescript::LANGUAGE_TYPESCRIPT.into(),
+            Self::Tsx => tree_sitter_typescript::LANGUAGE_TSX.into(),
+            Self::Go => tree_sitter_go::LANGUAGE.into(),
+        }
+    }
+
+    /// Node kinds that name a symbol, with the keyword shown before the name.
+    fn symbol_kinds(self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            Self::Rust => &[
+                ("function_item", "fn"),
+                ("function_signature_item", "fn"),
+                ("impl_item", "impl"),
+                ("trait_item", "trait"),
+                ("mod_item", "mod"),
+                ("struct_item", "struct"),
+                ("enum_item", "enum"),
+            ],
+            Self::Python => &[
+                ("function_definition", "def"),
+                ("class_definition", "class"),
+            ],
+            Self::JavaScript | Self::TypeScript | Self::Tsx => &[
+                ("function_declaration", "function"),
+                ("generator_function_declaration", "function"),
+                ("class_declaration", "class"),
+                ("abstract_class_declaration", "class"),
+                ("interface_declaration", "interface"),
+                ("method_definition", "method"),
+            ],
+            Self::Go => &[
+                ("function_declaration", "func"),
+                ("method_declaration", "func"),
+                ("type_spec", "type"),
+            ],
+        }
+    }
+}
+
+/// Labels each hunk with its enclosing symbol. `read_source` returns the new
+/// version of a file, if available.
+pub(super) fn annotate(hunks: &mut [ParsedHunk], read_source: impl Fn(&str) -> Option<String>) {
+    let mut i = 0;
+    while i < hunks.len() {
+        let path = hunks[i].file_path.clone();
+        let end = hunks[i..]
+            .iter()
+            .position(|h| h.file_path != path)
+            .map_or(hunks.len(), |n| i + n);
+        let file_hunks = &mut hunks[i..end];
+
+        let source = Lang::from_path(&path).and_then(|lang| {
+            let source = read_source(&path)?;
+            let lines: Vec<&str> = source.lines().collect();
+            file_hunks
+                .iter()
+                .all(|h| matches_source(&h.content, &lines))
+                .then_some((lang, source))
+        });
+        let tree = source.as_ref().and_then(|(lang, source)| {
+            let mut parser = Parser::new();
+            parser.set_language(&lang.language()).ok()?;
+            parser.parse(source, None).map(|tree| (*lang, tree))
+        });
+
+        for hunk in file_hunks {
+            let from_tree =
+                tree.as_ref()
+                    .zip(source.as_ref())
+                    .and_then(|((lang, tree), (_, source))| {
+                        let (start, end) = changed_range(&hunk.content, source)?;
+                        let node = tree.root_node().descendant_for_point_range(start, end)?;
+                        enclosing_symbols(node, *lang, source)
+                    });
+            hunk.symbol = from_tree.or_else(|| section_heading(&hunk.content));
+        }
+        i = end;
+    }
+}
+
+/// Whether the hunk's context and added lines appear in `lines` (the new
+/// file) at the hunk's line numbers.
+fn matches_source(content: &str, lines: &[&str]) -> bool {
+    let mut body = content.lines();
+    let Some(new_start) = body
+        .next()
+        .and_then(super::parse_hunk_header)
+        .map(|(_, new)| new)
+    else {
+        return false;
+    };
+    let mut line_number = new_start;
+    for line in body {
+        if line.starts_with('-') || line.starts_with('\\') {
+            continue;
+        }
+        let text = line.get(1..).unwrap_or("");
+        if line_number == 0 || lines.get(line_number - 1) != Some(&text) {
+            return false;
+        }
+        line_number += 1;
+    }
+    true
+}
+
+/// The span of the hunk's changed lines in the new file, from the first
+/// non-blank column of the first line to the end of the last. Removed lines
+/// count as the line that follows them.
+fn changed_range(content: &str, source: &str) -> Option<(Point, Point)> {
+    let mut body = content.lines();
+    let (_, new_start) = super::parse_hunk_header(body.next()?)?;
+    let mut row = new_start.saturating_sub(1);
+    let mut first: Option<usize> = None;
+    let mut last: Option<usize> = None;
+    for line in body {
+        match line.chars().next() {
+            Some('+') => {
+                first.get_or_insert(row);
+                last = Some(row);
+                row += 1;
+            }
+            Some('-') => {
+                first.get_or_insert(row);
+                last = Some(last.map_or(row, |l| l.max(row)));
+            }
+            Some('\\') => {}
+            _ => row += 1,
+        }
+    }
+
+    let lines: Vec<&str> = source.lines().collect();
+    let last_row = lines.len().checked_sub(1)?;
+    let (first, last) = (first?.min(last_row), last?.min(last_row));
+    let indent = lines[first].len() - lines[first].trim_start().len();
+    let end_column = lines[last].trim_end().len();
+    Some((Point::new(first, indent), Point::new(last, end_column)))
+}
+
+/// The symbols enclosing `node`, outermost first.
+fn enclosing_symbols(node: Node, lang: Lang, source: &str) -> Option<String> {
+    let kinds = lang.symbol_kinds();
+    let mut symbols = Vec::new();
+    let mut current = Some(node);
+    while let Some(n) = current {
+        if let Some((_, keyword)) = kinds.iter().find(|(kind, _)| *kind == n.kind())
+            && let Some(name) = symbol_name(n, source)
+        {
+            symbols.push(format!("{} {}", keyword, name));
+        }
+        current = n.parent();
+    }
+    if symbols.is_empty() {
+        return None;
+    }
+    symbols.reverse();
+    Some(symbols.join(NESTING_SEPARATOR))
+}
+
+fn symbol_name(node: Node, source: &str) -> Option<String> {
+    let text = |field: &str| {
+        node.child_by_field_name(field)
+            .and_then(|n| n.utf8_text(source.as_bytes()).ok())
+    };
+    if node.kind() == "impl_item" {
+        let target = text("type")?;
+        return Some(match text("trait") {
+            Some(trait_name) => format!("{} for {}", trait_name, target),
+            None => target.to_string(),
+        });
+    }
+    text("name").map(String::from)
+}
+
+/// The text git prints after the `@@` header, usually the enclosing function
+/// signature.
+fn section_heading(content: &str) -> Option<String> {
+    let header = content.lines().next()?.strip_prefix("@@ ")?;
+    let (_, heading) = header.split_once(" @@")?;
+    let heading = heading.trim();
+    (!heading.is_empty()).then(|| heading.trim_end_matches('{').trim_end().to_string())
+}
+
+/// Reads the new version of `path` from the working tree.
+pub fn read_worktree_file(path: &str) -> Option<String> {
+    std::fs::read_to_string(path).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::diff::ParsedDiff;
+
+    const SOURCE: &str = "\
+struct Session {
+    step: usize,
+}
+
+impl Session {
+    pub fn next(&mut self) {
+        self.step += 1;
+    }
+}
+";
+
+    const DIFF: &str = "\
+diff --git a/src/session.rs b/src/session.rs
+--- a/src/session.rs
++++ b/src/session.rs
+@@ -5,4 +5,4 @@ impl Session {
+ impl Session {
+     pub fn next(&mut self) {
+-        self.step = self.step + 1;
++        self.step += 1;
+     }
+";
+
+    #[test]
+    fn test_symbol_from_tree() {
+        let mut parsed = ParsedDiff::parse(DIFF).unwrap();
+        parsed.annotate_symbols(|_| Some(SOURCE.to_string()));
+        assert_eq!(
+            parsed.hunks[0].symbol.as_deref(),
+            Some("impl Session > fn next")
+        );
+    }
+
+    #[test]
+    fn test_falls_back_to_section_heading_when_file_differs() {
+        let mut parsed = ParsedDiff::parse(DIFF).unwrap();
+        parsed.annotate_symbols(|_| Some("fn unrelated() {}\n".to_string()));
+        assert_eq!(parsed.hunks[0].symbol.as_deref(), Some("impl Session"));
+
+        parsed.annotate_symbols(|_| None);
+        assert_eq!(parsed.hunks[0].symbol.as_deref(), Some("impl Session"));
+    }
+
+    #[test]
+    fn test_python_method() {
+        let source = "class Cart:\n    def total(self):\n        return sum(self.items)\n";
+        let diff = "\
+--- a/cart.py
++++ b/cart.py
+@@ -3 +3 @@
+-        return 0
++        return sum(self.items)
+";
+        let mut parsed = ParsedDiff::parse(diff).unwrap();
+        parsed.annotate_symbols(|_| Some(source.to_string()));
+        assert_eq!(
+            parsed.hunks[0].symbol.as_deref(),
+            Some("class Cart > def total")
+        );
+    }
+}
@@ -1,10 +1,18 @@
-use tui_textarea::TextArea;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use tui_textarea::{CursorMove, TextArea};
+
+/// Sent questions kept for recalling
+const MAX_HISTORY: usize = 100;
 
 /// Vim mode state for the text editor
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum VimInputMode {
     Normal,
     Insert,
+    /// `v`: characters from the anchor to the cursor, both included
+    Visual,
+    /// `V`: whole lines from the anchor's to the cursor's
+    VisualLine,
 }
 
 /// Text editor state (vim mode + textarea)
@@ -12,6 +20,17 @@ pub struct Editor<'a> {
     pub textarea: TextArea<'a>,
     pub vim_enabled: bool,
     pub vim_mode: VimInputMode,
+    /// Where the visual selection started, as (row, column)
+    visual_anchor: (usize, usize),
+    /// The register's text when it holds whole lines, so `p` puts them on
+    /// their own line
+    yanked_lines: Option<String>,
+    /// Questions sent this session, oldest first
+    history: Vec<String>,
+    /// Which of `history` is in the input while recalling
+    history_index: Option<usize>,
+    /// What was typed before recalling began
+    draft: String,
 }
 
 impl<'a> Editor<'a> {
@@ -22,6 +41,11 @@ impl<'a> Editor<'a> {
             textarea,
             vim_enabled,
             vim_mode: VimInputMode::Normal,
+            visual_anchor: (0, 0),
+            yanked_lines: None,
+            history: Vec::new(),
+            history_index: None,
+            draft: String::new(),
         }
     }
 
@@ -29,8 +53,380 @@ impl<'a> Editor<'a> {
         self.textarea.lines().iter().all(|l| l.is_empty())
     }
 
+    /// Applies a readline editing key, returning false for any other key.
+    /// Killed text goes to the register, for `Ctrl+y` to yank back.
+    pub fn readline(&mut self, key: KeyEvent) -> bool {
+        let textarea = &mut self.textarea;
+        if key.modifiers.contains(KeyModifiers::CONTROL) {
+            match key.code {
+                KeyCode::Char('a') => textarea.move_cursor(CursorMove::Head),
+                KeyCode::Char('e') => textarea.move_cursor(CursorMove::End),
+                KeyCode::Char('b') => textarea.move_cursor(CursorMove::Back),
+                KeyCode::Char('f') => textarea.move_cursor(CursorMove::Forward),
+                KeyCode::Char('h') => {
+                    textarea.delete_char();
+                }
+                KeyCode::Char('d') => {
+                    textarea.delete_next_char();
+                }
+                KeyCode::Char('w') => {
+                    textarea.delete_word();
+                }
+                KeyCode::Char('u') => {
+                    textarea.delete_line_by_head();
+                }
+                KeyCode::Char('k') => {
+                    textarea.delete_line_by_end();
+                }
+                KeyCode::Char('y') => {
+                    textarea.paste();
+                }
+                _ => return false,
+            }
+        } else if key.modifiers.contains(KeyModifiers::ALT) {
+            match key.code {
+                KeyCode::Char('b') => textarea.move_cursor(CursorMove::WordBack),
+                KeyCode::Char('f') => {
+                    let (row, col) = textarea.cursor();
+                    let line: Vec<char> = textarea.lines()[row].chars().collect();
+                    if col == line.len() {
+                        textarea.move_cursor(CursorMove::Forward);
+                    } else {
+                        let end = past_next_word(&line, col);
+                        self.jump(row, end);
+                    }
+                }
+                KeyCode::Char('d') => {
+                    textarea.delete_next_word();
+                }
+                KeyCode::Backspace => {
+                    textarea.delete_word();
+                }
+                _ => return false,
+            }
+        } else {
+            return false;
+        }
+        self.yanked_lines = None;
+        true
+    }
+
+    /// Adds a sent question to the history, moving a repeat to the end.
+    pub fn remember(&mut self, question: String) {
+        self.history.retain(|q| *q != question);
+        self.history.push(question);
+        if self.history.len() > MAX_HISTORY {
+            self.history.remove(0);
+        }
+        self.history_index = None;
+    }
+
+    /// Replaces the input with the question sent before the one shown, if
+    /// the cursor is on the first line. Returns whether the key was used.
+    pub fn recall_prev(&mut self) -> bool {
+        if self.textarea.cursor().0 > 0 {
+            return false;
+        }
+        let index = match self.history_index {
+            Some(index) => index.saturating_sub(1),
+            None if self.history.is_empty() => return false,
+            None => {
+                self.draft = self.textarea.lines().join("\n");
+                self.history.len() - 1
+            }
+        };
+        self.history_index = Some(index);
+        self.set_text(&self.history[index].clone());
+        true
+    }
+
+    /// Replaces the input with the question sent after the one shown, or
+    /// with the draft, if the cursor is on the last line. Returns whether
+    /// the key was used.
+    pub fn recall_next(&mut self) -> bool {
+        let Some(index) = self.history_index else {
+            return false;
+        };
+        if self.textarea.cursor().0 + 1 < self.textarea.lines().len() {
+            return false;
+        }
+        if index + 1 < self.history.len() {
+            self.history_index = Some(index + 1);
+            self.set_text(&self.history[index + 1].clone());
+        } else {
+            self.history_index = None;
+            let draft = std::mem::take(&mut self.draft);
+            self.set_text(&draft);
+        }
+        true
+    }
+
+    /// Empties the input, returning what was typed.
+    pub fn take_draft(&mut self) -> String {
+        let draft = self.textarea.lines().join("\n");
+        self.set_text("");
+        draft
+    }
+
+    /// Puts a draft taken earlier back into the input.
+    pub fn set_draft(&mut self, draft: &str) {
+        self.history_index = None;
+        self.set_text(draft);
+    }
+
+    fn set_text(&mut self, text: &str) {
+        self.textarea.select_all();
+        self.textarea.delete_char();
+        self.textarea.insert_str(text);
+    }
+
     /// Reset to normal mode (called when leaving chat pane)
     pub fn reset_mode(&mut self) {
+        self.textarea.cancel_selection();
+        self.vim_mode = VimInputMode::Normal;
+    }
+
+    /// Starts a visual selection at the cursor, of lines if `linewise`.
+    pub fn start_visual(&mut self, linewise: bool) {
+        self.visual_anchor = self.textarea.cursor();
+        self.textarea.cancel_selection();
+        self.textarea.start_selection();
+        self.vim_mode = if linewise {
+            VimInputMode::VisualLine
+        } else {
+            VimInputMode::Visual
+        };
+        self.update_visual();
+    }
+
+    /// Leaves visual mode without touching the text.
+    pub fn exit_visual(&mut self) {
+        self.textarea.cancel_selection();
+        self.vim_mode = VimInputMode::Normal;
+    }
+
+    /// Stretches a line selection over whole lines after the cursor moved.
+    pub fn update_visual(&mut self) {
+        if self.vim_mode != VimInputMode::VisualLine {
+            return;
+        }
+        let anchor = self.visual_anchor.0;
+        let row = self.textarea.cursor().0;
+        self.textarea.cancel_selection();
+        if row >= anchor {
+            self.jump(anchor, 0);
+            self.textarea.start_selection();
+            self.jump(row, usize::MAX);
+        } else {
+            self.jump(anchor, usize::MAX);
+            self.textarea.start_selection();
+            self.jump(row, 0);
+        }
+    }
+
+    /// Copies the selection into the register, leaves visual mode, and
+    /// returns the text.
+    pub fn yank_selection(&mut self) -> String {
+        let linewise = self.select_inclusive();
+        let (start, _) = self.textarea.selection_range().unwrap_or_default();
+        self.textarea.copy();
+        self.textarea.cancel_selection();
+        self.jump(start.0, start.1);
+        self.vim_mode = VimInputMode::Normal;
+        self.remember_yank(linewise)
+    }
+
+    /// Cuts the selection into the register and leaves visual mode. Lines
+    /// cut from a line selection go away entirely.
+    pub fn delete_selection(&mut self) {
+        let linewise = self.select_inclusive();
+        self.textarea.cut();
         self.vim_mode = VimInputMode::Normal;
+        self.remember_yank(linewise);
+        if linewise && self.textarea.lines().len() > 1 {
+            let row = self.textarea.cursor().0;
+            if row + 1 < self.textarea.lines().len() {
+                self.textarea.delete_next_char();
+            } else {
+                self.textarea.delete_char();
+                self.textarea.move_cursor(CursorMove::Head);
+            }
+        }
+    }
+
+    /// Cuts the selection and starts inserting in its place. A line
+    /// selection leaves an empty line behind.
+    pub fn change_selection(&mut self) {
+        let linewise = self.select_inclusive();
+        self.textarea.cut();
+        self.remember_yank(linewise);
+        self.vim_mode = VimInputMode::Insert;
+    }
+
+    /// Puts the register after the cursor, or before it with `before`.
+    /// Lines go below or above the cursor's line.
+    pub fn paste(&mut self, before: bool) {
+        let text = self.textarea.yank_text();
+        if self.yanked_lines.as_ref() == Some(&text) {
+            if before {
+                self.textarea.move_cursor(CursorMove::Head);
+                self.textarea.insert_newline();
+                self.textarea.move_cursor(CursorMove::Up);
+            } else {
+                self.textarea.move_cursor(CursorMove::End);
+                self.textarea.insert_newline();
+            }
+            self.textarea.paste();
+            self.textarea.move_cursor(CursorMove::Head);
+        } else {
+            let (row, col) = self.textarea.cursor();
+            if !before && col < self.textarea.lines()[row].chars().count() {
+                self.textarea.move_cursor(CursorMove::Forward);
+            }
+            self.textarea.paste();
+        }
+    }
+
+    /// Selects the visual range with the character under the cursor
+    /// included, as vim does. Returns whether it covers whole lines.
+    fn select_inclusive(&mut self) -> bool {
+        let linewise = self.vim_mode == VimInputMode::VisualLine;
+        let Some((start, end)) = self.textarea.selection_range() else {
+            return linewise;
+        };
+        self.textarea.cancel_selection();
+        self.jump(start.0, start.1);
+        self.textarea.start_selection();
+        if linewise {
+            self.jump(end.0, usize::MAX);
+        } else {
+            self.jump(end.0, end.1 + 1);
+        }
+        linewise
+    }
+
+    fn remember_yank(&mut self, linewise: bool) -> String {
+        let text = self.textarea.yank_text();
+        self.yanked_lines = linewise.then(|| text.clone());
+        text
+    }
+
+    /// Moves the cursor, clamping to the text.
+    fn jump(&mut self, row: usize, col: usize) {
+        let row = u16::try_from(row).unwrap_or(u16::MAX);
+        let col = u16::try_from(col).unwrap_or(u16::MAX);
+        self.textarea.move_cursor(CursorMove::Jump(row, col));
+    }
+}
+
+/// Column just past the end of the word at or after `col`, as readline's
+/// `forward-word` moves.
+fn past_next_word(line: &[char], col: usize) -> usize {
+    let start = (col..line.len())
+        .find(|&i| line[i].is_alphanumeric())
+        .unwrap_or(line.len());
+    (start..line.len())
+        .find(|&i| !line[i].is_alphanumeric())
+        .unwrap_or(line.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn editor(lines: &[&str]) -> Editor<'static> {
+        let mut editor = Editor::new(true);
+        editor.textarea = TextArea::from(lines.iter().map(|l| l.to_string()));
+        editor
+    }
+
+    #[test]
+    fn test_readline_kill_and_yank() {
+        let ctrl = |c| KeyEvent::new(KeyCode::Char(c), KeyModifiers::CONTROL);
+        let alt = |c| KeyEvent::new(KeyCode::Char(c), KeyModifiers::ALT);
+        let mut editor = editor(&["why does this retry"]);
+        editor.textarea.move_cursor(CursorMove::End);
+
+        assert!(editor.readline(ctrl('w')));
+        assert_eq!(editor.textarea.lines(), ["why does this "]);
+        assert!(editor.readline(ctrl('a')));
+        assert!(editor.readline(alt('f')));
+        assert!(editor.readline(ctrl('k')));
+        assert_eq!(editor.textarea.lines(), ["why"]);
+        assert!(editor.readline(ctrl('y')));
+        assert_eq!(editor.textarea.lines(), ["why does this "]);
+        assert!(editor.readline(ctrl('u')));
+        assert_eq!(editor.textarea.lines(), [""]);
+
+        assert!(!editor.readline(KeyEvent::from(KeyCode::Char('a'))));
+        assert!(!editor.readline(ctrl('t')));
+    }
+
+    #[test]
+    fn test_history_recall() {
+        let mut editor = editor(&[]);
+        assert!(!editor.recall_prev());
+        editor.remember("why?".to_string());
+        editor.remember("what\nelse?".to_string());
+        editor.remember("why?".to_string());
+        editor.textarea.insert_str("draft");
+
+        assert!(editor.recall_prev());
+        assert_eq!(editor.textarea.lines(), ["why?"]);
+        assert!(editor.recall_prev());
+        assert_eq!(editor.textarea.lines(), ["what", "else?"]);
+        // Up moves within a recalled question before recalling further
+        assert!(!editor.recall_prev());
+        editor.textarea.move_cursor(CursorMove::Top);
+        assert!(editor.recall_prev());
+        assert_eq!(editor.textarea.lines(), ["what", "else?"]);
+
+        assert!(editor.recall_next());
+        assert_eq!(editor.textarea.lines(), ["why?"]);
+        assert!(editor.recall_next());
+        assert_eq!(editor.textarea.lines(), ["draft"]);
+        assert!(!editor.recall_next());
+    }
+
+    #[test]
+    fn test_visual_yank_includes_the_cursor() {
+        let mut editor = editor(&["hello world"]);
+        editor.start_visual(false);
+        editor.textarea.move_cursor(CursorMove::WordForward);
+        assert_eq!(editor.yank_selection(), "hello w");
+        assert_eq!(editor.vim_mode, VimInputMode::Normal);
+        assert_eq!(editor.textarea.cursor(), (0, 0));
+
+        editor.textarea.move_cursor(CursorMove::End);
+        editor.paste(false);
+        assert_eq!(editor.textarea.lines(), ["hello worldhello w"]);
+    }
+
+    #[test]
+    fn test_line_selections_cut_and_paste_whole_lines() {
+        let mut editor = editor(&["one", "two", "three"]);
+        editor.textarea.move_cursor(CursorMove::Down);
+        editor.textarea.move_cursor(CursorMove::Forward);
+        editor.start_visual(true);
+        editor.delete_selection();
+        assert_eq!(editor.textarea.lines(), ["one", "three"]);
+
+        editor.paste(false);
+        assert_eq!(editor.textarea.lines(), ["one", "three", "two"]);
+        editor.paste(true);
+        assert_eq!(editor.textarea.lines(), ["one", "three", "two", "two"]);
+        assert_eq!(editor.textarea.cursor(), (2, 0));
+    }
+
+    #[test]
+    fn test_deleting_the_last_lines() {
+        let mut editor = editor(&["one", "two", "three"]);
+        editor.textarea.move_cursor(CursorMove::Bottom);
+        editor.start_visual(true);
+        editor.textarea.move_cursor(CursorMove::Up);
+        editor.update_visual();
+        editor.delete_selection();
+        assert_eq!(editor.textarea.lines(), ["one"]);
+        assert_eq!(editor.textarea.yank_text(), "two\nthree");
     }
 }
@@ -0,0 +1,441 @@
+//! Single-file interactive HTML bundle: collapsible steps, syntax-highlighted
+//! diffs, notes, chat transcripts, findings, and flags, with styles and the
+//! step navigation script inlined so the file can be attached to a ticket or
+//! review record as-is.
+
+use std::fmt::Write;
+
+use pulldown_cmark::{Parser, html};
+
+use crate::diff::{Highlight, highlight_hunk};
+use crate::model::{Message, MessageRole, Priority, ReviewMode, Step, Walkthrough};
+
+const STYLE: &str = r#"
+body { margin: 0; font-family: -apple-system, BlinkMacSystemFont, "Segoe UI", sans-serif; color: #1f2328; display: flex; }
+nav { position: sticky; top: 0; height: 100vh; overflow-y: auto; width: 280px; flex-shrink: 0; background: #f6f8fa; border-right: 1px solid #d0d7de; padding: 16px; box-sizing: border-box; }
+nav ol { padding-left: 20px; }
+nav a { color: #1f2328; text-decoration: none; }
+nav a:hover { text-decoration: underline; }
+main { flex: 1; padding: 24px 32px; min-width: 0; }
+details.step { border: 1px solid #d0d7de; border-radius: 6px; margin-bottom: 16px; }
+details.step > summary { cursor: pointer; padding: 10px 14px; font-weight: 600; background: #f6f8fa; border-radius: 6px; }
+.step-body { padding: 4px 16px 16px; }
+.priority { font-size: 12px; padding: 1px 6px; border-radius: 10px; margin-left: 8px; font-weight: normal; }
+.priority.critical { background: #ffebe9; color: #cf222e; }
+.priority.normal { background: #ddf4ff; color: #0969da; }
+.priority.minor { background: #eaeef2; color: #57606a; }
+.reviewed { color: #1a7f37; margin-right: 6px; }
+pre.diff { font-family: ui-monospace, SFMono-Regular, Menlo, monospace; font-size: 12px; background: #fff; border: 1px solid #d0d7de; border-radius: 6px; overflow-x: auto; margin: 8px 0; padding: 0; }
+pre.diff span { display: block; padding: 0 8px; white-space: pre; }
+.file { font-weight: 600; margin-top: 12px; font-family: ui-monospace, monospace; font-size: 13px; }
+.symbol { font-weight: 400; color: #6b7280; }
+.add { background: #e6ffec; }
+.del { background: #ffebe9; }
+.hunk { color: #0969da; background: #ddf4ff; }
+.chat { border-left: 3px solid #d0d7de; padding-left: 12px; margin-top: 12px; }
+.msg { margin: 8px 0; }
+.msg.user { background: #f6f8fa; padding: 6px 10px; border-radius: 6px; }
+.notes { border-left: 3px solid #d4a72c; padding-left: 12px; margin-top: 12px; }
+.notes p { margin: 6px 0; white-space: pre-wrap; }
+.findings { border: 1px solid #cf222e; border-radius: 6px; padding: 8px 16px; margin-bottom: 24px; }
+.flags { border: 1px solid #d0d7de; border-radius: 6px; padding: 8px 16px; margin-bottom: 24px; }
+.flag { font-size: 12px; padding: 1px 6px; border-radius: 10px; color: #fff; }
+.flag.blocker { background: #cf222e; }
+.flag.question { background: #0969da; }
+.flag.nit { background: #6e7781; }
+nav a.current { font-weight: 600; color: #0969da; }
+.toolbar { color: #57606a; font-size: 13px; margin-bottom: 16px; }
+.toolbar button { font: inherit; margin-right: 6px; }
+.step-nav { display: flex; justify-content: space-between; margin-top: 16px; font-size: 13px; }
+pre.diff span.tok { display: inline; padding: 0; }
+.tok.keyword { color: #cf222e; }
+.tok.string { color: #0a3069; }
+.tok.comment { color: #6e7781; font-style: italic; }
+.tok.function { color: #8250df; }
+.tok.type, .tok.constructor { color: #953800; }
+.tok.constant, .tok.number, .tok.property { color: #0550ae; }
+.tok.attribute { color: #116329; }
+"#;
+
+/// Keyboard navigation between steps (`j`/`k` or `n`/`p`, and `o` to open or
+/// close the current one), keeping the sidebar's current step marked.
+const SCRIPT: &str = r##"
+const steps = [...document.querySelectorAll("details.step")];
+const links = [...document.querySelectorAll("nav a")];
+let current = Math.max(0, steps.findIndex(step => "#" + step.id === location.hash));
+function mark() {
+  links.forEach((link, i) => link.classList.toggle("current", i === current));
+}
+function show(i) {
+  if (i < 0 || i >= steps.length) return;
+  current = i;
+  steps[i].open = true;
+  steps[i].scrollIntoView({ block: "start" });
+  history.replaceState(null, "", "#" + steps[i].id);
+  mark();
+}
+document.addEventListener("keydown", event => {
+  if (event.metaKey || event.ctrlKey || event.altKey) return;
+  if (event.key === "j" || event.key === "n") show(current + 1);
+  else if (event.key === "k" || event.key === "p") show(current - 1);
+  else if (event.key === "o" && steps[current]) steps[current].open = !steps[current].open;
+  else return;
+  event.preventDefault();
+});
+window.addEventListener("scroll", () => {
+  const passed = steps.filter(step => step.getBoundingClientRect().top <= 80).length;
+  if (passed > 0 && passed - 1 !== current) {
+    current = passed - 1;
+    mark();
+  }
+});
+links.forEach((link, i) => link.addEventListener("click", () => {
+  current = i;
+  steps[i].open = true;
+  mark();
+}));
+document.getElementById("expand-all").onclick = () => steps.forEach(step => step.open = true);
+document.getElementById("collapse-all").onclick = () => steps.forEach(step => step.open = false);
+mark();
+"##;
+
+/// Renders the walkthrough as a standalone HTML document.
+pub fn render(walkthrough: &Walkthrough, reviewed: &[bool], mode: ReviewMode) -> String {
+    let title = match mode {
+        ReviewMode::Review => "docent review",
+        ReviewMode::Walkthrough => "docent walkthrough",
+    };
+    let is_reviewed = |i: usize| reviewed.get(i).copied().unwrap_or(false);
+
+    let mut out = String::new();
+    let _ = write!(
+        out,
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+         <title>{title}</title>\n<style>{STYLE}</style>\n</head>\n<body>\n"
+    );
+
+    // Sidebar navigation
+    out.push_str("<nav>\n<h3>Steps</h3>\n<ol>\n");
+    for (i, step) in walkthrough.steps.iter().enumerate() {
+        let mark = if is_reviewed(i) { "✓ " } else { "" };
+        let _ = writeln!(
+            out,
+            "<li><a href=\"#step-{}\">{}{}</a></li>",
+            i + 1,
+            mark,
+            escape(&step.title)
+        );
+    }
+    out.push_str("</ol>\n</nav>\n<main>\n");
+
+    let reviewed_count = (0..walkthrough.step_count()).filter(|&i| is_reviewed(i)).count();
+    let _ = writeln!(
+        out,
+        "<h1>{}</h1>\n<p>{} steps, {} reviewed</p>",
+        title,
+        walkthrough.step_count(),
+        reviewed_count
+    );
+    out.push_str(
+        "<div class=\"toolbar\"><button id=\"expand-all\">Expand all</button>\
+         <button id=\"collapse-all\">Collapse all</button> \
+         <kbd>j</kbd>/<kbd>k</kbd> next and previous step, <kbd>o</kbd> open or close it</div>\n",
+    );
+
+    render_findings(&mut out, walkthrough);
+    render_flags(&mut out, walkthrough);
+    if !walkthrough.messages.is_empty() {
+        out.push_str("<section class=\"chat\">\n<h2>Discussion of the whole change</h2>\n");
+        render_messages(&mut out, &walkthrough.messages);
+        out.push_str("</section>\n");
+    }
+
+    for (i, step) in walkthrough.steps.iter().enumerate() {
+        render_step(&mut out, i, step, is_reviewed(i), walkthrough.step_count());
+    }
+
+    let _ = write!(out, "</main>\n<script>{SCRIPT}</script>\n</body>\n</html>\n");
+    out
+}
+
+fn render_findings(out: &mut String, walkthrough: &Walkthrough) {
+    let critical: Vec<(usize, &Step)> = walkthrough
+        .steps
+        .iter()
+        .enumerate()
+        .filter(|(_, s)| s.priority == Priority::Critical)
+        .collect();
+    if critical.is_empty() {
+        return;
+    }
+
+    out.push_str("<section class=\"findings\">\n<h2>Findings</h2>\n<ul>\n");
+    for (i, step) in critical {
+        let _ = writeln!(
+            out,
+            "<li><a href=\"#step-{}\">{}</a> (critical)</li>",
+            i + 1,
+            escape(&step.title)
+        );
+    }
+    out.push_str("</ul>\n</section>\n");
+}
+
+/// The reviewer's flags across all steps, most serious first.
+fn render_flags(out: &mut String, walkthrough: &Walkthrough) {
+    let flags = walkthrough.flags();
+    if flags.is_empty() {
+        return;
+    }
+
+    let _ = writeln!(
+        out,
+        "<section class=\"flags\">\n<h2>Review summary</h2>\n<p>{}</p>\n<ul>",
+        walkthrough.flag_tally()
+    );
+    for (i, flag) in flags {
+        let _ = write!(
+            out,
+            "<li><span class=\"flag {0}\">{0}</span> <a href=\"#step-{1}\">{2}</a>",
+            flag.severity.label(),
+            i + 1,
+            escape(&walkthrough.steps[i].title)
+        );
+        if let Some(location) = flag.location_label() {
+            let _ = write!(out, " <code>{}</code>", escape(&location));
+        }
+        if !flag.text.is_empty() {
+            let _ = write!(out, " — {}", escape(flag.text.trim()));
+        }
+        out.push_str("</li>\n");
+    }
+    out.push_str("</ul>\n</section>\n");
+}
+
+fn render_step(out: &mut String, index: usize, step: &Step, reviewed: bool, step_count: usize) {
+    let (priority_class, priority_label) = match step.priority {
+        Priority::Critical => ("critical", "critical"),
+        Priority::Normal => ("normal", "normal"),
+        Priority::Minor => ("minor", "minor"),
+    };
+    let mark = if reviewed {
+        "<span class=\"reviewed\">✓</span>"
+    } else {
+        ""
+    };
+    let _ = writeln!(
+        out,
+        "<details class=\"step\" id=\"step-{}\" open>\n<summary>{}{} {}<span class=\"priority {}\">{}</span></summary>\n<div class=\"step-body\">",
+        index + 1,
+        mark,
+        escape(&step.id),
+        escape(&step.title),
+        priority_class,
+        priority_label
+    );
+
+    out.push_str(&markdown_to_html(&step.summary));
+
+    for hunk in &step.hunks {
+        let symbol = hunk
+            .symbol
+            .as_ref()
+            .map(|s| format!(" <span class=\"symbol\">· {}</span>", escape(s)))
+            .unwrap_or_default();
+        let _ = writeln!(
+            out,
+            "<div class=\"file\">{}{}</div>",
+            escape(&hunk.file_path),
+            symbol
+        );
+        out.push_str("<pre class=\"diff\">");
+        let highlights = highlight_hunk(&hunk.file_path, &hunk.content);
+        for (n, line) in hunk.content.lines().enumerate() {
+            let class = if line.starts_with("@@") {
+                "hunk"
+            } else if line.starts_with('+') {
+                "add"
+            } else if line.starts_with('-') {
+                "del"
+            } else {
+                "ctx"
+            };
+            let tokens = highlights.as_ref().and_then(|h| h.get(n)).map_or(&[][..], Vec::as_slice);
+            let _ = write!(
+                out,
+                "<span class=\"{}\">{}</span>",
+                class,
+                highlight_line(line, tokens)
+            );
+        }
+        out.push_str("</pre>\n");
+    }
+
+    if !step.notes.is_empty() {
+        out.push_str("<div class=\"notes\">\n<h4>Notes</h4>\n");
+        for note in &step.notes {
+            let _ = writeln!(out, "<p>{}</p>", escape(note.text.trim()));
+        }
+        out.push_str("</div>\n");
+    }
+
+    // The first assistant message is the seeded summary; only real chat follows.
+    let seeded = step
+        .messages
+        .iter()
+        .take_while(|m| m.role == MessageRole::Assistant && m.content == step.summary)
+        .count();
+    let chat = &step.messages[seeded..];
+    if !chat.is_empty() {
+        out.push_str("<div class=\"chat\">\n<h4>Discussion</h4>\n");
+        render_messages(out, chat);
+        out.push_str("</div>\n");
+    }
+
+    // Links to the neighbouring steps, for reading without the script
+    out.push_str("<div class=\"step-nav\"><span>");
+    if index > 0 {
+        let _ = write!(out, "<a href=\"#step-{}\">← Previous step</a>", index);
+    }
+    out.push_str("</span><span>");
+    if index + 1 < step_count {
+        let _ = write!(out, "<a href=\"#step-{}\">Next step →</a>", index + 2);
+    }
+    out.push_str("</span></div>\n");
+
+    out.push_str("</div>\n</details>\n");
+}
+
+fn render_messages(out: &mut String, messages: &[Message]) {
+    for message in messages {
+        match message.role {
+            MessageRole::User => {
+                let _ = writeln!(
+                    out,
+                    "<div class=\"msg user\">{}</div>",
+                    escape(&message.content)
+                );
+            }
+            MessageRole::Assistant => {
+                let _ = writeln!(
+                    out,
+                    "<div class=\"msg assistant\">{}</div>",
+                    markdown_to_html(&message.content)
+                );
+            }
+        }
+    }
+}
+
+/// A diff line, escaped, with its syntax highlights wrapped in spans.
+fn highlight_line(line: &str, highlights: &[Highlight]) -> String {
+    let mut out = String::new();
+    let mut at = 0;
+    for (range, kind) in highlights {
+        out.push_str(&escape(&line[at..range.start]));
+        let _ = write!(
+            out,
+            "<span class=\"tok {}\">{}</span>",
+            kind,
+            escape(&line[range.clone()])
+        );
+        at = range.end;
+    }
+    out.push_str(&escape(&line[at..]));
+    out
+}
+
+fn markdown_to_html(text: &str) -> String {
+    let mut rendered = String::new();
+    html::push_html(&mut rendered, Parser::new(text));
+    rendered
+}
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{Flag, Hunk, Message, Note, Severity};
+
+    fn sample() -> Walkthrough {
+        Walkthrough {
+            steps: vec![Step {
+                title: "Add <Session> model".to_string(),
+                summary: "Adds **sessions**".to_string(),
+                priority: Priority::Critical,
+                hunks: vec![Hunk::test(
+                    "src/session.rs",
+                    1,
+                    2,
+                    "@@ -0,0 +1,2 @@\n+struct Session;\n-old",
+                )],
+                messages: vec![
+                    Message::assistant("Adds **sessions**"),
+                    Message::user("why <this>?"),
+                    Message::assistant("Because."),
+                ],
+                notes: vec![Note {
+                    text: "Lock <order>?".to_string(),
+                }],
+                flags: vec![Flag {
+                    severity: Severity::Question,
+                    text: "Why <Mutex>?".to_string(),
+                    location: None,
+                }],
+                ..Step::test("1", "src/session.rs")
+            }],
+            messages: vec![],
+        }
+    }
+
+    #[test]
+    fn escapes_titles_and_diff() {
+        let html = render(&sample(), &[false], ReviewMode::Review);
+        assert!(html.contains("Add &lt;Session&gt; model"));
+        assert!(!html.contains("<Session>"));
+        assert!(html.contains(
+            "<span class=\"add\">+<span class=\"tok keyword\">struct</span> \
+             <span class=\"tok type\">Session</span>;</span>"
+        ));
+        assert!(html.contains("<span class=\"del\">-old</span>"));
+    }
+
+    #[test]
+    fn includes_findings_notes_and_chat() {
+        let html = render(&sample(), &[true], ReviewMode::Review);
+        assert!(html.contains("<h2>Findings</h2>"));
+        assert!(html.contains("<p>1 question</p>"));
+        assert!(html.contains("Add &lt;Session&gt; model</a> — Why &lt;Mutex&gt;?</li>"));
+        assert!(html.contains("<p>Lock &lt;order&gt;?</p>"));
+        assert!(html.contains("why &lt;this&gt;?"));
+        assert!(html.contains("<strong>sessions</strong>"));
+        assert!(html.contains("1 reviewed"));
+    }
+
+    #[test]
+    fn links_steps_and_includes_the_whole_change_chat() {
+        let mut walkthrough = sample();
+        walkthrough.steps.push(walkthrough.steps[0].clone());
+        walkthrough.messages = vec![Message::user("Any <migration> risk?")];
+        let html = render(&walkthrough, &[false, false], ReviewMode::Review);
+        assert!(html.contains("<h2>Discussion of the whole change</h2>"));
+        assert!(html.contains("Any &lt;migration&gt; risk?"));
+        assert!(html.contains("<a href=\"#step-2\">Next step →</a>"));
+        assert!(html.contains("<a href=\"#step-1\">← Previous step</a>"));
+        assert!(html.contains("<script>"));
+    }
+}
@@ -0,0 +1,213 @@
+//! Markdown report: step titles, priorities, summaries, hunks as diff code
+//! blocks, and the notes and chat under each step, ready to paste into a PR
+//! comment.
+
+use std::fmt::Write;
+
+use crate::model::{MessageRole, Priority, ReviewMode, Step, Walkthrough};
+
+/// Renders the walkthrough as a single Markdown document.
+pub fn render(walkthrough: &Walkthrough, reviewed: &[bool], mode: ReviewMode) -> String {
+    let title = match mode {
+        ReviewMode::Review => "docent review",
+        ReviewMode::Walkthrough => "docent walkthrough",
+    };
+    let is_reviewed = |i: usize| reviewed.get(i).copied().unwrap_or(false);
+    let reviewed_count = (0..walkthrough.step_count())
+        .filter(|&i| is_reviewed(i))
+        .count();
+
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "# {}\n\n{} steps, {} reviewed\n",
+        title,
+        walkthrough.step_count(),
+        reviewed_count
+    );
+
+    let critical: Vec<(usize, &Step)> = walkthrough
+        .steps
+        .iter()
+        .enumerate()
+        .filter(|(_, s)| s.priority == Priority::Critical)
+        .collect();
+    if !critical.is_empty() {
+        out.push_str("## Findings\n\n");
+        for (i, step) in critical {
+            let _ = writeln!(out, "- Step {}: {} (critical)", i + 1, step.title);
+        }
+        out.push('\n');
+    }
+    render_flags(&mut out, walkthrough);
+
+    for (i, step) in walkthrough.steps.iter().enumerate() {
+        render_step(&mut out, i, step, is_reviewed(i));
+    }
+    out
+}
+
+/// The reviewer's flags across all steps, most serious first.
+fn render_flags(out: &mut String, walkthrough: &Walkthrough) {
+    let flags = walkthrough.flags();
+    if flags.is_empty() {
+        return;
+    }
+    let _ = writeln!(out, "## Review summary\n\n{}\n", walkthrough.flag_tally());
+    for (i, flag) in flags {
+        let _ = write!(
+            out,
+            "- **{}** Step {}: {}",
+            flag.severity.label(),
+            i + 1,
+            walkthrough.steps[i].title
+        );
+        if let Some(location) = flag.location_label() {
+            let _ = write!(out, " (`{}`)", location);
+        }
+        if !flag.text.is_empty() {
+            let _ = write!(out, " — {}", flag.text.trim().replace('\n', " "));
+        }
+        out.push('\n');
+    }
+    out.push('\n');
+}
+
+fn render_step(out: &mut String, index: usize, step: &Step, reviewed: bool) {
+    let priority = match step.priority {
+        Priority::Critical => "critical",
+        Priority::Normal => "normal",
+        Priority::Minor => "minor",
+    };
+    let mark = if reviewed { " ✓" } else { "" };
+    let _ = writeln!(
+        out,
+        "## {}. {}{}\n\n*Priority: {}*\n\n{}\n",
+        index + 1,
+        step.title,
+        mark,
+        priority,
+        step.summary.trim()
+    );
+
+    for hunk in &step.hunks {
+        let symbol = hunk
+            .symbol
+            .as_ref()
+            .map(|s| format!(" · {}", s))
+            .unwrap_or_default();
+        let fence = fence_for(&hunk.content);
+        let _ = writeln!(
+            out,
+            "**`{}`**{}\n\n{}diff\n{}\n{}\n",
+            hunk.file_path,
+            symbol,
+            fence,
+            hunk.content.trim_end(),
+            fence
+        );
+    }
+
+    if !step.notes.is_empty() {
+        out.push_str("### Notes\n\n");
+        for note in &step.notes {
+            let mut lines = note.text.trim().lines();
+            let _ = writeln!(out, "- {}", lines.next().unwrap_or_default());
+            for line in lines {
+                let _ = writeln!(out, "  {}", line);
+            }
+        }
+        out.push('\n');
+    }
+
+    // The first assistant message is the seeded summary; only real chat follows.
+    let chat: Vec<_> = step
+        .messages
+        .iter()
+        .skip_while(|m| m.role == MessageRole::Assistant && m.content == step.summary)
+        .collect();
+    if !chat.is_empty() {
+        out.push_str("### Discussion\n\n");
+        for message in chat {
+            match message.role {
+                MessageRole::User => {
+                    for line in message.content.trim().lines() {
+                        let _ = writeln!(out, "> {}", line);
+                    }
+                    out.push('\n');
+                }
+                MessageRole::Assistant => {
+                    let _ = writeln!(out, "{}\n", message.content.trim());
+                }
+            }
+        }
+    }
+}
+
+/// A code fence longer than any backtick run in `content`.
+fn fence_for(content: &str) -> String {
+    let longest = content.split(|c| c != '`').map(str::len).max().unwrap_or(0);
+    "`".repeat(longest.max(2) + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{Flag, Hunk, Message, Note, Severity};
+
+    fn sample() -> Walkthrough {
+        Walkthrough {
+            steps: vec![Step {
+                title: "Add session model".to_string(),
+                summary: "Adds **sessions**".to_string(),
+                priority: Priority::Critical,
+                hunks: vec![Hunk {
+                    symbol: Some("Session".to_string()),
+                    ..Hunk::test(
+                        "src/session.rs",
+                        1,
+                        2,
+                        "@@ -0,0 +1,2 @@\n+/// ```\n+struct Session;",
+                    )
+                }],
+                messages: vec![
+                    Message::assistant("Adds **sessions**"),
+                    Message::user("why this?"),
+                    Message::assistant("Because."),
+                ],
+                notes: vec![Note {
+                    text: "Check the lock order\nagainst main".to_string(),
+                }],
+                flags: vec![Flag {
+                    severity: Severity::Blocker,
+                    text: "Never unlocked".to_string(),
+                    location: Some(("src/session.rs".to_string(), 1)),
+                }],
+                ..Step::test("1", "src/session.rs")
+            }],
+            messages: vec![],
+        }
+    }
+
+    #[test]
+    fn renders_steps_hunks_and_chat() {
+        let markdown = render(&sample(), &[true], ReviewMode::Review);
+        assert!(markdown.contains("1 steps, 1 reviewed"));
+        assert!(markdown.contains("- Step 1: Add session model (critical)"));
+        assert!(markdown.contains("## 1. Add session model ✓"));
+        assert!(markdown.contains("**`src/session.rs`** · Session"));
+        assert!(markdown.contains("### Notes\n\n- Check the lock order\n  against main\n"));
+        assert!(markdown.contains("## Review summary\n\n1 blocker\n"));
+        assert!(markdown.contains(
+            "- **blocker** Step 1: Add session model (`src/session.rs:1`) — Never unlocked"
+        ));
+        assert!(markdown.contains("> why this?\n\nBecause."));
+    }
+
+    #[test]
+    fn fences_outlast_backticks_in_hunks() {
+        let markdown = render(&sample(), &[false], ReviewMode::Review);
+        assert!(markdown.contains("````diff\n@@ -0,0 +1,2 @@"));
+        assert!(markdown.contains("+struct Session;\n````"));
+    }
+}
@@ -0,0 +1,158 @@
+mod html;
+mod markdown;
+mod summary;
+
+pub use html::render as render_html;
+pub use markdown::render as render_markdown;
+pub use summary::{ReviewTotals, render as render_summary};
+
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::model::{ReviewMode, Walkthrough};
+use crate::session::Session;
+
+pub const HTML_EXPORT_FILE: &str = "docent-review.html";
+pub const MARKDOWN_EXPORT_FILE: &str = "docent-review.md";
+pub const JSON_EXPORT_FILE: &str = "docent-review.json";
+
+/// Output format for walkthroughs generated without the TUI.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+pub enum ReportFormat {
+    #[default]
+    Markdown,
+    Json,
+}
+
+/// A walkthrough and its review state as JSON: steps with their hunks,
+/// chats, notes and flags, the chat about the whole change, and which steps
+/// are reviewed. Other tools can produce one to open with
+/// `--from-walkthrough`.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WalkthroughFile {
+    #[serde(default)]
+    pub mode: ReviewMode,
+    #[serde(flatten)]
+    pub walkthrough: Walkthrough,
+    /// Whether each step is reviewed, in step order
+    #[serde(default)]
+    pub reviewed: Vec<bool>,
+}
+
+impl WalkthroughFile {
+    /// Reads a walkthrough written by `render_json` or another tool.
+    pub fn read(path: &Path) -> std::io::Result<Self> {
+        let text = std::fs::read_to_string(path)?;
+        let mut file: Self = serde_json::from_str(&text).map_err(|e| {
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("{} is not a docent walkthrough: {}", path.display(), e),
+            )
+        })?;
+        file.reviewed.resize(file.walkthrough.steps.len(), false);
+        Ok(file)
+    }
+}
+
+/// Renders a walkthrough and which of its steps are reviewed as JSON.
+pub fn render_json(walkthrough: &Walkthrough, reviewed: &[bool], mode: ReviewMode) -> String {
+    let file = WalkthroughFile {
+        mode,
+        walkthrough: walkthrough.clone(),
+        reviewed: reviewed.to_vec(),
+    };
+    let json = serde_json::to_string_pretty(&file).unwrap_or_default();
+    format!("{json}\n")
+}
+
+/// Renders a freshly generated walkthrough, with no steps reviewed yet.
+pub fn render_report(walkthrough: &Walkthrough, mode: ReviewMode, format: ReportFormat) -> String {
+    match format {
+        ReportFormat::Markdown => {
+            render_markdown(walkthrough, &vec![false; walkthrough.steps.len()], mode)
+        }
+        ReportFormat::Json => {
+            render_json(walkthrough, &vec![false; walkthrough.steps.len()], mode)
+        }
+    }
+}
+
+/// Writes the session's walkthrough as a standalone HTML bundle.
+pub fn write_html(session: &Session, path: &Path) -> std::io::Result<PathBuf> {
+    let html = render_html(
+        &session.walkthrough,
+        &session.reviewed_steps,
+        session.review_mode,
+    );
+    std::fs::write(path, html)?;
+    Ok(path.to_path_buf())
+}
+
+/// Writes the session's walkthrough as a Markdown report.
+pub fn write_markdown(session: &Session, path: &Path) -> std::io::Result<PathBuf> {
+    let markdown = render_markdown(
+        &session.walkthrough,
+        &session.reviewed_steps,
+        session.review_mode,
+    );
+    std::fs::write(path, markdown)?;
+    Ok(path.to_path_buf())
+}
+
+/// Writes the session's walkthrough and review state as JSON.
+pub fn write_json(session: &Session, path: &Path) -> std::io::Result<PathBuf> {
+    let json = render_json(
+        &session.walkthrough,
+        &session.reviewed_steps,
+        session.review_mode,
+    );
+    std::fs::write(path, json)?;
+    Ok(path.to_path_buf())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{Message, Priority, Step};
+
+    #[test]
+    fn json_report() {
+        let walkthrough = Walkthrough {
+            steps: vec![Step {
+                title: "Add parser".to_string(),
+                summary: "Parses input.".to_string(),
+                hunks: vec![],
+                ..Step::test("1", "")
+            }],
+            messages: vec![],
+        };
+        let json = render_report(&walkthrough, ReviewMode::Walkthrough, ReportFormat::Json);
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value["mode"], "walkthrough");
+        assert_eq!(value["steps"][0]["title"], "Add parser");
+        assert_eq!(value["reviewed"][0], false);
+    }
+
+    #[test]
+    fn json_walkthrough_round_trips() {
+        let walkthrough = Walkthrough {
+            steps: vec![Step {
+                title: "Add parser".to_string(),
+                summary: "Parses input.".to_string(),
+                priority: Priority::Critical,
+                hunks: vec![],
+                messages: vec![Message::user("Why a parser?")],
+                ..Step::test("1", "")
+            }],
+            messages: vec![Message::user("Any migration risk?")],
+        };
+        let json = render_json(&walkthrough, &[true], ReviewMode::Review);
+        let file: WalkthroughFile = serde_json::from_str(&json).unwrap();
+        assert_eq!(file.mode, ReviewMode::Review);
+        assert_eq!(file.reviewed, [true]);
+        assert_eq!(file.walkthrough.steps[0].priority, Priority::Critical);
+        assert_eq!(file.walkthrough.steps[0].messages[0].content, "Why a parser?");
+        assert_eq!(file.walkthrough.messages[0].content, "Any migration risk?");
+    }
+}
@@ -0,0 +1,122 @@
+//! Plain-text summary of a finished review: progress, time, tokens, and the
+//! reviewer's flags, short enough to paste into a PR comment or chat.
+
+use std::fmt::Write;
+use std::time::Duration;
+
+use crate::api::TokenUsage;
+use crate::model::{ReviewMode, Walkthrough};
+use crate::stats::format_duration;
+
+/// Steps reviewed and in total, time spent, and tokens used (with their
+/// estimated cost, when the model's prices are known) over a review.
+#[derive(Debug, Clone, Copy)]
+pub struct ReviewTotals {
+    pub reviewed: usize,
+    pub steps: usize,
+    pub elapsed: Duration,
+    pub usage: TokenUsage,
+    pub cost_usd: Option<f64>,
+}
+
+/// Renders the summary, with one line per flag, most serious first.
+pub fn render(walkthrough: &Walkthrough, mode: ReviewMode, totals: ReviewTotals) -> String {
+    let title = match mode {
+        ReviewMode::Review => "docent review",
+        ReviewMode::Walkthrough => "docent walkthrough",
+    };
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "{}: {} of {} steps reviewed in {}",
+        title,
+        totals.reviewed,
+        totals.steps,
+        format_duration(totals.elapsed.as_secs())
+    );
+    let _ = write!(
+        out,
+        "Tokens: {} in, {} out",
+        totals.usage.input_tokens, totals.usage.output_tokens
+    );
+    if let Some(cost) = totals.cost_usd {
+        let _ = write!(out, " (~${:.2})", cost);
+    }
+    out.push('\n');
+
+    let flags = walkthrough.flags();
+    if flags.is_empty() {
+        out.push_str("No flags raised\n");
+        return out;
+    }
+    let _ = writeln!(out, "Flags: {}", walkthrough.flag_tally());
+    for (i, flag) in flags {
+        let _ = write!(
+            out,
+            "- {} · Step {}: {}",
+            flag.severity.label(),
+            i + 1,
+            walkthrough.steps[i].title
+        );
+        if let Some(location) = flag.location_label() {
+            let _ = write!(out, " ({})", location);
+        }
+        if !flag.text.is_empty() {
+            let _ = write!(out, " — {}", flag.text.trim().replace('\n', " "));
+        }
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{Flag, Severity, Step};
+
+    fn totals() -> ReviewTotals {
+        ReviewTotals {
+            reviewed: 2,
+            steps: 2,
+            elapsed: Duration::from_secs(843),
+            usage: TokenUsage {
+                input_tokens: 12_000,
+                output_tokens: 900,
+            },
+            cost_usd: Some(0.05),
+        }
+    }
+
+    #[test]
+    fn summarizes_progress_and_flags() {
+        let walkthrough = Walkthrough {
+            steps: vec![Step {
+                title: "Add cache".to_string(),
+                hunks: vec![],
+                flags: vec![Flag {
+                    severity: Severity::Blocker,
+                    text: "Never evicts".to_string(),
+                    location: Some(("src/cache.rs".to_string(), 12)),
+                }],
+                ..Step::test("1", "")
+            }],
+            messages: vec![],
+        };
+        let summary = render(&walkthrough, ReviewMode::Review, totals());
+        assert_eq!(
+            summary,
+            "docent review: 2 of 2 steps reviewed in 14m 3s\n\
+             Tokens: 12000 in, 900 out (~$0.05)\n\
+             Flags: 1 blocker\n\
+             - blocker · Step 1: Add cache (src/cache.rs:12) — Never evicts\n"
+        );
+    }
+
+    #[test]
+    fn says_when_nothing_was_flagged() {
+        let walkthrough = Walkthrough::default();
+        let summary = render(&walkthrough, ReviewMode::Walkthrough, totals());
+        assert!(summary.starts_with("docent walkthrough: "));
+        assert!(summary.ends_with("No flags raised\n"));
+    }
+}
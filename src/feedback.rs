@@ -0,0 +1,217 @@
+//! Thumbs up/down feedback on generated steps.
+//!
+//! Each rating is appended as a JSON line to ~/.docent/feedback.jsonl along
+//! with the model and prompt options that produced the step, so ratings can
+//! be compared across configurations with `docent feedback`.
+
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::fs::{self, OpenOptions};
+use std::io::Write as _;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::model::{ReviewMode, Step, StepOrganization};
+use crate::session::Session;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Rating {
+    Up,
+    Down,
+}
+
+impl Rating {
+    pub fn toggle(self) -> Self {
+        match self {
+            Self::Up => Self::Down,
+            Self::Down => Self::Up,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeedbackEntry {
+    /// Seconds since the Unix epoch
+    pub recorded_at: u64,
+    pub model: Option<String>,
+    pub mode: ReviewMode,
+    pub organization: StepOrganization,
+    #[serde(default)]
+    pub interdiff: bool,
+    pub step_title: String,
+    pub hunk_count: usize,
+    pub rating: Rating,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+}
+
+impl FeedbackEntry {
+    pub fn new(session: &Session, step: &Step, rating: Rating, comment: &str) -> Self {
+        let comment = comment.trim();
+        Self {
+            recorded_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |d| d.as_secs()),
+            model: session.generation_progress.model.clone(),
+            mode: session.review_mode,
+            organization: session.step_organization,
+            interdiff: session.interdiff,
+            step_title: step.title.clone(),
+            hunk_count: step.hunks.len(),
+            rating,
+            comment: (!comment.is_empty()).then(|| comment.to_string()),
+        }
+    }
+
+    /// Model and prompt options, as shown in the summary.
+    fn configuration(&self) -> String {
+        let mode = match self.mode {
+            ReviewMode::Review => "review",
+            ReviewMode::Walkthrough => "walkthrough",
+        };
+        let organization = match self.organization {
+            StepOrganization::Narrative => "narrative",
+            StepOrganization::Directory => "directory",
+        };
+        let mut label = format!(
+            "{} · {} · {}",
+            self.model.as_deref().unwrap_or("unknown model"),
+            mode,
+            organization
+        );
+        if self.interdiff {
+            label.push_str(" · interdiff");
+        }
+        label
+    }
+}
+
+/// The feedback dialog for the current step.
+#[derive(Debug, Clone)]
+pub struct FeedbackDialog {
+    pub step: usize,
+    pub rating: Rating,
+    pub comment: String,
+}
+
+impl FeedbackDialog {
+    pub fn new(step: usize) -> Self {
+        Self {
+            step,
+            rating: Rating::Up,
+            comment: String::new(),
+        }
+    }
+}
+
+fn feedback_path() -> Option<PathBuf> {
+    dirs::home_dir().map(|h| h.join(".docent").join("feedback.jsonl"))
+}
+
+/// Appends an entry to the feedback log.
+pub fn record(entry: &FeedbackEntry) -> Result<(), String> {
+    let path = feedback_path().ok_or("Could not determine home directory")?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Failed to create feedback dir: {e}"))?;
+    }
+    let line =
+        serde_json::to_string(entry).map_err(|e| format!("Failed to serialize feedback: {e}"))?;
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .map_err(|e| format!("Failed to open feedback log: {e}"))?;
+    writeln!(file, "{line}").map_err(|e| format!("Failed to write feedback: {e}"))
+}
+
+/// All recorded entries, skipping lines that don't parse.
+pub fn load() -> Vec<FeedbackEntry> {
+    feedback_path()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .map(|contents| {
+            contents
+                .lines()
+                .filter_map(|line| serde_json::from_str(line).ok())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Ratings per configuration, best-rated first, with the comments on
+/// thumbs-down steps.
+pub fn summary(entries: &[FeedbackEntry]) -> String {
+    if entries.is_empty() {
+        return "No feedback recorded yet. Press f on a step to rate it.\n".to_string();
+    }
+
+    let mut groups: BTreeMap<String, Vec<&FeedbackEntry>> = BTreeMap::new();
+    for entry in entries {
+        groups.entry(entry.configuration()).or_default().push(entry);
+    }
+    let mut groups: Vec<_> = groups.into_iter().collect();
+    let share_up = |entries: &[&FeedbackEntry]| {
+        let up = entries.iter().filter(|e| e.rating == Rating::Up).count();
+        up as f64 / entries.len() as f64
+    };
+    groups.sort_by(|(_, a), (_, b)| share_up(b).total_cmp(&share_up(a)));
+
+    let mut out = String::new();
+    for (configuration, entries) in groups {
+        let up = entries.iter().filter(|e| e.rating == Rating::Up).count();
+        let _ = writeln!(
+            out,
+            "{}: {} up, {} down ({:.0}% useful)",
+            configuration,
+            up,
+            entries.len() - up,
+            share_up(&entries) * 100.0
+        );
+        for entry in entries.iter().filter(|e| e.rating == Rating::Down) {
+            if let Some(comment) = &entry.comment {
+                let _ = writeln!(out, "  - {}: {}", entry.step_title, comment);
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(model: &str, rating: Rating, comment: Option<&str>) -> FeedbackEntry {
+        FeedbackEntry {
+            recorded_at: 0,
+            model: Some(model.to_string()),
+            mode: ReviewMode::Review,
+            organization: StepOrganization::Narrative,
+            interdiff: false,
+            step_title: "Add retry".to_string(),
+            hunk_count: 2,
+            rating,
+            comment: comment.map(String::from),
+        }
+    }
+
+    #[test]
+    fn test_summary_groups_by_configuration() {
+        let entries = [
+            entry("model-a", Rating::Down, Some("missed the race")),
+            entry("model-a", Rating::Up, None),
+            entry("model-b", Rating::Up, None),
+        ];
+        let summary = summary(&entries);
+        let lines: Vec<&str> = summary.lines().collect();
+        assert_eq!(
+            lines,
+            [
+                "model-b · review · narrative: 1 up, 0 down (100% useful)",
+                "model-a · review · narrative: 1 up, 1 down (50% useful)",
+                "  - Add retry: missed the race",
+            ]
+        );
+    }
+}
@@ -0,0 +1,174 @@
+//! Pre-generation file selection.
+//!
+//! Lists the files in the diff with checkboxes, pre-checked according to the
+//! `--filter`/`--exclude` patterns and the default denylist, so lockfiles and
+//! snapshots can be dropped interactively before the walkthrough is generated.
+
+use crate::diff::{FileFilter, ParsedDiff};
+
+#[derive(Debug, Clone)]
+pub struct FileEntry {
+    pub path: String,
+    pub hunks: usize,
+    pub selected: bool,
+    /// Unchecked by default because it matches the generated/vendored denylist
+    pub denied: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct FileSelection {
+    pub files: Vec<FileEntry>,
+    pub cursor: usize,
+    pub error: Option<String>,
+}
+
+impl FileSelection {
+    /// Builds the file list from a diff, checking the files that pass
+    /// `filter`. Returns None when there is nothing to choose between.
+    pub fn from_diff(diff_text: &str, filter: &FileFilter) -> Option<Self> {
+        let parsed = ParsedDiff::parse(diff_text).ok()?;
+        let mut files: Vec<FileEntry> = Vec::new();
+        for hunk in &parsed.hunks {
+            match files.iter_mut().find(|f| f.path == hunk.file_path) {
+                Some(file) => file.hunks += 1,
+                None => files.push(FileEntry {
+                    path: hunk.file_path.clone(),
+                    hunks: 1,
+                    selected: filter.matches(&hunk.file_path),
+                    denied: filter.is_denied(&hunk.file_path),
+                }),
+            }
+        }
+        (files.len() > 1).then_some(Self {
+            files,
+            cursor: 0,
+            error: None,
+        })
+    }
+
+    pub fn move_cursor(&mut self, delta: isize) {
+        let last = self.files.len().saturating_sub(1) as isize;
+        self.cursor = (self.cursor as isize).saturating_add(delta).clamp(0, last) as usize;
+    }
+
+    pub fn toggle(&mut self) {
+        if let Some(file) = self.files.get_mut(self.cursor) {
+            file.selected = !file.selected;
+            self.error = None;
+        }
+    }
+
+    pub fn select_all(&mut self, selected: bool) {
+        for file in &mut self.files {
+            file.selected = selected;
+        }
+        self.error = None;
+    }
+
+    /// Flips every checkbox, so a few files can be reviewed on their own.
+    pub fn invert(&mut self) {
+        for file in &mut self.files {
+            file.selected = !file.selected;
+        }
+        self.error = None;
+    }
+
+    /// Re-checks the files the denylist skipped.
+    pub fn include_denied(&mut self) {
+        for file in self.files.iter_mut().filter(|f| f.denied) {
+            file.selected = true;
+        }
+        self.error = None;
+    }
+
+    /// Denylisted files that are still unchecked.
+    pub fn skipped_denied(&self) -> Vec<&str> {
+        self.files
+            .iter()
+            .filter(|f| f.denied && !f.selected)
+            .map(|f| f.path.as_str())
+            .collect()
+    }
+
+    pub fn selected_count(&self) -> usize {
+        self.files.iter().filter(|f| f.selected).count()
+    }
+
+    /// The filter to generate with: everything except the unchecked files.
+    /// The checkboxes replace the CLI patterns, which only set the defaults.
+    pub fn to_filter(&self) -> FileFilter {
+        FileFilter::excluding_files(
+            self.files
+                .iter()
+                .filter(|f| !f.selected)
+                .map(|f| f.path.clone()),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DIFF: &str = "\
+diff --git a/src/main.rs b/src/main.rs
+--- a/src/main.rs
++++ b/src/main.rs
+@@ -1,2 +1,2 @@
+-old
++new
+@@ -10,2 +10,2 @@
+-old
++new
+diff --git a/Cargo.lock b/Cargo.lock
+--- a/Cargo.lock
++++ b/Cargo.lock
+@@ -1,2 +1,2 @@
+-old
++new
+";
+
+    #[test]
+    fn test_files_prechecked_from_filter() {
+        let filter = FileFilter::new(&[], &["*.lock".to_string()]).unwrap();
+        let selection = FileSelection::from_diff(DIFF, &filter).unwrap();
+        assert_eq!(selection.files.len(), 2);
+        assert_eq!(selection.files[0].hunks, 2);
+        assert!(selection.files[0].selected);
+        assert!(!selection.files[1].selected);
+    }
+
+    #[test]
+    fn test_include_denied_files() {
+        let filter = FileFilter::default()
+            .with_denylist(&["**/Cargo.lock".to_string()])
+            .unwrap();
+        let mut selection = FileSelection::from_diff(DIFF, &filter).unwrap();
+        assert_eq!(selection.skipped_denied(), ["Cargo.lock"]);
+
+        selection.include_denied();
+        assert!(selection.skipped_denied().is_empty());
+        assert_eq!(selection.selected_count(), 2);
+    }
+
+    #[test]
+    fn test_unchecked_files_are_excluded() {
+        let mut selection = FileSelection::from_diff(DIFF, &FileFilter::default()).unwrap();
+        selection.move_cursor(5);
+        selection.toggle();
+        assert_eq!(selection.selected_count(), 1);
+
+        let filter = selection.to_filter();
+        assert!(filter.matches("src/main.rs"));
+        assert!(!filter.matches("Cargo.lock"));
+    }
+
+    #[test]
+    fn test_invert_flips_every_file() {
+        let filter = FileFilter::new(&[], &["*.lock".to_string()]).unwrap();
+        let mut selection = FileSelection::from_diff(DIFF, &filter).unwrap();
+        selection.invert();
+        assert!(!selection.files[0].selected);
+        assert!(selection.files[1].selected);
+    }
+}
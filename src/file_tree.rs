@@ -0,0 +1,105 @@
+//! The changed files of a walkthrough as a directory tree.
+//!
+//! Each file lists the steps whose hunks touch it, so a reviewer who thinks
+//! in files can find the steps covering one.
+
+use std::collections::BTreeMap;
+
+use crate::model::Step;
+
+/// A row of the file tree: a directory, or a file with its steps.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileTreeEntry {
+    /// Nesting below the repository root
+    pub depth: usize,
+    /// Last component of the path
+    pub name: String,
+    /// Full path, for files only
+    pub path: Option<String>,
+    /// Indexes of the steps touching the file, in walkthrough order
+    pub steps: Vec<usize>,
+}
+
+/// Builds the tree of every file in `steps`, sorted by path, with a row for
+/// each directory ahead of its contents.
+pub fn build(steps: &[Step]) -> Vec<FileTreeEntry> {
+    let mut files: BTreeMap<&str, Vec<usize>> = BTreeMap::new();
+    for (index, step) in steps.iter().enumerate() {
+        for hunk in &step.hunks {
+            let touching = files.entry(&hunk.file_path).or_default();
+            if touching.last() != Some(&index) {
+                touching.push(index);
+            }
+        }
+    }
+
+    let mut entries = Vec::new();
+    let mut open_dirs: Vec<&str> = Vec::new();
+    for (path, touching) in files {
+        let mut components: Vec<&str> = path.split('/').collect();
+        let name = components.pop().unwrap_or(path);
+        // Keep the directories shared with the previous file open
+        let shared = open_dirs
+            .iter()
+            .zip(&components)
+            .take_while(|(open, dir)| open == dir)
+            .count();
+        open_dirs.truncate(shared);
+        for dir in &components[shared..] {
+            entries.push(FileTreeEntry {
+                depth: open_dirs.len(),
+                name: dir.to_string(),
+                path: None,
+                steps: Vec::new(),
+            });
+            open_dirs.push(dir);
+        }
+        entries.push(FileTreeEntry {
+            depth: open_dirs.len(),
+            name: name.to_string(),
+            path: Some(path.to_string()),
+            steps: touching,
+        });
+    }
+    entries
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::Hunk;
+
+    fn step(files: &[&str]) -> Step {
+        Step {
+            hunks: files
+                .iter()
+                .map(|file| Hunk::test(file, 1, 1, ""))
+                .collect(),
+            ..Step::test("", "")
+        }
+    }
+
+    #[test]
+    fn test_files_nest_under_their_directories() {
+        let steps = vec![
+            step(&["src/api/client.rs", "README.md"]),
+            step(&["src/main.rs", "src/api/client.rs", "src/api/client.rs"]),
+        ];
+        let tree = build(&steps);
+        let rows: Vec<(usize, &str, Vec<usize>)> = tree
+            .iter()
+            .map(|e| (e.depth, e.name.as_str(), e.steps.clone()))
+            .collect();
+        assert_eq!(
+            rows,
+            [
+                (0, "README.md", vec![0]),
+                (0, "src", vec![]),
+                (1, "api", vec![]),
+                (2, "client.rs", vec![0, 1]),
+                (1, "main.rs", vec![1]),
+            ]
+        );
+        assert_eq!(tree[3].path.as_deref(), Some("src/api/client.rs"));
+    }
+}
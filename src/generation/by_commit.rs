@@ -0,0 +1,152 @@
+//! Commit-by-commit walkthroughs of a revision range.
+//!
+//! Each commit becomes a top-level section step holding no hunks, with the
+//! steps generated from that commit's own diff nested beneath it. The
+//! commit's message goes into its prompt as context, so steps can speak to
+//! what the commit set out to do. Commits whose changes are all filtered
+//! out are left out.
+
+use tokio::sync::mpsc;
+
+use crate::api::{ClientConfig, TokenUsage};
+use crate::diff::{DiffParseError, FileFilter, FilterError};
+use crate::model::{CommitInfo, Message, Priority, ReviewMode, Step};
+
+use super::generator::{GenerationError, GenerationOptions, StreamEvent, WalkthroughGenerator};
+
+/// Characters of a commit hash shown in section titles.
+const SHORT_SHA_LEN: usize = 7;
+
+/// One commit of a range and the diff it introduced.
+#[derive(Debug, Clone)]
+pub struct CommitDiff {
+    pub commit: CommitInfo,
+    pub diff_text: String,
+}
+
+/// Generates a walkthrough of each commit in turn, streaming section steps
+/// and the steps under them as they are ready. The usage covers every
+/// commit's request. Fails like a filtered diff would when no commit has
+/// changes left to review.
+pub async fn generate_by_commit(
+    commits: Vec<CommitDiff>,
+    filter: &FileFilter,
+    mode: ReviewMode,
+    config: ClientConfig,
+    options: GenerationOptions,
+    event_tx: mpsc::Sender<StreamEvent>,
+) -> Result<TokenUsage, GenerationError> {
+    let mut usage = TokenUsage::default();
+    let mut sections = 0;
+    let mut started = false;
+
+    for CommitDiff { commit, diff_text } in commits {
+        let generator = match WalkthroughGenerator::with_filter(
+            &diff_text,
+            filter,
+            mode,
+            config.clone(),
+            vec![commit.clone()],
+        ) {
+            Ok(generator) => generator.with_options(options.clone()),
+            Err(GenerationError::DiffParse(
+                DiffParseError::EmptyDiff | DiffParseError::Filter(_),
+            )) => continue,
+            Err(e) => return Err(e),
+        };
+        sections += 1;
+        let _ = event_tx
+            .send(StreamEvent::StepReady(section_step(sections, &commit)))
+            .await;
+
+        let (commit_tx, mut commit_rx) = mpsc::channel::<StreamEvent>(32);
+        let tokens_before = usage.output_tokens;
+        let forward = async {
+            let mut steps = 0;
+            while let Some(event) = commit_rx.recv().await {
+                let event = match event {
+                    // Only the first request's start is reported
+                    StreamEvent::Started(_) if started => continue,
+                    StreamEvent::Started(model) => {
+                        started = true;
+                        StreamEvent::Started(model)
+                    }
+                    StreamEvent::Progress(tokens) => StreamEvent::Progress(tokens_before + tokens),
+                    StreamEvent::StepReady(step) => {
+                        steps += 1;
+                        StreamEvent::StepReady(nest_step(step, sections, steps))
+                    }
+                };
+                let _ = event_tx.send(event).await;
+            }
+        };
+        let (commit_usage, ()) = tokio::join!(generator.generate_streaming(commit_tx), forward);
+        usage += commit_usage?;
+    }
+
+    if sections == 0 {
+        return Err(DiffParseError::Filter(FilterError::NoMatches).into());
+    }
+    Ok(usage)
+}
+
+/// The top-level step introducing a commit.
+fn section_step(number: usize, commit: &CommitInfo) -> Step {
+    let short_sha: String = commit.sha.chars().take(SHORT_SHA_LEN).collect();
+    let summary = format!(
+        "Commit `{}`: {}\n\nThe steps below walk through the changes this commit made to {} \
+         file{}.",
+        short_sha,
+        commit.message,
+        commit.files.len(),
+        if commit.files.len() == 1 { "" } else { "s" }
+    );
+    Step {
+        id: number.to_string(),
+        title: format!("{} {}", short_sha, commit.message),
+        messages: vec![Message::assistant(&summary)],
+        summary,
+        priority: Priority::Normal,
+        hunks: vec![],
+        depth: 0,
+        notes: vec![],
+        flags: vec![],
+    }
+}
+
+/// A step generated from a commit's diff, moved under its section.
+fn nest_step(mut step: Step, section: usize, number: usize) -> Step {
+    step.id = format!("{}.{}", section, number);
+    step.depth += 1;
+    step
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn commit() -> CommitInfo {
+        CommitInfo {
+            sha: "0123456789abcdef".to_string(),
+            message: "Add retry to the client".to_string(),
+            files: vec!["src/client.rs".to_string()],
+        }
+    }
+
+    #[test]
+    fn section_step_introduces_the_commit() {
+        let step = section_step(2, &commit());
+        assert_eq!(step.id, "2");
+        assert_eq!(step.title, "0123456 Add retry to the client");
+        assert!(step.hunks.is_empty());
+        assert!(step.summary.contains("to 1 file."));
+        assert_eq!(step.messages[0].content, step.summary);
+    }
+
+    #[test]
+    fn nested_steps_sit_under_their_section() {
+        let step = nest_step(section_step(1, &commit()), 3, 2);
+        assert_eq!(step.id, "3.2");
+        assert_eq!(step.depth, 1);
+    }
+}
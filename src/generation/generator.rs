@@ -1,13 +1,25 @@
+use std::collections::{BTreeMap, HashSet};
+
 use thiserror::Error;
 use tokio::sync::mpsc;
 
 use crate::api::{
-    ApiError, ClientStreamEvent, ClaudeClient, RechunkResponse, TokenUsage, WalkthroughStepResponse,
+    ApiError, ClientConfig, ClientStreamEvent, LlmClient, RechunkResponse, TokenUsage, WalkthroughStepResponse,
+    walkthrough_system_prompt,
+};
+use crate::diff::{
+    DiffParseError, FileFilter, ParsedDiff, ParsedHunk, parse_hunk_header, read_worktree_file,
+};
+use crate::model::{
+    CommitInfo, Hunk, Message, Priority, PullRequestInfo, ReviewMode, Step, StepOrganization,
+    Walkthrough,
 };
-use crate::diff::{DiffParseError, FileFilter, ParsedDiff};
-use crate::model::{CommitInfo, Hunk, Message, Priority, ReviewMode, Step};
 
 pub enum StreamEvent {
+    /// Generation request sent to the given model
+    Started(String),
+    /// Output tokens received so far
+    Progress(u32),
     StepReady(Step),
 }
 
@@ -30,11 +42,38 @@ pub enum GenerationError {
     },
 }
 
+/// Aborts a spawned task when dropped, so cancelling generation also stops
+/// the API request it started.
+struct AbortOnDrop(tokio::task::AbortHandle);
+
+impl Drop for AbortOnDrop {
+    fn drop(&mut self) {
+        self.0.abort();
+    }
+}
+
+/// Choices and context that shape the generation prompt.
+#[derive(Debug, Clone, Default)]
+pub struct GenerationOptions {
+    pub organization: StepOrganization,
+    /// The diff is an interdiff between two revisions of the same change
+    pub interdiff: bool,
+    /// The pull request the diff came from, for the author's stated intent
+    pub pull_request: Option<PullRequestInfo>,
+    /// The reviewer's own steering for this walkthrough, e.g. "fewer, larger steps"
+    pub instructions: Option<String>,
+}
+
 pub struct WalkthroughGenerator {
     parsed_diff: ParsedDiff,
+    /// Lockfile and generated hunks, kept out of the prompt
+    generated:   Vec<ParsedHunk>,
     commits:     Vec<CommitInfo>,
-    client:      ClaudeClient,
+    client:      Box<dyn LlmClient>,
     mode:        ReviewMode,
+    options:     GenerationOptions,
+    /// The walkthrough system prompt, when the settings or persona change it
+    system:      Option<String>,
 }
 
 impl WalkthroughGenerator {
@@ -42,21 +81,73 @@ impl WalkthroughGenerator {
         diff_text: &str,
         filter: &FileFilter,
         mode: ReviewMode,
-        api_key: String,
+        config: ClientConfig,
         commits: Vec<CommitInfo>,
     ) -> Result<Self, GenerationError> {
         let mut parsed_diff = ParsedDiff::parse(diff_text)?;
         parsed_diff.apply_filter(filter)?;
+        let generated = parsed_diff.take_generated();
+        parsed_diff.annotate_symbols(read_worktree_file);
 
-        let client = ClaudeClient::new(api_key);
+        let base = walkthrough_system_prompt(mode);
+        let system = config.settings.prompts.walkthrough_prompt(base)?;
+        let system = (system != base).then_some(system);
+        let client = crate::api::connect(config);
         Ok(Self {
             parsed_diff,
+            generated,
             commits,
             client,
             mode,
+            options: GenerationOptions::default(),
+            system,
         })
     }
 
+    /// Sets how steps are organized and whether the diff is an interdiff.
+    pub fn with_options(mut self, options: GenerationOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Identifies what this generator would ask for: the model, review
+    /// mode, and full prompt (see `cache`).
+    pub fn cache_key(&self) -> String {
+        let mode = format!("{:?}", self.mode);
+        let generated: String = self.generated.iter().map(|h| h.content.as_str()).collect();
+        let prompt = self.build_prompt();
+        match &self.system {
+            Some(system) => crate::cache::hash_key(&[
+                self.client.model(),
+                &mode,
+                &prompt,
+                &generated,
+                system,
+            ]),
+            None => crate::cache::hash_key(&[self.client.model(), &mode, &prompt, &generated]),
+        }
+    }
+
+    /// Generates the whole walkthrough, for callers that don't show steps
+    /// as they arrive.
+    pub async fn generate(self) -> Result<(Walkthrough, TokenUsage), GenerationError> {
+        let (event_tx, mut event_rx) = mpsc::channel::<StreamEvent>(32);
+        let collector = tokio::spawn(async move {
+            let mut steps = Vec::new();
+            while let Some(event) = event_rx.recv().await {
+                if let StreamEvent::StepReady(step) = event {
+                    steps.push(step);
+                }
+            }
+            steps
+        });
+        let usage = self.generate_streaming(event_tx).await?;
+        let steps = collector
+            .await
+            .map_err(|e| GenerationError::Api(ApiError::Parse(e.to_string())))?;
+        Ok((Walkthrough { steps, messages: vec![] }, usage))
+    }
+
     pub async fn generate_streaming(
         self,
         event_tx: mpsc::Sender<StreamEvent>,
@@ -65,26 +156,42 @@ impl WalkthroughGenerator {
 
         let WalkthroughGenerator {
             parsed_diff,
+            generated,
             client,
             mode,
+            options,
             ..
         } = self;
 
         let (client_tx, mut client_rx) = mpsc::channel::<ClientStreamEvent>(32);
+        let _ = event_tx
+            .send(StreamEvent::Started(client.model().to_string()))
+            .await;
 
         let api_task = tokio::spawn(async move {
             client
                 .generate_walkthrough_streaming(&prompt, mode, client_tx)
                 .await
         });
+        let _abort_api_task = AbortOnDrop(api_task.abort_handle());
 
         let max_hunk_index = parsed_diff.hunks.len();
         let mut step_index = 0;
-
-        while let Some(ClientStreamEvent::StepComplete(response)) = client_rx.recv().await {
+        let mut covered = HashSet::new();
+
+        while let Some(event) = client_rx.recv().await {
+            let response = match event {
+                ClientStreamEvent::StepComplete(response) => response,
+                ClientStreamEvent::Progress(tokens) => {
+                    let _ = event_tx.send(StreamEvent::Progress(tokens)).await;
+                    continue;
+                }
+            };
+            let hunk_indices = response.hunk_indices.clone();
             match correlate_step(&parsed_diff, response, step_index, max_hunk_index) {
                 Ok(step) => {
                     step_index += 1;
+                    covered.extend(hunk_indices);
                     let _ = event_tx.send(StreamEvent::StepReady(step)).await;
                 }
                 Err(_) => {
@@ -97,21 +204,71 @@ impl WalkthroughGenerator {
             .await
             .map_err(|e| GenerationError::Api(ApiError::Parse(e.to_string())))??;
 
+        // Hunks the model left out still need reviewing
+        let fallback = fallback_steps(&parsed_diff, &covered, options.organization, step_index);
+        step_index += fallback.len();
+        for step in fallback {
+            let _ = event_tx.send(StreamEvent::StepReady(step)).await;
+        }
+        if let Some(step) = generated_step(generated, step_index) {
+            let _ = event_tx.send(StreamEvent::StepReady(step)).await;
+        }
+
         Ok(usage)
     }
 
     fn build_prompt(&self) -> String {
         let mut prompt = format!(
             "Please analyze this diff and create a code review walkthrough.\n\n\
-             The diff contains {} hunks, numbered below:\n\n{}",
+             The diff contains {} hunks, numbered below. Where known, a hunk's header \
+             names the function or type it changes; use this to group related changes.\n\n{}",
             self.parsed_diff.hunks.len(),
             self.parsed_diff.format_for_prompt()
         );
 
+        if !self.generated.is_empty() {
+            prompt.push_str(&format_generated_files(&self.generated));
+        }
+
+        if let Some(pull_request) = &self.options.pull_request {
+            prompt.push_str(&format_pull_request(pull_request));
+        }
+
         if !self.commits.is_empty() {
             prompt.push_str(&format_commits(&self.commits));
         }
 
+        if self.options.interdiff {
+            prompt.push_str(
+                "\n\n## Re-review\n\n\
+                 The reviewer has already reviewed an earlier revision of this change. \
+                 This diff is an interdiff: it shows only what changed between that \
+                 revision and the current one. Focus the walkthrough on how the change \
+                 evolved, such as review feedback that was addressed and new code added \
+                 since. Removed lines in a hunk are code from the earlier revision that \
+                 was dropped or rewritten, not necessarily code from the base branch.",
+            );
+        }
+
+        if self.options.organization == StepOrganization::Directory {
+            prompt.push_str(
+                "\n\n## Step Organization\n\n\
+                 Organize the steps by area of the codebase rather than as a narrative: \
+                 each step should cover the changes in one top-level directory or module. \
+                 Order the areas so that ones other areas depend on come first, and mention \
+                 cross-area interactions in the summaries.",
+            );
+        }
+
+        if let Some(instructions) = &self.options.instructions {
+            prompt.push_str(&format!(
+                "\n\n## Reviewer Instructions\n\n\
+                 The reviewer asked for this walkthrough to follow these instructions, \
+                 which take precedence over the usual guidance where they conflict:\n\n{}",
+                instructions
+            ));
+        }
+
         prompt
     }
 
@@ -136,6 +293,9 @@ fn correlate_step(
                 start_line: parsed_hunk.start_line,
                 end_line: parsed_hunk.end_line,
                 content: parsed_hunk.content.clone(),
+                symbol: parsed_hunk.symbol.clone(),
+                reviewed: false,
+                folded: false,
             });
         }
     }
@@ -150,9 +310,146 @@ fn correlate_step(
         hunks,
         messages: vec![Message::assistant(&response.summary)],
         depth: 0,
+        notes: vec![],
+        flags: vec![],
+    })
+}
+
+/// The area a file belongs to when grouping by directory: its directory,
+/// truncated to two levels (`src/ui/mod.rs` -> `src/ui`).
+pub fn area_of(path: &str) -> String {
+    let dirs: Vec<&str> = path.split('/').collect();
+    let dirs = &dirs[..dirs.len().saturating_sub(1)];
+    if dirs.is_empty() {
+        "(root)".to_string()
+    } else {
+        dirs[..dirs.len().min(2)].join("/")
+    }
+}
+
+/// Builds steps for hunks no generated step covered: one step per area when
+/// organizing by directory, otherwise a single catch-all step.
+fn fallback_steps(
+    parsed_diff: &ParsedDiff,
+    covered: &HashSet<usize>,
+    organization: StepOrganization,
+    first_index: usize,
+) -> Vec<Step> {
+    let mut groups: BTreeMap<String, Vec<Hunk>> = BTreeMap::new();
+    for parsed_hunk in parsed_diff.hunks.iter().filter(|h| !covered.contains(&h.index)) {
+        let key = match organization {
+            StepOrganization::Directory => area_of(&parsed_hunk.file_path),
+            StepOrganization::Narrative => String::new(),
+        };
+        groups.entry(key).or_default().push(Hunk {
+            file_path: parsed_hunk.file_path.clone(),
+            start_line: parsed_hunk.start_line,
+            end_line: parsed_hunk.end_line,
+            content: parsed_hunk.content.clone(),
+            symbol: parsed_hunk.symbol.clone(),
+            reviewed: false,
+            folded: false,
+        });
+    }
+
+    groups
+        .into_iter()
+        .enumerate()
+        .map(|(i, (area, hunks))| {
+            let (title, summary) = if area.is_empty() {
+                (
+                    "Other changes".to_string(),
+                    "Changes not covered by the steps above.".to_string(),
+                )
+            } else {
+                (
+                    format!("Changes in {}", area),
+                    format!("Changes in `{}` not covered by the steps above.", area),
+                )
+            };
+            Step {
+                id: format!("{}", first_index + i + 1),
+                title,
+                messages: vec![Message::assistant(&summary)],
+                summary,
+                priority: Priority::Normal,
+                hunks,
+                depth: 0,
+                notes: vec![],
+                flags: vec![],
+            }
+        })
+        .collect()
+}
+
+/// Tells the model which lockfiles and generated files changed, without
+/// their hunks, so steps can mention them.
+fn format_generated_files(generated: &[ParsedHunk]) -> String {
+    let mut files: Vec<&str> = generated.iter().map(|h| h.file_path.as_str()).collect();
+    files.dedup();
+    format!(
+        "\n\n## Generated Files\n\n\
+         These lockfiles and generated files also changed. Their hunks are left out \
+         and shown to the reviewer in a separate step:\n{}",
+        files.iter().map(|f| format!("- {}", f)).collect::<Vec<_>>().join("\n")
+    )
+}
+
+/// A minor step holding the lockfile and generated hunks, which the model
+/// never saw.
+fn generated_step(generated: Vec<ParsedHunk>, index: usize) -> Option<Step> {
+    if generated.is_empty() {
+        return None;
+    }
+    let mut files: Vec<String> = generated.iter().map(|h| format!("`{}`", h.file_path)).collect();
+    files.dedup();
+    let summary = format!(
+        "Lockfiles and generated code, grouped here without being sent to the model: {}.",
+        files.join(", ")
+    );
+    let hunks = generated
+        .into_iter()
+        .map(|parsed_hunk| Hunk {
+            file_path: parsed_hunk.file_path,
+            start_line: parsed_hunk.start_line,
+            end_line: parsed_hunk.end_line,
+            content: parsed_hunk.content,
+            symbol: parsed_hunk.symbol,
+            reviewed: false,
+            folded: false,
+        })
+        .collect();
+    Some(Step {
+        id: format!("{}", index + 1),
+        title: "Dependency and generated changes".to_string(),
+        messages: vec![Message::assistant(&summary)],
+        summary,
+        priority: Priority::Minor,
+        hunks,
+        depth: 0,
+        notes: vec![],
+        flags: vec![],
     })
 }
 
+fn format_pull_request(pull_request: &PullRequestInfo) -> String {
+    let mut out = format!(
+        "\n\n## Pull Request\n\n\
+         The author opened this change as a pull request titled \"{}\".",
+        pull_request.title.trim()
+    );
+    let body = pull_request.body.trim();
+    if !body.is_empty() {
+        out.push_str(
+            " Its description is below. Use it to understand the intent, but describe \
+             what the code actually does, and point out where it differs.\n\n",
+        );
+        out.push_str(body);
+    }
+    out.push('\n');
+    out
+}
+
 fn format_commits(commits: &[CommitInfo]) -> String {
     use std::fmt::Write;
 
@@ -212,32 +509,6 @@ pub fn format_step_for_rechunk(step: &Step) -> String {
     output
 }
 
-/// Parse a @@ header to extract old and new starting line numbers.
-fn parse_hunk_header(header: &str) -> Option<(usize, usize)> {
-    // Format: @@ -A,B +C,D @@ optional text
-    let header = header.strip_prefix("@@ ")?;
-    let parts: Vec<&str> = header.splitn(3, ' ').collect();
-    if parts.len() < 2 {
-        return None;
-    }
-
-    let old_start = parts[0]
-        .strip_prefix('-')?
-        .split(',')
-        .next()?
-        .parse::<usize>()
-        .ok()?;
-
-    let new_start = parts[1]
-        .strip_prefix('+')?
-        .split(',')
-        .next()?
-        .parse::<usize>()
-        .ok()?;
-
-    Some((old_start, new_start))
-}
-
 /// Slice a hunk by extracting content lines in the range `start_line..=end_line` (1-based).
 /// Reconstructs a valid Hunk with correct @@ header line numbers.
 pub fn slice_hunk(
@@ -296,6 +567,9 @@ pub fn slice_hunk(
         start_line: hunk.start_line + new_offset,
         end_line: hunk.start_line + new_offset + new_count.saturating_sub(1),
         content,
+        symbol: hunk.symbol.clone(),
+        reviewed: hunk.reviewed,
+        folded: false,
     })
 }
 
@@ -333,6 +607,8 @@ pub fn create_sub_steps(
             hunks,
             messages: vec![Message::assistant(&step_response.summary)],
             depth: 0,
+            notes: vec![],
+            flags: vec![],
         });
     }
 
@@ -344,23 +620,82 @@ mod tests {
     use super::*;
 
     fn make_hunk(content: &str) -> Hunk {
-        Hunk {
-            file_path: "src/foo.rs".to_string(),
-            start_line: 1,
-            end_line: 10,
-            content: content.to_string(),
-        }
+        Hunk::test("src/foo.rs", 1, 10, content)
     }
 
     #[test]
-    fn test_parse_hunk_header() {
-        assert_eq!(parse_hunk_header("@@ -0,0 +1,25 @@"), Some((0, 1)));
-        assert_eq!(parse_hunk_header("@@ -15,6 +15,21 @@"), Some((15, 15)));
-        assert_eq!(
-            parse_hunk_header("@@ -10,3 +10,18 @@ fn something"),
-            Some((10, 10))
-        );
-        assert_eq!(parse_hunk_header("not a header"), None);
+    fn test_area_of() {
+        assert_eq!(area_of("src/ui/mod.rs"), "src/ui");
+        assert_eq!(area_of("src/ui/widgets/list.rs"), "src/ui");
+        assert_eq!(area_of("src/main.rs"), "src");
+        assert_eq!(area_of("README.md"), "(root)");
+    }
+
+    #[test]
+    fn test_fallback_steps_group_uncovered_hunks_by_directory() {
+        let diff = ParsedDiff::parse(
+            "diff --git a/src/ui/mod.rs b/src/ui/mod.rs\n\
+             --- a/src/ui/mod.rs\n\
+             +++ b/src/ui/mod.rs\n\
+             @@ -1,1 +1,1 @@\n\
+             -a\n\
+             +b\n\
+             diff --git a/src/ui/setup.rs b/src/ui/setup.rs\n\
+             --- a/src/ui/setup.rs\n\
+             +++ b/src/ui/setup.rs\n\
+             @@ -1,1 +1,1 @@\n\
+             -a\n\
+             +b\n\
+             diff --git a/README.md b/README.md\n\
+             --- a/README.md\n\
+             +++ b/README.md\n\
+             @@ -1,1 +1,1 @@\n\
+             -a\n\
+             +b\n",
+        )
+        .unwrap();
+        let covered = HashSet::from([1]);
+
+        let steps = fallback_steps(&diff, &covered, StepOrganization::Directory, 4);
+        let titles: Vec<_> = steps.iter().map(|s| s.title.as_str()).collect();
+        assert_eq!(titles, ["Changes in (root)", "Changes in src/ui"]);
+        assert_eq!(steps[0].id, "5");
+        assert_eq!(steps[1].hunks[0].file_path, "src/ui/setup.rs");
+
+        let steps = fallback_steps(&diff, &covered, StepOrganization::Narrative, 4);
+        assert_eq!(steps.len(), 1);
+        assert_eq!(steps[0].hunks.len(), 2);
+
+        let all = HashSet::from([1, 2, 3]);
+        assert!(fallback_steps(&diff, &all, StepOrganization::Directory, 4).is_empty());
+    }
+
+    #[test]
+    fn test_generated_step_is_minor() {
+        let mut diff = ParsedDiff::parse(
+            "diff --git a/Cargo.lock b/Cargo.lock\n\
+             --- a/Cargo.lock\n\
+             +++ b/Cargo.lock\n\
+             @@ -1,1 +1,1 @@\n\
+             -a\n\
+             +b\n\
+             diff --git a/src/main.rs b/src/main.rs\n\
+             --- a/src/main.rs\n\
+             +++ b/src/main.rs\n\
+             @@ -1,1 +1,1 @@\n\
+             -a\n\
+             +b\n",
+        )
+        .unwrap();
+        let generated = diff.take_generated();
+        assert!(format_generated_files(&generated).contains("- Cargo.lock"));
+        assert!(!diff.format_for_prompt().contains("Cargo.lock"));
+
+        let step = generated_step(generated, 2).unwrap();
+        assert_eq!(step.id, "3");
+        assert_eq!(step.priority, Priority::Minor);
+        assert_eq!(step.hunks[0].file_path, "Cargo.lock");
+        assert!(generated_step(vec![], 2).is_none());
     }
 
     #[test]
@@ -412,13 +747,10 @@ mod tests {
     #[test]
     fn test_create_sub_steps() {
         let step = Step {
-            id: "3".to_string(),
             title: "Big step".to_string(),
             summary: "A big step".to_string(),
-            priority: Priority::Normal,
             hunks: vec![make_hunk("@@ -0,0 +1,4 @@\n+aaa\n+bbb\n+ccc\n+ddd")],
-            messages: vec![],
-            depth: 0,
+            ..Step::test("3", "")
         };
 
         let response = RechunkResponse {
@@ -456,16 +788,30 @@ mod tests {
         assert!(sub_steps[1].hunks[0].content.contains("+ccc"));
     }
 
+    #[test]
+    fn test_format_pull_request() {
+        let pr = PullRequestInfo {
+            title: "Cache parsed diffs ".to_string(),
+            body: "Speeds up re-review.\n".to_string(),
+        };
+        let section = format_pull_request(&pr);
+        assert!(section.contains("titled \"Cache parsed diffs\"."));
+        assert!(section.ends_with("Speeds up re-review.\n"));
+
+        let no_description = PullRequestInfo {
+            body: "  ".to_string(),
+            ..pr
+        };
+        assert!(!format_pull_request(&no_description).contains("description"));
+    }
+
     #[test]
     fn test_format_step_for_rechunk() {
         let step = Step {
-            id: "1".to_string(),
             title: "Test".to_string(),
             summary: "Test".to_string(),
-            priority: Priority::Normal,
             hunks: vec![make_hunk("@@ -0,0 +1,2 @@\n+hello\n+world")],
-            messages: vec![],
-            depth: 0,
+            ..Step::test("1", "")
         };
 
         let output = format_step_for_rechunk(&step);
@@ -1,3 +1,7 @@
+mod by_commit;
 mod generator;
 
-pub use generator::{StreamEvent, WalkthroughGenerator, create_sub_steps, format_step_for_rechunk};
+pub use by_commit::{CommitDiff, generate_by_commit};
+pub use generator::{
+    GenerationError, GenerationOptions, StreamEvent, WalkthroughGenerator, area_of, create_sub_steps, format_step_for_rechunk,
+};
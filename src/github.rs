@@ -1,3 +1,14 @@
+use reqwest::Method;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tokio::sync::OnceCell;
+
+use crate::diff::parse_hunk_header;
+use crate::model::{Hunk, PullRequestInfo, Step, Walkthrough};
+
+/// Media type of the GitHub API's JSON responses.
+const JSON: &str = "application/vnd.github.v3+json";
+
 #[derive(Debug, PartialEq)]
 pub enum GitHubUrl<'a> {
     PullRequest {
@@ -74,6 +85,113 @@ pub fn parse_github_url(url: &str) -> Option<GitHubUrl<'_>> {
     }
 }
 
+/// Owned copy of the forge location a session was loaded from, used to build
+/// browser links back to the PR, commit, or compare view.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ForgeLink {
+    owner: String,
+    repo: String,
+    target: ForgeTarget,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+enum ForgeTarget {
+    PullRequest(String),
+    Commit(String),
+    Compare(String),
+}
+
+impl ForgeLink {
+    pub fn from_url(url: &str) -> Option<Self> {
+        let target = match parse_github_url(url)? {
+            GitHubUrl::PullRequest { owner, repo, number } => {
+                (owner, repo, ForgeTarget::PullRequest(number.to_string()))
+            }
+            GitHubUrl::Commit { owner, repo, sha } => {
+                (owner, repo, ForgeTarget::Commit(sha.to_string()))
+            }
+            GitHubUrl::Compare {
+                owner,
+                repo,
+                basehead,
+            } => (owner, repo, ForgeTarget::Compare(basehead.to_string())),
+        };
+        Some(Self {
+            owner: target.0.to_string(),
+            repo: target.1.to_string(),
+            target: target.2,
+        })
+    }
+
+    /// `owner/repo`.
+    pub fn repository(&self) -> String {
+        format!("{}/{}", self.owner, self.repo)
+    }
+
+    /// `owner/repo#number` when the link is to a pull request.
+    pub fn pull_request(&self) -> Option<String> {
+        match &self.target {
+            ForgeTarget::PullRequest(number) => {
+                Some(format!("{}/{}#{}", self.owner, self.repo, number))
+            }
+            _ => None,
+        }
+    }
+
+    /// The PR, commit, or compare page on the forge.
+    pub fn page_url(&self) -> String {
+        let Self { owner, repo, .. } = self;
+        match &self.target {
+            ForgeTarget::PullRequest(number) => {
+                format!("https://github.com/{owner}/{repo}/pull/{number}")
+            }
+            ForgeTarget::Commit(sha) => format!("https://github.com/{owner}/{repo}/commit/{sha}"),
+            ForgeTarget::Compare(basehead) => {
+                format!("https://github.com/{owner}/{repo}/compare/{basehead}")
+            }
+        }
+    }
+
+    /// A link to a file (and optionally a new-file line) within the diff view.
+    /// GitHub anchors diff entries by the SHA-256 of the file path.
+    pub fn file_url(&self, path: &str, line: Option<usize>) -> String {
+        let files_page = match self.target {
+            ForgeTarget::PullRequest(_) => format!("{}/files", self.page_url()),
+            _ => self.page_url(),
+        };
+        let anchor: String = Sha256::digest(path.as_bytes())
+            .iter()
+            .map(|b| format!("{b:02x}"))
+            .collect();
+        match line {
+            Some(line) => format!("{files_page}#diff-{anchor}R{line}"),
+            None => format!("{files_page}#diff-{anchor}"),
+        }
+    }
+}
+
+/// Opens a URL with the platform's default browser handler.
+pub fn open_in_browser(url: &str) -> Result<(), String> {
+    let mut command = if cfg!(target_os = "macos") {
+        std::process::Command::new("open")
+    } else if cfg!(target_os = "windows") {
+        let mut command = std::process::Command::new("cmd");
+        command.args(["/C", "start", ""]);
+        command
+    } else {
+        std::process::Command::new("xdg-open")
+    };
+
+    command
+        .arg(url)
+        .stdin(std::process::Stdio::null())
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .spawn()
+        .map(|_| ())
+        .map_err(|e| format!("failed to open browser: {e}"))
+}
+
 pub async fn fetch_diff(url: &str) -> Result<String, String> {
     let parsed =
         parse_github_url(url).ok_or_else(|| format!("Not a recognized GitHub URL: {url}"))?;
@@ -103,21 +221,8 @@ pub async fn fetch_diff(url: &str) -> Result<String, String> {
 
     eprintln!("Fetching diff for {label}...");
 
-    let client = reqwest::Client::builder()
-        .timeout(std::time::Duration::from_secs(30))
-        .build()
-        .map_err(|e| format!("Failed to create HTTP client: {e}"))?;
-
-    let mut request = client
-        .get(&api_url)
-        .header("Accept", "application/vnd.github.v3.diff")
-        .header("User-Agent", "docent");
-
-    if let Ok(token) = std::env::var("GITHUB_TOKEN") {
-        request = request.header("Authorization", format!("Bearer {token}"));
-    }
-
-    let response = request
+    let response = github_request(Method::GET, &api_url, "application/vnd.github.v3.diff")
+        .await?
         .send()
         .await
         .map_err(|e| format!("Failed to fetch diff: {e}"))?;
@@ -145,21 +250,8 @@ pub async fn fetch_diff(url: &str) -> Result<String, String> {
 pub async fn fetch_pr_commits(owner: &str, repo: &str, number: &str) -> Result<Vec<crate::model::CommitInfo>, String> {
     let api_url = format!("https://api.github.com/repos/{owner}/{repo}/pulls/{number}/commits?per_page=100");
 
-    let client = reqwest::Client::builder()
-        .timeout(std::time::Duration::from_secs(30))
-        .build()
-        .map_err(|e| format!("Failed to create HTTP client: {e}"))?;
-
-    let mut request = client
-        .get(&api_url)
-        .header("Accept", "application/vnd.github.v3+json")
-        .header("User-Agent", "docent");
-
-    if let Ok(token) = std::env::var("GITHUB_TOKEN") {
-        request = request.header("Authorization", format!("Bearer {token}"));
-    }
-
-    let response = request
+    let response = github_request(Method::GET, &api_url, JSON)
+        .await?
         .send()
         .await
         .map_err(|e| format!("Failed to fetch PR commits: {e}"))?;
@@ -192,6 +284,246 @@ pub async fn fetch_pr_commits(owner: &str, repo: &str, number: &str) -> Result<V
     Ok(commits)
 }
 
+/// Title and description of a pull request, for the generation prompt.
+pub async fn fetch_pr_info(
+    owner: &str,
+    repo: &str,
+    number: &str,
+) -> Result<PullRequestInfo, String> {
+    let api_url = format!("https://api.github.com/repos/{owner}/{repo}/pulls/{number}");
+
+    let response = github_request(Method::GET, &api_url, JSON)
+        .await?
+        .send()
+        .await
+        .map_err(|e| format!("Failed to fetch PR: {e}"))?;
+
+    if !response.status().is_success() {
+        return Err(format!(
+            "GitHub returned HTTP {} fetching PR",
+            response.status()
+        ));
+    }
+
+    let pr: serde_json::Value = response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse PR response: {e}"))?;
+
+    Ok(PullRequestInfo {
+        title: pr["title"].as_str().unwrap_or_default().to_string(),
+        body: pr["body"].as_str().unwrap_or_default().to_string(),
+    })
+}
+
+pub fn pull_request_url(owner: &str, repo: &str, number: u64) -> String {
+    format!("https://github.com/{owner}/{repo}/pull/{number}")
+}
+
+/// Owner and repository of a GitHub remote URL, in HTTPS or SSH form.
+fn parse_remote_url(url: &str) -> Option<(&str, &str)> {
+    let path = strip_github_prefix(url)
+        .or_else(|| url.strip_prefix("git@github.com:"))
+        .or_else(|| url.strip_prefix("ssh://git@github.com/"))?;
+    let path = path.trim_end_matches('/');
+    let path = path.strip_suffix(".git").unwrap_or(path);
+    let (owner, repo) = path.split_once('/')?;
+    if owner.is_empty() || repo.is_empty() || repo.contains('/') {
+        return None;
+    }
+    Some((owner, repo))
+}
+
+/// Owner and repository of the current repository's `origin` remote.
+pub async fn origin_repo() -> Result<(String, String), String> {
+    let output = tokio::process::Command::new("git")
+        .args(["remote", "get-url", "origin"])
+        .output()
+        .await
+        .map_err(|e| format!("Failed to run git: {e}"))?;
+    if !output.status.success() {
+        return Err("No `origin` remote to look up the pull request on".to_string());
+    }
+    let url = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    parse_remote_url(&url)
+        .map(|(owner, repo)| (owner.to_string(), repo.to_string()))
+        .ok_or_else(|| format!("`origin` is not a GitHub repository: {url}"))
+}
+
+/// A comment on one line of a pull request's diff.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ReviewComment {
+    pub path: String,
+    pub line: usize,
+    /// `RIGHT` for a line in the new file, `LEFT` for a removed line
+    pub side: &'static str,
+    pub body: String,
+}
+
+/// A pull request review built from the notes left on steps.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PullRequestReview {
+    pub body: String,
+    pub event: &'static str,
+    pub comments: Vec<ReviewComment>,
+}
+
+impl PullRequestReview {
+    /// Anchors each step's notes to the last changed line of its first hunk.
+    /// Notes on steps without hunks (split parents) go in the review body.
+    pub fn from_notes(walkthrough: &Walkthrough, summary: &str) -> Self {
+        let mut body = summary.to_string();
+        let mut comments = vec![];
+        for step in &walkthrough.steps {
+            let Some(note) = posted_note(step) else {
+                continue;
+            };
+            let text = format!("**{}**\n\n{}", step.title, note);
+            let anchor = step
+                .hunks
+                .iter()
+                .find_map(|hunk| comment_line(hunk).map(|(line, side)| (hunk, line, side)));
+            match anchor {
+                Some((hunk, line, side)) => comments.push(ReviewComment {
+                    path: hunk.file_path.clone(),
+                    line,
+                    side,
+                    body: text,
+                }),
+                None => {
+                    body.push_str("\n\n");
+                    body.push_str(&text);
+                }
+            }
+        }
+        Self {
+            body,
+            event: "COMMENT",
+            comments,
+        }
+    }
+
+    /// Whether any step has a note to post.
+    pub fn has_notes(walkthrough: &Walkthrough) -> bool {
+        walkthrough.steps.iter().any(|s| posted_note(s).is_some())
+    }
+}
+
+/// A step's non-empty notes as one comment, separated by blank lines.
+fn posted_note(step: &Step) -> Option<String> {
+    let notes: Vec<&str> = step
+        .notes
+        .iter()
+        .map(|n| n.text.trim())
+        .filter(|n| !n.is_empty())
+        .collect();
+    (!notes.is_empty()).then(|| notes.join("\n\n"))
+}
+
+/// The line a comment on a hunk is anchored to: its last added line in the
+/// new file, or its last removed line in the old file if nothing was added.
+fn comment_line(hunk: &Hunk) -> Option<(usize, &'static str)> {
+    let mut lines = hunk.content.lines();
+    let (mut old_line, mut new_line) = parse_hunk_header(lines.next()?)?;
+    let mut added = None;
+    let mut removed = None;
+    for line in lines {
+        match line.chars().next() {
+            Some('+') => {
+                added = Some(new_line);
+                new_line += 1;
+            }
+            Some('-') => {
+                removed = Some(old_line);
+                old_line += 1;
+            }
+            Some('\\') => {}
+            _ => {
+                old_line += 1;
+                new_line += 1;
+            }
+        }
+    }
+    added
+        .map(|line| (line, "RIGHT"))
+        .or(removed.map(|line| (line, "LEFT")))
+}
+
+/// A token for the GitHub API: `GITHUB_TOKEN`, or the GitHub CLI's login.
+/// Looked up once per run.
+async fn github_token() -> Option<String> {
+    static TOKEN: OnceCell<Option<String>> = OnceCell::const_new();
+    TOKEN
+        .get_or_init(|| async {
+            if let Ok(token) = std::env::var("GITHUB_TOKEN") {
+                return Some(token);
+            }
+            let output = tokio::process::Command::new("gh")
+                .args(["auth", "token"])
+                .output()
+                .await
+                .ok()?;
+            let token = String::from_utf8_lossy(&output.stdout).trim().to_string();
+            (output.status.success() && !token.is_empty()).then_some(token)
+        })
+        .await
+        .clone()
+}
+
+/// A GitHub API request with docent's headers, authorized with
+/// `github_token` when there is one.
+async fn github_request(
+    method: Method,
+    url: &str,
+    accept: &str,
+) -> Result<reqwest::RequestBuilder, String> {
+    let client = reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(30))
+        .build()
+        .map_err(|e| format!("Failed to create HTTP client: {e}"))?;
+    let mut request = client
+        .request(method, url)
+        .header("Accept", accept)
+        .header("User-Agent", "docent");
+    if let Some(token) = github_token().await {
+        request = request.header("Authorization", format!("Bearer {token}"));
+    }
+    Ok(request)
+}
+
+/// Submits the review to the pull request the session was loaded from.
+/// Returns how many comments were posted.
+pub async fn post_review(link: &ForgeLink, review: &PullRequestReview) -> Result<usize, String> {
+    let ForgeTarget::PullRequest(number) = &link.target else {
+        return Err("Not a pull request".to_string());
+    };
+    if github_token().await.is_none() {
+        return Err("Set GITHUB_TOKEN or log in with `gh auth login` to post reviews".to_string());
+    }
+    let api_url = format!(
+        "https://api.github.com/repos/{}/{}/pulls/{number}/reviews",
+        link.owner, link.repo
+    );
+
+    let response = github_request(Method::POST, &api_url, JSON)
+        .await?
+        .json(review)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to post review: {e}"))?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let body: serde_json::Value = response.json().await.unwrap_or_default();
+        let message = body["message"].as_str().unwrap_or("Unknown");
+        return Err(format!(
+            "GitHub returned HTTP {status} posting review: {message}"
+        ));
+    }
+
+    Ok(review.comments.len())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -365,6 +697,101 @@ mod tests {
         );
     }
 
+    // -- Forge links --
+
+    #[test]
+    fn forge_link_pr_page_and_file() {
+        let link = ForgeLink::from_url("https://github.com/owner/repo/pull/123/files").unwrap();
+        assert_eq!(link.page_url(), "https://github.com/owner/repo/pull/123");
+        assert_eq!(
+            link.file_url("README.md", Some(7)),
+            "https://github.com/owner/repo/pull/123/files#diff-b335630551682c19a781afebcf4d07bf978fb1f8ac04c6bf87428ed5106870f5R7"
+        );
+    }
+
+    #[test]
+    fn forge_link_commit_file_without_line() {
+        let link = ForgeLink::from_url("https://github.com/owner/repo/commit/abc1234").unwrap();
+        assert_eq!(
+            link.file_url("README.md", None),
+            "https://github.com/owner/repo/commit/abc1234#diff-b335630551682c19a781afebcf4d07bf978fb1f8ac04c6bf87428ed5106870f5"
+        );
+    }
+
+    // -- Review comments --
+
+    fn hunk(content: &str) -> Hunk {
+        Hunk::test("src/lib.rs", 10, 13, content)
+    }
+
+    #[test]
+    fn comment_on_last_added_line() {
+        let h = hunk("@@ -10,3 +10,4 @@\n ctx\n-old\n+new\n+more\n ctx");
+        assert_eq!(comment_line(&h), Some((12, "RIGHT")));
+    }
+
+    #[test]
+    fn comment_on_removed_line_when_nothing_added() {
+        let h = hunk("@@ -10,3 +10,1 @@\n ctx\n-gone\n-also gone");
+        assert_eq!(comment_line(&h), Some((12, "LEFT")));
+    }
+
+    #[test]
+    fn review_from_notes() {
+        use crate::model::Note;
+        let step = |title: &str, hunks: Vec<Hunk>, notes: &[&str]| Step {
+            title: title.to_string(),
+            hunks,
+            notes: notes
+                .iter()
+                .map(|text| Note {
+                    text: text.to_string(),
+                })
+                .collect(),
+            ..Step::test(title, "")
+        };
+        let walkthrough = Walkthrough {
+            steps: vec![
+                step(
+                    "Parser",
+                    vec![hunk("@@ -1,1 +1,1 @@\n-a\n+b")],
+                    &["Handle CRLF?", "Also tabs"],
+                ),
+                step("Split parent", vec![], &["Needs a test"]),
+                step("Quiet", vec![hunk("@@ -1,1 +1,1 @@\n-a\n+b")], &[" "]),
+            ],
+            messages: vec![],
+        };
+        let review = PullRequestReview::from_notes(&walkthrough, "Reviewed");
+        assert_eq!(
+            review.comments,
+            [ReviewComment {
+                path: "src/lib.rs".to_string(),
+                line: 1,
+                side: "RIGHT",
+                body: "**Parser**\n\nHandle CRLF?\n\nAlso tabs".to_string(),
+            }]
+        );
+        assert_eq!(review.body, "Reviewed\n\n**Split parent**\n\nNeeds a test");
+        assert!(PullRequestReview::has_notes(&walkthrough));
+    }
+
+    // -- Remotes --
+
+    #[test]
+    fn remote_urls() {
+        for url in [
+            "https://github.com/owner/repo.git",
+            "https://github.com/owner/repo",
+            "git@github.com:owner/repo.git",
+            "ssh://git@github.com/owner/repo.git",
+        ] {
+            assert_eq!(parse_remote_url(url), Some(("owner", "repo")), "{url}");
+        }
+        assert_eq!(parse_remote_url("git@gitlab.com:owner/repo.git"), None);
+        assert_eq!(parse_remote_url("https://github.com/owner"), None);
+    }
+
     // -- General rejections --
 
     #[test]
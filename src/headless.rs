@@ -6,28 +6,35 @@ use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
 use tokio::net::{UnixListener, UnixStream};
 use tokio::sync::mpsc;
 
-use crate::api::{ClaudeClient, TokenUsage};
+use crate::api::{ClientConfig, RetryNotice, TokenUsage};
+use crate::cache;
 use crate::diff::FileFilter;
-use crate::generation::{StreamEvent, WalkthroughGenerator, create_sub_steps, format_step_for_rechunk};
-use crate::model::{CommitInfo, Message, ReviewMode, Step, Walkthrough};
+use crate::export::{self, ReportFormat};
+use crate::github::ForgeLink;
+use crate::generation::{GenerationOptions, StreamEvent, WalkthroughGenerator, create_sub_steps, format_step_for_rechunk};
+use crate::model::{CommitInfo, Message, ReviewMode, Step, StepOrganization, Walkthrough};
 use crate::protocol::{
-    NavigateAction, NavigateParams, Notification, Request, Response, SendMessageParams,
-    StateSnapshot,
+    GenerateParams, NavigateAction, NavigateParams, Notification, RegenerateParams, Request,
+    Response, SendMessageParams, StateSnapshot,
 };
-use crate::session::{Session, SessionState};
+use crate::session::{ChatThread, Session, SessionState};
 use crate::settings::Settings;
 
-use super::DiffInput;
+use super::{DiffInput, RunOverrides};
 
 enum EngineEvent {
     GenerationComplete(TokenUsage),
     GenerationError(String),
+    GenerationStarted(String),
+    GenerationProgress(u32),
     StepReady(Step),
     ChatChunk(usize, String),
     ChatComplete(usize, TokenUsage),
     ChatError(usize, String),
+    ChatSummarized(usize, usize, String, TokenUsage),
     RechunkComplete(usize, Vec<Step>, TokenUsage),
     RechunkError(String),
+    RetryScheduled(RetryNotice),
 }
 
 enum ServerEvent {
@@ -41,6 +48,8 @@ pub async fn run(
     diff_input: Option<DiffInput>,
     filter: FileFilter,
     mode: ReviewMode,
+    organization: StepOrganization,
+    overrides: &RunOverrides,
 ) -> io::Result<()> {
     let diff_input = diff_input.ok_or_else(|| {
         io::Error::new(
@@ -50,20 +59,31 @@ pub async fn run(
     })?;
     let diff_text = diff_input.diff_text;
     let commits = diff_input.commits;
+    let pull_request = diff_input.pull_request;
+    let forge = diff_input.forge;
+    let interdiff = diff_input.interdiff;
 
-    let settings = Settings::load();
+    let settings = overrides.apply(&Settings::load());
     let (api_key, source) = settings.resolve_api_key();
     if api_key.is_none() {
         return Err(io::Error::new(
             io::ErrorKind::InvalidInput,
-            "no API key found (set ANTHROPIC_API_KEY or configure in ~/.docent/settings.json)",
+            format!(
+                "no API key found (set {} or configure in ~/.docent/settings.json)",
+                settings.provider.env_var()
+            ),
         ));
     }
 
-    let mut session = Session::new(Walkthrough { steps: vec![] }, mode);
-    session.diff_input = Some(diff_text.clone());
-    session.commits = commits.clone();
-    session.diff_filter = filter.clone();
+    let mut session = Session::new(Walkthrough::default(), mode);
+    session.diff_filter = filter;
+    session.step_organization = organization;
+    session.chat_context = settings.chat.context;
+    session.chat_context_lines = settings.chat.context_lines;
+    // Follow-up suggestions aren't part of the protocol
+    session.chat_follow_ups = false;
+    session.provider = settings.provider;
+    session.api_settings = settings.api.clone();
     session.api_key_input = api_key.unwrap_or_default();
     session.api_key_source = source;
 
@@ -83,6 +103,7 @@ pub async fn run(
     }
 
     let (tx, mut rx) = mpsc::channel::<ServerEvent>(64);
+    session.retry_notices = Some(spawn_retry_forwarder(tx.clone()));
 
     // Spawn connection acceptor
     let tx_accept = tx.clone();
@@ -93,21 +114,17 @@ pub async fn run(
     });
 
     // Start walkthrough generation immediately
-    session.generation_in_progress = true;
-    spawn_generation(tx.clone(), session.api_key_input.clone(), diff_text, filter, mode, commits);
+    session.request_generation(diff_text);
+    session.commits = commits;
+    session.pull_request = pull_request;
+    session.forge = forge;
+    session.interdiff = interdiff;
 
     let mut clients: Vec<(usize, mpsc::Sender<String>)> = Vec::new();
     let mut next_client_id: usize = 0;
 
     loop {
-        // Poll for pending requests from session
-        if let Some((step_index, walkthrough, messages)) = session.chat_request.take() {
-            spawn_chat(tx.clone(), session.api_key_input.clone(), step_index, walkthrough, messages, mode);
-        }
-
-        if let Some((step_index, step, diff_text)) = session.rechunk_request.take() {
-            spawn_rechunk_task(tx.clone(), session.api_key_input.clone(), step_index, step, diff_text, mode);
-        }
+        spawn_pending_requests(&mut session, &tx);
 
         let event = tokio::select! {
             event = rx.recv() => {
@@ -232,6 +249,280 @@ pub async fn run(
     Ok(())
 }
 
+/// Runs the engine over stdio for editor integrations that spawn docent as a
+/// child process: requests arrive one per line on stdin, and responses and
+/// notifications are written as JSON lines to stdout. A diff can be supplied
+/// up front or later via the `generate` request.
+pub async fn serve(
+    diff_input: Option<DiffInput>,
+    filter: FileFilter,
+    mode: ReviewMode,
+    organization: StepOrganization,
+    overrides: &RunOverrides,
+) -> io::Result<()> {
+    let settings = overrides.apply(&Settings::load());
+    let (api_key, source) = settings.resolve_api_key();
+    let Some(api_key) = api_key else {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!(
+                "no API key found (set {} or configure in ~/.docent/settings.json)",
+                settings.provider.env_var()
+            ),
+        ));
+    };
+
+    let mut session = Session::new(Walkthrough::default(), mode);
+    session.diff_filter = filter;
+    session.step_organization = organization;
+    session.chat_context = settings.chat.context;
+    session.chat_context_lines = settings.chat.context_lines;
+    // Follow-up suggestions aren't part of the protocol
+    session.chat_follow_ups = false;
+    session.provider = settings.provider;
+    session.api_settings = settings.api.clone();
+    session.api_key_input = api_key;
+    session.api_key_source = source;
+    if let Some(input) = diff_input {
+        session.request_generation(input.diff_text);
+        session.commits = input.commits;
+        session.pull_request = input.pull_request;
+        session.forge = input.forge;
+        session.interdiff = input.interdiff;
+    }
+
+    let (tx, mut rx) = mpsc::channel::<ServerEvent>(64);
+    session.retry_notices = Some(spawn_retry_forwarder(tx.clone()));
+
+    // Stdin reader: stdin is the single client, EOF means it went away
+    let tx_read = tx.clone();
+    tokio::spawn(async move {
+        let mut reader = BufReader::new(tokio::io::stdin());
+        let mut line = String::new();
+        loop {
+            line.clear();
+            match reader.read_line(&mut line).await {
+                Ok(0) | Err(_) => {
+                    let _ = tx_read.send(ServerEvent::ClientDisconnected(0)).await;
+                    break;
+                }
+                Ok(_) => {
+                    let trimmed = line.trim().to_string();
+                    if !trimmed.is_empty() {
+                        let _ = tx_read.send(ServerEvent::ClientMessage(0, trimmed)).await;
+                    }
+                }
+            }
+        }
+    });
+
+    let mut stdout = tokio::io::stdout();
+    let snapshot = Notification::new("state_snapshot", build_snapshot(&session));
+    write_message(&mut stdout, &snapshot).await?;
+
+    loop {
+        spawn_pending_requests(&mut session, &tx);
+
+        let event = tokio::select! {
+            event = rx.recv() => {
+                match event {
+                    Some(e) => e,
+                    None => break,
+                }
+            }
+            _ = tokio::signal::ctrl_c() => {
+                write_message(&mut stdout, &Notification::shutdown()).await?;
+                break;
+            }
+        };
+
+        match event {
+            ServerEvent::NewClient(_) => {}
+
+            ServerEvent::ClientDisconnected(_) => break,
+
+            ServerEvent::ClientMessage(_, msg) => match serde_json::from_str::<Request>(&msg) {
+                Ok(req) => {
+                    let (response, notifications) = handle_request(&mut session, &req);
+                    write_message(&mut stdout, &response).await?;
+                    for notif in notifications {
+                        write_message(&mut stdout, &notif).await?;
+                    }
+                    if req.method == "shutdown" {
+                        break;
+                    }
+                }
+                Err(e) => {
+                    let resp = Response::error(0, format!("invalid request: {}", e));
+                    write_message(&mut stdout, &resp).await?;
+                }
+            },
+
+            ServerEvent::Engine(engine_event) => {
+                for notif in handle_engine_event(&mut session, engine_event) {
+                    write_message(&mut stdout, &notif).await?;
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Generates a walkthrough and writes it as a report (`-` for stdout), printing
+/// step titles to stderr as they arrive.
+pub async fn export(
+    diff_input: Option<DiffInput>,
+    filter: FileFilter,
+    mode: ReviewMode,
+    organization: StepOrganization,
+    overrides: &RunOverrides,
+    output: &str,
+    format: ReportFormat,
+) -> io::Result<()> {
+    let diff_input = diff_input.ok_or_else(|| {
+        io::Error::new(io::ErrorKind::InvalidInput, "export requires a diff input")
+    })?;
+    let settings = overrides.apply(&Settings::load());
+    let (api_key, _) = settings.resolve_api_key();
+    let api_key = api_key.ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!(
+                "no API key found (set {} or configure in ~/.docent/settings.json)",
+                settings.provider.env_var()
+            ),
+        )
+    })?;
+    let mut api_settings = settings.api;
+    if !api_settings.prompts.is_empty() {
+        let repo = diff_input.forge.as_ref().map(ForgeLink::repository);
+        let repo = repo.as_deref().unwrap_or(crate::settings::project_name());
+        let title = diff_input.pull_request.as_ref().map_or("", |pr| pr.title.as_str());
+        api_settings.prompts.set_default("repo", repo);
+        api_settings.prompts.set_default("pr_title", title);
+    }
+
+    let options = GenerationOptions {
+        organization,
+        interdiff: diff_input.interdiff,
+        pull_request: diff_input.pull_request,
+        instructions: None,
+    };
+    // Waits for rate limits go to stderr alongside the step titles
+    let (retry_tx, mut retry_rx) = mpsc::unbounded_channel::<RetryNotice>();
+    tokio::spawn(async move {
+        while let Some(notice) = retry_rx.recv().await {
+            eprintln!("{}", notice.message());
+        }
+    });
+    let generator = WalkthroughGenerator::with_filter(
+        &diff_input.diff_text,
+        &filter,
+        mode,
+        ClientConfig {
+            provider: settings.provider,
+            api_key,
+            settings: api_settings,
+            retry_notices: Some(retry_tx),
+        },
+        diff_input.commits,
+    )
+    .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e.to_string()))?
+    .with_options(options);
+
+    let cache_key = generator.cache_key();
+    let steps = match cache::load(&cache_key, &settings.cache) {
+        Some((_, steps)) => {
+            eprintln!("Walkthrough loaded from cache");
+            steps
+        }
+        None => {
+            let (event_tx, mut event_rx) = mpsc::channel::<StreamEvent>(32);
+            let collector = tokio::spawn(async move {
+                let mut model = String::new();
+                let mut steps = Vec::new();
+                while let Some(event) = event_rx.recv().await {
+                    match event {
+                        StreamEvent::Started(m) => model = m,
+                        StreamEvent::StepReady(step) => {
+                            eprintln!("{}. {}", steps.len() + 1, step.title);
+                            steps.push(step);
+                        }
+                        StreamEvent::Progress(_) => {}
+                    }
+                }
+                (model, steps)
+            });
+            generator
+                .generate_streaming(event_tx)
+                .await
+                .map_err(|e| io::Error::other(e.to_string()))?;
+            let (model, steps) = collector.await.map_err(io::Error::other)?;
+            let _ = cache::save(&cache_key, &model, &steps, &settings.cache);
+            steps
+        }
+    };
+
+    let report = export::render_report(&Walkthrough { steps, messages: vec![] }, mode, format);
+    if output == "-" {
+        print!("{report}");
+    } else {
+        std::fs::write(output, report)?;
+        eprintln!("Export written to {output}");
+    }
+    Ok(())
+}
+
+async fn write_message(
+    out: &mut tokio::io::Stdout,
+    message: &impl serde::Serialize,
+) -> io::Result<()> {
+    let json = serde_json::to_string(message).unwrap_or_default();
+    out.write_all(format!("{}\n", json).as_bytes()).await?;
+    out.flush().await
+}
+
+/// Spawns background tasks for any generation, chat, or rechunk work the
+/// session has queued since the last iteration.
+fn spawn_pending_requests(session: &mut Session, tx: &mpsc::Sender<ServerEvent>) {
+    let mode = session.review_mode;
+
+    if session.generation_requested {
+        session.generation_requested = false;
+        if let Some(diff_text) = session.diff_input.clone() {
+            spawn_generation(
+                tx.clone(),
+                session.client_config(),
+                diff_text,
+                session.diff_filter.clone(),
+                mode,
+                session.generation_options(),
+                session.commits.clone(),
+            );
+        }
+    }
+
+    // Protocol clients only ask about steps
+    if let Some((ChatThread::Step(step_index), context, messages, summarize)) =
+        session.chat_request.take()
+    {
+        spawn_chat(
+            tx.clone(),
+            session.client_config(),
+            step_index,
+            context,
+            messages,
+            summarize,
+            mode,
+        );
+    }
+
+    if let Some((step_index, step, diff_text)) = session.rechunk_request.take() {
+        spawn_rechunk_task(tx.clone(), session.client_config(), step_index, step, diff_text, mode);
+    }
+}
+
 fn handle_request(session: &mut Session, req: &Request) -> (Response, Vec<Notification>) {
     let mut notifications = Vec::new();
 
@@ -306,6 +597,37 @@ fn handle_request(session: &mut Session, req: &Request) -> (Response, Vec<Notifi
             Err(e) => Response::error(req.id, format!("invalid params: {}", e)),
         },
 
+        "generate" => match serde_json::from_value::<GenerateParams>(req.params.clone()) {
+            Ok(params) => {
+                if session.generation_in_progress {
+                    Response::error(req.id, "generation already in progress")
+                } else {
+                    if let Some(mode) = params.mode {
+                        session.review_mode = mode;
+                    }
+                    if let Some(organization) = params.organize_by {
+                        session.step_organization = organization;
+                    }
+                    session.request_generation(params.diff);
+                    notifications.push(Notification::state_changed(&session.state));
+                    Response::ok(req.id, "ok")
+                }
+            }
+            Err(e) => Response::error(req.id, format!("invalid params: {}", e)),
+        },
+
+        "regenerate" => match serde_json::from_value::<RegenerateParams>(req.params.clone()) {
+            Ok(params) => {
+                if session.request_regeneration(params.instructions) {
+                    notifications.push(Notification::state_changed(&session.state));
+                    Response::ok(req.id, "ok")
+                } else {
+                    Response::error(req.id, "nothing to regenerate, or generation in progress")
+                }
+            }
+            Err(e) => Response::error(req.id, format!("invalid params: {}", e)),
+        },
+
         "rechunk" => {
             if session.rechunk_pending {
                 Response::error(req.id, "rechunk already pending")
@@ -356,6 +678,12 @@ fn handle_engine_event(session: &mut Session, event: EngineEvent) -> Vec<Notific
                 notifications.push(Notification::error(&message));
             }
         }
+        EngineEvent::GenerationStarted(model) => {
+            session.receive_generation_started(model);
+        }
+        EngineEvent::GenerationProgress(tokens) => {
+            session.receive_generation_progress(tokens);
+        }
         EngineEvent::StepReady(step) => {
             let index = session.walkthrough.steps.len();
             session.receive_step_ready(step);
@@ -373,16 +701,20 @@ fn handle_engine_event(session: &mut Session, event: EngineEvent) -> Vec<Notific
             }
         }
         EngineEvent::ChatChunk(step_index, chunk) => {
-            session.receive_chat_chunk(step_index, chunk.clone());
+            session.receive_chat_chunk(ChatThread::Step(step_index), chunk.clone());
             notifications.push(Notification::chat_chunk(step_index, &chunk));
         }
         EngineEvent::ChatComplete(step_index, usage) => {
-            session.add_usage(usage);
-            session.receive_chat_complete(step_index);
+            session.add_chat_usage(usage);
+            session.receive_chat_complete(ChatThread::Step(step_index));
             notifications.push(Notification::chat_complete(step_index));
         }
+        EngineEvent::ChatSummarized(step_index, covered, summary, usage) => {
+            session.add_chat_usage(usage);
+            session.receive_chat_summary(ChatThread::Step(step_index), covered, summary);
+        }
         EngineEvent::ChatError(step_index, error) => {
-            session.receive_chat_error(step_index, error.clone());
+            session.receive_chat_error(ChatThread::Step(step_index), error.clone());
             notifications.push(Notification::error(&error));
         }
         EngineEvent::RechunkComplete(step_index, sub_steps, usage) => {
@@ -398,6 +730,9 @@ fn handle_engine_event(session: &mut Session, event: EngineEvent) -> Vec<Notific
             session.receive_rechunk_error(error.clone());
             notifications.push(Notification::error(&error));
         }
+        EngineEvent::RetryScheduled(notice) => {
+            notifications.push(Notification::retry_scheduled(&notice));
+        }
     }
 
     notifications
@@ -412,7 +747,7 @@ fn build_snapshot(session: &Session) -> StateSnapshot {
         reviewed: session.reviewed_steps.clone(),
         walkthrough_complete: session.walkthrough_complete,
         generation_in_progress: session.generation_in_progress,
-        chat_pending: session.chat_pending,
+        chat_pending: session.chat_pending.and_then(ChatThread::step),
         rechunk_pending: session.rechunk_pending,
     }
 }
@@ -427,23 +762,28 @@ async fn broadcast(clients: &[(usize, mpsc::Sender<String>)], notification: &Not
 
 fn spawn_generation(
     tx: mpsc::Sender<ServerEvent>,
-    api_key: String,
+    config: ClientConfig,
     diff_text: String,
     filter: FileFilter,
     mode: ReviewMode,
+    options: GenerationOptions,
     commits: Vec<CommitInfo>,
 ) {
     tokio::spawn(async move {
-        match WalkthroughGenerator::with_filter(&diff_text, &filter, mode, api_key, commits) {
+        match WalkthroughGenerator::with_filter(&diff_text, &filter, mode, config, commits) {
             Ok(generator) => {
+                let generator = generator.with_options(options);
                 let (event_tx, mut event_rx) = mpsc::channel::<StreamEvent>(32);
 
                 let tx_forward = tx.clone();
                 let forward_task = tokio::spawn(async move {
-                    while let Some(StreamEvent::StepReady(s)) = event_rx.recv().await {
-                        let _ = tx_forward
-                            .send(ServerEvent::Engine(EngineEvent::StepReady(s)))
-                            .await;
+                    while let Some(event) = event_rx.recv().await {
+                        let event = match event {
+                            StreamEvent::Started(model) => EngineEvent::GenerationStarted(model),
+                            StreamEvent::Progress(tokens) => EngineEvent::GenerationProgress(tokens),
+                            StreamEvent::StepReady(s) => EngineEvent::StepReady(s),
+                        };
+                        let _ = tx_forward.send(ServerEvent::Engine(event)).await;
                     }
                 });
 
@@ -474,16 +814,46 @@ fn spawn_generation(
     });
 }
 
+/// A sender for API clients to announce rate-limit waits on, forwarded to
+/// the event loop.
+fn spawn_retry_forwarder(tx: mpsc::Sender<ServerEvent>) -> mpsc::UnboundedSender<RetryNotice> {
+    let (retry_tx, mut retry_rx) = mpsc::unbounded_channel();
+    tokio::spawn(async move {
+        while let Some(notice) = retry_rx.recv().await {
+            let event = ServerEvent::Engine(EngineEvent::RetryScheduled(notice));
+            if tx.send(event).await.is_err() {
+                break;
+            }
+        }
+    });
+    retry_tx
+}
+
 fn spawn_chat(
     tx: mpsc::Sender<ServerEvent>,
-    api_key: String,
+    config: ClientConfig,
     step_index: usize,
-    walkthrough: Walkthrough,
+    mut context: String,
     messages: Vec<Message>,
+    summarize: Option<(usize, String)>,
     mode: ReviewMode,
 ) {
     tokio::spawn(async move {
-        let client = ClaudeClient::new(api_key);
+        let client = crate::api::connect(config);
+        // Without a summary the oldest messages are simply left out
+        if let Some((covered, prompt)) = summarize
+            && let Ok((response, usage)) = client.summarize_history(&prompt).await
+        {
+            context = crate::api::context_with_summary(&context, &response.summary);
+            let _ = tx
+                .send(ServerEvent::Engine(EngineEvent::ChatSummarized(
+                    step_index,
+                    covered,
+                    response.summary,
+                    usage,
+                )))
+                .await;
+        }
         let (chunk_tx, mut chunk_rx) = mpsc::channel::<String>(32);
 
         let tx_chunks = tx.clone();
@@ -498,7 +868,7 @@ fn spawn_chat(
         });
 
         match client
-            .chat_streaming(&walkthrough, step_index, &messages, mode, chunk_tx)
+            .chat_streaming(&context, &messages, mode, chunk_tx)
             .await
         {
             Ok(usage) => {
@@ -521,14 +891,14 @@ fn spawn_chat(
 
 fn spawn_rechunk_task(
     tx: mpsc::Sender<ServerEvent>,
-    api_key: String,
+    config: ClientConfig,
     step_index: usize,
     step: Step,
     diff_text: Option<String>,
     mode: ReviewMode,
 ) {
     tokio::spawn(async move {
-        let client = ClaudeClient::new(api_key);
+        let client = crate::api::connect(config);
         let step_content = format_step_for_rechunk(&step);
         let mut prompt = format!(
             "Please split this step into smaller sub-steps.\n\n\
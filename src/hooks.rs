@@ -0,0 +1,258 @@
+//! User-configured commands run on review lifecycle events.
+//!
+//! Hooks are shell commands listed per event under `hooks` in
+//! ~/.docent/settings.json (see `HookSettings`). Each receives a JSON
+//! description of the event on stdin (and its name in `DOCENT_EVENT`), runs
+//! in the working directory, and its outcome is shown as a toast.
+
+use std::collections::HashSet;
+use std::process::Stdio;
+use std::time::Duration;
+
+use serde_json::{Value, json};
+use tokio::io::AsyncWriteExt;
+use tokio::process::Command;
+
+use crate::session::Session;
+
+/// Hooks still running after this long are killed.
+const HOOK_TIMEOUT: Duration = Duration::from_secs(600);
+
+/// Output lines kept for the toast.
+const OUTPUT_TAIL_LINES: usize = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HookKind {
+    StepEntered,
+    StepCompleted,
+    ReviewFinished,
+}
+
+impl HookKind {
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::StepEntered => "step_entered",
+            Self::StepCompleted => "step_completed",
+            Self::ReviewFinished => "review_finished",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct HookEvent {
+    pub kind: HookKind,
+    /// JSON sent to the hook on stdin
+    pub payload: Value,
+}
+
+#[derive(Debug, Clone)]
+pub struct HookOutcome {
+    pub command: String,
+    pub success: bool,
+    /// Last lines of combined stdout and stderr, or the spawn error
+    pub output: String,
+}
+
+/// Turns session changes into hook events by comparing against what was
+/// seen on the previous call. Steps are keyed by part and first hunk rather
+/// than by id, which re-sorting and step edits rewrite, so neither fires
+/// events for steps already handled.
+#[derive(Debug, Default)]
+pub struct HookTracker {
+    current: Option<String>,
+    reviewed: HashSet<String>,
+    finished: bool,
+}
+
+impl HookTracker {
+    pub fn observe(&mut self, session: &Session) -> Vec<HookEvent> {
+        let mut events = Vec::new();
+        if session.walkthrough.steps.is_empty() {
+            return events;
+        }
+        let key = |index: usize| {
+            let step = &session.walkthrough.steps[index];
+            match step.hunks.first() {
+                Some(hunk) => format!(
+                    "{}:{}:{}",
+                    session.current_part, hunk.file_path, hunk.start_line
+                ),
+                None => format!("{}:{}", session.current_part, step.title),
+            }
+        };
+
+        for index in 0..session.walkthrough.step_count() {
+            if session.is_step_reviewed(index) && self.reviewed.insert(key(index)) {
+                events.push(step_event(HookKind::StepCompleted, session, index));
+            }
+        }
+
+        let current = key(session.current_step);
+        if self.current.as_ref() != Some(&current) {
+            self.current = Some(current);
+            events.push(step_event(
+                HookKind::StepEntered,
+                session,
+                session.current_step,
+            ));
+        }
+
+        let finished = session.is_walkthrough_complete();
+        if finished && !self.finished {
+            let (reviewed, total) = session.overall_progress();
+            events.push(HookEvent {
+                kind: HookKind::ReviewFinished,
+                payload: json!({
+                    "event": HookKind::ReviewFinished.name(),
+                    "reviewed_steps": reviewed,
+                    "total_steps": total,
+                    "files": files(session, 0..session.walkthrough.step_count()),
+                }),
+            });
+        }
+        self.finished = finished;
+        events
+    }
+}
+
+fn step_event(kind: HookKind, session: &Session, index: usize) -> HookEvent {
+    let step = &session.walkthrough.steps[index];
+    HookEvent {
+        kind,
+        payload: json!({
+            "event": kind.name(),
+            "step": {
+                "index": index,
+                "id": step.id,
+                "title": step.title,
+                "priority": step.priority,
+                "reviewed": session.is_step_reviewed(index),
+            },
+            "files": files(session, index..index + 1),
+        }),
+    }
+}
+
+/// Files touched by the given steps, in order of first appearance.
+fn files(session: &Session, steps: std::ops::Range<usize>) -> Vec<String> {
+    let mut files: Vec<String> = Vec::new();
+    for step in &session.walkthrough.steps[steps] {
+        for hunk in &step.hunks {
+            if !files.contains(&hunk.file_path) {
+                files.push(hunk.file_path.clone());
+            }
+        }
+    }
+    files
+}
+
+/// Runs `command` through the shell with the event's payload on stdin.
+pub async fn run(command: String, event: HookEvent) -> HookOutcome {
+    let result = tokio::time::timeout(HOOK_TIMEOUT, run_shell(&command, &event)).await;
+    let (success, output) = match result {
+        Ok(Ok(outcome)) => outcome,
+        Ok(Err(e)) => (false, e.to_string()),
+        Err(_) => (false, "timed out".to_string()),
+    };
+    HookOutcome {
+        command,
+        success,
+        output,
+    }
+}
+
+async fn run_shell(command: &str, event: &HookEvent) -> std::io::Result<(bool, String)> {
+    let mut shell = if cfg!(windows) {
+        let mut shell = Command::new("cmd");
+        shell.arg("/C");
+        shell
+    } else {
+        let mut shell = Command::new("sh");
+        shell.arg("-c");
+        shell
+    };
+    let mut child = shell
+        .arg(command)
+        .env("DOCENT_EVENT", event.kind.name())
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        // A hook that ignores stdin may exit before reading it
+        let _ = stdin.write_all(event.payload.to_string().as_bytes()).await;
+    }
+    let output = child.wait_with_output().await?;
+    let combined = format!(
+        "{}{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+    Ok((output.status.success(), tail(&combined)))
+}
+
+fn tail(output: &str) -> String {
+    let lines: Vec<&str> = output.lines().filter(|l| !l.trim().is_empty()).collect();
+    lines[lines.len().saturating_sub(OUTPUT_TAIL_LINES)..].join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{ReviewMode, Step, Walkthrough};
+
+    fn kinds(events: &[HookEvent]) -> Vec<HookKind> {
+        events.iter().map(|e| e.kind).collect()
+    }
+
+    #[test]
+    fn test_tracker_fires_each_event_once() {
+        let walkthrough = Walkthrough {
+            steps: vec![Step::test("1", "src/a.rs"), Step::test("2", "src/b.rs")],
+            messages: vec![],
+        };
+        let mut session = Session::new(walkthrough, ReviewMode::Review);
+        let mut tracker = HookTracker::default();
+
+        assert_eq!(kinds(&tracker.observe(&session)), [HookKind::StepEntered]);
+        assert!(tracker.observe(&session).is_empty());
+
+        session.complete_step_and_advance();
+        let events = tracker.observe(&session);
+        assert_eq!(
+            kinds(&events),
+            [HookKind::StepCompleted, HookKind::StepEntered]
+        );
+        assert_eq!(events[0].payload["files"], json!(["src/a.rs"]));
+
+        session.complete_step_and_advance();
+        assert_eq!(
+            kinds(&tracker.observe(&session)),
+            [HookKind::StepCompleted, HookKind::ReviewFinished]
+        );
+    }
+
+    #[test]
+    fn test_resorting_fires_no_events() {
+        let walkthrough = Walkthrough {
+            steps: vec![Step::test("1", "src/b.rs"), Step::test("2", "src/a.rs")],
+            messages: vec![],
+        };
+        let mut session = Session::new(walkthrough, ReviewMode::Review);
+        let mut tracker = HookTracker::default();
+        tracker.observe(&session);
+        session.complete_step_and_advance();
+        tracker.observe(&session);
+
+        assert!(session.toggle_step_order());
+        assert_eq!(session.walkthrough.steps[0].hunks[0].file_path, "src/a.rs");
+        assert!(tracker.observe(&session).is_empty());
+    }
+
+    #[test]
+    fn test_tail_keeps_last_lines() {
+        assert_eq!(tail("a\nb\n\nc\nd\n"), "b\nc\nd");
+    }
+}
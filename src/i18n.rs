@@ -0,0 +1,1198 @@
+//! User-facing UI strings, selectable by the `language` setting.
+//!
+//! Each locale is a `Strings` table; UI code reads from `app.strings` rather
+//! than using literals so adding a language means adding one table here.
+
+use serde::{Deserialize, Serialize};
+
+use crate::api::{ChatContext, RetryNotice};
+use crate::model::{PriorityFilter, Severity, StepOrder, Walkthrough};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Language {
+    En,
+    Es,
+}
+
+impl Language {
+    /// Maps a POSIX locale like `es_ES.UTF-8` to a supported language.
+    pub fn from_locale(locale: &str) -> Option<Self> {
+        let code = locale.split(['_', '.', '-']).next()?.to_lowercase();
+        match code.as_str() {
+            "en" => Some(Self::En),
+            "es" => Some(Self::Es),
+            _ => None,
+        }
+    }
+
+    /// Language from the environment (`LC_ALL`, `LC_MESSAGES`, `LANG`), defaulting to English.
+    pub fn detect() -> Self {
+        ["LC_ALL", "LC_MESSAGES", "LANG"]
+            .iter()
+            .filter_map(|var| std::env::var(var).ok())
+            .find(|value| !value.is_empty())
+            .and_then(|value| Self::from_locale(&value))
+            .unwrap_or(Self::En)
+    }
+
+    pub fn strings(self) -> &'static Strings {
+        match self {
+            Self::En => &EN,
+            Self::Es => &ES,
+        }
+    }
+}
+
+pub struct Strings {
+    // Help bar
+    pub quit_confirm: &'static str,
+    pub mode_zoomed: &'static str,
+    pub mode_scrollback: &'static str,
+    pub mode_zoomed_scrollback: &'static str,
+    pub mode_insert: &'static str,
+    pub mode_zoomed_insert: &'static str,
+    pub mode_visual: &'static str,
+    pub mode_visual_line: &'static str,
+    pub act_scroll: &'static str,
+    pub act_switch_pane: &'static str,
+    pub act_help: &'static str,
+    pub act_quit: &'static str,
+    pub act_zoom: &'static str,
+    pub act_unzoom: &'static str,
+    pub act_exit: &'static str,
+    pub act_switch_step: &'static str,
+    pub act_mark_reviewed: &'static str,
+    pub act_step: &'static str,
+    pub act_search: &'static str,
+    pub act_next_prev: &'static str,
+    pub act_clear: &'static str,
+    pub act_split_step: &'static str,
+    pub act_context: &'static str,
+    pub act_whole_change: &'static str,
+    pub act_filter: &'static str,
+    pub act_move: &'static str,
+    pub act_copy: &'static str,
+    pub act_delete: &'static str,
+    pub act_change: &'static str,
+
+    // Help modal
+    pub help_title: &'static str,
+    pub heading_navigation: &'static str,
+    pub heading_actions: &'static str,
+    pub heading_search: &'static str,
+    pub help_next_prev_step: &'static str,
+    pub help_scroll_diff: &'static str,
+    pub help_half_page: &'static str,
+    pub help_top_bottom: &'static str,
+    pub help_cycle_panes: &'static str,
+    pub help_move_panes: &'static str,
+    pub help_sections: &'static str,
+    pub help_mark_advance: &'static str,
+    pub help_toggle_reviewed: &'static str,
+    pub help_hunk_cursor: &'static str,
+    pub help_file_cursor: &'static str,
+    pub help_toggle_hunk: &'static str,
+    pub help_visual_select: &'static str,
+    pub help_line_cursor: &'static str,
+    pub help_quote_selection: &'static str,
+    pub help_yank: &'static str,
+    pub help_regenerate: &'static str,
+    pub help_regenerate_summary: &'static str,
+    pub help_persona: &'static str,
+    pub help_search_all: &'static str,
+    pub help_find_step: &'static str,
+    pub help_usage: &'static str,
+    pub help_split: &'static str,
+    pub help_step_order: &'static str,
+    pub help_move_step: &'static str,
+    pub help_merge_step: &'static str,
+    pub help_split_hunks: &'static str,
+    pub help_move_hunk: &'static str,
+    pub help_file_view: &'static str,
+    pub help_priority_filter: &'static str,
+    pub help_related: &'static str,
+    pub help_quick_actions: &'static str,
+    pub help_feedback: &'static str,
+    pub help_note: &'static str,
+    pub help_flag: &'static str,
+    pub help_post_review: &'static str,
+    pub help_parts: &'static str,
+    pub help_chat: &'static str,
+    pub help_whole_change_chat: &'static str,
+    pub help_follow_ups: &'static str,
+    pub help_zoom: &'static str,
+    pub help_fold: &'static str,
+    pub help_fold_all: &'static str,
+    pub help_file_tree: &'static str,
+    pub help_open_browser: &'static str,
+    pub help_mouse_capture: &'static str,
+    pub help_export_markdown: &'static str,
+    pub help_export_html: &'static str,
+    pub help_export_json: &'static str,
+    pub help_record: &'static str,
+    pub help_start_search: &'static str,
+    pub help_next_prev_match: &'static str,
+    pub help_clear_search: &'static str,
+    pub help_close: &'static str,
+
+    // Dialogs
+    pub loading_title: &'static str,
+    pub loading_cancel: &'static str,
+    pub error_title: &'static str,
+    pub error_retry: &'static str,
+    pub too_small_title: &'static str,
+    pub too_small_need: &'static str,
+    pub too_small_now: &'static str,
+
+    // Panes
+    pub pane_steps: &'static str,
+    pub pane_chat: &'static str,
+    pub pane_diff: &'static str,
+    pub pane_files: &'static str,
+    pub lines: &'static str,
+    pub hunks: &'static str,
+    pub lines_selected: &'static str,
+    pub generating: &'static str,
+    pub progress_steps: &'static str,
+    pub progress_tokens: &'static str,
+    pub retrying_in: &'static str,
+    pub no_step_selected: &'static str,
+    pub related_label: &'static str,
+    pub chat_context: &'static str,
+    pub chat_trimmed: &'static str,
+    pub related_step: &'static str,
+    pub related_jump: &'static str,
+    pub no_diff_content: &'static str,
+    pub chat_placeholder: &'static str,
+    pub thinking: &'static str,
+    pub splitting_step: &'static str,
+    pub complete_title: &'static str,
+    pub complete_all_reviewed: &'static str,
+    pub complete_steps: &'static str,
+    pub complete_time: &'static str,
+    pub complete_tokens: &'static str,
+    pub complete_no_flags: &'static str,
+    pub complete_export: &'static str,
+    pub complete_copy: &'static str,
+    pub complete_record: &'static str,
+    pub complete_back: &'static str,
+
+    // Setup screen
+    pub setup_subtitle: &'static str,
+    pub setup_mode: &'static str,
+    pub setup_review: &'static str,
+    pub setup_review_desc: &'static str,
+    pub setup_walkthrough: &'static str,
+    pub setup_walkthrough_desc: &'static str,
+    pub setup_api_key: &'static str,
+    pub setup_no_key: &'static str,
+    pub setup_start: &'static str,
+    pub setup_switch: &'static str,
+    pub setup_quit: &'static str,
+    pub key_from_env: &'static str,
+    pub key_saved: &'static str,
+    pub key_entered: &'static str,
+    pub key_session: &'static str,
+    pub key_not_required: &'static str,
+
+    // File selection
+    pub files_title: &'static str,
+    pub files_selected: &'static str,
+    pub files_hunks: &'static str,
+    pub files_skipped: &'static str,
+    pub files_include_skipped: &'static str,
+    pub files_toggle: &'static str,
+    pub files_all: &'static str,
+    pub files_none: &'static str,
+    pub files_invert: &'static str,
+    pub files_back: &'static str,
+
+    // Split review
+    pub split_title: &'static str,
+    pub split_changed_lines: &'static str,
+    pub split_limit: &'static str,
+    pub split_by_directory: &'static str,
+    pub split_by_model: &'static str,
+    pub split_whole: &'static str,
+    pub split_partitioning: &'static str,
+    pub parts_title: &'static str,
+    pub parts_part: &'static str,
+    pub parts_reviewed: &'static str,
+    pub parts_not_generated: &'static str,
+    pub parts_open: &'static str,
+
+    // Quick actions
+    pub quick_title: &'static str,
+    pub quick_hunk: &'static str,
+    pub quick_explain: &'static str,
+    pub quick_risks: &'static str,
+    pub quick_tests: &'static str,
+    pub quick_simplify: &'static str,
+    pub quick_run: &'static str,
+
+    // Hunk split
+    pub hunk_split_title: &'static str,
+    pub hunk_split_step: &'static str,
+    pub hunk_split_pick: &'static str,
+    pub hunk_split_confirm: &'static str,
+    pub hunk_move_title: &'static str,
+    pub hunk_move_confirm: &'static str,
+    pub feedback_title: &'static str,
+    pub feedback_useful: &'static str,
+    pub feedback_not_useful: &'static str,
+    pub feedback_comment: &'static str,
+    pub feedback_switch: &'static str,
+    pub feedback_save: &'static str,
+    pub note_title: &'static str,
+    pub note_empty: &'static str,
+    pub note_new: &'static str,
+    pub note_hint: &'static str,
+    pub note_add: &'static str,
+    pub note_newline: &'static str,
+    pub note_delete: &'static str,
+    pub flag_title: &'static str,
+    pub flag_empty: &'static str,
+    pub flag_severity: &'static str,
+    pub flag_new: &'static str,
+    pub flag_cycle: &'static str,
+    pub flag_add: &'static str,
+    pub complete_flags: &'static str,
+    pub regenerate_title: &'static str,
+    pub regenerate_intro: &'static str,
+    pub regenerate_hint: &'static str,
+    pub regenerate_run: &'static str,
+    pub summary_title: &'static str,
+    pub summary_intro: &'static str,
+    pub summary_hint: &'static str,
+    pub follow_ups_label: &'static str,
+    pub chat_whole_change: &'static str,
+    pub chat_whole_change_hint: &'static str,
+    pub global_search_title: &'static str,
+    pub global_search_none: &'static str,
+    pub global_search_truncated: &'static str,
+    pub global_search_jump: &'static str,
+    pub global_search_this_step: &'static str,
+    pub step_finder_title: &'static str,
+    pub search_all_steps: &'static str,
+    pub search_regex: &'static str,
+    pub usage_title: &'static str,
+    pub usage_generation: &'static str,
+    pub usage_chat: &'static str,
+    pub usage_total: &'static str,
+    pub usage_model: &'static str,
+    pub usage_prices: &'static str,
+    pub usage_no_prices: &'static str,
+    pub recovery_title: &'static str,
+    pub recovery_intro: &'static str,
+    pub recovery_saved: &'static str,
+    pub recovery_progress: &'static str,
+    pub recovery_other_directory: &'static str,
+    pub recovery_restore: &'static str,
+    pub recovery_discard: &'static str,
+    pub diff_update_title: &'static str,
+    pub diff_update_intro: &'static str,
+    pub diff_update_reviewed: &'static str,
+    pub diff_update_regenerate: &'static str,
+    pub diff_update_keep: &'static str,
+
+    // Notifications
+    pub toast_no_answer: &'static str,
+    pub toast_copied: &'static str,
+    pub toast_line: &'static str,
+    pub toast_lines: &'static str,
+    pub toast_no_selection: &'static str,
+    pub toast_no_retry: &'static str,
+    pub toast_clear_input: &'static str,
+    pub toast_no_edit: &'static str,
+    pub toast_chat_context: &'static str,
+    pub toast_persona: &'static str,
+    pub toast_persona_regenerate: &'static str,
+    pub toast_chat_whole_change: &'static str,
+    pub toast_chat_step: &'static str,
+    pub toast_chat_cancelled: &'static str,
+    pub toast_generation_cancelled: &'static str,
+    pub toast_steps_kept: &'static str,
+    pub toast_wait_answer: &'static str,
+    pub toast_session_restored: &'static str,
+    pub toast_feedback_saved: &'static str,
+    pub toast_feedback_failed: &'static str,
+    pub toast_note_added: &'static str,
+    pub toast_flagged: &'static str,
+    pub toast_wait_generation: &'static str,
+    pub toast_regenerating: &'static str,
+    pub toast_regenerating_diff: &'static str,
+    pub toast_wait_request: &'static str,
+    pub toast_rewriting_summary: &'static str,
+    pub toast_export_written: &'static str,
+    pub toast_export_failed: &'static str,
+    pub toast_no_pull_request: &'static str,
+    pub toast_no_notes: &'static str,
+    pub toast_posting_review: &'static str,
+    pub toast_review_posted: &'static str,
+    pub toast_comments: &'static str,
+    pub toast_post_failed: &'static str,
+    pub toast_recording_review: &'static str,
+    pub toast_review_recorded: &'static str,
+    pub toast_report: &'static str,
+    pub toast_record_failed: &'static str,
+    pub toast_no_forge: &'static str,
+    pub toast_opened: &'static str,
+    pub toast_parts_busy: &'static str,
+    pub toast_part: &'static str,
+    pub toast_of: &'static str,
+    pub toast_mouse_on: &'static str,
+    pub toast_mouse_off: &'static str,
+    pub toast_mouse_restore: &'static str,
+    pub toast_sort_busy: &'static str,
+    pub toast_steps_in: &'static str,
+    pub toast_rearrange_busy: &'static str,
+    pub toast_merged: &'static str,
+    pub toast_merge_first: &'static str,
+    pub toast_merge_sub_steps: &'static str,
+    pub toast_split_one_hunk: &'static str,
+    pub toast_split_moved: &'static str,
+    pub toast_split_pick: &'static str,
+    pub toast_move_only_hunk: &'static str,
+    pub toast_hunk_moved: &'static str,
+    pub toast_no_related: &'static str,
+    pub toast_rate_limited: &'static str,
+    pub toast_overloaded: &'static str,
+    pub toast_retrying_in: &'static str,
+    pub toast_attempt: &'static str,
+    pub toast_walkthrough_ready: &'static str,
+    pub toast_loaded_from_cache: &'static str,
+    pub toast_thread_step: &'static str,
+    pub toast_thread_whole_change: &'static str,
+    pub toast_chat_ready: &'static str,
+    pub toast_chat_error: &'static str,
+    pub toast_chat_summary_failed: &'static str,
+    pub toast_chat_summary_left_out: &'static str,
+    pub toast_summary_rewritten: &'static str,
+    pub toast_summary_failed: &'static str,
+    pub toast_hook: &'static str,
+    pub toast_hook_passed: &'static str,
+    pub toast_hook_failed: &'static str,
+    pub toast_steps: &'static str,
+    pub toast_step: &'static str,
+    pub toast_marked_reviewed: &'static str,
+    pub toast_marked_unreviewed: &'static str,
+    pub toast_walkthrough_complete: &'static str,
+    pub toast_response_ready: &'static str,
+    pub toast_parts_fallback: &'static str,
+    pub toast_showing: &'static str,
+    pub toast_select_file: &'static str,
+
+    // Labels
+    pub severity_blocker: &'static str,
+    pub severity_blockers: &'static str,
+    pub severity_question: &'static str,
+    pub severity_questions: &'static str,
+    pub severity_nit: &'static str,
+    pub severity_nits: &'static str,
+    pub order_narrative: &'static str,
+    pub order_file: &'static str,
+    pub filter_all: &'static str,
+    pub filter_hide_minor: &'static str,
+    pub filter_critical_only: &'static str,
+    pub context_step: &'static str,
+    pub context_overview: &'static str,
+    pub context_neighbors: &'static str,
+    pub context_surrounding: &'static str,
+    pub context_full_file: &'static str,
+}
+
+impl Strings {
+    pub fn severity_label(&self, severity: Severity) -> &'static str {
+        match severity {
+            Severity::Blocker => self.severity_blocker,
+            Severity::Question => self.severity_question,
+            Severity::Nit => self.severity_nit,
+        }
+    }
+
+    /// How many flags there are of each severity, e.g. "1 blocker, 2 nits".
+    pub fn flag_tally(&self, walkthrough: &Walkthrough) -> String {
+        walkthrough
+            .flag_counts()
+            .into_iter()
+            .map(|(severity, count)| {
+                let label = match (severity, count) {
+                    (_, 1) => self.severity_label(severity),
+                    (Severity::Blocker, _) => self.severity_blockers,
+                    (Severity::Question, _) => self.severity_questions,
+                    (Severity::Nit, _) => self.severity_nits,
+                };
+                format!("{} {}", count, label)
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    pub fn step_order_label(&self, order: StepOrder) -> &'static str {
+        match order {
+            StepOrder::Narrative => self.order_narrative,
+            StepOrder::FilePath => self.order_file,
+        }
+    }
+
+    pub fn priority_filter_label(&self, filter: PriorityFilter) -> &'static str {
+        match filter {
+            PriorityFilter::All => self.filter_all,
+            PriorityFilter::HideMinor => self.filter_hide_minor,
+            PriorityFilter::CriticalOnly => self.filter_critical_only,
+        }
+    }
+
+    pub fn chat_context_label(&self, context: ChatContext) -> &'static str {
+        match context {
+            ChatContext::Step => self.context_step,
+            ChatContext::Overview => self.context_overview,
+            ChatContext::Neighbors => self.context_neighbors,
+            ChatContext::Surrounding => self.context_surrounding,
+            ChatContext::FullFile => self.context_full_file,
+        }
+    }
+
+    /// Why a request is being retried: rate limited or overloaded.
+    pub fn retry_reason(&self, notice: &RetryNotice) -> &'static str {
+        if notice.status == 429 {
+            self.toast_rate_limited
+        } else {
+            self.toast_overloaded
+        }
+    }
+}
+
+pub static EN: Strings = Strings {
+    quit_confirm: "Press Ctrl+C again to quit",
+    mode_zoomed: "-- ZOOMED -- ",
+    mode_scrollback: "-- SCROLLBACK -- ",
+    mode_zoomed_scrollback: "-- ZOOMED | SCROLLBACK -- ",
+    mode_insert: "-- INSERT --",
+    mode_zoomed_insert: "-- ZOOMED | INSERT --",
+    mode_visual: "-- VISUAL -- ",
+    mode_visual_line: "-- VISUAL LINE -- ",
+    act_scroll: "scroll",
+    act_switch_pane: "switch pane",
+    act_help: "help",
+    act_quit: "quit",
+    act_zoom: "zoom",
+    act_unzoom: "unzoom",
+    act_exit: "exit",
+    act_switch_step: "switch step",
+    act_mark_reviewed: "mark reviewed",
+    act_step: "step",
+    act_search: "search",
+    act_next_prev: "next/prev",
+    act_clear: "clear",
+    act_split_step: "split step",
+    act_context: "context",
+    act_whole_change: "whole change",
+    act_filter: "filter",
+    act_move: "move",
+    act_copy: "copy",
+    act_delete: "delete",
+    act_change: "change",
+
+    help_title: " Keybindings ",
+    heading_navigation: "Navigation",
+    heading_actions: "Actions",
+    heading_search: "Search",
+    help_next_prev_step: "Next/previous step",
+    help_scroll_diff: "Scroll diff",
+    help_half_page: "Half-page scroll",
+    help_top_bottom: "Top/bottom of diff",
+    help_cycle_panes: "Cycle panes",
+    help_move_panes: "Move between panes",
+    help_sections: "Previous/next section (linear)",
+    help_mark_advance: "Mark step & advance",
+    help_toggle_reviewed: "Toggle step reviewed",
+    help_hunk_cursor: "Select previous/next hunk",
+    help_file_cursor: "Jump to previous/next file",
+    help_toggle_hunk: "Toggle hunk reviewed",
+    help_visual_select: "Select diff lines (extend with scroll keys)",
+    help_line_cursor: "Toggle a line cursor moved by the scroll keys",
+    help_quote_selection: "Quote selected lines into chat",
+    help_yank: "Copy selected lines or hunk (y in chat: last answer)",
+    help_regenerate: "Regenerate with instructions",
+    help_regenerate_summary: "Rewrite this step's summary",
+    help_persona: "Switch review persona",
+    help_search_all: "Search all steps (Tab switches from /)",
+    help_find_step: "Jump to a step by title or file",
+    help_usage: "Token usage and cost",
+    help_split: "Split step (rechunk)",
+    help_step_order: "Narrative/file step order",
+    help_move_step: "Move step down/up (step list)",
+    help_merge_step: "Merge step into previous (step list)",
+    help_split_hunks: "Move hunks to a new step (step list)",
+    help_move_hunk: "Move selected hunk to another step (diff)",
+    help_file_view: "Every hunk in the selected hunk's file (diff)",
+    help_priority_filter: "Hide minor / non-critical steps (step list)",
+    help_related: "Jump to a related step",
+    help_quick_actions: "Quick actions on the current hunk",
+    help_feedback: "Rate this step (saved locally)",
+    help_note: "Add or remove notes on this step",
+    help_flag: "Flag a concern on this step or the selected hunk",
+    help_post_review: "Post notes as PR review comments",
+    help_parts: "Switch part (split reviews)",
+    help_chat: "Enter chat mode",
+    help_whole_change_chat: "Ask about the whole change, or back to the step",
+    help_follow_ups: "Ask a suggested follow-up question",
+    help_zoom: "Toggle zoom",
+    help_fold: "Fold the selected hunk, or every hunk of its file",
+    help_fold_all: "Unfold or fold every hunk in the step",
+    help_file_tree: "Show or hide the file tree",
+    help_open_browser: "Open PR/file in browser",
+    help_mouse_capture: "Toggle mouse capture",
+    help_export_markdown: "Export Markdown report",
+    help_export_html: "Export HTML bundle",
+    help_export_json: "Export walkthrough as JSON",
+    help_record: "Record review as git note",
+    help_start_search: "Start search",
+    help_next_prev_match: "Next/prev match",
+    help_clear_search: "Clear search",
+    help_close: "Press any key to close",
+
+    loading_title: " Generating Walkthrough ",
+    loading_cancel: "Press Ctrl+C to cancel",
+    error_title: " Error ",
+    error_retry: "Press 'r' to retry or 'q' to quit",
+    too_small_title: "Terminal too small",
+    too_small_need: "Need at least",
+    too_small_now: "now",
+
+    pane_steps: "Steps",
+    pane_chat: "Chat",
+    pane_diff: "Diff",
+    pane_files: "Files",
+    lines: "lines",
+    hunks: "hunks",
+    lines_selected: "lines selected",
+    generating: "Generating…",
+    progress_steps: "steps",
+    progress_tokens: "tokens",
+    retrying_in: "retrying in",
+    no_step_selected: "No step selected",
+    related_label: "Related:",
+    chat_context: "context:",
+    chat_trimmed: "older messages trimmed",
+    related_step: "step",
+    related_jump: "r to jump",
+    no_diff_content: "No diff content",
+    chat_placeholder: "Press 'i' to ask a question",
+    thinking: "Thinking...",
+    splitting_step: "Splitting step...",
+    complete_title: "✓ Walkthrough complete!",
+    complete_all_reviewed: "All steps have been reviewed.",
+    complete_steps: "Steps reviewed",
+    complete_time: "Time spent",
+    complete_tokens: "Tokens",
+    complete_no_flags: "none",
+    complete_export: "export Markdown",
+    complete_copy: "copy summary",
+    complete_record: "record as git note",
+    complete_back: "back to steps",
+
+    setup_subtitle: "AI-guided code review walkthrough",
+    setup_mode: "Mode",
+    setup_review: "Review",
+    setup_review_desc: "Call out potential issues",
+    setup_walkthrough: "Walkthrough",
+    setup_walkthrough_desc: "Describe the changes",
+    setup_api_key: "API Key",
+    setup_no_key: "No API key found",
+    setup_start: "start",
+    setup_switch: "switch",
+    setup_quit: "quit",
+    key_from_env: "from env",
+    key_saved: "saved",
+    key_entered: "entered",
+    key_session: "this session only",
+    key_not_required: "no key needed",
+
+    files_title: " Files to Review ",
+    files_selected: "files selected",
+    files_hunks: "hunks",
+    files_skipped: "generated/vendored files skipped",
+    files_include_skipped: "include them",
+    files_toggle: "toggle",
+    files_all: "all",
+    files_none: "none",
+    files_invert: "invert",
+    files_back: "back",
+
+    split_title: " Large Diff ",
+    split_changed_lines: "changed lines",
+    split_limit: "limit",
+    split_by_directory: "Split into parts by directory",
+    split_by_model: "Split into parts suggested by the model",
+    split_whole: "Review as a single walkthrough",
+    split_partitioning: "Asking the model for a partitioning...",
+    parts_title: " Parts ",
+    parts_part: "Part",
+    parts_reviewed: "steps reviewed",
+    parts_not_generated: "not generated yet",
+    parts_open: "open",
+
+    quick_title: " Quick Actions ",
+    quick_hunk: "Hunk",
+    quick_explain: "Explain this hunk",
+    quick_risks: "List risks",
+    quick_tests: "Suggest tests",
+    quick_simplify: "Suggest simplifications",
+    quick_run: "ask",
+
+    hunk_split_title: " Move Hunks to a New Step ",
+    hunk_split_step: "Step",
+    hunk_split_pick: "pick",
+    hunk_split_confirm: "split",
+    hunk_move_title: " Move Hunk to Step ",
+    hunk_move_confirm: "move",
+    feedback_title: " Rate This Step ",
+    feedback_useful: "Useful",
+    feedback_not_useful: "Not useful",
+    feedback_comment: "Comment (optional):",
+    feedback_switch: "switch",
+    feedback_save: "save",
+    note_title: " Notes on This Step ",
+    note_empty: "No notes yet.",
+    note_new: "New note:",
+    note_hint: "Notes are kept in exports and posted to the pull request with R.",
+    note_add: "add",
+    note_newline: "newline",
+    note_delete: "delete last",
+    flag_title: " Flags on This Step ",
+    flag_empty: "No flags yet.",
+    flag_severity: "Severity:",
+    flag_new: "Concern (optional):",
+    flag_cycle: "severity",
+    flag_add: "flag",
+    complete_flags: "Flags",
+    regenerate_title: " Regenerate Walkthrough ",
+    regenerate_intro: "Instructions for the new walkthrough (optional):",
+    regenerate_hint: "e.g. \"focus on the concurrency changes\" or \"fewer, larger steps\". \
+                      Steps with unchanged hunks keep their chat and notes.",
+    regenerate_run: "regenerate",
+    summary_title: " Rewrite Step Summary ",
+    summary_intro: "Instructions for the new summary (optional):",
+    summary_hint: "e.g. \"explain in more depth\" or \"focus on the error handling\". \
+                   The rest of the walkthrough is left as is.",
+    follow_ups_label: "Suggested questions:",
+    chat_whole_change: "whole change",
+    chat_whole_change_hint: "Questions here are about the change as a whole. Each one is sent with \
+                             every step's summary.",
+    global_search_title: " Search All Steps ",
+    global_search_none: "No matches",
+    global_search_truncated: "Showing the first results only",
+    global_search_jump: "jump",
+    global_search_this_step: "this step",
+    step_finder_title: " Go to Step ",
+    search_all_steps: "all steps",
+    search_regex: "regex",
+    usage_title: " Usage ",
+    usage_generation: "Walkthrough",
+    usage_chat: "Chat",
+    usage_total: "Total",
+    usage_model: "Model",
+    usage_prices: "per million tokens",
+    usage_no_prices: "No prices known for this model. Set api.pricing for a cost estimate.",
+    recovery_title: " Restore Session? ",
+    recovery_intro: "docent didn't exit cleanly last time. Pick up where you left off?",
+    recovery_saved: "Saved",
+    recovery_progress: "steps reviewed",
+    recovery_other_directory: "Saved in another directory. Once restored, file lookups, git \
+                               notes, and chats use this one.",
+    recovery_restore: "restore",
+    recovery_discard: "discard",
+    diff_update_title: " Diff Changed ",
+    diff_update_intro: "The diff changed since this walkthrough was generated. Regenerate it?",
+    diff_update_reviewed: "Reviewed hunks that haven't changed stay reviewed.",
+    diff_update_regenerate: "regenerate",
+    diff_update_keep: "keep reviewing",
+    toast_no_answer: "No answer to copy",
+    toast_copied: "Copied",
+    toast_line: "line",
+    toast_lines: "lines",
+    toast_no_selection: "No diff lines selected",
+    toast_no_retry: "No question to retry",
+    toast_clear_input: "Clear the chat input first",
+    toast_no_edit: "No question to edit",
+    toast_chat_context: "Chat context",
+    toast_persona: "Persona",
+    toast_persona_regenerate: "regenerates with it",
+    toast_chat_whole_change: "Chat: the whole change",
+    toast_chat_step: "Chat: this step",
+    toast_chat_cancelled: "Chat reply cancelled",
+    toast_generation_cancelled: "Generation cancelled",
+    toast_steps_kept: "steps kept",
+    toast_wait_answer: "Wait for the current answer to finish",
+    toast_session_restored: "Session restored",
+    toast_feedback_saved: "Feedback saved",
+    toast_feedback_failed: "Feedback not saved",
+    toast_note_added: "Note added",
+    toast_flagged: "Flagged as",
+    toast_wait_generation: "Wait for generation to finish",
+    toast_regenerating: "Regenerating walkthrough",
+    toast_regenerating_diff: "Regenerating walkthrough for the updated diff",
+    toast_wait_request: "Wait for the current request to finish",
+    toast_rewriting_summary: "Rewriting step summary",
+    toast_export_written: "Export written to",
+    toast_export_failed: "Export failed",
+    toast_no_pull_request: "Notes can only be posted to a GitHub pull request",
+    toast_no_notes: "No step notes to post; add one with",
+    toast_posting_review: "Posting review...",
+    toast_review_posted: "Review posted",
+    toast_comments: "comments",
+    toast_post_failed: "Error posting review",
+    toast_recording_review: "Recording review...",
+    toast_review_recorded: "Recorded review on HEAD",
+    toast_report: "report",
+    toast_record_failed: "Error recording review",
+    toast_no_forge: "No forge link for this diff",
+    toast_opened: "Opened",
+    toast_parts_busy: "Parts can be switched once generation and pending requests finish",
+    toast_part: "Part",
+    toast_of: "of",
+    toast_mouse_on: "Mouse capture on",
+    toast_mouse_off: "Mouse capture off: select text with the mouse, press",
+    toast_mouse_restore: "to restore",
+    toast_sort_busy: "Steps can be re-sorted once generation and pending requests finish",
+    toast_steps_in: "Steps in",
+    toast_rearrange_busy: "Steps can be rearranged once generation and pending requests finish",
+    toast_merged: "Merged into the previous step",
+    toast_merge_first: "The first step has no step to merge into",
+    toast_merge_sub_steps: "Steps with sub-steps can't be merged",
+    toast_split_one_hunk: "Only steps with more than one hunk can be split",
+    toast_split_moved: "Moved to new step",
+    toast_split_pick: "Pick some hunks, but not all of them",
+    toast_move_only_hunk: "A step's only hunk can't be moved; merge the step instead",
+    toast_hunk_moved: "Moved hunk to step",
+    toast_no_related: "No related steps",
+    toast_rate_limited: "Rate limited",
+    toast_overloaded: "API overloaded",
+    toast_retrying_in: "retrying in",
+    toast_attempt: "attempt",
+    toast_walkthrough_ready: "Walkthrough ready",
+    toast_loaded_from_cache: "Walkthrough loaded from cache",
+    toast_thread_step: "step",
+    toast_thread_whole_change: "the whole change",
+    toast_chat_ready: "Chat response ready on",
+    toast_chat_error: "Chat error on",
+    toast_chat_summary_failed: "Couldn't summarize the start of the chat on",
+    toast_chat_summary_left_out: "so it was left out",
+    toast_summary_rewritten: "Summary rewritten for step",
+    toast_summary_failed: "Summary failed for step",
+    toast_hook: "Hook",
+    toast_hook_passed: "passed",
+    toast_hook_failed: "failed",
+    toast_steps: "steps",
+    toast_step: "Step",
+    toast_marked_reviewed: "marked reviewed",
+    toast_marked_unreviewed: "marked not reviewed",
+    toast_walkthrough_complete: "Walkthrough complete. All steps reviewed.",
+    toast_response_ready: "Response ready for",
+    toast_parts_fallback: "Couldn't get suggested parts, splitting by directory",
+    toast_showing: "Showing",
+    toast_select_file: "Select at least one file",
+
+    severity_blocker: "blocker",
+    severity_blockers: "blockers",
+    severity_question: "question",
+    severity_questions: "questions",
+    severity_nit: "nit",
+    severity_nits: "nits",
+    order_narrative: "narrative order",
+    order_file: "file order",
+    filter_all: "all steps",
+    filter_hide_minor: "minor steps hidden",
+    filter_critical_only: "critical steps only",
+    context_step: "step",
+    context_overview: "overview",
+    context_neighbors: "neighbors",
+    context_surrounding: "surrounding",
+    context_full_file: "full file",
+};
+
+pub static ES: Strings = Strings {
+    quit_confirm: "Pulsa Ctrl+C otra vez para salir",
+    mode_zoomed: "-- AMPLIADO -- ",
+    mode_scrollback: "-- HISTORIAL -- ",
+    mode_zoomed_scrollback: "-- AMPLIADO | HISTORIAL -- ",
+    mode_insert: "-- INSERTAR --",
+    mode_zoomed_insert: "-- AMPLIADO | INSERTAR --",
+    mode_visual: "-- VISUAL -- ",
+    mode_visual_line: "-- VISUAL LÍNEA -- ",
+    act_scroll: "desplazar",
+    act_switch_pane: "cambiar panel",
+    act_help: "ayuda",
+    act_quit: "salir",
+    act_zoom: "ampliar",
+    act_unzoom: "reducir",
+    act_exit: "volver",
+    act_switch_step: "cambiar paso",
+    act_mark_reviewed: "marcar revisado",
+    act_step: "paso",
+    act_search: "buscar",
+    act_next_prev: "sig./ant.",
+    act_clear: "limpiar",
+    act_split_step: "dividir paso",
+    act_context: "contexto",
+    act_whole_change: "todo el cambio",
+    act_filter: "filtrar",
+    act_move: "mover",
+    act_copy: "copiar",
+    act_delete: "borrar",
+    act_change: "cambiar",
+
+    help_title: " Atajos de teclado ",
+    heading_navigation: "Navegación",
+    heading_actions: "Acciones",
+    heading_search: "Búsqueda",
+    help_next_prev_step: "Paso siguiente/anterior",
+    help_scroll_diff: "Desplazar diff",
+    help_half_page: "Desplazar media página",
+    help_top_bottom: "Inicio/final del diff",
+    help_cycle_panes: "Rotar paneles",
+    help_move_panes: "Moverse entre paneles",
+    help_sections: "Sección anterior/siguiente (lineal)",
+    help_mark_advance: "Marcar paso y avanzar",
+    help_toggle_reviewed: "Alternar paso revisado",
+    help_hunk_cursor: "Seleccionar fragmento anterior/siguiente",
+    help_file_cursor: "Ir al archivo anterior/siguiente",
+    help_toggle_hunk: "Alternar fragmento revisado",
+    help_visual_select: "Seleccionar líneas del diff (ampliar con desplazamiento)",
+    help_line_cursor: "Alternar un cursor de línea movido con desplazamiento",
+    help_quote_selection: "Citar las líneas seleccionadas en el chat",
+    help_yank: "Copiar líneas o fragmento (y en el chat: última respuesta)",
+    help_regenerate: "Regenerar con instrucciones",
+    help_regenerate_summary: "Reescribir el resumen de este paso",
+    help_persona: "Cambiar el enfoque de la revisión",
+    help_search_all: "Buscar en todos los pasos (Tab cambia desde /)",
+    help_find_step: "Ir a un paso por título o archivo",
+    help_usage: "Uso de tokens y coste",
+    help_split: "Dividir paso",
+    help_step_order: "Orden narrativo/por archivo",
+    help_move_step: "Bajar/subir paso (lista)",
+    help_merge_step: "Unir paso con el anterior (lista)",
+    help_split_hunks: "Mover fragmentos a un paso nuevo (lista)",
+    help_move_hunk: "Mover fragmento a otro paso (diff)",
+    help_file_view: "Todos los fragmentos del archivo seleccionado (diff)",
+    help_priority_filter: "Ocultar pasos menores / no críticos (lista)",
+    help_related: "Saltar a un paso relacionado",
+    help_quick_actions: "Acciones rápidas sobre el fragmento actual",
+    help_feedback: "Valorar este paso (se guarda localmente)",
+    help_note: "Añadir o quitar notas de este paso",
+    help_flag: "Señalar un problema en este paso o en el fragmento elegido",
+    help_post_review: "Publicar notas como comentarios en el PR",
+    help_parts: "Cambiar de parte (revisiones divididas)",
+    help_chat: "Escribir en el chat",
+    help_whole_change_chat: "Preguntar por todo el cambio, o volver al paso",
+    help_follow_ups: "Hacer una pregunta sugerida",
+    help_zoom: "Alternar ampliación",
+    help_fold: "Plegar el fragmento seleccionado, o todos los de su archivo",
+    help_fold_all: "Desplegar o plegar todos los fragmentos del paso",
+    help_file_tree: "Mostrar u ocultar el árbol de archivos",
+    help_open_browser: "Abrir PR/archivo en el navegador",
+    help_mouse_capture: "Alternar captura del ratón",
+    help_export_markdown: "Exportar informe Markdown",
+    help_export_html: "Exportar HTML",
+    help_export_json: "Exportar recorrido en JSON",
+    help_record: "Registrar revisión como nota git",
+    help_start_search: "Iniciar búsqueda",
+    help_next_prev_match: "Coincidencia sig./ant.",
+    help_clear_search: "Limpiar búsqueda",
+    help_close: "Pulsa cualquier tecla para cerrar",
+
+    loading_title: " Generando recorrido ",
+    loading_cancel: "Pulsa Ctrl+C para cancelar",
+    error_title: " Error ",
+    error_retry: "Pulsa 'r' para reintentar o 'q' para salir",
+    too_small_title: "Terminal demasiado pequeña",
+    too_small_need: "Se necesita al menos",
+    too_small_now: "ahora",
+
+    pane_steps: "Pasos",
+    pane_chat: "Chat",
+    pane_diff: "Diff",
+    pane_files: "Archivos",
+    lines: "líneas",
+    hunks: "fragmentos",
+    lines_selected: "líneas seleccionadas",
+    generating: "Generando…",
+    progress_steps: "pasos",
+    progress_tokens: "tokens",
+    retrying_in: "reintentando en",
+    no_step_selected: "Ningún paso seleccionado",
+    related_label: "Relacionado:",
+    chat_context: "contexto:",
+    chat_trimmed: "mensajes antiguos recortados",
+    related_step: "paso",
+    related_jump: "r para saltar",
+    no_diff_content: "Sin contenido de diff",
+    chat_placeholder: "Pulsa 'i' para hacer una pregunta",
+    thinking: "Pensando...",
+    splitting_step: "Dividiendo paso...",
+    complete_title: "✓ ¡Recorrido completado!",
+    complete_all_reviewed: "Se han revisado todos los pasos.",
+    complete_steps: "Pasos revisados",
+    complete_time: "Tiempo dedicado",
+    complete_tokens: "Tokens",
+    complete_no_flags: "ninguna",
+    complete_export: "exportar Markdown",
+    complete_copy: "copiar resumen",
+    complete_record: "registrar como nota git",
+    complete_back: "volver a los pasos",
+
+    setup_subtitle: "Recorrido de revisión de código guiado por IA",
+    setup_mode: "Modo",
+    setup_review: "Revisión",
+    setup_review_desc: "Señala posibles problemas",
+    setup_walkthrough: "Recorrido",
+    setup_walkthrough_desc: "Describe los cambios",
+    setup_api_key: "Clave de API",
+    setup_no_key: "No se encontró ninguna clave de API",
+    setup_start: "empezar",
+    setup_switch: "cambiar",
+    setup_quit: "salir",
+    key_from_env: "del entorno",
+    key_saved: "guardada",
+    key_entered: "introducida",
+    key_session: "solo esta sesión",
+    key_not_required: "no requiere clave",
+
+    files_title: " Archivos a revisar ",
+    files_selected: "archivos seleccionados",
+    files_hunks: "fragmentos",
+    files_skipped: "archivos generados/externos omitidos",
+    files_include_skipped: "incluirlos",
+    files_toggle: "marcar",
+    files_all: "todos",
+    files_none: "ninguno",
+    files_invert: "invertir",
+    files_back: "volver",
+
+    split_title: " Diff grande ",
+    split_changed_lines: "líneas cambiadas",
+    split_limit: "límite",
+    split_by_directory: "Dividir en partes por directorio",
+    split_by_model: "Dividir en partes sugeridas por el modelo",
+    split_whole: "Revisar como un solo recorrido",
+    split_partitioning: "Pidiendo al modelo una división...",
+    parts_title: " Partes ",
+    parts_part: "Parte",
+    parts_reviewed: "pasos revisados",
+    parts_not_generated: "aún sin generar",
+    parts_open: "abrir",
+
+    quick_title: " Acciones rápidas ",
+    quick_hunk: "Fragmento",
+    quick_explain: "Explicar este fragmento",
+    quick_risks: "Enumerar riesgos",
+    quick_tests: "Sugerir pruebas",
+    quick_simplify: "Sugerir simplificaciones",
+    quick_run: "preguntar",
+
+    hunk_split_title: " Mover fragmentos a un paso nuevo ",
+    hunk_split_step: "Paso",
+    hunk_split_pick: "elegir",
+    hunk_split_confirm: "dividir",
+    hunk_move_title: " Mover fragmento a un paso ",
+    hunk_move_confirm: "mover",
+    feedback_title: " Valorar este paso ",
+    feedback_useful: "Útil",
+    feedback_not_useful: "No útil",
+    feedback_comment: "Comentario (opcional):",
+    feedback_switch: "cambiar",
+    feedback_save: "guardar",
+    note_title: " Notas sobre este paso ",
+    note_empty: "Aún no hay notas.",
+    note_new: "Nueva nota:",
+    note_hint: "Las notas se incluyen al exportar y se publican en el pull request con R.",
+    note_add: "añadir",
+    note_newline: "nueva línea",
+    note_delete: "borrar la última",
+    flag_title: " Señales en este paso ",
+    flag_empty: "Aún no hay señales.",
+    flag_severity: "Gravedad:",
+    flag_new: "Problema (opcional):",
+    flag_cycle: "gravedad",
+    flag_add: "señalar",
+    complete_flags: "Señales",
+    regenerate_title: " Regenerar recorrido ",
+    regenerate_intro: "Instrucciones para el nuevo recorrido (opcional):",
+    regenerate_hint: "p. ej. \"céntrate en los cambios de concurrencia\" o \"menos pasos, más \
+                      grandes\". Los pasos con los mismos fragmentos conservan su chat y notas.",
+    regenerate_run: "regenerar",
+    summary_title: " Reescribir resumen del paso ",
+    summary_intro: "Instrucciones para el nuevo resumen (opcional):",
+    summary_hint: "p. ej. \"explícalo con más detalle\" o \"céntrate en el manejo de \
+                   errores\". El resto del recorrido no cambia.",
+    follow_ups_label: "Preguntas sugeridas:",
+    chat_whole_change: "todo el cambio",
+    chat_whole_change_hint: "Aquí las preguntas tratan del cambio completo. Cada una se envía con \
+                             el resumen de todos los pasos.",
+    global_search_title: " Buscar en todos los pasos ",
+    global_search_none: "Sin coincidencias",
+    global_search_truncated: "Solo se muestran los primeros resultados",
+    global_search_jump: "ir",
+    global_search_this_step: "este paso",
+    step_finder_title: " Ir a un paso ",
+    search_all_steps: "todos los pasos",
+    search_regex: "regex",
+    usage_title: " Uso ",
+    usage_generation: "Recorrido",
+    usage_chat: "Chat",
+    usage_total: "Total",
+    usage_model: "Modelo",
+    usage_prices: "por millón de tokens",
+    usage_no_prices: "No se conocen los precios de este modelo. Define api.pricing para \
+                      estimar el coste.",
+    recovery_title: " ¿Restaurar sesión? ",
+    recovery_intro: "docent no se cerró correctamente la última vez. ¿Continuar donde lo dejaste?",
+    recovery_saved: "Guardada",
+    recovery_progress: "pasos revisados",
+    recovery_other_directory: "Guardada en otro directorio. Al restaurarla, las búsquedas \
+                               de archivos, las notas de git y los chats usan este.",
+    recovery_restore: "restaurar",
+    recovery_discard: "descartar",
+    diff_update_title: " El diff cambió ",
+    diff_update_intro: "El diff cambió desde que se generó este recorrido. ¿Regenerarlo?",
+    diff_update_reviewed: "Los hunks revisados que no cambiaron siguen revisados.",
+    diff_update_regenerate: "regenerar",
+    diff_update_keep: "seguir revisando",
+    toast_no_answer: "No hay respuesta que copiar",
+    toast_copied: "Copiado",
+    toast_line: "línea",
+    toast_lines: "líneas",
+    toast_no_selection: "No hay líneas del diff seleccionadas",
+    toast_no_retry: "No hay pregunta que reintentar",
+    toast_clear_input: "Vacía primero el campo del chat",
+    toast_no_edit: "No hay pregunta que editar",
+    toast_chat_context: "Contexto del chat",
+    toast_persona: "Enfoque",
+    toast_persona_regenerate: "regenera con él",
+    toast_chat_whole_change: "Chat: todo el cambio",
+    toast_chat_step: "Chat: este paso",
+    toast_chat_cancelled: "Respuesta del chat cancelada",
+    toast_generation_cancelled: "Generación cancelada",
+    toast_steps_kept: "pasos conservados",
+    toast_wait_answer: "Espera a que termine la respuesta actual",
+    toast_session_restored: "Sesión restaurada",
+    toast_feedback_saved: "Valoración guardada",
+    toast_feedback_failed: "Valoración no guardada",
+    toast_note_added: "Nota añadida",
+    toast_flagged: "Marcado como",
+    toast_wait_generation: "Espera a que termine la generación",
+    toast_regenerating: "Regenerando el recorrido",
+    toast_regenerating_diff: "Regenerando el recorrido para el diff actualizado",
+    toast_wait_request: "Espera a que termine la petición actual",
+    toast_rewriting_summary: "Reescribiendo el resumen del paso",
+    toast_export_written: "Exportación guardada en",
+    toast_export_failed: "La exportación falló",
+    toast_no_pull_request: "Las notas solo se pueden publicar en un pull request de GitHub",
+    toast_no_notes: "No hay notas que publicar; añade una con",
+    toast_posting_review: "Publicando la revisión...",
+    toast_review_posted: "Revisión publicada",
+    toast_comments: "comentarios",
+    toast_post_failed: "Error al publicar la revisión",
+    toast_recording_review: "Registrando la revisión...",
+    toast_review_recorded: "Revisión registrada en HEAD",
+    toast_report: "informe",
+    toast_record_failed: "Error al registrar la revisión",
+    toast_no_forge: "Este diff no tiene enlace a la forja",
+    toast_opened: "Abierto",
+    toast_parts_busy: "Las partes se pueden cambiar cuando terminen la generación y las peticiones \
+                       pendientes",
+    toast_part: "Parte",
+    toast_of: "de",
+    toast_mouse_on: "Captura del ratón activada",
+    toast_mouse_off: "Captura del ratón desactivada: selecciona texto con el ratón, pulsa",
+    toast_mouse_restore: "para restaurarla",
+    toast_sort_busy: "Los pasos se pueden reordenar cuando terminen la generación y las peticiones \
+                      pendientes",
+    toast_steps_in: "Pasos en",
+    toast_rearrange_busy: "Los pasos se pueden reorganizar cuando terminen la generación y las \
+                           peticiones pendientes",
+    toast_merged: "Unido al paso anterior",
+    toast_merge_first: "El primer paso no tiene un paso anterior al que unirse",
+    toast_merge_sub_steps: "Los pasos con subpasos no se pueden unir",
+    toast_split_one_hunk: "Solo se pueden dividir pasos con más de un hunk",
+    toast_split_moved: "Movido al nuevo paso",
+    toast_split_pick: "Elige algunos hunks, pero no todos",
+    toast_move_only_hunk: "El único hunk de un paso no se puede mover; une el paso en su lugar",
+    toast_hunk_moved: "Hunk movido al paso",
+    toast_no_related: "No hay pasos relacionados",
+    toast_rate_limited: "Límite de peticiones alcanzado",
+    toast_overloaded: "API sobrecargada",
+    toast_retrying_in: "reintentando en",
+    toast_attempt: "intento",
+    toast_walkthrough_ready: "Recorrido listo",
+    toast_loaded_from_cache: "Recorrido cargado de la caché",
+    toast_thread_step: "paso",
+    toast_thread_whole_change: "todo el cambio",
+    toast_chat_ready: "Respuesta del chat lista en",
+    toast_chat_error: "Error del chat en",
+    toast_chat_summary_failed: "No se pudo resumir el inicio del chat en",
+    toast_chat_summary_left_out: "así que se omitió",
+    toast_summary_rewritten: "Resumen reescrito para el paso",
+    toast_summary_failed: "Falló el resumen del paso",
+    toast_hook: "Hook",
+    toast_hook_passed: "correcto",
+    toast_hook_failed: "falló",
+    toast_steps: "pasos",
+    toast_step: "Paso",
+    toast_marked_reviewed: "marcado como revisado",
+    toast_marked_unreviewed: "marcado como no revisado",
+    toast_walkthrough_complete: "Recorrido completo. Todos los pasos revisados.",
+    toast_response_ready: "Respuesta lista para",
+    toast_parts_fallback: "No se pudieron sugerir partes, se divide por directorio",
+    toast_showing: "Mostrando",
+    toast_select_file: "Selecciona al menos un archivo",
+
+    severity_blocker: "bloqueante",
+    severity_blockers: "bloqueantes",
+    severity_question: "pregunta",
+    severity_questions: "preguntas",
+    severity_nit: "detalle",
+    severity_nits: "detalles",
+    order_narrative: "orden narrativo",
+    order_file: "orden por archivo",
+    filter_all: "todos los pasos",
+    filter_hide_minor: "pasos menores ocultos",
+    filter_critical_only: "solo pasos críticos",
+    context_step: "paso",
+    context_overview: "resumen",
+    context_neighbors: "vecinos",
+    context_surrounding: "alrededores",
+    context_full_file: "archivo completo",
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{Flag, Step};
+
+    #[test]
+    fn test_flag_tally() {
+        let flag = |severity| Flag {
+            severity,
+            text: String::new(),
+            location: None,
+        };
+        let mut walkthrough = Walkthrough::default();
+        walkthrough.steps.push(Step::test("1", "src/a.rs"));
+        walkthrough.steps[0].flags = vec![flag(Severity::Nit), flag(Severity::Blocker)];
+        walkthrough.steps[0].flags.push(flag(Severity::Nit));
+        assert_eq!(EN.flag_tally(&walkthrough), "1 blocker, 2 nits");
+        assert_eq!(ES.flag_tally(&walkthrough), "1 bloqueante, 2 detalles");
+        assert_eq!(ES.flag_tally(&Walkthrough::default()), "");
+    }
+
+    #[test]
+    fn test_from_locale() {
+        assert_eq!(Language::from_locale("es_ES.UTF-8"), Some(Language::Es));
+        assert_eq!(Language::from_locale("en-US"), Some(Language::En));
+        assert_eq!(Language::from_locale("C"), None);
+    }
+}
@@ -5,17 +5,14 @@ use tui_textarea::{CursorMove, Input};
 use crate::app::{App, SetupFocus};
 use crate::constants::{DIVIDER_HIT_ZONE, HELP_BAR_HEIGHT};
 use crate::editor::VimInputMode;
-use crate::layout::{Divider, Pane};
+use crate::keymap::{Action, KeyPress};
+use crate::layout::{Divider, FocusDirection, Pane};
 use crate::model::ReviewMode;
+use crate::onboarding::OnboardingStep;
+use crate::quick_actions::QuickAction;
 use crate::session::SessionState;
 use crate::settings::ApiKeySource;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum PendingKey {
-    None,
-    G, // Waiting for second 'g' for gg
-}
-
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum VimPending {
     None,
@@ -24,24 +21,25 @@ enum VimPending {
 }
 
 pub struct InputHandler {
-    pending: PendingKey,
+    /// First key of a multi-key binding, waiting for the next
+    pending: Option<KeyPress>,
     vim_pending: VimPending,
 }
 
 impl InputHandler {
     pub fn new() -> Self {
         Self {
-            pending: PendingKey::None,
+            pending: None,
             vim_pending: VimPending::None,
         }
     }
 
     pub fn handle_key(&mut self, key: KeyEvent, app: &mut App, viewport_height: usize) {
-        // Handle Ctrl+C for quit (requires confirmation)
+        // Handle Ctrl+C: cancel a request in flight, otherwise quit (requires confirmation)
         if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
             if app.quit_pending {
                 app.quit();
-            } else {
+            } else if !app.cancel_request() {
                 app.quit_pending = true;
             }
             return;
@@ -56,6 +54,12 @@ impl InputHandler {
             return;
         }
 
+        // Usage popup: any key dismisses it
+        if app.show_usage {
+            app.show_usage = false;
+            return;
+        }
+
         // Handle error state
         if app.session.is_error() {
             match key.code {
@@ -66,12 +70,144 @@ impl InputHandler {
             return;
         }
 
+        // Offer to restore a session that crashed, before anything else
+        if app.recovery_offer.is_some() {
+            match key.code {
+                KeyCode::Enter => app.restore_session(),
+                KeyCode::Esc | KeyCode::Char('n') => app.discard_recovery(),
+                _ => {}
+            }
+            return;
+        }
+
+        // First-run onboarding takes over the setup screen
+        if app.onboarding.is_some() {
+            self.handle_onboarding_input(key, app);
+            return;
+        }
+
+        // Offer to split an oversized diff, after the file checklist
+        if app.split_offer.is_some() {
+            self.handle_split_offer_input(key, app);
+            return;
+        }
+
+        // File checklist between setup and generation
+        if app.file_selection.is_some() {
+            self.handle_file_selection_input(key, app);
+            return;
+        }
+
         // Handle setup screen
         if matches!(app.session.state, SessionState::Setup) {
             self.handle_setup_input(key, app);
             return;
         }
 
+        // Offer to regenerate for a changed diff under --watch
+        if app.offered_diff_update().is_some() {
+            match key.code {
+                KeyCode::Enter => app.accept_diff_update(),
+                KeyCode::Esc | KeyCode::Char('n') => app.diff_update = None,
+                _ => {}
+            }
+            return;
+        }
+
+        // Parts picker overlay
+        if app.parts_picker.is_some() {
+            self.handle_parts_picker_input(key, app);
+            return;
+        }
+
+        // Destination step for the selected hunk
+        if app.hunk_move.is_some() {
+            self.handle_hunk_move_input(key, app);
+            return;
+        }
+
+        // Hunks to split off the current step
+        if app.hunk_split.is_some() {
+            self.handle_hunk_split_input(key, app);
+            return;
+        }
+
+        // Quick actions menu for the current hunk
+        if app.quick_actions.is_some() {
+            self.handle_quick_actions_input(key, app);
+            return;
+        }
+
+        // Note editor for the current step
+        if app.note_editor.is_some() {
+            self.handle_note_input(key, app);
+            return;
+        }
+
+        // Flag dialog for the current step or hunk
+        if app.flag_editor.is_some() {
+            self.handle_flag_input(key, app);
+            return;
+        }
+
+        // Instructions for regenerating the walkthrough
+        if app.regenerate_prompt.is_some() {
+            self.handle_regenerate_input(key, app);
+            return;
+        }
+
+        // Instructions for rewriting the current step's summary
+        if app.summary_prompt.is_some() {
+            self.handle_summary_input(key, app);
+            return;
+        }
+
+        // Feedback dialog for the current step
+        if app.feedback.is_some() {
+            self.handle_feedback_input(key, app);
+            return;
+        }
+
+        // Walkthrough-wide search dialog
+        if app.global_search.is_some() {
+            self.handle_global_search_input(key, app);
+            return;
+        }
+
+        // Every hunk of one file
+        if let Some(view) = app.file_view.as_mut() {
+            let last = view.hunks.len().saturating_sub(1);
+            match key.code {
+                KeyCode::Char('j') | KeyCode::Down => view.selected = (view.selected + 1).min(last),
+                KeyCode::Char('k') | KeyCode::Up => view.selected = view.selected.saturating_sub(1),
+                KeyCode::Char('g') | KeyCode::Home => view.selected = 0,
+                KeyCode::Char('G') | KeyCode::End => view.selected = last,
+                KeyCode::Enter => app.jump_to_file_view_hunk(),
+                KeyCode::Esc | KeyCode::Char('F') | KeyCode::Char('q') => app.file_view = None,
+                _ => {}
+            }
+            return;
+        }
+
+        // Jump-to-step dialog
+        if app.step_finder.is_some() {
+            self.handle_step_finder_input(key, app);
+            return;
+        }
+
+        // The completion screen once every step is reviewed
+        if app.session.is_walkthrough_complete() {
+            match key.code {
+                KeyCode::Char('e') => app.export_markdown(),
+                KeyCode::Char('c') => app.copy_review_summary(),
+                KeyCode::Char('T') => app.record_review(),
+                KeyCode::Char('q') => app.quit(),
+                KeyCode::Esc => app.leave_completion(),
+                _ => {}
+            }
+            return;
+        }
+
         // Handle search input mode
         if app.search.active {
             self.handle_search_input(key, app);
@@ -88,6 +224,102 @@ impl InputHandler {
         self.handle_normal_mode(key, app, viewport_height);
     }
 
+    /// Handles a bracketed paste: the whole clipboard arrives as one string
+    /// instead of being replayed key by key (which would send on each newline).
+    pub fn handle_paste(&mut self, text: String, app: &mut App) {
+        app.show_help = false;
+        app.quit_pending = false;
+
+        // Some terminals send bare carriage returns for newlines
+        let text = text.replace("\r\n", "\n").replace('\r', "\n");
+
+        if app.recovery_offer.is_some() || app.offered_diff_update().is_some() {
+            return;
+        }
+
+        if let Some(onboarding) = app.onboarding.as_mut() {
+            if onboarding.step == OnboardingStep::ApiKey {
+                onboarding.api_key.push_str(text.trim());
+                onboarding.error = None;
+            }
+            return;
+        }
+
+        if matches!(app.session.state, SessionState::Setup) {
+            if app.file_selection.is_some() || app.split_offer.is_some() {
+                return;
+            }
+            let is_editable = matches!(
+                app.session.api_key_source,
+                ApiKeySource::Missing | ApiKeySource::UserEntry
+            );
+            if app.setup_focus == SetupFocus::ApiKey && is_editable {
+                app.session.api_key_input.push_str(text.trim());
+                if !app.session.api_key_input.is_empty() {
+                    app.session.api_key_source = ApiKeySource::UserEntry;
+                }
+            }
+            return;
+        }
+
+        if !matches!(app.session.state, SessionState::Ready) {
+            return;
+        }
+
+        if let Some(dialog) = app.feedback.as_mut() {
+            dialog.comment.push_str(&text.replace('\n', " "));
+            return;
+        }
+
+        if let Some(note_editor) = app.note_editor.as_mut() {
+            note_editor.editor.textarea.insert_str(text);
+            return;
+        }
+
+        if let Some(flag_editor) = app.flag_editor.as_mut() {
+            flag_editor.editor.textarea.insert_str(text);
+            return;
+        }
+
+        if let Some(editor) = app.regenerate_prompt.as_mut() {
+            editor.textarea.insert_str(text);
+            return;
+        }
+
+        if let Some(editor) = app.summary_prompt.as_mut() {
+            editor.textarea.insert_str(text);
+            return;
+        }
+
+        if let Some(search) = app.global_search.as_mut() {
+            search.input.extend(text.chars().filter(|c| *c != '\n'));
+            app.update_global_search();
+            return;
+        }
+
+        if let Some(finder) = app.step_finder.as_mut() {
+            finder.input.extend(text.chars().filter(|c| *c != '\n'));
+            app.update_step_finder();
+            return;
+        }
+
+        if app.search.active {
+            for c in text.chars().filter(|c| *c != '\n') {
+                app.search.push_char(c);
+            }
+            app.execute_search_incremental();
+            return;
+        }
+
+        // Pasting anywhere else goes into the chat input
+        if app.layout.active_pane != Pane::Chat {
+            app.set_active_pane(Pane::Chat);
+        }
+        app.editor.vim_mode = VimInputMode::Insert;
+        app.exit_chat_scrollback();
+        app.editor.textarea.insert_str(text);
+    }
+
     fn handle_search_input(&mut self, key: KeyEvent, app: &mut App) {
         match key.code {
             KeyCode::Esc => {
@@ -97,6 +329,24 @@ impl InputHandler {
                 // Finalize search and exit input mode
                 app.execute_search();
             }
+            // Widen the search to every step
+            KeyCode::Tab => {
+                let input = std::mem::take(&mut app.search.input);
+                app.search.clear();
+                app.open_global_search(input);
+            }
+            KeyCode::Char('r') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                app.search.toggle_regex();
+                app.execute_search_incremental();
+            }
+            KeyCode::Up => {
+                app.search.history_prev();
+                app.execute_search_incremental();
+            }
+            KeyCode::Down => {
+                app.search.history_next();
+                app.execute_search_incremental();
+            }
             KeyCode::Backspace => {
                 app.search.pop_char();
                 app.execute_search_incremental();
@@ -109,8 +359,313 @@ impl InputHandler {
         }
     }
 
+    fn handle_global_search_input(&mut self, key: KeyEvent, app: &mut App) {
+        let Some(search) = app.global_search.as_mut() else {
+            return;
+        };
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        match key.code {
+            KeyCode::Esc => app.global_search = None,
+            KeyCode::Enter => app.jump_to_global_match(),
+            KeyCode::Tab => app.search_current_step(),
+            KeyCode::Down => search.select_next(),
+            KeyCode::Up => search.select_prev(),
+            KeyCode::Char('n') if ctrl => search.select_next(),
+            KeyCode::Char('p') if ctrl => search.select_prev(),
+            KeyCode::Backspace => {
+                search.input.pop();
+                app.update_global_search();
+            }
+            KeyCode::Char(c) if !ctrl => {
+                search.input.push(c);
+                app.update_global_search();
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_step_finder_input(&mut self, key: KeyEvent, app: &mut App) {
+        let Some(finder) = app.step_finder.as_mut() else {
+            return;
+        };
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        match key.code {
+            KeyCode::Esc => app.step_finder = None,
+            KeyCode::Enter => app.jump_to_found_step(),
+            KeyCode::Down => finder.select_next(),
+            KeyCode::Up => finder.select_prev(),
+            KeyCode::Char('n') if ctrl => finder.select_next(),
+            KeyCode::Char('p') if ctrl => finder.select_prev(),
+            KeyCode::Backspace => {
+                finder.input.pop();
+                app.update_step_finder();
+            }
+            KeyCode::Char(c) if !ctrl => {
+                finder.input.push(c);
+                app.update_step_finder();
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_onboarding_input(&mut self, key: KeyEvent, app: &mut App) {
+        let Some(onboarding) = app.onboarding.as_mut() else {
+            return;
+        };
+
+        let finished = match (onboarding.step, key.code) {
+            (_, KeyCode::Enter) => onboarding.advance(),
+            (_, KeyCode::Esc) => {
+                onboarding.back();
+                false
+            }
+
+            // API key entry: typing goes into the key; Tab/arrows pick where it's kept
+            (
+                OnboardingStep::ApiKey,
+                KeyCode::Tab | KeyCode::BackTab | KeyCode::Up | KeyCode::Down,
+            ) => {
+                onboarding.select(1);
+                false
+            }
+            (OnboardingStep::ApiKey, KeyCode::Backspace) => {
+                onboarding.api_key.pop();
+                false
+            }
+            (OnboardingStep::ApiKey, KeyCode::Char(c)) => {
+                onboarding.api_key.push(c);
+                onboarding.error = None;
+                false
+            }
+
+            // Option lists
+            (_, KeyCode::Char('j') | KeyCode::Down | KeyCode::Tab) => {
+                onboarding.select(1);
+                false
+            }
+            (_, KeyCode::Char('k') | KeyCode::Up | KeyCode::BackTab) => {
+                onboarding.select(-1);
+                false
+            }
+            (_, KeyCode::Char('q')) => {
+                app.quit();
+                return;
+            }
+            _ => false,
+        };
+
+        if finished {
+            app.finish_onboarding();
+        }
+    }
+
+    fn handle_file_selection_input(&mut self, key: KeyEvent, app: &mut App) {
+        let Some(selection) = app.file_selection.as_mut() else {
+            return;
+        };
+        match key.code {
+            KeyCode::Char('j') | KeyCode::Down => selection.move_cursor(1),
+            KeyCode::Char('k') | KeyCode::Up => selection.move_cursor(-1),
+            KeyCode::Char('g') | KeyCode::Home => selection.move_cursor(isize::MIN),
+            KeyCode::Char('G') | KeyCode::End => selection.move_cursor(isize::MAX),
+            KeyCode::Char(' ') => selection.toggle(),
+            KeyCode::Char('a') => selection.select_all(true),
+            KeyCode::Char('n') => selection.select_all(false),
+            KeyCode::Char('i') => selection.invert(),
+            KeyCode::Char('d') => selection.include_denied(),
+            KeyCode::Enter => app.confirm_file_selection(),
+            KeyCode::Esc => app.file_selection = None,
+            KeyCode::Char('q') => app.quit(),
+            _ => {}
+        }
+    }
+
+    fn handle_split_offer_input(&mut self, key: KeyEvent, app: &mut App) {
+        let Some(offer) = app.split_offer.as_mut() else {
+            return;
+        };
+        if offer.pending {
+            if key.code == KeyCode::Char('q') {
+                app.quit();
+            }
+            return;
+        }
+        match key.code {
+            KeyCode::Char('j') | KeyCode::Down => offer.move_cursor(1),
+            KeyCode::Char('k') | KeyCode::Up => offer.move_cursor(-1),
+            KeyCode::Enter => app.confirm_split(),
+            KeyCode::Esc => app.split_offer = None,
+            KeyCode::Char('q') => app.quit(),
+            _ => {}
+        }
+    }
+
+    fn handle_parts_picker_input(&mut self, key: KeyEvent, app: &mut App) {
+        let Some(cursor) = app.parts_picker else {
+            return;
+        };
+        let last = app.session.parts.len().saturating_sub(1);
+        match key.code {
+            KeyCode::Char('j') | KeyCode::Down => app.parts_picker = Some((cursor + 1).min(last)),
+            KeyCode::Char('k') | KeyCode::Up => app.parts_picker = Some(cursor.saturating_sub(1)),
+            KeyCode::Enter => app.switch_part(cursor),
+            KeyCode::Esc | KeyCode::Char('P') | KeyCode::Char('q') => app.parts_picker = None,
+            _ => {}
+        }
+    }
+
+    fn handle_hunk_move_input(&mut self, key: KeyEvent, app: &mut App) {
+        let last = app.session.walkthrough.step_count().saturating_sub(1);
+        let Some(pick) = app.hunk_move.as_mut() else {
+            return;
+        };
+        match key.code {
+            KeyCode::Char('j') | KeyCode::Down => pick.cursor = (pick.cursor + 1).min(last),
+            KeyCode::Char('k') | KeyCode::Up => pick.cursor = pick.cursor.saturating_sub(1),
+            KeyCode::Enter => app.confirm_hunk_move(),
+            KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('m') => app.hunk_move = None,
+            _ => {}
+        }
+    }
+
+    fn handle_hunk_split_input(&mut self, key: KeyEvent, app: &mut App) {
+        let Some(split) = app.hunk_split.as_mut() else {
+            return;
+        };
+        let last = split.chosen.len().saturating_sub(1);
+        match key.code {
+            KeyCode::Char('j') | KeyCode::Down => split.cursor = (split.cursor + 1).min(last),
+            KeyCode::Char('k') | KeyCode::Up => split.cursor = split.cursor.saturating_sub(1),
+            KeyCode::Char(' ') => {
+                if let Some(chosen) = split.chosen.get_mut(split.cursor) {
+                    *chosen = !*chosen;
+                }
+            }
+            KeyCode::Enter => app.confirm_hunk_split(),
+            KeyCode::Esc | KeyCode::Char('q') => app.hunk_split = None,
+            _ => {}
+        }
+    }
+
+    fn handle_quick_actions_input(&mut self, key: KeyEvent, app: &mut App) {
+        let Some(menu) = app.quick_actions.as_mut() else {
+            return;
+        };
+        match key.code {
+            KeyCode::Char('j') | KeyCode::Down => menu.move_cursor(1),
+            KeyCode::Char('k') | KeyCode::Up => menu.move_cursor(-1),
+            KeyCode::Enter => {
+                let action = menu.selected();
+                app.run_quick_action(action);
+            }
+            KeyCode::Char(c) => match QuickAction::from_key(c) {
+                Some(action) => app.run_quick_action(action),
+                None if c == 'a' || c == 'q' => app.quick_actions = None,
+                None => {}
+            },
+            KeyCode::Esc => app.quick_actions = None,
+            _ => {}
+        }
+    }
+
+    fn handle_feedback_input(&mut self, key: KeyEvent, app: &mut App) {
+        let Some(dialog) = app.feedback.as_mut() else {
+            return;
+        };
+        match key.code {
+            KeyCode::Left | KeyCode::Right | KeyCode::Tab | KeyCode::BackTab => {
+                dialog.rating = dialog.rating.toggle();
+            }
+            KeyCode::Char(c) => dialog.comment.push(c),
+            KeyCode::Backspace => {
+                dialog.comment.pop();
+            }
+            KeyCode::Enter => app.submit_feedback(),
+            KeyCode::Esc => app.feedback = None,
+            _ => {}
+        }
+    }
+
+    fn handle_note_input(&mut self, key: KeyEvent, app: &mut App) {
+        let Some(note_editor) = app.note_editor.as_mut() else {
+            return;
+        };
+        match key.code {
+            KeyCode::Esc => app.note_editor = None,
+            // Enter saves, Shift+Enter for newline
+            KeyCode::Enter if key.modifiers.contains(KeyModifiers::SHIFT) => {
+                note_editor.editor.textarea.insert_newline();
+            }
+            KeyCode::Enter => app.save_note(),
+            KeyCode::Char('d') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                app.delete_last_note();
+            }
+            _ => {
+                note_editor.editor.textarea.input(Input::from(key));
+            }
+        }
+    }
+
+    fn handle_flag_input(&mut self, key: KeyEvent, app: &mut App) {
+        let Some(flag_editor) = app.flag_editor.as_mut() else {
+            return;
+        };
+        match key.code {
+            KeyCode::Esc => app.flag_editor = None,
+            KeyCode::Tab => flag_editor.severity = flag_editor.severity.next(),
+            // Enter saves, Shift+Enter for newline
+            KeyCode::Enter if key.modifiers.contains(KeyModifiers::SHIFT) => {
+                flag_editor.editor.textarea.insert_newline();
+            }
+            KeyCode::Enter => app.save_flag(),
+            KeyCode::Char('d') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                app.delete_last_flag();
+            }
+            _ => {
+                flag_editor.editor.textarea.input(Input::from(key));
+            }
+        }
+    }
+
+    fn handle_regenerate_input(&mut self, key: KeyEvent, app: &mut App) {
+        let Some(editor) = app.regenerate_prompt.as_mut() else {
+            return;
+        };
+        match key.code {
+            KeyCode::Esc => app.regenerate_prompt = None,
+            // Enter regenerates, Shift+Enter for newline
+            KeyCode::Enter if key.modifiers.contains(KeyModifiers::SHIFT) => {
+                editor.textarea.insert_newline();
+            }
+            KeyCode::Enter => app.regenerate(),
+            _ => {
+                editor.textarea.input(Input::from(key));
+            }
+        }
+    }
+
+    fn handle_summary_input(&mut self, key: KeyEvent, app: &mut App) {
+        let Some(editor) = app.summary_prompt.as_mut() else {
+            return;
+        };
+        match key.code {
+            KeyCode::Esc => app.summary_prompt = None,
+            // Enter rewrites, Shift+Enter for newline
+            KeyCode::Enter if key.modifiers.contains(KeyModifiers::SHIFT) => {
+                editor.textarea.insert_newline();
+            }
+            KeyCode::Enter => app.regenerate_summary(),
+            _ => {
+                editor.textarea.input(Input::from(key));
+            }
+        }
+    }
+
     fn handle_setup_input(&mut self, key: KeyEvent, app: &mut App) {
-        let api_key_focusable = app.session.api_key_source != ApiKeySource::EnvVar;
+        let api_key_focusable = !matches!(
+            app.session.api_key_source,
+            ApiKeySource::EnvVar | ApiKeySource::NotRequired
+        );
 
         match app.setup_focus {
             SetupFocus::Review => match key.code {
@@ -125,7 +680,7 @@ impl InputHandler {
                 KeyCode::Tab | KeyCode::BackTab if api_key_focusable => {
                     app.setup_focus = SetupFocus::ApiKey;
                 }
-                KeyCode::Enter => app.session.confirm_setup(),
+                KeyCode::Enter => app.confirm_setup(),
                 KeyCode::Char('q') => app.quit(),
                 _ => {}
             },
@@ -144,7 +699,7 @@ impl InputHandler {
                 KeyCode::Tab | KeyCode::BackTab if api_key_focusable => {
                     app.setup_focus = SetupFocus::ApiKey;
                 }
-                KeyCode::Enter => app.session.confirm_setup(),
+                KeyCode::Enter => app.confirm_setup(),
                 KeyCode::Char('q') => app.quit(),
                 _ => {}
             },
@@ -166,7 +721,7 @@ impl InputHandler {
                     KeyCode::Up => {
                         app.setup_focus = SetupFocus::Walkthrough;
                     }
-                    KeyCode::Enter => app.session.confirm_setup(),
+                    KeyCode::Enter => app.confirm_setup(),
                     KeyCode::Backspace if is_editable => {
                         app.session.api_key_input.pop();
                         if app.session.api_key_input.is_empty() {
@@ -188,6 +743,23 @@ impl InputHandler {
     }
 
     fn handle_chat_input(&mut self, key: KeyEvent, app: &mut App) {
+        // Esc stops a reply while it streams
+        if key.code == KeyCode::Esc && app.cancel_chat() {
+            return;
+        }
+
+        // Ctrl+t changes how much context the next question sends
+        if key.code == KeyCode::Char('t') && key.modifiers.contains(KeyModifiers::CONTROL) {
+            app.cycle_chat_context();
+            return;
+        }
+
+        // Ctrl+g switches between the step's conversation and the whole change's
+        if key.code == KeyCode::Char('g') && key.modifiers.contains(KeyModifiers::CONTROL) {
+            app.toggle_whole_change_chat();
+            return;
+        }
+
         // Tab/Shift+Tab always switches panes
         if key.code == KeyCode::Tab {
             app.set_active_pane(Pane::Diff);
@@ -202,6 +774,7 @@ impl InputHandler {
             match app.editor.vim_mode {
                 VimInputMode::Normal => self.handle_vim_normal(key, app),
                 VimInputMode::Insert => self.handle_vim_insert(key, app),
+                VimInputMode::Visual | VimInputMode::VisualLine => self.handle_vim_visual(key, app),
             }
         } else {
             // Non-vim: always in insert mode for the textarea
@@ -250,8 +823,33 @@ impl InputHandler {
             return;
         }
 
+        // While reading back through the chat, y copies the latest answer,
+        // r asks the last question again, and e takes it back to edit
+        if app.chat_scroll.in_scrollback() {
+            match key.code {
+                KeyCode::Char('y') => return app.yank_last_answer(),
+                KeyCode::Char('r') => return app.retry_last_question(),
+                KeyCode::Char('e') => return app.edit_last_question(),
+                _ => {}
+            }
+        }
+
         // Any other input exits scrollback mode and goes to textarea
         app.exit_chat_scrollback();
+
+        if app.editor.readline(key) {
+            return;
+        }
+
+        // Up on the first line and Down on the last recall sent questions
+        let recalled = match key.code {
+            KeyCode::Up => app.editor.recall_prev(),
+            KeyCode::Down => app.editor.recall_next(),
+            _ => false,
+        };
+        if recalled {
+            return;
+        }
         let input = Input::from(key);
         app.editor.textarea.input(input);
     }
@@ -261,11 +859,26 @@ impl InputHandler {
             return;
         }
 
+        // Zoom goes through the keymap; other keys here are the editor's own
+        let press = KeyPress::from(key);
+        let sequence = match self.pending.take() {
+            Some(first) => vec![first, press],
+            None => vec![press],
+        };
+        if app.keymap.actions(&sequence).contains(&Action::Zoom) {
+            app.layout.toggle_zoom();
+            return;
+        }
+        if app.keymap.starts(Action::Zoom, press) {
+            self.pending = Some(press);
+            return;
+        }
+
         match key.code {
             KeyCode::Esc => app.exit_chat_scrollback(),
             KeyCode::Enter => app.send_message(),
             KeyCode::Tab => app.set_active_pane(app.layout.active_pane.next()),
-            KeyCode::BackTab => app.set_active_pane(app.layout.active_pane.prev()),
+            KeyCode::BackTab => app.set_active_pane(app.layout.prev_pane()),
 
             // Chat scroll (Ctrl+n/p)
             KeyCode::Char('n') if key.modifiers.contains(KeyModifiers::CONTROL) => {
@@ -301,11 +914,17 @@ impl InputHandler {
                 app.editor.vim_mode = VimInputMode::Insert;
             }
 
+            // Visual mode
+            KeyCode::Char('v') => app.editor.start_visual(false),
+            KeyCode::Char('V') => app.editor.start_visual(true),
+
             // Motion commands
             KeyCode::Char('h') | KeyCode::Left => app.editor.textarea.move_cursor(CursorMove::Back),
             KeyCode::Char('l') | KeyCode::Right => {
                 app.editor.textarea.move_cursor(CursorMove::Forward)
             }
+            KeyCode::Up if app.editor.recall_prev() => {}
+            KeyCode::Down if app.editor.recall_next() => {}
             KeyCode::Char('j') | KeyCode::Down => app.editor.textarea.move_cursor(CursorMove::Down),
             KeyCode::Char('k') | KeyCode::Up => app.editor.textarea.move_cursor(CursorMove::Up),
             KeyCode::Char('w') => app.editor.textarea.move_cursor(CursorMove::WordForward),
@@ -328,6 +947,8 @@ impl InputHandler {
                 app.editor.textarea.delete_line_by_end();
                 app.editor.vim_mode = VimInputMode::Insert;
             }
+            KeyCode::Char('p') => app.editor.paste(false),
+            KeyCode::Char('P') => app.editor.paste(true),
             KeyCode::Char('u') => {
                 app.editor.textarea.undo();
             }
@@ -335,11 +956,52 @@ impl InputHandler {
                 app.editor.textarea.redo();
             }
 
-            // Toggle zoom mode
-            KeyCode::Char('z') => app.layout.toggle_zoom(),
+            KeyCode::Char('y') => app.yank_last_answer(),
+            KeyCode::Char('r') => app.retry_last_question(),
+            KeyCode::Char('e') => app.edit_last_question(),
+
+            _ => {}
+        }
+    }
+
+    fn handle_vim_visual(&mut self, key: KeyEvent, app: &mut App) {
+        let textarea = &mut app.editor.textarea;
+        match key.code {
+            KeyCode::Esc => app.editor.exit_visual(),
+            KeyCode::Char('v') | KeyCode::Char('V') => {
+                let linewise = key.code == KeyCode::Char('V');
+                let same = (app.editor.vim_mode == VimInputMode::VisualLine) == linewise;
+                if same {
+                    app.editor.exit_visual();
+                } else {
+                    app.editor.vim_mode = if linewise {
+                        VimInputMode::VisualLine
+                    } else {
+                        VimInputMode::Visual
+                    };
+                    app.editor.update_visual();
+                }
+            }
+
+            // Operators on the selection
+            KeyCode::Char('y') => app.yank_chat_selection(),
+            KeyCode::Char('d') | KeyCode::Char('x') => app.editor.delete_selection(),
+            KeyCode::Char('c') => app.editor.change_selection(),
 
+            // Motions extend the selection
+            KeyCode::Char('h') | KeyCode::Left => textarea.move_cursor(CursorMove::Back),
+            KeyCode::Char('l') | KeyCode::Right => textarea.move_cursor(CursorMove::Forward),
+            KeyCode::Char('j') | KeyCode::Down => textarea.move_cursor(CursorMove::Down),
+            KeyCode::Char('k') | KeyCode::Up => textarea.move_cursor(CursorMove::Up),
+            KeyCode::Char('w') => textarea.move_cursor(CursorMove::WordForward),
+            KeyCode::Char('b') => textarea.move_cursor(CursorMove::WordBack),
+            KeyCode::Char('0') | KeyCode::Home => textarea.move_cursor(CursorMove::Head),
+            KeyCode::Char('$') | KeyCode::End => textarea.move_cursor(CursorMove::End),
+            KeyCode::Char('g') => textarea.move_cursor(CursorMove::Top),
+            KeyCode::Char('G') => textarea.move_cursor(CursorMove::Bottom),
             _ => {}
         }
+        app.editor.update_visual();
     }
 
     /// Handles pending vim operator (d/c) + motion combinations.
@@ -397,85 +1059,107 @@ impl InputHandler {
     }
 
     fn handle_normal_mode(&mut self, key: KeyEvent, app: &mut App, viewport_height: usize) {
-        // Handle pending key sequences first
-        match self.pending {
-            PendingKey::G => {
-                self.pending = PendingKey::None;
-                if key.code == KeyCode::Char('g') {
-                    app.scroll_to_top();
-                }
+        let key = KeyPress::from(key);
+
+        // Second key of a sequence like gg; the pair is consumed either way
+        if let Some(first) = self.pending.take() {
+            let actions = app.keymap.actions(&[first, key]).to_vec();
+            self.dispatch(&actions, app, viewport_height);
+            return;
+        }
+        if app.keymap.is_prefix(key) {
+            self.pending = Some(key);
+            return;
+        }
+
+        let actions = app.keymap.actions(&[key]).to_vec();
+        self.dispatch(&actions, app, viewport_height);
+    }
+
+    /// Runs the first of `actions` that applies in the current state.
+    fn dispatch(&mut self, actions: &[Action], app: &mut App, viewport_height: usize) {
+        for &action in actions {
+            if Self::run_action(action, app, viewport_height) {
                 return;
             }
-            PendingKey::None => {}
         }
+    }
 
-        match key.code {
-            // 'i' focuses Chat and enters insert mode
-            KeyCode::Char('i') => {
+    /// Performs `action`, returning false if it doesn't apply right now.
+    fn run_action(action: Action, app: &mut App, viewport_height: usize) -> bool {
+        match action {
+            // Focus Chat and enter insert mode
+            Action::Chat => {
                 app.set_active_pane(Pane::Chat);
                 app.editor.vim_mode = VimInputMode::Insert;
             }
-
-            // Pane navigation (Ctrl+h/j/k/l) - must come before plain j/k
-            // Note: Ctrl+h often comes as Backspace, Ctrl+j as Enter in terminals
-            KeyCode::Char('h') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                if app.layout.active_pane == Pane::Diff {
-                    app.set_active_pane(Pane::Chat);
-                }
-            }
-            KeyCode::Backspace if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                // Ctrl+h fallback (terminals send this as Ctrl+Backspace)
-                if app.layout.active_pane == Pane::Diff {
-                    app.set_active_pane(Pane::Chat);
-                }
+            Action::WholeChangeChat => {
+                app.toggle_whole_change_chat();
+                app.set_active_pane(Pane::Chat);
+                app.editor.vim_mode = VimInputMode::Insert;
             }
-            KeyCode::Char('l') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                if app.layout.active_pane != Pane::Diff {
-                    app.set_active_pane(Pane::Diff);
-                }
+
+            // Pane navigation
+            Action::FocusLeft => app.focus_neighbor(FocusDirection::Left),
+            Action::FocusRight => app.focus_neighbor(FocusDirection::Right),
+            Action::FocusDown => app.focus_neighbor(FocusDirection::Down),
+            Action::FocusUp => app.focus_neighbor(FocusDirection::Up),
+            Action::NextPane => app.set_active_pane(app.layout.next_pane()),
+            Action::PrevPane => app.set_active_pane(app.layout.prev_pane()),
+
+            // Step navigation
+            Action::NextStep => app.next_step(),
+            Action::PrevStep => app.prev_step(),
+
+            // Moving through the file tree while it's focused
+            Action::ScrollDown if app.layout.active_pane == Pane::Files => {
+                app.move_file_tree_cursor(1)
             }
-            KeyCode::Char('j') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                if app.layout.active_pane == Pane::Minimap {
-                    app.set_active_pane(Pane::Chat);
-                }
+            Action::ScrollUp if app.layout.active_pane == Pane::Files => {
+                app.move_file_tree_cursor(-1)
             }
-            KeyCode::Char('k') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                if app.layout.active_pane == Pane::Chat {
-                    app.set_active_pane(Pane::Minimap);
-                }
+            Action::Top if app.layout.active_pane == Pane::Files => {
+                app.move_file_tree_cursor(isize::MIN)
             }
-
-            // Tab/Shift+Tab as alternative pane navigation
-            KeyCode::Tab => app.set_active_pane(app.layout.active_pane.next()),
-            KeyCode::BackTab => app.set_active_pane(app.layout.active_pane.prev()),
-
-            // Step navigation (arrow keys and Ctrl+n/p)
-            KeyCode::Down => app.next_step(),
-            KeyCode::Up => app.prev_step(),
-            KeyCode::Char('n') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                app.next_step();
+            Action::Bottom if app.layout.active_pane == Pane::Files => {
+                app.move_file_tree_cursor(isize::MAX)
             }
-            KeyCode::Char('p') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                app.prev_step();
+            Action::CompleteStep if app.layout.active_pane == Pane::Files => {
+                app.jump_to_tree_file()
             }
 
-            // Scrolling diff
-            KeyCode::Char('j') => app.scroll_down(1),
-            KeyCode::Char('k') => app.scroll_up(1),
-            KeyCode::Char('d') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                app.scroll_down(viewport_height / 2);
+            // Scrolling diff, or extending the visual selection
+            Action::ScrollDown if app.current_selection().is_some() => {
+                app.extend_selection(1, viewport_height)
             }
-            KeyCode::Char('u') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                app.scroll_up(viewport_height / 2);
+            Action::ScrollUp if app.current_selection().is_some() => {
+                app.extend_selection(-1, viewport_height)
             }
-
-            // Go to top (gg)
-            KeyCode::Char('g') => {
-                self.pending = PendingKey::G;
+            // Moving the line cursor, with the diff following it
+            Action::ScrollDown
+            | Action::ScrollUp
+            | Action::HalfPageDown
+            | Action::HalfPageUp
+            | Action::Top
+            | Action::Bottom
+                if app.diff_cursor.is_some() =>
+            {
+                let delta = match action {
+                    Action::ScrollDown => 1,
+                    Action::ScrollUp => -1,
+                    Action::HalfPageDown => (viewport_height / 2) as isize,
+                    Action::HalfPageUp => -((viewport_height / 2) as isize),
+                    Action::Top => isize::MIN,
+                    _ => isize::MAX,
+                };
+                app.move_line_cursor(delta, viewport_height);
             }
-
-            // Go to bottom (G)
-            KeyCode::Char('G') => {
+            Action::ScrollDown => app.scroll_down(1),
+            Action::ScrollUp => app.scroll_up(1),
+            Action::HalfPageDown => app.scroll_down(viewport_height / 2),
+            Action::HalfPageUp => app.scroll_up(viewport_height / 2),
+            Action::Top => app.scroll_to_top(),
+            Action::Bottom => {
                 let content_height = app
                     .session
                     .current_step_data()
@@ -484,40 +1168,120 @@ impl InputHandler {
                 app.scroll_to_bottom(content_height, viewport_height);
             }
 
-            // Search navigation (n/p/N only active during search)
-            KeyCode::Char('n') if app.search.query.is_some() => app.next_search_match(),
-            KeyCode::Char('N') if app.search.query.is_some() => app.prev_search_match(),
-            KeyCode::Char('p') if app.search.query.is_some() => app.prev_search_match(),
+            // Search navigation, only active during search
+            Action::NextMatch | Action::PrevMatch if app.search.query.is_none() => return false,
+            Action::NextMatch => app.next_search_match(),
+            Action::PrevMatch => app.prev_search_match(),
+
+            // Visual selection of diff lines, quoted into chat
+            Action::VisualSelect => app.toggle_selection(),
+            Action::LineCursor => app.toggle_line_cursor(),
+            Action::QuoteSelection if app.current_selection().is_none() => return false,
+            Action::QuoteSelection => app.quote_selection(),
+            Action::Yank => app.yank(),
 
             // Complete step and advance (or finish walkthrough)
-            KeyCode::Enter => app.complete_step_and_advance(),
+            Action::CompleteStep => app.complete_step_and_advance(),
+            Action::MarkReviewed => app.toggle_step_reviewed(),
 
-            // Toggle step reviewed
-            KeyCode::Char('x') => app.session.toggle_step_reviewed(),
+            // Hunk cursor and per-hunk review
+            Action::NextHunk => app.move_hunk_cursor(true),
+            Action::PrevHunk => app.move_hunk_cursor(false),
+            Action::NextFile => app.move_file_cursor(true),
+            Action::PrevFile => app.move_file_cursor(false),
+            Action::ToggleHunk => app.toggle_hunk_reviewed(),
 
-            // Toggle zoom mode
-            KeyCode::Char('z') => app.layout.toggle_zoom(),
+            // Section jumps in the linear stream
+            Action::NextSection | Action::PrevSection if !app.layout.linear => return false,
+            Action::NextSection => app.jump_linear_section(true),
+            Action::PrevSection => app.jump_linear_section(false),
 
+            Action::Zoom => app.layout.toggle_zoom(),
+            Action::Fold => app.toggle_fold(),
+            Action::FoldFile => app.toggle_file_fold(),
+            Action::UnfoldAll => app.set_all_folded(false),
+            Action::FoldAll => app.set_all_folded(true),
+            // The linear stream has no panes
+            Action::FileTree if app.layout.linear => return false,
+            Action::FileTree => app.toggle_file_tree(),
             // Rechunk (split step into sub-steps)
-            KeyCode::Char('+') => app.session.request_rechunk(),
-
-            // Help modal
-            KeyCode::Char('?') => app.toggle_help(),
-
-            // Search
-            KeyCode::Char('/') => app.search.start(),
-            KeyCode::Esc => app.search.clear(),
-
-            _ => {}
+            Action::SplitStep => app.session.request_rechunk(),
+            Action::Regenerate => app.open_regenerate_prompt(),
+            Action::RegenerateSummary => app.open_summary_prompt(),
+            Action::Persona => app.cycle_persona(),
+            // Ask a suggested follow-up question
+            Action::FollowUp1 => return app.session.send_follow_up(0),
+            Action::FollowUp2 => return app.session.send_follow_up(1),
+            Action::FollowUp3 => return app.session.send_follow_up(2),
+            // Re-sort steps: narrative vs file order
+            Action::StepOrder => app.toggle_step_order(),
+            // Rearrange steps, only from the step list
+            Action::MoveStepDown | Action::MoveStepUp | Action::MergeStep | Action::SplitHunks
+                if app.layout.active_pane != Pane::Minimap =>
+            {
+                return false;
+            }
+            Action::MoveStepDown => app.move_step(true),
+            Action::MoveStepUp => app.move_step(false),
+            Action::MergeStep => app.merge_step(),
+            Action::SplitHunks => app.open_hunk_split(),
+            // Hide lower-priority steps, only from the step list
+            Action::PriorityFilter if app.layout.active_pane != Pane::Minimap => return false,
+            Action::PriorityFilter => app.cycle_priority_filter(),
+            // Follow links between steps touching the same code
+            Action::Related => app.jump_to_related(),
+            Action::Parts => app.open_parts_picker(),
+            Action::QuickActions => app.open_quick_actions(),
+            Action::Feedback => app.open_feedback(),
+            Action::Notes => app.open_note_editor(),
+            Action::Flag => app.open_flag_editor(),
+            Action::PostReview => app.post_pr_review(),
+            Action::MoveHunk if app.layout.active_pane != Pane::Diff => return false,
+            Action::MoveHunk => app.open_hunk_move(),
+            Action::FileView if app.layout.active_pane != Pane::Diff => return false,
+            Action::FileView => app.open_file_view(),
+            // Off allows native terminal text selection
+            Action::MouseCapture => app.toggle_mouse_capture(),
+            Action::OpenInBrowser => app.open_in_browser(),
+            Action::ExportMarkdown => app.export_markdown(),
+            Action::ExportHtml => app.export_html(),
+            Action::ExportJson => app.export_json(),
+            // Record completed review as a git note
+            Action::RecordReview => app.record_review(),
+            Action::Help => app.toggle_help(),
+            Action::Usage => app.show_usage = true,
+            Action::Search => app.search.start(),
+            Action::SearchAll => app.open_global_search(String::new()),
+            Action::FindStep => app.open_step_finder(),
+            // Esc leaves visual selection before clearing the search
+            Action::ClearSearch if app.current_selection().is_some() => app.diff_selection = None,
+            Action::ClearSearch => app.search.clear(),
         }
+        true
     }
 
     pub fn handle_mouse(&mut self, mouse: MouseEvent, app: &mut App, size: Size) {
+        // Nothing but the size notice is on screen
+        if crate::ui::too_small(size.width, size.height) {
+            return;
+        }
+
+        // Linear mode has no panes: the wheel scrolls the single stream
+        if app.layout.linear {
+            match mouse.kind {
+                MouseEventKind::ScrollUp => app.scroll_up(3),
+                MouseEventKind::ScrollDown => app.scroll_down(3),
+                _ => {}
+            }
+            return;
+        }
+
         let content_height = size.height.saturating_sub(HELP_BAR_HEIGHT);
         let left_pane_width =
             (size.width as u32 * app.layout.left_pane_percent as u32 / 100) as u16;
         let minimap_height =
             (content_height as u32 * app.layout.minimap_percent as u32 / 100) as u16;
+        let file_tree_left = size.width - crate::ui::file_tree_width(size.width, app);
 
         let near_vertical_divider = mouse.column.abs_diff(left_pane_width) <= DIVIDER_HIT_ZONE;
         let near_horizontal_divider =
@@ -525,7 +1289,17 @@ impl InputHandler {
 
         match mouse.kind {
             MouseEventKind::Down(_) => {
-                if near_vertical_divider {
+                if app.diff_scroll.track_contains(mouse.column, mouse.row) {
+                    app.set_active_pane(Pane::Diff);
+                    app.layout.scrollbar_drag = Some(Pane::Diff);
+                    app.diff_scroll.drag_to(mouse.row);
+                } else if app.chat_scroll.track_contains(mouse.column, mouse.row) {
+                    app.set_active_pane(Pane::Chat);
+                    app.layout.scrollbar_drag = Some(Pane::Chat);
+                    app.chat_scroll.drag_to(mouse.row);
+                } else if mouse.column >= file_tree_left {
+                    app.set_active_pane(Pane::Files);
+                } else if near_vertical_divider {
                     app.layout.start_drag(Divider::Vertical);
                 } else if near_horizontal_divider {
                     app.layout.start_drag(Divider::Horizontal);
@@ -549,6 +1323,11 @@ impl InputHandler {
                 }
             }
             MouseEventKind::Drag(_) => {
+                match app.layout.scrollbar_drag {
+                    Some(Pane::Diff) => app.diff_scroll.drag_to(mouse.row),
+                    Some(Pane::Chat) => app.chat_scroll.drag_to(mouse.row),
+                    _ => {}
+                }
                 if let Some(divider) = app.layout.dragging {
                     match divider {
                         Divider::Vertical if size.width > 0 => {
@@ -569,7 +1348,9 @@ impl InputHandler {
                 app.layout.stop_drag();
             }
             MouseEventKind::ScrollUp => {
-                if mouse.column < left_pane_width {
+                if mouse.column >= file_tree_left {
+                    app.move_file_tree_cursor(-3);
+                } else if mouse.column < left_pane_width {
                     // Scroll in left pane - could be chat
                     if mouse.row >= minimap_height {
                         app.scroll_chat_up(3);
@@ -580,7 +1361,9 @@ impl InputHandler {
                 }
             }
             MouseEventKind::ScrollDown => {
-                if mouse.column < left_pane_width {
+                if mouse.column >= file_tree_left {
+                    app.move_file_tree_cursor(3);
+                } else if mouse.column < left_pane_width {
                     // Scroll in left pane - could be chat
                     if mouse.row >= minimap_height {
                         app.scroll_chat_down(3);
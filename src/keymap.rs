@@ -0,0 +1,533 @@
+//! Key bindings for the walkthrough view.
+//!
+//! Every normal-mode command is an [`Action`] with default keys. The `keys`
+//! section of settings rebinds actions by name; an action listed there loses
+//! its defaults, and a key it claims is taken away from other actions.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+use crate::settings::KeyList;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Action {
+    Chat,
+    WholeChangeChat,
+    FocusLeft,
+    FocusRight,
+    FocusDown,
+    FocusUp,
+    NextPane,
+    PrevPane,
+    NextStep,
+    PrevStep,
+    ScrollDown,
+    ScrollUp,
+    HalfPageDown,
+    HalfPageUp,
+    Top,
+    Bottom,
+    NextMatch,
+    PrevMatch,
+    CompleteStep,
+    MarkReviewed,
+    NextHunk,
+    PrevHunk,
+    NextFile,
+    PrevFile,
+    ToggleHunk,
+    VisualSelect,
+    LineCursor,
+    Yank,
+    QuoteSelection,
+    NextSection,
+    PrevSection,
+    Zoom,
+    Fold,
+    FoldFile,
+    UnfoldAll,
+    FoldAll,
+    FileTree,
+    SplitStep,
+    Regenerate,
+    RegenerateSummary,
+    Persona,
+    FollowUp1,
+    FollowUp2,
+    FollowUp3,
+    StepOrder,
+    MoveStepDown,
+    MoveStepUp,
+    MergeStep,
+    SplitHunks,
+    PriorityFilter,
+    Related,
+    Parts,
+    QuickActions,
+    Feedback,
+    Notes,
+    Flag,
+    PostReview,
+    MoveHunk,
+    FileView,
+    MouseCapture,
+    OpenInBrowser,
+    ExportMarkdown,
+    ExportHtml,
+    ExportJson,
+    RecordReview,
+    Usage,
+    Help,
+    Search,
+    SearchAll,
+    FindStep,
+    ClearSearch,
+}
+
+/// Settings name and default keys of each action. When a key is bound to
+/// several actions, the first one that applies wins (`Enter` quotes selected
+/// diff lines into chat while selecting and completes the step otherwise; `f`
+/// filters steps by priority in the step list and rates the step elsewhere).
+const ACTIONS: &[(Action, &str, &[&str])] = &[
+    (Action::Chat, "chat", &["i"]),
+    (Action::WholeChangeChat, "whole_change_chat", &["W"]),
+    (
+        Action::FocusLeft,
+        "focus_left",
+        &["ctrl+h", "ctrl+backspace"],
+    ),
+    (Action::FocusRight, "focus_right", &["ctrl+l"]),
+    (Action::FocusDown, "focus_down", &["ctrl+j"]),
+    (Action::FocusUp, "focus_up", &["ctrl+k"]),
+    (Action::NextPane, "next_pane", &["tab"]),
+    (Action::PrevPane, "prev_pane", &["shift+tab"]),
+    (Action::NextStep, "next_step", &["down", "ctrl+n"]),
+    (Action::PrevStep, "prev_step", &["up", "ctrl+p"]),
+    (Action::ScrollDown, "scroll_down", &["j"]),
+    (Action::ScrollUp, "scroll_up", &["k"]),
+    (Action::HalfPageDown, "half_page_down", &["ctrl+d"]),
+    (Action::HalfPageUp, "half_page_up", &["ctrl+u"]),
+    (Action::Top, "top", &["g g"]),
+    (Action::Bottom, "bottom", &["G"]),
+    (Action::NextMatch, "next_match", &["n"]),
+    (Action::PrevMatch, "prev_match", &["N", "p"]),
+    (Action::QuoteSelection, "quote_selection", &["enter"]),
+    (Action::CompleteStep, "complete_step", &["enter"]),
+    (Action::MarkReviewed, "mark_reviewed", &["x"]),
+    (Action::NextHunk, "next_hunk", &["] h"]),
+    (Action::PrevHunk, "prev_hunk", &["[ h"]),
+    (Action::NextFile, "next_file", &["] f"]),
+    (Action::PrevFile, "prev_file", &["[ f"]),
+    (Action::ToggleHunk, "toggle_hunk", &["space"]),
+    (Action::VisualSelect, "visual_select", &["v"]),
+    (Action::LineCursor, "line_cursor", &["c"]),
+    (Action::Yank, "yank", &["y"]),
+    (Action::NextSection, "next_section", &["}"]),
+    (Action::PrevSection, "prev_section", &["{"]),
+    (Action::Zoom, "zoom", &["z z"]),
+    (Action::Fold, "fold", &["z a"]),
+    (Action::FoldFile, "fold_file", &["z A"]),
+    (Action::UnfoldAll, "unfold_all", &["z R"]),
+    (Action::FoldAll, "fold_all", &["z M"]),
+    (Action::FileTree, "file_tree", &["t"]),
+    (Action::SplitStep, "split_step", &["+"]),
+    (Action::Regenerate, "regenerate", &["ctrl+r"]),
+    (Action::RegenerateSummary, "regenerate_summary", &["g r"]),
+    (Action::Persona, "persona", &["g p"]),
+    (Action::FollowUp1, "follow_up_1", &["1"]),
+    (Action::FollowUp2, "follow_up_2", &["2"]),
+    (Action::FollowUp3, "follow_up_3", &["3"]),
+    (Action::StepOrder, "step_order", &["s"]),
+    (Action::MoveStepDown, "move_step_down", &["J"]),
+    (Action::MoveStepUp, "move_step_up", &["K"]),
+    (Action::MergeStep, "merge_step", &["M"]),
+    (Action::SplitHunks, "split_hunks", &["S"]),
+    (Action::PriorityFilter, "priority_filter", &["f"]),
+    (Action::Related, "related", &["r"]),
+    (Action::Parts, "parts", &["P"]),
+    (Action::QuickActions, "quick_actions", &["a"]),
+    (Action::Feedback, "feedback", &["f"]),
+    (Action::Notes, "notes", &["g n"]),
+    (Action::Flag, "flag", &["!"]),
+    (Action::PostReview, "post_review", &["R"]),
+    (Action::MoveHunk, "move_hunk", &["g m"]),
+    (Action::FileView, "file_view", &["F"]),
+    (Action::MouseCapture, "mouse_capture", &["m"]),
+    (Action::OpenInBrowser, "open_in_browser", &["o"]),
+    (Action::ExportMarkdown, "export_markdown", &["e"]),
+    (Action::ExportHtml, "export_html", &["E"]),
+    (Action::ExportJson, "export_json", &["ctrl+e"]),
+    (Action::RecordReview, "record_review", &["T"]),
+    (Action::Usage, "usage", &["$"]),
+    (Action::Help, "help", &["?"]),
+    (Action::Search, "search", &["/"]),
+    (Action::SearchAll, "search_all", &["ctrl+f"]),
+    (Action::FindStep, "find_step", &["ctrl+o"]),
+    (Action::ClearSearch, "clear_search", &["esc"]),
+];
+
+/// Actions asking the suggested follow-up questions, in order.
+pub const FOLLOW_UP_ACTIONS: [Action; 3] =
+    [Action::FollowUp1, Action::FollowUp2, Action::FollowUp3];
+
+impl Action {
+    pub fn from_name(name: &str) -> Option<Self> {
+        ACTIONS
+            .iter()
+            .find(|(_, n, _)| *n == name)
+            .map(|(action, _, _)| *action)
+    }
+}
+
+/// A single key press, normalized so that settings and terminal events compare
+/// equal: Shift is folded into the character (`G`, not `shift+g`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct KeyPress {
+    pub code: KeyCode,
+    pub modifiers: KeyModifiers,
+}
+
+impl KeyPress {
+    fn new(code: KeyCode, modifiers: KeyModifiers) -> Self {
+        let mut modifiers =
+            modifiers & (KeyModifiers::CONTROL | KeyModifiers::ALT | KeyModifiers::SHIFT);
+        let code = match code {
+            KeyCode::Char(c) if modifiers.contains(KeyModifiers::SHIFT) => {
+                modifiers.remove(KeyModifiers::SHIFT);
+                KeyCode::Char(c.to_ascii_uppercase())
+            }
+            KeyCode::Tab if modifiers.contains(KeyModifiers::SHIFT) => {
+                modifiers.remove(KeyModifiers::SHIFT);
+                KeyCode::BackTab
+            }
+            KeyCode::BackTab => {
+                modifiers.remove(KeyModifiers::SHIFT);
+                KeyCode::BackTab
+            }
+            code => code,
+        };
+        Self { code, modifiers }
+    }
+
+    /// Parse a key spec like `j`, `G`, `ctrl+d`, `shift+tab`, or `enter`.
+    pub fn parse(spec: &str) -> Option<Self> {
+        let (mods, key) = if spec == "+" {
+            ("", "+")
+        } else if let Some(mods) = spec.strip_suffix("++") {
+            (mods, "+")
+        } else {
+            spec.rsplit_once('+').unwrap_or(("", spec))
+        };
+
+        let mut modifiers = KeyModifiers::NONE;
+        for m in mods.split('+').filter(|m| !m.is_empty()) {
+            modifiers |= match m.to_ascii_lowercase().as_str() {
+                "ctrl" | "control" => KeyModifiers::CONTROL,
+                "alt" => KeyModifiers::ALT,
+                "shift" => KeyModifiers::SHIFT,
+                _ => return None,
+            };
+        }
+
+        let mut chars = key.chars();
+        let code = match (chars.next(), chars.next()) {
+            (Some(c), None) => KeyCode::Char(c),
+            _ => match key.to_ascii_lowercase().as_str() {
+                "enter" | "return" => KeyCode::Enter,
+                "esc" | "escape" => KeyCode::Esc,
+                "tab" => KeyCode::Tab,
+                "backtab" => KeyCode::BackTab,
+                "backspace" => KeyCode::Backspace,
+                "delete" | "del" => KeyCode::Delete,
+                "space" => KeyCode::Char(' '),
+                "up" => KeyCode::Up,
+                "down" => KeyCode::Down,
+                "left" => KeyCode::Left,
+                "right" => KeyCode::Right,
+                "home" => KeyCode::Home,
+                "end" => KeyCode::End,
+                "pageup" => KeyCode::PageUp,
+                "pagedown" => KeyCode::PageDown,
+                f => KeyCode::F(f.strip_prefix('f')?.parse().ok()?),
+            },
+        };
+        Some(Self::new(code, modifiers))
+    }
+}
+
+impl From<KeyEvent> for KeyPress {
+    fn from(key: KeyEvent) -> Self {
+        Self::new(key.code, key.modifiers)
+    }
+}
+
+impl fmt::Display for KeyPress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            write!(f, "Ctrl+")?;
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            write!(f, "Alt+")?;
+        }
+        if self.modifiers.contains(KeyModifiers::SHIFT) {
+            write!(f, "Shift+")?;
+        }
+        match self.code {
+            KeyCode::Char(' ') => write!(f, "Space"),
+            KeyCode::Char(c) => write!(f, "{c}"),
+            KeyCode::Enter => write!(f, "Enter"),
+            KeyCode::Esc => write!(f, "Esc"),
+            KeyCode::Tab => write!(f, "Tab"),
+            KeyCode::BackTab => write!(f, "Shift+Tab"),
+            KeyCode::Backspace => write!(f, "Backspace"),
+            KeyCode::Delete => write!(f, "Del"),
+            KeyCode::Up => write!(f, "↑"),
+            KeyCode::Down => write!(f, "↓"),
+            KeyCode::Left => write!(f, "←"),
+            KeyCode::Right => write!(f, "→"),
+            KeyCode::Home => write!(f, "Home"),
+            KeyCode::End => write!(f, "End"),
+            KeyCode::PageUp => write!(f, "PgUp"),
+            KeyCode::PageDown => write!(f, "PgDn"),
+            KeyCode::F(n) => write!(f, "F{n}"),
+            _ => write!(f, "?"),
+        }
+    }
+}
+
+/// A key sequence: one key, or space-separated keys pressed in turn (`g g`).
+fn parse_sequence(spec: &str) -> Option<Vec<KeyPress>> {
+    let keys: Vec<KeyPress> = spec
+        .split_whitespace()
+        .map(KeyPress::parse)
+        .collect::<Option<_>>()?;
+    (!keys.is_empty()).then_some(keys)
+}
+
+#[derive(Debug, Clone)]
+pub struct Keymap {
+    bindings: HashMap<Vec<KeyPress>, Vec<Action>>,
+    /// Keys of each action in binding order, for help text
+    keys: HashMap<Action, Vec<Vec<KeyPress>>>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        Self::from_settings(&BTreeMap::new())
+    }
+}
+
+impl Keymap {
+    /// Starts from the defaults and applies overrides from settings.
+    /// Unknown action names and keys that don't parse are ignored.
+    pub fn from_settings(overrides: &BTreeMap<String, KeyList>) -> Self {
+        let overrides: Vec<(Action, Vec<Vec<KeyPress>>)> = overrides
+            .iter()
+            .filter_map(|(name, keys)| {
+                let action = Action::from_name(name)?;
+                let keys = keys
+                    .specs()
+                    .iter()
+                    .filter_map(|s| parse_sequence(s))
+                    .collect();
+                Some((action, keys))
+            })
+            .collect();
+        let claimed: Vec<&Vec<KeyPress>> = overrides.iter().flat_map(|(_, keys)| keys).collect();
+
+        let mut keymap = Self {
+            bindings: HashMap::new(),
+            keys: HashMap::new(),
+        };
+        for (action, _, defaults) in ACTIONS {
+            let keys = match overrides.iter().find(|(a, _)| a == action) {
+                Some((_, keys)) => keys.clone(),
+                None => defaults
+                    .iter()
+                    .filter_map(|s| parse_sequence(s))
+                    .filter(|seq| !claimed.contains(&seq))
+                    .collect(),
+            };
+            for seq in &keys {
+                keymap
+                    .bindings
+                    .entry(seq.clone())
+                    .or_default()
+                    .push(*action);
+            }
+            keymap.keys.insert(*action, keys);
+        }
+        keymap
+    }
+
+    /// Actions bound to a key sequence, in priority order.
+    pub fn actions(&self, keys: &[KeyPress]) -> &[Action] {
+        self.bindings.get(keys).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    /// Whether `key` starts a longer sequence, so the next key should be awaited.
+    pub fn is_prefix(&self, key: KeyPress) -> bool {
+        self.bindings
+            .keys()
+            .any(|seq| seq.len() > 1 && seq[0] == key)
+    }
+
+    /// Whether `key` starts a longer sequence bound to `action`.
+    pub fn starts(&self, action: Action, key: KeyPress) -> bool {
+        self.keys
+            .get(&action)
+            .is_some_and(|seqs| seqs.iter().any(|seq| seq.len() > 1 && seq[0] == key))
+    }
+
+    /// Label for a group of related actions, like `j/k` or `↑/↓  Ctrl+p/n`.
+    /// Pairs up the actions' keys by position; a shared modifier is written once.
+    pub fn label(&self, actions: &[Action]) -> String {
+        let keys: Vec<&[Vec<KeyPress>]> = actions
+            .iter()
+            .map(|a| self.keys.get(a).map(Vec::as_slice).unwrap_or(&[]))
+            .collect();
+        let count = keys.iter().map(|k| k.len()).min().unwrap_or(0);
+        (0..count)
+            .map(|i| group_label(keys.iter().map(|k| k[i].as_slice())))
+            .collect::<Vec<_>>()
+            .join("  ")
+    }
+}
+
+fn sequence_label(seq: &[KeyPress]) -> String {
+    seq.iter().map(ToString::to_string).collect()
+}
+
+fn group_label<'k>(seqs: impl Iterator<Item = &'k [KeyPress]>) -> String {
+    let seqs: Vec<&[KeyPress]> = seqs.collect();
+    let shared = match seqs.first() {
+        Some([first]) if !first.modifiers.is_empty() => first.modifiers,
+        _ => KeyModifiers::NONE,
+    };
+    let compact = !shared.is_empty()
+        && seqs.iter().all(|seq| {
+            matches!(seq, [key] if key.modifiers == shared && matches!(key.code, KeyCode::Char(_)))
+        });
+    if compact {
+        let prefix = KeyPress::new(KeyCode::Char(' '), shared).to_string();
+        let prefix = prefix.trim_end_matches("Space");
+        let chars: Vec<String> = seqs
+            .iter()
+            .map(|seq| KeyPress::new(seq[0].code, KeyModifiers::NONE).to_string())
+            .collect();
+        format!("{prefix}{}", chars.join("/"))
+    } else {
+        seqs.iter()
+            .map(|seq| sequence_label(seq))
+            .collect::<Vec<_>>()
+            .join("/")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(spec: &str) -> Vec<KeyPress> {
+        parse_sequence(spec).unwrap()
+    }
+
+    #[test]
+    fn test_parse_specs() {
+        let ctrl_d = KeyPress::new(KeyCode::Char('d'), KeyModifiers::CONTROL);
+        assert_eq!(KeyPress::parse("ctrl+d"), Some(ctrl_d));
+        assert_eq!(
+            KeyPress::parse("Ctrl+D").map(|k| k.modifiers),
+            Some(KeyModifiers::CONTROL)
+        );
+        assert_eq!(KeyPress::parse("shift+g"), KeyPress::parse("G"));
+        assert_eq!(
+            KeyPress::parse("shift+tab").map(|k| k.code),
+            Some(KeyCode::BackTab)
+        );
+        assert_eq!(
+            KeyPress::parse("+").map(|k| k.code),
+            Some(KeyCode::Char('+'))
+        );
+        assert_eq!(
+            KeyPress::parse("ctrl++").map(|k| k.code),
+            Some(KeyCode::Char('+'))
+        );
+        assert_eq!(KeyPress::parse("f5").map(|k| k.code), Some(KeyCode::F(5)));
+        assert_eq!(KeyPress::parse("hyper+x"), None);
+        assert_eq!(KeyPress::parse("nonsense"), None);
+        assert_eq!(key("g g").len(), 2);
+    }
+
+    #[test]
+    fn test_events_match_specs() {
+        // Terminals report uppercase letters and BackTab with Shift set
+        let event = KeyEvent::new(KeyCode::Char('G'), KeyModifiers::SHIFT);
+        assert_eq!(vec![KeyPress::from(event)], key("G"));
+        let event = KeyEvent::new(KeyCode::BackTab, KeyModifiers::SHIFT);
+        assert_eq!(vec![KeyPress::from(event)], key("shift+tab"));
+    }
+
+    #[test]
+    fn test_defaults() {
+        let keymap = Keymap::default();
+        assert_eq!(keymap.actions(&key("j")), [Action::ScrollDown]);
+        assert_eq!(keymap.actions(&key("N")), [Action::PrevMatch]);
+        assert_eq!(keymap.actions(&key("g n")), [Action::Notes]);
+        assert!(keymap.is_prefix(key("g")[0]));
+        assert_eq!(keymap.actions(&key("g g")), [Action::Top]);
+        assert!(keymap.starts(Action::Zoom, key("z")[0]));
+        assert!(!keymap.starts(Action::Zoom, key("g")[0]));
+        assert!(keymap.actions(&key("q")).is_empty());
+    }
+
+    #[test]
+    fn test_overrides_replace_defaults_and_claim_keys() {
+        let overrides = BTreeMap::from([
+            ("zoom".to_string(), KeyList::One("Z".to_string())),
+            (
+                "next_step".to_string(),
+                KeyList::Many(vec!["J".to_string(), "j".to_string()]),
+            ),
+            ("no_such_action".to_string(), KeyList::One("q".to_string())),
+            ("help".to_string(), KeyList::One("bogus".to_string())),
+        ]);
+        let keymap = Keymap::from_settings(&overrides);
+        assert!(keymap.actions(&key("z")).is_empty());
+        assert_eq!(keymap.actions(&key("Z")), [Action::Zoom]);
+        assert_eq!(keymap.actions(&key("j")), [Action::NextStep]);
+        assert!(keymap.actions(&key("down")).is_empty());
+        assert!(keymap.actions(&key("q")).is_empty());
+        assert!(keymap.actions(&key("?")).is_empty());
+    }
+
+    #[test]
+    fn test_labels() {
+        let keymap = Keymap::default();
+        assert_eq!(keymap.label(&[Action::ScrollDown, Action::ScrollUp]), "j/k");
+        assert_eq!(
+            keymap.label(&[Action::PrevStep, Action::NextStep]),
+            "↑/↓  Ctrl+p/n"
+        );
+        assert_eq!(
+            keymap.label(&[Action::HalfPageDown, Action::HalfPageUp]),
+            "Ctrl+d/u"
+        );
+        assert_eq!(keymap.label(&[Action::Top, Action::Bottom]), "gg/G");
+        assert_eq!(
+            keymap.label(&[Action::NextPane, Action::PrevPane]),
+            "Tab/Shift+Tab"
+        );
+        assert_eq!(
+            keymap.label(&[
+                Action::FocusLeft,
+                Action::FocusDown,
+                Action::FocusUp,
+                Action::FocusRight
+            ]),
+            "Ctrl+h/j/k/l"
+        );
+    }
+}
@@ -7,26 +7,50 @@ pub enum Pane {
     Minimap,
     Chat,
     Diff,
+    Files,
 }
 
 impl Pane {
-    /// Cycle to the next pane (Minimap -> Chat -> Diff -> Minimap)
+    /// Cycle to the next pane (Minimap -> Chat -> Diff -> Files -> Minimap)
     pub fn next(self) -> Self {
         match self {
             Self::Minimap => Self::Chat,
             Self::Chat => Self::Diff,
-            Self::Diff => Self::Minimap,
+            Self::Diff => Self::Files,
+            Self::Files => Self::Minimap,
         }
     }
 
-    /// Cycle to the previous pane (Minimap -> Diff -> Chat -> Minimap)
+    /// Cycle to the previous pane (Minimap -> Files -> Diff -> Chat -> Minimap)
     pub fn prev(self) -> Self {
         match self {
-            Self::Minimap => Self::Diff,
+            Self::Minimap => Self::Files,
             Self::Chat => Self::Minimap,
             Self::Diff => Self::Chat,
+            Self::Files => Self::Diff,
         }
     }
+
+    /// The pane reached by moving focus in a direction (Ctrl+h/j/k/l), if any
+    pub fn neighbor(self, direction: FocusDirection) -> Option<Self> {
+        match (self, direction) {
+            (Self::Diff, FocusDirection::Left) => Some(Self::Chat),
+            (Self::Minimap | Self::Chat, FocusDirection::Right) => Some(Self::Diff),
+            (Self::Diff, FocusDirection::Right) => Some(Self::Files),
+            (Self::Files, FocusDirection::Left) => Some(Self::Diff),
+            (Self::Minimap, FocusDirection::Down) => Some(Self::Chat),
+            (Self::Chat, FocusDirection::Up) => Some(Self::Minimap),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FocusDirection {
+    Left,
+    Right,
+    Up,
+    Down,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -46,8 +70,14 @@ pub struct Layout {
     pub minimap_percent: u16,
     /// Currently dragging a divider
     pub dragging: Option<Divider>,
+    /// Pane whose scrollbar is being dragged
+    pub scrollbar_drag: Option<Pane>,
     /// Zoomed pane (None = normal layout, Some(pane) = that pane is fullscreen)
     pub zoomed: Option<Pane>,
+    /// Screen-reader friendly single-column presentation
+    pub linear: bool,
+    /// Whether the file tree pane is shown right of the diff viewer
+    pub file_tree: bool,
 }
 
 impl Default for Layout {
@@ -57,7 +87,10 @@ impl Default for Layout {
             left_pane_percent: 50,
             minimap_percent: 40,
             dragging: None,
+            scrollbar_drag: None,
             zoomed: None,
+            linear: false,
+            file_tree: false,
         }
     }
 }
@@ -77,6 +110,7 @@ impl Layout {
 
     pub fn stop_drag(&mut self) {
         self.dragging = None;
+        self.scrollbar_drag = None;
     }
 
     pub fn toggle_zoom(&mut self) {
@@ -89,4 +123,20 @@ impl Layout {
     pub fn is_zoomed(&self) -> bool {
         self.zoomed.is_some()
     }
+
+    /// The pane after the active one, skipping the file tree while hidden
+    pub fn next_pane(&self) -> Pane {
+        match self.active_pane.next() {
+            Pane::Files if !self.file_tree => Pane::Minimap,
+            pane => pane,
+        }
+    }
+
+    /// The pane before the active one, skipping the file tree while hidden
+    pub fn prev_pane(&self) -> Pane {
+        match self.active_pane.prev() {
+            Pane::Files if !self.file_tree => Pane::Diff,
+            pane => pane,
+        }
+    }
 }
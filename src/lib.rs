@@ -0,0 +1,56 @@
+//! Docent's engine: parse a diff, have a model organize it into a
+//! walkthrough of review steps, and answer questions about a step. The
+//! `docent` binary is a terminal frontend to it; a review bot or editor
+//! plugin can use it directly.
+//!
+//! - [`diff`] parses unified diffs and filters their files.
+//! - [`generation`] turns a diff into a [`model::Walkthrough`].
+//! - [`api`] talks to the model provider, including chat about a step.
+//! - [`model`] holds walkthroughs, steps, and hunks, which serialize with
+//!   serde.
+//! - [`cache`] keeps generated walkthroughs for reruns on the same diff.
+//!
+//! ```no_run
+//! use docent::api::{self, ApiSettings, ChatContext, ClientConfig, Provider};
+//! use docent::diff::FileFilter;
+//! use docent::generation::WalkthroughGenerator;
+//! use docent::model::{Message, ReviewMode};
+//!
+//! # async fn review(diff_text: &str) -> Result<(), Box<dyn std::error::Error>> {
+//! let config = ClientConfig {
+//!     provider: Provider::Anthropic,
+//!     api_key: std::env::var("ANTHROPIC_API_KEY")?,
+//!     settings: ApiSettings::default(),
+//!     retry_notices: None,
+//! };
+//!
+//! // Generate the walkthrough and serialize it
+//! let filter = FileFilter::default();
+//! let mode = ReviewMode::Review;
+//! let generator =
+//!     WalkthroughGenerator::with_filter(diff_text, &filter, mode, config.clone(), vec![])?;
+//! let (walkthrough, _usage) = generator.generate().await?;
+//! println!("{}", serde_json::to_string_pretty(&walkthrough)?);
+//!
+//! // Ask about the first step, printing the answer as it streams in
+//! let context = api::chat_context(&walkthrough, 0, ChatContext::Overview, 20).unwrap_or_default();
+//! let question = [Message::user("Is the error handling here complete?")];
+//! let client = api::connect(config);
+//! let (chunk_tx, mut chunks) = tokio::sync::mpsc::channel(32);
+//! let answer = client.chat_streaming(&context, &question, mode, chunk_tx);
+//! let print = async {
+//!     while let Some(chunk) = chunks.recv().await {
+//!         print!("{chunk}");
+//!     }
+//! };
+//! let (usage, ()) = tokio::join!(answer, print);
+//! usage?;
+//! # Ok(())
+//! # }
+//! ```
+
+pub mod api;
+pub mod cache;
+pub mod diff;
+pub mod generation;
+pub mod model;
@@ -1,80 +1,163 @@
-mod api;
 mod app;
+mod chats;
+mod clipboard;
 mod colors;
+mod console;
 mod constants;
-mod diff;
 mod editor;
-mod generation;
+mod export;
+mod feedback;
+mod file_selection;
+mod file_tree;
 mod github;
+mod hooks;
+mod i18n;
 mod input;
+mod keymap;
 mod layout;
-mod model;
+mod onboarding;
+mod parts;
 mod protocol;
+mod quick_actions;
+mod recovery;
+mod review_record;
+mod script;
 mod scroll;
 mod search;
 mod session;
 mod settings;
+mod stats;
+mod toast;
 mod ui;
+mod vcs;
 
 use std::io::{self, IsTerminal, Read, stdout};
 
-use clap::Parser;
+use docent::{api, cache, diff, generation, model};
+
+use clap::{Parser, Subcommand};
 use crossterm::{
     ExecutableCommand,
-    event::{self, DisableMouseCapture, EnableMouseCapture, Event},
+    event::{
+        self, DisableBracketedPaste, DisableMouseCapture, EnableBracketedPaste, EnableMouseCapture,
+        Event,
+    },
     terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
 };
 use ratatui::prelude::*;
 use tokio::sync::mpsc;
+use tokio::task::AbortHandle;
 
-use api::{ClaudeClient, TokenUsage};
+use api::{
+    ClientConfig, FollowUpResponse, PartitionResponse, PromptSettings, RetryNotice, TokenUsage,
+};
 use app::App;
-use constants::{EVENT_POLL_INTERVAL, EVENT_RECV_TIMEOUT, VIEWPORT_HEIGHT_OFFSET};
-use diff::FileFilter;
-use generation::{StreamEvent, WalkthroughGenerator, create_sub_steps, format_step_for_rechunk};
+use constants::{
+    DIFF_WATCH_INTERVAL, EVENT_POLL_INTERVAL, EVENT_RECV_TIMEOUT, VIEWPORT_HEIGHT_OFFSET,
+};
+use diff::{FileFilter, IgnoreFile};
+use export::ReportFormat;
+use generation::{
+    StreamEvent, WalkthroughGenerator, create_sub_steps, format_step_for_rechunk,
+    generate_by_commit,
+};
+use github::PullRequestReview;
+use i18n::Strings;
 use input::InputHandler;
-use model::{CommitInfo, Message, ReviewMode, Step, Walkthrough};
+use model::{CommitInfo, Message, PullRequestInfo, ReviewMode, Step, StepOrganization};
+use session::ChatThread;
 #[cfg(debug_assertions)]
 use model::mock_walkthrough;
-use settings::Settings;
+use settings::{CacheSettings, Settings};
 
 enum AppEvent {
     Terminal(Event),
     GenerationComplete(TokenUsage),
     GenerationError(String),
+    GenerationStarted(String),
+    GenerationProgress(u32),
     StepReady(Step),
-    ChatChunk(usize, String),
-    ChatComplete(usize, TokenUsage),
-    ChatError(usize, String),
+    ChatChunk(ChatThread, String),
+    ChatComplete(ChatThread, TokenUsage),
+    ChatError(ChatThread, String),
+    /// A conversation's given number of oldest messages, summarized
+    ChatSummarized(ChatThread, usize, String, TokenUsage),
+    ChatSummaryError(ChatThread, String),
     RechunkComplete(usize, Vec<Step>, TokenUsage),
     RechunkError(String),
+    SummaryComplete(usize, String, TokenUsage),
+    SummaryError(usize, String),
+    FollowUpsSuggested(usize, Result<(FollowUpResponse, TokenUsage), String>),
+    ReviewRecorded(Result<String, String>),
+    PrReviewPosted(Result<usize, String>),
+    HookFinished(hooks::HookOutcome),
+    PartsSuggested(Result<(PartitionResponse, TokenUsage), String>),
+    RetryScheduled(RetryNotice),
+    /// The watched diff reads differently than when last checked
+    DiffChanged(String, Vec<CommitInfo>),
+    /// The walkthrough about to arrive was generated on an earlier run
+    LoadedFromCache,
 }
 
+/// Generates the session's walkthrough of `diff_text`, or loads it from the
+/// cache. Regenerating skips reading the cache, but the new walkthrough is
+/// still saved.
 fn spawn_walkthrough_generation(
     tx: mpsc::Sender<AppEvent>,
-    api_key: String,
+    session: &session::Session,
     diff_text: String,
-    filter: FileFilter,
-    mode: ReviewMode,
-    commits: Vec<CommitInfo>,
-) {
+    cache_settings: CacheSettings,
+) -> AbortHandle {
+    let config = session.client_config();
+    let filter = session.diff_filter.clone();
+    let mode = session.review_mode;
+    let options = session.generation_options();
+    let commits = session.commits.clone();
+    if session.by_commit {
+        return tokio::spawn(async move {
+            let (event_tx, event_rx) = mpsc::channel::<StreamEvent>(32);
+            let forward_task = forward_generation(tx.clone(), event_rx);
+            let result = async move {
+                let diffs = vcs::read_commit_diffs(&commits).await.map_err(|e| e.to_string())?;
+                generate_by_commit(diffs, &filter, mode, config, options, event_tx)
+                    .await
+                    .map_err(|e| e.to_string())
+            }
+            .await;
+            let _ = forward_task.await;
+            let event = match result {
+                Ok(usage) => AppEvent::GenerationComplete(usage),
+                Err(e) => AppEvent::GenerationError(e),
+            };
+            let _ = tx.send(event).await;
+        })
+        .abort_handle();
+    }
+    let read_cache = !session.is_regenerating();
     tokio::spawn(async move {
-        match WalkthroughGenerator::with_filter(&diff_text, &filter, mode, api_key, commits) {
+        match WalkthroughGenerator::with_filter(&diff_text, &filter, mode, config, commits) {
             Ok(generator) => {
-                let (event_tx, mut event_rx) = mpsc::channel::<StreamEvent>(32);
-
-                let tx_forward = tx.clone();
-                let forward_task = tokio::spawn(async move {
-                    while let Some(StreamEvent::StepReady(s)) = event_rx.recv().await {
-                        if tx_forward.send(AppEvent::StepReady(s)).await.is_err() {
-                            break;
-                        }
+                let generator = generator.with_options(options);
+                let cache_key = generator.cache_key();
+                if read_cache
+                    && let Some((model, steps)) = cache::load(&cache_key, &cache_settings)
+                {
+                    let _ = tx.send(AppEvent::LoadedFromCache).await;
+                    let _ = tx.send(AppEvent::GenerationStarted(model)).await;
+                    for step in steps {
+                        let _ = tx.send(AppEvent::StepReady(step)).await;
                     }
-                });
+                    let _ = tx.send(AppEvent::GenerationComplete(TokenUsage::default())).await;
+                    return;
+                }
+                let (event_tx, event_rx) = mpsc::channel::<StreamEvent>(32);
+                let forward_task = forward_generation(tx.clone(), event_rx);
 
                 match generator.generate_streaming(event_tx).await {
                     Ok(usage) => {
-                        let _ = forward_task.await;
+                        if let Ok((model, steps)) = forward_task.await {
+                            let _ = cache::save(&cache_key, &model, &steps, &cache_settings);
+                        }
                         let _ = tx.send(AppEvent::GenerationComplete(usage)).await;
                     }
                     Err(e) => {
@@ -86,7 +169,70 @@ fn spawn_walkthrough_generation(
                 let _ = tx.send(AppEvent::GenerationError(e.to_string())).await;
             }
         }
+    })
+    .abort_handle()
+}
+
+/// Forwards generation events to the app, returning the model and steps
+/// once the generator is done.
+fn forward_generation(
+    tx: mpsc::Sender<AppEvent>,
+    mut event_rx: mpsc::Receiver<StreamEvent>,
+) -> tokio::task::JoinHandle<(String, Vec<Step>)> {
+    tokio::spawn(async move {
+        let mut model = String::new();
+        let mut steps = Vec::new();
+        while let Some(event) = event_rx.recv().await {
+            let event = match event {
+                StreamEvent::Started(m) => {
+                    model = m.clone();
+                    AppEvent::GenerationStarted(m)
+                }
+                StreamEvent::Progress(tokens) => AppEvent::GenerationProgress(tokens),
+                StreamEvent::StepReady(s) => {
+                    steps.push(s.clone());
+                    AppEvent::StepReady(s)
+                }
+            };
+            if tx.send(event).await.is_err() {
+                break;
+            }
+        }
+        (model, steps)
+    })
+}
+
+/// Reads the diff again every few seconds and reports each change from
+/// `diff_text`, the version last seen.
+fn spawn_diff_watcher(tx: mpsc::Sender<AppEvent>, local: vcs::LocalDiff, mut diff_text: String) {
+    tokio::spawn(async move {
+        loop {
+            tokio::time::sleep(DIFF_WATCH_INTERVAL).await;
+            let Some((latest, commits)) = local.read().await else {
+                continue;
+            };
+            if latest != diff_text {
+                diff_text.clone_from(&latest);
+                if tx.send(AppEvent::DiffChanged(latest, commits)).await.is_err() {
+                    break;
+                }
+            }
+        }
+    });
+}
+
+/// A sender for API clients to announce rate-limit waits on, forwarded to
+/// the event loop.
+fn spawn_retry_forwarder(tx: mpsc::Sender<AppEvent>) -> mpsc::UnboundedSender<RetryNotice> {
+    let (retry_tx, mut retry_rx) = mpsc::unbounded_channel();
+    tokio::spawn(async move {
+        while let Some(notice) = retry_rx.recv().await {
+            if tx.send(AppEvent::RetryScheduled(notice)).await.is_err() {
+                break;
+            }
+        }
     });
+    retry_tx
 }
 
 /// Spawns a thread to read terminal events and forward them to the event channel
@@ -96,6 +242,7 @@ fn spawn_terminal_reader(tx: mpsc::Sender<AppEvent>) {
             match event::poll(EVENT_POLL_INTERVAL) {
                 Ok(true) => {
                     if let Ok(evt) = event::read()
+                        && console::is_actionable(&evt)
                         && tx.blocking_send(AppEvent::Terminal(evt)).is_err()
                     {
                         break;
@@ -110,24 +257,63 @@ fn spawn_terminal_reader(tx: mpsc::Sender<AppEvent>) {
     });
 }
 
-/// Spawns a task to handle streaming chat with the Claude API
+/// Feeds scripted key events into the app as if they were typed.
+fn spawn_script_player(tx: mpsc::Sender<AppEvent>, steps: Vec<script::ScriptStep>) {
+    tokio::spawn(async move {
+        for step in steps {
+            match step {
+                script::ScriptStep::Wait(duration) => tokio::time::sleep(duration).await,
+                script::ScriptStep::Event(event) => {
+                    if tx.send(AppEvent::Terminal(event)).await.is_err() {
+                        break;
+                    }
+                }
+            }
+        }
+    });
+}
+
+/// "step 3", or "the whole change", for status messages about a chat reply.
+fn chat_thread_label(strings: &Strings, thread: ChatThread) -> String {
+    match thread {
+        ChatThread::Step(index) => format!("{} {}", strings.toast_thread_step, index + 1),
+        ChatThread::Walkthrough => strings.toast_thread_whole_change.to_string(),
+    }
+}
+
+/// Spawns a task to handle streaming chat with the model provider, first
+/// summarizing the conversation's oldest messages when `summarize` asks to
 fn spawn_chat_handler(
     tx: mpsc::Sender<AppEvent>,
-    api_key: String,
-    step_index: usize,
-    walkthrough: Walkthrough,
+    config: ClientConfig,
+    thread: ChatThread,
+    mut context: String,
     messages: Vec<Message>,
+    summarize: Option<(usize, String)>,
     mode: ReviewMode,
-) {
+) -> AbortHandle {
     tokio::spawn(async move {
-        let client = ClaudeClient::new(api_key);
+        let client = api::connect(config);
+        if let Some((covered, prompt)) = summarize {
+            // Without a summary the oldest messages are simply left out
+            match client.summarize_history(&prompt).await {
+                Ok((response, usage)) => {
+                    context = api::context_with_summary(&context, &response.summary);
+                    let event = AppEvent::ChatSummarized(thread, covered, response.summary, usage);
+                    let _ = tx.send(event).await;
+                }
+                Err(e) => {
+                    let _ = tx.send(AppEvent::ChatSummaryError(thread, e.to_string())).await;
+                }
+            }
+        }
         let (chunk_tx, mut chunk_rx) = mpsc::channel::<String>(32);
 
         let tx_chunks = tx.clone();
         let forward_task = tokio::spawn(async move {
             while let Some(chunk) = chunk_rx.recv().await {
                 if tx_chunks
-                    .send(AppEvent::ChatChunk(step_index, chunk))
+                    .send(AppEvent::ChatChunk(thread, chunk))
                     .await
                     .is_err()
                 {
@@ -137,32 +323,33 @@ fn spawn_chat_handler(
         });
 
         match client
-            .chat_streaming(&walkthrough, step_index, &messages, mode, chunk_tx)
+            .chat_streaming(&context, &messages, mode, chunk_tx)
             .await
         {
             Ok(usage) => {
                 let _ = forward_task.await;
-                let _ = tx.send(AppEvent::ChatComplete(step_index, usage)).await;
+                let _ = tx.send(AppEvent::ChatComplete(thread, usage)).await;
             }
             Err(e) => {
                 let _ = tx
-                    .send(AppEvent::ChatError(step_index, e.to_string()))
+                    .send(AppEvent::ChatError(thread, e.to_string()))
                     .await;
             }
         }
-    });
+    })
+    .abort_handle()
 }
 
 fn spawn_rechunk(
     tx: mpsc::Sender<AppEvent>,
-    api_key: String,
+    config: ClientConfig,
     step_index: usize,
     step: Step,
     diff_text: Option<String>,
     mode: ReviewMode,
 ) {
     tokio::spawn(async move {
-        let client = ClaudeClient::new(api_key);
+        let client = api::connect(config);
         let step_content = format_step_for_rechunk(&step);
         let mut prompt = format!(
             "Please split this step into smaller sub-steps.\n\n\
@@ -194,6 +381,79 @@ fn spawn_rechunk(
     });
 }
 
+/// Asks for a new summary of a step, collecting the streamed reply so it
+/// replaces the old summary in one go. Tools are off, so lookup markers
+/// don't end up in the summary.
+fn spawn_summary(
+    tx: mpsc::Sender<AppEvent>,
+    mut config: ClientConfig,
+    step_index: usize,
+    context: String,
+    messages: Vec<Message>,
+    mode: ReviewMode,
+) {
+    config.settings.chat_tools = false;
+    tokio::spawn(async move {
+        let client = api::connect(config);
+        let (chunk_tx, mut chunk_rx) = mpsc::channel::<String>(32);
+        let collect = async {
+            let mut summary = String::new();
+            while let Some(chunk) = chunk_rx.recv().await {
+                summary.push_str(&chunk);
+            }
+            summary
+        };
+        let reply = client.chat_streaming(&context, &messages, mode, chunk_tx);
+        let event = match tokio::join!(reply, collect) {
+            (Ok(usage), summary) => AppEvent::SummaryComplete(step_index, summary, usage),
+            (Err(e), _) => AppEvent::SummaryError(step_index, e.to_string()),
+        };
+        let _ = tx.send(event).await;
+    });
+}
+
+fn spawn_follow_ups(
+    tx: mpsc::Sender<AppEvent>,
+    config: ClientConfig,
+    step_index: usize,
+    prompt: String,
+) {
+    tokio::spawn(async move {
+        let client = api::connect(config);
+        let result = client.suggest_follow_ups(&prompt).await.map_err(|e| e.to_string());
+        let _ = tx.send(AppEvent::FollowUpsSuggested(step_index, result)).await;
+    });
+}
+
+fn spawn_partition(tx: mpsc::Sender<AppEvent>, config: ClientConfig, prompt: String) {
+    tokio::spawn(async move {
+        let client = api::connect(config);
+        let result = client.suggest_parts(&prompt).await.map_err(|e| e.to_string());
+        let _ = tx.send(AppEvent::PartsSuggested(result)).await;
+    });
+}
+
+fn spawn_review_record(tx: mpsc::Sender<AppEvent>, report: String, verdict: String) {
+    tokio::spawn(async move {
+        let result = review_record::record_git_note(&report, &verdict).await;
+        let _ = tx.send(AppEvent::ReviewRecorded(result)).await;
+    });
+}
+
+fn spawn_pr_review(tx: mpsc::Sender<AppEvent>, forge: github::ForgeLink, review: PullRequestReview) {
+    tokio::spawn(async move {
+        let result = github::post_review(&forge, &review).await;
+        let _ = tx.send(AppEvent::PrReviewPosted(result)).await;
+    });
+}
+
+fn spawn_hook(tx: mpsc::Sender<AppEvent>, command: String, event: hooks::HookEvent) {
+    tokio::spawn(async move {
+        let outcome = hooks::run(command, event).await;
+        let _ = tx.send(AppEvent::HookFinished(outcome)).await;
+    });
+}
+
 /// AI-guided code review walkthrough tool
 #[derive(Parser, Debug)]
 #[command(name = "docent", version, about)]
@@ -202,31 +462,190 @@ struct Args {
     #[arg(value_name = "FILE_OR_URL")]
     diff_file: Option<String>,
 
+    /// Revision range (`main...HEAD`) or single revision (`HEAD~3`, diffed
+    /// against the working copy) to review
+    #[arg(long = "git", value_name = "RANGE", conflicts_with = "diff_file")]
+    git: Option<String>,
+
+    /// Number of a pull request on this repository's GitHub `origin` to review
+    #[arg(long = "pr", value_name = "NUMBER", conflicts_with_all = ["diff_file", "git"])]
+    pr: Option<u64>,
+
+    /// Open a walkthrough saved as JSON (by `export --json` or `Ctrl+e`)
+    /// instead of generating one
+    #[arg(
+        long = "from-walkthrough",
+        value_name = "FILE",
+        conflicts_with_all = ["diff_file", "git", "pr", "since", "watch", "by_commit", "print"]
+    )]
+    from_walkthrough: Option<String>,
+
     /// Use mock data instead of generating from a diff
     #[cfg(debug_assertions)]
     #[arg(long = "mock")]
     use_mock: bool,
 
     /// Only include files matching these glob patterns (e.g., "*.clj", "src/**/*.rs")
-    #[arg(short = 'f', long = "filter", value_name = "PATTERN")]
+    #[arg(
+        short = 'f',
+        long = "filter",
+        visible_alias = "include",
+        value_name = "PATTERN",
+        global = true
+    )]
     filters: Vec<String>,
 
     /// Exclude files matching these glob patterns
-    #[arg(short = 'x', long = "exclude", value_name = "PATTERN")]
+    #[arg(short = 'x', long = "exclude", value_name = "PATTERN", global = true)]
     excludes: Vec<String>,
 
+    /// Don't skip generated and vendored files (lockfiles, minified JS, vendor/, dist/)
+    #[arg(long = "no-default-excludes", global = true)]
+    no_default_excludes: bool,
+
     /// Walkthrough mode: describe the changes instead of giving an opinionated review
-    #[arg(short = 'w', long = "walkthrough")]
+    #[arg(short = 'w', long = "walkthrough", global = true)]
     walkthrough: bool,
 
+    /// Screen-reader friendly mode: a single linear text stream instead of panes
+    #[arg(long = "linear")]
+    linear: bool,
+
+    /// Start with mouse capture off, so the terminal's own text selection works
+    #[arg(long = "no-mouse")]
+    no_mouse: bool,
+
+    /// Offer to regenerate the walkthrough when the working copy or revision
+    /// range being reviewed changes
+    #[arg(long = "watch", conflicts_with_all = ["since", "print", "headless"])]
+    watch: bool,
+
+    /// Walk through a git revision range one commit at a time, with each
+    /// commit's steps grouped under it
+    #[arg(long = "by-commit", conflicts_with_all = ["since", "print", "headless"])]
+    by_commit: bool,
+
+    /// Previous revision of the same change (file, URL, or range); review only
+    /// what changed since it
+    #[arg(long = "since", value_name = "FILE_OR_URL")]
+    since: Option<String>,
+
+    /// Generate a fresh walkthrough instead of loading a cached one, and don't cache it
+    #[arg(long = "no-cache", global = true)]
+    no_cache: bool,
+
+    /// Model provider for this run (overrides `provider` in settings)
+    #[arg(long = "provider", value_name = "PROVIDER", global = true)]
+    provider: Option<settings::Provider>,
+
+    /// Model to use for this run (overrides `api.model` in settings)
+    #[arg(long = "model", value_name = "MODEL", global = true)]
+    model: Option<String>,
+
+    /// Review persona for this run: general, security, perf, api, or one from
+    /// settings (overrides `api.prompts.persona`)
+    #[arg(long = "persona", value_name = "PERSONA", global = true)]
+    persona: Option<String>,
+
+    /// How to organize steps (overrides `organize_by` in the project's .docent.json)
+    #[arg(long = "group-by", value_name = "ORGANIZATION", global = true)]
+    group_by: Option<StepOrganization>,
+
+    /// Replay keystrokes from a script file (see README) for demos and reproductions
+    #[arg(long = "script", value_name = "FILE")]
+    script: Option<String>,
+
     /// Run in headless mode (server only, no TUI)
     #[arg(long = "headless")]
     headless: bool,
+
+    /// Generate the walkthrough and print it to stdout instead of opening the TUI
+    #[arg(long = "print", conflicts_with_all = ["headless", "script"])]
+    print: bool,
+
+    /// Report format for `--print` and `export`
+    #[arg(long = "format", value_name = "FORMAT", default_value = "markdown", global = true)]
+    format: ReportFormat,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Review a diff in the TUI (the default when no subcommand is given)
+    Review {
+        /// Path to a diff/patch file or GitHub PR URL (or pipe diff via stdin)
+        #[arg(value_name = "FILE_OR_URL")]
+        diff_file: Option<String>,
+    },
+    /// Generate a walkthrough and print it to stdout (same as `--print`)
+    Generate {
+        /// Path to a diff/patch file or GitHub PR URL (or pipe diff via stdin)
+        #[arg(value_name = "FILE_OR_URL")]
+        diff_file: Option<String>,
+    },
+    /// List interrupted reviews that can be restored
+    Sessions {
+        /// Open the TUI offering the review with this number in the list
+        #[arg(long, value_name = "NUMBER")]
+        restore: Option<usize>,
+    },
+    /// Serve walkthrough generation, step data, and chat as JSON-RPC over stdio
+    Serve {
+        /// Path to a diff/patch file or GitHub PR URL (or send a `generate` request later)
+        #[arg(value_name = "FILE_OR_URL")]
+        diff_file: Option<String>,
+    },
+    /// Generate a walkthrough and write it as a report (Markdown by default), without the TUI
+    Export {
+        /// Path to a diff/patch file or GitHub PR URL (or pipe diff via stdin)
+        #[arg(value_name = "FILE_OR_URL")]
+        diff_file: Option<String>,
+
+        /// Where to write the report (`-` for stdout); `docent-review.md`, or
+        /// `docent-review.json` for JSON, by default
+        #[arg(short = 'o', long = "output", value_name = "PATH")]
+        output: Option<String>,
+
+        /// Write the walkthrough as JSON (the same as `--format json`)
+        #[arg(long = "json")]
+        json: bool,
+    },
+    /// Summarize locally recorded usage (enable with `stats.enabled` in settings)
+    Stats,
+    /// Summarize step ratings by model and prompt options
+    Feedback {
+        /// Print every recorded rating as JSON instead of the summary
+        #[arg(long = "json")]
+        json: bool,
+    },
 }
 
 struct DiffInput {
-    diff_text: String,
-    commits:   Vec<CommitInfo>,
+    diff_text:    String,
+    commits:      Vec<CommitInfo>,
+    forge:        Option<github::ForgeLink>,
+    pull_request: Option<PullRequestInfo>,
+    /// `diff_text` is an interdiff against a previous revision
+    interdiff:    bool,
+    /// Where in the repository `diff_text` came from, for `--watch`
+    local:        Option<vcs::LocalDiff>,
+}
+
+impl DiffInput {
+    /// A diff with no forge link or pull request attached.
+    fn new(diff_text: String, commits: Vec<CommitInfo>) -> Self {
+        Self { diff_text, commits, forge: None, pull_request: None, interdiff: false, local: None }
+    }
+}
+
+/// What the TUI opens: a diff to generate a walkthrough of, a walkthrough
+/// saved earlier, or an interrupted review picked with `docent sessions`.
+enum ReviewSource {
+    Diff(DiffInput),
+    Saved(export::WalkthroughFile),
+    Restore(recovery::RecoveryOffer),
 }
 
 async fn read_diff_input(args: &Args) -> io::Result<Option<DiffInput>> {
@@ -236,30 +655,23 @@ async fn read_diff_input(args: &Args) -> io::Result<Option<DiffInput>> {
     }
 
     if let Some(input) = &args.diff_file {
-        if let Some(parsed) = github::parse_github_url(input) {
-            let diff = github::fetch_diff(input).await.map_err(io::Error::other)?;
-            let commits = if let github::GitHubUrl::PullRequest { owner, repo, number } = parsed {
-                github::fetch_pr_commits(owner, repo, number)
-                    .await
-                    .unwrap_or_default()
-            } else {
-                vec![]
-            };
-            return Ok(Some(DiffInput { diff_text: diff, commits }));
-        }
-        if input.starts_with("https://") || input.starts_with("http://") {
-            return Err(io::Error::other(format!(
-                "Unsupported URL: {input}\nExpected a GitHub URL like:\n  https://github.com/owner/repo/pull/123\n  https://github.com/owner/repo/commit/<sha>\n  https://github.com/owner/repo/compare/base...head"
-            )));
-        }
-        if is_git_range(input) {
-            return read_git_range(input).await.map(Some);
-        }
-        return Ok(Some(DiffInput { diff_text: std::fs::read_to_string(input)?, commits: vec![] }));
+        return read_diff_source(input).await.map(Some);
+    }
+
+    if let Some(number) = args.pr {
+        let (owner, repo) = github::origin_repo().await.map_err(io::Error::other)?;
+        let url = github::pull_request_url(&owner, &repo, number);
+        return read_diff_source(&url).await.map(Some);
     }
 
-    // Check if stdin is piped - read from it before crossterm initializes
-    // The "use-dev-tty" feature in crossterm will handle terminal events from /dev/tty
+    if let Some(spec) = &args.git {
+        let (diff_text, commits) = vcs::read_revision(spec).await?;
+        let local = Some(vcs::LocalDiff::Revision(spec.clone()));
+        return Ok(Some(DiffInput { local, ..DiffInput::new(diff_text, commits) }));
+    }
+
+    // Check if stdin is piped - read from it before crossterm initializes.
+    // Key events then come from the console instead (see `console`).
     if !std::io::stdin().is_terminal() {
         let mut input = String::new();
         std::io::stdin().read_to_string(&mut input)?;
@@ -267,91 +679,238 @@ async fn read_diff_input(args: &Args) -> io::Result<Option<DiffInput>> {
         if input.trim().is_empty() {
             return Ok(None);
         }
-        return Ok(Some(DiffInput { diff_text: input, commits: vec![] }));
+        return Ok(Some(DiffInput::new(input, vec![])));
     }
 
-    Ok(None)
+    // No input given: review the working copy's uncommitted changes, if any
+    Ok(vcs::read_working_copy().await.map(|diff_text| DiffInput {
+        local: Some(vcs::LocalDiff::WorkingCopy),
+        ..DiffInput::new(diff_text, vec![])
+    }))
 }
 
-fn is_git_range(input: &str) -> bool {
-    (input.contains("..") || input.contains("..."))
-        && !input.starts_with("http")
-        && !std::path::Path::new(input).exists()
+/// Reads a diff from an explicit source: a GitHub URL, a revision range, or a file path.
+async fn read_diff_source(input: &str) -> io::Result<DiffInput> {
+    if let Some(parsed) = github::parse_github_url(input) {
+        let diff = github::fetch_diff(input).await.map_err(io::Error::other)?;
+        let mut diff_input = DiffInput::new(diff, vec![]);
+        if let github::GitHubUrl::PullRequest { owner, repo, number } = parsed {
+            diff_input.commits = github::fetch_pr_commits(owner, repo, number)
+                .await
+                .unwrap_or_default();
+            diff_input.pull_request = github::fetch_pr_info(owner, repo, number).await.ok();
+        }
+        diff_input.forge = github::ForgeLink::from_url(input);
+        return Ok(diff_input);
+    }
+    if input.starts_with("https://") || input.starts_with("http://") {
+        return Err(io::Error::other(format!(
+            "Unsupported URL: {input}\nExpected a GitHub URL like:\n  https://github.com/owner/repo/pull/123\n  https://github.com/owner/repo/commit/<sha>\n  https://github.com/owner/repo/compare/base...head"
+        )));
+    }
+    if vcs::is_range(input) {
+        let (diff_text, commits) = vcs::read_range(input).await?;
+        let local = Some(vcs::LocalDiff::Revision(input.to_string()));
+        return Ok(DiffInput { local, ..DiffInput::new(diff_text, commits) });
+    }
+    Ok(DiffInput::new(std::fs::read_to_string(input)?, vec![]))
+}
+
+/// Replaces `input`'s diff with what changed since the `previous` revision.
+async fn compare_with_previous(previous: &str, input: DiffInput) -> io::Result<DiffInput> {
+    let previous = read_diff_source(previous).await?;
+    let diff_text = diff::interdiff(&previous.diff_text, &input.diff_text)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
+    if diff_text.is_empty() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "no changes since the previous revision",
+        ));
+    }
+    Ok(DiffInput { diff_text, interdiff: true, ..input })
 }
 
-async fn read_git_range(range: &str) -> io::Result<DiffInput> {
-    let diff_output = tokio::process::Command::new("git")
-        .args(["diff", range])
-        .output()
-        .await
-        .map_err(|e| io::Error::other(format!("failed to run git diff: {e}")))?;
+#[tokio::main]
+async fn main() -> io::Result<()> {
+    let mut args = Args::parse();
 
-    if !diff_output.status.success() {
-        let stderr = String::from_utf8_lossy(&diff_output.stderr);
-        return Err(io::Error::other(format!("git diff {range} failed: {stderr}")));
+    // `review` is the default TUI and `generate` is `--print`, each with its own source
+    if let Some(Command::Review { diff_file } | Command::Generate { diff_file }) =
+        &mut args.command
+    {
+        args.diff_file = diff_file.take().or(args.diff_file.take());
+        args.print |= matches!(args.command, Some(Command::Generate { .. }));
+        args.command = None;
     }
 
-    let diff_text = String::from_utf8_lossy(&diff_output.stdout).into_owned();
-    if diff_text.trim().is_empty() {
-        return Err(io::Error::other(format!("git diff {range} produced no output")));
+    // Build and validate the file filter early
+    let mut filter = FileFilter::new(&args.filters, &args.excludes)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e.to_string()))?;
+    if !args.no_default_excludes {
+        filter = filter
+            .with_denylist(&Settings::load().filter.default_excludes)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e.to_string()))?;
+    }
+    if let Some(ignore_file) = settings::project_root()
+        .map(|root| IgnoreFile::load(&root))
+        .transpose()
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e.to_string()))?
+        .flatten()
+    {
+        filter = filter.with_ignore_file(ignore_file);
     }
 
-    let commits = read_git_log(range).await.unwrap_or_default();
+    let mode = if args.walkthrough {
+        ReviewMode::Walkthrough
+    } else {
+        ReviewMode::default()
+    };
 
-    Ok(DiffInput { diff_text, commits })
-}
+    let project = settings::ProjectSettings::load().map_err(|e| {
+        let file = settings::PROJECT_SETTINGS_FILE;
+        io::Error::new(io::ErrorKind::InvalidInput, format!("{file}: {e}"))
+    })?;
+    let organization = args.group_by.or(project.organize_by).unwrap_or_default();
+    let overrides = RunOverrides::from_args(&args, project.prompts);
 
-const GIT_LOG_SEPARATOR: &str = "---commit-boundary---";
+    if let Some(Command::Stats) = &args.command {
+        print!("{}", stats::summary(&stats::load(), Settings::load().stats.enabled));
+        return Ok(());
+    }
 
-async fn read_git_log(range: &str) -> io::Result<Vec<CommitInfo>> {
-    let format = format!("{GIT_LOG_SEPARATOR}%n%H%n%s");
-    let output = tokio::process::Command::new("git")
-        .args(["log", "--reverse", &format!("--format={format}"), "--name-only", range])
-        .output()
-        .await?;
+    let restore = match &args.command {
+        Some(Command::Sessions { restore: None }) => {
+            print!("{}", recovery::summary(&recovery::RecoveryOffer::all()));
+            return Ok(());
+        }
+        Some(Command::Sessions {
+            restore: Some(number),
+        }) => {
+            let offer = number
+                .checked_sub(1)
+                .and_then(|index| recovery::RecoveryOffer::all().into_iter().nth(index));
+            Some(offer.ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("No interrupted review {number}; see docent sessions"),
+                )
+            })?)
+        }
+        _ => None,
+    };
 
-    if !output.status.success() {
-        return Err(io::Error::other("git log failed"));
+    if let Some(Command::Feedback { json }) = &args.command {
+        let entries = feedback::load();
+        if *json {
+            let json = serde_json::to_string_pretty(&entries).map_err(io::Error::other)?;
+            println!("{json}");
+        } else {
+            print!("{}", feedback::summary(&entries));
+        }
+        return Ok(());
     }
 
-    let text = String::from_utf8_lossy(&output.stdout);
-    let mut commits = Vec::new();
+    // stdin carries the RPC stream in serve mode, so only an explicit source is read
+    if let Some(Command::Serve { diff_file }) = &args.command {
+        let diff_input = match diff_file {
+            Some(input) => Some(read_diff_source(input).await?),
+            None => None,
+        };
+        return headless::serve(diff_input, filter, mode, organization, &overrides).await;
+    }
 
-    for block in text.split(GIT_LOG_SEPARATOR).skip(1) {
-        let mut lines = block.lines().filter(|l| !l.is_empty());
-        let Some(sha) = lines.next() else { continue };
-        let Some(message) = lines.next() else { continue };
-        let files: Vec<String> = lines.map(String::from).collect();
-        commits.push(CommitInfo {
-            sha:     sha.to_string(),
-            message: message.to_string(),
-            files,
+    if let Some(Command::Export { diff_file, output, json }) = &args.command {
+        let diff_input = match diff_file {
+            Some(input) => Some(read_diff_source(input).await?),
+            None => read_diff_input(&args).await?,
+        };
+        let format = if *json { ReportFormat::Json } else { args.format };
+        let output = output.as_deref().unwrap_or(match format {
+            ReportFormat::Markdown => export::MARKDOWN_EXPORT_FILE,
+            ReportFormat::Json => export::JSON_EXPORT_FILE,
         });
+        return headless::export(
+            diff_input,
+            filter,
+            mode,
+            organization,
+            &overrides,
+            output,
+            format,
+        )
+        .await;
     }
 
-    Ok(commits)
-}
+    let opened = match &args.from_walkthrough {
+        Some(path) => Some(export::WalkthroughFile::read(std::path::Path::new(path))?),
+        None => None,
+    };
+    let mut diff_input = if opened.is_some() || restore.is_some() {
+        None
+    } else {
+        read_diff_input(&args).await?
+    };
+    if let Some(previous) = &args.since {
+        let input = diff_input.ok_or_else(|| {
+            io::Error::new(io::ErrorKind::InvalidInput, "--since requires a diff to compare")
+        })?;
+        diff_input = Some(compare_with_previous(previous, input).await?);
+    }
 
-#[tokio::main]
-async fn main() -> io::Result<()> {
-    let args = Args::parse();
-    let diff_input = read_diff_input(&args).await?;
+    if args.by_commit && diff_input.as_ref().is_none_or(|input| input.commits.is_empty()) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "--by-commit needs a git revision range with at least one commit",
+        ));
+    }
 
-    // Build and validate the file filter early
-    let filter = FileFilter::new(&args.filters, &args.excludes)
-        .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e.to_string()))?;
+    if args.watch && diff_input.as_ref().is_none_or(|input| input.local.is_none()) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "--watch needs changes in the working copy or a revision range to watch",
+        ));
+    }
 
-    let mode = if args.walkthrough {
-        ReviewMode::Walkthrough
-    } else {
-        ReviewMode::default()
-    };
+    // Exit codes: 0 on success, 1 if generation fails, 2 for bad input or setup
+    if args.print {
+        let result = headless::export(
+            diff_input,
+            filter,
+            mode,
+            organization,
+            &overrides,
+            "-",
+            args.format,
+        )
+        .await;
+        if let Err(e) = result {
+            eprintln!("docent: {e}");
+            let code = if e.kind() == io::ErrorKind::InvalidInput { 2 } else { 1 };
+            std::process::exit(code);
+        }
+        return Ok(());
+    }
 
     if args.headless {
-        return headless::run(diff_input, filter, mode).await;
+        return headless::run(diff_input, filter, mode, organization, &overrides).await;
+    }
+
+    let script = match &args.script {
+        Some(path) => {
+            let text = std::fs::read_to_string(path)?;
+            let steps = script::parse(&text)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e.to_string()))?;
+            Some(steps)
+        }
+        None => None,
+    };
+
+    if !std::io::stdin().is_terminal() {
+        console::ensure_console_input()?;
     }
 
     // Emit OSC 7 so tmux knows our working directory for new panes/windows
+    #[cfg(unix)]
     if let Ok(cwd) = std::env::current_dir() {
         use std::io::Write;
         let _ = write!(stdout(), "\x1b]7;file://localhost{}\x1b\\", cwd.display());
@@ -361,13 +920,20 @@ async fn main() -> io::Result<()> {
     // Setup terminal
     enable_raw_mode()?;
     stdout().execute(EnterAlternateScreen)?;
-    stdout().execute(EnableMouseCapture)?;
+    // Best effort: legacy Windows consoles don't support bracketed paste
+    let _ = stdout().execute(EnableBracketedPaste);
     let mut terminal = Terminal::new(CrosstermBackend::new(stdout()))?;
 
     // Run app
-    let result = run_app(&mut terminal, diff_input, filter, mode).await;
+    let source = match (opened, restore) {
+        (Some(file), _) => Some(ReviewSource::Saved(file)),
+        (None, Some(offer)) => Some(ReviewSource::Restore(offer)),
+        (None, None) => diff_input.map(ReviewSource::Diff),
+    };
+    let result = run_app(&mut terminal, source, filter, mode, organization, overrides, script).await;
 
     // Restore terminal
+    let _ = stdout().execute(DisableBracketedPaste);
     stdout().execute(DisableMouseCapture)?;
     disable_raw_mode()?;
     stdout().execute(LeaveAlternateScreen)?;
@@ -375,47 +941,172 @@ async fn main() -> io::Result<()> {
     result
 }
 
+/// Command-line and project settings that apply to this run only.
+struct RunOverrides {
+    linear: bool,
+    no_mouse: bool,
+    no_cache: bool,
+    /// Read the diff again while reviewing (see `spawn_diff_watcher`)
+    watch: bool,
+    /// Generate one section per commit (see `generate_by_commit`)
+    by_commit: bool,
+    provider: Option<settings::Provider>,
+    model: Option<String>,
+    persona: Option<String>,
+    /// The `prompts` of the project's .docent.json
+    prompts: Option<PromptSettings>,
+}
+
+impl RunOverrides {
+    fn from_args(args: &Args, prompts: Option<PromptSettings>) -> Self {
+        Self {
+            linear: args.linear,
+            no_mouse: args.no_mouse,
+            no_cache: args.no_cache,
+            watch: args.watch,
+            by_commit: args.by_commit,
+            provider: args.provider,
+            model: args.model.clone(),
+            persona: args.persona.clone(),
+            prompts,
+        }
+    }
+
+    /// `settings` with the overrides applied. The result is for this run
+    /// only and is never written back.
+    fn apply(&self, settings: &Settings) -> Settings {
+        let mut settings = settings.clone();
+        settings.accessibility.linear_mode |= self.linear;
+        settings.terminal.disable_mouse_capture |= self.no_mouse;
+        settings.cache.enabled &= !self.no_cache;
+        if let Some(provider) = self.provider {
+            settings.provider = provider;
+        }
+        if let Some(model) = &self.model {
+            settings.api.model = Some(model.clone());
+        }
+        if let Some(prompts) = &self.prompts {
+            settings.api.prompts = settings.api.prompts.overridden_by(prompts);
+        }
+        if let Some(persona) = &self.persona {
+            settings.api.prompts.persona = Some(persona.clone());
+        }
+        settings
+    }
+}
+
 async fn run_app<B: Backend + Send>(
     terminal: &mut Terminal<B>,
-    diff_input: Option<DiffInput>,
+    source: Option<ReviewSource>,
     filter: FileFilter,
     mode: ReviewMode,
+    organization: StepOrganization,
+    overrides: RunOverrides,
+    script: Option<Vec<script::ScriptStep>>,
 ) -> io::Result<()> {
     let mut settings = Settings::load();
+    let run_settings = overrides.apply(&settings);
 
-    let mut app = if let Some(diff) = diff_input {
-        let mut app = App::setup(&settings, mode);
+    let mut watched = None;
+    let mut app = if let Some(ReviewSource::Saved(file)) = source {
+        let mut app = App::new(file.walkthrough, &run_settings, file.mode);
+        app.session.reviewed_steps = file.reviewed;
+        app
+    } else if let Some(ReviewSource::Diff(diff)) = source {
+        if overrides.watch {
+            watched = diff.local.map(|local| (local, diff.diff_text.clone()));
+        }
+        let mut app = App::setup(&run_settings, mode);
         app.session.commits = diff.commits;
+        app.session.pull_request = diff.pull_request;
         app.session.diff_input = Some(diff.diff_text);
+        app.session.interdiff = diff.interdiff;
+        app.session.forge = diff.forge;
         app.session.diff_filter = filter;
+        app.session.step_organization = organization;
+        app.session.by_commit = overrides.by_commit;
+        app
+    } else if let Some(ReviewSource::Restore(offer)) = source {
+        let mut app = App::setup(&run_settings, mode);
+        app.recovery_offer = Some(offer);
         app
     } else {
         #[cfg(debug_assertions)]
         {
-            App::new(mock_walkthrough(), &settings, mode)
+            App::new(mock_walkthrough(), &run_settings, mode)
         }
         #[cfg(not(debug_assertions))]
         {
-            App::setup(&settings, mode)
+            App::setup(&run_settings, mode)
         }
     };
 
+    if matches!(app.session.state, session::SessionState::Setup)
+        && onboarding::Onboarding::is_needed(&run_settings)
+    {
+        app.onboarding = Some(onboarding::Onboarding::new());
+    }
+
+    if app.recovery_offer.is_none() {
+        app.recovery_offer = recovery::RecoveryOffer::find();
+    }
+    let mut autosaver = recovery::Autosaver::new();
+    let cwd = std::env::current_dir()
+        .map(|dir| dir.display().to_string())
+        .unwrap_or_default();
+
     let mut input_handler = InputHandler::new();
     let (tx, mut rx) = mpsc::channel::<AppEvent>(32);
 
     spawn_terminal_reader(tx.clone());
+    app.session.retry_notices = Some(spawn_retry_forwarder(tx.clone()));
+    if let Some(steps) = script {
+        spawn_script_player(tx.clone(), steps);
+    }
+    if let Some((local, diff_text)) = watched {
+        spawn_diff_watcher(tx.clone(), local, diff_text);
+    }
+
+    // With reduced motion nothing animates, so only redraw after an event
+    let mut needs_redraw = true;
+    // Mouse capture is enabled on the first iteration unless the settings turn it off
+    let mut mouse_captured = false;
+    let mut hook_tracker = hooks::HookTracker::default();
+    // Tasks for the requests that can be cancelled
+    let mut generation_task: Option<AbortHandle> = None;
+    let mut chat_task: Option<AbortHandle> = None;
 
     loop {
+        if app.mouse_capture != mouse_captured {
+            if app.mouse_capture {
+                stdout().execute(EnableMouseCapture)?;
+            } else {
+                stdout().execute(DisableMouseCapture)?;
+            }
+            mouse_captured = app.mouse_capture;
+        }
+
+        // Showing a step marks its answer as read
+        app.session.mark_chat_read();
+
         let viewport_height = terminal
             .size()?
             .height
             .saturating_sub(VIEWPORT_HEIGHT_OFFSET) as usize;
 
-        terminal.draw(|frame| {
-            ui::render(frame, &app);
-        })?;
+        if needs_redraw || !app.reduced_motion {
+            terminal.draw(|frame| {
+                ui::render(frame, &app);
+            })?;
+            needs_redraw = false;
+        }
+
+        if app.toasts.expire(std::time::Instant::now()) {
+            needs_redraw = true;
+        }
 
         if let Ok(Some(event)) = tokio::time::timeout(EVENT_RECV_TIMEOUT, rx.recv()).await {
+            needs_redraw = true;
             handle_app_event(
                 event,
                 &mut app,
@@ -425,21 +1116,33 @@ async fn run_app<B: Backend + Send>(
             )?;
         }
 
-        if let Some((step_index, walkthrough, messages)) = app.session.chat_request.take() {
-            spawn_chat_handler(
+        if std::mem::take(&mut app.session.chat_cancel_requested)
+            && let Some(task) = chat_task.take()
+        {
+            task.abort();
+        }
+        if std::mem::take(&mut app.session.generation_cancel_requested)
+            && let Some(task) = generation_task.take()
+        {
+            task.abort();
+        }
+
+        if let Some((thread, context, messages, summarize)) = app.session.chat_request.take() {
+            chat_task = Some(spawn_chat_handler(
                 tx.clone(),
-                app.session.api_key_input.clone(),
-                step_index,
-                walkthrough,
+                app.session.client_config(),
+                thread,
+                context,
                 messages,
+                summarize,
                 app.session.review_mode,
-            );
+            ));
         }
 
         if let Some((step_index, step, diff_text)) = app.session.rechunk_request.take() {
             spawn_rechunk(
                 tx.clone(),
-                app.session.api_key_input.clone(),
+                app.session.client_config(),
                 step_index,
                 step,
                 diff_text,
@@ -447,7 +1150,88 @@ async fn run_app<B: Backend + Send>(
             );
         }
 
+        if let Some((step_index, prompt)) = app.session.follow_up_request.take() {
+            spawn_follow_ups(tx.clone(), app.session.client_config(), step_index, prompt);
+        }
+
+        if let Some((step_index, context, messages)) = app.session.summary_request.take() {
+            spawn_summary(
+                tx.clone(),
+                app.session.client_config(),
+                step_index,
+                context,
+                messages,
+                app.session.review_mode,
+            );
+        }
+
+        if let Some((files, max_lines)) = app.session.partition_request.take() {
+            spawn_partition(
+                tx.clone(),
+                app.session.client_config(),
+                parts::partition_prompt(&files, max_lines),
+            );
+        }
+
+        if app.session.record_requested {
+            app.session.record_requested = false;
+            let session = &app.session;
+            spawn_review_record(
+                tx.clone(),
+                review_record::build_report(
+                    &session.walkthrough,
+                    &session.reviewed_steps,
+                    session.review_mode,
+                ),
+                review_record::verdict(&session.walkthrough, &session.reviewed_steps),
+            );
+        }
+
+        if app.session.pr_review_requested {
+            app.session.pr_review_requested = false;
+            if let Some(forge) = app.session.forge.clone() {
+                spawn_pr_review(tx.clone(), forge, app.session.pr_review());
+            }
+        }
+
+        if !settings.hooks.is_empty() {
+            for event in hook_tracker.observe(&app.session) {
+                for command in settings.hooks.commands(event.kind) {
+                    spawn_hook(tx.clone(), command.clone(), event.clone());
+                }
+            }
+        }
+
+        if let Some(onboarding) = app.onboarding_finished.take() {
+            onboarding.apply_to(&mut settings);
+            let _ = settings.save();
+        }
+
+        // A restored or declined snapshot is kept until this session's own is saved
+        if autosaver.tick(&app.session)
+            && let Some(path) = app.superseded_recovery.take()
+        {
+            recovery::discard(&path);
+        }
+
         if app.should_quit {
+            autosaver.clear();
+            if let Some(diff_text) = &app.session.diff_input
+                && !app.session.walkthrough.steps.is_empty()
+            {
+                let _ = chats::save(
+                    &chats::review_key(&cwd, diff_text),
+                    &app.session.walkthrough,
+                    app.session.saved_chats.as_ref(),
+                    settings.chat.history_days,
+                );
+            }
+            let review = stats::ReviewStats::from_session(&app.session, app.started.elapsed());
+            if settings.stats.enabled
+                && let Some(review) = review
+            {
+                let _ = stats::record(&review);
+            }
             break;
         }
 
@@ -464,14 +1248,17 @@ async fn run_app<B: Backend + Send>(
         if should_generate
             && let Some(diff_text) = app.session.diff_input.clone()
         {
-            spawn_walkthrough_generation(
+            app.session.saved_chats =
+                chats::load(&chats::review_key(&cwd, &diff_text), settings.chat.history_days);
+            if let Some(task) = generation_task.take() {
+                task.abort();
+            }
+            generation_task = Some(spawn_walkthrough_generation(
                 tx.clone(),
-                app.session.api_key_input.clone(),
+                &app.session,
                 diff_text,
-                app.session.diff_filter.clone(),
-                app.session.review_mode,
-                app.session.commits.clone(),
-            );
+                run_settings.cache.clone(),
+            ));
         }
     }
 
@@ -482,7 +1269,7 @@ fn handle_app_event<B: Backend>(
     event: AppEvent,
     app: &mut App,
     input_handler: &mut InputHandler,
-    terminal: &Terminal<B>,
+    terminal: &mut Terminal<B>,
     viewport_height: usize,
 ) -> io::Result<()> {
     match event {
@@ -492,40 +1279,91 @@ fn handle_app_event<B: Backend>(
         AppEvent::Terminal(Event::Mouse(mouse)) => {
             input_handler.handle_mouse(mouse, app, terminal.size()?);
         }
+        AppEvent::Terminal(Event::Paste(text)) => {
+            input_handler.handle_paste(text, app);
+        }
+        AppEvent::Terminal(Event::Resize(..)) => {
+            // Repaint everything: some terminals keep stale cells after a resize
+            terminal.clear()?;
+            app.handle_resize();
+        }
         AppEvent::Terminal(_) => {}
         AppEvent::GenerationComplete(usage) => {
             app.session.add_usage(usage);
-            if app.session.walkthrough.steps.is_empty() {
+            if !app.session.generation_in_progress {
+                // Cancelled after this was sent
+            } else if app.session.walkthrough.steps.is_empty() {
                 app.session.generation_in_progress = false;
                 app.session.set_error(
                     "Generation completed but no steps were produced".to_string(),
                 );
             } else {
                 app.session.generation_finished();
+                let count = app.session.walkthrough.step_count();
+                let s = app.strings;
+                app.notify(format!("{}: {} {}", s.toast_walkthrough_ready, count, s.toast_steps));
             }
         }
         AppEvent::GenerationError(message) => {
-            if app.session.generation_in_progress
-                && !app.session.walkthrough.steps.is_empty()
-            {
+            if !app.session.generation_in_progress {
+                // Cancelled after this was sent
+            } else if !app.session.walkthrough.steps.is_empty() {
                 app.session.generation_finished();
             } else {
                 app.session.generation_in_progress = false;
                 app.session.set_error(message);
             }
         }
+        AppEvent::DiffChanged(diff_text, commits) => {
+            app.receive_diff_update(diff_text, commits);
+        }
+        AppEvent::LoadedFromCache => {
+            app.notify(app.strings.toast_loaded_from_cache);
+        }
+        AppEvent::GenerationStarted(model) => {
+            app.session.receive_generation_started(model);
+        }
+        AppEvent::GenerationProgress(tokens) => {
+            app.session.receive_generation_progress(tokens);
+        }
         AppEvent::StepReady(step) => {
             app.session.receive_step_ready(step);
         }
-        AppEvent::ChatChunk(step_index, chunk) => {
-            app.session.receive_chat_chunk(step_index, chunk);
+        AppEvent::ChatChunk(thread, chunk) => {
+            app.session.receive_chat_chunk(thread, chunk);
         }
-        AppEvent::ChatComplete(step_index, usage) => {
-            app.session.add_usage(usage);
-            app.session.receive_chat_complete(step_index);
+        AppEvent::ChatComplete(thread, usage) => {
+            app.session.add_chat_usage(usage);
+            app.session.receive_chat_complete(thread);
+            let s = app.strings;
+            let label = chat_thread_label(s, thread);
+            app.announce(format!("{} {}", s.toast_response_ready, label));
+            if thread != app.session.chat_thread() {
+                app.notify(format!("{} {}", s.toast_chat_ready, label));
+            }
         }
-        AppEvent::ChatError(step_index, error) => {
-            app.session.receive_chat_error(step_index, error);
+        AppEvent::ChatError(thread, error) => {
+            app.session.receive_chat_error(thread, error);
+            let s = app.strings;
+            let label = chat_thread_label(s, thread);
+            app.announce(format!("{} {}", s.toast_chat_error, label));
+            if thread != app.session.chat_thread() {
+                app.notify_error(format!("{} {}", s.toast_chat_error, label));
+            }
+        }
+        AppEvent::ChatSummarized(thread, covered, summary, usage) => {
+            app.session.add_chat_usage(usage);
+            app.session.receive_chat_summary(thread, covered, summary);
+        }
+        AppEvent::ChatSummaryError(thread, error) => {
+            let s = app.strings;
+            app.notify_error(format!(
+                "{} {}, {}: {}",
+                s.toast_chat_summary_failed,
+                chat_thread_label(s, thread),
+                s.toast_chat_summary_left_out,
+                error
+            ));
         }
         AppEvent::RechunkComplete(step_index, sub_steps, usage) => {
             app.session.add_usage(usage);
@@ -534,6 +1372,47 @@ fn handle_app_event<B: Backend>(
         AppEvent::RechunkError(error) => {
             app.session.receive_rechunk_error(error);
         }
+        AppEvent::FollowUpsSuggested(step_index, result) => {
+            // Suggestions are optional, so a failed request goes unmentioned
+            if let Ok((response, usage)) = result {
+                app.session.add_chat_usage(usage);
+                app.session.receive_follow_ups(step_index, response.questions);
+            }
+        }
+        AppEvent::SummaryComplete(step_index, summary, usage) => {
+            app.session.add_chat_usage(usage);
+            app.session.receive_summary_complete(step_index, summary);
+            let s = app.strings;
+            app.notify(format!("{} {}", s.toast_summary_rewritten, step_index + 1));
+        }
+        AppEvent::SummaryError(step_index, error) => {
+            app.session.receive_summary_error();
+            let s = app.strings;
+            app.notify_error(format!("{} {}: {}", s.toast_summary_failed, step_index + 1, error));
+        }
+        AppEvent::ReviewRecorded(result) => app.receive_review_recorded(result),
+        AppEvent::PrReviewPosted(result) => app.receive_pr_review_posted(result),
+        AppEvent::HookFinished(outcome) => {
+            let s = app.strings;
+            let status = if outcome.success { s.toast_hook_passed } else { s.toast_hook_failed };
+            let mut message = format!("{} `{}` {}", s.toast_hook, outcome.command, status);
+            if !outcome.output.is_empty() {
+                message.push_str(&format!("\n{}", outcome.output));
+            }
+            if outcome.success {
+                app.notify(message);
+            } else {
+                app.notify_error(message);
+            }
+        }
+        AppEvent::PartsSuggested(result) => {
+            let result = result.map(|(response, usage)| {
+                app.session.add_usage(usage);
+                response
+            });
+            app.receive_partition(result);
+        }
+        AppEvent::RetryScheduled(notice) => app.set_loading_status(notice),
     }
     Ok(())
 }
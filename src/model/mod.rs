@@ -1,6 +1,11 @@
+pub mod related;
 pub mod walkthrough;
 
-pub use walkthrough::{CommitInfo, Hunk, Message, MessageRole, Priority, ReviewMode, Step, Walkthrough};
+pub use related::Relation;
+pub use walkthrough::{
+    CommitInfo, Flag, Hunk, Message, MessageRole, Note, Priority, PriorityFilter, PullRequestInfo,
+    ReviewMode, Severity, Step, StepOrder, StepOrganization, Walkthrough,
+};
 
 #[cfg(debug_assertions)]
 pub use walkthrough::mock_walkthrough;
@@ -0,0 +1,211 @@
+//! Links between steps that touch the same code.
+//!
+//! Two steps are related when they change nearby lines of the same file, or
+//! when one defines a symbol that the other's changed lines mention, so a
+//! definition change can be followed to its call-site updates.
+
+use std::collections::HashSet;
+
+use super::{Step, Walkthrough};
+
+/// Hunks in the same file within this many lines count as the same region.
+const REGION_GAP: usize = 10;
+
+/// Shortest symbol name considered, to avoid linking on `new`, `id`, etc.
+const MIN_SYMBOL_LEN: usize = 4;
+
+/// Keywords that introduce a definition in common languages.
+const DEFINITION_KEYWORDS: &[&str] = &[
+    "fn",
+    "struct",
+    "enum",
+    "trait",
+    "type",
+    "const",
+    "static",
+    "mod",
+    "class",
+    "interface",
+    "def",
+    "func",
+    "function",
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Relation {
+    /// Both steps change nearby lines of this file
+    SameRegion(String),
+    /// One step defines this symbol and the other mentions it
+    Symbol(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RelatedStep {
+    pub index: usize,
+    pub relation: Relation,
+}
+
+impl Walkthrough {
+    /// Steps related to the step at `index`, in step order, each with the
+    /// first reason found.
+    pub fn related_steps(&self, index: usize) -> Vec<RelatedStep> {
+        let Some(step) = self.steps.get(index) else {
+            return vec![];
+        };
+        let defined = defined_symbols(step);
+        let mentioned = mentioned_words(step);
+
+        self.steps
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| *i != index)
+            .filter_map(|(i, other)| {
+                let relation = shared_region(step, other)
+                    .map(Relation::SameRegion)
+                    .or_else(|| {
+                        shared_symbol(&defined, &mentioned_words(other))
+                            .or_else(|| shared_symbol(&defined_symbols(other), &mentioned))
+                            .map(Relation::Symbol)
+                    })?;
+                Some(RelatedStep { index: i, relation })
+            })
+            .collect()
+    }
+}
+
+fn shared_region(a: &Step, b: &Step) -> Option<String> {
+    a.hunks.iter().find_map(|ha| {
+        b.hunks
+            .iter()
+            .any(|hb| {
+                ha.file_path == hb.file_path
+                    && ha.start_line <= hb.end_line + REGION_GAP
+                    && hb.start_line <= ha.end_line + REGION_GAP
+            })
+            .then(|| ha.file_path.clone())
+    })
+}
+
+fn shared_symbol(defined: &[String], mentioned: &HashSet<String>) -> Option<String> {
+    defined
+        .iter()
+        .find(|name| mentioned.contains(*name))
+        .cloned()
+}
+
+fn changed_lines(step: &Step) -> impl Iterator<Item = &str> {
+    step.hunks.iter().flat_map(|hunk| {
+        hunk.content
+            .lines()
+            .filter(|line| {
+                (line.starts_with('+') && !line.starts_with("+++"))
+                    || (line.starts_with('-') && !line.starts_with("---"))
+            })
+            .map(|line| &line[1..])
+    })
+}
+
+fn words(line: &str) -> impl Iterator<Item = &str> {
+    line.split(|c: char| !(c.is_alphanumeric() || c == '_'))
+        .filter(|word| !word.is_empty())
+}
+
+/// Names following a definition keyword on the step's changed lines, in
+/// order of first appearance.
+fn defined_symbols(step: &Step) -> Vec<String> {
+    let mut symbols: Vec<String> = Vec::new();
+    for line in changed_lines(step) {
+        let mut tokens = words(line);
+        while let Some(token) = tokens.next() {
+            if !DEFINITION_KEYWORDS.contains(&token) {
+                continue;
+            }
+            if let Some(name) = tokens.next()
+                && name.len() >= MIN_SYMBOL_LEN
+                && !name.starts_with(|c: char| c.is_ascii_digit())
+                && !symbols.iter().any(|s| s == name)
+            {
+                symbols.push(name.to_string());
+            }
+            break;
+        }
+    }
+    symbols
+}
+
+fn mentioned_words(step: &Step) -> HashSet<String> {
+    changed_lines(step)
+        .flat_map(words)
+        .filter(|word| word.len() >= MIN_SYMBOL_LEN)
+        .map(String::from)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::Hunk;
+
+    fn step(hunks: &[(&str, usize, usize, &str)]) -> Step {
+        Step {
+            hunks: hunks
+                .iter()
+                .map(|&(path, start, end, content)| Hunk::test(path, start, end, content))
+                .collect(),
+            ..Step::test("", "")
+        }
+    }
+
+    #[test]
+    fn test_definition_links_to_call_sites() {
+        let walkthrough = Walkthrough {
+            steps: vec![
+                step(&[(
+                    "src/session.rs",
+                    10,
+                    12,
+                    "@@ -10,1 +10,2 @@\n-pub fn validate(&self) {\n+pub fn is_valid(&self) -> bool {",
+                )]),
+                step(&[(
+                    "src/api.rs",
+                    40,
+                    41,
+                    "@@ -40,1 +40,1 @@\n-    session.validate();\n+    if !session.is_valid() {",
+                )]),
+                step(&[("README.md", 1, 2, "@@ -1,1 +1,1 @@\n-Old text\n+New text")]),
+            ],
+            messages: vec![],
+        };
+
+        assert_eq!(
+            walkthrough.related_steps(0),
+            [RelatedStep {
+                index: 1,
+                relation: Relation::Symbol("validate".to_string()),
+            }]
+        );
+        assert_eq!(walkthrough.related_steps(1)[0].index, 0);
+        assert!(walkthrough.related_steps(2).is_empty());
+    }
+
+    #[test]
+    fn test_nearby_hunks_in_same_file() {
+        let walkthrough = Walkthrough {
+            steps: vec![
+                step(&[("src/lib.rs", 10, 20, "@@ -10,1 +10,1 @@\n+a")]),
+                step(&[("src/lib.rs", 25, 30, "@@ -25,1 +25,1 @@\n+b")]),
+                step(&[("src/lib.rs", 200, 210, "@@ -200,1 +200,1 @@\n+c")]),
+            ],
+            messages: vec![],
+        };
+
+        assert_eq!(
+            walkthrough.related_steps(0),
+            [RelatedStep {
+                index: 1,
+                relation: Relation::SameRegion("src/lib.rs".to_string()),
+            }]
+        );
+        assert!(walkthrough.related_steps(2).is_empty());
+    }
+}
@@ -1,13 +1,80 @@
+use std::ops::Range;
+
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CommitInfo {
-    pub sha:     String,
+    pub sha: String,
     pub message: String,
-    pub files:   Vec<String>,
+    pub files: Vec<String>,
+}
+
+/// A concern or reminder the reviewer jotted down on a step.
+#[derive(Debug, Clone, PartialEq, Hash, Serialize, Deserialize)]
+pub struct Note {
+    pub text: String,
+}
+
+/// How serious a flagged concern is, declared from most to least serious.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Severity {
+    /// Must be addressed before merging
+    Blocker,
+    /// Needs an answer from the author
+    Question,
+    /// Minor, take it or leave it
+    Nit,
+}
+
+impl Severity {
+    pub fn next(self) -> Self {
+        match self {
+            Self::Blocker => Self::Question,
+            Self::Question => Self::Nit,
+            Self::Nit => Self::Blocker,
+        }
+    }
+
+    /// The name used in exports; the UI uses `Strings::severity_label`.
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Blocker => "blocker",
+            Self::Question => "question",
+            Self::Nit => "nit",
+        }
+    }
+}
+
+/// A concern the reviewer raised on a step, or on one of its hunks.
+#[derive(Debug, Clone, PartialEq, Hash, Serialize, Deserialize)]
+pub struct Flag {
+    pub severity: Severity,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub text: String,
+    /// File and first line of the flagged hunk; none when the whole step is
+    /// flagged
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub location: Option<(String, usize)>,
+}
+
+impl Flag {
+    /// The flagged hunk as `path:line`, if a hunk was flagged.
+    pub fn location_label(&self) -> Option<String> {
+        self.location
+            .as_ref()
+            .map(|(path, line)| format!("{}:{}", path, line))
+    }
+}
+
+/// Title and description of the pull request a diff was fetched from.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PullRequestInfo {
+    pub title: String,
+    pub body: String,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum ReviewMode {
     Walkthrough,
@@ -15,7 +82,39 @@ pub enum ReviewMode {
     Review,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+/// How generated steps are organized.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "snake_case")]
+pub enum StepOrganization {
+    /// Steps follow the story of the change
+    #[default]
+    Narrative,
+    /// One area of the codebase (directory or module) per step
+    Directory,
+}
+
+/// Order in which steps are listed once generated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum StepOrder {
+    /// The model's narrative order
+    #[default]
+    Narrative,
+    /// Sorted by the first file path (and line) each step touches
+    FilePath,
+}
+
+impl StepOrder {
+    pub fn toggled(self) -> Self {
+        match self {
+            Self::Narrative => Self::FilePath,
+            Self::FilePath => Self::Narrative,
+        }
+    }
+}
+
+/// Declared from most to least important, so the lesser of two is the more
+/// important.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum Priority {
     Critical,
     Normal,
@@ -32,6 +131,34 @@ impl Priority {
     }
 }
 
+/// Which steps the step list shows and step navigation visits, for reviews
+/// on a time budget.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PriorityFilter {
+    #[default]
+    All,
+    HideMinor,
+    CriticalOnly,
+}
+
+impl PriorityFilter {
+    pub fn next(self) -> Self {
+        match self {
+            Self::All => Self::HideMinor,
+            Self::HideMinor => Self::CriticalOnly,
+            Self::CriticalOnly => Self::All,
+        }
+    }
+
+    pub fn shows(self, priority: Priority) -> bool {
+        match self {
+            Self::All => true,
+            Self::HideMinor => priority != Priority::Minor,
+            Self::CriticalOnly => priority == Priority::Critical,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum MessageRole {
     Assistant,
@@ -66,6 +193,118 @@ pub struct Hunk {
     pub start_line: usize,
     pub end_line: usize,
     pub content: String,
+    /// Enclosing function or type, e.g. `impl Session > fn next_step`
+    #[serde(default)]
+    pub symbol: Option<String>,
+    /// Marked reviewed on its own, for finer progress within a large step
+    #[serde(default)]
+    pub reviewed: bool,
+    /// Collapsed to its @@ header in the diff viewer
+    #[serde(skip)]
+    pub folded: bool,
+}
+
+impl Hunk {
+    /// Whether `other` makes the same change to the same file, wherever it
+    /// now sits in the file (the @@ header is ignored).
+    pub fn same_change(&self, other: &Hunk) -> bool {
+        fn body(content: &str) -> &str {
+            match content.split_once('\n') {
+                Some((header, rest)) if header.starts_with("@@") => rest,
+                _ if content.starts_with("@@") => "",
+                _ => content,
+            }
+        }
+        self.file_path == other.file_path && body(&self.content) == body(&other.content)
+    }
+
+    /// Lines of `content` shown in the diff: all of them, or while folded
+    /// just the @@ header with a count of the changed lines.
+    pub fn shown_lines(&self) -> Vec<String> {
+        if !self.folded {
+            return self.content.lines().map(str::to_string).collect();
+        }
+        let header = self.content.lines().next().filter(|l| l.starts_with("@@"));
+        let body = self.content.lines().skip(usize::from(header.is_some()));
+        let (mut added, mut removed) = (0, 0);
+        for line in body {
+            if line.starts_with('+') {
+                added += 1;
+            } else if line.starts_with('-') {
+                removed += 1;
+            }
+        }
+        vec![format!(
+            "{} (+{} −{}, folded)",
+            header.unwrap_or("@@"),
+            added,
+            removed
+        )]
+    }
+
+    /// Number of `shown_lines`.
+    pub fn shown_line_count(&self) -> usize {
+        if self.folded {
+            1
+        } else {
+            self.content.lines().count()
+        }
+    }
+
+    /// New-file line number for the line at `index` within `content` (the @@
+    /// header maps to the hunk's first line; removed lines map to the next
+    /// surviving line).
+    pub fn new_line_at(&self, index: usize) -> usize {
+        let lines: Vec<&str> = self.content.lines().collect();
+        let (new_start, body_start) = match lines.first() {
+            Some(header) if header.starts_with("@@") => (
+                crate::diff::parse_hunk_header(header)
+                    .map(|(_, new)| new)
+                    .unwrap_or(self.start_line),
+                1,
+            ),
+            _ => (self.start_line, 0),
+        };
+        if index <= body_start {
+            return new_start;
+        }
+        let end = index.min(lines.len());
+        new_start
+            + lines[body_start..end]
+                .iter()
+                .filter(|l| !l.starts_with('-'))
+                .count()
+    }
+
+    /// Old- and new-file line numbers of each line of `content`: removed
+    /// lines only have an old number, added lines only a new one, and the @@
+    /// header has neither.
+    pub fn line_numbers(&self) -> Vec<(Option<usize>, Option<usize>)> {
+        let (mut old, mut new) = self
+            .content
+            .lines()
+            .next()
+            .and_then(crate::diff::parse_hunk_header)
+            .unwrap_or((self.start_line, self.start_line));
+        self.content
+            .lines()
+            .map(|line| {
+                if line.starts_with("@@") {
+                    (None, None)
+                } else if line.starts_with('-') {
+                    old += 1;
+                    (Some(old - 1), None)
+                } else if line.starts_with('+') {
+                    new += 1;
+                    (None, Some(new - 1))
+                } else {
+                    old += 1;
+                    new += 1;
+                    (Some(old - 1), Some(new - 1))
+                }
+            })
+            .collect()
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -79,6 +318,12 @@ pub struct Step {
     pub messages: Vec<Message>,
     #[serde(default)]
     pub depth: u8,
+    /// The reviewer's own notes, kept in exports and posted as PR review comments
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub notes: Vec<Note>,
+    /// Concerns the reviewer flagged, gathered into the review summary
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub flags: Vec<Flag>,
 }
 
 impl Step {
@@ -88,24 +333,153 @@ impl Step {
     pub fn display_lines(&self) -> Vec<String> {
         let mut lines = Vec::new();
         for hunk in &self.hunks {
-            lines.push(format!("─── {} ───", hunk.file_path));
-            lines.push(String::new());
-            for line in hunk.content.lines() {
-                lines.push(line.to_string());
+            match &hunk.symbol {
+                Some(symbol) => lines.push(format!("─── {} · {} ───", hunk.file_path, symbol)),
+                None => lines.push(format!("─── {} ───", hunk.file_path)),
             }
             lines.push(String::new());
+            lines.extend(hunk.shown_lines());
+            lines.push(String::new());
         }
         lines
     }
 
+    /// Old- and new-file line numbers of each display line (see
+    /// `display_lines`); file headers and spacers have neither.
+    pub fn display_line_numbers(&self) -> Vec<(Option<usize>, Option<usize>)> {
+        let mut numbers = Vec::new();
+        for hunk in &self.hunks {
+            numbers.extend([(None, None); 2]);
+            if hunk.folded {
+                numbers.push((None, None));
+            } else {
+                numbers.extend(hunk.line_numbers());
+            }
+            numbers.push((None, None));
+        }
+        numbers
+    }
+
     pub fn diff_line_count(&self) -> usize {
         self.display_lines().len()
     }
+
+    /// Display lines taken up by each hunk: its file header, spacers, and content.
+    pub fn hunk_line_counts(&self) -> impl Iterator<Item = usize> + '_ {
+        self.hunks.iter().map(|hunk| hunk.shown_line_count() + 3)
+    }
+
+    /// Display line of hunk `index`'s file header.
+    pub fn hunk_header_line(&self, index: usize) -> Option<usize> {
+        (index < self.hunks.len()).then(|| self.hunk_line_counts().take(index).sum())
+    }
+
+    /// First hunk of the next file after hunk `index`, or of the previous
+    /// file before it, where a file is a run of hunks with the same path.
+    pub fn file_start_hunk(&self, index: usize, forward: bool) -> Option<usize> {
+        let path = |i: usize| self.hunks[i].file_path.as_str();
+        let run_start = |mut i: usize| {
+            while i > 0 && path(i - 1) == path(i) {
+                i -= 1;
+            }
+            i
+        };
+        let current = index.min(self.hunks.len().checked_sub(1)?);
+        if forward {
+            (current + 1..self.hunks.len()).find(|&i| path(i) != path(current))
+        } else {
+            let start = run_start(current);
+            (start > 0).then(|| run_start(start - 1))
+        }
+    }
+
+    /// Whether `other` covers exactly the same changes, in any order.
+    pub fn same_hunks(&self, other: &Step) -> bool {
+        let key = |step: &Step| {
+            let mut hunks: Vec<(String, String)> = step
+                .hunks
+                .iter()
+                .map(|h| (h.file_path.clone(), h.content.clone()))
+                .collect();
+            hunks.sort();
+            hunks
+        };
+        key(self) == key(other)
+    }
+
+    pub fn reviewed_hunk_count(&self) -> usize {
+        self.hunks.iter().filter(|hunk| hunk.reviewed).count()
+    }
+
+    /// Maps a display line (see `display_lines`) to the index of the hunk it
+    /// belongs to, plus the line's index within that hunk's content when it is
+    /// a content line rather than a file header or spacer.
+    pub fn locate_display_line(&self, line: usize) -> Option<(usize, Option<usize>)> {
+        let mut pos = 0;
+        for (i, hunk) in self.hunks.iter().enumerate() {
+            let content_len = hunk.shown_line_count();
+            let block_len = content_len + 3;
+            if line < pos + block_len {
+                let within = line - pos;
+                let content_index = (2..2 + content_len).contains(&within).then(|| within - 2);
+                return Some((i, content_index));
+            }
+            pos += block_len;
+        }
+        None
+    }
+
+    /// Quotes the diff content between display lines `first` and `last`
+    /// (inclusive) as Markdown: one fenced block per hunk, headed by its file
+    /// and new-file line range. File headers, spacers and `@@` lines are
+    /// skipped.
+    pub fn quote_display_lines(&self, first: usize, last: usize) -> Option<String> {
+        let mut blocks: Vec<(usize, Vec<usize>)> = Vec::new();
+        for line in first..=last {
+            let Some((hunk, Some(index))) = self.locate_display_line(line) else {
+                continue;
+            };
+            let text = self.hunks[hunk].content.lines().nth(index);
+            if text.is_some_and(|l| l.starts_with("@@")) {
+                continue;
+            }
+            match blocks.last_mut() {
+                Some((h, indices)) if *h == hunk => indices.push(index),
+                _ => blocks.push((hunk, vec![index])),
+            }
+        }
+
+        let quoted: Vec<String> = blocks
+            .into_iter()
+            .map(|(hunk, indices)| {
+                let hunk = &self.hunks[hunk];
+                let content: Vec<&str> = hunk.content.lines().collect();
+                let start = hunk.new_line_at(indices[0]);
+                let end = hunk.new_line_at(indices[indices.len() - 1]);
+                let range = if start == end {
+                    format!("line {}", start)
+                } else {
+                    format!("lines {}-{}", start, end)
+                };
+                let body: Vec<&str> = indices.iter().map(|&i| content[i]).collect();
+                format!(
+                    "`{}` {}:\n```diff\n{}\n```",
+                    hunk.file_path,
+                    range,
+                    body.join("\n")
+                )
+            })
+            .collect();
+        (!quoted.is_empty()).then(|| quoted.join("\n\n"))
+    }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Walkthrough {
     pub steps: Vec<Step>,
+    /// Questions about the change as a whole, outside any one step
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub messages: Vec<Message>,
 }
 
 impl Walkthrough {
@@ -113,9 +487,240 @@ impl Walkthrough {
         self.steps.len()
     }
 
+    /// The first step after `index` (or before it, going back) that `filter`
+    /// shows.
+    pub fn next_shown_step(
+        &self,
+        index: usize,
+        filter: PriorityFilter,
+        forward: bool,
+    ) -> Option<usize> {
+        let shown = |i: &usize| filter.shows(self.steps[*i].priority);
+        if forward {
+            (index + 1..self.steps.len()).find(shown)
+        } else {
+            (0..index.min(self.steps.len())).rev().find(shown)
+        }
+    }
+
     pub fn get_step(&self, index: usize) -> Option<&Step> {
         self.steps.get(index)
     }
+
+    /// Every flag with the index of its step, most serious first and in step
+    /// order within a severity.
+    pub fn flags(&self) -> Vec<(usize, &Flag)> {
+        let mut flags: Vec<(usize, &Flag)> = self
+            .steps
+            .iter()
+            .enumerate()
+            .flat_map(|(i, step)| step.flags.iter().map(move |flag| (i, flag)))
+            .collect();
+        flags.sort_by_key(|(_, flag)| flag.severity);
+        flags
+    }
+
+    /// Every hunk touching `path` as (step, hunk) indexes, in file order.
+    pub fn file_hunks(&self, path: &str) -> Vec<(usize, usize)> {
+        let mut hunks: Vec<(usize, usize)> = self
+            .steps
+            .iter()
+            .enumerate()
+            .flat_map(|(i, step)| {
+                step.hunks
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, hunk)| hunk.file_path == path)
+                    .map(move |(h, _)| (i, h))
+            })
+            .collect();
+        hunks.sort_by_key(|&(step, hunk)| self.steps[step].hunks[hunk].start_line);
+        hunks
+    }
+
+    /// How many flags there are of each severity present, most serious first.
+    pub fn flag_counts(&self) -> Vec<(Severity, usize)> {
+        let flags = self.flags();
+        [Severity::Blocker, Severity::Question, Severity::Nit]
+            .into_iter()
+            .map(|severity| {
+                let count = flags.iter().filter(|(_, f)| f.severity == severity).count();
+                (severity, count)
+            })
+            .filter(|&(_, count)| count > 0)
+            .collect()
+    }
+
+    /// How many flags there are of each severity, e.g. "1 blocker, 2 nits",
+    /// for exports.
+    pub fn flag_tally(&self) -> String {
+        self.flag_counts()
+            .into_iter()
+            .map(|(severity, count)| {
+                let plural = if count == 1 { "" } else { "s" };
+                format!("{} {}{}", count, severity.label(), plural)
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    /// Splits the steps into groups, each a top-level step followed by its
+    /// sub-steps, so reordering never separates a split step from its parts.
+    pub fn step_groups(&self) -> Vec<Range<usize>> {
+        let mut groups: Vec<Range<usize>> = Vec::new();
+        for (i, step) in self.steps.iter().enumerate() {
+            match groups.last_mut() {
+                Some(group) if step.depth > 0 => group.end = i + 1,
+                _ => groups.push(i..i + 1),
+            }
+        }
+        groups
+    }
+
+    /// The earliest (file, line) touched by any step in `group`. Groups with
+    /// no hunks sort last.
+    pub fn group_location(&self, group: Range<usize>) -> Option<(&str, usize)> {
+        self.steps[group]
+            .iter()
+            .flat_map(|step| &step.hunks)
+            .map(|hunk| (hunk.file_path.as_str(), hunk.start_line))
+            .min()
+    }
+
+    /// Step order after swapping the group holding step `index` with the
+    /// group after it (or before it), as old indices in their new positions.
+    pub fn moved_group_order(&self, index: usize, down: bool) -> Option<Vec<usize>> {
+        let groups = self.step_groups();
+        let group = groups.iter().position(|g| g.contains(&index))?;
+        let other = if down {
+            group + 1
+        } else {
+            group.checked_sub(1)?
+        };
+        if other >= groups.len() {
+            return None;
+        }
+        let mut order = Vec::with_capacity(self.steps.len());
+        for i in 0..groups.len() {
+            let source = match i {
+                i if i == group => other,
+                i if i == other => group,
+                i => i,
+            };
+            order.extend(groups[source].clone());
+        }
+        Some(order)
+    }
+
+    /// Moves step `index`'s hunks, notes, flags, and conversation onto the step
+    /// before it, which keeps the more important priority, and removes it.
+    /// A step with sub-steps can't be merged away.
+    pub fn merge_into_previous(&mut self, index: usize) -> bool {
+        if index == 0 || index >= self.steps.len() {
+            return false;
+        }
+        let depth = self.steps[index].depth;
+        if self
+            .steps
+            .get(index + 1)
+            .is_some_and(|next| next.depth > depth)
+        {
+            return false;
+        }
+        let step = self.steps.remove(index);
+        let target = &mut self.steps[index - 1];
+        target.hunks.extend(step.hunks);
+        target.notes.extend(step.notes);
+        target.flags.extend(step.flags);
+        target.messages.extend(step.messages);
+        target.priority = target.priority.min(step.priority);
+        true
+    }
+
+    /// Moves hunk `hunk` of step `from` to the end of step `to`. The source
+    /// step must keep at least one hunk.
+    pub fn move_hunk(&mut self, from: usize, hunk: usize, to: usize) -> bool {
+        let valid = from != to
+            && to < self.steps.len()
+            && self
+                .steps
+                .get(from)
+                .is_some_and(|step| hunk < step.hunks.len() && step.hunks.len() > 1);
+        if !valid {
+            return false;
+        }
+        let moved = self.steps[from].hunks.remove(hunk);
+        self.steps[to].hunks.push(moved);
+        true
+    }
+
+    /// Moves the hunks of step `index` at positions `hunks` into a new step
+    /// at the same depth, placed after the step and its sub-steps. Both
+    /// steps must keep at least one hunk. Returns the new step's index.
+    pub fn split_off_hunks(&mut self, index: usize, hunks: &[usize]) -> Option<usize> {
+        let step = self.steps.get_mut(index)?;
+        let moved = (0..step.hunks.len()).filter(|i| hunks.contains(i)).count();
+        if moved == 0 || moved == step.hunks.len() {
+            return None;
+        }
+        let (moved, kept): (Vec<_>, Vec<_>) = std::mem::take(&mut step.hunks)
+            .into_iter()
+            .enumerate()
+            .partition(|(i, _)| hunks.contains(i));
+        step.hunks = kept.into_iter().map(|(_, hunk)| hunk).collect();
+        let new_step = Step {
+            id: String::new(),
+            title: format!("{} (split)", step.title),
+            summary: String::new(),
+            priority: step.priority,
+            hunks: moved.into_iter().map(|(_, hunk)| hunk).collect(),
+            messages: vec![],
+            depth: step.depth,
+            notes: vec![],
+            flags: vec![],
+        };
+        let depth = step.depth;
+        let position = (index + 1..self.steps.len())
+            .find(|&i| self.steps[i].depth <= depth)
+            .unwrap_or(self.steps.len());
+        self.steps.insert(position, new_step);
+        Some(position)
+    }
+}
+
+/// Fixtures for tests. Also built in debug builds so the binary's tests can
+/// use them.
+#[cfg(any(test, debug_assertions))]
+impl Hunk {
+    pub fn test(path: &str, start_line: usize, end_line: usize, content: &str) -> Self {
+        Self {
+            file_path: path.to_string(),
+            start_line,
+            end_line,
+            content: content.to_string(),
+            symbol: None,
+            reviewed: false,
+            folded: false,
+        }
+    }
+}
+
+#[cfg(any(test, debug_assertions))]
+impl Step {
+    /// A top-level step titled "Step {id}" with one empty hunk of `path`.
+    pub fn test(id: &str, path: &str) -> Self {
+        Self {
+            id: id.to_string(),
+            title: format!("Step {id}"),
+            summary: String::new(),
+            priority: Priority::Normal,
+            hunks: vec![Hunk::test(path, 1, 1, "")],
+            messages: vec![],
+            depth: 0,
+            notes: vec![],
+            flags: vec![],
+        }
+    }
 }
 
 #[cfg(debug_assertions)]
@@ -143,6 +748,8 @@ pub fn mock_walkthrough() -> Walkthrough {
                 priority: Priority::Critical,
                 messages: vec![Message::assistant(summary1)],
                 depth: 0,
+                notes: vec![],
+                flags: vec![],
                 hunks: vec![Hunk {
                     file_path: "src/models/session.rs".to_string(),
                     start_line: 1,
@@ -174,6 +781,9 @@ pub fn mock_walkthrough() -> Walkthrough {
 +    }
 +}"#
                     .to_string(),
+                    symbol: None,
+                    reviewed: false,
+                    folded: false,
                 }],
             },
             Step {
@@ -183,6 +793,8 @@ pub fn mock_walkthrough() -> Walkthrough {
                 priority: Priority::Critical,
                 messages: vec![Message::assistant(summary2)],
                 depth: 0,
+                notes: vec![],
+                flags: vec![],
                 hunks: vec![Hunk {
                     file_path: "src/models/session.rs".to_string(),
                     start_line: 26,
@@ -212,6 +824,9 @@ pub fn mock_walkthrough() -> Walkthrough {
 +    }
 +}"#
                     .to_string(),
+                    symbol: None,
+                    reviewed: false,
+                    folded: false,
                 }],
             },
             Step {
@@ -221,6 +836,8 @@ pub fn mock_walkthrough() -> Walkthrough {
                 priority: Priority::Normal,
                 messages: vec![Message::assistant(summary3)],
                 depth: 0,
+                notes: vec![],
+                flags: vec![],
                 hunks: vec![Hunk {
                     file_path: "src/handlers/middleware.rs".to_string(),
                     start_line: 15,
@@ -248,6 +865,9 @@ pub fn mock_walkthrough() -> Walkthrough {
 +    }
  }"#
                     .to_string(),
+                    symbol: None,
+                    reviewed: false,
+                    folded: false,
                 }],
             },
             Step {
@@ -257,6 +877,8 @@ pub fn mock_walkthrough() -> Walkthrough {
                 priority: Priority::Minor,
                 messages: vec![Message::assistant(summary4)],
                 depth: 0,
+                notes: vec![],
+                flags: vec![],
                 hunks: vec![Hunk {
                     file_path: "src/models/session_test.rs".to_string(),
                     start_line: 1,
@@ -298,6 +920,9 @@ pub fn mock_walkthrough() -> Walkthrough {
 +    }
 +}"#
                     .to_string(),
+                    symbol: None,
+                    reviewed: false,
+                    folded: false,
                 }],
             },
             Step {
@@ -307,6 +932,8 @@ pub fn mock_walkthrough() -> Walkthrough {
                 priority: Priority::Minor,
                 messages: vec![Message::assistant(summary5)],
                 depth: 0,
+                notes: vec![],
+                flags: vec![],
                 hunks: vec![Hunk {
                     file_path: "docs/API.md".to_string(),
                     start_line: 45,
@@ -331,8 +958,285 @@ pub fn mock_walkthrough() -> Walkthrough {
 +
 +Clients should handle 401 responses by redirecting to login."#
                         .to_string(),
+                    symbol: None,
+                    reviewed: false,
+                    folded: false,
                 }],
             },
         ],
+        messages: vec![],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn step(depth: u8, hunks: &[(&str, usize)]) -> Step {
+        Step {
+            hunks: hunks
+                .iter()
+                .map(|&(path, start_line)| Hunk::test(path, start_line, start_line, ""))
+                .collect(),
+            depth,
+            ..Step::test("", "")
+        }
+    }
+
+    #[test]
+    fn test_file_start_hunk_moves_between_runs_of_hunks() {
+        let step = step(
+            0,
+            &[
+                ("a.rs", 1),
+                ("a.rs", 9),
+                ("b.rs", 3),
+                ("c.rs", 1),
+                ("c.rs", 7),
+            ],
+        );
+        assert_eq!(step.file_start_hunk(0, true), Some(2));
+        assert_eq!(step.file_start_hunk(2, true), Some(3));
+        assert_eq!(step.file_start_hunk(4, true), None);
+        assert_eq!(step.file_start_hunk(4, false), Some(2));
+        assert_eq!(step.file_start_hunk(2, false), Some(0));
+        assert_eq!(step.file_start_hunk(1, false), None);
+    }
+
+    #[test]
+    fn test_next_shown_step_skips_filtered_priorities() {
+        let mut walkthrough = Walkthrough {
+            steps: vec![step(0, &[]), step(0, &[]), step(0, &[]), step(0, &[])],
+            messages: vec![],
+        };
+        walkthrough.steps[0].priority = Priority::Critical;
+        walkthrough.steps[1].priority = Priority::Minor;
+        walkthrough.steps[3].priority = Priority::Critical;
+
+        let filter = PriorityFilter::HideMinor;
+        assert_eq!(walkthrough.next_shown_step(0, filter, true), Some(2));
+        assert_eq!(walkthrough.next_shown_step(2, filter, false), Some(0));
+        let filter = PriorityFilter::CriticalOnly;
+        assert_eq!(walkthrough.next_shown_step(0, filter, true), Some(3));
+        assert_eq!(walkthrough.next_shown_step(3, filter, true), None);
+        assert_eq!(
+            walkthrough.next_shown_step(3, PriorityFilter::All, false),
+            Some(2)
+        );
+    }
+
+    #[test]
+    fn test_step_groups_keep_sub_steps_with_parent() {
+        let walkthrough = Walkthrough {
+            steps: vec![
+                step(0, &[("b.rs", 1)]),
+                step(0, &[]),
+                step(1, &[("c.rs", 5)]),
+                step(1, &[("a.rs", 9)]),
+                step(0, &[]),
+            ],
+            messages: vec![],
+        };
+        assert_eq!(walkthrough.step_groups(), vec![0..1, 1..4, 4..5]);
+        assert_eq!(walkthrough.group_location(1..4), Some(("a.rs", 9)));
+        assert_eq!(walkthrough.group_location(4..5), None);
+    }
+
+    #[test]
+    fn test_flags_list_most_serious_first() {
+        let flag = |severity, text: &str| Flag {
+            severity,
+            text: text.to_string(),
+            location: None,
+        };
+        let mut walkthrough = Walkthrough {
+            steps: vec![step(0, &[]), step(0, &[])],
+            messages: vec![],
+        };
+        walkthrough.steps[0].flags = vec![flag(Severity::Nit, "naming"), flag(Severity::Nit, "")];
+        walkthrough.steps[1].flags = vec![flag(Severity::Blocker, "races")];
+
+        let flags = walkthrough.flags();
+        assert_eq!(flags[0], (1, &walkthrough.steps[1].flags[0]));
+        assert_eq!(flags[1].1.text, "naming");
+        assert_eq!(walkthrough.flag_tally(), "1 blocker, 2 nits");
+        assert_eq!(Walkthrough::default().flag_tally(), "");
+    }
+
+    #[test]
+    fn test_folded_hunks_show_only_their_header() {
+        let mut step = step(0, &[("a.rs", 1), ("b.rs", 1)]);
+        step.hunks[0].content = "@@ -1,2 +1,2 @@\n-old\n+new\n+more\n keep".to_string();
+        step.hunks[0].folded = true;
+
+        let lines = step.display_lines();
+        assert_eq!(lines[2], "@@ -1,2 +1,2 @@ (+2 −1, folded)");
+        assert_eq!(lines.len(), step.display_line_numbers().len());
+        assert_eq!(step.hunk_header_line(1), Some(4));
+        assert_eq!(step.locate_display_line(2), Some((0, Some(0))));
+        assert_eq!(step.locate_display_line(4), Some((1, None)));
+    }
+
+    #[test]
+    fn test_file_hunks_gathers_a_file_across_steps() {
+        let walkthrough = Walkthrough {
+            steps: vec![
+                step(0, &[("a.rs", 40), ("b.rs", 1)]),
+                step(0, &[("a.rs", 5), ("a.rs", 90)]),
+            ],
+            messages: vec![],
+        };
+        assert_eq!(walkthrough.file_hunks("a.rs"), [(1, 0), (0, 0), (1, 1)]);
+        assert!(walkthrough.file_hunks("c.rs").is_empty());
+    }
+
+    #[test]
+    fn test_moved_group_order_swaps_whole_groups() {
+        let walkthrough = Walkthrough {
+            steps: vec![step(0, &[]), step(0, &[]), step(1, &[]), step(0, &[])],
+            messages: vec![],
+        };
+        assert_eq!(
+            walkthrough.moved_group_order(0, true),
+            Some(vec![1, 2, 0, 3])
+        );
+        assert_eq!(
+            walkthrough.moved_group_order(2, true),
+            Some(vec![0, 3, 1, 2])
+        );
+        assert_eq!(
+            walkthrough.moved_group_order(3, false),
+            Some(vec![0, 3, 1, 2])
+        );
+        assert_eq!(walkthrough.moved_group_order(0, false), None);
+        assert_eq!(walkthrough.moved_group_order(3, true), None);
+    }
+
+    #[test]
+    fn test_merge_and_split_steps() {
+        let mut walkthrough = Walkthrough {
+            steps: vec![
+                step(0, &[("a.rs", 1)]),
+                step(0, &[("b.rs", 1), ("c.rs", 1)]),
+                step(1, &[("d.rs", 1)]),
+                step(0, &[("e.rs", 1)]),
+            ],
+            messages: vec![],
+        };
+        walkthrough.steps[3].priority = Priority::Critical;
+
+        // Step 1 has a sub-step, so it can't be merged away
+        assert!(!walkthrough.merge_into_previous(1));
+        assert!(walkthrough.merge_into_previous(3));
+        assert_eq!(walkthrough.steps.len(), 3);
+        assert_eq!(walkthrough.steps[2].hunks.len(), 2);
+        assert_eq!(walkthrough.steps[2].priority, Priority::Critical);
+
+        assert_eq!(walkthrough.split_off_hunks(1, &[0, 1]), None);
+        assert_eq!(walkthrough.split_off_hunks(1, &[]), None);
+        // The new step goes after the split step's sub-steps
+        assert_eq!(walkthrough.split_off_hunks(1, &[0]), Some(3));
+        assert_eq!(walkthrough.steps[1].hunks[0].file_path, "c.rs");
+        assert_eq!(walkthrough.steps[3].hunks[0].file_path, "b.rs");
+        assert_eq!(walkthrough.steps[3].depth, 0);
+    }
+
+    #[test]
+    fn test_move_hunk_keeps_a_hunk_in_the_source_step() {
+        let mut walkthrough = Walkthrough {
+            steps: vec![
+                step(0, &[("a.rs", 1), ("b.rs", 1)]),
+                step(0, &[("c.rs", 1)]),
+            ],
+            messages: vec![],
+        };
+        assert!(!walkthrough.move_hunk(0, 0, 0));
+        assert!(!walkthrough.move_hunk(0, 2, 1));
+        assert!(walkthrough.move_hunk(0, 0, 1));
+        assert_eq!(walkthrough.steps[1].hunks[1].file_path, "a.rs");
+        assert!(!walkthrough.move_hunk(0, 0, 1));
+    }
+
+    #[test]
+    fn test_hunk_header_lines_match_display_lines() {
+        let mut step = step(0, &[("a.rs", 1), ("b.rs", 4)]);
+        step.hunks[0].content = "@@ -1,2 +1,2 @@\n-a\n+b".to_string();
+        let headers: Vec<usize> = step
+            .display_lines()
+            .iter()
+            .enumerate()
+            .filter(|(_, line)| line.starts_with("───"))
+            .map(|(i, _)| i)
+            .collect();
+        assert_eq!(headers, [0, 6]);
+        assert_eq!(step.hunk_header_line(0), Some(0));
+        assert_eq!(step.hunk_header_line(1), Some(6));
+        assert_eq!(step.hunk_header_line(2), None);
+        assert_eq!(
+            step.hunk_line_counts().sum::<usize>(),
+            step.diff_line_count()
+        );
+    }
+
+    #[test]
+    fn test_line_numbers() {
+        let mut step = step(0, &[("a.rs", 1)]);
+        step.hunks[0].content = "@@ -10,3 +20,3 @@ fn f\n a\n-b\n+c\n+d\n e".to_string();
+        assert_eq!(
+            step.hunks[0].line_numbers(),
+            [
+                (None, None),
+                (Some(10), Some(20)),
+                (Some(11), None),
+                (None, Some(21)),
+                (None, Some(22)),
+                (Some(12), Some(23)),
+            ]
+        );
+        let numbers = step.display_line_numbers();
+        assert_eq!(numbers.len(), step.diff_line_count());
+        assert_eq!(numbers[3], (Some(10), Some(20)));
+    }
+
+    #[test]
+    fn test_same_hunks_ignores_order() {
+        let a = step(0, &[("a.rs", 1), ("b.rs", 4)]);
+        let b = step(1, &[("b.rs", 4), ("a.rs", 1)]);
+        let c = step(0, &[("a.rs", 1)]);
+        assert!(a.same_hunks(&b));
+        assert!(!a.same_hunks(&c));
+    }
+
+    #[test]
+    fn test_same_change_ignores_hunk_position() {
+        let mut hunks = step(0, &[("a.rs", 1), ("a.rs", 5), ("b.rs", 1)]).hunks;
+        hunks[0].content = "@@ -1,1 +1,1 @@\n-a\n+b".to_string();
+        hunks[1].content = "@@ -5,1 +5,1 @@\n-a\n+b".to_string();
+        hunks[2].content = hunks[0].content.clone();
+        assert!(hunks[0].same_change(&hunks[1]));
+        assert!(!hunks[0].same_change(&hunks[2]));
+        hunks[1].content.push_str("\n c");
+        assert!(!hunks[0].same_change(&hunks[1]));
+    }
+
+    #[test]
+    fn test_quote_display_lines() {
+        let mut step = step(0, &[("a.rs", 1), ("b.rs", 4)]);
+        step.hunks[0].content = "@@ -1,2 +10,2 @@\n-a\n+b\n c".to_string();
+        step.hunks[1].content = "@@ -4,1 +4,1 @@\n+d".to_string();
+        // Display lines: 0 header, 1 spacer, 2 @@, 3 -a, 4 +b, 5 c, 6 spacer,
+        // 7 header, 8 spacer, 9 @@, 10 +d, 11 spacer
+        assert_eq!(
+            step.quote_display_lines(4, 5).as_deref(),
+            Some("`a.rs` lines 10-11:\n```diff\n+b\n c\n```")
+        );
+        assert_eq!(
+            step.quote_display_lines(5, 10).as_deref(),
+            Some(
+                "`a.rs` line 11:\n```diff\n c\n```\n\n\
+                 `b.rs` line 4:\n```diff\n+d\n```"
+            )
+        );
+        assert_eq!(step.quote_display_lines(0, 2), None);
     }
 }
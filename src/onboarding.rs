@@ -0,0 +1,195 @@
+//! First-run onboarding: provider, API key, and editor mode.
+//!
+//! Shown instead of the setup screen when there is no settings file and no
+//! API key, so a new user is walked through configuration rather than
+//! landing on a "No API key found" message.
+
+use crate::settings::{Provider, Settings, VimMode};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OnboardingStep {
+    Provider,
+    ApiKey,
+    EditorMode,
+}
+
+/// Where the entered API key is kept.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyStorage {
+    /// Written to ~/.docent/settings.json
+    SettingsFile,
+    /// Used for this run only
+    Session,
+}
+
+pub const VIM_MODES: [VimMode; 3] = [VimMode::Auto, VimMode::Enabled, VimMode::Disabled];
+
+#[derive(Debug, Clone)]
+pub struct Onboarding {
+    pub step: OnboardingStep,
+    pub provider: Provider,
+    pub api_key: String,
+    pub key_storage: KeyStorage,
+    pub vim_mode: VimMode,
+    pub error: Option<String>,
+}
+
+impl Default for Onboarding {
+    fn default() -> Self {
+        Self {
+            step: OnboardingStep::Provider,
+            provider: Provider::default(),
+            api_key: String::new(),
+            key_storage: KeyStorage::SettingsFile,
+            vim_mode: VimMode::Auto,
+            error: None,
+        }
+    }
+}
+
+impl Onboarding {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether onboarding should run: first launch and no key from anywhere.
+    pub fn is_needed(settings: &Settings) -> bool {
+        !Settings::exists() && settings.resolve_api_key().0.is_none()
+    }
+
+    pub fn step_number(&self) -> usize {
+        match self.step {
+            OnboardingStep::Provider => 1,
+            OnboardingStep::ApiKey => 2,
+            OnboardingStep::EditorMode => 3,
+        }
+    }
+
+    /// Moves to the next step. Returns true when the last step was confirmed.
+    pub fn advance(&mut self) -> bool {
+        self.error = None;
+        match self.step {
+            // A local provider needs no key, so skip asking for one
+            OnboardingStep::Provider if !self.provider.requires_api_key() => {
+                self.step = OnboardingStep::EditorMode;
+            }
+            OnboardingStep::Provider => self.step = OnboardingStep::ApiKey,
+            OnboardingStep::ApiKey => {
+                if self.api_key.trim().is_empty() {
+                    self.error = Some("Enter an API key to continue".to_string());
+                } else {
+                    self.step = OnboardingStep::EditorMode;
+                }
+            }
+            OnboardingStep::EditorMode => return true,
+        }
+        false
+    }
+
+    pub fn back(&mut self) {
+        self.error = None;
+        self.step = match self.step {
+            OnboardingStep::Provider | OnboardingStep::ApiKey => OnboardingStep::Provider,
+            OnboardingStep::EditorMode if !self.provider.requires_api_key() => {
+                OnboardingStep::Provider
+            }
+            OnboardingStep::EditorMode => OnboardingStep::ApiKey,
+        };
+    }
+
+    /// Moves the selection within the current step's options.
+    pub fn select(&mut self, delta: isize) {
+        match self.step {
+            OnboardingStep::Provider => {
+                self.provider = cycle(&Provider::ALL, self.provider, delta);
+            }
+            OnboardingStep::ApiKey => {
+                self.key_storage = match self.key_storage {
+                    KeyStorage::SettingsFile => KeyStorage::Session,
+                    KeyStorage::Session => KeyStorage::SettingsFile,
+                };
+            }
+            OnboardingStep::EditorMode => {
+                self.vim_mode = cycle(&VIM_MODES, self.vim_mode, delta);
+            }
+        }
+    }
+
+    /// Writes the chosen provider, editor mode, and (if requested) key into settings.
+    pub fn apply_to(&self, settings: &mut Settings) {
+        settings.provider = self.provider;
+        settings.editor.vim_mode = self.vim_mode;
+        if self.key_storage == KeyStorage::SettingsFile && !self.api_key.trim().is_empty() {
+            settings.api_key = Some(self.api_key.trim().to_string());
+        }
+    }
+}
+
+fn cycle<T: Copy + PartialEq>(options: &[T], current: T, delta: isize) -> T {
+    let index = options.iter().position(|o| *o == current).unwrap_or(0) as isize;
+    let len = options.len() as isize;
+    options[(index + delta).rem_euclid(len) as usize]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_api_key_required_to_advance() {
+        let mut onboarding = Onboarding::new();
+        assert!(!onboarding.advance());
+        assert_eq!(onboarding.step, OnboardingStep::ApiKey);
+
+        assert!(!onboarding.advance());
+        assert_eq!(onboarding.step, OnboardingStep::ApiKey);
+        assert!(onboarding.error.is_some());
+
+        onboarding.api_key = "sk-ant-test".to_string();
+        assert!(!onboarding.advance());
+        assert_eq!(onboarding.step, OnboardingStep::EditorMode);
+        assert!(onboarding.advance());
+    }
+
+    #[test]
+    fn test_local_provider_skips_api_key() {
+        let mut onboarding = Onboarding::new();
+        onboarding.provider = Provider::Ollama;
+        assert!(!onboarding.advance());
+        assert_eq!(onboarding.step, OnboardingStep::EditorMode);
+        onboarding.back();
+        assert_eq!(onboarding.step, OnboardingStep::Provider);
+
+        let mut settings = Settings::default();
+        onboarding.apply_to(&mut settings);
+        assert_eq!(settings.provider, Provider::Ollama);
+        assert_eq!(settings.api_key, None);
+    }
+
+    #[test]
+    fn test_apply_respects_key_storage() {
+        let mut onboarding = Onboarding::new();
+        onboarding.api_key = " sk-ant-test ".to_string();
+        onboarding.vim_mode = VimMode::Enabled;
+
+        let mut settings = Settings::default();
+        onboarding.apply_to(&mut settings);
+        assert_eq!(settings.api_key.as_deref(), Some("sk-ant-test"));
+        assert_eq!(settings.editor.vim_mode, VimMode::Enabled);
+
+        onboarding.key_storage = KeyStorage::Session;
+        let mut settings = Settings::default();
+        onboarding.apply_to(&mut settings);
+        assert_eq!(settings.api_key, None);
+    }
+
+    #[test]
+    fn test_select_wraps_editor_modes() {
+        let mut onboarding = Onboarding::new();
+        onboarding.step = OnboardingStep::EditorMode;
+        onboarding.select(-1);
+        assert_eq!(onboarding.vim_mode, VimMode::Disabled);
+        onboarding.select(1);
+        assert_eq!(onboarding.vim_mode, VimMode::Auto);
+    }
+}
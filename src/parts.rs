@@ -0,0 +1,229 @@
+//! Splitting very large diffs into parts, each reviewed as its own walkthrough.
+//!
+//! When a diff is over the configured size, the user is offered a split by
+//! directory or by the model's suggested partitioning. Each part is generated
+//! on demand when it is first opened from the parts picker.
+
+use std::collections::{BTreeMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+
+use crate::api::PartitionResponse;
+use crate::diff::{FileFilter, ParsedDiff};
+use crate::generation::area_of;
+use crate::model::Walkthrough;
+
+/// One part of a split review. Its walkthrough is stashed here while another
+/// part is active, and stays empty until the part is first generated.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReviewPart {
+    pub title: String,
+    pub files: Vec<String>,
+    pub walkthrough: Walkthrough,
+    pub reviewed_steps: Vec<bool>,
+}
+
+impl ReviewPart {
+    pub fn new(title: impl Into<String>, files: Vec<String>) -> Self {
+        Self {
+            title: title.into(),
+            files,
+            walkthrough: Walkthrough::default(),
+            reviewed_steps: vec![],
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SplitChoice {
+    Whole,
+    ByDirectory,
+    ByModel,
+}
+
+pub const SPLIT_CHOICES: [SplitChoice; 3] = [
+    SplitChoice::ByDirectory,
+    SplitChoice::ByModel,
+    SplitChoice::Whole,
+];
+
+/// The prompt shown before generation when the diff is over the size limit.
+#[derive(Debug, Clone)]
+pub struct SplitOffer {
+    /// Changed lines per file, in diff order
+    pub files: Vec<(String, usize)>,
+    pub changed_lines: usize,
+    pub max_lines: usize,
+    pub cursor: usize,
+    /// Waiting on the model's suggested partitioning
+    pub pending: bool,
+}
+
+impl SplitOffer {
+    /// Returns an offer when the filtered diff has more than `max_lines`
+    /// changed lines across more than one file.
+    pub fn for_diff(diff_text: &str, filter: &FileFilter, max_lines: usize) -> Option<Self> {
+        let mut parsed = ParsedDiff::parse(diff_text).ok()?;
+        parsed.apply_filter(filter).ok()?;
+        let files = file_sizes(&parsed);
+        let changed_lines = files.iter().map(|(_, lines)| lines).sum();
+        (changed_lines > max_lines && files.len() > 1).then_some(Self {
+            files,
+            changed_lines,
+            max_lines,
+            cursor: 0,
+            pending: false,
+        })
+    }
+
+    pub fn move_cursor(&mut self, delta: isize) {
+        let last = SPLIT_CHOICES.len() as isize - 1;
+        self.cursor = (self.cursor as isize + delta).clamp(0, last) as usize;
+    }
+
+    pub fn choice(&self) -> SplitChoice {
+        SPLIT_CHOICES[self.cursor]
+    }
+}
+
+/// Added and removed lines in each file, in diff order.
+pub fn file_sizes(parsed: &ParsedDiff) -> Vec<(String, usize)> {
+    let mut files: Vec<(String, usize)> = Vec::new();
+    for hunk in &parsed.hunks {
+        let changed = hunk
+            .content
+            .lines()
+            .skip(1)
+            .filter(|line| line.starts_with('+') || line.starts_with('-'))
+            .count();
+        match files.iter_mut().find(|(path, _)| *path == hunk.file_path) {
+            Some((_, lines)) => *lines += changed,
+            None => files.push((hunk.file_path.clone(), changed)),
+        }
+    }
+    files
+}
+
+/// Groups files by area (see `area_of`), then packs neighbouring areas into
+/// parts of at most `max_lines` changed lines. An area bigger than the limit
+/// becomes a part on its own.
+pub fn split_by_directory(files: &[(String, usize)], max_lines: usize) -> Vec<ReviewPart> {
+    let mut areas: BTreeMap<String, (Vec<String>, usize)> = BTreeMap::new();
+    for (path, lines) in files {
+        let area = areas.entry(area_of(path)).or_default();
+        area.0.push(path.clone());
+        area.1 += lines;
+    }
+
+    let mut parts: Vec<(Vec<String>, Vec<String>, usize)> = Vec::new();
+    for (name, (paths, lines)) in areas {
+        match parts.last_mut() {
+            Some((names, part_paths, part_lines)) if *part_lines + lines <= max_lines => {
+                names.push(name);
+                part_paths.extend(paths);
+                *part_lines += lines;
+            }
+            _ => parts.push((vec![name], paths, lines)),
+        }
+    }
+
+    parts
+        .into_iter()
+        .map(|(names, paths, _)| ReviewPart::new(names.join(", "), paths))
+        .collect()
+}
+
+/// Prompt asking the model to partition the files.
+pub fn partition_prompt(files: &[(String, usize)], max_lines: usize) -> String {
+    let listing: Vec<String> = files
+        .iter()
+        .map(|(path, lines)| format!("- {} ({} changed lines)", path, lines))
+        .collect();
+    format!(
+        "Split this change into parts of at most about {} changed lines each.\n\n\
+         ## Files\n\n{}",
+        max_lines,
+        listing.join("\n")
+    )
+}
+
+/// Turns the model's partitioning into parts, keeping only files that are in
+/// the diff. Files the model assigned twice stay in their first part; files
+/// it left out are collected into a final part.
+pub fn parts_from_suggestion(
+    response: PartitionResponse,
+    files: &[(String, usize)],
+) -> Vec<ReviewPart> {
+    let known: HashSet<&str> = files.iter().map(|(path, _)| path.as_str()).collect();
+    let mut assigned: HashSet<String> = HashSet::new();
+    let mut parts = Vec::new();
+
+    for part in response.parts {
+        let part_files: Vec<String> = part
+            .files
+            .into_iter()
+            .filter(|path| known.contains(path.as_str()) && assigned.insert(path.clone()))
+            .collect();
+        if !part_files.is_empty() {
+            parts.push(ReviewPart::new(part.title, part_files));
+        }
+    }
+
+    let leftover: Vec<String> = files
+        .iter()
+        .map(|(path, _)| path.clone())
+        .filter(|path| !assigned.contains(path))
+        .collect();
+    if !leftover.is_empty() {
+        parts.push(ReviewPart::new("Other changes", leftover));
+    }
+    parts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::PartitionPart;
+
+    fn files() -> Vec<(String, usize)> {
+        vec![
+            ("src/api/client.rs".to_string(), 300),
+            ("src/api/types.rs".to_string(), 100),
+            ("src/ui/mod.rs".to_string(), 500),
+            ("README.md".to_string(), 50),
+        ]
+    }
+
+    #[test]
+    fn test_split_by_directory_packs_areas() {
+        let parts = split_by_directory(&files(), 500);
+        let titles: Vec<_> = parts.iter().map(|p| p.title.as_str()).collect();
+        assert_eq!(titles, ["(root), src/api", "src/ui"]);
+        assert_eq!(parts[0].files.len(), 3);
+    }
+
+    #[test]
+    fn test_suggestion_drops_unknown_and_collects_leftovers() {
+        let response = PartitionResponse {
+            parts: vec![
+                PartitionPart {
+                    title: "API".to_string(),
+                    files: vec![
+                        "src/api/client.rs".to_string(),
+                        "src/api/types.rs".to_string(),
+                        "src/made_up.rs".to_string(),
+                    ],
+                },
+                PartitionPart {
+                    title: "Duplicate".to_string(),
+                    files: vec!["src/api/client.rs".to_string()],
+                },
+            ],
+        };
+        let parts = parts_from_suggestion(response, &files());
+        assert_eq!(parts.len(), 2);
+        assert_eq!(parts[0].files, ["src/api/client.rs", "src/api/types.rs"]);
+        assert_eq!(parts[1].title, "Other changes");
+        assert_eq!(parts[1].files, ["src/ui/mod.rs", "README.md"]);
+    }
+}
@@ -1,6 +1,7 @@
 use serde::{Deserialize, Serialize};
 
-use crate::model::{ReviewMode, Step, Walkthrough};
+use crate::api::RetryNotice;
+use crate::model::{ReviewMode, Step, StepOrganization, Walkthrough};
 use crate::session::SessionState;
 
 // --- Client → Server ---
@@ -32,6 +33,21 @@ pub struct SendMessageParams {
     pub content: String,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct GenerateParams {
+    pub diff: String,
+    pub mode: Option<ReviewMode>,
+    #[serde(default)]
+    pub organize_by: Option<StepOrganization>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RegenerateParams {
+    /// Steering added to the generation prompt
+    #[serde(default)]
+    pub instructions: Option<String>,
+}
+
 // --- Server → Client ---
 
 #[derive(Debug, Serialize)]
@@ -114,6 +130,18 @@ impl Notification {
         )
     }
 
+    pub fn retry_scheduled(notice: &RetryNotice) -> Self {
+        Self::new(
+            "retry_scheduled",
+            serde_json::json!({
+                "status": notice.status,
+                "retry_in_secs": notice.delay.as_secs(),
+                "attempt": notice.attempt,
+                "max_attempts": notice.max_attempts,
+            }),
+        )
+    }
+
     pub fn chat_chunk(step_index: usize, chunk: &str) -> Self {
         Self::new(
             "chat_chunk",
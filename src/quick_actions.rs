@@ -0,0 +1,98 @@
+//! One-shot questions about the hunk under the cursor.
+//!
+//! Each action sends a canned question to the step's chat, so the answer is
+//! kept with the step like any other message.
+
+use crate::model::Hunk;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuickAction {
+    Explain,
+    Risks,
+    Tests,
+    Simplify,
+}
+
+pub const QUICK_ACTIONS: [QuickAction; 4] = [
+    QuickAction::Explain,
+    QuickAction::Risks,
+    QuickAction::Tests,
+    QuickAction::Simplify,
+];
+
+impl QuickAction {
+    /// Shortcut within the menu.
+    pub fn key(self) -> char {
+        match self {
+            Self::Explain => 'e',
+            Self::Risks => 'r',
+            Self::Tests => 't',
+            Self::Simplify => 's',
+        }
+    }
+
+    pub fn from_key(key: char) -> Option<Self> {
+        QUICK_ACTIONS.into_iter().find(|action| action.key() == key)
+    }
+
+    /// The chat message sent for hunk `index` (0-based) of the current step.
+    pub fn prompt(self, hunk: &Hunk, index: usize) -> String {
+        let target = format!(
+            "hunk {} ({}, line {})",
+            index + 1,
+            hunk.file_path,
+            hunk.start_line
+        );
+        match self {
+            Self::Explain => format!("Explain {target}: what it changes and why."),
+            Self::Risks => format!(
+                "List the risks in {target}: bugs, edge cases, and behavior changes \
+                 worth double-checking."
+            ),
+            Self::Tests => format!(
+                "Suggest tests for {target}: which cases to cover and what each \
+                 should assert."
+            ),
+            Self::Simplify => format!(
+                "Could {target} be simpler? Suggest concrete simplifications, with a \
+                 short code sketch if it helps."
+            ),
+        }
+    }
+}
+
+/// The open quick actions menu.
+#[derive(Debug, Clone)]
+pub struct QuickActionMenu {
+    /// Hunk within the current step the actions apply to
+    pub hunk: usize,
+    pub cursor: usize,
+}
+
+impl QuickActionMenu {
+    pub fn new(hunk: usize) -> Self {
+        Self { hunk, cursor: 0 }
+    }
+
+    pub fn move_cursor(&mut self, delta: isize) {
+        let last = QUICK_ACTIONS.len() as isize - 1;
+        self.cursor = (self.cursor as isize + delta).clamp(0, last) as usize;
+    }
+
+    pub fn selected(&self) -> QuickAction {
+        QUICK_ACTIONS[self.cursor]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prompt_names_the_hunk() {
+        let hunk = Hunk::test("src/session.rs", 26, 40, "");
+        let prompt = QuickAction::Tests.prompt(&hunk, 1);
+        assert!(prompt.starts_with("Suggest tests for hunk 2 (src/session.rs, line 26)"));
+        assert_eq!(QuickAction::from_key('s'), Some(QuickAction::Simplify));
+    }
+}
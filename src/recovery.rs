@@ -0,0 +1,366 @@
+//! Crash recovery for the review in progress.
+//!
+//! While a walkthrough is open, its state is saved every few seconds (when it
+//! has changed) to ~/.docent/recovery/<pid>.json, and the file is removed on
+//! a clean exit. The session also holds an exclusive lock on <pid>.lock,
+//! which the OS releases however the process ends. A snapshot whose lock is
+//! free means docent crashed or its terminal died, and the next launch offers
+//! to restore it.
+
+use std::collections::hash_map::DefaultHasher;
+use std::fmt::Write;
+use std::fs::{self, File, OpenOptions};
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::api::TokenUsage;
+use crate::github::ForgeLink;
+use crate::model::{CommitInfo, PullRequestInfo, ReviewMode, StepOrganization, Walkthrough};
+use crate::parts::ReviewPart;
+use crate::session::Session;
+
+/// Minimum time between checks for changes worth saving.
+const AUTOSAVE_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Everything needed to pick a review up where it was left.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Snapshot {
+    /// Seconds since the Unix epoch
+    pub saved_at: u64,
+    pub pid: u32,
+    /// Working directory of the crashed session
+    pub cwd: String,
+    pub diff_input: Option<String>,
+    pub interdiff: bool,
+    pub commits: Vec<CommitInfo>,
+    #[serde(default)]
+    pub pull_request: Option<PullRequestInfo>,
+    pub forge: Option<ForgeLink>,
+    pub review_mode: ReviewMode,
+    pub step_organization: StepOrganization,
+    pub model: Option<String>,
+    pub walkthrough: Walkthrough,
+    pub reviewed_steps: Vec<bool>,
+    pub current_step: usize,
+    pub parts: Vec<ReviewPart>,
+    pub current_part: usize,
+    pub token_usage: TokenUsage,
+    #[serde(default)]
+    pub chat_usage: TokenUsage,
+}
+
+impl Snapshot {
+    /// Captures the session, or `None` while there is nothing to restore
+    /// (no steps yet, or a walkthrough still being generated).
+    pub fn capture(session: &Session) -> Option<Self> {
+        if session.walkthrough.steps.is_empty() || session.generation_in_progress {
+            return None;
+        }
+        Some(Self {
+            saved_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |d| d.as_secs()),
+            pid: std::process::id(),
+            cwd: std::env::current_dir()
+                .map(|dir| dir.display().to_string())
+                .unwrap_or_default(),
+            diff_input: session.diff_input.clone(),
+            interdiff: session.interdiff,
+            commits: session.commits.clone(),
+            pull_request: session.pull_request.clone(),
+            forge: session.forge.clone(),
+            review_mode: session.review_mode,
+            step_organization: session.step_organization,
+            model: session.generation_progress.model.clone(),
+            walkthrough: session.walkthrough.clone(),
+            reviewed_steps: session.reviewed_steps.clone(),
+            current_step: session.current_step,
+            parts: session.parts.clone(),
+            current_part: session.current_part,
+            token_usage: session.token_usage,
+            chat_usage: session.chat_usage,
+        })
+    }
+}
+
+/// A snapshot left behind by a session that didn't exit cleanly.
+#[derive(Debug, Clone)]
+pub struct RecoveryOffer {
+    pub path: PathBuf,
+    pub snapshot: Snapshot,
+}
+
+impl RecoveryOffer {
+    /// The snapshot to offer at startup: the most recent one saved in this
+    /// working directory, or failing that the most recent from anywhere,
+    /// which the dialog warns about.
+    pub fn find() -> Option<Self> {
+        let offers = Self::all();
+        let here = offers.iter().position(|offer| !offer.in_other_directory());
+        offers.into_iter().nth(here.unwrap_or(0))
+    }
+
+    /// Every snapshot whose process is no longer running, newest first.
+    pub fn all() -> Vec<Self> {
+        let own_pid = std::process::id();
+        let Some(entries) = recovery_dir().and_then(|dir| fs::read_dir(dir).ok()) else {
+            return vec![];
+        };
+        let mut offers: Vec<Self> = entries
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+            .filter_map(|path| {
+                let snapshot: Snapshot =
+                    serde_json::from_str(&fs::read_to_string(&path).ok()?).ok()?;
+                Some(Self { path, snapshot })
+            })
+            .filter(|offer| offer.snapshot.pid != own_pid && !session_running(&offer.path))
+            .collect();
+        offers.sort_by_key(|offer| std::cmp::Reverse(offer.snapshot.saved_at));
+        offers
+    }
+
+    /// Whether the review was saved in another working directory. Restoring
+    /// it here points tools, notes, and `record_review` at this repository.
+    pub fn in_other_directory(&self) -> bool {
+        std::env::current_dir().is_ok_and(|dir| dir.display().to_string() != self.snapshot.cwd)
+    }
+}
+
+/// Deletes a snapshot and its lock file.
+pub fn discard(snapshot: &Path) {
+    let _ = fs::remove_file(snapshot);
+    let _ = fs::remove_file(snapshot.with_extension("lock"));
+}
+
+/// One line per interrupted review, for `docent sessions`.
+pub fn summary(offers: &[RecoveryOffer]) -> String {
+    if offers.is_empty() {
+        return "No interrupted reviews to restore.\n".to_string();
+    }
+    let mut out = String::new();
+    for (index, offer) in offers.iter().enumerate() {
+        let snapshot = &offer.snapshot;
+        let reviewed = snapshot.reviewed_steps.iter().filter(|&&r| r).count();
+        let _ = write!(
+            out,
+            "{:>2}. {:<10} {}/{} steps reviewed  {}",
+            index + 1,
+            ago(snapshot.saved_at),
+            reviewed,
+            snapshot.walkthrough.step_count(),
+            snapshot.cwd
+        );
+        if let Some(pr) = &snapshot.pull_request {
+            let _ = write!(out, "  {}", pr.title);
+        }
+        out.push('\n');
+    }
+    out.push_str(
+        "\nRun docent in a review's directory to restore the most recent one there, \
+         or docent sessions --restore <number> to pick one.\n",
+    );
+    out
+}
+
+/// Rough age of a Unix timestamp, e.g. "5m ago".
+pub fn ago(timestamp: u64) -> String {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs());
+    match now.saturating_sub(timestamp) {
+        s if s < 60 => "just now".to_string(),
+        s if s < 3600 => format!("{}m ago", s / 60),
+        s if s < 86_400 => format!("{}h ago", s / 3600),
+        s => format!("{}d ago", s / 86_400),
+    }
+}
+
+/// Saves the session when it has changed, at most every `AUTOSAVE_INTERVAL`.
+pub struct Autosaver {
+    path: Option<PathBuf>,
+    last_check: Instant,
+    last_fingerprint: Option<u64>,
+    /// Held until exit so other launches can tell this session is running
+    lock: Option<File>,
+}
+
+impl Autosaver {
+    pub fn new() -> Self {
+        Self {
+            path: recovery_dir().map(|dir| dir.join(format!("{}.json", std::process::id()))),
+            last_check: Instant::now(),
+            last_fingerprint: None,
+            lock: None,
+        }
+    }
+
+    /// Saves the session if it's due and has changed. Returns whether it
+    /// was saved.
+    pub fn tick(&mut self, session: &Session) -> bool {
+        if self.last_check.elapsed() < AUTOSAVE_INTERVAL {
+            return false;
+        }
+        self.last_check = Instant::now();
+
+        let fingerprint = fingerprint(session);
+        if self.last_fingerprint == Some(fingerprint) {
+            return false;
+        }
+        if let (Some(path), Some(snapshot)) = (&self.path, Snapshot::capture(session)) {
+            if self.lock.is_none() {
+                self.lock = lock_session(path).ok();
+            }
+            if write_atomically(path, &snapshot).is_ok() {
+                self.last_fingerprint = Some(fingerprint);
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Removes this session's snapshot on a clean exit.
+    pub fn clear(&self) {
+        if let Some(path) = &self.path {
+            let _ = fs::remove_file(path);
+            let _ = fs::remove_file(path.with_extension("lock"));
+        }
+    }
+}
+
+/// Cheap summary of the parts of a session that change during a review.
+fn fingerprint(session: &Session) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    session.generation_in_progress.hash(&mut hasher);
+    session.current_part.hash(&mut hasher);
+    session.current_step.hash(&mut hasher);
+    session.reviewed_steps.hash(&mut hasher);
+    for step in &session.walkthrough.steps {
+        step.id.hash(&mut hasher);
+        step.summary.hash(&mut hasher);
+        step.notes.hash(&mut hasher);
+        step.flags.hash(&mut hasher);
+        for hunk in &step.hunks {
+            hunk.reviewed.hash(&mut hasher);
+        }
+        step.messages.len().hash(&mut hasher);
+        step.messages
+            .last()
+            .map(|m| m.content.len())
+            .hash(&mut hasher);
+    }
+    // The conversation about the whole change
+    let messages = &session.walkthrough.messages;
+    messages.len().hash(&mut hasher);
+    messages.last().map(|m| m.content.len()).hash(&mut hasher);
+    hasher.finish()
+}
+
+fn write_atomically(path: &PathBuf, snapshot: &Snapshot) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let json = serde_json::to_string(snapshot).map_err(std::io::Error::other)?;
+    let tmp = path.with_extension("json.tmp");
+    fs::write(&tmp, json)?;
+    fs::rename(tmp, path)
+}
+
+fn recovery_dir() -> Option<PathBuf> {
+    dirs::home_dir().map(|h| h.join(".docent").join("recovery"))
+}
+
+/// Creates and exclusively locks the lock file next to a snapshot.
+fn lock_session(snapshot: &Path) -> std::io::Result<File> {
+    if let Some(parent) = snapshot.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let file = File::create(snapshot.with_extension("lock"))?;
+    file.try_lock()?;
+    Ok(file)
+}
+
+/// Whether the process that wrote a snapshot still holds its lock. A missing
+/// lock file, or one that can't be locked at all, counts as not running.
+fn session_running(snapshot: &Path) -> bool {
+    let Ok(file) = OpenOptions::new()
+        .write(true)
+        .open(snapshot.with_extension("lock"))
+    else {
+        return false;
+    };
+    matches!(file.try_lock(), Err(fs::TryLockError::WouldBlock))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{Flag, Hunk, Message, Note, Severity, Step};
+
+    fn session() -> Session {
+        let step = Step {
+            title: "Add parser".to_string(),
+            summary: "Parses input.".to_string(),
+            hunks: vec![Hunk::test("src/parser.rs", 1, 3, "+fn parse() {}")],
+            ..Step::test("1", "src/parser.rs")
+        };
+        let walkthrough = Walkthrough {
+            steps: vec![step],
+            messages: vec![],
+        };
+        Session::new(walkthrough, ReviewMode::default())
+    }
+
+    #[test]
+    fn test_review_changes_change_the_fingerprint() {
+        let mut session = session();
+        let mut seen = vec![fingerprint(&session)];
+        let mut changed = |session: &Session| {
+            let print = fingerprint(session);
+            assert!(!seen.contains(&print));
+            seen.push(print);
+        };
+
+        let note = Note {
+            text: "Check the error path".to_string(),
+        };
+        session.walkthrough.steps[0].notes.push(note);
+        changed(&session);
+        session.walkthrough.steps[0].notes[0].text = "Check both error paths".to_string();
+        changed(&session);
+        session.walkthrough.steps[0].hunks[0].reviewed = true;
+        changed(&session);
+        session.walkthrough.steps[0].summary = "Parses all input.".to_string();
+        changed(&session);
+        session.walkthrough.steps[0].flags.push(Flag {
+            severity: Severity::Question,
+            text: String::new(),
+            location: None,
+        });
+        changed(&session);
+        session.walkthrough.steps[0].flags[0].severity = Severity::Blocker;
+        changed(&session);
+        session
+            .walkthrough
+            .messages
+            .push(Message::assistant("No migration needed."));
+        changed(&session);
+    }
+
+    #[test]
+    fn test_a_session_runs_while_its_lock_is_held() {
+        let dir = std::env::temp_dir().join(format!("docent-recovery-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let snapshot = dir.join("42.json");
+        assert!(!session_running(&snapshot));
+
+        let lock = lock_session(&snapshot).unwrap();
+        assert!(session_running(&snapshot));
+        drop(lock);
+        assert!(!session_running(&snapshot));
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,165 @@
+//! Records a completed review in git so there is an auditable trace that a
+//! structured review happened.
+//!
+//! The review report is stored as a git blob, and a note is attached to HEAD
+//! (under `refs/notes/docent`) carrying a `Reviewed-with` trailer plus the
+//! report's object hash. Notes leave commit history untouched; the same
+//! trailer can be pasted into a merge commit message if preferred.
+
+use std::fmt::Write;
+
+use tokio::io::AsyncWriteExt;
+use tokio::process::Command;
+
+use crate::model::{Priority, ReviewMode, Walkthrough};
+
+pub const NOTES_REF: &str = "docent";
+
+/// Short verdict describing how thoroughly the walkthrough was reviewed.
+pub fn verdict(walkthrough: &Walkthrough, reviewed: &[bool]) -> String {
+    let reviewed_count = reviewed.iter().filter(|&&r| r).count();
+    let status = if reviewed_count == walkthrough.step_count() {
+        "reviewed".to_string()
+    } else {
+        format!("{}/{} steps reviewed", reviewed_count, walkthrough.step_count())
+    };
+
+    let critical = walkthrough
+        .steps
+        .iter()
+        .filter(|s| s.priority == Priority::Critical)
+        .count();
+    if critical > 0 {
+        format!("{}, {} critical", status, critical)
+    } else {
+        status
+    }
+}
+
+/// The `Reviewed-with` trailer line for a commit message or note.
+pub fn trailer(verdict: &str) -> String {
+    format!("Reviewed-with: docent ({})", verdict)
+}
+
+/// Plain-text report of the review: one line per step with its state.
+pub fn build_report(walkthrough: &Walkthrough, reviewed: &[bool], mode: ReviewMode) -> String {
+    let mode_label = match mode {
+        ReviewMode::Review => "review",
+        ReviewMode::Walkthrough => "walkthrough",
+    };
+
+    let mut out = format!("docent {} report\n\n", mode_label);
+    for (i, step) in walkthrough.steps.iter().enumerate() {
+        let mark = if reviewed.get(i).copied().unwrap_or(false) {
+            "x"
+        } else {
+            " "
+        };
+        let indent = "  ".repeat(step.depth as usize);
+        let _ = writeln!(
+            out,
+            "{}[{}] {} {} ({:?})",
+            indent, mark, step.id, step.title, step.priority
+        );
+        for hunk in &step.hunks {
+            let _ = writeln!(
+                out,
+                "{}      {}:{}-{}",
+                indent, hunk.file_path, hunk.start_line, hunk.end_line
+            );
+        }
+    }
+    out
+}
+
+/// Writes the report as a git blob and attaches a note to HEAD referencing it.
+/// Returns the report blob's abbreviated hash.
+pub async fn record_git_note(report: &str, verdict: &str) -> Result<String, String> {
+    let report_hash = hash_object(report).await?;
+    let note = format!("{}\nDocent-Report: {}\n", trailer(verdict), report_hash);
+
+    let output = Command::new("git")
+        .args(["notes", "--ref", NOTES_REF, "add", "-f", "-m", &note, "HEAD"])
+        .output()
+        .await
+        .map_err(|e| format!("failed to run git notes: {e}"))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("git notes failed: {}", stderr.trim()));
+    }
+
+    Ok(report_hash[..12.min(report_hash.len())].to_string())
+}
+
+async fn hash_object(content: &str) -> Result<String, String> {
+    let mut child = Command::new("git")
+        .args(["hash-object", "-w", "--stdin"])
+        .stdin(std::process::Stdio::piped())
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("failed to run git hash-object: {e}"))?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin
+            .write_all(content.as_bytes())
+            .await
+            .map_err(|e| format!("failed to write report: {e}"))?;
+    }
+
+    let output = child
+        .wait_with_output()
+        .await
+        .map_err(|e| format!("git hash-object failed: {e}"))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("git hash-object failed: {}", stderr.trim()));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{Hunk, Step};
+
+    fn step(id: &str, priority: Priority) -> Step {
+        Step {
+            priority,
+            hunks: vec![Hunk::test("src/lib.rs", 1, 4, "@@ -1,3 +1,4 @@")],
+            ..Step::test(id, "src/lib.rs")
+        }
+    }
+
+    #[test]
+    fn verdict_counts_reviewed_and_critical() {
+        let walkthrough = Walkthrough {
+            steps: vec![step("1", Priority::Critical), step("2", Priority::Minor)],
+            messages: vec![],
+        };
+        assert_eq!(verdict(&walkthrough, &[true, true]), "reviewed, 1 critical");
+        assert_eq!(
+            verdict(&walkthrough, &[true, false]),
+            "1/2 steps reviewed, 1 critical"
+        );
+    }
+
+    #[test]
+    fn trailer_format() {
+        assert_eq!(trailer("reviewed"), "Reviewed-with: docent (reviewed)");
+    }
+
+    #[test]
+    fn report_lists_steps_and_hunks() {
+        let walkthrough = Walkthrough {
+            steps: vec![step("1", Priority::Normal)],
+            messages: vec![],
+        };
+        let report = build_report(&walkthrough, &[true], ReviewMode::Review);
+        assert!(report.contains("[x] 1 Step 1 (Normal)"));
+        assert!(report.contains("src/lib.rs:1-4"));
+    }
+}
@@ -0,0 +1,188 @@
+//! Scripted key replay for demos, smoke tests, and bug reproductions.
+//!
+//! A script is a text file with one command per line:
+//!
+//! ```text
+//! # Open the second step and ask about it
+//! delay 150ms        # pause between keys from here on
+//! key Down
+//! key Ctrl+l
+//! type Why is this needed?
+//! key Enter
+//! wait 2s
+//! paste multi-word text in one event
+//! ```
+//!
+//! Blank lines and `#` comments are ignored. Keys are sent through the same
+//! channel as terminal input, so the real keyboard keeps working alongside.
+
+use std::time::Duration;
+
+use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers};
+use thiserror::Error;
+
+/// Pause between scripted events unless the script sets its own.
+const DEFAULT_DELAY: Duration = Duration::from_millis(100);
+
+#[derive(Debug, Error)]
+#[error("script line {line}: {message}")]
+pub struct ScriptError {
+    pub line: usize,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ScriptStep {
+    Event(Event),
+    Wait(Duration),
+}
+
+/// Parses a script into events with the pauses to leave around them.
+pub fn parse(script: &str) -> Result<Vec<ScriptStep>, ScriptError> {
+    let mut delay = DEFAULT_DELAY;
+    let mut steps = Vec::new();
+
+    for (index, raw) in script.lines().enumerate() {
+        let error = |message: String| ScriptError {
+            line: index + 1,
+            message,
+        };
+        let line = match raw.split_once(" #") {
+            Some((before, _)) => before,
+            None => raw,
+        };
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let (command, argument) = line.split_once(' ').unwrap_or((line, ""));
+
+        let mut events = Vec::new();
+        match command {
+            "key" => events.push(Event::Key(parse_key(argument.trim()).map_err(error)?)),
+            "type" => events.extend(
+                argument
+                    .chars()
+                    .map(|c| Event::Key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE))),
+            ),
+            "paste" => events.push(Event::Paste(argument.to_string())),
+            "wait" => steps.push(ScriptStep::Wait(
+                parse_duration(argument.trim()).map_err(error)?,
+            )),
+            "delay" => delay = parse_duration(argument.trim()).map_err(error)?,
+            other => return Err(error(format!("unknown command '{}'", other))),
+        }
+        for event in events {
+            steps.push(ScriptStep::Event(event));
+            steps.push(ScriptStep::Wait(delay));
+        }
+    }
+    Ok(steps)
+}
+
+/// Parses `Enter`, `j`, `Ctrl+d`, `Shift+Tab`, `F5` and similar.
+fn parse_key(spec: &str) -> Result<KeyEvent, String> {
+    if spec.is_empty() {
+        return Err("missing key".to_string());
+    }
+    let mut modifiers = KeyModifiers::NONE;
+    let mut name = spec;
+    // A lone "+" is the key itself, not a separator
+    while let Some((modifier, rest)) = name.split_once('+').filter(|(_, rest)| !rest.is_empty()) {
+        modifiers |= match modifier.to_ascii_lowercase().as_str() {
+            "ctrl" | "control" => KeyModifiers::CONTROL,
+            "alt" => KeyModifiers::ALT,
+            "shift" => KeyModifiers::SHIFT,
+            _ => return Err(format!("unknown modifier '{}'", modifier)),
+        };
+        name = rest;
+    }
+
+    let mut chars = name.chars();
+    let code = match (chars.next(), chars.next()) {
+        (Some(c), None) => KeyCode::Char(c),
+        _ => match name.to_ascii_lowercase().as_str() {
+            "enter" | "return" => KeyCode::Enter,
+            "esc" | "escape" => KeyCode::Esc,
+            "tab" if modifiers.contains(KeyModifiers::SHIFT) => KeyCode::BackTab,
+            "tab" => KeyCode::Tab,
+            "backtab" => KeyCode::BackTab,
+            "backspace" => KeyCode::Backspace,
+            "delete" | "del" => KeyCode::Delete,
+            "space" => KeyCode::Char(' '),
+            "up" => KeyCode::Up,
+            "down" => KeyCode::Down,
+            "left" => KeyCode::Left,
+            "right" => KeyCode::Right,
+            "home" => KeyCode::Home,
+            "end" => KeyCode::End,
+            "pageup" => KeyCode::PageUp,
+            "pagedown" => KeyCode::PageDown,
+            function => match function.strip_prefix('f').and_then(|n| n.parse().ok()) {
+                Some(n) => KeyCode::F(n),
+                None => return Err(format!("unknown key '{}'", name)),
+            },
+        },
+    };
+    Ok(KeyEvent::new(code, modifiers))
+}
+
+/// Parses `500ms`, `2s` or `1.5s`.
+fn parse_duration(spec: &str) -> Result<Duration, String> {
+    let invalid = || format!("invalid duration '{}' (expected e.g. 500ms or 2s)", spec);
+    if let Some(millis) = spec.strip_suffix("ms") {
+        return millis
+            .parse()
+            .map(Duration::from_millis)
+            .map_err(|_| invalid());
+    }
+    let seconds: f64 = spec
+        .strip_suffix('s')
+        .and_then(|s| s.parse().ok())
+        .ok_or_else(invalid)?;
+    Duration::try_from_secs_f64(seconds).map_err(|_| invalid())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(code: KeyCode, modifiers: KeyModifiers) -> ScriptStep {
+        ScriptStep::Event(Event::Key(KeyEvent::new(code, modifiers)))
+    }
+
+    #[test]
+    fn test_parse_script() {
+        let script = "\
+# demo
+delay 50ms
+key Ctrl+d   # scroll
+type hi
+wait 1.5s
+key +
+";
+        let wait = ScriptStep::Wait(Duration::from_millis(50));
+        assert_eq!(
+            parse(script).unwrap(),
+            [
+                key(KeyCode::Char('d'), KeyModifiers::CONTROL),
+                wait.clone(),
+                key(KeyCode::Char('h'), KeyModifiers::NONE),
+                wait.clone(),
+                key(KeyCode::Char('i'), KeyModifiers::NONE),
+                wait.clone(),
+                ScriptStep::Wait(Duration::from_millis(1500)),
+                key(KeyCode::Char('+'), KeyModifiers::NONE),
+                wait,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_errors_name_the_line() {
+        let err = parse("key j\nkey Hyper+x\n").unwrap_err();
+        assert_eq!(err.line, 2);
+        assert!(parse("wait soon").is_err());
+        assert!(parse("jump 3").is_err());
+    }
+}
@@ -1,5 +1,7 @@
 use std::cell::Cell;
 
+use ratatui::layout::{Position, Rect};
+
 /// Core scroll state with render-time clamping.
 ///
 /// Uses `Cell` because render needs to clamp the scroll position to the actual
@@ -8,6 +10,10 @@ use std::cell::Cell;
 #[derive(Debug, Default)]
 pub struct Scroll {
     offset: Cell<usize>,
+    /// Largest offset as of the last render
+    max: Cell<usize>,
+    /// Where the scrollbar was last drawn, if it was
+    track: Cell<Option<Rect>>,
 }
 
 impl Scroll {
@@ -37,10 +43,39 @@ impl Scroll {
 
     /// Clamp offset to max, persist, and return clamped value.
     pub fn clamped(&self, max: usize) -> usize {
+        self.max.set(max);
         let clamped = self.offset.get().min(max);
         self.offset.set(clamped);
         clamped
     }
+
+    /// Records where the scrollbar was drawn, so the mouse can grab it.
+    pub fn set_track(&self, track: Option<Rect>) {
+        self.track.set(track);
+    }
+
+    pub fn track_contains(&self, column: u16, row: u16) -> bool {
+        self.track
+            .get()
+            .is_some_and(|track| track.contains(Position::new(column, row)))
+    }
+
+    /// Offset for the mouse at `row` on the scrollbar: the top of the track
+    /// is the start of the content and the bottom is the end.
+    fn offset_at_row(&self, row: u16) -> Option<usize> {
+        let track = self.track.get()?;
+        let last_row = track.height.saturating_sub(1);
+        let span = usize::from(last_row.max(1));
+        let pos = usize::from(row.saturating_sub(track.y).min(last_row));
+        Some((pos * self.max.get() + span / 2) / span)
+    }
+
+    /// Scrolls to match the mouse at `row` on the scrollbar.
+    pub fn drag_to(&self, row: u16) {
+        if let Some(offset) = self.offset_at_row(row) {
+            self.set(offset);
+        }
+    }
 }
 
 /// Diff scroll: top-anchored, offset = lines from top.
@@ -96,6 +131,17 @@ impl ChatScroll {
         self.in_scrollback.set(false);
     }
 
+    /// Lines scrolled up from the bottom.
+    pub fn offset(&self) -> usize {
+        self.scroll.get()
+    }
+
+    /// Scrolls back to an `offset` taken earlier.
+    pub fn restore(&mut self, offset: usize) {
+        self.scroll.set(offset);
+        self.in_scrollback.set(offset > 0);
+    }
+
     /// Get scroll position from top for rendering.
     /// Clamps offset to max_offset and resets scrollback mode if nothing to scroll.
     pub fn position_from_top(&self, max_offset: usize) -> usize {
@@ -106,4 +152,52 @@ impl ChatScroll {
         }
         max_offset.saturating_sub(clamped)
     }
+
+    pub fn set_track(&self, track: Option<Rect>) {
+        self.scroll.set_track(track);
+    }
+
+    pub fn track_contains(&self, column: u16, row: u16) -> bool {
+        self.scroll.track_contains(column, row)
+    }
+
+    /// Scrolls to match the mouse at `row` on the scrollbar.
+    pub fn drag_to(&mut self, row: u16) {
+        if let Some(from_top) = self.scroll.offset_at_row(row) {
+            let from_bottom = self.scroll.max.get().saturating_sub(from_top);
+            self.scroll.set(from_bottom);
+            self.in_scrollback.set(from_bottom > 0);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dragging_the_scrollbar_maps_rows_to_offsets() {
+        let scroll = Scroll::new();
+        scroll.clamped(100);
+        scroll.set_track(Some(Rect::new(10, 1, 1, 11)));
+        assert!(scroll.track_contains(10, 5));
+        assert!(!scroll.track_contains(9, 5));
+
+        scroll.drag_to(1);
+        assert_eq!(scroll.get(), 0);
+        scroll.drag_to(6);
+        assert_eq!(scroll.get(), 50);
+        // Past the end of the track while dragging
+        scroll.drag_to(40);
+        assert_eq!(scroll.get(), 100);
+
+        let mut chat = ChatScroll::new();
+        chat.position_from_top(100);
+        chat.set_track(Some(Rect::new(0, 0, 1, 11)));
+        chat.drag_to(0);
+        assert!(chat.in_scrollback());
+        assert_eq!(chat.position_from_top(100), 0);
+        chat.drag_to(10);
+        assert!(!chat.in_scrollback());
+    }
 }
@@ -1,13 +1,23 @@
 //! Search functionality for the diff viewer.
 
+use regex::{Regex, RegexBuilder};
+
+use crate::model::Step;
+
+/// Results beyond this are left out of a walkthrough-wide search.
+pub const MAX_STEP_MATCHES: usize = 500;
+
+/// Executed queries kept for recall; older ones are dropped.
+const MAX_HISTORY: usize = 50;
+
 /// A match found in the diff content.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Hash)]
 pub struct SearchMatch {
     /// Line index (0-based) within the flattened diff content
     pub line: usize,
-    /// Start column of the match
+    /// Byte offset of the match start within the line
     pub start: usize,
-    /// End column (exclusive) of the match
+    /// Byte offset (exclusive) of the match end within the line
     pub end: usize,
 }
 
@@ -24,6 +34,14 @@ pub struct SearchState {
     pub matches: Vec<SearchMatch>,
     /// Index of the current match (for n/N navigation)
     pub current: usize,
+    /// Whether queries are regular expressions rather than literal text
+    pub regex: bool,
+    /// Executed queries, oldest first, without repeats
+    pub history: Vec<String>,
+    /// Position in `history` while recalling queries with Up/Down
+    history_index: Option<usize>,
+    /// What was typed before recalling began, restored after the newest query
+    draft: String,
 }
 
 impl SearchState {
@@ -35,12 +53,14 @@ impl SearchState {
     pub fn start(&mut self) {
         self.active = true;
         self.input.clear();
+        self.history_index = None;
     }
 
     /// Cancel search input mode without searching
     pub fn cancel(&mut self) {
         self.active = false;
         self.input.clear();
+        self.history_index = None;
     }
 
     /// Execute search with the current input (finalizes and exits input mode)
@@ -54,6 +74,7 @@ impl SearchState {
         self.query = Some(self.input.clone());
         self.find_matches(lines);
         self.current = 0;
+        self.remember(self.input.clone());
     }
 
     /// Execute incremental search while typing (stays in input mode)
@@ -79,32 +100,68 @@ impl SearchState {
         self.current = 0;
     }
 
+    /// Adds a query to the end of the history, moving it there if it was
+    /// already searched for.
+    fn remember(&mut self, query: String) {
+        self.history.retain(|q| *q != query);
+        self.history.push(query);
+        if self.history.len() > MAX_HISTORY {
+            self.history.remove(0);
+        }
+        self.history_index = None;
+    }
+
+    /// Replaces the input with the query before the one being recalled.
+    pub fn history_prev(&mut self) {
+        let index = match self.history_index {
+            Some(index) => index.saturating_sub(1),
+            None if self.history.is_empty() => return,
+            None => {
+                self.draft = std::mem::take(&mut self.input);
+                self.history.len() - 1
+            }
+        };
+        self.history_index = Some(index);
+        self.input = self.history[index].clone();
+    }
+
+    /// Replaces the input with the query after the one being recalled, or
+    /// with what was typed before recalling began.
+    pub fn history_next(&mut self) {
+        let Some(index) = self.history_index else {
+            return;
+        };
+        if index + 1 < self.history.len() {
+            self.history_index = Some(index + 1);
+            self.input = self.history[index + 1].clone();
+        } else {
+            self.history_index = None;
+            self.input = std::mem::take(&mut self.draft);
+        }
+    }
+
+    /// Switches between regex and literal queries.
+    pub fn toggle_regex(&mut self) {
+        self.regex = !self.regex;
+    }
+
     /// Find all matches for the current query in the given lines
     fn find_matches(&mut self, lines: &[String]) {
         self.matches.clear();
 
-        let query = match &self.query {
-            Some(q) => q.to_lowercase(),
+        let pattern = match &self.query {
+            Some(q) => query_pattern(q, self.regex),
             None => return,
         };
 
         for (line_idx, line) in lines.iter().enumerate() {
-            let line_lower = line.to_lowercase();
-            let mut search_start = 0;
-
-            while let Some(pos) = line_lower.get(search_start..).and_then(|s| s.find(&query)) {
-                let start = search_start + pos;
-                let end = start + query.len();
-
-                // Only add match if positions are valid for original line
-                if end <= line.len() {
-                    self.matches.push(SearchMatch {
-                        line: line_idx,
-                        start,
-                        end,
-                    });
-                }
-                search_start = end;
+            // Empty matches (from `a*` and the like) have nothing to highlight
+            for found in pattern.find_iter(line).filter(|m| !m.is_empty()) {
+                self.matches.push(SearchMatch {
+                    line: line_idx,
+                    start: found.start(),
+                    end: found.end(),
+                });
             }
         }
     }
@@ -156,6 +213,215 @@ impl SearchState {
     }
 }
 
+/// Compiles a search query. Matching ignores case unless the query has an
+/// uppercase letter. A query that isn't a valid regex is searched for as
+/// literal text.
+fn query_pattern(query: &str, regex: bool) -> Regex {
+    let build = |pattern: &str| {
+        RegexBuilder::new(pattern)
+            .case_insensitive(!query.chars().any(char::is_uppercase))
+            .build()
+    };
+    regex
+        .then(|| build(query).ok())
+        .flatten()
+        .unwrap_or_else(|| build(&regex::escape(query)).expect("escaped queries are valid"))
+}
+
+/// A line matching a walkthrough-wide search.
+#[derive(Debug, Clone)]
+pub struct StepMatch {
+    /// Index of the step the line belongs to
+    pub step: usize,
+    /// Line index within the step's display lines
+    pub line: usize,
+    /// File and new-file line number, or just the file for a file header
+    pub location: String,
+    pub text: String,
+}
+
+/// Finds the lines of every step containing `query`, ignoring case, in
+/// walkthrough order.
+pub fn search_steps(steps: &[Step], query: &str) -> Vec<StepMatch> {
+    if query.is_empty() {
+        return Vec::new();
+    }
+    let query = query.to_lowercase();
+    let mut results = Vec::new();
+    for (step_index, step) in steps.iter().enumerate() {
+        for (line, text) in step.display_lines().into_iter().enumerate() {
+            if !text.to_lowercase().contains(&query) {
+                continue;
+            }
+            let location = match step.locate_display_line(line) {
+                Some((hunk, Some(index))) => {
+                    let hunk = &step.hunks[hunk];
+                    format!("{}:{}", hunk.file_path, hunk.new_line_at(index))
+                }
+                Some((hunk, None)) => step.hunks[hunk].file_path.clone(),
+                None => String::new(),
+            };
+            results.push(StepMatch {
+                step: step_index,
+                line,
+                location,
+                text,
+            });
+            if results.len() == MAX_STEP_MATCHES {
+                return results;
+            }
+        }
+    }
+    results
+}
+
+/// The walkthrough-wide search dialog: its query and results.
+#[derive(Debug, Clone, Default)]
+pub struct GlobalSearch {
+    pub input: String,
+    pub results: Vec<StepMatch>,
+    /// Index of the highlighted result
+    pub selected: usize,
+}
+
+impl GlobalSearch {
+    pub fn new(input: String, steps: &[Step]) -> Self {
+        let mut search = Self {
+            input,
+            ..Self::default()
+        };
+        search.update(steps);
+        search
+    }
+
+    /// Searches again for the current input.
+    pub fn update(&mut self, steps: &[Step]) {
+        self.results = search_steps(steps, &self.input);
+        self.selected = 0;
+    }
+
+    pub fn select_next(&mut self) {
+        if self.selected + 1 < self.results.len() {
+            self.selected += 1;
+        }
+    }
+
+    pub fn select_prev(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    pub fn selected_match(&self) -> Option<&StepMatch> {
+        self.results.get(self.selected)
+    }
+}
+
+/// Scores how well `query` fuzzy-matches `text`: every character of the
+/// query must appear in order, ignoring case. Runs of consecutive characters
+/// and characters starting a word score higher. None when the query isn't
+/// found.
+pub fn fuzzy_score(query: &str, text: &str) -> Option<i64> {
+    let mut text_chars = text.chars();
+    let mut prev: Option<char> = None;
+    let mut score = 0;
+    let mut first = true;
+    for q in query.chars().filter(|c| !c.is_whitespace()) {
+        let mut adjacent = !first;
+        loop {
+            let c = text_chars.next()?;
+            let word_start = prev.is_none_or(|p| !p.is_alphanumeric());
+            prev = Some(c);
+            if c.to_lowercase().eq(q.to_lowercase()) {
+                score += 1 + if adjacent { 4 } else { 0 } + if word_start { 3 } else { 0 };
+                break;
+            }
+            adjacent = false;
+        }
+        first = false;
+    }
+    Some(score)
+}
+
+/// A step matching the step finder's query.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StepCandidate {
+    /// Index of the step in the walkthrough
+    pub step: usize,
+    /// First hunk in the file whose path matched, when the path matched
+    /// better than the title or summary
+    pub hunk: Option<usize>,
+}
+
+/// The steps whose title, summary, or file paths fuzzy-match `query`, best
+/// first. Titles count double. Every step matches an empty query, in order.
+pub fn find_steps(steps: &[Step], query: &str) -> Vec<StepCandidate> {
+    let mut scored: Vec<(i64, StepCandidate)> = steps
+        .iter()
+        .enumerate()
+        .filter_map(|(index, step)| {
+            let text = [
+                fuzzy_score(query, &step.title).map(|score| score * 2),
+                fuzzy_score(query, &step.summary),
+            ]
+            .into_iter()
+            .flatten()
+            .max();
+            let path = step
+                .hunks
+                .iter()
+                .enumerate()
+                .filter_map(|(hunk, h)| Some((fuzzy_score(query, &h.file_path)?, hunk)))
+                // The first hunk wins ties, so it isn't a later hunk in the file
+                .min_by_key(|(score, hunk)| (-score, *hunk));
+            let (score, hunk) = match (text, path) {
+                (Some(text), Some((score, _))) if text >= score => (text, None),
+                (_, Some((score, hunk))) => (score, Some(hunk)),
+                (Some(text), None) => (text, None),
+                (None, None) => return None,
+            };
+            Some((score, StepCandidate { step: index, hunk }))
+        })
+        .collect();
+    scored.sort_by_key(|(score, _)| -score);
+    scored.into_iter().map(|(_, candidate)| candidate).collect()
+}
+
+/// The jump-to-step dialog: its query and the matching steps.
+#[derive(Debug, Clone, Default)]
+pub struct StepFinder {
+    pub input: String,
+    pub results: Vec<StepCandidate>,
+    /// Index of the highlighted result
+    pub selected: usize,
+}
+
+impl StepFinder {
+    pub fn new(steps: &[Step]) -> Self {
+        let mut finder = Self::default();
+        finder.update(steps);
+        finder
+    }
+
+    /// Matches again for the current input.
+    pub fn update(&mut self, steps: &[Step]) {
+        self.results = find_steps(steps, &self.input);
+        self.selected = 0;
+    }
+
+    pub fn select_next(&mut self) {
+        if self.selected + 1 < self.results.len() {
+            self.selected += 1;
+        }
+    }
+
+    pub fn select_prev(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    pub fn selected_match(&self) -> Option<&StepCandidate> {
+        self.results.get(self.selected)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -189,6 +455,74 @@ mod tests {
         assert_eq!(state.matches.len(), 3);
     }
 
+    #[test]
+    fn test_smart_case() {
+        let lines = vec!["FOO foo Foo".to_string()];
+
+        let mut state = SearchState::new();
+        state.input = "Foo".to_string();
+        state.execute(&lines);
+
+        assert_eq!(state.matches.len(), 1);
+        assert_eq!(state.matches[0].start, 8);
+    }
+
+    #[test]
+    fn test_regex_queries() {
+        let lines = vec!["let x1 = y22; // (a)".to_string()];
+
+        let mut state = SearchState::new();
+        state.toggle_regex();
+        state.input = r"\d+".to_string();
+        state.execute(&lines);
+        let spans: Vec<(usize, usize)> = state.matches.iter().map(|m| (m.start, m.end)).collect();
+        assert_eq!(spans, [(5, 6), (10, 12)]);
+
+        // An unbalanced group falls back to a literal search
+        state.input = "(a".to_string();
+        state.execute(&lines);
+        assert_eq!(state.matches.len(), 1);
+        assert_eq!(state.matches[0].start, 17);
+    }
+
+    #[test]
+    fn test_match_offsets_are_bytes_of_the_original_line() {
+        // Lowercasing `İ` changes its length, which used to shift the spans
+        let lines = vec!["İx ix".to_string()];
+
+        let mut state = SearchState::new();
+        state.input = "ix".to_string();
+        state.execute(&lines);
+
+        let last = state.matches.last().unwrap();
+        assert_eq!(&lines[0][last.start..last.end], "ix");
+    }
+
+    #[test]
+    fn test_history_recall() {
+        let lines = vec!["foo bar".to_string()];
+
+        let mut state = SearchState::new();
+        for query in ["foo", "bar", "foo"] {
+            state.start();
+            state.input = query.to_string();
+            state.execute(&lines);
+        }
+        assert_eq!(state.history, ["bar", "foo"]);
+
+        state.start();
+        state.input = "ba".to_string();
+        state.history_prev();
+        assert_eq!(state.input, "foo");
+        state.history_prev();
+        state.history_prev();
+        assert_eq!(state.input, "bar");
+        state.history_next();
+        assert_eq!(state.input, "foo");
+        state.history_next();
+        assert_eq!(state.input, "ba");
+    }
+
     #[test]
     fn test_navigation() {
         let lines = vec!["a a a".to_string()];
@@ -212,4 +546,56 @@ mod tests {
         state.prev_match();
         assert_eq!(state.current, 2); // wraps back
     }
+
+    fn step(file: &str, content: &str) -> Step {
+        Step {
+            hunks: vec![crate::model::Hunk::test(file, 1, 1, content)],
+            ..Step::test("", file)
+        }
+    }
+
+    #[test]
+    fn test_search_steps() {
+        let steps = vec![
+            step("a.rs", "@@ -1,1 +1,2 @@\n let x = 1;\n+let foo = 2;"),
+            step("foo.rs", "@@ -5,1 +5,1 @@\n-Foo::new()"),
+        ];
+
+        let results = search_steps(&steps, "FOO");
+        let found: Vec<(usize, usize, &str)> = results
+            .iter()
+            .map(|m| (m.step, m.line, m.location.as_str()))
+            .collect();
+        assert_eq!(
+            found,
+            [(0, 4, "a.rs:2"), (1, 0, "foo.rs"), (1, 3, "foo.rs:5")]
+        );
+        assert!(search_steps(&steps, "").is_empty());
+    }
+
+    #[test]
+    fn test_fuzzy_score() {
+        assert!(fuzzy_score("rtc", "Retry the client").is_some());
+        assert!(fuzzy_score("ctr", "Retry the client").is_none());
+        assert_eq!(fuzzy_score("", "anything"), Some(0));
+        // Word starts and runs beat scattered letters
+        assert!(fuzzy_score("cli", "client") > fuzzy_score("cli", "cache lookup index"));
+        assert!(fuzzy_score("rtc", "Retry the client") > fuzzy_score("rtc", "interactive"));
+    }
+
+    #[test]
+    fn test_find_steps() {
+        let mut retry = step("src/client.rs", "");
+        retry.title = "Add retry to the client".to_string();
+        let mut config = step("src/config/retry.rs", "");
+        config.title = "Read settings".to_string();
+        let steps = vec![retry, config, step("README.md", "")];
+
+        let found: Vec<(usize, Option<usize>)> = find_steps(&steps, "retry")
+            .iter()
+            .map(|c| (c.step, c.hunk))
+            .collect();
+        assert_eq!(found, [(0, None), (1, Some(0))]);
+        assert_eq!(find_steps(&steps, "").len(), 3);
+    }
 }
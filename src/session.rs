@@ -1,9 +1,55 @@
-use crate::api::TokenUsage;
-use crate::diff::FileFilter;
-use crate::model::{CommitInfo, Message, ReviewMode, Step, Walkthrough};
-use crate::settings::ApiKeySource;
+use crate::api::{
+    ClientConfig, MAX_FOLLOW_UPS, RetryNotice, TokenUsage, chat_context, context_with_summary,
+    estimate_tokens, fit_chat_history, follow_up_prompt, history_summary_prompt, model_pricing,
+    whole_change_context,
+};
+use crate::chats::SavedChats;
+use crate::diff::{FileFilter, ParsedDiff};
+use crate::generation::GenerationOptions;
+use crate::github::{ForgeLink, PullRequestReview};
+use crate::model::{
+    CommitInfo, Message, MessageRole, PriorityFilter, PullRequestInfo, ReviewMode, Step, StepOrder,
+    StepOrganization, Walkthrough,
+};
+use crate::parts::{ReviewPart, file_sizes};
+use crate::recovery::Snapshot;
+use crate::review_record;
+use crate::settings::{ApiKeySource, ApiSettings, ChatContext, ChatSettings, Provider};
 
 use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::time::Instant;
+use tokio::sync::mpsc;
+
+/// The prompt asking for a summary of a conversation's oldest messages, and
+/// how many they are.
+pub type HistorySummaryPrompt = (usize, String);
+
+/// A conversation: a step's own, or the one about the whole change.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ChatThread {
+    Step(usize),
+    Walkthrough,
+}
+
+impl ChatThread {
+    /// The step index of a step's conversation.
+    pub fn step(self) -> Option<usize> {
+        match self {
+            ChatThread::Step(index) => Some(index),
+            ChatThread::Walkthrough => None,
+        }
+    }
+}
+
+/// Why step notes can't be posted as a pull request review.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PrReviewBlocked {
+    /// The diff didn't come from a GitHub pull request
+    NoPullRequest,
+    /// No step has a note
+    NoNotes,
+}
 
 #[derive(Debug, Clone, Default, Serialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
@@ -21,6 +67,19 @@ pub enum SessionState {
     },
 }
 
+/// Live progress of an in-flight walkthrough generation, driven by events
+/// from the generation task.
+#[derive(Debug, Clone, Default)]
+pub struct GenerationProgress {
+    pub model: Option<String>,
+    pub started_at: Option<Instant>,
+    pub output_tokens: u32,
+    pub steps_received: usize,
+    /// The wait announced after the API rate limited the request, and when
+    /// it ends
+    pub retry: Option<(RetryNotice, Instant)>,
+}
+
 pub struct Session {
     pub state: SessionState,
     pub walkthrough: Walkthrough,
@@ -28,19 +87,100 @@ pub struct Session {
     pub reviewed_steps: Vec<bool>,
     pub walkthrough_complete: bool,
     pub generation_in_progress: bool,
+    pub generation_progress: GenerationProgress,
     pub review_mode: ReviewMode,
-    pub chat_pending: Option<usize>,
-    pub chat_request: Option<(usize, Walkthrough, Vec<Message>)>,
+    pub step_organization: StepOrganization,
+    /// Generate one section per commit of the range (`--by-commit`)
+    pub by_commit: bool,
+    pub step_order: StepOrder,
+    /// Steps hidden from the step list and skipped by step navigation
+    pub priority_filter: PriorityFilter,
+    /// Position of each step in the model's original order, parallel to
+    /// `walkthrough.steps`, so narrative order can be restored after sorting.
+    narrative_rank: Vec<usize>,
+    pub chat_pending: Option<ChatThread>,
+    /// The conversation asked in, the context built for it, its messages to
+    /// send, and the prompt summarizing those too old to send
+    pub chat_request: Option<(ChatThread, String, Vec<Message>, Option<HistorySummaryPrompt>)>,
+    /// Summarize messages too old to send instead of leaving them out
+    /// (`chat.summarize_history`)
+    pub summarize_chat_history: bool,
+    /// The summary of each conversation's oldest messages, and how many it
+    /// covers
+    chat_summaries: HashMap<ChatThread, (usize, String)>,
+    /// Messages left out of the most recent chat request to fit the context
+    /// window
+    pub chat_history_omitted: usize,
+    /// Questions go to the conversation about the whole change instead of
+    /// the current step's
+    pub whole_change_chat: bool,
+    /// Send as many steps' hunks as fit with questions about the whole
+    /// change (`chat.whole_change_hunks`)
+    pub whole_change_hunks: bool,
+    /// Diff lines quoted from a step (its index and the quote), added to the
+    /// context of the next question about that step
+    pub chat_selection: Option<(usize, String)>,
+    /// How much surrounding context chat questions include
+    pub chat_context: ChatContext,
+    /// Lines around each hunk read for the `Surrounding` chat context
+    pub chat_context_lines: usize,
+    /// Estimated tokens of context in the most recent chat request
+    pub chat_context_tokens: Option<usize>,
+    /// Ask for follow-up questions after each answer (`chat.follow_ups`)
+    pub chat_follow_ups: bool,
+    /// Questions suggested after the latest answer, and the step they're for
+    pub follow_ups: Option<(usize, Vec<String>)>,
+    pub follow_up_request: Option<(usize, String)>,
+    /// Steps whose answer finished while another step was shown
+    pub unread_chats: HashSet<usize>,
     pub rechunk_pending: bool,
     pub rechunk_request: Option<(usize, Step, Option<String>)>,
+    /// Step whose summary is being rewritten
+    pub summary_pending: Option<usize>,
+    pub summary_request: Option<(usize, String, Vec<Message>)>,
     pub retry_requested: bool,
     pub generation_requested: bool,
+    /// The in-flight generation should be aborted
+    pub generation_cancel_requested: bool,
+    /// Steering added to the generation prompt by a regeneration
+    pub generation_instructions: Option<String>,
+    /// The walkthrough being regenerated and its reviewed steps. Conversations
+    /// carry over to new steps covering the same hunks.
+    previous_walkthrough: Option<(Walkthrough, Vec<bool>)>,
+    /// Conversations from an earlier run on the same diff, restored onto the
+    /// steps covering the same hunks as they arrive
+    pub saved_chats: Option<SavedChats>,
+    /// The in-flight chat request should be aborted
+    pub chat_cancel_requested: bool,
     pub diff_input: Option<String>,
+    /// `diff_input` is an interdiff against a previous revision
+    pub interdiff: bool,
     pub commits: Vec<CommitInfo>,
+    pub pull_request: Option<PullRequestInfo>,
+    pub forge: Option<ForgeLink>,
     pub diff_filter: FileFilter,
+    /// Parts of a split review; empty when the diff is reviewed as a whole
+    pub parts: Vec<ReviewPart>,
+    pub current_part: usize,
+    /// The filter before splitting, which each part narrows to its own files
+    parts_base_filter: FileFilter,
+    /// Files with their changed-line counts and the part size limit, for the
+    /// main loop to ask the model for a partitioning
+    pub partition_request: Option<(Vec<(String, usize)>, usize)>,
     pub api_key_input: String,
     pub api_key_source: ApiKeySource,
+    pub provider: Provider,
+    pub api_settings: ApiSettings,
+    /// Where API clients announce waits before retrying a rate-limited request
+    pub retry_notices: Option<mpsc::UnboundedSender<RetryNotice>>,
+    /// Tokens used by every request, chat included
     pub token_usage: TokenUsage,
+    /// Tokens used by chat answers alone
+    pub chat_usage: TokenUsage,
+    pub record_requested: bool,
+    /// Step notes should be posted as a review on the forge pull request
+    pub pr_review_requested: bool,
+    pub status_message: Option<String>,
 }
 
 impl Session {
@@ -53,43 +193,144 @@ impl Session {
             reviewed_steps: vec![false; step_count],
             walkthrough_complete: false,
             generation_in_progress: false,
+            generation_progress: GenerationProgress::default(),
             review_mode: mode,
+            step_organization: StepOrganization::default(),
+            by_commit: false,
+            step_order: StepOrder::default(),
+            priority_filter: PriorityFilter::default(),
+            narrative_rank: (0..step_count).collect(),
             chat_pending: None,
+            chat_selection: None,
             chat_request: None,
+            chat_context: ChatContext::default(),
+            chat_context_lines: ChatSettings::default().context_lines,
+            chat_context_tokens: None,
+            chat_follow_ups: true,
+            whole_change_chat: false,
+            whole_change_hunks: true,
+            summarize_chat_history: true,
+            chat_summaries: HashMap::new(),
+            chat_history_omitted: 0,
+            follow_ups: None,
+            unread_chats: HashSet::new(),
+            follow_up_request: None,
             rechunk_pending: false,
             rechunk_request: None,
+            summary_pending: None,
+            summary_request: None,
             retry_requested: false,
             generation_requested: false,
+            generation_cancel_requested: false,
+            generation_instructions: None,
+            previous_walkthrough: None,
+            saved_chats: None,
+            chat_cancel_requested: false,
             diff_input: None,
+            interdiff: false,
             commits: vec![],
+            pull_request: None,
+            forge: None,
             diff_filter: FileFilter::default(),
+            parts: vec![],
+            current_part: 0,
+            parts_base_filter: FileFilter::default(),
+            partition_request: None,
             api_key_input: String::new(),
             api_key_source: ApiKeySource::Missing,
+            provider: Provider::default(),
+            api_settings: ApiSettings::default(),
+            retry_notices: None,
             token_usage: TokenUsage::default(),
+            chat_usage: TokenUsage::default(),
+            record_requested: false,
+            pr_review_requested: false,
+            status_message: None,
         }
     }
 
     pub fn setup(api_key_input: String, api_key_source: ApiKeySource, mode: ReviewMode) -> Self {
         Self {
             state: SessionState::Setup,
-            walkthrough: Walkthrough { steps: vec![] },
+            walkthrough: Walkthrough::default(),
             current_step: 0,
             reviewed_steps: vec![],
             walkthrough_complete: false,
             generation_in_progress: false,
+            generation_progress: GenerationProgress::default(),
             review_mode: mode,
+            step_organization: StepOrganization::default(),
+            by_commit: false,
+            step_order: StepOrder::default(),
+            priority_filter: PriorityFilter::default(),
+            narrative_rank: vec![],
             chat_pending: None,
+            chat_selection: None,
             chat_request: None,
+            chat_context: ChatContext::default(),
+            chat_context_lines: ChatSettings::default().context_lines,
+            chat_context_tokens: None,
+            chat_follow_ups: true,
+            whole_change_chat: false,
+            whole_change_hunks: true,
+            summarize_chat_history: true,
+            chat_summaries: HashMap::new(),
+            chat_history_omitted: 0,
+            follow_ups: None,
+            unread_chats: HashSet::new(),
+            follow_up_request: None,
             rechunk_pending: false,
             rechunk_request: None,
+            summary_pending: None,
+            summary_request: None,
             retry_requested: false,
             generation_requested: false,
+            generation_cancel_requested: false,
+            generation_instructions: None,
+            previous_walkthrough: None,
+            saved_chats: None,
+            chat_cancel_requested: false,
             diff_input: None,
+            interdiff: false,
             commits: vec![],
+            pull_request: None,
+            forge: None,
             diff_filter: FileFilter::default(),
+            parts: vec![],
+            current_part: 0,
+            parts_base_filter: FileFilter::default(),
+            partition_request: None,
             api_key_input,
             api_key_source,
+            provider: Provider::default(),
+            api_settings: ApiSettings::default(),
+            retry_notices: None,
             token_usage: TokenUsage::default(),
+            chat_usage: TokenUsage::default(),
+            record_requested: false,
+            pr_review_requested: false,
+            status_message: None,
+        }
+    }
+
+    /// What API clients for this session are created with.
+    pub fn client_config(&self) -> ClientConfig {
+        let mut settings = self.api_settings.clone();
+        // The working tree says nothing about a diff fetched from GitHub
+        settings.chat_tools &= self.forge.is_none();
+        if !settings.prompts.is_empty() {
+            let repo = self.forge.as_ref().map(ForgeLink::repository);
+            settings
+                .prompts
+                .set_default("repo", repo.as_deref().unwrap_or(crate::settings::project_name()));
+            let title = self.pull_request.as_ref().map_or("", |pr| pr.title.as_str());
+            settings.prompts.set_default("pr_title", title);
+        }
+        ClientConfig {
+            provider: self.provider,
+            api_key: self.api_key_input.clone(),
+            settings,
+            retry_notices: self.retry_notices.clone(),
         }
     }
 
@@ -97,16 +338,159 @@ impl Session {
         self.token_usage += usage;
     }
 
+    pub fn add_chat_usage(&mut self, usage: TokenUsage) {
+        self.chat_usage += usage;
+        self.add_usage(usage);
+    }
+
+    /// Prices for the cost estimate: the configured ones, or the model's
+    /// published prices.
+    pub fn pricing(&self) -> Option<(f64, f64)> {
+        if let Some(pricing) = self.api_settings.pricing {
+            return Some((pricing.input, pricing.output));
+        }
+        let model = self
+            .generation_progress
+            .model
+            .as_deref()
+            .or(self.api_settings.model.as_deref())?;
+        model_pricing(model)
+    }
+
+    /// Estimated cost of the session so far, when the model's prices are known.
+    pub fn cost_usd(&self) -> Option<f64> {
+        self.pricing().map(|pricing| self.token_usage.cost_usd(pricing))
+    }
+
     // --- State transitions ---
 
+    /// Whether there's a key to call the API with, or the provider needs none.
+    pub fn has_api_key(&self) -> bool {
+        self.api_key_source == ApiKeySource::NotRequired || !self.api_key_input.trim().is_empty()
+    }
+
     pub fn confirm_setup(&mut self) {
-        if self.api_key_input.trim().is_empty() {
+        if !self.has_api_key() {
             return;
         }
-        self.walkthrough = Walkthrough { steps: vec![] };
+        self.walkthrough = Walkthrough::default();
         self.reviewed_steps = vec![];
+        self.reset_step_order();
+        self.unread_chats.clear();
+        self.chat_summaries.clear();
         self.current_step = 0;
         self.generation_in_progress = true;
+        self.generation_progress = GenerationProgress::default();
+        self.state = SessionState::Ready;
+        self.generation_requested = true;
+    }
+
+    pub fn generation_options(&self) -> GenerationOptions {
+        GenerationOptions {
+            organization: self.step_organization,
+            interdiff: self.interdiff,
+            pull_request: self.pull_request.clone(),
+            instructions: self.generation_instructions.clone(),
+        }
+    }
+
+    /// Generates the walkthrough again with the reviewer's `instructions`
+    /// added to the prompt. Steps whose hunks come back unchanged keep their
+    /// conversation and notes, and unchanged hunks stay reviewed.
+    pub fn request_regeneration(&mut self, instructions: Option<String>) -> bool {
+        if self.generation_in_progress || self.diff_input.is_none() {
+            return false;
+        }
+        self.generation_instructions = instructions.filter(|i| !i.trim().is_empty());
+        let walkthrough = std::mem::take(&mut self.walkthrough);
+        self.previous_walkthrough = Some((walkthrough, std::mem::take(&mut self.reviewed_steps)));
+        self.reset_step_order();
+        self.unread_chats.clear();
+        self.chat_summaries.clear();
+        self.current_step = 0;
+        self.walkthrough_complete = false;
+        self.generation_in_progress = true;
+        self.generation_progress = GenerationProgress::default();
+        self.state = SessionState::Ready;
+        self.generation_requested = true;
+        true
+    }
+
+    /// Picks up a review saved by crash recovery at the step it was on.
+    pub fn restore(&mut self, snapshot: Snapshot) {
+        self.diff_input = snapshot.diff_input;
+        self.interdiff = snapshot.interdiff;
+        self.commits = snapshot.commits;
+        self.pull_request = snapshot.pull_request;
+        self.forge = snapshot.forge;
+        self.review_mode = snapshot.review_mode;
+        self.step_organization = snapshot.step_organization;
+        self.generation_progress = GenerationProgress {
+            model: snapshot.model,
+            ..GenerationProgress::default()
+        };
+        self.walkthrough = snapshot.walkthrough;
+        self.reviewed_steps = snapshot.reviewed_steps;
+        self.reviewed_steps.resize(self.walkthrough.step_count(), false);
+        self.reset_step_order();
+        self.unread_chats.clear();
+        self.chat_summaries.clear();
+        self.current_step = snapshot.current_step.min(self.walkthrough.step_count().saturating_sub(1));
+        self.parts = snapshot.parts;
+        self.current_part = snapshot.current_part;
+        self.token_usage = snapshot.token_usage;
+        self.chat_usage = snapshot.chat_usage;
+        self.walkthrough_complete = false;
+        self.generation_in_progress = false;
+        self.generation_requested = false;
+        self.state = SessionState::Ready;
+
+        // The original filter isn't saved. Files of the diff that aren't in
+        // the review were filtered out, so exclude them by name.
+        let reviewed_files: HashSet<String> = if self.parts.is_empty() {
+            self.walkthrough
+                .steps
+                .iter()
+                .flat_map(|step| step.hunks.iter().map(|h| h.file_path.clone()))
+                .collect()
+        } else {
+            self.parts.iter().flat_map(|part| part.files.clone()).collect()
+        };
+        let diff_files = self
+            .diff_input
+            .as_deref()
+            .and_then(|diff| ParsedDiff::parse(diff).ok())
+            .map(|parsed| file_sizes(&parsed))
+            .unwrap_or_default();
+        let filtered_out = diff_files
+            .into_iter()
+            .map(|(path, _)| path)
+            .filter(|path| !reviewed_files.contains(path));
+        self.parts_base_filter = FileFilter::default().and_excluding_files(filtered_out);
+        self.diff_filter = if self.parts.is_empty() {
+            self.parts_base_filter.clone()
+        } else {
+            self.part_filter(self.current_part)
+        };
+    }
+
+    /// Replaces the diff under review and requests a fresh walkthrough for it.
+    pub fn request_generation(&mut self, diff_text: String) {
+        self.diff_input = Some(diff_text);
+        self.interdiff = false;
+        self.commits = vec![];
+        self.pull_request = None;
+        self.parts = vec![];
+        self.current_part = 0;
+        self.walkthrough = Walkthrough::default();
+        self.reviewed_steps = vec![];
+        self.reset_step_order();
+        self.unread_chats.clear();
+        self.chat_summaries.clear();
+        self.current_step = 0;
+        self.walkthrough_complete = false;
+        self.generation_in_progress = true;
+        self.generation_progress = GenerationProgress::default();
         self.state = SessionState::Ready;
         self.generation_requested = true;
     }
@@ -120,45 +504,201 @@ impl Session {
     }
 
     pub fn request_retry(&mut self) {
-        self.walkthrough = Walkthrough { steps: vec![] };
+        self.walkthrough = Walkthrough::default();
         self.reviewed_steps = vec![];
+        self.reset_step_order();
+        self.unread_chats.clear();
+        self.chat_summaries.clear();
         self.current_step = 0;
         self.generation_in_progress = true;
+        self.generation_progress = GenerationProgress::default();
         self.state = SessionState::Ready;
         self.retry_requested = true;
     }
 
-    pub fn receive_step_ready(&mut self, step: Step) {
+    pub fn receive_generation_started(&mut self, model: String) {
+        self.generation_progress.model = Some(model);
+        self.generation_progress.started_at = Some(Instant::now());
+    }
+
+    pub fn receive_generation_progress(&mut self, output_tokens: u32) {
+        self.generation_progress.output_tokens = output_tokens;
+        self.generation_progress.retry = None;
+    }
+
+    /// Records a wait before an API request is retried. Returns whether it
+    /// belongs to the generation in progress, which shows it in its progress.
+    pub fn receive_retry_notice(&mut self, notice: RetryNotice) -> bool {
+        if !self.generation_in_progress || self.generation_progress.output_tokens > 0 {
+            return false;
+        }
+        let until = Instant::now() + notice.delay;
+        self.generation_progress.retry = Some((notice, until));
+        true
+    }
+
+    pub fn receive_step_ready(&mut self, mut step: Step) {
         if matches!(self.state, SessionState::Ready) && self.generation_in_progress {
+            let reviewed = self.carry_reviewed_hunks(&mut step);
+            if let Some(previous) = self
+                .previous_walkthrough
+                .as_mut()
+                .and_then(|(w, _)| w.steps.iter_mut().find(|s| s.same_hunks(&step)))
+            {
+                // The new summary takes the place of the old one
+                let history = std::mem::take(&mut previous.messages);
+                let old_summary = history.first().is_some_and(|m| m.role == MessageRole::Assistant);
+                step.messages.extend(history.into_iter().skip(usize::from(old_summary)));
+                step.notes = std::mem::take(&mut previous.notes);
+                step.flags = std::mem::take(&mut previous.flags);
+            } else if let Some(history) =
+                self.saved_chats.as_mut().and_then(|saved| saved.take_for(&step))
+            {
+                step.messages.extend(history);
+            }
+            if self.walkthrough.messages.is_empty() {
+                // The conversation about the whole change comes back with the
+                // first step
+                let previous = self.previous_walkthrough.as_mut().map(|(w, _)| &mut w.messages);
+                let saved = self.saved_chats.as_mut().map(|saved| &mut saved.whole_change);
+                if let Some(messages) = previous.into_iter().chain(saved).find(|m| !m.is_empty()) {
+                    self.walkthrough.messages = std::mem::take(messages);
+                }
+            }
+            self.generation_progress.steps_received += 1;
             self.walkthrough.steps.push(step);
-            self.reviewed_steps.push(false);
+            self.reviewed_steps.push(reviewed);
+            self.narrative_rank.push(self.narrative_rank.len());
+        }
+    }
+
+    /// Marks the hunks of a regenerated `step` that were reviewed in the
+    /// walkthrough it replaces and haven't changed since. Returns whether
+    /// that covers the whole step.
+    fn carry_reviewed_hunks(&self, step: &mut Step) -> bool {
+        let Some((previous, reviewed_steps)) = &self.previous_walkthrough else {
+            return false;
+        };
+        for hunk in &mut step.hunks {
+            hunk.reviewed = previous.steps.iter().enumerate().any(|(i, old)| {
+                let step_reviewed = reviewed_steps.get(i).copied().unwrap_or(false);
+                old.hunks
+                    .iter()
+                    .any(|h| (step_reviewed || h.reviewed) && h.same_change(hunk))
+            });
+        }
+        !step.hunks.is_empty() && step.hunks.iter().all(|h| h.reviewed)
+    }
+
+    /// Stops an in-flight generation. Steps already received are kept; with
+    /// none, the session returns to setup so it can be started again.
+    pub fn cancel_generation(&mut self) -> bool {
+        if !self.generation_in_progress {
+            return false;
+        }
+        self.generation_in_progress = false;
+        self.generation_requested = false;
+        self.retry_requested = false;
+        self.generation_cancel_requested = true;
+        if self.walkthrough.steps.is_empty() {
+            // A cancelled regeneration goes back to the walkthrough it replaced
+            match self.previous_walkthrough.take() {
+                Some((previous, reviewed_steps)) => {
+                    self.walkthrough = previous;
+                    self.reviewed_steps = reviewed_steps;
+                    self.reset_step_order();
+                    self.unread_chats.clear();
+                    self.chat_summaries.clear();
+                    self.narrative_rank = (0..self.walkthrough.step_count()).collect();
+                }
+                None => self.state = SessionState::Setup,
+            }
+        }
+        self.previous_walkthrough = None;
+        true
+    }
+
+    /// Generates the walkthrough again for a newer version of the diff, with
+    /// the last regeneration's instructions.
+    pub fn regenerate_with_diff(&mut self, diff_text: String, commits: Vec<CommitInfo>) -> bool {
+        if self.generation_in_progress {
+            return false;
         }
+        self.diff_input = Some(diff_text);
+        self.commits = commits;
+        self.request_regeneration(self.generation_instructions.clone())
+    }
+
+    /// Whether the walkthrough being generated replaces an earlier one.
+    pub fn is_regenerating(&self) -> bool {
+        self.previous_walkthrough.is_some()
     }
 
     pub fn generation_finished(&mut self) {
         self.generation_in_progress = false;
+        self.previous_walkthrough = None;
     }
 
     // --- Navigation ---
 
     /// Returns true if the step actually changed.
     pub fn next_step(&mut self) -> bool {
-        if self.current_step < self.walkthrough.step_count().saturating_sub(1) {
-            self.current_step += 1;
-            true
-        } else {
-            false
+        match self.next_shown_step(true) {
+            Some(index) => {
+                self.current_step = index;
+                true
+            }
+            None => false,
         }
     }
 
     /// Returns true if the step actually changed.
     pub fn prev_step(&mut self) -> bool {
-        if self.current_step > 0 {
-            self.current_step -= 1;
-            self.walkthrough_complete = false;
-            true
-        } else {
-            false
+        match self.next_shown_step(false) {
+            Some(index) => {
+                self.current_step = index;
+                self.walkthrough_complete = false;
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn next_shown_step(&self, forward: bool) -> Option<usize> {
+        self.walkthrough
+            .next_shown_step(self.current_step, self.priority_filter, forward)
+    }
+
+    /// Whether the step list shows step `index` under the priority filter.
+    /// The current step is always shown.
+    pub fn is_step_shown(&self, index: usize) -> bool {
+        index == self.current_step
+            || self
+                .walkthrough
+                .steps
+                .get(index)
+                .is_some_and(|step| self.priority_filter.shows(step.priority))
+    }
+
+    /// Switches to the next priority filter, moving off the current step
+    /// when the filter hides it. Returns true if the step changed.
+    pub fn cycle_priority_filter(&mut self) -> bool {
+        self.priority_filter = self.priority_filter.next();
+        let hidden = self
+            .walkthrough
+            .steps
+            .get(self.current_step)
+            .is_some_and(|step| !self.priority_filter.shows(step.priority));
+        if !hidden {
+            return false;
+        }
+        match self.next_shown_step(true).or(self.next_shown_step(false)) {
+            Some(index) => {
+                self.current_step = index;
+                self.walkthrough_complete = false;
+                true
+            }
+            None => false,
         }
     }
 
@@ -181,8 +721,8 @@ impl Session {
         self.set_step_reviewed(self.current_step, true);
         self.sync_parent_completion(self.current_step);
 
-        if self.current_step < self.walkthrough.step_count().saturating_sub(1) {
-            self.current_step += 1;
+        if let Some(index) = self.next_shown_step(true) {
+            self.current_step = index;
             true
         } else {
             self.walkthrough_complete = true;
@@ -200,6 +740,39 @@ impl Session {
         self.walkthrough_complete = false;
     }
 
+    /// Toggles hunk `hunk` of the current step. Reviewing its last hunk marks
+    /// the step reviewed; unmarking a hunk unmarks the step.
+    pub fn toggle_hunk_reviewed(&mut self, hunk: usize) {
+        let index = self.current_step;
+        let Some(step) = self.walkthrough.steps.get_mut(index) else {
+            return;
+        };
+        let Some(target) = step.hunks.get_mut(hunk) else {
+            return;
+        };
+        target.reviewed = !target.reviewed;
+        let (reviewed, all_reviewed) = (target.reviewed, step.hunks.iter().all(|h| h.reviewed));
+        if reviewed && all_reviewed {
+            self.set_step_reviewed(index, true);
+        } else if !reviewed {
+            self.set_step_reviewed(index, false);
+            self.walkthrough_complete = false;
+        }
+        self.sync_parent_completion(index);
+    }
+
+    /// Folds or unfolds hunks `hunks` of the current step.
+    pub fn fold_hunks(&mut self, hunks: &[usize], folded: bool) {
+        let Some(step) = self.walkthrough.steps.get_mut(self.current_step) else {
+            return;
+        };
+        for &hunk in hunks {
+            if let Some(hunk) = step.hunks.get_mut(hunk) {
+                hunk.folded = folded;
+            }
+        }
+    }
+
     // --- Queries ---
 
     pub fn is_walkthrough_complete(&self) -> bool {
@@ -228,16 +801,43 @@ impl Session {
             .sum()
     }
 
+    /// Lines of reviewed steps, plus the reviewed hunks of the other steps.
     pub fn reviewed_diff_lines(&self) -> usize {
         self.walkthrough
             .steps
             .iter()
             .enumerate()
-            .filter(|(i, _)| self.is_step_reviewed(*i))
-            .map(|(_, step)| step.diff_line_count())
+            .map(|(i, step)| {
+                if self.is_step_reviewed(i) {
+                    step.diff_line_count()
+                } else {
+                    step.hunks
+                        .iter()
+                        .zip(step.hunk_line_counts())
+                        .filter(|(hunk, _)| hunk.reviewed)
+                        .map(|(_, lines)| lines)
+                        .sum()
+                }
+            })
             .sum()
     }
 
+    /// Reviewed and total hunks, counting every hunk of a reviewed step.
+    pub fn hunk_progress(&self) -> (usize, usize) {
+        self.walkthrough
+            .steps
+            .iter()
+            .enumerate()
+            .fold((0, 0), |(reviewed, total), (i, step)| {
+                let done = if self.is_step_reviewed(i) {
+                    step.hunks.len()
+                } else {
+                    step.reviewed_hunk_count()
+                };
+                (reviewed + done, total + step.hunks.len())
+            })
+    }
+
     // --- Chat ---
 
     /// Sends a user message (content provided directly). Used by headless mode.
@@ -249,53 +849,383 @@ impl Session {
             return;
         }
 
-        let step_index = self.current_step;
-        if let Some(step) = self.walkthrough.steps.get_mut(step_index) {
-            step.messages.push(Message::user(content));
-            let messages_clone = step.messages.clone();
-            let walkthrough_clone = self.walkthrough.clone();
-            self.chat_pending = Some(step_index);
-            self.chat_request = Some((step_index, walkthrough_clone, messages_clone));
+        let thread = self.chat_thread();
+        let context = match thread {
+            ChatThread::Step(step_index) => {
+                let Some(mut context) = chat_context(
+                    &self.walkthrough,
+                    step_index,
+                    self.chat_context,
+                    self.chat_context_lines,
+                ) else {
+                    return;
+                };
+                if let Some((_, quote)) =
+                    self.chat_selection.take().filter(|(i, _)| *i == step_index)
+                {
+                    context.push_str(&format!(
+                        "\n\n## Selected Lines\n\
+                         The question is about these lines of the current step:\n\n{}",
+                        quote
+                    ));
+                }
+                context
+            }
+            ChatThread::Walkthrough => {
+                whole_change_context(&self.walkthrough, self.whole_change_hunks)
+            }
+        };
+        let Some(messages) = self.thread_messages_mut(thread) else {
+            return;
+        };
+        messages.push(Message::user(content));
+        let messages = messages.clone();
+        let (_, omitted) = fit_chat_history(&messages);
+        let mut context = context;
+        let mut summarize = None;
+        if omitted > 0 && self.summarize_chat_history {
+            // Reuse the summary of the dropped messages, or ask for a new one
+            let previous = self.chat_summaries.get(&thread);
+            summarize = history_summary_prompt(&messages, previous.map(|(n, s)| (*n, s.as_str())));
+            if summarize.is_none()
+                && let Some((_, summary)) = previous
+            {
+                context = context_with_summary(&context, summary);
+            }
+            self.status_message = Some(format!(
+                "Long conversation: the {} oldest messages are sent as a summary",
+                omitted
+            ));
+        } else if omitted > 0 {
+            self.status_message = Some(format!(
+                "Long conversation: the {} oldest messages are no longer sent to the model",
+                omitted
+            ));
+        }
+        self.chat_history_omitted = omitted;
+        self.chat_context_tokens = Some(estimate_tokens(&context));
+        self.chat_pending = Some(thread);
+        self.chat_request = Some((thread, context, messages, summarize));
+        self.follow_ups = None;
+    }
+
+    /// The conversation questions currently go to.
+    pub fn chat_thread(&self) -> ChatThread {
+        if self.whole_change_chat {
+            ChatThread::Walkthrough
+        } else {
+            ChatThread::Step(self.current_step)
+        }
+    }
+
+    /// Switches questions between the current step and the whole change.
+    pub fn toggle_whole_change_chat(&mut self) {
+        self.whole_change_chat = !self.whole_change_chat;
+    }
+
+    /// The messages of the conversation questions currently go to.
+    pub fn chat_messages(&self) -> &[Message] {
+        match self.chat_thread() {
+            ChatThread::Step(index) => self
+                .walkthrough
+                .steps
+                .get(index)
+                .map_or(&[], |step| &step.messages),
+            ChatThread::Walkthrough => &self.walkthrough.messages,
+        }
+    }
+
+    fn thread_messages_mut(&mut self, thread: ChatThread) -> Option<&mut Vec<Message>> {
+        match thread {
+            ChatThread::Step(index) => self
+                .walkthrough
+                .steps
+                .get_mut(index)
+                .map(|step| &mut step.messages),
+            ChatThread::Walkthrough => Some(&mut self.walkthrough.messages),
+        }
+    }
+
+    /// Takes the current step's last question back out of the conversation,
+    /// along with the answer or error that followed it. None while a reply
+    /// streams or before anything was asked.
+    pub fn take_last_question(&mut self) -> Option<String> {
+        if self.chat_pending.is_some() {
+            return None;
         }
+        let messages = self.thread_messages_mut(self.chat_thread())?;
+        let last = messages.iter().rposition(|m| m.role == MessageRole::User)?;
+        let question = messages.drain(last..).next().map(|m| m.content);
+        self.follow_ups = None;
+        question
+    }
+
+    /// Asks the current step's last question again in place of its answer.
+    /// Returns false if there is no question to retry.
+    pub fn retry_last_question(&mut self) -> bool {
+        let Some(question) = self.take_last_question() else {
+            return false;
+        };
+        self.send_message(question);
+        true
     }
 
-    pub fn receive_chat_chunk(&mut self, step_index: usize, chunk: String) {
-        if self.chat_pending != Some(step_index) {
+    /// Keeps the summary of a conversation's `covered` oldest messages for
+    /// its next questions.
+    pub fn receive_chat_summary(&mut self, thread: ChatThread, covered: usize, summary: String) {
+        self.chat_summaries.insert(thread, (covered, summary));
+    }
+
+    pub fn receive_chat_chunk(&mut self, thread: ChatThread, chunk: String) {
+        if self.chat_pending != Some(thread) {
             return;
         }
-        if let Some(step) = self.walkthrough.steps.get_mut(step_index) {
-            if let Some(last_msg) = step.messages.last_mut() {
+        if let Some(messages) = self.thread_messages_mut(thread) {
+            if let Some(last_msg) = messages.last_mut() {
                 if last_msg.role == crate::model::MessageRole::Assistant {
                     last_msg.content.push_str(&chunk);
                 } else {
-                    step.messages.push(Message::assistant(chunk));
+                    messages.push(Message::assistant(chunk));
                 }
             } else {
-                step.messages.push(Message::assistant(chunk));
+                messages.push(Message::assistant(chunk));
             }
         }
     }
 
-    pub fn receive_chat_complete(&mut self, step_index: usize) {
-        if self.chat_pending == Some(step_index) {
-            self.chat_pending = None;
+    /// Stops the reply being streamed, keeping any partial answer. A question
+    /// with no answer yet is taken back out and returned, to edit and resend.
+    pub fn cancel_chat(&mut self) -> Option<String> {
+        let thread = self.chat_pending.take()?;
+        self.chat_request = None;
+        self.chat_cancel_requested = true;
+        let messages = self.thread_messages_mut(thread)?;
+        let last = messages.last_mut()?;
+        match last.role {
+            MessageRole::User => messages.pop().map(|m| m.content),
+            MessageRole::Assistant => {
+                last.content.push_str("\n\n(cancelled)");
+                None
+            }
+        }
+    }
+
+    pub fn receive_chat_complete(&mut self, thread: ChatThread) {
+        if self.chat_pending != Some(thread) {
+            return;
+        }
+        self.chat_pending = None;
+        // Follow-ups and unread marks are for steps' conversations
+        let Some(step_index) = thread.step() else {
+            return;
+        };
+        if step_index != self.current_step {
+            self.unread_chats.insert(step_index);
+        }
+        if !self.chat_follow_ups {
+            return;
+        }
+        let context = chat_context(
+            &self.walkthrough,
+            step_index,
+            self.chat_context,
+            self.chat_context_lines,
+        );
+        if let (Some(context), Some(step)) = (context, self.walkthrough.steps.get(step_index)) {
+            self.follow_up_request = Some((step_index, follow_up_prompt(&context, &step.messages)));
+        }
+    }
+
+    /// Shows the questions suggested for a step, unless another question was
+    /// asked there in the meantime.
+    pub fn receive_follow_ups(&mut self, step_index: usize, mut questions: Vec<String>) {
+        if self.chat_pending.is_some() {
+            return;
         }
+        questions.retain(|q| !q.trim().is_empty());
+        questions.truncate(MAX_FOLLOW_UPS);
+        if !questions.is_empty() {
+            self.follow_ups = Some((step_index, questions));
+        }
+    }
+
+    /// The suggested follow-up questions for the current step.
+    pub fn current_follow_ups(&self) -> &[String] {
+        match &self.follow_ups {
+            Some((step_index, questions))
+                if *step_index == self.current_step && !self.whole_change_chat =>
+            {
+                questions
+            }
+            _ => &[],
+        }
+    }
+
+    /// Asks the current step's suggested question `n` (0-based). Returns
+    /// false if there is no such question.
+    pub fn send_follow_up(&mut self, n: usize) -> bool {
+        let Some(question) = self.current_follow_ups().get(n).cloned() else {
+            return false;
+        };
+        self.send_message(question);
+        true
     }
 
-    pub fn receive_chat_error(&mut self, step_index: usize, error: String) {
-        if self.chat_pending == Some(step_index) {
+    /// Whether step `index` has an answer that finished while another step
+    /// was shown.
+    pub fn has_unread_chat(&self, index: usize) -> bool {
+        self.unread_chats.contains(&index)
+    }
+
+    /// Marks the current step's answer as seen.
+    pub fn mark_chat_read(&mut self) {
+        self.unread_chats.remove(&self.current_step);
+    }
+
+    pub fn receive_chat_error(&mut self, thread: ChatThread, error: String) {
+        if self.chat_pending == Some(thread) {
             self.chat_pending = None;
-            if let Some(step) = self.walkthrough.steps.get_mut(step_index) {
-                step.messages
-                    .push(Message::assistant(format!("Error: {}", error)));
+            if let Some(step_index) = thread.step()
+                && step_index != self.current_step
+            {
+                self.unread_chats.insert(step_index);
+            }
+            if let Some(messages) = self.thread_messages_mut(thread) {
+                messages.push(Message::assistant(format!("Error: {}", error)));
             }
         }
     }
 
+    // --- Review record ---
+
+    /// Requests that the completed review be recorded as a git note on HEAD.
+    /// Returns whether it was requested.
+    pub fn request_review_record(&mut self) -> bool {
+        if self.is_walkthrough_complete() {
+            self.record_requested = true;
+        }
+        self.record_requested
+    }
+
+    // --- Pull request review ---
+
+    /// Requests that step notes be posted as review comments on the pull
+    /// request this diff came from.
+    pub fn request_pr_review(&mut self) -> Result<(), PrReviewBlocked> {
+        if self.forge.as_ref().and_then(|f| f.pull_request()).is_none() {
+            return Err(PrReviewBlocked::NoPullRequest);
+        }
+        if !PullRequestReview::has_notes(&self.walkthrough) {
+            return Err(PrReviewBlocked::NoNotes);
+        }
+        self.pr_review_requested = true;
+        Ok(())
+    }
+
+    /// The review to post: a verdict summary plus each step's note.
+    pub fn pr_review(&self) -> PullRequestReview {
+        let summary = review_record::trailer(&review_record::verdict(
+            &self.walkthrough,
+            &self.reviewed_steps,
+        ));
+        PullRequestReview::from_notes(&self.walkthrough, &summary)
+    }
+
+    // --- Parts ---
+
+    /// Starts a split review with the first part.
+    pub fn start_parts(&mut self, parts: Vec<ReviewPart>) {
+        if parts.is_empty() {
+            self.confirm_setup();
+            return;
+        }
+        self.parts_base_filter = self.diff_filter.clone();
+        self.parts = parts;
+        self.current_part = 0;
+        self.diff_filter = self.part_filter(0);
+        self.confirm_setup();
+    }
+
+    /// The base filter narrowed to part `index`'s files.
+    fn part_filter(&self, index: usize) -> FileFilter {
+        let others = self
+            .parts
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| *i != index)
+            .flat_map(|(_, part)| part.files.iter().cloned());
+        self.parts_base_filter.clone().and_excluding_files(others)
+    }
+
+    /// Switches to another part, stashing the current one's walkthrough and
+    /// generating the target's on first visit. Returns false while a request
+    /// tied to the current part is in flight.
+    pub fn switch_part(&mut self, index: usize) -> bool {
+        if index >= self.parts.len()
+            || self.generation_in_progress
+            || self.chat_pending.is_some()
+            || self.rechunk_pending
+            || self.summary_pending.is_some()
+        {
+            return false;
+        }
+        if index == self.current_part {
+            return true;
+        }
+
+        // Stash in narrative order, since the order mapping isn't stashed
+        if self.step_order != StepOrder::Narrative {
+            self.toggle_step_order();
+        }
+        let current = &mut self.parts[self.current_part];
+        current.walkthrough = std::mem::take(&mut self.walkthrough);
+        current.reviewed_steps = std::mem::take(&mut self.reviewed_steps);
+
+        let target = &mut self.parts[index];
+        self.walkthrough = std::mem::take(&mut target.walkthrough);
+        self.reviewed_steps = std::mem::take(&mut target.reviewed_steps);
+        self.narrative_rank = (0..self.walkthrough.step_count()).collect();
+        self.unread_chats.clear();
+        self.chat_summaries.clear();
+        self.current_part = index;
+        self.current_step = 0;
+        self.walkthrough_complete = false;
+        self.diff_filter = self.part_filter(index);
+
+        if self.walkthrough.steps.is_empty() {
+            self.generation_in_progress = true;
+            self.generation_progress = GenerationProgress::default();
+            self.generation_requested = true;
+        }
+        true
+    }
+
+    /// Reviewed and total steps in part `index`; zero steps if not yet generated.
+    pub fn part_progress(&self, index: usize) -> (usize, usize) {
+        let reviewed = if index == self.current_part {
+            &self.reviewed_steps
+        } else {
+            &self.parts[index].reviewed_steps
+        };
+        (reviewed.iter().filter(|&&r| r).count(), reviewed.len())
+    }
+
+    /// Reviewed and total steps across all parts generated so far, or in the
+    /// walkthrough when the review isn't split.
+    pub fn overall_progress(&self) -> (usize, usize) {
+        if self.parts.is_empty() {
+            let reviewed = self.reviewed_steps.iter().filter(|&&r| r).count();
+            return (reviewed, self.reviewed_steps.len());
+        }
+        (0..self.parts.len())
+            .map(|i| self.part_progress(i))
+            .fold((0, 0), |(r, t), (pr, pt)| (r + pr, t + pt))
+    }
+
     // --- Rechunk ---
 
     pub fn request_rechunk(&mut self) {
-        if self.rechunk_pending || self.chat_pending.is_some() {
+        if self.rechunk_pending || self.chat_pending.is_some() || self.summary_pending.is_some() {
             return;
         }
         if let Some(step) = self.current_step_data() {
@@ -317,6 +1247,7 @@ impl Session {
         }
 
         let parent_depth = self.walkthrough.steps[step_index].depth;
+        let parent_rank = self.narrative_rank[step_index];
 
         self.walkthrough.steps[step_index].hunks.clear();
 
@@ -325,13 +1256,226 @@ impl Session {
             sub_step.depth = parent_depth + 1;
             self.walkthrough.steps.insert(insert_pos + i, sub_step);
             self.reviewed_steps.insert(insert_pos + i, false);
+            self.narrative_rank.insert(insert_pos + i, parent_rank);
         }
 
         self.current_step = insert_pos;
         self.renumber_steps();
     }
 
+    // --- Summary ---
+
+    /// Asks the model for a new summary of the current step, following the
+    /// reviewer's `instructions` if any. Returns false while the steps are
+    /// busy.
+    pub fn request_summary(&mut self, instructions: &str) -> bool {
+        if self.steps_busy() {
+            return false;
+        }
+        let step_index = self.current_step;
+        let Some(context) = chat_context(
+            &self.walkthrough,
+            step_index,
+            self.chat_context,
+            self.chat_context_lines,
+        ) else {
+            return false;
+        };
+        let mut prompt = "Rewrite the summary of the current step. Reply with only the new \
+                          summary, in the same Markdown style, with no heading or preamble."
+            .to_string();
+        if !instructions.is_empty() {
+            prompt.push_str(&format!("\n\nInstructions from the reviewer: {}", instructions));
+        }
+        self.summary_pending = Some(step_index);
+        self.summary_request = Some((step_index, context, vec![Message::user(prompt)]));
+        true
+    }
+
+    /// Replaces the step's summary, and the seed message that repeats it at
+    /// the start of its chat.
+    pub fn receive_summary_complete(&mut self, step_index: usize, summary: String) {
+        self.summary_pending = None;
+        let summary = summary.trim().to_string();
+        let Some(step) = self.walkthrough.steps.get_mut(step_index) else {
+            return;
+        };
+        if summary.is_empty() {
+            return;
+        }
+        match step.messages.first_mut() {
+            Some(seed) if seed.role == MessageRole::Assistant => seed.content = summary.clone(),
+            _ => step.messages.insert(0, Message::assistant(&summary)),
+        }
+        step.summary = summary;
+    }
+
+    pub fn receive_summary_error(&mut self) {
+        self.summary_pending = None;
+    }
+
+    // --- Step order ---
+
+    fn reset_step_order(&mut self) {
+        self.step_order = StepOrder::default();
+        self.narrative_rank = vec![];
+    }
+
+    /// Switches between narrative and file order. Reviewed state and the
+    /// current selection follow their steps. Returns false (and leaves the
+    /// order alone) while generation or a request tied to a step index is in
+    /// flight.
+    pub fn toggle_step_order(&mut self) -> bool {
+        if self.steps_busy() {
+            return false;
+        }
+        self.step_order = self.step_order.toggled();
+
+        let mut groups = self.walkthrough.step_groups();
+        match self.step_order {
+            StepOrder::Narrative => groups.sort_by_key(|g| self.narrative_rank[g.start]),
+            StepOrder::FilePath => groups.sort_by(|a, b| {
+                let (a, b) = (
+                    self.walkthrough.group_location(a.clone()),
+                    self.walkthrough.group_location(b.clone()),
+                );
+                // Steps without hunks sort after everything else
+                a.is_none().cmp(&b.is_none()).then(a.cmp(&b))
+            }),
+        }
+        let permutation: Vec<usize> = groups.into_iter().flatten().collect();
+        self.apply_step_permutation(&permutation);
+        true
+    }
+
+    /// Reorders the steps and their per-step state, `permutation` holding old
+    /// indices in their new positions.
+    fn apply_step_permutation(&mut self, permutation: &[usize]) {
+        let mut steps: Vec<Option<Step>> = std::mem::take(&mut self.walkthrough.steps)
+            .into_iter()
+            .map(Some)
+            .collect();
+        self.walkthrough.steps = permutation
+            .iter()
+            .map(|&i| steps[i].take().unwrap())
+            .collect();
+        self.reviewed_steps = permutation
+            .iter()
+            .map(|&i| self.reviewed_steps[i])
+            .collect();
+        self.narrative_rank = permutation
+            .iter()
+            .map(|&i| self.narrative_rank[i])
+            .collect();
+        if let Some(pos) = permutation.iter().position(|&i| i == self.current_step) {
+            self.current_step = pos;
+        }
+        self.renumber_steps();
+    }
+
+    // --- Manual step edits ---
+
+    /// Whether generation or a request tied to a step index is in flight, so
+    /// the steps can't be reordered, merged, or split.
+    pub fn steps_busy(&self) -> bool {
+        self.generation_in_progress
+            || self.chat_pending.is_some()
+            || self.rechunk_pending
+            || self.summary_pending.is_some()
+    }
+
+    /// Moves the current step, with its sub-steps, past the step after it (or
+    /// before it). The resulting order becomes the narrative order.
+    pub fn move_step(&mut self, down: bool) -> bool {
+        if self.steps_busy() {
+            return false;
+        }
+        let Some(order) = self
+            .walkthrough
+            .moved_group_order(self.current_step, down)
+        else {
+            return false;
+        };
+        self.apply_step_permutation(&order);
+        self.step_order = StepOrder::Narrative;
+        self.narrative_rank = vec![0; self.walkthrough.step_count()];
+        for (rank, group) in self.walkthrough.step_groups().into_iter().enumerate() {
+            for i in group {
+                self.narrative_rank[i] = rank;
+            }
+        }
+        true
+    }
+
+    /// Merges the current step into the one before it. The merged step is
+    /// reviewed only if both were.
+    pub fn merge_step_into_previous(&mut self) -> bool {
+        let index = self.current_step;
+        if self.steps_busy() || !self.walkthrough.merge_into_previous(index) {
+            return false;
+        }
+        if index < self.reviewed_steps.len() {
+            let reviewed = self.reviewed_steps.remove(index);
+            self.reviewed_steps[index - 1] &= reviewed;
+        }
+        if index < self.narrative_rank.len() {
+            self.narrative_rank.remove(index);
+        }
+        self.current_step = index - 1;
+        self.renumber_steps();
+        true
+    }
+
+    /// Moves hunk `hunk` of the current step to step `to`. A reviewed step
+    /// that receives an unreviewed hunk is no longer reviewed.
+    pub fn move_hunk(&mut self, hunk: usize, to: usize) -> bool {
+        let from = self.current_step;
+        // Every hunk of a reviewed step counts as reviewed, so it stays so
+        let reviewed = self.is_step_reviewed(from)
+            || self
+                .current_step_data()
+                .and_then(|step| step.hunks.get(hunk))
+                .is_some_and(|h| h.reviewed);
+        if self.steps_busy() || !self.walkthrough.move_hunk(from, hunk, to) {
+            return false;
+        }
+        if let Some(moved) = self.walkthrough.steps[to].hunks.last_mut() {
+            moved.reviewed = reviewed;
+        }
+        if !reviewed {
+            self.set_step_reviewed(to, false);
+        }
+        true
+    }
+
+    /// Moves the current step's hunks at positions `hunks` into a new step
+    /// after it, and selects the new step.
+    pub fn split_off_hunks(&mut self, hunks: &[usize]) -> bool {
+        let index = self.current_step;
+        if self.steps_busy() {
+            return false;
+        }
+        let Some(position) = self.walkthrough.split_off_hunks(index, hunks) else {
+            return false;
+        };
+        if position <= self.reviewed_steps.len() {
+            let reviewed = self.is_step_reviewed(index);
+            self.reviewed_steps.insert(position, reviewed);
+        }
+        if position <= self.narrative_rank.len() {
+            let rank = self.narrative_rank.get(index).copied().unwrap_or_default();
+            self.narrative_rank.insert(position, rank);
+        }
+        self.current_step = position;
+        self.renumber_steps();
+        true
+    }
+
     fn renumber_steps(&mut self) {
+        // Suggestions and unread answers are tied to a step index
+        self.follow_ups = None;
+        self.unread_chats.clear();
+        self.chat_summaries.clear();
         let mut counters: Vec<usize> = vec![0];
         for step in &mut self.walkthrough.steps {
             let d = step.depth as usize;
@@ -1,34 +1,311 @@
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::PathBuf;
+use std::sync::OnceLock;
+
+use crate::api::{ApiError, PromptSettings};
+use crate::hooks::HookKind;
+use crate::i18n::Language;
+use crate::model::StepOrganization;
+
+pub use crate::api::{ApiSettings, ChatContext, Provider};
+pub use crate::cache::CacheSettings;
+
+/// Per-project settings file, looked up at the repository root.
+pub const PROJECT_SETTINGS_FILE: &str = ".docent.json";
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
 #[serde(rename_all = "lowercase")]
 pub enum VimMode {
     #[default]
+    #[serde(alias = "default")]
     Auto,
+    #[serde(alias = "vim")]
     Enabled,
+    /// Readline (emacs) editing
+    #[serde(alias = "emacs")]
     Disabled,
 }
 
+impl VimMode {
+    /// Whether vim keybindings are on, consulting .inputrc for Auto.
+    pub fn resolve(self) -> bool {
+        match self {
+            VimMode::Enabled => true,
+            VimMode::Disabled => false,
+            VimMode::Auto => detect_vim_from_inputrc(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ApiKeySource {
     EnvVar,
     Settings,
     UserEntry,
+    /// Entered during onboarding for this run only; never written to settings
+    Session,
+    /// The provider runs locally and takes no key
+    NotRequired,
     Missing,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct EditorSettings {
-    #[serde(default)]
+    /// Also read as `keymap`, with `default`, `vim`, or `emacs`
+    #[serde(default, alias = "keymap")]
     pub vim_mode: VimMode,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum DiffPalette {
+    /// Green for added lines, red for removed lines
+    #[default]
+    Default,
+    /// Blue for added lines, orange for removed lines
+    Colorblind,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct DiffColorSettings {
+    #[serde(default)]
+    pub palette: DiffPalette,
+    /// Override for added lines: a color name ("blue"), index ("33"), or hex ("#0072b2")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub added: Option<String>,
+    /// Override for removed lines, in the same format as `added`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub removed: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum ThemeName {
+    /// Light when the terminal reports a light background (`COLORFGBG`), dark otherwise
+    #[default]
+    Auto,
+    Dark,
+    Light,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct ThemeSettings {
+    #[serde(default)]
+    pub name: ThemeName,
+    /// Per-role overrides ("accent": "#005f87"), in the same formats as diff colors
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub colors: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct AccessibilitySettings {
+    /// Present each step as a single linear stream instead of multiple panes
+    #[serde(default)]
+    pub linear_mode: bool,
+    /// Replace spinners and other animated indicators with static text
+    #[serde(default)]
+    pub reduced_motion: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct TerminalSettings {
+    /// Start with mouse capture off so the terminal's native text selection works
+    #[serde(default)]
+    pub disable_mouse_capture: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FilterSettings {
+    /// Generated and vendored files skipped before generation unless included explicitly
+    #[serde(default = "default_excludes")]
+    pub default_excludes: Vec<String>,
+}
+
+impl Default for FilterSettings {
+    fn default() -> Self {
+        Self {
+            default_excludes: default_excludes(),
+        }
+    }
+}
+
+fn default_excludes() -> Vec<String> {
+    [
+        "**/package-lock.json",
+        "**/Cargo.lock",
+        "**/*.min.js",
+        "vendor/**",
+        "dist/**",
+    ]
+    .iter()
+    .map(|p| p.to_string())
+    .collect()
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PartSettings {
+    /// Diffs with more changed lines than this are offered a split into parts
+    #[serde(default = "default_max_changed_lines")]
+    pub max_changed_lines: usize,
+}
+
+impl Default for PartSettings {
+    fn default() -> Self {
+        Self {
+            max_changed_lines: default_max_changed_lines(),
+        }
+    }
+}
+
+fn default_max_changed_lines() -> usize {
+    2000
+}
+
+/// Commands to run for each event.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct HookSettings {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub step_entered: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub step_completed: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub review_finished: Vec<String>,
+}
+
+impl HookSettings {
+    pub fn commands(&self, kind: HookKind) -> &[String] {
+        match kind {
+            HookKind::StepEntered => &self.step_entered,
+            HookKind::StepCompleted => &self.step_completed,
+            HookKind::ReviewFinished => &self.review_finished,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.step_entered.is_empty()
+            && self.step_completed.is_empty()
+            && self.review_finished.is_empty()
+    }
+}
+
+/// Keys bound to one action: a single key spec or a list of them.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum KeyList {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl KeyList {
+    pub fn specs(&self) -> &[String] {
+        match self {
+            Self::One(spec) => std::slice::from_ref(spec),
+            Self::Many(specs) => specs,
+        }
+    }
+}
+
+/// Opt-in local usage statistics (see `docent stats`).
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct StatsSettings {
+    #[serde(default)]
+    pub enabled: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChatSettings {
+    #[serde(default)]
+    pub context: ChatContext,
+    /// Lines of the working tree file above and below each hunk with the
+    /// `surrounding` context
+    #[serde(default = "default_context_lines")]
+    pub context_lines: usize,
+    /// Days to keep each review's questions and answers for when it is
+    /// reopened; 0 keeps none
+    #[serde(default = "default_history_days")]
+    pub history_days: u32,
+    /// Suggest questions to ask next after each answer, with a second request
+    #[serde(default = "default_follow_ups")]
+    pub follow_ups: bool,
+    /// Send steps' hunks, as many as fit, with questions about the whole
+    /// change; otherwise only their summaries
+    #[serde(default = "default_whole_change_hunks")]
+    pub whole_change_hunks: bool,
+    /// Once a conversation outgrows the context window, send its oldest
+    /// messages as a summary, with a second request; otherwise leave them out
+    #[serde(default = "default_summarize_history")]
+    pub summarize_history: bool,
+}
+
+impl Default for ChatSettings {
+    fn default() -> Self {
+        Self {
+            context: ChatContext::default(),
+            context_lines: default_context_lines(),
+            history_days: default_history_days(),
+            follow_ups: default_follow_ups(),
+            whole_change_hunks: default_whole_change_hunks(),
+            summarize_history: default_summarize_history(),
+        }
+    }
+}
+
+fn default_context_lines() -> usize {
+    20
+}
+
+fn default_history_days() -> u32 {
+    30
+}
+
+fn default_follow_ups() -> bool {
+    true
+}
+
+fn default_whole_change_hunks() -> bool {
+    true
+}
+
+fn default_summarize_history() -> bool {
+    true
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct Settings {
+    #[serde(default)]
+    pub provider: Provider,
+    /// UI language; detected from the locale when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language: Option<Language>,
     #[serde(default)]
     pub editor: EditorSettings,
+    #[serde(default)]
+    pub accessibility: AccessibilitySettings,
+    #[serde(default)]
+    pub diff_colors: DiffColorSettings,
+    #[serde(default)]
+    pub theme: ThemeSettings,
+    #[serde(default)]
+    pub terminal: TerminalSettings,
+    #[serde(default)]
+    pub filter: FilterSettings,
+    #[serde(default)]
+    pub parts: PartSettings,
+    #[serde(default)]
+    pub chat: ChatSettings,
+    #[serde(default)]
+    pub api: ApiSettings,
+    #[serde(default, skip_serializing_if = "HookSettings::is_empty")]
+    pub hooks: HookSettings,
+    #[serde(default)]
+    pub stats: StatsSettings,
+    #[serde(default)]
+    pub cache: CacheSettings,
+    /// Key overrides by action name, e.g. `"zoom": "Z"` (see `keymap`)
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub keys: BTreeMap<String, KeyList>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub api_key: Option<String>,
 }
@@ -53,6 +330,15 @@ impl Settings {
         fs::write(&path, json).map_err(|e| format!("Failed to write settings: {e}"))
     }
 
+    pub fn language(&self) -> Language {
+        self.language.unwrap_or_else(Language::detect)
+    }
+
+    /// Whether a settings file has been written yet (false on first run).
+    pub fn exists() -> bool {
+        Self::settings_path().is_some_and(|path| path.exists())
+    }
+
     fn settings_path() -> Option<PathBuf> {
         dirs::home_dir().map(|h| h.join(".docent").join("settings.json"))
     }
@@ -60,16 +346,12 @@ impl Settings {
     /// Resolve whether vim mode should be enabled.
     /// If set to Auto, checks .inputrc for "set editing-mode vi".
     pub fn vim_enabled(&self) -> bool {
-        match self.editor.vim_mode {
-            VimMode::Enabled => true,
-            VimMode::Disabled => false,
-            VimMode::Auto => detect_vim_from_inputrc(),
-        }
+        self.editor.vim_mode.resolve()
     }
 
     /// Resolve the API key from env var or saved settings, returning the key and its source.
     pub fn resolve_api_key(&self) -> (Option<String>, ApiKeySource) {
-        if let Ok(key) = std::env::var("ANTHROPIC_API_KEY")
+        if let Ok(key) = std::env::var(self.provider.env_var())
             && !key.is_empty()
         {
             return (Some(key), ApiKeySource::EnvVar);
@@ -79,10 +361,65 @@ impl Settings {
         {
             return (Some(key.clone()), ApiKeySource::Settings);
         }
+        if !self.provider.requires_api_key() {
+            return (Some(String::new()), ApiKeySource::NotRequired);
+        }
         (None, ApiKeySource::Missing)
     }
 }
 
+/// Settings checked into a repository, so a team can share review preferences.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct ProjectSettings {
+    /// How to organize walkthrough steps for this codebase
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub organize_by: Option<StepOrganization>,
+    /// Prompt changes for this codebase, over the user's `api.prompts`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prompts: Option<PromptSettings>,
+}
+
+impl ProjectSettings {
+    /// Load `.docent.json` from the git repository root (or the working directory).
+    /// Prompt files are relative to the repository root, and must be inside it.
+    pub fn load() -> Result<Self, ApiError> {
+        let Some(root) = project_root() else {
+            return Ok(Self::default());
+        };
+        let mut project: Self = fs::read_to_string(root.join(PROJECT_SETTINGS_FILE))
+            .ok()
+            .and_then(|contents| serde_json::from_str(&contents).ok())
+            .unwrap_or_default();
+        project.prompts = project
+            .prompts
+            .map(|prompts| prompts.within_project(&root))
+            .transpose()?;
+        Ok(project)
+    }
+}
+
+/// The git repository root, or the working directory outside a repository.
+pub fn project_root() -> Option<PathBuf> {
+    let toplevel = std::process::Command::new("git")
+        .args(["rev-parse", "--show-toplevel"])
+        .stderr(std::process::Stdio::null())
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| PathBuf::from(String::from_utf8_lossy(&output.stdout).trim()));
+    toplevel.or_else(|| std::env::current_dir().ok())
+}
+
+/// The directory name of `project_root`, looked up once.
+pub fn project_name() -> &'static str {
+    static NAME: OnceLock<String> = OnceLock::new();
+    NAME.get_or_init(|| {
+        project_root()
+            .and_then(|root| Some(root.file_name()?.to_string_lossy().into_owned()))
+            .unwrap_or_default()
+    })
+}
+
 /// Check if .inputrc contains "set editing-mode vi"
 fn detect_vim_from_inputrc() -> bool {
     let inputrc_path = dirs::home_dir().map(|h| h.join(".inputrc"));
@@ -0,0 +1,227 @@
+//! Opt-in local usage statistics.
+//!
+//! When `stats.enabled` is set in ~/.docent/settings.json, one JSON line per
+//! review is appended to ~/.docent/stats.jsonl when docent exits. Nothing is
+//! sent anywhere; `docent stats` summarizes the file.
+
+use std::collections::HashSet;
+use std::fmt::Write as _;
+use std::fs::{self, OpenOptions};
+use std::io::Write as _;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::model::ReviewMode;
+use crate::session::Session;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReviewStats {
+    /// Seconds since the Unix epoch when the review ended
+    pub finished_at: u64,
+    pub duration_secs: u64,
+    pub mode: ReviewMode,
+    pub model: Option<String>,
+    pub files: usize,
+    pub diff_lines: usize,
+    pub steps: usize,
+    pub reviewed_steps: usize,
+    pub input_tokens: u32,
+    pub output_tokens: u32,
+    /// Estimated cost in USD, when the model's prices were known
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cost_usd: Option<f64>,
+}
+
+impl ReviewStats {
+    /// Stats for the session's review, or `None` if nothing was generated.
+    pub fn from_session(session: &Session, duration: Duration) -> Option<Self> {
+        let (reviewed_steps, steps) = session.overall_progress();
+        if steps == 0 {
+            return None;
+        }
+
+        let mut walkthroughs = vec![&session.walkthrough];
+        walkthroughs.extend(
+            session
+                .parts
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| *i != session.current_part)
+                .map(|(_, part)| &part.walkthrough),
+        );
+        let files: HashSet<&str> = walkthroughs
+            .iter()
+            .flat_map(|w| &w.steps)
+            .flat_map(|step| &step.hunks)
+            .map(|hunk| hunk.file_path.as_str())
+            .collect();
+        let diff_lines = walkthroughs
+            .iter()
+            .flat_map(|w| &w.steps)
+            .map(|step| step.diff_line_count())
+            .sum();
+
+        Some(Self {
+            finished_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |d| d.as_secs()),
+            duration_secs: duration.as_secs(),
+            mode: session.review_mode,
+            model: session.generation_progress.model.clone(),
+            files: files.len(),
+            diff_lines,
+            steps,
+            reviewed_steps,
+            input_tokens: session.token_usage.input_tokens,
+            output_tokens: session.token_usage.output_tokens,
+            cost_usd: session.cost_usd(),
+        })
+    }
+}
+
+fn stats_path() -> Option<PathBuf> {
+    dirs::home_dir().map(|h| h.join(".docent").join("stats.jsonl"))
+}
+
+/// Appends a review to the stats log.
+pub fn record(stats: &ReviewStats) -> Result<(), String> {
+    let path = stats_path().ok_or("Could not determine home directory")?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Failed to create stats dir: {e}"))?;
+    }
+    let line =
+        serde_json::to_string(stats).map_err(|e| format!("Failed to serialize stats: {e}"))?;
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .map_err(|e| format!("Failed to open stats log: {e}"))?;
+    writeln!(file, "{line}").map_err(|e| format!("Failed to write stats: {e}"))
+}
+
+/// All recorded reviews, skipping lines that don't parse.
+pub fn load() -> Vec<ReviewStats> {
+    stats_path()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .map(|contents| {
+            contents
+                .lines()
+                .filter_map(|line| serde_json::from_str(line).ok())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Totals and averages across reviews.
+pub fn summary(reviews: &[ReviewStats], enabled: bool) -> String {
+    if reviews.is_empty() {
+        return if enabled {
+            "No reviews recorded yet.\n".to_string()
+        } else {
+            "Usage statistics are off. Set \"stats\": { \"enabled\": true } in \
+             ~/.docent/settings.json to start recording.\n"
+                .to_string()
+        };
+    }
+
+    let count = reviews.len();
+    let total = |field: fn(&ReviewStats) -> u64| reviews.iter().map(field).sum::<u64>();
+    let diff_lines = total(|r| r.diff_lines as u64);
+    let steps = total(|r| r.steps as u64);
+    let reviewed = total(|r| r.reviewed_steps as u64);
+    let seconds = total(|r| r.duration_secs);
+    let input_tokens = total(|r| r.input_tokens as u64);
+    let output_tokens = total(|r| r.output_tokens as u64);
+    let completed = reviews
+        .iter()
+        .filter(|r| r.reviewed_steps == r.steps)
+        .count();
+    let walkthroughs = reviews
+        .iter()
+        .filter(|r| r.mode == ReviewMode::Walkthrough)
+        .count();
+
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "Reviews:       {} ({} fully reviewed, {} in walkthrough mode)",
+        count, completed, walkthroughs
+    );
+    let _ = writeln!(
+        out,
+        "Diff lines:    {} total, {} per review",
+        diff_lines,
+        diff_lines / count as u64
+    );
+    let _ = writeln!(
+        out,
+        "Steps:         {} reviewed of {} ({:.0}%)",
+        reviewed,
+        steps,
+        reviewed as f64 * 100.0 / steps.max(1) as f64
+    );
+    let _ = writeln!(
+        out,
+        "Time:          {} total, {} per review",
+        format_duration(seconds),
+        format_duration(seconds / count as u64)
+    );
+    let _ = writeln!(
+        out,
+        "Tokens:        {} in, {} out",
+        input_tokens, output_tokens
+    );
+    let costs: Vec<f64> = reviews.iter().filter_map(|r| r.cost_usd).collect();
+    if !costs.is_empty() {
+        let cost: f64 = costs.iter().sum();
+        let _ = writeln!(
+            out,
+            "Cost:          ~${:.2} total, ~${:.2} per review",
+            cost,
+            cost / costs.len() as f64
+        );
+    }
+    out
+}
+
+/// A duration as seconds, minutes and seconds, or hours and minutes.
+pub fn format_duration(seconds: u64) -> String {
+    match seconds {
+        s if s < 60 => format!("{}s", s),
+        s if s < 3600 => format!("{}m {}s", s / 60, s % 60),
+        s => format!("{}h {}m", s / 3600, s % 3600 / 60),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn review(steps: usize, reviewed_steps: usize, duration_secs: u64) -> ReviewStats {
+        ReviewStats {
+            finished_at: 0,
+            duration_secs,
+            mode: ReviewMode::Review,
+            model: None,
+            files: 3,
+            diff_lines: 120,
+            steps,
+            reviewed_steps,
+            input_tokens: 1000,
+            output_tokens: 200,
+            cost_usd: Some(0.006),
+        }
+    }
+
+    #[test]
+    fn test_summary_totals() {
+        let summary = summary(&[review(4, 4, 300), review(6, 3, 4000)], true);
+        assert!(summary.contains("Reviews:       2 (1 fully reviewed, 0 in walkthrough mode)"));
+        assert!(summary.contains("Steps:         7 reviewed of 10 (70%)"));
+        assert!(summary.contains("Time:          1h 11m total, 35m 50s per review"));
+        assert!(summary.contains("Tokens:        2000 in, 400 out"));
+        assert!(summary.contains("Cost:          ~$0.01 total, ~$0.01 per review"));
+    }
+}
@@ -0,0 +1,90 @@
+//! Transient notifications for background events.
+
+use std::collections::VecDeque;
+use std::time::Instant;
+
+use crate::constants::{TOAST_DURATION, TOAST_MAX_VISIBLE};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ToastKind {
+    Info,
+    Error,
+}
+
+/// A single notification shown in the bottom-right corner.
+#[derive(Debug, Clone)]
+pub struct Toast {
+    pub message: String,
+    pub kind: ToastKind,
+    shown_at: Instant,
+}
+
+/// Queue of active toasts, oldest first.
+#[derive(Debug, Default)]
+pub struct Toasts {
+    items: VecDeque<Toast>,
+}
+
+impl Toasts {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(&mut self, message: impl Into<String>, kind: ToastKind) {
+        self.push_at(message, kind, Instant::now());
+    }
+
+    fn push_at(&mut self, message: impl Into<String>, kind: ToastKind, now: Instant) {
+        self.items.push_back(Toast {
+            message: message.into(),
+            kind,
+            shown_at: now,
+        });
+        // Drop the oldest rather than stacking toasts up the screen
+        while self.items.len() > TOAST_MAX_VISIBLE {
+            self.items.pop_front();
+        }
+    }
+
+    /// Removes toasts that have been visible longer than `TOAST_DURATION`.
+    /// Returns true if anything was removed, so the caller knows to redraw.
+    pub fn expire(&mut self, now: Instant) -> bool {
+        let before = self.items.len();
+        self.items
+            .retain(|toast| now.duration_since(toast.shown_at) < TOAST_DURATION);
+        self.items.len() != before
+    }
+
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &Toast> {
+        self.items.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_toasts_expire_after_duration() {
+        let start = Instant::now();
+        let mut toasts = Toasts::new();
+        toasts.push_at("first", ToastKind::Info, start);
+        toasts.push_at("second", ToastKind::Info, start + Duration::from_secs(1));
+
+        assert!(!toasts.expire(start + TOAST_DURATION - Duration::from_millis(1)));
+        assert!(toasts.expire(start + TOAST_DURATION));
+        let remaining: Vec<_> = toasts.iter().map(|t| t.message.as_str()).collect();
+        assert_eq!(remaining, ["second"]);
+    }
+
+    #[test]
+    fn test_oldest_toast_dropped_when_full() {
+        let mut toasts = Toasts::new();
+        for i in 0..=TOAST_MAX_VISIBLE {
+            toasts.push(format!("toast {i}"), ToastKind::Info);
+        }
+        assert_eq!(toasts.iter().count(), TOAST_MAX_VISIBLE);
+        assert_eq!(toasts.iter().next().unwrap().message, "toast 1");
+    }
+}
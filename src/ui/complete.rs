@@ -0,0 +1,107 @@
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Padding, Paragraph, Wrap},
+};
+
+use crate::app::App;
+use crate::stats::format_duration;
+
+use super::{format_cost, format_token_count, severity_color};
+
+/// Width of the labels in the totals column.
+const LABEL_WIDTH: usize = 16;
+
+/// The completion screen shown in place of the panes once every step is
+/// reviewed: the review's totals, its flags, and what to do next.
+pub fn render(frame: &mut Frame, area: Rect, app: &App) {
+    let s = app.strings;
+    let walkthrough = &app.session.walkthrough;
+    let totals = app.review_totals();
+    let dim = Style::default().fg(app.theme.dim);
+    let bold = Style::default().add_modifier(Modifier::BOLD);
+    let row = |label: &str, value: String| {
+        Line::from(vec![
+            Span::styled(format!("{:<LABEL_WIDTH$}", label), dim),
+            Span::raw(value),
+        ])
+    };
+
+    let flags = walkthrough.flags();
+    let tally = if flags.is_empty() {
+        s.complete_no_flags.to_string()
+    } else {
+        s.flag_tally(walkthrough)
+    };
+    let mut tokens = format!(
+        "↑{} ↓{}",
+        format_token_count(totals.usage.input_tokens),
+        format_token_count(totals.usage.output_tokens)
+    );
+    if let Some(cost) = totals.cost_usd {
+        tokens.push_str(&format!("  {}", format_cost(cost)));
+    }
+
+    let mut text = vec![
+        Line::from(Span::styled(
+            s.complete_title,
+            bold.fg(app.theme.step_completed),
+        )),
+        Line::from(s.complete_all_reviewed),
+        Line::from(""),
+        row(
+            s.complete_steps,
+            format!("{} / {}", totals.reviewed, totals.steps),
+        ),
+        row(s.complete_flags, tally),
+        row(s.complete_time, format_duration(totals.elapsed.as_secs())),
+        row(s.complete_tokens, tokens),
+    ];
+
+    if !flags.is_empty() {
+        text.push(Line::from(""));
+        for (i, flag) in flags {
+            let mut spans = vec![
+                Span::styled(
+                    format!("⚑ {:<10}", s.severity_label(flag.severity)),
+                    Style::default().fg(severity_color(flag.severity, &app.theme)),
+                ),
+                Span::raw(format!("{}. {} ", i + 1, walkthrough.steps[i].title)),
+            ];
+            if let Some(location) = flag.location_label() {
+                spans.push(Span::styled(format!("{} ", location), dim));
+            }
+            if !flag.text.is_empty() {
+                spans.push(Span::styled(
+                    format!("— {}", flag.text.replace('\n', " ")),
+                    dim,
+                ));
+            }
+            text.push(Line::from(spans));
+        }
+    }
+
+    let key_style = Style::default().fg(app.theme.key);
+    text.push(Line::from(""));
+    text.push(Line::from(vec![
+        Span::styled(" e ", key_style),
+        Span::raw(format!("{}  ", s.complete_export)),
+        Span::styled(" c ", key_style),
+        Span::raw(format!("{}  ", s.complete_copy)),
+        Span::styled(" T ", key_style),
+        Span::raw(format!("{}  ", s.complete_record)),
+        Span::styled(" Esc ", key_style),
+        Span::raw(format!("{}  ", s.complete_back)),
+        Span::styled(" q ", key_style),
+        Span::raw(s.act_quit),
+    ]));
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(app.theme.step_completed))
+        .padding(Padding::new(2, 2, 1, 1));
+    let paragraph = Paragraph::new(text).block(block).wrap(Wrap { trim: false });
+    frame.render_widget(paragraph, area);
+}
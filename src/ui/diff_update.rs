@@ -0,0 +1,59 @@
+use ratatui::{
+    Frame,
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Padding, Paragraph, Wrap},
+};
+
+use crate::app::App;
+use crate::constants::{DIFF_UPDATE_DIALOG_HEIGHT, DIFF_UPDATE_DIALOG_WIDTH};
+
+use super::centered_rect;
+
+/// Offer to regenerate the walkthrough after the watched diff changed.
+pub fn render(frame: &mut Frame, area: Rect, app: &App) {
+    let s = app.strings;
+    let dialog_area = centered_rect(DIFF_UPDATE_DIALOG_WIDTH, DIFF_UPDATE_DIALOG_HEIGHT, area);
+    frame.render_widget(Clear, dialog_area);
+
+    let block = Block::default()
+        .title(s.diff_update_title)
+        .title_alignment(Alignment::Center)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(app.theme.accent))
+        .padding(Padding::new(2, 2, 1, 1));
+    let inner = block.inner(dialog_area);
+    frame.render_widget(block, dialog_area);
+
+    let sections = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(1),    // Details
+            Constraint::Length(1), // Help bar
+        ])
+        .split(inner);
+
+    let lines = vec![
+        Line::from(s.diff_update_intro),
+        Line::from(""),
+        Line::from(Span::styled(
+            s.diff_update_reviewed,
+            Style::default().fg(app.theme.dim),
+        )),
+    ];
+    frame.render_widget(
+        Paragraph::new(lines).wrap(Wrap { trim: false }),
+        sections[0],
+    );
+
+    let key_style = Style::default().fg(app.theme.key);
+    let help_line = Paragraph::new(Line::from(vec![
+        Span::styled(" Enter ", key_style),
+        Span::raw(format!("{} ", s.diff_update_regenerate)),
+        Span::styled(" Esc ", key_style),
+        Span::raw(s.diff_update_keep),
+    ]))
+    .alignment(Alignment::Center);
+    frame.render_widget(help_line, sections[1]);
+}
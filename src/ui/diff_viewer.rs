@@ -1,17 +1,116 @@
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::ops::Range;
+
 use ratatui::{
     Frame,
     layout::Rect,
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::{Line, Span},
     widgets::{Borders, Clear, Paragraph},
 };
 
-use super::pane_block;
+use super::{pane_block, render_scrollbar};
 use crate::app::App;
-use crate::colors;
+use crate::colors::Theme;
+use crate::diff::intraline_changes;
 use crate::layout::Pane;
+use crate::model::Step;
 use crate::search::SearchState;
 
+/// What the cached lines were styled from.
+#[derive(PartialEq)]
+struct CacheKey {
+    step: usize,
+    /// Hash of the step's hunks, which moving or splitting hunks changes
+    hunks: u64,
+    /// Hash of the search matches, which change with the query and with
+    /// regex mode
+    matches: Option<u64>,
+    current_match: usize,
+    width: u16,
+}
+
+impl CacheKey {
+    fn new(step_index: usize, step: &Step, search: &SearchState, width: u16) -> Self {
+        let mut hasher = DefaultHasher::new();
+        for hunk in &step.hunks {
+            (&hunk.file_path, &hunk.symbol, &hunk.content, hunk.folded).hash(&mut hasher);
+        }
+        let matches = search.query.as_ref().map(|_| {
+            let mut hasher = DefaultHasher::new();
+            search.matches.hash(&mut hasher);
+            hasher.finish()
+        });
+        Self {
+            step: step_index,
+            hunks: hasher.finish(),
+            matches,
+            current_match: search.current,
+            width,
+        }
+    }
+}
+
+/// The current step's diff lines with their diff and search styling, kept
+/// between frames so scrolling a large step doesn't restyle every line.
+/// The hunk cursor, reviewed marks, line numbers, and selection are cheap
+/// and added to the visible lines on each draw.
+#[derive(Default)]
+pub struct DiffLineCache {
+    key: Option<CacheKey>,
+    lines: Vec<Line<'static>>,
+    numbers: Vec<(Option<usize>, Option<usize>)>,
+    /// Digits in the largest line number
+    number_width: usize,
+    /// Display line of each hunk's file header
+    headers: Vec<usize>,
+}
+
+impl DiffLineCache {
+    fn refresh(&mut self, key: CacheKey, step: &Step, search: &SearchState, theme: &Theme) {
+        if self.key.as_ref() == Some(&key) {
+            return;
+        }
+        self.numbers = step.display_line_numbers();
+        self.number_width = self
+            .numbers
+            .iter()
+            .flat_map(|&(old, new)| [old, new])
+            .flatten()
+            .max()
+            .map_or(1, |n| n.to_string().len());
+        self.headers = step
+            .hunk_line_counts()
+            .scan(0, |pos, count| {
+                let header = *pos;
+                *pos += count;
+                Some(header)
+            })
+            .collect();
+        // Changed words of each display line, from its hunk's pairing pass
+        let mut changed: Vec<Vec<Range<usize>>> = Vec::new();
+        for hunk in &step.hunks {
+            changed.extend([Vec::new(), Vec::new()]);
+            if hunk.folded {
+                changed.push(Vec::new());
+            } else {
+                changed.extend(intraline_changes(&hunk.content));
+            }
+            changed.push(Vec::new());
+        }
+        self.lines = step
+            .display_lines()
+            .into_iter()
+            .enumerate()
+            .map(|(line_index, text)| {
+                let words = changed.get(line_index).map_or(&[][..], Vec::as_slice);
+                style_diff_line_with_search(&text, line_index, words, search, theme)
+            })
+            .collect();
+        self.key = Some(key);
+    }
+}
+
 pub fn render(frame: &mut Frame, area: Rect, app: &App) {
     // Reserve space for search prompt when active
     let search_active = app.search.active;
@@ -22,32 +121,66 @@ pub fn render(frame: &mut Frame, area: Rect, app: &App) {
         inner_height
     };
 
-    let lines: Vec<Line> = if let Some(step) = app.session.current_step_data() {
-        step.display_lines()
-            .into_iter()
-            .enumerate()
-            .map(|(line_index, text)| style_diff_line_with_search(&text, line_index, &app.search))
-            .collect()
-    } else {
-        vec![Line::from("No diff content")]
-    };
-
-    let total_lines = lines.len();
+    let mut cache = app.diff_lines.borrow_mut();
+    let step = app.session.current_step_data();
+    if let Some(step) = step {
+        let key = CacheKey::new(app.session.current_step, step, &app.search, area.width);
+        cache.refresh(key, step, &app.search, &app.theme);
+    }
+    let total_lines = if step.is_some() { cache.lines.len() } else { 1 };
 
     // Apply scroll offset (clamped to valid range)
     let max_scroll = total_lines.saturating_sub(content_height);
     let scroll = app.diff_scroll.clamped(max_scroll);
-    let visible_lines: Vec<Line> = lines
-        .into_iter()
-        .skip(scroll)
-        .take(content_height)
-        .collect();
 
+    let visible_lines: Vec<Line> = if let Some(step) = step {
+        let step_reviewed = app.session.is_step_reviewed(app.session.current_step);
+        // The cursor only matters when there's more than one hunk to choose from
+        let cursor = (step.hunks.len() > 1).then(|| app.current_hunk());
+        let selection = app.current_selection();
+        let cursor_line = app.cursor_line();
+        let cache = &*cache;
+        cache
+            .lines
+            .iter()
+            .enumerate()
+            .skip(scroll)
+            .take(content_height)
+            .map(|(line_index, line)| {
+                let mut line = line.clone();
+                if let Some(hunk) = cache.headers.iter().position(|&h| h == line_index) {
+                    let reviewed = step_reviewed || step.hunks[hunk].reviewed;
+                    mark_hunk_header(&mut line, cursor == Some(hunk), reviewed, &app.theme);
+                }
+                let gutter = cache.numbers.get(line_index).copied().unwrap_or((None, None));
+                let gutter = line_number_gutter(gutter, cache.number_width, &app.theme);
+                line.spans.insert(0, gutter);
+                if selection.is_some_and(|(first, last)| (first..=last).contains(&line_index)) {
+                    line = line.patch_style(Style::default().add_modifier(Modifier::REVERSED));
+                } else if cursor_line == Some(line_index) {
+                    line = line.patch_style(Style::default().bg(app.theme.diff_cursor_line));
+                }
+                line
+            })
+            .collect()
+    } else {
+        vec![Line::from(app.strings.no_diff_content)]
+    };
+
+    let title = match app.current_selection() {
+        Some((first, last)) => format!(
+            "{} · {} {}",
+            app.strings.pane_diff,
+            last - first + 1,
+            app.strings.lines_selected
+        ),
+        None => app.strings.pane_diff.to_string(),
+    };
     let scroll_indicator = if total_lines > content_height && max_scroll > 0 {
         let percent = (scroll * 100) / max_scroll;
-        format!(" Diff [{}%] ", percent.min(100))
+        format!(" {} [{}%] ", title, percent.min(100))
     } else {
-        " Diff ".to_string()
+        format!(" {} ", title)
     };
 
     let is_active = app.layout.active_pane == Pane::Diff;
@@ -56,11 +189,24 @@ pub fn render(frame: &mut Frame, area: Rect, app: &App) {
     } else {
         Borders::TOP | Borders::RIGHT | Borders::BOTTOM
     };
-    let block = pane_block(&scroll_indicator, borders, is_active);
+    let block = pane_block(&scroll_indicator, borders, is_active, &app.theme);
+    let inner = block.inner(area);
     let paragraph = Paragraph::new(visible_lines).block(block);
 
     frame.render_widget(paragraph, area);
 
+    // In the right padding column, beside the lines it scrolls
+    if max_scroll > 0 && inner.height > 0 {
+        let track = Rect {
+            x: inner.right(),
+            y: inner.y,
+            width: 1,
+            height: content_height as u16,
+        };
+        render_scrollbar(frame, track, scroll, max_scroll, &app.theme);
+        app.diff_scroll.set_track(Some(track));
+    }
+
     // Render search prompt at bottom of diff area (inside the border)
     if search_active {
         let prompt_area = Rect {
@@ -73,115 +219,142 @@ pub fn render(frame: &mut Frame, area: Rect, app: &App) {
         frame.render_widget(Clear, prompt_area);
 
         let prompt = Line::from(vec![
-            Span::styled("/", Style::default().fg(Color::White)),
+            Span::styled("/", Style::default().fg(app.theme.text)),
             Span::raw(&app.search.input),
-            Span::styled("_", Style::default().fg(Color::DarkGray)),
+            Span::styled("_", Style::default().fg(app.theme.dim)),
+            Span::styled(
+                format!("  Tab {}", app.strings.search_all_steps),
+                Style::default().fg(app.theme.dim),
+            ),
+            Span::styled(
+                format!("  Ctrl+r {}", app.strings.search_regex),
+                Style::default().fg(if app.search.regex {
+                    app.theme.accent
+                } else {
+                    app.theme.dim
+                }),
+            ),
         ]);
         frame.render_widget(Paragraph::new(prompt), prompt_area);
     }
 }
 
-fn get_base_style(line: &str) -> Style {
+/// Old and new line numbers ahead of a diff line, blank for lines outside
+/// the hunk content.
+fn line_number_gutter(
+    (old, new): (Option<usize>, Option<usize>),
+    width: usize,
+    theme: &Theme,
+) -> Span<'static> {
+    let text = if old.is_none() && new.is_none() {
+        " ".repeat(width * 2 + 4)
+    } else {
+        let column = |n: Option<usize>| {
+            n.map_or_else(|| " ".repeat(width), |n| format!("{n:>width$}"))
+        };
+        format!("{} {} │ ", column(old), column(new))
+    };
+    Span::styled(text, Style::default().fg(theme.dim))
+}
+
+/// Adds the cursor and reviewed markers to a hunk's file header line.
+fn mark_hunk_header(line: &mut Line<'static>, selected: bool, reviewed: bool, theme: &Theme) {
+    if selected {
+        let style = Style::default()
+            .fg(theme.accent)
+            .add_modifier(Modifier::BOLD);
+        line.spans.insert(0, Span::styled("▶ ", style));
+    }
+    if reviewed {
+        line.spans
+            .push(Span::styled(" ✓", Style::default().fg(theme.success)));
+    }
+}
+
+pub(super) fn get_base_style(line: &str, theme: &Theme) -> Style {
     if line.starts_with("───") {
         Style::default()
-            .fg(colors::DIFF_FILE_HEADER)
+            .fg(theme.diff_file_header)
             .add_modifier(Modifier::BOLD)
     } else if line.starts_with("@@") {
-        Style::default().fg(colors::DIFF_HUNK_HEADER)
+        Style::default().fg(theme.diff_hunk_header)
     } else if line.starts_with('+') && !line.starts_with("+++") {
-        Style::default().fg(colors::DIFF_ADDED)
+        Style::default().fg(theme.diff.added)
     } else if line.starts_with('-') && !line.starts_with("---") {
-        Style::default().fg(colors::DIFF_REMOVED)
+        Style::default().fg(theme.diff.removed)
     } else {
         Style::default()
     }
 }
 
+/// Styles a diff line, highlighting the `changed` byte ranges of a paired
+/// removed/added line and then any search matches over them.
 fn style_diff_line_with_search(
     line: &str,
     line_index: usize,
+    changed: &[Range<usize>],
     search: &SearchState,
+    theme: &Theme,
 ) -> Line<'static> {
-    let base_style = get_base_style(line);
-    style_line_with_search(line, line_index, search, Some(base_style))
-}
-
-fn style_line_with_search(
-    line: &str,
-    line_index: usize,
-    search: &SearchState,
-    base_style: Option<Style>,
-) -> Line<'static> {
-    let base_style = base_style.unwrap_or_default();
-    let owned_line = line.to_string();
-
-    // If no search query, return simple styled line
-    if search.query.is_none() {
-        return Line::from(Span::styled(owned_line, base_style));
-    }
-
-    // Find all matches on this line
-    let matches_on_line: Vec<_> = search
-        .matches
+    let base_style = get_base_style(line, theme);
+    let emphasis = if line.starts_with('+') {
+        theme.diff_added_emphasis
+    } else {
+        theme.diff_removed_emphasis
+    };
+    let changed_style = Style::default().fg(theme.text).bg(emphasis);
+    let mut highlights: Vec<(Range<usize>, Style)> = changed
         .iter()
-        .enumerate()
-        .filter(|(_, m)| m.line == line_index)
+        .map(|range| (range.clone(), changed_style))
         .collect();
 
-    if matches_on_line.is_empty() {
-        return Line::from(Span::styled(owned_line, base_style));
-    }
-
-    // Build spans with highlighting
-    let mut spans = Vec::new();
-    let mut pos = 0;
-    let line_len = owned_line.len();
-
-    for (match_idx, m) in matches_on_line {
-        // Validate match bounds
-        if m.start > line_len || m.end > line_len || m.start > m.end {
-            continue;
-        }
-
-        // Add text before match
-        if m.start > pos
-            && let Some(text) = owned_line.get(pos..m.start)
-        {
-            spans.push(Span::styled(text.to_string(), base_style));
-        }
-
-        // Add highlighted match
-        let is_current = match_idx == search.current;
-        let highlight_style = if is_current {
-            Style::default()
-                .bg(colors::SEARCH_MATCH_CURRENT)
-                .fg(colors::SEARCH_MATCH_TEXT)
-                .add_modifier(Modifier::BOLD)
-        } else {
-            Style::default()
-                .bg(colors::SEARCH_MATCH)
-                .fg(colors::SEARCH_MATCH_TEXT)
-        };
-
-        if let Some(text) = owned_line.get(m.start..m.end) {
-            spans.push(Span::styled(text.to_string(), highlight_style));
+    if search.query.is_some() {
+        for (match_idx, m) in search.matches.iter().enumerate() {
+            if m.line != line_index || m.start > m.end || m.end > line.len() {
+                continue;
+            }
+            let highlight_style = if match_idx == search.current {
+                Style::default()
+                    .bg(theme.search_match_current)
+                    .fg(theme.search_match_text)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default()
+                    .bg(theme.search_match)
+                    .fg(theme.search_match_text)
+            };
+            highlights.push((m.start..m.end, highlight_style));
         }
+    }
 
-        pos = m.end;
+    if highlights.is_empty() {
+        return Line::from(Span::styled(line.to_string(), base_style));
     }
 
-    // Add remaining text after last match
-    if pos < line_len
-        && let Some(text) = owned_line.get(pos..)
-    {
-        spans.push(Span::styled(text.to_string(), base_style));
+    // Split the line at every highlight edge; later highlights win
+    let mut edges: Vec<usize> = vec![0, line.len()];
+    for (range, _) in &highlights {
+        edges.extend([range.start, range.end]);
     }
+    edges.sort_unstable();
+    edges.dedup();
+
+    let spans: Vec<Span<'static>> = edges
+        .windows(2)
+        .filter_map(|pair| {
+            let text = line.get(pair[0]..pair[1])?;
+            let style = highlights
+                .iter()
+                .rev()
+                .find(|(range, _)| range.start <= pair[0] && pair[1] <= range.end)
+                .map_or(base_style, |(_, style)| *style);
+            Some(Span::styled(text.to_string(), style))
+        })
+        .collect();
 
-    // Fallback if no spans were created
     if spans.is_empty() {
-        return Line::from(Span::styled(owned_line, base_style));
+        return Line::from(Span::styled(line.to_string(), base_style));
     }
-
     Line::from(spans)
 }
 
@@ -1,4 +1,4 @@
-use pulldown_cmark::{Event, Parser, Tag, TagEnd};
+use pulldown_cmark::{CodeBlockKind, Event, HeadingLevel, Parser, Tag, TagEnd};
 use ratatui::{
     Frame,
     layout::{Constraint, Direction, Layout, Rect},
@@ -7,64 +7,241 @@ use ratatui::{
     widgets::{Block, Borders, Paragraph, Wrap},
 };
 
-use super::pane_block;
+use super::{pane_block, render_scrollbar};
 use crate::app::App;
-use crate::colors;
+use crate::colors::Theme;
 use crate::constants::{INPUT_MAX_LINES, INPUT_MIN_LINES};
+use crate::keymap::FOLLOW_UP_ACTIONS;
 use crate::layout::Pane;
-use crate::model::MessageRole;
+use crate::model::{MessageRole, Relation};
 
-/// Parse markdown text and return styled spans.
-fn parse_markdown(text: &str) -> Vec<Span<'static>> {
-    let mut spans = Vec::new();
-    let parser = Parser::new(text);
+/// Renders an assistant message's markdown as lines: paragraphs, headings,
+/// bullet and numbered lists, block quotes, and fenced code blocks (with
+/// added and removed lines colored in `diff` blocks).
+fn render_markdown(text: &str, theme: &Theme) -> Vec<Line<'static>> {
+    let mut renderer = MarkdownRenderer::new(theme);
+    for event in Parser::new(text) {
+        renderer.event(event);
+    }
+    renderer.finish()
+}
+
+struct MarkdownRenderer<'t> {
+    theme: &'t Theme,
+    lines: Vec<Line<'static>>,
+    /// Spans of the line being built
+    spans: Vec<Span<'static>>,
+    styles: Vec<Style>,
+    /// Next number of each open list, innermost last; `None` for bullets
+    lists: Vec<Option<u64>>,
+    /// Language of the fenced code block being rendered, "" when unlabeled
+    code_block: Option<String>,
+    quote_depth: usize,
+}
+
+impl<'t> MarkdownRenderer<'t> {
+    fn new(theme: &'t Theme) -> Self {
+        Self {
+            theme,
+            lines: Vec::new(),
+            spans: Vec::new(),
+            styles: vec![Style::default().fg(theme.chat_assistant_text)],
+            lists: Vec::new(),
+            code_block: None,
+            quote_depth: 0,
+        }
+    }
+
+    fn style(&self) -> Style {
+        self.styles.last().copied().unwrap_or_default()
+    }
+
+    fn push_style(&mut self, style: Style) {
+        self.styles.push(style);
+    }
+
+    fn pop_style(&mut self) {
+        if self.styles.len() > 1 {
+            self.styles.pop();
+        }
+    }
+
+    fn dim(&self) -> Style {
+        Style::default().fg(self.theme.dim)
+    }
+
+    /// Quote bars and list indentation at the start of a line.
+    fn indent(&self, list_levels: usize) -> Span<'static> {
+        Span::styled(
+            format!(
+                "{}{}",
+                "│ ".repeat(self.quote_depth),
+                "   ".repeat(list_levels)
+            ),
+            self.dim(),
+        )
+    }
+
+    fn push_text(&mut self, text: String, style: Style) {
+        if self.spans.is_empty() {
+            self.spans.push(self.indent(self.lists.len()));
+        }
+        self.spans.push(Span::styled(text, style));
+    }
 
-    let normal_style = Style::default().fg(colors::CHAT_ASSISTANT_TEXT);
-    let bold_style = Style::default()
-        .fg(colors::CHAT_ASSISTANT_BOLD)
-        .add_modifier(Modifier::BOLD);
-    let code_style = Style::default().fg(colors::CHAT_ASSISTANT_CODE);
+    fn flush(&mut self) {
+        if !self.spans.is_empty() {
+            self.lines.push(Line::from(std::mem::take(&mut self.spans)));
+        }
+    }
 
-    let mut current_style = normal_style;
-    let mut style_stack: Vec<Style> = vec![normal_style];
+    /// Ends the current block with a blank line, except between list items.
+    fn end_block(&mut self) {
+        self.flush();
+        if self.lists.is_empty() && self.lines.last().is_some_and(|line| line.width() > 0) {
+            self.lines.push(Line::default());
+        }
+    }
 
-    for event in parser {
+    fn code_line(&mut self, line: &str) {
+        let style = match self.code_block.as_deref() {
+            Some("diff" | "patch") if line.starts_with('+') => {
+                Style::default().fg(self.theme.diff.added)
+            }
+            Some("diff" | "patch") if line.starts_with('-') => {
+                Style::default().fg(self.theme.diff.removed)
+            }
+            Some("diff" | "patch") if line.starts_with("@@") => {
+                Style::default().fg(self.theme.diff_hunk_header)
+            }
+            _ => Style::default().fg(self.theme.chat_assistant_code),
+        };
+        if self.spans.is_empty() {
+            self.spans.push(self.indent(self.lists.len()));
+            self.spans.push(Span::styled("▏ ", self.dim()));
+        }
+        self.spans.push(Span::styled(line.to_string(), style));
+    }
+
+    fn event(&mut self, event: Event) {
         match event {
-            Event::Start(Tag::Strong) => {
-                style_stack.push(bold_style);
-                current_style = bold_style;
+            Event::Start(Tag::Heading { level, .. }) => {
+                self.flush();
+                let mut style = Style::default()
+                    .fg(self.theme.chat_assistant_bold)
+                    .add_modifier(Modifier::BOLD);
+                if level <= HeadingLevel::H2 {
+                    style = style.add_modifier(Modifier::UNDERLINED);
+                }
+                self.push_style(style);
+            }
+            Event::End(TagEnd::Heading(_)) => {
+                self.pop_style();
+                self.end_block();
+            }
+            Event::End(TagEnd::Paragraph) => self.end_block(),
+            Event::Start(Tag::List(start)) => {
+                self.flush();
+                self.lists.push(start);
+            }
+            Event::End(TagEnd::List(_)) => {
+                self.lists.pop();
+                self.end_block();
+            }
+            Event::Start(Tag::Item) => {
+                self.flush();
+                let marker = match self.lists.last_mut() {
+                    Some(Some(n)) => {
+                        *n += 1;
+                        format!("{:<3}", format!("{}.", *n - 1))
+                    }
+                    _ => "•  ".to_string(),
+                };
+                self.spans
+                    .push(self.indent(self.lists.len().saturating_sub(1)));
+                self.spans.push(Span::styled(
+                    marker,
+                    Style::default().fg(self.theme.chat_assistant_bullet),
+                ));
             }
-            Event::End(TagEnd::Strong) => {
-                style_stack.pop();
-                current_style = *style_stack.last().unwrap_or(&normal_style);
+            Event::End(TagEnd::Item) => self.flush(),
+            Event::Start(Tag::BlockQuote(_)) => {
+                self.flush();
+                self.quote_depth += 1;
+            }
+            Event::End(TagEnd::BlockQuote(_)) => {
+                self.flush();
+                self.quote_depth = self.quote_depth.saturating_sub(1);
+                self.end_block();
+            }
+            Event::Start(Tag::CodeBlock(kind)) => {
+                self.flush();
+                self.code_block = Some(match kind {
+                    CodeBlockKind::Fenced(lang) => {
+                        lang.split_whitespace().next().unwrap_or("").to_string()
+                    }
+                    CodeBlockKind::Indented => String::new(),
+                });
+            }
+            Event::End(TagEnd::CodeBlock) => {
+                self.flush();
+                self.code_block = None;
+                self.end_block();
+            }
+            Event::Text(text) if self.code_block.is_some() => {
+                for line in text.split_inclusive('\n') {
+                    self.code_line(line.trim_end_matches('\n'));
+                    if line.ends_with('\n') {
+                        self.flush();
+                    }
+                }
+            }
+            Event::Start(Tag::Strong) => {
+                let style = Style::default()
+                    .fg(self.theme.chat_assistant_bold)
+                    .add_modifier(Modifier::BOLD);
+                self.push_style(style);
             }
             Event::Start(Tag::Emphasis) => {
-                let italic_style = current_style.add_modifier(Modifier::ITALIC);
-                style_stack.push(italic_style);
-                current_style = italic_style;
+                self.push_style(self.style().add_modifier(Modifier::ITALIC))
             }
-            Event::End(TagEnd::Emphasis) => {
-                style_stack.pop();
-                current_style = *style_stack.last().unwrap_or(&normal_style);
+            Event::Start(Tag::Link { .. }) => {
+                self.push_style(self.style().add_modifier(Modifier::UNDERLINED))
             }
+            Event::End(TagEnd::Strong | TagEnd::Emphasis | TagEnd::Link) => self.pop_style(),
             Event::Code(code) => {
-                spans.push(Span::styled(code.to_string(), code_style));
+                let style = Style::default().fg(self.theme.chat_assistant_code);
+                self.push_text(code.to_string(), style);
+            }
+            Event::Text(text) | Event::Html(text) | Event::InlineHtml(text) => {
+                let style = self.style();
+                self.push_text(text.to_string(), style);
             }
-            Event::Text(text) => {
-                spans.push(Span::styled(text.to_string(), current_style));
+            Event::SoftBreak => {
+                let style = self.style();
+                self.push_text(" ".to_string(), style);
             }
-            Event::SoftBreak | Event::HardBreak => {
-                spans.push(Span::raw(" "));
+            Event::HardBreak => self.flush(),
+            Event::Rule => {
+                self.flush();
+                self.lines
+                    .push(Line::from(Span::styled("─".repeat(24), self.dim())));
+                self.end_block();
             }
             _ => {}
         }
     }
 
-    if spans.is_empty() {
-        spans.push(Span::styled(String::new(), normal_style));
+    fn finish(mut self) -> Vec<Line<'static>> {
+        self.flush();
+        while self.lines.last().is_some_and(|line| line.width() == 0) {
+            self.lines.pop();
+        }
+        if self.lines.is_empty() {
+            self.lines.push(Line::default());
+        }
+        self.lines
     }
-
-    spans
 }
 
 pub fn render(frame: &mut Frame, area: Rect, app: &App) {
@@ -74,7 +251,15 @@ pub fn render(frame: &mut Frame, area: Rect, app: &App) {
     } else {
         Borders::ALL
     };
-    let outer_block = pane_block(" Chat ", borders, is_active);
+    let title = if app.session.whole_change_chat {
+        format!(
+            " {} · {} ",
+            app.strings.pane_chat, app.strings.chat_whole_change
+        )
+    } else {
+        format!(" {} ", app.strings.pane_chat)
+    };
+    let outer_block = pane_block(&title, borders, is_active, &app.theme);
     let inner_area = outer_block.inner(area);
     frame.render_widget(outer_block, area);
 
@@ -94,41 +279,30 @@ pub fn render(frame: &mut Frame, area: Rect, app: &App) {
 }
 
 fn render_chat_history(frame: &mut Frame, area: Rect, app: &App) {
-    let lines: Vec<Line> = if app.session.is_walkthrough_complete() {
-        vec![
-            Line::from(Span::styled(
-                "✓ Walkthrough complete!",
-                Style::default().fg(colors::STEP_COMPLETED),
-            )),
-            Line::from(""),
-            Line::from("All steps have been reviewed."),
-            Line::from("Press 'q' to exit or navigate back to review steps."),
-        ]
-    } else if let Some(step) = app.session.current_step_data() {
+    let lines: Vec<Line> = if let Some(step) = app.session.current_step_data() {
         let mut all_lines: Vec<Line> = Vec::new();
+        let whole_change = app.session.whole_change_chat;
+        let messages = app.session.chat_messages();
+
+        if whole_change && messages.is_empty() {
+            all_lines.push(Line::from(Span::styled(
+                app.strings.chat_whole_change_hint,
+                Style::default().fg(app.theme.dim),
+            )));
+        }
 
-        for message in &step.messages {
+        for (m, message) in messages.iter().enumerate() {
             match message.role {
                 MessageRole::Assistant => {
-                    let mut prev_empty = false;
-                    for (i, line) in message.content.lines().enumerate() {
-                        // Skip consecutive empty lines
-                        let is_empty = line.trim().is_empty();
-                        if is_empty && prev_empty {
-                            continue;
-                        }
-                        prev_empty = is_empty;
-
-                        let mut spans = if i == 0 {
-                            vec![Span::styled(
-                                "⏺ ",
-                                Style::default().fg(colors::CHAT_ASSISTANT_BULLET),
-                            )]
+                    let lines = render_markdown(&message.content, &app.theme);
+                    for (i, mut line) in lines.into_iter().enumerate() {
+                        let prefix = if i == 0 {
+                            Span::styled("⏺ ", Style::default().fg(app.theme.chat_assistant_bullet))
                         } else {
-                            vec![Span::raw("  ")]
+                            Span::raw("  ")
                         };
-                        spans.extend(parse_markdown(line));
-                        all_lines.push(Line::from(spans));
+                        line.spans.insert(0, prefix);
+                        all_lines.push(line);
                     }
                 }
                 MessageRole::User => {
@@ -137,56 +311,139 @@ fn render_chat_history(frame: &mut Frame, area: Rect, app: &App) {
                         all_lines.push(Line::from(Span::styled(
                             format!("{}{} ", prefix, line),
                             Style::default()
-                                .bg(colors::CHAT_USER_BG)
-                                .fg(colors::CHAT_USER_TEXT),
+                                .bg(app.theme.chat_user_bg)
+                                .fg(app.theme.chat_user_text),
                         )));
                     }
                 }
             }
             all_lines.push(Line::from("")); // Spacing between messages
+
+            // Links to related steps go right under the summary
+            if m == 0
+                && !whole_change
+                && let Some(line) = related_line(app)
+            {
+                all_lines.push(line);
+                all_lines.push(Line::from(""));
+            }
+
+            // The reviewer's notes follow the summary too
+            if m == 0 && !whole_change && !step.notes.is_empty() {
+                for note in &step.notes {
+                    for (i, line) in note.text.lines().enumerate() {
+                        let prefix = if i == 0 { "✎ " } else { "  " };
+                        all_lines.push(Line::from(vec![
+                            Span::styled(prefix, Style::default().fg(app.theme.chat_note)),
+                            Span::raw(line.to_string()),
+                        ]));
+                    }
+                }
+                all_lines.push(Line::from(""));
+            }
+        }
+
+        let follow_ups = app.session.current_follow_ups();
+        if !follow_ups.is_empty() {
+            let dim = Style::default().fg(app.theme.dim);
+            all_lines.push(Line::from(Span::styled(app.strings.follow_ups_label, dim)));
+            for (question, action) in follow_ups.iter().zip(FOLLOW_UP_ACTIONS) {
+                all_lines.push(Line::from(vec![
+                    Span::styled(
+                        format!("  {} ", app.keymap.label(&[action])),
+                        Style::default().fg(app.theme.key),
+                    ),
+                    Span::styled(question.clone(), dim),
+                ]));
+            }
+            all_lines.push(Line::from(""));
         }
 
         // Show thinking indicator if chat is pending and no response started yet
-        if app.session.chat_pending == Some(app.session.current_step) {
-            let show_thinking = step
-                .messages
+        if app.session.chat_pending == Some(app.session.chat_thread()) {
+            let show_thinking = messages
                 .last()
                 .map(|m| m.role == MessageRole::User)
                 .unwrap_or(true);
             if show_thinking {
                 all_lines.push(Line::from(Span::styled(
-                    "● Thinking...",
-                    Style::default().fg(colors::CHAT_ASSISTANT_BULLET),
+                    format!("● {}", app.strings.thinking),
+                    Style::default().fg(app.theme.chat_assistant_bullet),
                 )));
             }
         }
 
         if app.session.rechunk_pending {
             all_lines.push(Line::from(Span::styled(
-                "● Splitting step...",
-                Style::default().fg(colors::CHAT_ASSISTANT_BULLET),
+                format!("● {}", app.strings.splitting_step),
+                Style::default().fg(app.theme.chat_assistant_bullet),
             )));
         }
 
         all_lines
     } else {
-        vec![Line::from("No step selected")]
+        vec![Line::from(app.strings.no_step_selected)]
     };
 
     let paragraph = Paragraph::new(lines).wrap(Wrap { trim: false });
 
     let line_count = paragraph.line_count(area.width) as usize;
     let max_scroll = line_count.saturating_sub(area.height as usize);
-    let scroll = app.chat_scroll.position_from_top(max_scroll) as u16;
+    let scroll = app.chat_scroll.position_from_top(max_scroll);
+
+    frame.render_widget(paragraph.scroll((scroll as u16, 0)), area);
+
+    // In the pane's right padding column, beside the history
+    if max_scroll > 0 && area.height > 0 {
+        let track = Rect {
+            x: area.right(),
+            width: 1,
+            ..area
+        };
+        render_scrollbar(frame, track, scroll, max_scroll, &app.theme);
+        app.chat_scroll.set_track(Some(track));
+    }
+}
 
-    frame.render_widget(paragraph.scroll((scroll, 0)), area);
+/// "Related: step 3 (`is_valid`) · step 5 (src/lib.rs)" for the current step.
+fn related_line(app: &App) -> Option<Line<'static>> {
+    let s = app.strings;
+    let related = app
+        .session
+        .walkthrough
+        .related_steps(app.session.current_step);
+    if related.is_empty() {
+        return None;
+    }
+
+    let dim = Style::default().fg(app.theme.border_inactive);
+    let link = Style::default()
+        .fg(app.theme.chat_assistant_bullet)
+        .add_modifier(Modifier::UNDERLINED);
+    let mut spans = vec![Span::styled(format!("{} ", s.related_label), dim)];
+    for (i, r) in related.iter().enumerate() {
+        if i > 0 {
+            spans.push(Span::styled(" · ", dim));
+        }
+        let reason = match &r.relation {
+            Relation::SameRegion(path) => path.clone(),
+            Relation::Symbol(name) => format!("`{}`", name),
+        };
+        spans.push(Span::styled(
+            format!("{} {}", s.related_step, r.index + 1),
+            link,
+        ));
+        spans.push(Span::styled(format!(" ({})", reason), dim));
+    }
+    spans.push(Span::styled(format!("  {}", s.related_jump), dim));
+    Some(Line::from(spans))
 }
 
-fn render_input_box(frame: &mut Frame, area: Rect, app: &App) {
+pub(super) fn render_input_box(frame: &mut Frame, area: Rect, app: &App) {
     // First render the top border across the full width
     let border_block = Block::default()
         .borders(Borders::TOP)
-        .border_style(Style::default().fg(colors::BORDER_INACTIVE));
+        .border_style(Style::default().fg(app.theme.border_inactive));
     let inner_area = border_block.inner(area);
     frame.render_widget(border_block, area);
 
@@ -200,7 +457,7 @@ fn render_input_box(frame: &mut Frame, area: Rect, app: &App) {
         .split(inner_area);
 
     // Render the prompt
-    let prompt_style = Style::default().fg(colors::BORDER_INACTIVE);
+    let prompt_style = Style::default().fg(app.theme.border_inactive);
     let prompt = Paragraph::new(Line::from(Span::styled("> ", prompt_style)));
     frame.render_widget(prompt, chunks[0]);
 
@@ -208,8 +465,8 @@ fn render_input_box(frame: &mut Frame, area: Rect, app: &App) {
 
     // Show placeholder or the textarea
     if app.editor.is_empty() && !chat_focused {
-        let placeholder_style = Style::default().fg(colors::INPUT_PLACEHOLDER);
-        let placeholder = "Press 'i' to ask a question";
+        let placeholder_style = Style::default().fg(app.theme.input_placeholder);
+        let placeholder = app.strings.chat_placeholder;
         let input = Paragraph::new(Line::from(Span::styled(placeholder, placeholder_style)));
         frame.render_widget(input, chunks[1]);
     } else {
@@ -219,8 +476,8 @@ fn render_input_box(frame: &mut Frame, area: Rect, app: &App) {
         if chat_focused {
             textarea.set_cursor_style(
                 Style::default()
-                    .bg(colors::INPUT_CURSOR_BG)
-                    .fg(colors::INPUT_CURSOR_FG),
+                    .bg(app.theme.input_cursor_bg)
+                    .fg(app.theme.input_cursor_fg),
             );
         } else {
             textarea.set_cursor_style(Style::default());
@@ -0,0 +1,87 @@
+use ratatui::{
+    Frame,
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Padding, Paragraph, Wrap},
+};
+
+use crate::app::App;
+use crate::constants::{FEEDBACK_DIALOG_HEIGHT, FEEDBACK_DIALOG_WIDTH};
+use crate::feedback::{FeedbackDialog, Rating};
+
+use super::centered_rect;
+
+pub fn render(frame: &mut Frame, area: Rect, dialog: &FeedbackDialog, app: &App) {
+    let s = app.strings;
+    let dialog_area = centered_rect(FEEDBACK_DIALOG_WIDTH, FEEDBACK_DIALOG_HEIGHT, area);
+    frame.render_widget(Clear, dialog_area);
+
+    let block = Block::default()
+        .title(s.feedback_title)
+        .title_alignment(Alignment::Center)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(app.theme.accent))
+        .padding(Padding::new(2, 2, 1, 1));
+    let inner = block.inner(dialog_area);
+    frame.render_widget(block, dialog_area);
+
+    let sections = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(2), // Step title
+            Constraint::Length(2), // Rating
+            Constraint::Length(1), // Comment label
+            Constraint::Min(1),    // Comment
+            Constraint::Length(1), // Help bar
+        ])
+        .split(inner);
+
+    if let Some(step) = app.session.walkthrough.steps.get(dialog.step) {
+        let title = Paragraph::new(Span::styled(
+            step.title.as_str(),
+            Style::default().fg(app.theme.dim),
+        ));
+        frame.render_widget(title, sections[0]);
+    }
+
+    let option = |rating: Rating, label: &str, color: Color| {
+        let marker = if dialog.rating == rating {
+            "●"
+        } else {
+            "○"
+        };
+        let style = if dialog.rating == rating {
+            Style::default().fg(color).add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(app.theme.dim)
+        };
+        Span::styled(format!("{} {}", marker, label), style)
+    };
+    let rating = Paragraph::new(Line::from(vec![
+        option(Rating::Up, s.feedback_useful, app.theme.success),
+        Span::raw("    "),
+        option(Rating::Down, s.feedback_not_useful, app.theme.error),
+    ]));
+    frame.render_widget(rating, sections[1]);
+
+    frame.render_widget(Paragraph::new(s.feedback_comment), sections[2]);
+    let comment = Paragraph::new(Line::from(vec![
+        Span::raw(dialog.comment.as_str()),
+        Span::styled("█", Style::default().fg(app.theme.accent)),
+    ]))
+    .wrap(Wrap { trim: false });
+    frame.render_widget(comment, sections[3]);
+
+    let key_style = Style::default().fg(app.theme.key);
+    let help_line = Paragraph::new(Line::from(vec![
+        Span::styled(" ←/→ ", key_style),
+        Span::raw(format!("{} ", s.feedback_switch)),
+        Span::styled(" Enter ", key_style),
+        Span::raw(format!("{} ", s.feedback_save)),
+        Span::styled(" Esc ", key_style),
+        Span::raw(s.act_exit),
+    ]))
+    .alignment(Alignment::Center);
+    frame.render_widget(help_line, sections[4]);
+}
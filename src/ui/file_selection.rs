@@ -0,0 +1,118 @@
+use ratatui::{
+    Frame,
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, ListState, Padding, Paragraph},
+};
+
+use crate::app::App;
+use crate::constants::{FILE_SELECT_DIALOG_HEIGHT, FILE_SELECT_DIALOG_WIDTH};
+use crate::file_selection::FileSelection;
+
+use super::centered_rect;
+
+pub fn render(frame: &mut Frame, area: Rect, selection: &FileSelection, app: &App) {
+    let s = app.strings;
+    let dialog_area = centered_rect(FILE_SELECT_DIALOG_WIDTH, FILE_SELECT_DIALOG_HEIGHT, area);
+    let block = Block::default()
+        .title(s.files_title)
+        .title_alignment(Alignment::Center)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(app.theme.accent))
+        .padding(Padding::new(2, 2, 1, 1));
+
+    let inner = block.inner(dialog_area);
+    frame.render_widget(block, dialog_area);
+
+    let sections = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // Summary
+            Constraint::Min(1),    // File list
+            Constraint::Length(1), // Error
+            Constraint::Length(1), // Help bar
+        ])
+        .split(inner);
+
+    let dim = Style::default().fg(app.theme.dim);
+    let mut summary = vec![Line::from(Span::styled(
+        format!(
+            "{}/{} {}",
+            selection.selected_count(),
+            selection.files.len(),
+            s.files_selected
+        ),
+        dim,
+    ))];
+    let skipped = selection.skipped_denied();
+    if !skipped.is_empty() {
+        summary.push(Line::from(vec![
+            Span::styled(
+                format!(
+                    "{} {} ({}) ",
+                    skipped.len(),
+                    s.files_skipped,
+                    skipped.join(", ")
+                ),
+                dim,
+            ),
+            Span::styled(" d ", Style::default().fg(app.theme.key)),
+            Span::raw(s.files_include_skipped),
+        ]));
+    }
+    let summary = Paragraph::new(summary);
+    frame.render_widget(summary, sections[0]);
+
+    let items: Vec<ListItem> = selection
+        .files
+        .iter()
+        .enumerate()
+        .map(|(i, file)| {
+            let checkbox = if file.selected { "[x] " } else { "[ ] " };
+            let path_style = match (i == selection.cursor, file.selected) {
+                (true, _) => Style::default()
+                    .fg(app.theme.accent)
+                    .add_modifier(Modifier::BOLD),
+                (false, true) => Style::default().fg(app.theme.text),
+                (false, false) => Style::default().fg(app.theme.dim),
+            };
+            ListItem::new(Line::from(vec![
+                Span::styled(checkbox, Style::default().fg(app.theme.key)),
+                Span::styled(file.path.as_str(), path_style),
+                Span::styled(
+                    format!("  {} {}", file.hunks, s.files_hunks),
+                    Style::default().fg(app.theme.dim),
+                ),
+            ]))
+        })
+        .collect();
+    let mut state = ListState::default().with_selected(Some(selection.cursor));
+    frame.render_stateful_widget(List::new(items), sections[1], &mut state);
+
+    if let Some(error) = &selection.error {
+        let error = Paragraph::new(Span::styled(
+            error.as_str(),
+            Style::default().fg(app.theme.error),
+        ));
+        frame.render_widget(error, sections[2]);
+    }
+
+    let key = |k: &'static str| Span::styled(k, Style::default().fg(app.theme.key));
+    let help_line = Paragraph::new(Line::from(vec![
+        key(" Space "),
+        Span::raw(format!("{} ", s.files_toggle)),
+        key(" a "),
+        Span::raw(format!("{} ", s.files_all)),
+        key(" n "),
+        Span::raw(format!("{} ", s.files_none)),
+        key(" i "),
+        Span::raw(format!("{} ", s.files_invert)),
+        key(" Enter "),
+        Span::raw(format!("{} ", s.setup_start)),
+        key(" Esc "),
+        Span::raw(s.files_back),
+    ]))
+    .alignment(Alignment::Center);
+    frame.render_widget(help_line, sections[3]);
+}
@@ -0,0 +1,74 @@
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Borders, Paragraph},
+};
+
+use crate::app::App;
+use crate::layout::Pane;
+
+use super::pane_block;
+
+/// The file tree pane: every changed file under its directories, with the
+/// numbers of the steps touching it. Files in the current step stand out.
+pub fn render(frame: &mut Frame, area: Rect, app: &App) {
+    let theme = &app.theme;
+    let is_active = app.layout.active_pane == Pane::Files;
+    let current = app.session.current_step;
+    let dim = Style::default().fg(theme.dim);
+
+    let mut lines: Vec<Line> = Vec::new();
+    let mut selected_row = 0;
+    let mut file_index = 0;
+    for entry in app.file_tree() {
+        let indent = "  ".repeat(entry.depth);
+        if entry.path.is_none() {
+            lines.push(Line::from(Span::styled(
+                format!("{}{}/", indent, entry.name),
+                dim,
+            )));
+            continue;
+        }
+
+        let selected = file_index == app.file_tree_cursor;
+        if selected {
+            selected_row = lines.len();
+        }
+        file_index += 1;
+        let mut name_style = if entry.steps.contains(&current) {
+            Style::default().fg(theme.accent)
+        } else {
+            Style::default().fg(theme.text)
+        };
+        if selected && is_active {
+            name_style = name_style.add_modifier(Modifier::REVERSED);
+        }
+        let steps: Vec<String> = entry.steps.iter().map(|i| (i + 1).to_string()).collect();
+        lines.push(Line::from(vec![
+            Span::raw(indent),
+            Span::styled(entry.name, name_style),
+            Span::styled(format!("  {}", steps.join(",")), dim),
+        ]));
+    }
+
+    let title = format!(" {} ", app.strings.pane_files);
+    let borders = if app.layout.is_zoomed() {
+        Borders::TOP | Borders::BOTTOM
+    } else {
+        Borders::TOP | Borders::RIGHT | Borders::BOTTOM
+    };
+    let block = pane_block(&title, borders, is_active, theme);
+
+    // Keep the selected file in view
+    let height = block.inner(area).height as usize;
+    let scroll = selected_row.saturating_sub(height.saturating_sub(1) / 2);
+    let scroll = scroll.min(lines.len().saturating_sub(height));
+    frame.render_widget(
+        Paragraph::new(lines)
+            .block(block)
+            .scroll((scroll as u16, 0)),
+        area,
+    );
+}
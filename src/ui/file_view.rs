@@ -0,0 +1,101 @@
+use ratatui::{
+    Frame,
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Padding, Paragraph},
+};
+
+use crate::app::{App, FileView};
+use crate::constants::{FILE_VIEW_DIALOG_HEIGHT, FILE_VIEW_DIALOG_WIDTH};
+
+use super::centered_rect;
+use super::diff_viewer::get_base_style;
+
+/// Every hunk of one file in file order, each under the step it belongs to.
+pub fn render(frame: &mut Frame, area: Rect, view: &FileView, app: &App) {
+    let s = app.strings;
+    let dialog_area = centered_rect(FILE_VIEW_DIALOG_WIDTH, FILE_VIEW_DIALOG_HEIGHT, area);
+    frame.render_widget(Clear, dialog_area);
+
+    let title = format!(" {} ", view.path);
+    let block = Block::default()
+        .title(title)
+        .title_alignment(Alignment::Center)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(app.theme.accent))
+        .padding(Padding::new(2, 2, 1, 1));
+    let inner = block.inner(dialog_area);
+    frame.render_widget(block, dialog_area);
+
+    let sections = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(1),    // Hunks
+            Constraint::Length(1), // Help bar
+        ])
+        .split(inner);
+
+    let dim = Style::default().fg(app.theme.dim);
+    let steps = &app.session.walkthrough.steps;
+    let mut lines: Vec<Line> = Vec::new();
+    let mut selected_row = 0;
+    for (i, &(step_index, hunk_index)) in view.hunks.iter().enumerate() {
+        let Some(step) = steps.get(step_index) else {
+            continue;
+        };
+        let Some(hunk) = step.hunks.get(hunk_index) else {
+            continue;
+        };
+        if i == view.selected {
+            selected_row = lines.len();
+        }
+        let mut heading_style = Style::default()
+            .fg(app.theme.accent)
+            .add_modifier(Modifier::BOLD);
+        if i == view.selected {
+            heading_style = heading_style.add_modifier(Modifier::REVERSED);
+        }
+        let reviewed = if hunk.reviewed { " ✓" } else { "" };
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!("{} {} · {}", s.hunk_split_step, step_index + 1, step.title),
+                heading_style,
+            ),
+            Span::styled(
+                format!(
+                    "  {} {}-{}{}",
+                    s.lines, hunk.start_line, hunk.end_line, reviewed
+                ),
+                dim,
+            ),
+        ]));
+        for line in hunk.content.lines() {
+            lines.push(Line::from(Span::styled(
+                line.to_string(),
+                get_base_style(line, &app.theme),
+            )));
+        }
+        lines.push(Line::from(""));
+    }
+
+    // Keep the selected hunk's heading at the top
+    let height = sections[0].height as usize;
+    let scroll = selected_row.min(lines.len().saturating_sub(height));
+    frame.render_widget(
+        Paragraph::new(lines).scroll((scroll as u16, 0)),
+        sections[0],
+    );
+
+    let key_style = Style::default().fg(app.theme.key);
+    let help_line = Paragraph::new(Line::from(vec![
+        Span::styled(" j/k ", key_style),
+        Span::raw(format!("{} ", s.act_scroll)),
+        Span::styled(" Enter ", key_style),
+        Span::raw(format!("{} ", s.global_search_jump)),
+        Span::styled(" Esc ", key_style),
+        Span::raw(s.act_exit),
+    ]))
+    .alignment(Alignment::Center);
+    frame.render_widget(help_line, sections[1]);
+}
@@ -0,0 +1,111 @@
+use ratatui::{
+    Frame,
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Padding, Paragraph, Wrap},
+};
+
+use crate::app::{App, FlagEditor};
+use crate::constants::{FLAG_DIALOG_HEIGHT, FLAG_DIALOG_WIDTH};
+use crate::model::Severity;
+
+use super::{centered_rect, severity_color};
+
+pub fn render(frame: &mut Frame, area: Rect, flag_editor: &FlagEditor, app: &App) {
+    let s = app.strings;
+    let Some(step) = app.session.walkthrough.steps.get(flag_editor.step) else {
+        return;
+    };
+    let dialog_area = centered_rect(FLAG_DIALOG_WIDTH, FLAG_DIALOG_HEIGHT, area);
+    frame.render_widget(Clear, dialog_area);
+
+    let block = Block::default()
+        .title(s.flag_title)
+        .title_alignment(Alignment::Center)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(app.theme.accent))
+        .padding(Padding::new(2, 2, 1, 1));
+    let inner = block.inner(dialog_area);
+    frame.render_widget(block, dialog_area);
+
+    let sections = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(2), // Step title and flagged hunk
+            Constraint::Min(1),    // Existing flags
+            Constraint::Length(2), // Severity
+            Constraint::Length(1), // Concern label
+            Constraint::Length(4), // Editor
+            Constraint::Length(1), // Help bar
+        ])
+        .split(inner);
+
+    let dim = Style::default().fg(app.theme.dim);
+    let target = match &flag_editor.location {
+        Some((path, line)) => format!("{} · {}:{}", step.title, path, line),
+        None => step.title.clone(),
+    };
+    frame.render_widget(Paragraph::new(Span::styled(target, dim)), sections[0]);
+
+    let flags: Vec<Line> = if step.flags.is_empty() {
+        vec![Line::from(Span::styled(s.flag_empty, dim))]
+    } else {
+        step.flags
+            .iter()
+            .map(|flag| {
+                let mut spans = vec![Span::styled(
+                    format!("⚑ {} ", s.severity_label(flag.severity)),
+                    Style::default().fg(severity_color(flag.severity, &app.theme)),
+                )];
+                if let Some(location) = flag.location_label() {
+                    spans.push(Span::styled(format!("{} ", location), dim));
+                }
+                spans.push(Span::raw(flag.text.replace('\n', " ")));
+                Line::from(spans)
+            })
+            .collect()
+    };
+    frame.render_widget(
+        Paragraph::new(flags).wrap(Wrap { trim: false }),
+        sections[1],
+    );
+
+    let mut severities = vec![Span::raw(s.flag_severity)];
+    for severity in [Severity::Blocker, Severity::Question, Severity::Nit] {
+        let style = Style::default().fg(severity_color(severity, &app.theme));
+        let style = if severity == flag_editor.severity {
+            style.add_modifier(Modifier::REVERSED | Modifier::BOLD)
+        } else {
+            style
+        };
+        severities.push(Span::raw(" "));
+        severities.push(Span::styled(
+            format!(" {} ", s.severity_label(severity)),
+            style,
+        ));
+    }
+    frame.render_widget(Paragraph::new(Line::from(severities)), sections[2]);
+
+    frame.render_widget(Paragraph::new(s.flag_new), sections[3]);
+    let editor_block = Block::default()
+        .borders(Borders::LEFT)
+        .border_style(Style::default().fg(app.theme.accent));
+    let editor_area = editor_block.inner(sections[4]);
+    frame.render_widget(editor_block, sections[4]);
+    frame.render_widget(&flag_editor.editor.textarea, editor_area);
+
+    let key_style = Style::default().fg(app.theme.key);
+    let help_line = Paragraph::new(Line::from(vec![
+        Span::styled(" Tab ", key_style),
+        Span::raw(format!("{} ", s.flag_cycle)),
+        Span::styled(" Enter ", key_style),
+        Span::raw(format!("{} ", s.flag_add)),
+        Span::styled(" Ctrl+d ", key_style),
+        Span::raw(format!("{} ", s.note_delete)),
+        Span::styled(" Esc ", key_style),
+        Span::raw(s.act_exit),
+    ]))
+    .alignment(Alignment::Center);
+    frame.render_widget(help_line, sections[5]);
+}
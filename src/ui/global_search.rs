@@ -0,0 +1,117 @@
+use ratatui::{
+    Frame,
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Padding, Paragraph},
+};
+
+use crate::app::App;
+use crate::constants::{GLOBAL_SEARCH_DIALOG_HEIGHT, GLOBAL_SEARCH_DIALOG_WIDTH};
+use crate::search::GlobalSearch;
+
+use super::centered_rect;
+
+pub fn render(frame: &mut Frame, area: Rect, search: &GlobalSearch, app: &App) {
+    let s = app.strings;
+    let dialog_area = centered_rect(
+        GLOBAL_SEARCH_DIALOG_WIDTH,
+        GLOBAL_SEARCH_DIALOG_HEIGHT,
+        area,
+    );
+    frame.render_widget(Clear, dialog_area);
+
+    let block = Block::default()
+        .title(s.global_search_title)
+        .title_alignment(Alignment::Center)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(app.theme.accent))
+        .padding(Padding::new(2, 2, 1, 1));
+    let inner = block.inner(dialog_area);
+    frame.render_widget(block, dialog_area);
+
+    let sections = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(2), // Query
+            Constraint::Min(1),    // Results
+            Constraint::Length(1), // Help bar
+        ])
+        .split(inner);
+
+    let dim = Style::default().fg(app.theme.dim);
+    let count = if search.input.is_empty() {
+        String::new()
+    } else {
+        format!("  {}", search.results.len())
+    };
+    let query = Paragraph::new(Line::from(vec![
+        Span::styled("/", Style::default().fg(app.theme.text)),
+        Span::raw(search.input.as_str()),
+        Span::styled("_", dim),
+        Span::styled(count, dim),
+    ]));
+    frame.render_widget(query, sections[0]);
+
+    // Results grouped under their step's title
+    let mut lines: Vec<Line> = Vec::new();
+    let mut selected_row = 0;
+    let mut last_step = None;
+    for (i, result) in search.results.iter().enumerate() {
+        if last_step != Some(result.step) {
+            last_step = Some(result.step);
+            let title = app
+                .session
+                .walkthrough
+                .steps
+                .get(result.step)
+                .map_or("", |step| step.title.as_str());
+            lines.push(Line::from(Span::styled(
+                format!("{}. {}", result.step + 1, title),
+                Style::default()
+                    .fg(app.theme.accent)
+                    .add_modifier(Modifier::BOLD),
+            )));
+        }
+        if i == search.selected {
+            selected_row = lines.len();
+        }
+        let text_style = if i == search.selected {
+            Style::default().add_modifier(Modifier::REVERSED)
+        } else {
+            Style::default()
+        };
+        lines.push(Line::from(vec![
+            Span::styled(format!("  {}  ", result.location), dim),
+            Span::styled(result.text.trim().to_string(), text_style),
+        ]));
+    }
+    if lines.is_empty() && !search.input.is_empty() {
+        lines.push(Line::from(Span::styled(s.global_search_none, dim)));
+    } else if search.results.len() >= crate::search::MAX_STEP_MATCHES {
+        lines.push(Line::from(Span::styled(s.global_search_truncated, dim)));
+    }
+
+    // Keep the highlighted result in view
+    let height = sections[1].height as usize;
+    let scroll = selected_row.saturating_sub(height.saturating_sub(1) / 2);
+    let scroll = scroll.min(lines.len().saturating_sub(height));
+    frame.render_widget(
+        Paragraph::new(lines).scroll((scroll as u16, 0)),
+        sections[1],
+    );
+
+    let key_style = Style::default().fg(app.theme.key);
+    let help_line = Paragraph::new(Line::from(vec![
+        Span::styled(" ↑/↓ ", key_style),
+        Span::raw(format!("{} ", s.act_scroll)),
+        Span::styled(" Enter ", key_style),
+        Span::raw(format!("{} ", s.global_search_jump)),
+        Span::styled(" Tab ", key_style),
+        Span::raw(format!("{} ", s.global_search_this_step)),
+        Span::styled(" Esc ", key_style),
+        Span::raw(s.act_exit),
+    ]))
+    .alignment(Alignment::Center);
+    frame.render_widget(help_line, sections[2]);
+}
@@ -0,0 +1,95 @@
+use ratatui::{
+    Frame,
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Padding, Paragraph},
+};
+
+use crate::app::{App, HunkMove};
+use crate::constants::{HUNK_MOVE_DIALOG_HEIGHT, HUNK_MOVE_DIALOG_WIDTH};
+
+use super::centered_rect;
+
+/// Step list for picking where the selected hunk goes.
+pub fn render(frame: &mut Frame, area: Rect, pick: &HunkMove, app: &App) {
+    let s = app.strings;
+    let session = &app.session;
+    let Some(hunk) = session
+        .current_step_data()
+        .and_then(|step| step.hunks.get(pick.hunk))
+    else {
+        return;
+    };
+    let dialog_area = centered_rect(HUNK_MOVE_DIALOG_WIDTH, HUNK_MOVE_DIALOG_HEIGHT, area);
+    frame.render_widget(Clear, dialog_area);
+
+    let block = Block::default()
+        .title(s.hunk_move_title)
+        .title_alignment(Alignment::Center)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(app.theme.accent))
+        .padding(Padding::new(2, 2, 1, 1));
+    let inner = block.inner(dialog_area);
+    frame.render_widget(block, dialog_area);
+
+    let sections = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(2), // Hunk
+            Constraint::Min(1),    // Steps
+            Constraint::Length(1), // Help bar
+        ])
+        .split(inner);
+
+    let heading = Paragraph::new(Span::styled(
+        format!(
+            "{} {} · {}:{}",
+            s.quick_hunk,
+            pick.hunk + 1,
+            hunk.file_path,
+            hunk.start_line
+        ),
+        Style::default().fg(app.theme.dim),
+    ));
+    frame.render_widget(heading, sections[0]);
+
+    let items: Vec<ListItem> = session
+        .walkthrough
+        .steps
+        .iter()
+        .enumerate()
+        .map(|(i, step)| {
+            let current = i == session.current_step;
+            let marker = if current { "● " } else { "  " };
+            let style = if current {
+                Style::default().fg(app.theme.dim)
+            } else if i == pick.cursor {
+                Style::default()
+                    .fg(app.theme.accent)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(app.theme.text)
+            };
+            let indent = "  ".repeat(step.depth as usize);
+            ListItem::new(Line::from(vec![
+                Span::styled(marker, Style::default().fg(app.theme.success)),
+                Span::styled(format!("{indent}{}. {}", step.id, step.title), style),
+            ]))
+        })
+        .collect();
+    let mut state = ListState::default().with_selected(Some(pick.cursor));
+    frame.render_stateful_widget(List::new(items), sections[1], &mut state);
+
+    let key_style = Style::default().fg(app.theme.key);
+    let help_line = Paragraph::new(Line::from(vec![
+        Span::styled(" j/k ", key_style),
+        Span::raw(format!("{} ", s.act_scroll)),
+        Span::styled(" Enter ", key_style),
+        Span::raw(format!("{} ", s.hunk_move_confirm)),
+        Span::styled(" Esc ", key_style),
+        Span::raw(s.act_exit),
+    ]))
+    .alignment(Alignment::Center);
+    frame.render_widget(help_line, sections[2]);
+}
@@ -0,0 +1,85 @@
+use ratatui::{
+    Frame,
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Padding, Paragraph},
+};
+
+use crate::app::{App, HunkSplit};
+use crate::constants::{HUNK_SPLIT_DIALOG_HEIGHT, HUNK_SPLIT_DIALOG_WIDTH};
+
+use super::centered_rect;
+
+/// Checklist of the current step's hunks to move into a new step.
+pub fn render(frame: &mut Frame, area: Rect, split: &HunkSplit, app: &App) {
+    let s = app.strings;
+    let Some(step) = app.session.current_step_data() else {
+        return;
+    };
+    let dialog_area = centered_rect(HUNK_SPLIT_DIALOG_WIDTH, HUNK_SPLIT_DIALOG_HEIGHT, area);
+    frame.render_widget(Clear, dialog_area);
+
+    let block = Block::default()
+        .title(s.hunk_split_title)
+        .title_alignment(Alignment::Center)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(app.theme.accent))
+        .padding(Padding::new(2, 2, 1, 1));
+    let inner = block.inner(dialog_area);
+    frame.render_widget(block, dialog_area);
+
+    let sections = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(2), // Step
+            Constraint::Min(1),    // Hunks
+            Constraint::Length(1), // Help bar
+        ])
+        .split(inner);
+
+    let heading = Paragraph::new(Span::styled(
+        format!("{} {} · {}", s.hunk_split_step, step.id, step.title),
+        Style::default().fg(app.theme.dim),
+    ));
+    frame.render_widget(heading, sections[0]);
+
+    let items: Vec<ListItem> = step
+        .hunks
+        .iter()
+        .zip(&split.chosen)
+        .enumerate()
+        .map(|(i, (hunk, &chosen))| {
+            let checkbox = if chosen { "[x] " } else { "[ ] " };
+            let location = match &hunk.symbol {
+                Some(symbol) => format!("{}:{} · {}", hunk.file_path, hunk.start_line, symbol),
+                None => format!("{}:{}", hunk.file_path, hunk.start_line),
+            };
+            let style = if i == split.cursor {
+                Style::default()
+                    .fg(app.theme.accent)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(app.theme.text)
+            };
+            ListItem::new(Line::from(vec![
+                Span::styled(checkbox, Style::default().fg(app.theme.success)),
+                Span::styled(location, style),
+            ]))
+        })
+        .collect();
+    let mut state = ListState::default().with_selected(Some(split.cursor));
+    frame.render_stateful_widget(List::new(items), sections[1], &mut state);
+
+    let key_style = Style::default().fg(app.theme.key);
+    let help_line = Paragraph::new(Line::from(vec![
+        Span::styled(" Space ", key_style),
+        Span::raw(format!("{} ", s.hunk_split_pick)),
+        Span::styled(" Enter ", key_style),
+        Span::raw(format!("{} ", s.hunk_split_confirm)),
+        Span::styled(" Esc ", key_style),
+        Span::raw(s.act_exit),
+    ]))
+    .alignment(Alignment::Center);
+    frame.render_widget(help_line, sections[2]);
+}
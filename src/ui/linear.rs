@@ -0,0 +1,151 @@
+//! Screen-reader friendly single-column presentation.
+//!
+//! Everything for the current step is rendered as one plain-text stream in
+//! reading order: step header, summary, each hunk with a spoken-style file
+//! header, then the conversation. There are no pane borders, box-drawing
+//! glyphs, or colour-only cues, so a screen reader reading the terminal
+//! line-by-line hears the review in a sensible order.
+
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout, Rect},
+    text::Line,
+    widgets::{Paragraph, Wrap},
+};
+
+use super::explanation;
+use crate::app::App;
+use crate::constants::{INPUT_MAX_LINES, INPUT_MIN_LINES};
+use crate::model::{MessageRole, Priority, Step};
+use crate::session::ChatThread;
+
+pub fn render(frame: &mut Frame, area: Rect, app: &App) {
+    let input_lines =
+        (app.editor.textarea.lines().len() as u16).clamp(INPUT_MIN_LINES, INPUT_MAX_LINES);
+
+    let chunks = Layout::default()
+        .constraints([
+            Constraint::Min(1),                  // Linear stream
+            Constraint::Length(input_lines + 1), // Input box (+1 for the top border)
+        ])
+        .split(area);
+
+    render_stream(frame, chunks[0], app);
+    explanation::render_input_box(frame, chunks[1], app);
+}
+
+fn render_stream(frame: &mut Frame, area: Rect, app: &App) {
+    let sections = sections(app);
+
+    // Record where each section starts (in wrapped rows) so `{` / `}` can jump
+    let mut offsets = Vec::with_capacity(sections.len());
+    let mut total = 0;
+    for section in &sections {
+        offsets.push(total);
+        total += Paragraph::new(section.clone())
+            .wrap(Wrap { trim: false })
+            .line_count(area.width);
+    }
+    *app.linear_sections.borrow_mut() = offsets;
+
+    let lines: Vec<Line> = sections.into_iter().flatten().collect();
+    let max_scroll = total.saturating_sub(area.height as usize);
+    let scroll = app.diff_scroll.clamped(max_scroll) as u16;
+
+    let paragraph = Paragraph::new(lines)
+        .wrap(Wrap { trim: false })
+        .scroll((scroll, 0));
+    frame.render_widget(paragraph, area);
+}
+
+/// Builds the stream as a list of sections, each ending with a blank line.
+fn sections(app: &App) -> Vec<Vec<Line<'static>>> {
+    let session = &app.session;
+
+    let Some(step) = session.current_step_data() else {
+        return vec![vec![Line::from("No step selected.")]];
+    };
+
+    let mut sections = vec![header_section(app, step)];
+
+    let file_count = step.hunks.len();
+    for (i, hunk) in step.hunks.iter().enumerate() {
+        let mut lines = vec![Line::from(format!(
+            "File {} of {}: {}, lines {} to {}",
+            i + 1,
+            file_count,
+            hunk.file_path,
+            hunk.start_line,
+            hunk.end_line
+        ))];
+        lines.extend(hunk.content.lines().map(|l| Line::from(l.to_string())));
+        lines.push(Line::from(""));
+        sections.push(lines);
+    }
+
+    sections.push(conversation_section(app, step));
+    sections
+}
+
+fn header_section(app: &App, step: &Step) -> Vec<Line<'static>> {
+    let session = &app.session;
+    let priority = match step.priority {
+        Priority::Critical => "critical priority",
+        Priority::Normal => "normal priority",
+        Priority::Minor => "minor priority",
+    };
+    let reviewed = if session.is_step_reviewed(session.current_step) {
+        "reviewed"
+    } else {
+        "not reviewed"
+    };
+
+    let mut lines = vec![Line::from(format!(
+        "Step {} of {}: {}. {}, {}.",
+        session.current_step + 1,
+        session.walkthrough.step_count(),
+        step.title,
+        priority,
+        reviewed
+    ))];
+    if session.generation_in_progress {
+        lines.push(Line::from("More steps are still being generated."));
+    }
+    lines.push(Line::from(""));
+    lines.extend(step.summary.lines().map(|l| Line::from(l.to_string())));
+    lines.push(Line::from(""));
+    lines
+}
+
+fn conversation_section(app: &App, step: &Step) -> Vec<Line<'static>> {
+    let session = &app.session;
+    let mut lines = vec![Line::from("Conversation")];
+
+    // The first assistant message is the seeded summary, already shown above
+    let messages: Vec<_> = step
+        .messages
+        .iter()
+        .skip_while(|m| m.role == MessageRole::Assistant && m.content == step.summary)
+        .collect();
+
+    if messages.is_empty() {
+        lines.push(Line::from("No messages yet. Press i to ask a question."));
+    }
+    for message in messages {
+        let speaker = match message.role {
+            MessageRole::Assistant => "Assistant:",
+            MessageRole::User => "You:",
+        };
+        lines.push(Line::from(speaker));
+        lines.extend(message.content.lines().map(|l| Line::from(l.to_string())));
+        lines.push(Line::from(""));
+    }
+
+    if session.chat_pending == Some(ChatThread::Step(session.current_step)) {
+        lines.push(Line::from("Assistant is responding…"));
+    }
+    if session.rechunk_pending {
+        lines.push(Line::from("Splitting step…"));
+    }
+    lines
+}
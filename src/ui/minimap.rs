@@ -1,16 +1,18 @@
+use std::time::Instant;
+
 use ratatui::{
     Frame,
     layout::Rect,
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::{Line, Span},
     widgets::{Borders, List, ListItem},
 };
 
-use super::pane_block;
+use super::{format_token_count, pane_block, severity_color, spinner_frame};
 use crate::app::App;
-use crate::colors;
+use crate::colors::Theme;
 use crate::layout::Pane;
-use crate::model::Step;
+use crate::model::{Priority, PriorityFilter, Step};
 
 fn is_last_child(steps: &[Step], index: usize) -> bool {
     let depth = steps[index].depth;
@@ -20,12 +22,93 @@ fn is_last_child(steps: &[Step], index: usize) -> bool {
     }
 }
 
+/// A top-level step holding only sub-steps, such as a commit in a
+/// `--by-commit` walkthrough, which is drawn as a section heading.
+fn is_section(steps: &[Step], index: usize) -> bool {
+    steps[index].depth == 0
+        && steps[index].hunks.is_empty()
+        && steps.get(index + 1).is_some_and(|next| next.depth > 0)
+}
+
+/// Marks the step's priority with a symbol as well as a color.
+fn priority_marker(step: &Step, theme: &Theme) -> Span<'static> {
+    let (symbol, color) = match step.priority {
+        Priority::Critical => ("! ", theme.priority_critical),
+        Priority::Normal => ("• ", theme.priority_normal),
+        Priority::Minor => ("· ", theme.priority_minor),
+    };
+    Span::styled(symbol, Style::default().fg(color))
+}
+
+/// Marks steps the reviewer has left notes on.
+fn note_indicator(step: &Step) -> &'static str {
+    if step.notes.is_empty() { "" } else { " ✎" }
+}
+
+/// Marks flagged steps, colored by their most serious flag.
+fn flag_indicator(step: &Step, theme: &Theme) -> Span<'static> {
+    match step.flags.iter().map(|flag| flag.severity).min() {
+        Some(severity) => Span::styled(" ⚑", Style::default().fg(severity_color(severity, theme))),
+        None => Span::raw(""),
+    }
+}
+
+/// Marks steps with an answer that finished while another step was shown.
+fn unread_indicator(app: &App, index: usize) -> Span<'static> {
+    if app.session.has_unread_chat(index) {
+        Span::styled(" ●", Style::default().fg(app.theme.accent))
+    } else {
+        Span::raw("")
+    }
+}
+
+/// Reviewed hunks of a partly reviewed step, e.g. " 3/12".
+fn hunk_indicator(step: &Step, is_reviewed: bool) -> String {
+    let reviewed = step.reviewed_hunk_count();
+    if is_reviewed || reviewed == 0 {
+        String::new()
+    } else {
+        format!(" {}/{}", reviewed, step.hunks.len())
+    }
+}
+
+/// Detail lines under the "Generating…" row: steps parsed, tokens received,
+/// elapsed time, and the model, once the generation task has reported them.
+fn generation_progress_lines(app: &App) -> Vec<String> {
+    let progress = &app.session.generation_progress;
+    let Some(started_at) = progress.started_at else {
+        return vec![];
+    };
+    let mut lines = vec![format!(
+        "  {} {} · {} {} · {}s",
+        progress.steps_received,
+        app.strings.progress_steps,
+        format_token_count(progress.output_tokens),
+        app.strings.progress_tokens,
+        started_at.elapsed().as_secs(),
+    )];
+    if let Some((notice, until)) = &progress.retry {
+        let remaining = until.saturating_duration_since(Instant::now());
+        lines.push(format!(
+            "  {}, {} {}s…",
+            app.strings.retry_reason(notice),
+            app.strings.retrying_in,
+            remaining.as_secs()
+        ));
+    }
+    if let Some(model) = &progress.model {
+        lines.push(format!("  {}", model));
+    }
+    lines
+}
+
 pub fn render(frame: &mut Frame, area: Rect, app: &App) {
     let steps = &app.session.walkthrough.steps;
 
     let mut items: Vec<ListItem> = steps
         .iter()
         .enumerate()
+        .filter(|(i, _)| app.session.is_step_shown(*i))
         .map(|(i, step)| {
             let is_current = i == app.session.current_step;
             let is_reviewed = app.session.is_step_reviewed(i);
@@ -39,19 +122,19 @@ pub fn render(frame: &mut Frame, area: Rect, app: &App) {
                 };
 
                 let tree_color = if is_reviewed {
-                    colors::STEP_COMPLETED
+                    app.theme.step_completed
                 } else {
-                    colors::STEP_PENDING
+                    app.theme.step_pending
                 };
 
                 let text_style = if is_current {
                     Style::default()
-                        .fg(colors::STEP_CURRENT)
+                        .fg(app.theme.step_current)
                         .add_modifier(Modifier::BOLD)
                 } else if is_reviewed {
-                    Style::default().fg(colors::STEP_COMPLETED)
+                    Style::default().fg(app.theme.step_completed)
                 } else {
-                    Style::default().fg(colors::STEP_PENDING)
+                    Style::default().fg(app.theme.step_pending)
                 };
 
                 let current_indicator = if is_current { " ←" } else { "" };
@@ -64,38 +147,87 @@ pub fn render(frame: &mut Frame, area: Rect, app: &App) {
                         format!("{}{}", indent, branch),
                         Style::default().fg(tree_color),
                     ),
+                    priority_marker(step, &app.theme),
                     Span::styled(&step.title, text_style),
-                    Span::styled(current_indicator, Style::default().fg(colors::STEP_CURRENT)),
+                    Span::styled(
+                        hunk_indicator(step, is_reviewed),
+                        Style::default().fg(app.theme.dim),
+                    ),
+                    Span::styled(
+                        note_indicator(step),
+                        Style::default().fg(app.theme.chat_note),
+                    ),
+                    flag_indicator(step, &app.theme),
+                    unread_indicator(app, i),
+                    Span::styled(
+                        current_indicator,
+                        Style::default().fg(app.theme.step_current),
+                    ),
                 ]);
 
                 ListItem::new(line)
             } else {
                 // Top-level step: original rendering
                 let (indicator, indicator_color) = if is_reviewed {
-                    ("✓", colors::STEP_COMPLETED)
+                    ("✓", app.theme.step_completed)
                 } else {
-                    ("○", colors::STEP_PENDING)
+                    ("○", app.theme.step_pending)
                 };
 
                 let text_style = if is_current {
                     Style::default()
-                        .fg(colors::STEP_CURRENT)
+                        .fg(app.theme.step_current)
                         .add_modifier(Modifier::BOLD)
                 } else if is_reviewed {
-                    Style::default().fg(colors::STEP_COMPLETED)
+                    Style::default().fg(app.theme.step_completed)
                 } else {
-                    Style::default().fg(colors::STEP_PENDING)
+                    Style::default().fg(app.theme.step_pending)
                 };
 
                 let current_indicator = if is_current { " ←" } else { "" };
 
+                if is_section(steps, i) {
+                    // Section heading: no priority, and bold like a title
+                    return ListItem::new(Line::from(vec![
+                        Span::styled(
+                            format!("{} ", indicator),
+                            Style::default().fg(indicator_color),
+                        ),
+                        Span::styled(&step.title, text_style.add_modifier(Modifier::BOLD)),
+                        Span::styled(
+                            note_indicator(step),
+                            Style::default().fg(app.theme.chat_note),
+                        ),
+                        flag_indicator(step, &app.theme),
+                        unread_indicator(app, i),
+                        Span::styled(
+                            current_indicator,
+                            Style::default().fg(app.theme.step_current),
+                        ),
+                    ]));
+                }
+
                 let line = Line::from(vec![
                     Span::styled(
                         format!("{} ", indicator),
                         Style::default().fg(indicator_color),
                     ),
+                    priority_marker(step, &app.theme),
                     Span::styled(&step.title, text_style),
-                    Span::styled(current_indicator, Style::default().fg(colors::STEP_CURRENT)),
+                    Span::styled(
+                        hunk_indicator(step, is_reviewed),
+                        Style::default().fg(app.theme.dim),
+                    ),
+                    Span::styled(
+                        note_indicator(step),
+                        Style::default().fg(app.theme.chat_note),
+                    ),
+                    flag_indicator(step, &app.theme),
+                    unread_indicator(app, i),
+                    Span::styled(
+                        current_indicator,
+                        Style::default().fg(app.theme.step_current),
+                    ),
                 ]);
 
                 ListItem::new(line)
@@ -104,36 +236,67 @@ pub fn render(frame: &mut Frame, area: Rect, app: &App) {
         .collect();
 
     if app.session.generation_in_progress {
-        let spinner_frames = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
-        let spinner_idx = (std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .map(|d| d.as_millis() / 100)
-            .unwrap_or(0) as usize)
-            % spinner_frames.len();
-
-        items.push(ListItem::new(Line::from(vec![
-            Span::styled(
-                format!("{} ", spinner_frames[spinner_idx]),
-                Style::default()
-                    .fg(Color::Cyan)
-                    .add_modifier(Modifier::BOLD),
-            ),
-            Span::styled("Generating...", Style::default().fg(Color::DarkGray)),
-        ])));
+        let mut lines = vec![if app.reduced_motion {
+            Line::from(Span::styled(
+                app.strings.generating,
+                Style::default().fg(app.theme.dim),
+            ))
+        } else {
+            Line::from(vec![
+                Span::styled(
+                    format!("{} ", spinner_frame()),
+                    Style::default()
+                        .fg(app.theme.accent)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(app.strings.generating, Style::default().fg(app.theme.dim)),
+            ])
+        }];
+        lines.extend(
+            generation_progress_lines(app)
+                .into_iter()
+                .map(|text| Line::from(Span::styled(text, Style::default().fg(app.theme.dim)))),
+        );
+        lines.push(Line::from(Span::styled(
+            format!("  {}", app.strings.loading_cancel),
+            Style::default().fg(app.theme.dim),
+        )));
+        items.push(ListItem::new(lines));
     }
 
     let step_count = app.session.walkthrough.step_count();
-    let title = if step_count == 0 {
-        " Steps ".to_string()
+    let mut title = if step_count == 0 {
+        format!(" {} ", app.strings.pane_steps)
     } else {
+        let (reviewed_hunks, total_hunks) = app.session.hunk_progress();
         format!(
-            " Steps ({}/{}) · {}/{} lines ",
+            " {} ({}/{}) · {}/{} {} · {}/{} {} ",
+            app.strings.pane_steps,
             app.session.current_step + 1,
             step_count,
             app.session.reviewed_diff_lines(),
             app.session.total_diff_lines(),
+            app.strings.lines,
+            reviewed_hunks,
+            total_hunks,
+            app.strings.hunks,
         )
     };
+    if app.session.priority_filter != PriorityFilter::All {
+        let filter = app
+            .strings
+            .priority_filter_label(app.session.priority_filter);
+        title = format!("{}· {} ", title, filter);
+    }
+    if !app.session.parts.is_empty() {
+        title = format!(
+            " {} {}/{} ·{}",
+            app.strings.parts_part,
+            app.session.current_part + 1,
+            app.session.parts.len(),
+            title
+        );
+    }
 
     let is_active = app.layout.active_pane == Pane::Minimap;
     let borders = if app.layout.is_zoomed() {
@@ -141,7 +304,7 @@ pub fn render(frame: &mut Frame, area: Rect, app: &App) {
     } else {
         Borders::TOP | Borders::LEFT | Borders::RIGHT
     };
-    let block = pane_block(&title, borders, is_active);
+    let block = pane_block(&title, borders, is_active, &app.theme);
     let list = List::new(items).block(block);
 
     frame.render_widget(list, area);
@@ -1,32 +1,62 @@
+mod complete;
+mod diff_update;
 pub mod diff_viewer;
 pub mod explanation;
+mod feedback;
+mod flag;
+mod global_search;
+mod hunk_move;
+mod hunk_split;
+mod note;
+mod file_selection;
+mod file_tree;
+mod file_view;
+mod linear;
 pub mod minimap;
+mod onboarding;
+mod parts;
+mod quick_actions;
+mod recovery;
+mod regenerate;
 mod setup;
+mod step_finder;
+mod toast;
+mod usage;
 
 use ratatui::{
     Frame,
     layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Padding, Paragraph, Wrap},
+    widgets::{
+        Block, Borders, Padding, Paragraph, Scrollbar, ScrollbarOrientation, ScrollbarState,
+        Wrap,
+    },
 };
 
 use crate::app::App;
-use crate::colors;
+use crate::colors::Theme;
 use crate::constants::{
-    ERROR_DIALOG_HEIGHT, ERROR_DIALOG_WIDTH, HELP_DIALOG_HEIGHT, HELP_DIALOG_WIDTH,
-    LOADING_DIALOG_HEIGHT, LOADING_DIALOG_WIDTH,
+    ERROR_DIALOG_HEIGHT, ERROR_DIALOG_WIDTH, FILE_TREE_PERCENT, HELP_DIALOG_HEIGHT,
+    HELP_DIALOG_WIDTH, LOADING_DIALOG_HEIGHT, LOADING_DIALOG_WIDTH, MIN_TERMINAL_HEIGHT,
+    MIN_TERMINAL_WIDTH,
 };
 use crate::editor::VimInputMode;
 use crate::layout::Pane;
+use crate::model::Severity;
 use crate::session::SessionState;
 
 /// Creates a styled block for a pane with consistent styling.
-pub fn pane_block(title: &str, borders: Borders, is_active: bool) -> Block<'_> {
+pub fn pane_block<'a>(
+    title: &'a str,
+    borders: Borders,
+    is_active: bool,
+    theme: &Theme,
+) -> Block<'a> {
     let border_color = if is_active {
-        colors::BORDER_ACTIVE
+        theme.border_active
     } else {
-        colors::BORDER_INACTIVE
+        theme.border_inactive
     };
     Block::default()
         .title(title)
@@ -35,27 +65,80 @@ pub fn pane_block(title: &str, borders: Borders, is_active: bool) -> Block<'_> {
         .padding(Padding::horizontal(1))
 }
 
+/// Draws a scrollbar down `track` for content scrolled to `position` of
+/// `max_scroll`.
+pub fn render_scrollbar(
+    frame: &mut Frame,
+    track: Rect,
+    position: usize,
+    max_scroll: usize,
+    theme: &Theme,
+) {
+    let scrollbar = Scrollbar::new(ScrollbarOrientation::VerticalRight)
+        .begin_symbol(None)
+        .end_symbol(None)
+        .track_symbol(Some("│"))
+        .track_style(Style::default().fg(theme.border_inactive))
+        .thumb_symbol("█")
+        .thumb_style(Style::default().fg(theme.dim));
+    let mut state = ScrollbarState::new(max_scroll + 1).position(position);
+    frame.render_stateful_widget(scrollbar, track, &mut state);
+}
+
+/// Whether the terminal is below the minimum size, where only a notice is
+/// drawn and the mouse is ignored.
+pub fn too_small(width: u16, height: u16) -> bool {
+    width < MIN_TERMINAL_WIDTH || height < MIN_TERMINAL_HEIGHT
+}
+
 pub fn render(frame: &mut Frame, app: &App) {
+    // Panes that draw a scrollbar this frame record it again
+    app.diff_scroll.set_track(None);
+    app.chat_scroll.set_track(None);
+
+    let area = frame.area();
+    if too_small(area.width, area.height) {
+        render_too_small(frame, area, app);
+        return;
+    }
+
     match &app.session.state {
-        SessionState::Setup => {
-            setup::render(frame, frame.area(), app);
-        }
+        SessionState::Setup => match (&app.onboarding, &app.file_selection, &app.split_offer) {
+            (Some(onboarding), _, _) => {
+                onboarding::render(frame, frame.area(), onboarding, &app.theme)
+            }
+            (None, _, Some(offer)) => parts::render_split_offer(frame, frame.area(), offer, app),
+            (None, Some(selection), None) => {
+                file_selection::render(frame, frame.area(), selection, app)
+            }
+            (None, None, None) => setup::render(frame, frame.area(), app),
+        },
         SessionState::Loading {
             status,
             step_titles,
         } => {
-            render_loading(frame, frame.area(), status, step_titles);
+            render_loading(frame, frame.area(), status, step_titles, app);
         }
         SessionState::Error { message } => {
-            render_error(frame, frame.area(), message);
+            render_error(frame, frame.area(), message, app);
         }
         SessionState::Ready => {
             render_ready(frame, app);
         }
     }
+
+    if let Some(offer) = &app.recovery_offer {
+        recovery::render(frame, frame.area(), offer, app);
+    }
 }
 
 fn render_ready(frame: &mut Frame, app: &App) {
+    if app.session.is_walkthrough_complete() {
+        complete::render(frame, frame.area(), app);
+        toast::render(frame, frame.area(), app);
+        return;
+    }
+
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
@@ -64,35 +147,95 @@ fn render_ready(frame: &mut Frame, app: &App) {
         ])
         .split(frame.area());
 
-    render_main(frame, chunks[0], app);
+    if app.layout.linear {
+        linear::render(frame, chunks[0], app);
+    } else {
+        render_main(frame, chunks[0], app);
+    }
     render_help_bar(frame, chunks[1], app);
+    toast::render(frame, chunks[0], app);
+
+    if let Some(cursor) = app.parts_picker {
+        parts::render_picker(frame, frame.area(), cursor, app);
+    }
+
+    if let Some(menu) = &app.quick_actions {
+        quick_actions::render(frame, frame.area(), menu, app);
+    }
+
+    if let Some(pick) = &app.hunk_move {
+        hunk_move::render(frame, frame.area(), pick, app);
+    }
+
+    if let Some(split) = &app.hunk_split {
+        hunk_split::render(frame, frame.area(), split, app);
+    }
+
+    if let Some(dialog) = &app.feedback {
+        feedback::render(frame, frame.area(), dialog, app);
+    }
+
+    if let Some(note_editor) = &app.note_editor {
+        note::render(frame, frame.area(), note_editor, app);
+    }
+
+    if let Some(flag_editor) = &app.flag_editor {
+        flag::render(frame, frame.area(), flag_editor, app);
+    }
+
+    if let Some(editor) = &app.regenerate_prompt {
+        regenerate::render(frame, frame.area(), editor, app);
+    }
+
+    if let Some(editor) = &app.summary_prompt {
+        regenerate::render_summary(frame, frame.area(), editor, app);
+    }
+
+    if let Some(search) = &app.global_search {
+        global_search::render(frame, frame.area(), search, app);
+    }
+
+    if let Some(view) = &app.file_view {
+        file_view::render(frame, frame.area(), view, app);
+    }
+
+    if let Some(finder) = &app.step_finder {
+        step_finder::render(frame, frame.area(), finder, app);
+    }
+
+    if app.show_usage {
+        usage::render(frame, frame.area(), app);
+    }
+
+    if app.offered_diff_update().is_some() {
+        diff_update::render(frame, frame.area(), app);
+    }
 
     if app.show_help {
-        render_help_modal(frame, frame.area());
+        render_help_modal(frame, frame.area(), app);
     }
 }
 
-fn render_loading(frame: &mut Frame, area: Rect, status: &str, step_titles: &[String]) {
+fn render_loading(frame: &mut Frame, area: Rect, status: &str, step_titles: &[String], app: &App) {
+    let strings = app.strings;
     let dialog_area = centered_rect(LOADING_DIALOG_WIDTH, LOADING_DIALOG_HEIGHT, area);
     let block = Block::default()
-        .title(" Generating Walkthrough ")
+        .title(strings.loading_title)
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Cyan))
+        .border_style(Style::default().fg(app.theme.accent))
         .padding(Padding::horizontal(2));
 
-    let spinner_frames = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
-    let spinner_idx = (std::time::SystemTime::now()
-        .duration_since(std::time::UNIX_EPOCH)
-        .map(|d| d.as_millis() / 100)
-        .unwrap_or(0) as usize)
-        % spinner_frames.len();
-    let spinner = spinner_frames[spinner_idx];
+    let spinner = if app.reduced_motion {
+        "•"
+    } else {
+        spinner_frame()
+    };
 
     let mut lines = vec![Line::from("")];
 
     for title in step_titles {
         lines.push(Line::from(vec![
-            Span::styled("  ✓ ", Style::default().fg(Color::Green)),
+            Span::styled("  ✓ ", Style::default().fg(app.theme.success)),
             Span::raw(title.as_str()),
         ]));
     }
@@ -102,7 +245,7 @@ fn render_loading(frame: &mut Frame, area: Rect, status: &str, step_titles: &[St
         Span::styled(
             spinner,
             Style::default()
-                .fg(Color::Cyan)
+                .fg(app.theme.accent)
                 .add_modifier(Modifier::BOLD),
         ),
         Span::raw(" "),
@@ -111,8 +254,8 @@ fn render_loading(frame: &mut Frame, area: Rect, status: &str, step_titles: &[St
 
     lines.push(Line::from(""));
     lines.push(Line::from(Span::styled(
-        "Press Ctrl+C to cancel",
-        Style::default().fg(Color::DarkGray),
+        strings.loading_cancel,
+        Style::default().fg(app.theme.dim),
     )));
 
     let paragraph = Paragraph::new(lines).block(block);
@@ -120,20 +263,31 @@ fn render_loading(frame: &mut Frame, area: Rect, status: &str, step_titles: &[St
     frame.render_widget(paragraph, dialog_area);
 }
 
-fn render_error(frame: &mut Frame, area: Rect, message: &str) {
+/// Current frame of the braille spinner, advancing every 100ms.
+pub(super) fn spinner_frame() -> &'static str {
+    let spinner_frames = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+    let spinner_idx = (std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_millis() / 100)
+        .unwrap_or(0) as usize)
+        % spinner_frames.len();
+    spinner_frames[spinner_idx]
+}
+
+fn render_error(frame: &mut Frame, area: Rect, message: &str, app: &App) {
     let block = Block::default()
-        .title(" Error ")
+        .title(app.strings.error_title)
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Red));
+        .border_style(Style::default().fg(app.theme.error));
 
     let text = vec![
         Line::from(""),
-        Line::from(Span::styled(message, Style::default().fg(Color::Red))),
+        Line::from(Span::styled(message, Style::default().fg(app.theme.error))),
         Line::from(""),
         Line::from(""),
         Line::from(Span::styled(
-            "Press 'r' to retry or 'q' to quit",
-            Style::default().fg(Color::DarkGray),
+            app.strings.error_retry,
+            Style::default().fg(app.theme.dim),
         )),
     ];
 
@@ -148,6 +302,49 @@ fn render_error(frame: &mut Frame, area: Rect, message: &str) {
     );
 }
 
+/// Asks for a bigger terminal instead of squeezing the panes into it.
+fn render_too_small(frame: &mut Frame, area: Rect, app: &App) {
+    let s = app.strings;
+    let text = vec![
+        Line::from(Span::styled(
+            s.too_small_title,
+            Style::default()
+                .fg(app.theme.accent)
+                .add_modifier(Modifier::BOLD),
+        )),
+        Line::from(format!(
+            "{} {MIN_TERMINAL_WIDTH}×{MIN_TERMINAL_HEIGHT}",
+            s.too_small_need
+        )),
+        Line::from(Span::styled(
+            format!("({} {}×{})", s.too_small_now, area.width, area.height),
+            Style::default().fg(app.theme.dim),
+        )),
+    ];
+    // Vertically centered when there's room for it
+    let top = area.height.saturating_sub(text.len() as u16) / 2;
+    let area = Rect {
+        y: area.y + top,
+        height: area.height - top,
+        ..area
+    };
+    frame.render_widget(
+        Paragraph::new(text)
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: true }),
+        area,
+    );
+}
+
+/// Color a flag of `severity` is drawn in.
+fn severity_color(severity: Severity, theme: &Theme) -> Color {
+    match severity {
+        Severity::Blocker => theme.priority_critical,
+        Severity::Question => theme.accent,
+        Severity::Nit => theme.dim,
+    }
+}
+
 fn centered_rect(percent_x: u16, percent_y: u16, area: Rect) -> Rect {
     let popup_layout = Layout::default()
         .direction(Direction::Vertical)
@@ -175,6 +372,7 @@ fn render_main(frame: &mut Frame, area: Rect, app: &App) {
             Pane::Minimap => minimap::render(frame, area, app),
             Pane::Chat => explanation::render(frame, area, app),
             Pane::Diff => diff_viewer::render(frame, area, app),
+            Pane::Files => file_tree::render(frame, area, app),
         }
         return;
     }
@@ -183,12 +381,25 @@ fn render_main(frame: &mut Frame, area: Rect, app: &App) {
         .direction(Direction::Horizontal)
         .constraints([
             Constraint::Percentage(app.layout.left_pane_percent),
-            Constraint::Percentage(100 - app.layout.left_pane_percent),
+            Constraint::Fill(1),
+            Constraint::Length(file_tree_width(area.width, app)),
         ])
         .split(area);
 
     render_left_pane(frame, chunks[0], app);
     diff_viewer::render(frame, chunks[1], app);
+    if app.layout.file_tree {
+        file_tree::render(frame, chunks[2], app);
+    }
+}
+
+/// Columns taken by the file tree pane on a screen `width` wide.
+pub fn file_tree_width(width: u16, app: &App) -> u16 {
+    if app.layout.file_tree {
+        (width as u32 * FILE_TREE_PERCENT as u32 / 100) as u16
+    } else {
+        0
+    }
 }
 
 fn render_left_pane(frame: &mut Frame, area: Rect, app: &App) {
@@ -204,26 +415,30 @@ fn render_left_pane(frame: &mut Frame, area: Rect, app: &App) {
     explanation::render(frame, chunks[1], app);
 }
 
-fn render_help_modal(frame: &mut Frame, area: Rect) {
+fn render_help_modal(frame: &mut Frame, area: Rect, app: &App) {
+    use crate::keymap::Action::{self, *};
     use ratatui::widgets::Clear;
 
+    let s = app.strings;
+
     let dialog_area = centered_rect(HELP_DIALOG_WIDTH, HELP_DIALOG_HEIGHT, area);
 
     frame.render_widget(Clear, dialog_area);
 
     let block = Block::default()
-        .title(" Keybindings ")
+        .title(s.help_title)
         .title_alignment(Alignment::Center)
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Cyan))
+        .border_style(Style::default().fg(app.theme.accent))
         .padding(Padding::new(2, 2, 1, 1));
 
-    let dim = Style::default().fg(Color::DarkGray);
-    let key_style = Style::default().fg(Color::Yellow);
+    let dim = Style::default().fg(app.theme.dim);
+    let key_style = Style::default().fg(app.theme.key);
     let heading = Style::default()
-        .fg(Color::Cyan)
+        .fg(app.theme.accent)
         .add_modifier(Modifier::BOLD);
 
+    let keys = |actions: &[Action]| app.keymap.label(actions);
     let help_line = |key: &str, desc: &str| -> Line<'static> {
         Line::from(vec![
             Span::styled(format!("  {key:<20}"), key_style),
@@ -232,41 +447,91 @@ fn render_help_modal(frame: &mut Frame, area: Rect) {
     };
 
     let text = vec![
-        Line::from(Span::styled("Navigation", heading)),
-        help_line("↑/↓  Ctrl+n/p", "Next/previous step"),
-        help_line("j/k", "Scroll diff"),
-        help_line("Ctrl+d/u", "Half-page scroll"),
-        help_line("gg/G", "Top/bottom of diff"),
-        help_line("Tab/Shift+Tab", "Cycle panes"),
-        help_line("Ctrl+h/j/k/l", "Move between panes"),
+        Line::from(Span::styled(s.heading_navigation, heading)),
+        help_line(&keys(&[PrevStep, NextStep]), s.help_next_prev_step),
+        help_line(&keys(&[ScrollDown, ScrollUp]), s.help_scroll_diff),
+        help_line(&keys(&[HalfPageDown, HalfPageUp]), s.help_half_page),
+        help_line(&keys(&[Top, Bottom]), s.help_top_bottom),
+        help_line(&keys(&[NextPane, PrevPane]), s.help_cycle_panes),
+        help_line(
+            &keys(&[FocusLeft, FocusDown, FocusUp, FocusRight]),
+            s.help_move_panes,
+        ),
+        help_line(&keys(&[PrevSection, NextSection]), s.help_sections),
         Line::from(""),
-        Line::from(Span::styled("Actions", heading)),
-        help_line("Enter", "Mark step & advance"),
-        help_line("x", "Toggle step reviewed"),
-        help_line("+", "Split step (rechunk)"),
-        help_line("i", "Enter chat mode"),
-        help_line("z", "Toggle zoom"),
+        Line::from(Span::styled(s.heading_actions, heading)),
+        help_line(&keys(&[CompleteStep]), s.help_mark_advance),
+        help_line(&keys(&[MarkReviewed]), s.help_toggle_reviewed),
+        help_line(&keys(&[PrevHunk, NextHunk]), s.help_hunk_cursor),
+        help_line(&keys(&[PrevFile, NextFile]), s.help_file_cursor),
+        help_line(&keys(&[ToggleHunk]), s.help_toggle_hunk),
+        help_line(&keys(&[VisualSelect]), s.help_visual_select),
+        help_line(&keys(&[LineCursor]), s.help_line_cursor),
+        help_line(&keys(&[QuoteSelection]), s.help_quote_selection),
+        help_line(&keys(&[Yank]), s.help_yank),
+        help_line(&keys(&[SplitStep]), s.help_split),
+        help_line(&keys(&[Regenerate]), s.help_regenerate),
+        help_line(&keys(&[RegenerateSummary]), s.help_regenerate_summary),
+        help_line(&keys(&[Persona]), s.help_persona),
+        help_line(&keys(&[StepOrder]), s.help_step_order),
+        help_line(&keys(&[MoveStepDown, MoveStepUp]), s.help_move_step),
+        help_line(&keys(&[MergeStep]), s.help_merge_step),
+        help_line(&keys(&[SplitHunks]), s.help_split_hunks),
+        help_line(&keys(&[MoveHunk]), s.help_move_hunk),
+        help_line(&keys(&[FileView]), s.help_file_view),
+        help_line(&keys(&[PriorityFilter]), s.help_priority_filter),
+        help_line(&keys(&[Parts]), s.help_parts),
+        help_line(&keys(&[Related]), s.help_related),
+        help_line(&keys(&[QuickActions]), s.help_quick_actions),
+        help_line(&keys(&[Feedback]), s.help_feedback),
+        help_line(&keys(&[Notes]), s.help_note),
+        help_line(&keys(&[Flag]), s.help_flag),
+        help_line(&keys(&[PostReview]), s.help_post_review),
+        help_line(&keys(&[Chat]), s.help_chat),
+        help_line(&keys(&[WholeChangeChat]), s.help_whole_change_chat),
+        help_line(&keys(&[FollowUp1, FollowUp2, FollowUp3]), s.help_follow_ups),
+        help_line(&keys(&[Zoom]), s.help_zoom),
+        help_line(&keys(&[Fold, FoldFile]), s.help_fold),
+        help_line(&keys(&[UnfoldAll, FoldAll]), s.help_fold_all),
+        help_line(&keys(&[FileTree]), s.help_file_tree),
+        help_line(&keys(&[OpenInBrowser]), s.help_open_browser),
+        help_line(&keys(&[MouseCapture]), s.help_mouse_capture),
+        help_line(&keys(&[ExportMarkdown]), s.help_export_markdown),
+        help_line(&keys(&[ExportHtml]), s.help_export_html),
+        help_line(&keys(&[ExportJson]), s.help_export_json),
+        help_line(&keys(&[RecordReview]), s.help_record),
+        help_line(&keys(&[Usage]), s.help_usage),
         Line::from(""),
-        Line::from(Span::styled("Search", heading)),
-        help_line("/", "Start search"),
-        help_line("n/p", "Next/prev match"),
-        help_line("Esc", "Clear search"),
+        Line::from(Span::styled(s.heading_search, heading)),
+        help_line(&keys(&[Search]), s.help_start_search),
+        help_line(&keys(&[SearchAll]), s.help_search_all),
+        help_line(&keys(&[FindStep]), s.help_find_step),
+        help_line(&keys(&[NextMatch, PrevMatch]), s.help_next_prev_match),
+        help_line(&keys(&[ClearSearch]), s.help_clear_search),
         Line::from(""),
-        Line::from(Span::styled("Press any key to close", dim)),
+        Line::from(Span::styled(s.help_close, dim)),
     ];
 
     let paragraph = Paragraph::new(text).block(block);
     frame.render_widget(paragraph, dialog_area);
 }
 
-fn help(key: &str, action: &str) -> [Span<'static>; 2] {
+fn help(key: &str, action: &str, theme: &Theme) -> [Span<'static>; 2] {
     [
-        Span::styled(format!(" {key} "), Style::default().fg(Color::Yellow)),
+        Span::styled(format!(" {key} "), Style::default().fg(theme.key)),
         Span::raw(format!("{action} ")),
     ]
 }
 
-fn format_token_count(n: u32) -> String {
+pub(super) fn format_cost(cost: f64) -> String {
+    if cost < 0.01 {
+        "<$0.01".to_string()
+    } else {
+        format!("~${:.2}", cost)
+    }
+}
+
+pub(super) fn format_token_count(n: u32) -> String {
     if n >= 1000 {
         format!("{:.1}k", n as f64 / 1000.0)
     } else {
@@ -275,18 +540,22 @@ fn format_token_count(n: u32) -> String {
 }
 
 fn render_help_bar(frame: &mut Frame, area: Rect, app: &App) {
+    use crate::keymap::Action::{self, *};
+
+    let keys = |actions: &[Action]| app.keymap.label(actions);
+    let theme = &app.theme;
     let is_zoomed = app.layout.is_zoomed();
+    let s = app.strings;
 
     let usage = &app.session.token_usage;
     let usage_text = if usage.input_tokens > 0 || usage.output_tokens > 0 {
-        let cost = usage.cost_usd();
-        let cost_str = if cost < 0.01 {
-            "<$0.01".to_string()
-        } else {
-            format!("~${:.2}", cost)
-        };
+        let cost_str = app
+            .session
+            .cost_usd()
+            .map(|cost| format!(" {}", format_cost(cost)))
+            .unwrap_or_default();
         Some(format!(
-            " ↑{} ↓{} {} ",
+            " ↑{} ↓{}{} ",
             format_token_count(usage.input_tokens),
             format_token_count(usage.output_tokens),
             cost_str,
@@ -297,8 +566,8 @@ fn render_help_bar(frame: &mut Frame, area: Rect, app: &App) {
 
     let help_text = if app.quit_pending {
         Line::from(Span::styled(
-            "Press Ctrl+C again to quit",
-            Style::default().fg(Color::White),
+            s.quit_confirm,
+            Style::default().fg(app.theme.text),
         ))
     } else {
         match app.layout.active_pane {
@@ -307,63 +576,94 @@ fn render_help_bar(frame: &mut Frame, area: Rect, app: &App) {
                     // Scrollback mode (works in both vim and non-vim)
                     let mut spans = vec![Span::styled(
                         if is_zoomed {
-                            "-- ZOOMED | SCROLLBACK -- "
+                            s.mode_zoomed_scrollback
                         } else {
-                            "-- SCROLLBACK -- "
+                            s.mode_scrollback
                         },
                         Style::default().fg(if is_zoomed {
-                            Color::Cyan
+                            app.theme.accent
                         } else {
-                            Color::DarkGray
+                            app.theme.dim
                         }),
                     )];
-                    spans.extend(help("Ctrl+n/p", "scroll"));
+                    spans.extend(help("Ctrl+n/p", s.act_scroll, theme));
+                    spans.extend(help("y", s.act_copy, theme));
                     if is_zoomed {
-                        spans.extend(help("z", "unzoom"));
+                        spans.extend(help("z", s.act_unzoom, theme));
                     }
-                    spans.extend(help("Esc", "exit"));
+                    spans.extend(help("Esc", s.act_exit, theme));
                     Line::from(spans)
                 } else if app.editor.vim_enabled && app.editor.vim_mode == VimInputMode::Insert {
                     // Vim insert mode
                     Line::from(Span::styled(
                         if is_zoomed {
-                            "-- ZOOMED | INSERT --"
+                            s.mode_zoomed_insert
                         } else {
-                            "-- INSERT --"
+                            s.mode_insert
                         },
                         Style::default().fg(if is_zoomed {
-                            Color::Cyan
+                            app.theme.accent
                         } else {
-                            Color::DarkGray
+                            app.theme.dim
                         }),
                     ))
+                } else if app.editor.vim_enabled
+                    && matches!(
+                        app.editor.vim_mode,
+                        VimInputMode::Visual | VimInputMode::VisualLine
+                    )
+                {
+                    // Vim visual mode
+                    let mut spans = vec![];
+                    if is_zoomed {
+                        spans.push(Span::styled(
+                            s.mode_zoomed,
+                            Style::default().fg(app.theme.accent),
+                        ));
+                    }
+                    let mode = if app.editor.vim_mode == VimInputMode::VisualLine {
+                        s.mode_visual_line
+                    } else {
+                        s.mode_visual
+                    };
+                    spans.push(Span::styled(mode, Style::default().fg(app.theme.dim)));
+                    spans.extend(help("y", s.act_copy, theme));
+                    spans.extend(help("d", s.act_delete, theme));
+                    spans.extend(help("c", s.act_change, theme));
+                    spans.extend(help("Esc", s.act_exit, theme));
+                    Line::from(spans)
                 } else if app.editor.vim_enabled {
                     // Vim normal mode
                     let mut spans = vec![];
                     if is_zoomed {
                         spans.push(Span::styled(
-                            "-- ZOOMED -- ",
-                            Style::default().fg(Color::Cyan),
+                            s.mode_zoomed,
+                            Style::default().fg(app.theme.accent),
                         ));
                     }
-                    spans.extend(help("Ctrl+n/p", "scroll"));
-                    spans.extend(help("z", if is_zoomed { "unzoom" } else { "zoom" }));
-                    spans.extend(help("?", "help"));
-                    spans.extend(help("Ctrl+C", "quit"));
+                    spans.extend(help("Ctrl+n/p", s.act_scroll, theme));
+                    spans.extend(help("Ctrl+t", s.act_context, theme));
+                    spans.extend(help("Ctrl+g", s.act_whole_change, theme));
+                    let zoom = if is_zoomed { s.act_unzoom } else { s.act_zoom };
+                    spans.extend(help("z", zoom, theme));
+                    spans.extend(help("?", s.act_help, theme));
+                    spans.extend(help("Ctrl+C", s.act_quit, theme));
                     Line::from(spans)
                 } else {
                     // Non-vim mode
                     let mut spans = vec![];
                     if is_zoomed {
                         spans.push(Span::styled(
-                            "-- ZOOMED -- ",
-                            Style::default().fg(Color::Cyan),
+                            s.mode_zoomed,
+                            Style::default().fg(app.theme.accent),
                         ));
                     }
-                    spans.extend(help("Ctrl+n/p", "scroll"));
-                    spans.extend(help("Tab", "switch pane"));
-                    spans.extend(help("?", "help"));
-                    spans.extend(help("Ctrl+C", "quit"));
+                    spans.extend(help("Ctrl+n/p", s.act_scroll, theme));
+                    spans.extend(help("Ctrl+t", s.act_context, theme));
+                    spans.extend(help("Ctrl+g", s.act_whole_change, theme));
+                    spans.extend(help("Tab", s.act_switch_pane, theme));
+                    spans.extend(help("?", s.act_help, theme));
+                    spans.extend(help("Ctrl+C", s.act_quit, theme));
                     Line::from(spans)
                 }
             }
@@ -371,23 +671,26 @@ fn render_help_bar(frame: &mut Frame, area: Rect, app: &App) {
                 let mut spans = vec![];
                 if is_zoomed {
                     spans.push(Span::styled(
-                        "-- ZOOMED -- ",
-                        Style::default().fg(Color::Cyan),
+                        s.mode_zoomed,
+                        Style::default().fg(app.theme.accent),
                     ));
                 }
-                spans.extend(help("↑/↓", "switch step"));
-                spans.extend(help("Enter", "mark reviewed"));
-                spans.extend(help("z", if is_zoomed { "unzoom" } else { "zoom" }));
-                spans.extend(help("?", "help"));
-                spans.extend(help("Ctrl+C", "quit"));
+                spans.extend(help(&keys(&[PrevStep, NextStep]), s.act_switch_step, theme));
+                spans.extend(help(&keys(&[CompleteStep]), s.act_mark_reviewed, theme));
+                spans.extend(help(&keys(&[PriorityFilter]), s.act_filter, theme));
+                spans.extend(help(&keys(&[MoveStepDown, MoveStepUp]), s.act_move, theme));
+                let zoom = if is_zoomed { s.act_unzoom } else { s.act_zoom };
+                spans.extend(help(&keys(&[Zoom]), zoom, theme));
+                spans.extend(help(&keys(&[Help]), s.act_help, theme));
+                spans.extend(help("Ctrl+C", s.act_quit, theme));
                 Line::from(spans)
             }
             Pane::Diff => {
                 let mut spans = vec![];
                 if is_zoomed {
                     spans.push(Span::styled(
-                        "-- ZOOMED -- ",
-                        Style::default().fg(Color::Cyan),
+                        s.mode_zoomed,
+                        Style::default().fg(app.theme.accent),
                     ));
                 }
 
@@ -395,38 +698,80 @@ fn render_help_bar(frame: &mut Frame, area: Rect, app: &App) {
                     let match_display = app.search.match_count_display();
                     spans.push(Span::styled(
                         format!("[{match_display}] "),
-                        Style::default().fg(Color::Yellow),
+                        Style::default().fg(app.theme.key),
                     ));
-                    spans.extend(help("n/p", "next/prev"));
-                    spans.extend(help("Esc", "clear"));
+                    spans.extend(help(&keys(&[NextMatch, PrevMatch]), s.act_next_prev, theme));
+                    spans.extend(help(&keys(&[ClearSearch]), s.act_clear, theme));
                 } else {
-                    spans.extend(help("↑/↓", "step"));
-                    spans.extend(help("/", "search"));
+                    spans.extend(help(&keys(&[PrevStep, NextStep]), s.act_step, theme));
+                    spans.extend(help(&keys(&[Search]), s.act_search, theme));
                 }
 
-                spans.extend(help("j/k", "scroll"));
-                spans.extend(help("+", "split step"));
-                spans.extend(help("z", if is_zoomed { "unzoom" } else { "zoom" }));
-                spans.extend(help("?", "help"));
-                spans.extend(help("Ctrl+C", "quit"));
+                spans.extend(help(&keys(&[ScrollDown, ScrollUp]), s.act_scroll, theme));
+                spans.extend(help(&keys(&[SplitStep]), s.act_split_step, theme));
+                let zoom = if is_zoomed { s.act_unzoom } else { s.act_zoom };
+                spans.extend(help(&keys(&[Zoom]), zoom, theme));
+                spans.extend(help(&keys(&[Help]), s.act_help, theme));
+                spans.extend(help("Ctrl+C", s.act_quit, theme));
+                Line::from(spans)
+            }
+            Pane::Files => {
+                let mut spans = vec![];
+                if is_zoomed {
+                    spans.push(Span::styled(
+                        s.mode_zoomed,
+                        Style::default().fg(app.theme.accent),
+                    ));
+                }
+                spans.extend(help(&keys(&[ScrollDown, ScrollUp]), s.act_scroll, theme));
+                spans.extend(help(&keys(&[CompleteStep]), s.global_search_jump, theme));
+                spans.extend(help(&keys(&[FileTree]), s.act_exit, theme));
+                spans.extend(help(&keys(&[Help]), s.act_help, theme));
+                spans.extend(help("Ctrl+C", s.act_quit, theme));
                 Line::from(spans)
             }
         }
     };
 
+    // Size of the context sent with the latest chat question, and whether
+    // its conversation had to be trimmed to fit
+    let context_text = app.session.chat_context_tokens.map(|tokens| {
+        let mut text = format!(
+            "{} {} ~{}",
+            s.chat_context,
+            s.chat_context_label(app.session.chat_context),
+            format_token_count(tokens as u32)
+        );
+        if app.session.chat_history_omitted > 0 {
+            text.push_str(&format!(", {}", s.chat_trimmed));
+        }
+        text
+    });
+    let usage_text = match (context_text, usage_text) {
+        (Some(context), Some(usage)) => Some(format!(" {} ·{}", context, usage)),
+        (Some(context), None) => Some(format!(" {} ", context)),
+        (None, usage) => usage,
+    };
+
+    let usage_text = match (&app.session.status_message, usage_text) {
+        (Some(status), Some(usage)) => Some(format!(" {} ·{}", status, usage)),
+        (Some(status), None) => Some(format!(" {} ", status)),
+        (None, usage) => usage,
+    };
+
     if let Some(usage_str) = usage_text {
-        let usage_width = usage_str.len() as u16;
+        let usage_width = usage_str.chars().count() as u16;
         let chunks = Layout::default()
             .direction(Direction::Horizontal)
-            .constraints([
-                Constraint::Min(1),
-                Constraint::Length(usage_width),
-            ])
+            .constraints([Constraint::Min(1), Constraint::Length(usage_width)])
             .split(area);
 
         frame.render_widget(Paragraph::new(help_text), chunks[0]);
         frame.render_widget(
-            Paragraph::new(Span::styled(usage_str, Style::default().fg(Color::DarkGray))),
+            Paragraph::new(Span::styled(
+                usage_str,
+                Style::default().fg(app.theme.dim),
+            )),
             chunks[1],
         );
     } else {
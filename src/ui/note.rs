@@ -0,0 +1,92 @@
+use ratatui::{
+    Frame,
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Padding, Paragraph, Wrap},
+};
+
+use crate::app::{App, NoteEditor};
+use crate::constants::{NOTE_DIALOG_HEIGHT, NOTE_DIALOG_WIDTH};
+
+use super::centered_rect;
+
+pub fn render(frame: &mut Frame, area: Rect, note_editor: &NoteEditor, app: &App) {
+    let s = app.strings;
+    let Some(step) = app.session.walkthrough.steps.get(note_editor.step) else {
+        return;
+    };
+    let dialog_area = centered_rect(NOTE_DIALOG_WIDTH, NOTE_DIALOG_HEIGHT, area);
+    frame.render_widget(Clear, dialog_area);
+
+    let block = Block::default()
+        .title(s.note_title)
+        .title_alignment(Alignment::Center)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(app.theme.accent))
+        .padding(Padding::new(2, 2, 1, 1));
+    let inner = block.inner(dialog_area);
+    frame.render_widget(block, dialog_area);
+
+    let sections = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(2), // Step title
+            Constraint::Min(1),    // Existing notes
+            Constraint::Length(1), // New note label
+            Constraint::Length(4), // Editor
+            Constraint::Length(2), // Hint
+            Constraint::Length(1), // Help bar
+        ])
+        .split(inner);
+
+    let dim = Style::default().fg(app.theme.dim);
+    let title = Paragraph::new(Span::styled(step.title.as_str(), dim));
+    frame.render_widget(title, sections[0]);
+
+    let notes: Vec<Line> = if step.notes.is_empty() {
+        vec![Line::from(Span::styled(s.note_empty, dim))]
+    } else {
+        step.notes
+            .iter()
+            .flat_map(|note| {
+                note.text.lines().enumerate().map(|(i, line)| {
+                    let prefix = if i == 0 { "✎ " } else { "  " };
+                    Line::from(vec![
+                        Span::styled(prefix, Style::default().fg(app.theme.chat_note)),
+                        Span::raw(line.to_string()),
+                    ])
+                })
+            })
+            .collect()
+    };
+    frame.render_widget(
+        Paragraph::new(notes).wrap(Wrap { trim: false }),
+        sections[1],
+    );
+
+    frame.render_widget(Paragraph::new(s.note_new), sections[2]);
+    let editor_block = Block::default()
+        .borders(Borders::LEFT)
+        .border_style(Style::default().fg(app.theme.accent));
+    let editor_area = editor_block.inner(sections[3]);
+    frame.render_widget(editor_block, sections[3]);
+    frame.render_widget(&note_editor.editor.textarea, editor_area);
+
+    let hint = Paragraph::new(Span::styled(s.note_hint, dim)).wrap(Wrap { trim: false });
+    frame.render_widget(hint, sections[4]);
+
+    let key_style = Style::default().fg(app.theme.key);
+    let help_line = Paragraph::new(Line::from(vec![
+        Span::styled(" Enter ", key_style),
+        Span::raw(format!("{} ", s.note_add)),
+        Span::styled(" Shift+Enter ", key_style),
+        Span::raw(format!("{} ", s.note_newline)),
+        Span::styled(" Ctrl+d ", key_style),
+        Span::raw(format!("{} ", s.note_delete)),
+        Span::styled(" Esc ", key_style),
+        Span::raw(s.act_exit),
+    ]))
+    .alignment(Alignment::Center);
+    frame.render_widget(help_line, sections[5]);
+}
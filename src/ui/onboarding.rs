@@ -0,0 +1,186 @@
+use ratatui::{
+    Frame,
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Padding, Paragraph, Wrap},
+};
+
+use crate::colors::Theme;
+use crate::constants::{SETUP_DIALOG_HEIGHT, SETUP_DIALOG_WIDTH};
+use crate::onboarding::{KeyStorage, Onboarding, OnboardingStep, VIM_MODES};
+use crate::settings::{Provider, VimMode};
+
+use super::centered_rect;
+
+pub fn render(frame: &mut Frame, area: Rect, onboarding: &Onboarding, theme: &Theme) {
+    let dialog_area = centered_rect(SETUP_DIALOG_WIDTH, SETUP_DIALOG_HEIGHT, area);
+    let block = Block::default()
+        .title(format!(
+            " Welcome to docent · {}/3 ",
+            onboarding.step_number()
+        ))
+        .title_alignment(Alignment::Center)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.accent))
+        .padding(Padding::new(2, 2, 1, 1));
+
+    let inner = block.inner(dialog_area);
+    frame.render_widget(block, dialog_area);
+
+    let sections = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(1),    // Step content
+            Constraint::Length(1), // Error
+            Constraint::Length(1), // Help bar
+        ])
+        .split(inner);
+
+    let lines = match onboarding.step {
+        OnboardingStep::Provider => provider_lines(onboarding, theme),
+        OnboardingStep::ApiKey => api_key_lines(onboarding, theme),
+        OnboardingStep::EditorMode => editor_mode_lines(onboarding, theme),
+    };
+    frame.render_widget(
+        Paragraph::new(lines).wrap(Wrap { trim: false }),
+        sections[0],
+    );
+
+    if let Some(error) = &onboarding.error {
+        let error = Paragraph::new(Span::styled(
+            error.as_str(),
+            Style::default().fg(theme.error),
+        ));
+        frame.render_widget(error, sections[1]);
+    }
+
+    let key = |k: &'static str| Span::styled(k, Style::default().fg(theme.key));
+    let mut help_spans = vec![key(" Enter "), Span::raw("continue ")];
+    if onboarding.step != OnboardingStep::Provider {
+        help_spans.extend([key(" Esc "), Span::raw("back ")]);
+    }
+    if onboarding.step != OnboardingStep::ApiKey {
+        help_spans.extend([key(" q "), Span::raw("quit")]);
+    }
+    let help_line = Paragraph::new(Line::from(help_spans)).alignment(Alignment::Center);
+    frame.render_widget(help_line, sections[2]);
+}
+
+fn heading(text: &str, theme: &Theme) -> Line<'static> {
+    Line::from(Span::styled(
+        text.to_string(),
+        Style::default()
+            .fg(theme.accent)
+            .add_modifier(Modifier::BOLD),
+    ))
+}
+
+fn hint(text: &str, theme: &Theme) -> Line<'static> {
+    Line::from(Span::styled(
+        text.to_string(),
+        Style::default().fg(theme.dim),
+    ))
+}
+
+/// A radio-button row: cursor, bullet, label, and a dim description.
+fn option(selected: bool, label: &str, description: &str, theme: &Theme) -> Line<'static> {
+    let (cursor, bullet, style) = if selected {
+        (
+            "> ",
+            "●",
+            Style::default().fg(theme.text).add_modifier(Modifier::BOLD),
+        )
+    } else {
+        ("  ", "○", Style::default().fg(theme.dim))
+    };
+    Line::from(vec![
+        Span::styled(cursor, Style::default().fg(theme.accent)),
+        Span::styled(format!("{bullet} {label}"), style),
+        Span::styled(format!("  {description}"), Style::default().fg(theme.dim)),
+    ])
+}
+
+fn provider_lines(onboarding: &Onboarding, theme: &Theme) -> Vec<Line<'static>> {
+    let mut lines = vec![
+        hint("Let's get you set up. This only happens once.", theme),
+        Line::from(""),
+        heading("Provider", theme),
+    ];
+    for provider in Provider::ALL {
+        lines.push(option(
+            provider == onboarding.provider,
+            provider.display_name(),
+            "",
+            theme,
+        ));
+    }
+    lines
+}
+
+fn api_key_lines(onboarding: &Onboarding, theme: &Theme) -> Vec<Line<'static>> {
+    let provider = onboarding.provider;
+    let display = if onboarding.api_key.is_empty() {
+        Span::styled(provider.key_placeholder(), Style::default().fg(theme.dim))
+    } else {
+        Span::styled(
+            super::setup::mask_api_key(&onboarding.api_key),
+            Style::default().fg(theme.text),
+        )
+    };
+
+    vec![
+        heading(&format!("{} API key", provider.display_name()), theme),
+        Line::from(vec![
+            Span::styled("> ", Style::default().fg(theme.accent)),
+            display,
+            Span::styled("█", Style::default().fg(theme.text)),
+        ]),
+        hint(
+            &format!(
+                "Paste your key, or quit and set {} to skip this step.",
+                provider.env_var()
+            ),
+            theme,
+        ),
+        Line::from(""),
+        heading("Keep it", theme),
+        option(
+            onboarding.key_storage == KeyStorage::SettingsFile,
+            "Saved",
+            "in ~/.docent/settings.json",
+            theme,
+        ),
+        option(
+            onboarding.key_storage == KeyStorage::Session,
+            "This session only",
+            "ask again next time",
+            theme,
+        ),
+    ]
+}
+
+fn editor_mode_lines(onboarding: &Onboarding, theme: &Theme) -> Vec<Line<'static>> {
+    let mut lines = vec![
+        heading("Chat input keybindings", theme),
+        hint(
+            "Change later under \"editor\" in ~/.docent/settings.json.",
+            theme,
+        ),
+        Line::from(""),
+    ];
+    for mode in VIM_MODES {
+        let (label, description) = match mode {
+            VimMode::Auto => ("Auto", "vim if ~/.inputrc sets editing-mode vi"),
+            VimMode::Enabled => ("Vim", "modal editing with Esc / i"),
+            VimMode::Disabled => ("Emacs", "standard readline-style editing"),
+        };
+        lines.push(option(
+            mode == onboarding.vim_mode,
+            label,
+            description,
+            theme,
+        ));
+    }
+    lines
+}
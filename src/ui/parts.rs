@@ -0,0 +1,181 @@
+use ratatui::{
+    Frame,
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Padding, Paragraph},
+};
+
+use crate::app::App;
+use crate::colors::Theme;
+use crate::constants::{
+    PARTS_DIALOG_HEIGHT, PARTS_DIALOG_WIDTH, SPLIT_DIALOG_HEIGHT, SPLIT_DIALOG_WIDTH,
+};
+use crate::parts::{SPLIT_CHOICES, SplitChoice, SplitOffer};
+
+use super::centered_rect;
+
+fn dialog_block(title: String, theme: &Theme) -> Block<'static> {
+    Block::default()
+        .title(title)
+        .title_alignment(Alignment::Center)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.accent))
+        .padding(Padding::new(2, 2, 1, 1))
+}
+
+fn key(k: &'static str, theme: &Theme) -> Span<'static> {
+    Span::styled(k, Style::default().fg(theme.key))
+}
+
+/// The offer to split an oversized diff, shown before generation.
+pub fn render_split_offer(frame: &mut Frame, area: Rect, offer: &SplitOffer, app: &App) {
+    let s = app.strings;
+    let dialog_area = centered_rect(SPLIT_DIALOG_WIDTH, SPLIT_DIALOG_HEIGHT, area);
+    let block = dialog_block(s.split_title.to_string(), &app.theme);
+    let inner = block.inner(dialog_area);
+    frame.render_widget(block, dialog_area);
+
+    let sections = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(2), // Size
+            Constraint::Min(1),    // Choices
+            Constraint::Length(1), // Help bar
+        ])
+        .split(inner);
+
+    let size = Paragraph::new(Span::styled(
+        format!(
+            "{} {} ({} {})",
+            offer.changed_lines, s.split_changed_lines, s.split_limit, offer.max_lines
+        ),
+        Style::default().fg(app.theme.dim),
+    ));
+    frame.render_widget(size, sections[0]);
+
+    if offer.pending {
+        let pending = Paragraph::new(Line::from(vec![
+            Span::styled(
+                super::spinner_frame(),
+                Style::default().fg(app.theme.accent),
+            ),
+            Span::raw(format!(" {}", s.split_partitioning)),
+        ]));
+        frame.render_widget(pending, sections[1]);
+        return;
+    }
+
+    let lines: Vec<Line> = SPLIT_CHOICES
+        .iter()
+        .enumerate()
+        .map(|(i, choice)| {
+            let label = match choice {
+                SplitChoice::ByDirectory => s.split_by_directory,
+                SplitChoice::ByModel => s.split_by_model,
+                SplitChoice::Whole => s.split_whole,
+            };
+            if i == offer.cursor {
+                Line::from(Span::styled(
+                    format!("> {label}"),
+                    Style::default()
+                        .fg(app.theme.accent)
+                        .add_modifier(Modifier::BOLD),
+                ))
+            } else {
+                Line::from(format!("  {label}"))
+            }
+        })
+        .collect();
+    frame.render_widget(Paragraph::new(lines), sections[1]);
+
+    let help_line = Paragraph::new(Line::from(vec![
+        key(" j/k ", &app.theme),
+        Span::raw(format!("{} ", s.act_scroll)),
+        key(" Enter ", &app.theme),
+        Span::raw(format!("{} ", s.setup_start)),
+        key(" Esc ", &app.theme),
+        Span::raw(s.files_back),
+    ]))
+    .alignment(Alignment::Center);
+    frame.render_widget(help_line, sections[2]);
+}
+
+/// The parts picker overlay, listing each part's progress.
+pub fn render_picker(frame: &mut Frame, area: Rect, cursor: usize, app: &App) {
+    let s = app.strings;
+    let session = &app.session;
+    let dialog_area = centered_rect(PARTS_DIALOG_WIDTH, PARTS_DIALOG_HEIGHT, area);
+    frame.render_widget(Clear, dialog_area);
+
+    let (reviewed, total) = session.overall_progress();
+    let block = dialog_block(
+        format!(
+            "{}({}/{} {}) ",
+            s.parts_title, reviewed, total, s.parts_reviewed
+        ),
+        &app.theme,
+    );
+    let inner = block.inner(dialog_area);
+    frame.render_widget(block, dialog_area);
+
+    let sections = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(1),    // Parts
+            Constraint::Length(1), // Help bar
+        ])
+        .split(inner);
+
+    let items: Vec<ListItem> = session
+        .parts
+        .iter()
+        .enumerate()
+        .map(|(i, part)| {
+            let (reviewed, total) = session.part_progress(i);
+            let marker = if i == session.current_part {
+                "● "
+            } else {
+                "  "
+            };
+            let progress = if total == 0 {
+                s.parts_not_generated.to_string()
+            } else {
+                format!("{}/{}", reviewed, total)
+            };
+            let title_style = if i == cursor {
+                Style::default()
+                    .fg(app.theme.accent)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(app.theme.text)
+            };
+            let done = total > 0 && reviewed == total;
+            ListItem::new(Line::from(vec![
+                Span::styled(marker, Style::default().fg(app.theme.success)),
+                Span::styled(format!("{}. {}", i + 1, part.title), title_style),
+                Span::styled(
+                    format!("  {}", progress),
+                    Style::default().fg(if done {
+                        app.theme.success
+                    } else {
+                        app.theme.dim
+                    }),
+                ),
+            ]))
+        })
+        .collect();
+    let mut state = ListState::default().with_selected(Some(cursor));
+    frame.render_stateful_widget(List::new(items), sections[0], &mut state);
+
+    let help_line = Paragraph::new(Line::from(vec![
+        key(" j/k ", &app.theme),
+        Span::raw(format!("{} ", s.act_scroll)),
+        key(" Enter ", &app.theme),
+        Span::raw(format!("{} ", s.parts_open)),
+        key(" Esc ", &app.theme),
+        Span::raw(s.act_exit),
+    ]))
+    .alignment(Alignment::Center);
+    frame.render_widget(help_line, sections[1]);
+}
@@ -0,0 +1,94 @@
+use ratatui::{
+    Frame,
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Padding, Paragraph},
+};
+
+use crate::app::App;
+use crate::constants::{QUICK_ACTIONS_DIALOG_HEIGHT, QUICK_ACTIONS_DIALOG_WIDTH};
+use crate::quick_actions::{QUICK_ACTIONS, QuickAction, QuickActionMenu};
+
+use super::centered_rect;
+
+pub fn render(frame: &mut Frame, area: Rect, menu: &QuickActionMenu, app: &App) {
+    let s = app.strings;
+    let dialog_area = centered_rect(
+        QUICK_ACTIONS_DIALOG_WIDTH,
+        QUICK_ACTIONS_DIALOG_HEIGHT,
+        area,
+    );
+    frame.render_widget(Clear, dialog_area);
+
+    let block = Block::default()
+        .title(s.quick_title)
+        .title_alignment(Alignment::Center)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(app.theme.accent))
+        .padding(Padding::new(2, 2, 1, 1));
+    let inner = block.inner(dialog_area);
+    frame.render_widget(block, dialog_area);
+
+    let sections = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(2), // Hunk
+            Constraint::Min(1),    // Actions
+            Constraint::Length(1), // Help bar
+        ])
+        .split(inner);
+
+    let hunk = app
+        .session
+        .current_step_data()
+        .and_then(|step| step.hunks.get(menu.hunk));
+    if let Some(hunk) = hunk {
+        let target = Paragraph::new(Span::styled(
+            format!(
+                "{} {} · {}:{}",
+                s.quick_hunk,
+                menu.hunk + 1,
+                hunk.file_path,
+                hunk.start_line
+            ),
+            Style::default().fg(app.theme.dim),
+        ));
+        frame.render_widget(target, sections[0]);
+    }
+
+    let key_style = Style::default().fg(app.theme.key);
+    let lines: Vec<Line> = QUICK_ACTIONS
+        .iter()
+        .enumerate()
+        .map(|(i, action)| {
+            let label = match action {
+                QuickAction::Explain => s.quick_explain,
+                QuickAction::Risks => s.quick_risks,
+                QuickAction::Tests => s.quick_tests,
+                QuickAction::Simplify => s.quick_simplify,
+            };
+            let label_style = if i == menu.cursor {
+                Style::default()
+                    .fg(app.theme.accent)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default()
+            };
+            Line::from(vec![
+                Span::styled(format!(" {} ", action.key()), key_style),
+                Span::styled(format!(" {}", label), label_style),
+            ])
+        })
+        .collect();
+    frame.render_widget(Paragraph::new(lines), sections[1]);
+
+    let help_line = Paragraph::new(Line::from(vec![
+        Span::styled(" Enter ", key_style),
+        Span::raw(format!("{} ", s.quick_run)),
+        Span::styled(" Esc ", key_style),
+        Span::raw(s.act_exit),
+    ]))
+    .alignment(Alignment::Center);
+    frame.render_widget(help_line, sections[2]);
+}
@@ -0,0 +1,90 @@
+use ratatui::{
+    Frame,
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Padding, Paragraph, Wrap},
+};
+
+use crate::app::App;
+use crate::constants::{RECOVERY_DIALOG_HEIGHT, RECOVERY_DIALOG_WIDTH};
+use crate::recovery::{RecoveryOffer, ago};
+
+use super::centered_rect;
+
+pub fn render(frame: &mut Frame, area: Rect, offer: &RecoveryOffer, app: &App) {
+    let s = app.strings;
+    let snapshot = &offer.snapshot;
+    let dialog_area = centered_rect(RECOVERY_DIALOG_WIDTH, RECOVERY_DIALOG_HEIGHT, area);
+    frame.render_widget(Clear, dialog_area);
+
+    let block = Block::default()
+        .title(s.recovery_title)
+        .title_alignment(Alignment::Center)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(app.theme.accent))
+        .padding(Padding::new(2, 2, 1, 1));
+    let inner = block.inner(dialog_area);
+    frame.render_widget(block, dialog_area);
+
+    let sections = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(1),    // Details
+            Constraint::Length(1), // Help bar
+        ])
+        .split(inner);
+
+    let dim = Style::default().fg(app.theme.dim);
+    let reviewed = snapshot.reviewed_steps.iter().filter(|&&r| r).count();
+    let mut lines = vec![
+        Line::from(s.recovery_intro),
+        Line::from(""),
+        Line::from(Span::styled(
+            format!(
+                "{} {} · {}",
+                s.recovery_saved,
+                ago(snapshot.saved_at),
+                snapshot.cwd
+            ),
+            dim,
+        )),
+        Line::from(Span::styled(
+            format!(
+                "{}/{} {}",
+                reviewed,
+                snapshot.walkthrough.step_count(),
+                s.recovery_progress
+            ),
+            dim,
+        )),
+    ];
+    if offer.in_other_directory() {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            s.recovery_other_directory,
+            Style::default().fg(app.theme.error),
+        )));
+    }
+    if let Some(step) = snapshot.walkthrough.get_step(snapshot.current_step) {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            format!("{}. {}", snapshot.current_step + 1, step.title),
+            Style::default().add_modifier(Modifier::BOLD),
+        )));
+    }
+    frame.render_widget(
+        Paragraph::new(lines).wrap(Wrap { trim: false }),
+        sections[0],
+    );
+
+    let key_style = Style::default().fg(app.theme.key);
+    let help_line = Paragraph::new(Line::from(vec![
+        Span::styled(" Enter ", key_style),
+        Span::raw(format!("{} ", s.recovery_restore)),
+        Span::styled(" Esc ", key_style),
+        Span::raw(s.recovery_discard),
+    ]))
+    .alignment(Alignment::Center);
+    frame.render_widget(help_line, sections[1]);
+}
@@ -0,0 +1,83 @@
+use ratatui::{
+    Frame,
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Padding, Paragraph, Wrap},
+};
+
+use crate::app::App;
+use crate::constants::{REGENERATE_DIALOG_HEIGHT, REGENERATE_DIALOG_WIDTH};
+use crate::editor::Editor;
+
+use super::centered_rect;
+
+pub fn render(frame: &mut Frame, area: Rect, editor: &Editor, app: &App) {
+    let s = app.strings;
+    let text = (s.regenerate_title, s.regenerate_intro, s.regenerate_hint);
+    render_prompt(frame, area, editor, app, text);
+}
+
+/// Instructions for rewriting the current step's summary.
+pub fn render_summary(frame: &mut Frame, area: Rect, editor: &Editor, app: &App) {
+    let s = app.strings;
+    let text = (s.summary_title, s.summary_intro, s.summary_hint);
+    render_prompt(frame, area, editor, app, text);
+}
+
+/// Instructions dialog with a title, a label above the editor, and a hint
+/// below it.
+fn render_prompt(
+    frame: &mut Frame,
+    area: Rect,
+    editor: &Editor,
+    app: &App,
+    (title, intro, hint): (&str, &str, &str),
+) {
+    let s = app.strings;
+    let dialog_area = centered_rect(REGENERATE_DIALOG_WIDTH, REGENERATE_DIALOG_HEIGHT, area);
+    frame.render_widget(Clear, dialog_area);
+
+    let block = Block::default()
+        .title(title)
+        .title_alignment(Alignment::Center)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(app.theme.accent))
+        .padding(Padding::new(2, 2, 1, 1));
+    let inner = block.inner(dialog_area);
+    frame.render_widget(block, dialog_area);
+
+    let sections = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1), // Label
+            Constraint::Min(3),    // Editor
+            Constraint::Length(3), // Hint
+            Constraint::Length(1), // Help bar
+        ])
+        .split(inner);
+
+    frame.render_widget(Paragraph::new(intro), sections[0]);
+    let editor_block = Block::default()
+        .borders(Borders::LEFT)
+        .border_style(Style::default().fg(app.theme.accent));
+    let editor_area = editor_block.inner(sections[1]);
+    frame.render_widget(editor_block, sections[1]);
+    frame.render_widget(&editor.textarea, editor_area);
+
+    let dim = Style::default().fg(app.theme.dim);
+    let hint = Paragraph::new(Span::styled(hint, dim)).wrap(Wrap { trim: false });
+    frame.render_widget(hint, sections[2]);
+
+    let key_style = Style::default().fg(app.theme.key);
+    let help_line = Paragraph::new(Line::from(vec![
+        Span::styled(" Enter ", key_style),
+        Span::raw(format!("{} ", s.regenerate_run)),
+        Span::styled(" Shift+Enter ", key_style),
+        Span::raw(format!("{} ", s.note_newline)),
+        Span::styled(" Esc ", key_style),
+        Span::raw(s.act_exit),
+    ]))
+    .alignment(Alignment::Center);
+    frame.render_widget(help_line, sections[3]);
+}
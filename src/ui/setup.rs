@@ -1,7 +1,7 @@
 use ratatui::{
     Frame,
     layout::{Alignment, Constraint, Direction, Layout, Rect},
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, Padding, Paragraph},
 };
@@ -19,7 +19,7 @@ pub fn render(frame: &mut Frame, area: Rect, app: &App) {
         .title(" docent ")
         .title_alignment(Alignment::Center)
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Cyan))
+        .border_style(Style::default().fg(app.theme.accent))
         .padding(Padding::new(2, 2, 1, 1));
 
     let inner = block.inner(dialog_area);
@@ -41,8 +41,8 @@ pub fn render(frame: &mut Frame, area: Rect, app: &App) {
 
     // Subtitle
     let subtitle = Paragraph::new(Line::from(Span::styled(
-        "AI-guided code review walkthrough",
-        Style::default().fg(Color::DarkGray),
+        app.strings.setup_subtitle,
+        Style::default().fg(app.theme.dim),
     )))
     .alignment(Alignment::Center);
     frame.render_widget(subtitle, sections[0]);
@@ -52,18 +52,21 @@ pub fn render(frame: &mut Frame, area: Rect, app: &App) {
 
     // Help bar
     let mut help_spans = vec![
-        Span::styled(" Enter ", Style::default().fg(Color::Yellow)),
-        Span::raw("start "),
+        Span::styled(" Enter ", Style::default().fg(app.theme.key)),
+        Span::raw(format!("{} ", app.strings.setup_start)),
     ];
-    if app.session.api_key_source != ApiKeySource::EnvVar {
+    if !matches!(
+        app.session.api_key_source,
+        ApiKeySource::EnvVar | ApiKeySource::NotRequired
+    ) {
         help_spans.extend([
-            Span::styled(" Tab ", Style::default().fg(Color::Yellow)),
-            Span::raw("switch "),
+            Span::styled(" Tab ", Style::default().fg(app.theme.key)),
+            Span::raw(format!("{} ", app.strings.setup_switch)),
         ]);
     }
     help_spans.extend([
-        Span::styled(" q ", Style::default().fg(Color::Yellow)),
-        Span::raw("quit"),
+        Span::styled(" q ", Style::default().fg(app.theme.key)),
+        Span::raw(app.strings.setup_quit),
     ]);
     let help_line = Paragraph::new(Line::from(help_spans)).alignment(Alignment::Center);
     frame.render_widget(help_line, sections[7]);
@@ -75,18 +78,26 @@ fn render_mode_section(frame: &mut Frame, sections: &[Rect], app: &App) {
         SetupFocus::Review | SetupFocus::Walkthrough
     );
     let mode_label_color = if mode_focused {
-        Color::Cyan
+        app.theme.accent
     } else {
-        Color::DarkGray
+        app.theme.dim
     };
     let mode_label = Paragraph::new(Line::from(Span::styled(
-        "Mode",
+        app.strings.setup_mode,
         Style::default()
             .fg(mode_label_color)
             .add_modifier(Modifier::BOLD),
     )));
     frame.render_widget(mode_label, sections[1]);
 
+    let s = app.strings;
+    // Pad labels to a common width so the descriptions line up in any language
+    let label_width = s
+        .setup_review
+        .chars()
+        .count()
+        .max(s.setup_walkthrough.chars().count());
+
     let review_selected = app.session.review_mode == ReviewMode::Review;
     let (review_bullet, walk_bullet) = if review_selected {
         ("●", "○")
@@ -96,17 +107,17 @@ fn render_mode_section(frame: &mut Frame, sections: &[Rect], app: &App) {
 
     let review_style = if review_selected {
         Style::default()
-            .fg(Color::White)
+            .fg(app.theme.text)
             .add_modifier(Modifier::BOLD)
     } else {
-        Style::default().fg(Color::DarkGray)
+        Style::default().fg(app.theme.dim)
     };
     let walk_style = if !review_selected {
         Style::default()
-            .fg(Color::White)
+            .fg(app.theme.text)
             .add_modifier(Modifier::BOLD)
     } else {
-        Style::default().fg(Color::DarkGray)
+        Style::default().fg(app.theme.dim)
     };
 
     let review_cursor = if app.setup_focus == SetupFocus::Review {
@@ -122,19 +133,25 @@ fn render_mode_section(frame: &mut Frame, sections: &[Rect], app: &App) {
 
     let mode_lines = vec![
         Line::from(vec![
-            Span::styled(review_cursor, Style::default().fg(Color::Cyan)),
-            Span::styled(format!("{review_bullet} Review"), review_style),
+            Span::styled(review_cursor, Style::default().fg(app.theme.accent)),
+            Span::styled(
+                format!("{review_bullet} {:<label_width$}", s.setup_review),
+                review_style,
+            ),
             Span::styled(
-                "       Call out potential issues",
-                Style::default().fg(Color::DarkGray),
+                format!("  {}", s.setup_review_desc),
+                Style::default().fg(app.theme.dim),
             ),
         ]),
         Line::from(vec![
-            Span::styled(walk_cursor, Style::default().fg(Color::Cyan)),
-            Span::styled(format!("{walk_bullet} Walkthrough"), walk_style),
+            Span::styled(walk_cursor, Style::default().fg(app.theme.accent)),
             Span::styled(
-                "  Describe the changes",
-                Style::default().fg(Color::DarkGray),
+                format!("{walk_bullet} {:<label_width$}", s.setup_walkthrough),
+                walk_style,
+            ),
+            Span::styled(
+                format!("  {}", s.setup_walkthrough_desc),
+                Style::default().fg(app.theme.dim),
             ),
         ]),
     ];
@@ -145,12 +162,12 @@ fn render_mode_section(frame: &mut Frame, sections: &[Rect], app: &App) {
 fn render_api_key_section(frame: &mut Frame, sections: &[Rect], app: &App) {
     let key_focused = app.setup_focus == SetupFocus::ApiKey;
     let key_label_color = if key_focused {
-        Color::Cyan
+        app.theme.accent
     } else {
-        Color::DarkGray
+        app.theme.dim
     };
     let key_label = Paragraph::new(Line::from(Span::styled(
-        "API Key",
+        app.strings.setup_api_key,
         Style::default()
             .fg(key_label_color)
             .add_modifier(Modifier::BOLD),
@@ -159,8 +176,12 @@ fn render_api_key_section(frame: &mut Frame, sections: &[Rect], app: &App) {
 
     let key_line = match app.session.api_key_source {
         ApiKeySource::Missing if !key_focused => Line::from(Span::styled(
-            "  No API key found",
-            Style::default().fg(Color::Red),
+            format!("  {}", app.strings.setup_no_key),
+            Style::default().fg(app.theme.error),
+        )),
+        ApiKeySource::NotRequired => Line::from(Span::styled(
+            format!("  ✓ {}", app.strings.key_not_required),
+            Style::default().fg(app.theme.dim),
         )),
         _ if key_focused
             && matches!(
@@ -169,40 +190,41 @@ fn render_api_key_section(frame: &mut Frame, sections: &[Rect], app: &App) {
             ) =>
         {
             let display = if app.session.api_key_input.is_empty() {
-                "sk-ant-...".to_string()
+                app.session.provider.key_placeholder().to_string()
             } else {
                 app.session.api_key_input.clone()
             };
             let style = if app.session.api_key_input.is_empty() {
-                Style::default().fg(Color::DarkGray)
+                Style::default().fg(app.theme.dim)
             } else {
-                Style::default().fg(Color::White)
+                Style::default().fg(app.theme.text)
             };
             Line::from(vec![
-                Span::styled("> ", Style::default().fg(Color::Cyan)),
+                Span::styled("> ", Style::default().fg(app.theme.accent)),
                 Span::styled(display, style),
-                Span::styled("█", Style::default().fg(Color::White)),
+                Span::styled("█", Style::default().fg(app.theme.text)),
             ])
         }
         _ => {
             let masked = mask_api_key(&app.session.api_key_input);
             let source_label = match app.session.api_key_source {
-                ApiKeySource::EnvVar => " ✓ from env",
-                ApiKeySource::Settings => " ✓ saved",
-                ApiKeySource::UserEntry => " ✓ entered",
-                ApiKeySource::Missing => "",
+                ApiKeySource::EnvVar => format!(" ✓ {}", app.strings.key_from_env),
+                ApiKeySource::Settings => format!(" ✓ {}", app.strings.key_saved),
+                ApiKeySource::UserEntry => format!(" ✓ {}", app.strings.key_entered),
+                ApiKeySource::Session => format!(" ✓ {}", app.strings.key_session),
+                ApiKeySource::NotRequired | ApiKeySource::Missing => String::new(),
             };
             Line::from(vec![
                 Span::raw("  "),
-                Span::styled(masked, Style::default().fg(Color::Green)),
-                Span::styled(source_label, Style::default().fg(Color::DarkGray)),
+                Span::styled(masked, Style::default().fg(app.theme.success)),
+                Span::styled(source_label, Style::default().fg(app.theme.dim)),
             ])
         }
     };
     frame.render_widget(Paragraph::new(key_line), sections[5]);
 }
 
-fn mask_api_key(key: &str) -> String {
+pub(super) fn mask_api_key(key: &str) -> String {
     if key.len() <= 12 {
         return "*".repeat(key.len());
     }
@@ -0,0 +1,92 @@
+use ratatui::{
+    Frame,
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Padding, Paragraph},
+};
+
+use crate::app::App;
+use crate::constants::{STEP_FINDER_DIALOG_HEIGHT, STEP_FINDER_DIALOG_WIDTH};
+use crate::search::StepFinder;
+
+use super::centered_rect;
+
+pub fn render(frame: &mut Frame, area: Rect, finder: &StepFinder, app: &App) {
+    let s = app.strings;
+    let dialog_area = centered_rect(STEP_FINDER_DIALOG_WIDTH, STEP_FINDER_DIALOG_HEIGHT, area);
+    frame.render_widget(Clear, dialog_area);
+
+    let block = Block::default()
+        .title(s.step_finder_title)
+        .title_alignment(Alignment::Center)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(app.theme.accent))
+        .padding(Padding::new(2, 2, 1, 1));
+    let inner = block.inner(dialog_area);
+    frame.render_widget(block, dialog_area);
+
+    let sections = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(2), // Query
+            Constraint::Min(1),    // Results
+            Constraint::Length(1), // Help bar
+        ])
+        .split(inner);
+
+    let dim = Style::default().fg(app.theme.dim);
+    let query = Paragraph::new(Line::from(vec![
+        Span::styled("> ", Style::default().fg(app.theme.text)),
+        Span::raw(finder.input.as_str()),
+        Span::styled("_", dim),
+        Span::styled(format!("  {}", finder.results.len()), dim),
+    ]));
+    frame.render_widget(query, sections[0]);
+
+    // One row per step, with the matched file beside the title
+    let steps = &app.session.walkthrough.steps;
+    let mut lines: Vec<Line> = Vec::new();
+    for (i, found) in finder.results.iter().enumerate() {
+        let Some(step) = steps.get(found.step) else {
+            continue;
+        };
+        let title_style = if i == finder.selected {
+            Style::default().add_modifier(Modifier::REVERSED)
+        } else {
+            Style::default()
+        };
+        let mut spans = vec![
+            Span::styled(format!("{:>3}. ", found.step + 1), dim),
+            Span::styled(step.title.clone(), title_style),
+        ];
+        if let Some(hunk) = found.hunk.and_then(|hunk| step.hunks.get(hunk)) {
+            spans.push(Span::styled(format!("  {}", hunk.file_path), dim));
+        }
+        lines.push(Line::from(spans));
+    }
+    if lines.is_empty() {
+        lines.push(Line::from(Span::styled(s.global_search_none, dim)));
+    }
+
+    // Keep the highlighted step in view
+    let height = sections[1].height as usize;
+    let scroll = finder.selected.saturating_sub(height.saturating_sub(1) / 2);
+    let scroll = scroll.min(lines.len().saturating_sub(height));
+    frame.render_widget(
+        Paragraph::new(lines).scroll((scroll as u16, 0)),
+        sections[1],
+    );
+
+    let key_style = Style::default().fg(app.theme.key);
+    let help_line = Paragraph::new(Line::from(vec![
+        Span::styled(" ↑/↓ ", key_style),
+        Span::raw(format!("{} ", s.act_scroll)),
+        Span::styled(" Enter ", key_style),
+        Span::raw(format!("{} ", s.global_search_jump)),
+        Span::styled(" Esc ", key_style),
+        Span::raw(s.act_exit),
+    ]))
+    .alignment(Alignment::Center);
+    frame.render_widget(help_line, sections[2]);
+}
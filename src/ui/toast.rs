@@ -0,0 +1,50 @@
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::Style,
+    widgets::{Block, Borders, Clear, Padding, Paragraph},
+};
+
+use crate::app::App;
+use crate::toast::ToastKind;
+
+/// Border rows above and below the message.
+const TOAST_BORDER_HEIGHT: u16 = 2;
+
+/// Renders active toasts stacked upward from the bottom-right of `area`,
+/// newest at the bottom.
+pub fn render(frame: &mut Frame, area: Rect, app: &App) {
+    let max_width = (area.width / 2).max(20).min(area.width);
+    let mut bottom = area.y + area.height;
+
+    for toast in app.toasts.iter().rev() {
+        let height = toast.message.lines().count().max(1) as u16 + TOAST_BORDER_HEIGHT;
+        if bottom < area.y + height {
+            break;
+        }
+        let longest_line = toast.message.lines().map(|l| l.chars().count()).max();
+        let width = (longest_line.unwrap_or(0) as u16 + 4).min(max_width);
+        let toast_area = Rect {
+            x: area.x + area.width - width,
+            y: bottom - height,
+            width,
+            height,
+        };
+        bottom -= height;
+
+        let color = match toast.kind {
+            ToastKind::Info => app.theme.accent,
+            ToastKind::Error => app.theme.error,
+        };
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(color))
+            .padding(Padding::horizontal(1));
+
+        frame.render_widget(Clear, toast_area);
+        frame.render_widget(
+            Paragraph::new(toast.message.as_str()).block(block),
+            toast_area,
+        );
+    }
+}
@@ -0,0 +1,86 @@
+use ratatui::{
+    Frame,
+    layout::{Alignment, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Padding, Paragraph, Wrap},
+};
+
+use crate::api::TokenUsage;
+use crate::app::App;
+use crate::constants::{USAGE_DIALOG_HEIGHT, USAGE_DIALOG_WIDTH};
+
+use super::{centered_rect, format_cost, format_token_count};
+
+pub fn render(frame: &mut Frame, area: Rect, app: &App) {
+    let s = app.strings;
+    let session = &app.session;
+    let dialog_area = centered_rect(USAGE_DIALOG_WIDTH, USAGE_DIALOG_HEIGHT, area);
+    frame.render_widget(Clear, dialog_area);
+
+    let block = Block::default()
+        .title(s.usage_title)
+        .title_alignment(Alignment::Center)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(app.theme.accent))
+        .padding(Padding::new(2, 2, 1, 1));
+
+    let dim = Style::default().fg(app.theme.dim);
+    let bold = Style::default().add_modifier(Modifier::BOLD);
+    let pricing = session.pricing();
+    let row = |label: &str, usage: TokenUsage, style: Style| -> Line<'static> {
+        let cost = pricing
+            .map(|pricing| format_cost(usage.cost_usd(pricing)))
+            .unwrap_or_default();
+        Line::from(Span::styled(
+            format!(
+                "{:<13}↑{:<9}↓{:<9}{}",
+                label,
+                format_token_count(usage.input_tokens),
+                format_token_count(usage.output_tokens),
+                cost
+            ),
+            style,
+        ))
+    };
+
+    let chat = session.chat_usage;
+    let generation = TokenUsage {
+        input_tokens: session
+            .token_usage
+            .input_tokens
+            .saturating_sub(chat.input_tokens),
+        output_tokens: session
+            .token_usage
+            .output_tokens
+            .saturating_sub(chat.output_tokens),
+    };
+    let model = session
+        .generation_progress
+        .model
+        .as_deref()
+        .or(session.api_settings.model.as_deref())
+        .unwrap_or("—");
+
+    let mut text = vec![
+        Line::from(vec![
+            Span::styled(format!("{:<13}", s.usage_model), dim),
+            Span::raw(model.to_string()),
+        ]),
+        Line::from(""),
+        row(s.usage_generation, generation, Style::default()),
+        row(s.usage_chat, chat, Style::default()),
+        row(s.usage_total, session.token_usage, bold),
+        Line::from(""),
+    ];
+    text.push(match pricing {
+        Some((input, output)) => Line::from(Span::styled(
+            format!("↑${input:.2} ↓${output:.2} {}", s.usage_prices),
+            dim,
+        )),
+        None => Line::from(Span::styled(s.usage_no_prices, dim)),
+    });
+
+    let paragraph = Paragraph::new(text).block(block).wrap(Wrap { trim: false });
+    frame.render_widget(paragraph, dialog_area);
+}
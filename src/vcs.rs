@@ -0,0 +1,390 @@
+//! Reading revision ranges from the version control system in use.
+//!
+//! Git, Mercurial, and Jujutsu are supported. The range syntax is git's
+//! (`base..head`, `base...head`) and is translated into each tool's flags.
+
+use std::io;
+use std::path::Path;
+
+use tokio::process::Command;
+
+use crate::generation::CommitDiff;
+use crate::model::CommitInfo;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Vcs {
+    Git,
+    Mercurial,
+    Jujutsu,
+}
+
+/// A parsed `base..head` or `base...head` range. An empty head means the
+/// checked-out revision, as in git (`HEAD`, Mercurial's `.`, Jujutsu's `@`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Range<'a> {
+    base: &'a str,
+    head: &'a str,
+    /// Three dots: diff from the merge base of `base` and `head`
+    from_merge_base: bool,
+}
+
+impl<'a> Range<'a> {
+    fn parse(input: &'a str) -> Option<Self> {
+        if let Some((base, head)) = input.split_once("...") {
+            return Some(Self {
+                base,
+                head,
+                from_merge_base: true,
+            });
+        }
+        let (base, head) = input.split_once("..")?;
+        Some(Self {
+            base,
+            head,
+            from_merge_base: false,
+        })
+    }
+}
+
+impl Vcs {
+    /// The VCS managing the working directory. Jujutsu is checked first since
+    /// colocated jj repositories also contain a `.git` directory.
+    pub fn detect() -> Self {
+        Self::find().unwrap_or(Self::Git)
+    }
+
+    /// The VCS managing the working directory, if it is inside a repository.
+    pub fn find() -> Option<Self> {
+        Self::detect_in(&std::env::current_dir().ok()?)
+    }
+
+    fn detect_in(dir: &Path) -> Option<Self> {
+        for ancestor in dir.ancestors() {
+            if ancestor.join(".jj").is_dir() {
+                return Some(Self::Jujutsu);
+            }
+            if ancestor.join(".hg").is_dir() {
+                return Some(Self::Mercurial);
+            }
+            if ancestor.join(".git").exists() {
+                return Some(Self::Git);
+            }
+        }
+        None
+    }
+
+    pub fn command(self) -> &'static str {
+        match self {
+            Self::Git => "git",
+            Self::Mercurial => "hg",
+            Self::Jujutsu => "jj",
+        }
+    }
+
+    /// Arguments for a unified diff of `range`.
+    fn diff_args(self, range: &Range) -> Vec<String> {
+        match self {
+            Self::Git => {
+                let dots = if range.from_merge_base { "..." } else { ".." };
+                vec![
+                    "diff".to_string(),
+                    format!("{}{}{}", range.base, dots, range.head),
+                ]
+            }
+            Self::Mercurial => {
+                let base = if range.from_merge_base {
+                    format!("ancestor({}, {})", range.base, or_default(range.head, "."))
+                } else {
+                    range.base.to_string()
+                };
+                vec![
+                    "diff".to_string(),
+                    "-r".to_string(),
+                    base,
+                    "-r".to_string(),
+                    or_default(range.head, ".").to_string(),
+                ]
+            }
+            Self::Jujutsu => {
+                let base = if range.from_merge_base {
+                    format!(
+                        "fork_point({} | {})",
+                        range.base,
+                        or_default(range.head, "@")
+                    )
+                } else {
+                    range.base.to_string()
+                };
+                // jj's default diff format is color-words, not a unified diff
+                let mut args = vec![
+                    "diff".to_string(),
+                    "--git".to_string(),
+                    "--from".to_string(),
+                    base,
+                ];
+                if !range.head.is_empty() {
+                    args.extend(["--to".to_string(), range.head.to_string()]);
+                }
+                args
+            }
+        }
+    }
+
+    /// Arguments for a unified diff from `rev` to the working copy, or of
+    /// the working copy's uncommitted changes when `rev` is `None`.
+    fn working_copy_args(self, rev: Option<&str>) -> Vec<String> {
+        let mut args = vec!["diff".to_string()];
+        match self {
+            Self::Git => args.push(rev.unwrap_or("HEAD").to_string()),
+            Self::Mercurial => {
+                if let Some(rev) = rev {
+                    args.extend(["-r".to_string(), rev.to_string()]);
+                }
+            }
+            Self::Jujutsu => {
+                args.push("--git".to_string());
+                if let Some(rev) = rev {
+                    args.extend(["--from".to_string(), rev.to_string()]);
+                }
+            }
+        }
+        args
+    }
+}
+
+fn or_default<'a>(rev: &'a str, default: &'a str) -> &'a str {
+    if rev.is_empty() { default } else { rev }
+}
+
+/// Whether a command-line argument is a revision range rather than a path.
+pub fn is_range(input: &str) -> bool {
+    (input.contains("..") || input.contains("..."))
+        && !input.starts_with("http")
+        && !Path::new(input).exists()
+}
+
+/// Diffs a revision range with the detected VCS. Commit messages are
+/// included for git ranges.
+pub async fn read_range(range: &str) -> io::Result<(String, Vec<CommitInfo>)> {
+    let vcs = Vcs::detect();
+    let parsed = Range::parse(range)
+        .ok_or_else(|| io::Error::other(format!("not a revision range: {range}")))?;
+    let diff_text = run_diff(vcs, vcs.diff_args(&parsed), range).await?;
+
+    let commits = match vcs {
+        Vcs::Git => read_git_log(range).await.unwrap_or_default(),
+        Vcs::Mercurial | Vcs::Jujutsu => vec![],
+    };
+
+    Ok((diff_text, commits))
+}
+
+/// Diffs a range, or a single revision against the working copy (like
+/// `git diff HEAD~3`).
+pub async fn read_revision(spec: &str) -> io::Result<(String, Vec<CommitInfo>)> {
+    if Range::parse(spec).is_some() {
+        return read_range(spec).await;
+    }
+    let vcs = Vcs::detect();
+    let diff_text = run_diff(vcs, vcs.working_copy_args(Some(spec)), spec).await?;
+
+    let commits = match vcs {
+        Vcs::Git => read_git_log(&format!("{spec}..HEAD"))
+            .await
+            .unwrap_or_default(),
+        Vcs::Mercurial | Vcs::Jujutsu => vec![],
+    };
+
+    Ok((diff_text, commits))
+}
+
+/// The uncommitted changes in the working copy, or `None` when the working
+/// directory isn't in a repository, has no changes, or can't be diffed.
+pub async fn read_working_copy() -> Option<String> {
+    let vcs = Vcs::find()?;
+    run_diff(vcs, vcs.working_copy_args(None), "working copy")
+        .await
+        .ok()
+}
+
+/// The diff each git commit introduced, for `--by-commit`. Commits that
+/// changed nothing (such as empty merges) are left out.
+pub async fn read_commit_diffs(commits: &[CommitInfo]) -> io::Result<Vec<CommitDiff>> {
+    let mut diffs = Vec::new();
+    for commit in commits {
+        let output = Command::new("git")
+            .args([
+                "show",
+                "--format=",
+                "--no-color",
+                "--diff-merges=first-parent",
+            ])
+            .arg(&commit.sha)
+            .output()
+            .await
+            .map_err(|e| io::Error::other(format!("failed to run git show: {e}")))?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(io::Error::other(format!(
+                "git show {} failed: {stderr}",
+                commit.sha
+            )));
+        }
+        let diff_text = String::from_utf8_lossy(&output.stdout).into_owned();
+        if !diff_text.trim().is_empty() {
+            diffs.push(CommitDiff {
+                commit: commit.clone(),
+                diff_text,
+            });
+        }
+    }
+    Ok(diffs)
+}
+
+/// A diff read from the local repository, which `--watch` reads again to
+/// notice changes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LocalDiff {
+    /// Uncommitted changes (`docent` with no diff source)
+    WorkingCopy,
+    /// A revision range, or a `--git` revision
+    Revision(String),
+}
+
+impl LocalDiff {
+    /// The diff as it is now, or `None` when it can't be read or is empty.
+    pub async fn read(&self) -> Option<(String, Vec<CommitInfo>)> {
+        match self {
+            Self::WorkingCopy => read_working_copy().await.map(|diff| (diff, vec![])),
+            Self::Revision(spec) => read_revision(spec).await.ok(),
+        }
+    }
+}
+
+async fn run_diff(vcs: Vcs, args: Vec<String>, label: &str) -> io::Result<String> {
+    let command = vcs.command();
+    let diff_output = Command::new(command)
+        .args(args)
+        .output()
+        .await
+        .map_err(|e| io::Error::other(format!("failed to run {command} diff: {e}")))?;
+
+    if !diff_output.status.success() {
+        let stderr = String::from_utf8_lossy(&diff_output.stderr);
+        return Err(io::Error::other(format!(
+            "{command} diff {label} failed: {stderr}"
+        )));
+    }
+
+    let diff_text = String::from_utf8_lossy(&diff_output.stdout).into_owned();
+    if diff_text.trim().is_empty() {
+        return Err(io::Error::other(format!(
+            "{command} diff {label} produced no output"
+        )));
+    }
+    Ok(diff_text)
+}
+
+const GIT_LOG_SEPARATOR: &str = "---commit-boundary---";
+
+async fn read_git_log(range: &str) -> io::Result<Vec<CommitInfo>> {
+    let format = format!("{GIT_LOG_SEPARATOR}%n%H%n%s");
+    let output = Command::new("git")
+        .args([
+            "log",
+            "--reverse",
+            &format!("--format={format}"),
+            "--name-only",
+            range,
+        ])
+        .output()
+        .await?;
+
+    if !output.status.success() {
+        return Err(io::Error::other("git log failed"));
+    }
+
+    let text = String::from_utf8_lossy(&output.stdout);
+    let mut commits = Vec::new();
+
+    for block in text.split(GIT_LOG_SEPARATOR).skip(1) {
+        let mut lines = block.lines().filter(|l| !l.is_empty());
+        let Some(sha) = lines.next() else { continue };
+        let Some(message) = lines.next() else {
+            continue;
+        };
+        let files: Vec<String> = lines.map(String::from).collect();
+        commits.push(CommitInfo {
+            sha: sha.to_string(),
+            message: message.to_string(),
+            files,
+        });
+    }
+
+    Ok(commits)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(vcs: Vcs, range: &str) -> Vec<String> {
+        vcs.diff_args(&Range::parse(range).unwrap())
+    }
+
+    #[test]
+    fn test_range_translation() {
+        assert_eq!(args(Vcs::Git, "main...feature"), ["diff", "main...feature"]);
+        assert_eq!(args(Vcs::Mercurial, "1..5"), ["diff", "-r", "1", "-r", "5"]);
+        assert_eq!(
+            args(Vcs::Mercurial, "default.."),
+            ["diff", "-r", "default", "-r", "."]
+        );
+        assert_eq!(
+            args(Vcs::Jujutsu, "main.."),
+            ["diff", "--git", "--from", "main"]
+        );
+        assert_eq!(
+            args(Vcs::Jujutsu, "main..@"),
+            ["diff", "--git", "--from", "main", "--to", "@"]
+        );
+        assert_eq!(
+            args(Vcs::Jujutsu, "main...feature"),
+            [
+                "diff",
+                "--git",
+                "--from",
+                "fork_point(main | feature)",
+                "--to",
+                "feature"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_working_copy_translation() {
+        assert_eq!(Vcs::Git.working_copy_args(None), ["diff", "HEAD"]);
+        assert_eq!(
+            Vcs::Git.working_copy_args(Some("HEAD~3")),
+            ["diff", "HEAD~3"]
+        );
+        assert_eq!(
+            Vcs::Mercurial.working_copy_args(Some("5")),
+            ["diff", "-r", "5"]
+        );
+        assert_eq!(Vcs::Jujutsu.working_copy_args(None), ["diff", "--git"]);
+    }
+
+    #[test]
+    fn test_detect_prefers_jj_over_colocated_git() {
+        let dir = std::env::temp_dir().join(format!("docent-vcs-{}", std::process::id()));
+        let nested = dir.join("src");
+        std::fs::create_dir_all(&nested).unwrap();
+        std::fs::create_dir_all(dir.join(".git")).unwrap();
+        assert_eq!(Vcs::detect_in(&nested), Some(Vcs::Git));
+
+        std::fs::create_dir_all(dir.join(".jj")).unwrap();
+        assert_eq!(Vcs::detect_in(&nested), Some(Vcs::Jujutsu));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}